@@ -0,0 +1,20 @@
+using SWEeM.Application.Dtos.Change;
+using SWEeM.Infrastructure.Persistence;
+
+public static class ChangeEndpoints
+{
+    public static RouteGroupBuilder MapChangeEndpoints(this WebApplication app)
+    {
+        var group = app.MapGroup("/changes")
+            .WithTags("Changes")
+            .WithDescription("Lightweight change detection for polling clients");
+
+        group.MapGet("/", (ChangeFeed feed) => Results.Ok(new ChangesDto(feed.Version, feed.LastModified)))
+        .WithName("GetChanges")
+        .WithSummary("Get the current data version")
+        .WithDescription("Returns a version that changes whenever clients, projects or users are modified; poll it and reload only when it differs")
+        .Produces<ChangesDto>(200, "application/json");
+
+        return group;
+    }
+}
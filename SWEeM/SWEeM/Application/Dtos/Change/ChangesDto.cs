@@ -0,0 +1,3 @@
+namespace SWEeM.Application.Dtos.Change;
+
+public record ChangesDto(long Version, DateTime LastModified);
@@ -0,0 +1,44 @@
+using Microsoft.EntityFrameworkCore;
+using Microsoft.EntityFrameworkCore.Diagnostics;
+
+namespace SWEeM.Infrastructure.Persistence;
+
+/// <summary>
+/// Process-wide data version, bumped whenever a save writes changes.
+/// Starts at the startup time in ticks, so a restarted server never reports a version a client has already seen.
+/// </summary>
+public class ChangeFeed
+{
+    private long _version = DateTime.UtcNow.Ticks;
+
+    public long Version => Interlocked.Read(ref _version);
+
+    public DateTime LastModified { get; private set; } = DateTime.UtcNow;
+
+    public void Bump()
+    {
+        Interlocked.Increment(ref _version);
+        LastModified = DateTime.UtcNow;
+    }
+}
+
+/// <summary>
+/// Bumps the <see cref="ChangeFeed"/> after every save that affected rows.
+/// </summary>
+public class ChangeFeedInterceptor(ChangeFeed feed) : SaveChangesInterceptor
+{
+    public override int SavedChanges(SaveChangesCompletedEventData eventData, int result)
+    {
+        if (result > 0) feed.Bump();
+        return result;
+    }
+
+    public override ValueTask<int> SavedChangesAsync(
+        SaveChangesCompletedEventData eventData,
+        int result,
+        CancellationToken cancellationToken = default)
+    {
+        if (result > 0) feed.Bump();
+        return ValueTask.FromResult(result);
+    }
+}
@@ -17,8 +17,12 @@ builder.Services.AddSwaggerGen(options =>
     });
 });
 
-builder.Services.AddDbContext<AppDbContext>(options =>
-    options.UseSqlite("Data Source=swee.db"));
+builder.Services.AddSingleton<ChangeFeed>();
+builder.Services.AddSingleton<ChangeFeedInterceptor>();
+
+builder.Services.AddDbContext<AppDbContext>((services, options) =>
+    options.UseSqlite("Data Source=swee.db")
+        .AddInterceptors(services.GetRequiredService<ChangeFeedInterceptor>()));
 
 builder.Services.AddScoped<ClientService>();
 builder.Services.AddScoped<ProjectService>();
@@ -41,5 +45,6 @@ app.UseHttpsRedirection();
 app.MapClientEndpoints();
 app.MapProjectEndpoints();
 app.MapUserEndpoints();
+app.MapChangeEndpoints();
 
 app.Run();
\ No newline at end of file
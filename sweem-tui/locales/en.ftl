@@ -0,0 +1,520 @@
+# English UI strings (the reference catalog; every other locale mirrors its keys)
+
+## Tabs, entities and roles
+app-title = SWEeM Management Console
+app-title-as-of = SWEeM Management Console — as of { $date }
+production-banner = PRODUCTION · { $profile }
+tab-clients = Clients
+tab-timeline = Timeline
+tab-projects = Projects
+tab-users = Users
+tab-stats = Stats
+tab-inbox = Inbox
+tab-audit = Audit
+tab-console = Console
+tab-plugin = Plugin
+tab-badge-overdue = { $count } / { $overdue } overdue
+entity-client = Client
+entity-project = Project
+entity-user = User
+role-admin = Admin
+role-manager = Manager
+compare-client = Client
+compare-manager = Manager
+compare-none = (no { $by })
+
+## Lists and panels
+clients-title = Clients
+users-title = Users
+projects-title = Projects
+clients-empty = No clients found
+users-empty = No users found
+teams-none = No team
+teams-counts = { $members } user(s), { $projects } project(s)
+projects-empty = No projects found
+loading = Loading...
+log-title = System Log
+debug-title = Debug
+debug-fps = FPS
+debug-dropped = Dropped
+debug-particles = Particles
+telemetry-readout = { $fps } fps · { $render } ms · { $entities } loaded · RSS { $rss }
+telemetry-rss-unknown = n/a
+
+## Timeline
+timeline-title = Project Timeline
+timeline-title-as-of = Project Timeline (as of { $date })
+timeline-sandbox-tag = [SANDBOX]
+timeline-mine-tag = [MINE]
+list-filter-tag = [/ { $filter }]
+list-marked-tag = [{ $count } marked]
+list-archived-tag = [{ $count } archived hidden]
+list-paged-tag = [{ $loaded } of { $total }]
+list-loading-page = loading next page…
+my-deadlines-title = Deadlines: { $login }
+my-load-title = Load, { $weeks } weeks (max { $peak })
+deadline-in = in { $days }d
+deadline-today = today
+deadline-late = { $days }d late
+due-in = ends in { $days }d
+due-today = ends today
+due-overdue = overdue by { $days }d
+column-due = Due
+column-source = Source
+timeline-capacity = Capacity
+timeline-capacity-max = max { $peak }
+timeline-legend-active = Active
+timeline-legend-done = Done
+timeline-legend-overdue = Overdue
+timeline-legend-today = Today
+timeline-legend-sla = SLA
+timeline-projects = { $count } projects
+timeline-no-selection = none
+sandbox-badge = SANDBOX
+sandbox-changed = { $count } changed
+sandbox-overdue = overdue { $before } →
+sandbox-hint = </> shift  {"{"}/{"}"} resize  S commit  X discard
+
+## Forms
+form-new-client = New Client
+form-edit-client = Edit Client
+form-new-project = New Project
+form-edit-project = Edit Project
+form-new-user = New User
+form-edit-user = Edit User
+field-name = Name
+field-address = Address
+field-client = Client
+field-manager = Manager
+field-start-date = Start Date
+field-end-date = End Date
+field-login = Login
+field-password = Password
+field-role = Role
+select-client = (Select client)
+select-manager = (Select manager)
+picker-no-matches = No matches
+picker-count = { $shown } of { $total }
+button-save = Save
+button-cancel = Cancel
+button-yes = Yes
+button-no = No
+calendar-title = Calendar
+calendar-months = January February March April May June July August September October November December
+calendar-weekdays = Mo Tu We Th Fr Sa Su
+calendar-hint = hl day jk wk HL month
+calendar-as-of = View as of
+calendar-as-of-hint = ⏎ pick t today ⌫ live
+
+## Dialogs and prompts
+confirm-delete-title = Delete { $entity }
+confirm-delete-message = Are you sure you want to delete "{ $name }"?
+    This action cannot be undone.
+confirm-commit-title = Commit Sandbox
+confirm-commit-message = Send { $count } schedule change(s) to the API?
+    Choose No to keep simulating.
+confirm-reschedule-title = Reschedule Projects
+confirm-reschedule-message = Shift { $count } project(s) by { $days } day(s)?
+confirm-quit-title = Quit
+confirm-quit-message = Quit anyway? This is still pending:
+confirm-undo-title = Undo
+confirm-undo-message = Undo { $label }?
+confirm-redo-title = Redo
+confirm-redo-message = Redo { $label }?
+confirm-production = This is the production environment "{ $profile }".
+confirm-production-input = Type the profile name and press Enter:
+quit-pending-change = Awaiting the server's answer: { $what }
+quit-pending-download = Download of { $name } ({ $received } so far)
+quit-pending-sandbox = { $count } uncommitted sandbox change(s)
+shutdown-title = Quitting
+shutdown-sending = Sending change { $done } of { $total } to the server…
+shutdown-grace = Quits without the rest after { $secs } s
+confirm-preview-more = ... and { $count } more
+prompt-hint = Enter: confirm  Esc: cancel
+prompt-reschedule = Shift { $count } project(s) by days (e.g. 14, -7)
+prompt-filter = Filter { $entity } (e.g. overdue && client:Acme, empty = none)
+prompt-audit-search = Search the audit log (actor, action or entity, empty = all)
+prompt-bookmark = Bookmark { $slot }
+error-dismiss-hint = Press ESC or ENTER to dismiss
+error-auth-title = Authentication Required
+error-auth-message = { $error }
+    Check the API credentials and try again.
+error-rejected-title = Request Rejected
+error-decode-title = Unexpected Response
+error-batch-title = Batch Update
+error-batch-message = { $succeeded } succeeded, { $failed } failed (see log)
+
+## Help overlay
+help-title = Help
+help-heading = Keyboard Shortcuts
+help-navigation = Navigation
+help-switch-tabs = Switch tabs / form fields
+help-move = Move up/down in lists
+help-scroll = Scroll timeline
+help-snap = Snap to week/month
+help-projects-table = Timeline ⇄ Projects table
+help-inline-edit = Projects table: edit a name or planned end in place
+help-compare = Compare timelines (client/manager)
+help-compare-cycle = Compare: cycle top/bottom pane
+help-as-of = View timeline as of a past date
+help-mermaid = Export Mermaid gantt chart
+help-sandbox = Sandbox: enter / commit changes
+help-sandbox-edit = Sandbox: shift, resize, discard
+help-bookmark-save = Save timeline bookmark
+help-bookmark-jump = Jump to timeline bookmark
+help-crud = CRUD Operations
+help-create = Create new item
+help-edit = Edit selected item
+help-clone = Clone selected project (pre-filled create form)
+help-mark = Mark project for batch actions (Esc clears)
+help-reschedule = Shift marked projects by N days
+help-archived = Hide/show archived (long completed) projects
+help-delete = Delete selected item
+help-undo = Undo / redo last change
+help-burndown = Burndown chart of selected client/manager
+help-copy-id = Copy selected UUID / deep-link command
+help-favorite = Star/unstar selected project or client
+help-watch = Watch/unwatch selected project or client (toast and Inbox item on changes)
+help-jumps = Back/forward through visited places
+help-changes = What changed in the latest refresh
+help-sort = Sort dialog (Clients/Users)
+help-columns = Show, hide and reorder columns (Projects/Clients/Users)
+help-teams = Group users by team (Users): Space folds, Enter filters the timeline
+help-menu = Actions of the selected row (also right-click)
+help-filter = Filter expression (Timeline/Clients/Users)
+help-markdown = Export the view as a Markdown table (Ctrl+y: copy)
+help-inspector = Raw JSON of the selected item
+help-attachments = Documents of the selected project (download)
+help-console = API console (GET any path)
+help-search = Search projects, clients and users
+help-form = Form Editing
+help-next-field = Move to next field
+help-dropdown = Change dropdown option / date
+help-date-picker = Date: day / week / month / today
+help-type = Edit text, search clients and managers
+help-enter = Next field / Submit on button
+help-esc = Cancel / Close form
+help-general = General
+help-refresh = Refresh data
+help-particles = Toggle particles
+help-my-projects = Toggle "My projects" view
+help-debug = Toggle debug overlay (FPS)
+help-telemetry = Toggle telemetry in the status bar (FPS, draw time, memory)
+help-settings = Settings (language, background, frame rate, watch, ...)
+help-diagnose = Diagnose the connection (DNS, TCP, TLS, HTTP)
+help-quit = Quit
+
+## Status bar
+status-connected = Connected
+status-disconnected = Disconnected
+status-backends = Backends: { $backends }
+status-loading = [Loading...]
+status-seconds-ago = ({ $secs }s ago)
+status-minutes-ago = ({ $mins }m ago)
+stale-age = STALE: last refresh { $mins } min ago
+stale-disconnected = STALE: disconnected, data { $mins } min old
+stale-recorded = RECORDED: data as of { $time }
+status-reconnect-in = reconnecting in { $secs }s (attempt { $attempt })
+status-reconnecting = reconnecting… (attempt { $attempt })
+status-keys = ?: Help | c: Create | e: Edit | d: Delete | q: Quit
+
+## Log messages
+log-initialized = SWEeM TUI initialized
+log-connecting = Connecting to API...
+log-connected = Connected to API
+log-disconnected = Disconnected from API
+log-diagnose-hint = Press Ctrl+d to check DNS, TCP, TLS and HTTP one by one
+log-production-mismatch = The typed name does not match the profile
+log-diagnose-unavailable = There is no API connection to diagnose
+log-backend-down = Backend { $name } is not answering; its data is left out
+log-backend-up = Backend { $name } is back
+log-refreshing = Refreshing data...
+log-remote-changed = Server data changed, reloading
+log-watch-unsupported = Server has no /changes endpoint; watch mode disabled
+log-webhook-failed = Posting to the webhook failed: { $error }
+log-watch-slowed = API budget low ({ $remaining } requests left): checking for changes every { $secs } s
+log-watch-restored = API budget recovered: checking for changes every { $secs } s again
+log-recording = Recording session to { $path }
+log-recording-failed = Session recording stopped: { $error }
+log-replay-started = Replaying { $events } recorded events ({ $seconds }s); terminal input resumes afterwards, Ctrl+C quits
+log-replay-size = Recorded in a { $recorded } terminal, this one is { $current }; the layout may differ
+log-remote-unavailable = Remote control disabled: { $error }
+log-remote-no-project = Remote: no project with id { $id }
+log-focus-not-found = { $entity } { $id } given with --focus is not loaded
+log-search-hidden = { $name } is hidden by the current filters
+log-filter-set = { $entity } filter: { $filter }
+log-filter-cleared = { $entity } filter cleared
+log-filter-invalid = Invalid filter: { $error }
+mermaid-title = Projects
+log-mermaid-exported = Exported { $count } projects as a Mermaid gantt chart to { $path }
+log-mermaid-failed = Mermaid export failed: { $error }
+log-markdown-exported = Exported { $count } rows as a Markdown table to { $path }
+log-markdown-copied = Copied { $count } rows as a Markdown table
+log-markdown-failed = Markdown export failed: { $error }
+log-markdown-no-table = This view has no table to export
+log-inspector-nothing = Select a project, client or user to inspect
+log-inspector-copied = Copied the JSON
+log-attachments-nothing = Select a project on the timeline to see its documents
+log-documents-failed = Could not load documents: { $error }
+log-download-busy = Wait for "{ $name }" to finish downloading
+log-download-saved = Downloaded { $path }
+log-download-failed = Download failed: { $error }
+log-audit-failed = Could not load the audit log: { $error }
+log-console-invalid = Invalid console request: { $error }
+log-copied = Copied to clipboard: { $text }
+log-copy-nothing = Nothing selected to copy
+log-menu-nothing = Nothing selected for the context menu
+log-clone-nothing = Select a project to clone
+log-clone-form = Cloning { $name }, dates moved by { $days } days
+log-complete-not-started = { $name } has not started yet (starts { $date })
+log-complete-invalid = Cannot complete the project: { $error }
+log-favorite-added = Starred { $name }
+log-favorite-removed = Unstarred { $name }
+log-favorite-persist-failed = Could not save favorites: { $error }
+log-watch-added = Watching { $name }
+log-watch-removed = Stopped watching { $name }
+log-watch-persist-failed = Could not save the watch list: { $error }
+log-teams-unavailable = No team or department fields on the loaded users
+log-sort-persist-failed = Could not save the sort order: { $error }
+log-columns-persist-failed = Could not save the column layout: { $error }
+log-settings-saved = Settings saved to { $path }
+log-settings-persist-failed = Could not save the settings: { $error }
+log-changes = { $entity }: { $count } changed since the previous load (w: what changed)
+log-loaded-projects = Loaded { $count } projects
+log-loaded-clients = Loaded { $count } clients
+log-loaded-users = Loaded { $count } users
+log-loaded-page = Loaded a { $entity } page: { $loaded } of { $total }
+log-script-filter-projects = Script filter hid { $count } projects
+log-script-filter-clients = Script filter hid { $count } clients
+log-script-filter-users = Script filter hid { $count } users
+log-created = { $entity } created ({ $id })
+log-updated = { $entity } updated
+log-deleted = { $entity } deleted ({ $id })
+log-creating-client = Creating client...
+log-updating-client = Updating client...
+log-creating-project = Creating project...
+log-updating-project = Updating project...
+log-inline-invalid = Cannot save the cell: { $error }
+log-creating-user = Creating user...
+log-updating-user = Updating user...
+log-deleting = Deleting { $entity }...
+log-rescheduled = { $count } project(s) rescheduled
+log-reschedule-failed = Rescheduling { $name } failed: { $error }
+log-committing = Committing { $count } schedule change(s)...
+log-rescheduling = Shifting { $count } project(s) by { $days } day(s)...
+log-reschedule-nothing = No marked projects (Space marks the selected one)
+log-reschedule-sandbox = Close the sandbox before rescheduling (S commits, X discards)
+log-reschedule-invalid = Not a number of days: { $input }
+log-sandbox-on = Sandbox on: </> shift, {"{"}/{"}"} resize, S commit, X discard
+log-sandbox-closed = Sandbox closed (no changes)
+log-sandbox-discarded = Sandbox changes discarded
+log-sandbox-blocked = Close the sandbox before changing projects (S commits, X discards)
+log-project-completed = Project completed: { $name }
+log-plugin-registered = Plugin tab registered: { $title }
+log-particle-mode = Particle mode: { $mode }
+log-as-of-today = Viewing timeline as of today
+log-as-of = Viewing timeline as of { $date }
+log-history-shown = Showing { $count } projects recorded at { $time }
+log-history-failed = Snapshot history unavailable: { $error }
+log-inbox-new = { $count } new alerts in the Inbox
+log-alert-rule = Alert rule { $rule }: { $name } { $detail }
+log-inbox-persist-failed = Could not save the Inbox: { $error }
+log-ranking-exported = Exported the ranking of { $count } clients to { $path }
+log-ranking-failed = Client ranking export failed: { $error }
+log-my-projects-on = Showing projects managed by { $login }
+log-my-projects-off = Showing all projects
+log-archived-hidden = Hiding projects completed more than { $days } days ago (H shows them)
+log-archived-shown = Showing archived projects
+log-archive-persist-failed = Could not save the archive setting: { $error }
+log-telemetry-persist-failed = Could not save the telemetry setting: { $error }
+log-my-projects-no-user = "My projects" needs a user: pass --user LOGIN or set user in the profile
+log-my-projects-unknown = No user with login "{ $login }"
+log-bookmark-saved = Bookmark { $slot } saved: { $name }
+log-bookmark-persist-failed = Could not persist bookmarks: { $error }
+log-bookmark-pick-slot = Save bookmark: press 1-9 to choose a slot
+log-bookmark-jump = Bookmark { $slot }: { $name }
+log-bookmark-empty = No bookmark in slot { $slot }
+log-comparing = Comparing timelines by { $by }
+log-comparison-off = Comparison mode off
+
+## Undo history
+undo-busy = Wait for the previous change to finish
+undo-undoing = Undoing { $label }...
+undo-redoing = Redoing { $label }...
+undo-undid = Undid { $label }
+undo-redid = Redid { $label }
+undo-nothing = Nothing to undo (deletes cannot be undone: the API has no restore)
+redo-nothing = Nothing to redo
+undo-label-client-update = client update
+undo-label-project-update = project update
+undo-label-user-update = user update
+undo-label-reschedule = reschedule of { $count } project(s)
+undo-label-client-creation = client creation
+undo-label-project-creation = project creation
+undo-label-user-creation = user creation
+
+## Deadline alerts
+alert-overdue-title = Project overdue
+alert-overdue = Project is now overdue: { $name }
+alert-due-soon-title = Deadline approaching
+alert-due-soon = Project is due within 24 hours: { $name }
+alert-rule-title = Alert rule
+alert-rule-added = added
+alert-rule-overdue = overdue (planned end { $date })
+alert-rule-moved = planned end { $from } → { $to } ({ $days } days)
+alert-rule-completed = completed on { $date }
+webhook-sla-breach = SLA { $rule } breached: { $name }
+inbox-title = Inbox ({ $unread } unread) - Enter open, Space read/unread, a all read, x dismiss
+inbox-empty = No alerts yet
+inbox-kind-overdue = Overdue
+inbox-kind-due-soon = Due soon
+inbox-kind-reassigned = Assigned
+inbox-kind-changed = Changed
+inbox-kind-rule = Rule
+inbox-kind-watched = Watched
+inbox-overdue = { $name } is overdue (planned end { $date })
+inbox-due-soon = { $name } is due on { $date }
+inbox-reassigned = { $name } is now managed by you
+inbox-changed = Projects changed since the last run: { $added } added, { $updated } updated, { $removed } removed
+inbox-rule = { $rule }: { $name } { $detail }
+inbox-watched = { $name }: { $detail }
+watch-added = added
+watch-removed = removed
+toast-watched-title = Watched item changed
+stats-monthly-title = Projects per month, { $from } - { $to }
+stats-started = started
+stats-completed = completed
+stats-keys = h/l month, H/L range, +/- range length, t current, v client ranking
+stats-overrun-title = Average overrun per quarter
+stats-days-suffix = d
+stats-on-time-title = On-time delivery
+stats-on-time = { $on_time } of { $completed } completed on time
+stats-started-total = { $count } started in this range
+stats-overdue-now = { $count } overdue now
+sla-title = SLA breaches
+sla-none = No SLA rules: add [[sla]] entries to config.toml
+ranking-title = Client ranking, { $from } - { $to }
+ranking-keys = s sort column, S reverse, x export CSV, v history, h/l month
+history-title = Projects per day, { $from } - { $to }
+history-keys = h/l month, H/L range, +/- range length, t current, v charts
+history-active = active
+history-overdue = overdue
+history-on-track-title = Active projects on track
+history-empty = Nothing recorded in this range yet: the history grows with every refresh
+history-no-store = The snapshot history is off ([history] in the config)
+ranking-client = Client
+ranking-completed = Completed
+ranking-on-time = On time
+ranking-on-time-percent = On time %
+ranking-overrun = Avg overrun
+burndown-title = Burndown: { $name }
+burndown-close = Esc close
+diff-title = What changed?
+diff-close = j/k scroll · Esc close
+diff-empty = No changes since the previous load
+diff-field-name = Name
+diff-field-start = Start
+diff-field-planned-end = Planned end
+diff-field-actual-end = Actual end
+diff-field-client = Client
+diff-field-manager = Manager
+diff-field-address = Address
+diff-field-login = Login
+diff-field-role = Role
+markdown-status = Status
+markdown-projects = Projects
+markdown-script = Script
+inspector-title = JSON: { $entity } { $id }
+inspector-close = j/k scroll · y copy · Esc close
+inspector-gone = The entity is no longer loaded
+inspector-unknown = unknown field
+attachments-title = Documents: { $name }
+attachments-hint = j/k select · Enter download · Esc close
+attachments-loading = Loading documents…
+attachments-unsupported = This server does not provide project documents
+attachments-failed = Could not load documents: { $error }
+attachments-empty = No documents attached
+attachments-progress = Downloading { $name }: { $received } of { $total }
+attachments-progress-unknown = Downloading { $name }: { $received }
+audit-title = Audit log - page { $page } of { $pages }, { $total } events - Enter open, [ ] page, / search, r reload
+audit-search = Search: { $search }
+audit-loading = Loading the audit log…
+audit-unsupported = This server does not provide an audit log
+audit-failed = Could not load the audit log: { $error }
+audit-empty = No matching events
+audit-system = system
+audit-column-time = Time (UTC)
+audit-column-actor = Actor
+audit-column-action = Action
+audit-column-entity = Entity
+audit-column-summary = Change
+console-title = API console
+console-hint = Enter send · Up/Down history · PgUp/PgDn scroll · Esc leave
+console-empty = Type a path such as /projects?page=1&pageSize=5 and press Enter
+console-running = Sending…
+console-status = { $request } · { $ms } ms
+console-failed = { $request } failed after { $ms } ms
+search-title = Search
+search-hint = type to search · Up/Down select · Enter go · Esc close
+search-count = { $shown } matches in { $total } loaded entities
+search-kind-project = project
+search-kind-client = client
+search-kind-user = user
+sort-title = Sort by
+sort-hint = Space cycle ↑/↓/off · c clear · Esc close
+sort-column-name = Name
+sort-column-address = Address
+sort-column-completion = Completion rate
+sort-column-total = Total projects
+sort-column-completed = Completed
+sort-column-login = Login
+sort-column-role = Role
+columns-title = Columns
+columns-hint = Space show/hide · J/K move · r reset · Esc close
+diagnose-title = Connection diagnosis
+diagnose-keys = r run again · Esc close
+diagnose-running = Checking DNS, TCP, TLS and HTTP...
+diagnose-skipped = skipped
+diagnose-proxy = through the proxy { $proxy }
+diagnose-hint-dns = The host name does not resolve: check the URL, the VPN connection and the DNS settings
+diagnose-hint-tcp = The server cannot be reached: the backend may be down, or a firewall, VPN or proxy-only network is in the way
+diagnose-hint-tls = The TLS handshake failed: an intercepting proxy or a certificate the system does not trust is likely
+diagnose-hint-http = The server answered, but not with projects: check the URL path, the proxy and the server log
+diagnose-ok = All stages passed: the connection works
+settings-title = Settings
+settings-hint = j/k select · h/l change · Esc save and close
+settings-language = Language
+settings-particles = Background
+settings-density = Particle density
+settings-fps = Frame rate
+settings-watch = Watch for changes
+settings-stale = Stale data after
+settings-start-tab = Start tab
+settings-zoom = Timeline zoom
+settings-notifications = Desktop notifications
+settings-auto = auto (LANG)
+settings-on = on
+settings-off = off
+settings-never = never
+settings-fps-value = { $fps } fps
+settings-seconds = every { $secs } s
+settings-minutes = { $minutes } min
+settings-zoom-value = { $days } days/column
+menu-details = Details (JSON)
+menu-copy-id = Copy ID
+menu-edit = Edit
+menu-clone = Clone
+menu-complete = Mark complete today
+menu-filter-client = Show this client's projects
+menu-filter-manager = Show this manager's projects
+menu-documents = Documents
+menu-burndown = Burndown chart
+menu-favorite = Star / unstar
+menu-watch = Watch / unwatch
+menu-delete = Delete
+list-sorted-by = sorted by { $spec }
+burndown-empty = No projects in this scope
+burndown-planned = Planned
+burndown-remaining = Remaining
+burndown-completed = Completed
+burndown-axis = project-days
@@ -0,0 +1,520 @@
+# Russian UI strings (keys mirror en.ftl)
+
+## Tabs, entities and roles
+app-title = Консоль управления SWEeM
+app-title-as-of = Консоль управления SWEeM — на { $date }
+production-banner = РАБОЧЕЕ ОКРУЖЕНИЕ · { $profile }
+tab-clients = Клиенты
+tab-timeline = Хронология
+tab-projects = Проекты
+tab-users = Пользователи
+tab-stats = Статистика
+tab-inbox = Входящие
+tab-audit = Аудит
+tab-console = Консоль
+tab-plugin = Плагин
+tab-badge-overdue = { $count } / { $overdue } просрочено
+entity-client = Клиент
+entity-project = Проект
+entity-user = Пользователь
+role-admin = Админ
+role-manager = Менеджер
+compare-client = Клиент
+compare-manager = Менеджер
+compare-none = (не указан: { $by })
+
+## Lists and panels
+clients-title = Клиенты
+users-title = Пользователи
+projects-title = Проекты
+clients-empty = Клиенты не найдены
+users-empty = Пользователи не найдены
+teams-none = Без команды
+teams-counts = пользователей: { $members }, проектов: { $projects }
+projects-empty = Проекты не найдены
+loading = Загрузка...
+log-title = Системный журнал
+debug-title = Отладка
+debug-fps = FPS
+debug-dropped = Пропуски
+debug-particles = Частицы
+telemetry-readout = { $fps } fps · { $render } мс · загружено { $entities } · RSS { $rss }
+telemetry-rss-unknown = н/д
+
+## Timeline
+timeline-title = Хронология проектов
+timeline-title-as-of = Хронология проектов (на { $date })
+timeline-sandbox-tag = [ПЕСОЧНИЦА]
+timeline-mine-tag = [МОИ]
+list-filter-tag = [/ { $filter }]
+list-marked-tag = [отмечено: { $count }]
+list-archived-tag = [скрыто в архиве: { $count }]
+list-paged-tag = [{ $loaded } из { $total }]
+list-loading-page = загрузка следующей страницы…
+my-deadlines-title = Сроки: { $login }
+my-load-title = Загрузка, { $weeks } нед. (макс. { $peak })
+deadline-in = через { $days } дн.
+deadline-today = сегодня
+deadline-late = просрочено { $days } дн.
+due-in = ещё { $days } дн.
+due-today = срок сегодня
+due-overdue = опоздание { $days } дн.
+column-due = Срок
+column-source = Источник
+timeline-capacity = Загрузка
+timeline-capacity-max = макс. { $peak }
+timeline-legend-active = Активен
+timeline-legend-done = Завершён
+timeline-legend-overdue = Просрочен
+timeline-legend-today = Сегодня
+timeline-legend-sla = SLA
+timeline-projects = Проектов: { $count }
+timeline-no-selection = нет
+sandbox-badge = ПЕСОЧНИЦА
+sandbox-changed = изменено: { $count }
+sandbox-overdue = просрочено { $before } →
+sandbox-hint = </> сдвиг  {"{"}/{"}"} длительность  S отправить  X отменить
+
+## Forms
+form-new-client = Новый клиент
+form-edit-client = Изменить клиента
+form-new-project = Новый проект
+form-edit-project = Изменить проект
+form-new-user = Новый пользователь
+form-edit-user = Изменить пользователя
+field-name = Название
+field-address = Адрес
+field-client = Клиент
+field-manager = Менеджер
+field-start-date = Начало
+field-end-date = Окончание
+field-login = Логин
+field-password = Пароль
+field-role = Роль
+select-client = (Выберите клиента)
+select-manager = (Выберите менеджера)
+picker-no-matches = Ничего не найдено
+picker-count = { $shown } из { $total }
+button-save = Сохранить
+button-cancel = Отмена
+button-yes = Да
+button-no = Нет
+calendar-title = Календарь
+calendar-months = Январь Февраль Март Апрель Май Июнь Июль Август Сентябрь Октябрь Ноябрь Декабрь
+calendar-weekdays = Пн Вт Ср Чт Пт Сб Вс
+calendar-hint = hl день jk нед HL мес
+calendar-as-of = Показать на дату
+calendar-as-of-hint = ⏎ выбор t сег. ⌫ сейчас
+
+## Dialogs and prompts
+confirm-delete-title = Удаление: { $entity }
+confirm-delete-message = Удалить «{ $name }»?
+    Это действие нельзя отменить.
+confirm-commit-title = Применить песочницу
+confirm-commit-message = Отправить изменения расписания в API ({ $count })?
+    Выберите «Нет», чтобы продолжить моделирование.
+confirm-reschedule-title = Перенос проектов
+confirm-reschedule-message = Сдвинуть проекты ({ $count }) на { $days } дн.?
+confirm-quit-title = Выход
+confirm-quit-message = Всё равно выйти? Ещё не завершено:
+confirm-undo-title = Отмена
+confirm-undo-message = Отменить: { $label }?
+confirm-redo-title = Повтор
+confirm-redo-message = Повторить: { $label }?
+confirm-production = Это рабочее окружение «{ $profile }».
+confirm-production-input = Введите имя профиля и нажмите Enter:
+quit-pending-change = Ждёт ответа сервера: { $what }
+quit-pending-download = Загрузка { $name } (пока { $received })
+quit-pending-sandbox = Незафиксированных изменений песочницы: { $count }
+shutdown-title = Выход
+shutdown-sending = Отправка изменения { $done } из { $total } на сервер…
+shutdown-grace = Через { $secs } с выход без оставшихся
+confirm-preview-more = ... и ещё { $count }
+prompt-hint = Enter: подтвердить  Esc: отмена
+prompt-reschedule = Сдвиг проектов ({ $count }) на дни (напр. 14, -7)
+prompt-filter = Фильтр: { $entity } (напр. overdue && client:Acme, пусто = нет)
+prompt-audit-search = Поиск в журнале аудита (автор, действие или объект, пусто = все)
+prompt-bookmark = Закладка { $slot }
+error-dismiss-hint = Нажмите ESC или ENTER, чтобы закрыть
+error-auth-title = Требуется аутентификация
+error-auth-message = { $error }
+    Проверьте учётные данные API и повторите попытку.
+error-rejected-title = Запрос отклонён
+error-decode-title = Неожиданный ответ
+error-batch-title = Пакетное обновление
+error-batch-message = Успешно: { $succeeded }, с ошибкой: { $failed } (см. журнал)
+
+## Help overlay
+help-title = Справка
+help-heading = Горячие клавиши
+help-navigation = Навигация
+help-switch-tabs = Вкладки / поля формы
+help-move = Вверх/вниз по списку
+help-scroll = Прокрутка хронологии
+help-snap = К неделе/месяцу
+help-projects-table = Хронология ⇄ таблица проектов
+help-inline-edit = Таблица проектов: правка имени или срока в ячейке
+help-compare = Сравнение (клиент/менеджер)
+help-compare-cycle = Сравнение: панели
+help-as-of = Хронология на прошлую дату
+help-mermaid = Экспорт диаграммы Ганта в Mermaid
+help-sandbox = Песочница: вход / отправка
+help-sandbox-edit = Песочница: сдвиг, длина, сброс
+help-bookmark-save = Сохранить закладку
+help-bookmark-jump = Перейти к закладке
+help-crud = Операции с данными
+help-create = Создать запись
+help-edit = Изменить выбранное
+help-clone = Копировать выбранный проект (заполненная форма создания)
+help-mark = Отметить проект для пакетных действий (Esc — снять)
+help-reschedule = Сдвинуть отмеченные проекты на N дней
+help-archived = Скрыть/показать архивные (давно завершённые) проекты
+help-delete = Удалить выбранное
+help-undo = Отменить / повторить
+help-burndown = Диаграмма сгорания выбранного клиента/менеджера
+help-copy-id = Копировать UUID / команду перехода к выбранному
+help-favorite = Добавить/убрать проект или клиента в избранное
+help-watch = Следить за проектом или клиентом (уведомление и запись во «Входящих» при изменениях)
+help-jumps = Назад/вперёд по посещённым местам
+help-changes = Что изменилось при последнем обновлении
+help-sort = Диалог сортировки (Клиенты/Пользователи)
+help-columns = Показать, скрыть и переставить столбцы (Проекты/Клиенты/Пользователи)
+help-teams = Группировка пользователей по командам (Пользователи): Space сворачивает, Enter фильтрует хронологию
+help-menu = Действия с выбранной строкой (или правый клик)
+help-filter = Выражение-фильтр (Таймлайн/Клиенты/Пользователи)
+help-markdown = Выгрузить представление таблицей Markdown (Ctrl+y: копировать)
+help-inspector = Исходный JSON выбранного элемента
+help-attachments = Документы выбранного проекта (скачивание)
+help-console = Консоль API (GET любого пути)
+help-search = Поиск проектов, клиентов и пользователей
+help-form = Редактирование формы
+help-next-field = Следующее поле
+help-dropdown = Выбор в списке / дата
+help-date-picker = Дата: день / неделя / месяц / сегодня
+help-type = Ввод текста, поиск клиентов и менеджеров
+help-enter = Далее / сохранить
+help-esc = Отмена / закрыть форму
+help-general = Общие
+help-refresh = Обновить данные
+help-particles = Частицы вкл/выкл
+help-my-projects = Переключить режим «Мои проекты»
+help-debug = Отладка (FPS)
+help-telemetry = Телеметрия в строке состояния (FPS, отрисовка, память)
+help-settings = Настройки (язык, фон, частота кадров, слежение, ...)
+help-diagnose = Диагностика соединения (DNS, TCP, TLS, HTTP)
+help-quit = Выход
+
+## Status bar
+status-connected = Подключено
+status-disconnected = Нет связи
+status-backends = Серверы: { $backends }
+status-loading = [Загрузка...]
+status-seconds-ago = ({ $secs } с назад)
+status-minutes-ago = ({ $mins } мин назад)
+stale-age = УСТАРЕЛО: обновлено { $mins } мин назад
+stale-disconnected = УСТАРЕЛО: нет соединения, данным { $mins } мин
+stale-recorded = ИЗ ИСТОРИИ: данные на { $time }
+status-reconnect-in = переподключение через { $secs } с (попытка { $attempt })
+status-reconnecting = переподключение… (попытка { $attempt })
+status-keys = ?: Справка | c: Создать | e: Изменить | d: Удалить | q: Выход
+
+## Log messages
+log-initialized = SWEeM TUI запущен
+log-connecting = Подключение к API...
+log-connected = Подключено к API
+log-disconnected = Соединение с API потеряно
+log-diagnose-hint = Нажмите Ctrl+d, чтобы проверить DNS, TCP, TLS и HTTP по очереди
+log-production-mismatch = Введённое имя не совпадает с профилем
+log-diagnose-unavailable = Нет соединения с API для диагностики
+log-backend-down = Сервер { $name } не отвечает; его данные не показаны
+log-backend-up = Сервер { $name } снова доступен
+log-refreshing = Обновление данных...
+log-remote-changed = Данные на сервере изменились, перезагрузка
+log-watch-unsupported = Сервер не поддерживает /changes; режим наблюдения отключён
+log-webhook-failed = Не удалось отправить в вебхук: { $error }
+log-watch-slowed = Бюджет API на исходе (осталось запросов: { $remaining }): проверка изменений раз в { $secs } с
+log-watch-restored = Бюджет API восстановлен: проверка изменений снова раз в { $secs } с
+log-recording = Запись сеанса в { $path }
+log-recording-failed = Запись сеанса остановлена: { $error }
+log-replay-started = Воспроизведение { $events } записанных событий ({ $seconds } с); затем снова работает ввод с терминала, Ctrl+C — выход
+log-replay-size = Запись сделана в терминале { $recorded }, текущий — { $current }; раскладка может отличаться
+log-remote-unavailable = Удалённое управление отключено: { $error }
+log-remote-no-project = Удалённо: нет проекта с id { $id }
+log-focus-not-found = { $entity } { $id } из --focus не загружен
+log-search-hidden = { $name } скрыт текущими фильтрами
+log-filter-set = Фильтр ({ $entity }): { $filter }
+log-filter-cleared = Фильтр ({ $entity }) сброшен
+log-filter-invalid = Некорректный фильтр: { $error }
+mermaid-title = Проекты
+log-mermaid-exported = { $count } проектов экспортировано в диаграмму Ганта Mermaid: { $path }
+log-mermaid-failed = Ошибка экспорта Mermaid: { $error }
+log-markdown-exported = { $count } строк выгружено таблицей Markdown в { $path }
+log-markdown-copied = { $count } строк скопировано таблицей Markdown
+log-markdown-failed = Не удалось выгрузить Markdown: { $error }
+log-markdown-no-table = В этом представлении нет таблицы для выгрузки
+log-inspector-nothing = Выберите проект, клиента или пользователя для просмотра
+log-inspector-copied = JSON скопирован
+log-attachments-nothing = Выберите проект на временной шкале, чтобы увидеть его документы
+log-documents-failed = Не удалось загрузить документы: { $error }
+log-download-busy = Дождитесь окончания загрузки «{ $name }»
+log-download-saved = Загружено: { $path }
+log-download-failed = Ошибка загрузки: { $error }
+log-audit-failed = Не удалось загрузить журнал аудита: { $error }
+log-console-invalid = Некорректный запрос консоли: { $error }
+log-copied = Скопировано в буфер обмена: { $text }
+log-copy-nothing = Нечего копировать: ничего не выбрано
+log-menu-nothing = Не выбрано ничего для контекстного меню
+log-clone-nothing = Выберите проект для копирования
+log-clone-form = Копия проекта { $name }, даты сдвинуты на { $days } дн.
+log-complete-not-started = { $name } ещё не начат (начало { $date })
+log-complete-invalid = Не удалось завершить проект: { $error }
+log-favorite-added = { $name } добавлен в избранное
+log-favorite-removed = { $name } убран из избранного
+log-favorite-persist-failed = Не удалось сохранить избранное: { $error }
+log-watch-added = Отслеживается: { $name }
+log-watch-removed = Больше не отслеживается: { $name }
+log-watch-persist-failed = Не удалось сохранить список отслеживания: { $error }
+log-teams-unavailable = У загруженных пользователей нет полей команды или отдела
+log-sort-persist-failed = Не удалось сохранить порядок сортировки: { $error }
+log-columns-persist-failed = Не удалось сохранить набор столбцов: { $error }
+log-settings-saved = Настройки сохранены в { $path }
+log-settings-persist-failed = Не удалось сохранить настройки: { $error }
+log-changes = { $entity }: изменений с прошлой загрузки — { $count } (w — что изменилось)
+log-loaded-projects = Загружено проектов: { $count }
+log-loaded-clients = Загружено клиентов: { $count }
+log-loaded-users = Загружено пользователей: { $count }
+log-loaded-page = Загружена страница ({ $entity }): { $loaded } из { $total }
+log-script-filter-projects = Фильтр скрипта скрыл проектов: { $count }
+log-script-filter-clients = Фильтр скрипта скрыл клиентов: { $count }
+log-script-filter-users = Фильтр скрипта скрыл пользователей: { $count }
+log-created = { $entity }: создан ({ $id })
+log-updated = { $entity }: обновлён
+log-deleted = { $entity }: удалён ({ $id })
+log-creating-client = Создание клиента...
+log-updating-client = Обновление клиента...
+log-creating-project = Создание проекта...
+log-updating-project = Обновление проекта...
+log-inline-invalid = Не удалось сохранить ячейку: { $error }
+log-creating-user = Создание пользователя...
+log-updating-user = Обновление пользователя...
+log-deleting = Удаление: { $entity }...
+log-rescheduled = Перенесено проектов: { $count }
+log-reschedule-failed = Не удалось перенести { $name }: { $error }
+log-committing = Отправка изменений расписания: { $count }...
+log-rescheduling = Сдвиг проектов ({ $count }) на { $days } дн....
+log-reschedule-nothing = Нет отмеченных проектов (Пробел отмечает выбранный)
+log-reschedule-sandbox = Закройте песочницу перед переносом (S — применить, X — отменить)
+log-reschedule-invalid = Не число дней: { $input }
+log-sandbox-on = Песочница: </> сдвиг, {"{"}/{"}"} длительность, S отправить, X отменить
+log-sandbox-closed = Песочница закрыта (без изменений)
+log-sandbox-discarded = Изменения песочницы отменены
+log-sandbox-blocked = Закройте песочницу перед изменением проектов (S — применить, X — отменить)
+log-project-completed = Проект завершён: { $name }
+log-plugin-registered = Подключена вкладка плагина: { $title }
+log-particle-mode = Режим частиц: { $mode }
+log-as-of-today = Хронология на сегодня
+log-as-of = Хронология на { $date }
+log-history-shown = Показаны проекты ({ $count }), сохранённые { $time }
+log-history-failed = История снимков недоступна: { $error }
+log-inbox-new = Новых уведомлений во «Входящих»: { $count }
+log-alert-rule = Правило { $rule }: { $name } { $detail }
+log-inbox-persist-failed = Не удалось сохранить «Входящие»: { $error }
+log-ranking-exported = Рейтинг { $count } клиентов экспортирован в { $path }
+log-ranking-failed = Ошибка экспорта рейтинга клиентов: { $error }
+log-my-projects-on = Показаны проекты менеджера { $login }
+log-my-projects-off = Показаны все проекты
+log-archived-hidden = Скрыты проекты, завершённые более { $days } дн. назад (H — показать)
+log-archived-shown = Архивные проекты показаны
+log-archive-persist-failed = Не удалось сохранить настройку архива: { $error }
+log-telemetry-persist-failed = Не удалось сохранить настройку телеметрии: { $error }
+log-my-projects-no-user = Для режима «Мои проекты» нужен пользователь: укажите --user LOGIN или user в профиле
+log-my-projects-unknown = Нет пользователя с логином «{ $login }»
+log-bookmark-saved = Закладка { $slot } сохранена: { $name }
+log-bookmark-persist-failed = Не удалось сохранить закладки: { $error }
+log-bookmark-pick-slot = Сохранение закладки: нажмите 1-9, чтобы выбрать слот
+log-bookmark-jump = Закладка { $slot }: { $name }
+log-bookmark-empty = Слот { $slot } пуст
+log-comparing = Сравнение хронологий: { $by }
+log-comparison-off = Режим сравнения выключен
+
+## Undo history
+undo-busy = Дождитесь завершения предыдущего изменения
+undo-undoing = Отмена: { $label }...
+undo-redoing = Повтор: { $label }...
+undo-undid = Отменено: { $label }
+undo-redid = Повторено: { $label }
+undo-nothing = Нечего отменять (удаление отменить нельзя: в API нет восстановления)
+redo-nothing = Нечего повторять
+undo-label-client-update = изменение клиента
+undo-label-project-update = изменение проекта
+undo-label-user-update = изменение пользователя
+undo-label-reschedule = перенос проектов ({ $count })
+undo-label-client-creation = создание клиента
+undo-label-project-creation = создание проекта
+undo-label-user-creation = создание пользователя
+
+## Deadline alerts
+alert-overdue-title = Проект просрочен
+alert-overdue = Проект просрочен: { $name }
+alert-due-soon-title = Срок на исходе
+alert-due-soon = До срока проекта меньше суток: { $name }
+alert-rule-title = Правило оповещения
+alert-rule-added = добавлен
+alert-rule-overdue = просрочен (плановое окончание { $date })
+alert-rule-moved = плановое окончание { $from } → { $to } ({ $days } дн.)
+alert-rule-completed = завершён { $date }
+webhook-sla-breach = Нарушено SLA { $rule }: { $name }
+inbox-title = Входящие (непрочитанных: { $unread }) - Enter открыть, Space прочитано, a все прочитаны, x удалить
+inbox-empty = Уведомлений пока нет
+inbox-kind-overdue = Просрочен
+inbox-kind-due-soon = Скоро срок
+inbox-kind-reassigned = Назначен
+inbox-kind-changed = Изменения
+inbox-kind-rule = Правило
+inbox-kind-watched = Слежение
+inbox-overdue = { $name } просрочен (плановое окончание { $date })
+inbox-due-soon = { $name }: срок { $date }
+inbox-reassigned = { $name } теперь под вашим руководством
+inbox-changed = Изменения с прошлого запуска: добавлено { $added }, изменено { $updated }, удалено { $removed }
+inbox-rule = { $rule }: { $name } { $detail }
+inbox-watched = { $name }: { $detail }
+watch-added = добавлен
+watch-removed = удалён
+toast-watched-title = Изменение отслеживаемого
+stats-monthly-title = Проекты по месяцам, { $from } - { $to }
+stats-started = начато
+stats-completed = завершено
+stats-keys = h/l месяц, H/L период, +/- длина периода, t текущий, v рейтинг клиентов
+stats-overrun-title = Средняя задержка по кварталам
+stats-days-suffix = д
+stats-on-time-title = Сдано в срок
+stats-on-time = { $on_time } из { $completed } завершены в срок
+stats-started-total = начато за период: { $count }
+stats-overdue-now = просрочено сейчас: { $count }
+sla-title = Нарушения SLA
+sla-none = Правил SLA нет: добавьте [[sla]] в config.toml
+ranking-title = Рейтинг клиентов, { $from } - { $to }
+ranking-keys = s столбец сортировки, S обратный порядок, x экспорт CSV, v история, h/l месяц
+history-title = Проекты по дням, { $from } - { $to }
+history-keys = h/l месяц, H/L диапазон, +/- длина диапазона, t текущий, v графики
+history-active = активные
+history-overdue = просроченные
+history-on-track-title = Активные проекты в срок
+history-empty = В этом диапазоне ещё ничего не записано: история пополняется при каждом обновлении
+history-no-store = История снимков отключена ([history] в конфигурации)
+ranking-client = Клиент
+ranking-completed = Завершено
+ranking-on-time = В срок
+ranking-on-time-percent = В срок, %
+ranking-overrun = Ср. задержка
+burndown-title = Диаграмма сгорания: { $name }
+burndown-close = Esc закрыть
+diff-title = Что изменилось?
+diff-close = j/k прокрутка · Esc закрыть
+diff-empty = С прошлой загрузки ничего не изменилось
+diff-field-name = Название
+diff-field-start = Начало
+diff-field-planned-end = План. окончание
+diff-field-actual-end = Факт. окончание
+diff-field-client = Клиент
+diff-field-manager = Менеджер
+diff-field-address = Адрес
+diff-field-login = Логин
+diff-field-role = Роль
+markdown-status = Статус
+markdown-projects = Проекты
+markdown-script = Скрипт
+inspector-title = JSON: { $entity } { $id }
+inspector-close = j/k прокрутка · y копировать · Esc закрыть
+inspector-gone = Сущность больше не загружена
+inspector-unknown = неизвестное поле
+attachments-title = Документы: { $name }
+attachments-hint = j/k выбор · Enter скачать · Esc закрыть
+attachments-loading = Загрузка документов…
+attachments-unsupported = Сервер не поддерживает документы проектов
+attachments-failed = Не удалось загрузить документы: { $error }
+attachments-empty = Нет прикреплённых документов
+attachments-progress = Загрузка { $name }: { $received } из { $total }
+attachments-progress-unknown = Загрузка { $name }: { $received }
+audit-title = Журнал аудита - страница { $page } из { $pages }, событий: { $total } - Enter открыть, [ ] страница, / поиск, r обновить
+audit-search = Поиск: { $search }
+audit-loading = Загрузка журнала аудита…
+audit-unsupported = Сервер не ведёт журнал аудита
+audit-failed = Не удалось загрузить журнал аудита: { $error }
+audit-empty = Нет подходящих событий
+audit-system = система
+audit-column-time = Время (UTC)
+audit-column-actor = Автор
+audit-column-action = Действие
+audit-column-entity = Объект
+audit-column-summary = Изменение
+console-title = Консоль API
+console-hint = Enter отправить · Up/Down история · PgUp/PgDn прокрутка · Esc выйти
+console-empty = Введите путь, например /projects?page=1&pageSize=5, и нажмите Enter
+console-running = Отправка…
+console-status = { $request } · { $ms } мс
+console-failed = { $request }: ошибка через { $ms } мс
+search-title = Поиск
+search-hint = вводите запрос · Up/Down выбор · Enter перейти · Esc закрыть
+search-count = Совпадений: { $shown } из { $total } загруженных
+search-kind-project = проект
+search-kind-client = клиент
+search-kind-user = пользователь
+sort-title = Сортировка
+sort-hint = Пробел ↑/↓/выкл · c сбросить · Esc закрыть
+sort-column-name = Название
+sort-column-address = Адрес
+sort-column-completion = Доля завершённых
+sort-column-total = Всего проектов
+sort-column-completed = Завершено
+sort-column-login = Логин
+sort-column-role = Роль
+columns-title = Столбцы
+columns-hint = Пробел показать/скрыть · J/K сдвинуть · r сбросить · Esc закрыть
+diagnose-title = Диагностика соединения
+diagnose-keys = r повторить · Esc закрыть
+diagnose-running = Проверка DNS, TCP, TLS и HTTP...
+diagnose-skipped = пропущено
+diagnose-proxy = через прокси { $proxy }
+diagnose-hint-dns = Имя хоста не разрешается: проверьте URL, подключение к VPN и настройки DNS
+diagnose-hint-tcp = Сервер недоступен: бэкенд может быть остановлен, либо мешает файрвол, VPN или сеть только через прокси
+diagnose-hint-tls = Рукопожатие TLS не удалось: вероятно, перехватывающий прокси или сертификат, которому система не доверяет
+diagnose-hint-http = Сервер ответил, но не проектами: проверьте путь в URL, прокси и журнал сервера
+diagnose-ok = Все этапы пройдены: соединение работает
+settings-title = Настройки
+settings-hint = j/k выбор · h/l изменить · Esc сохранить и закрыть
+settings-language = Язык
+settings-particles = Фон
+settings-density = Плотность частиц
+settings-fps = Частота кадров
+settings-watch = Следить за изменениями
+settings-stale = Данные устаревают через
+settings-start-tab = Вкладка при запуске
+settings-zoom = Масштаб шкалы
+settings-notifications = Уведомления на рабочем столе
+settings-auto = авто (LANG)
+settings-on = вкл
+settings-off = выкл
+settings-never = никогда
+settings-fps-value = { $fps } кадр/с
+settings-seconds = каждые { $secs } с
+settings-minutes = { $minutes } мин
+settings-zoom-value = { $days } дн./столбец
+menu-details = Подробности (JSON)
+menu-copy-id = Копировать ID
+menu-edit = Изменить
+menu-clone = Копировать
+menu-complete = Завершить сегодня
+menu-filter-client = Проекты этого клиента
+menu-filter-manager = Проекты этого менеджера
+menu-documents = Документы
+menu-burndown = Диаграмма сгорания
+menu-favorite = В избранное / убрать
+menu-watch = Следить / не следить
+menu-delete = Удалить
+list-sorted-by = сортировка: { $spec }
+burndown-empty = В этой выборке нет проектов
+burndown-planned = По плану
+burndown-remaining = Осталось
+burndown-completed = Завершено
+burndown-axis = проекто-дни
@@ -0,0 +1,191 @@
+//! Alert rules.
+//!
+//! `[[alerts]]` entries in the config describe changes worth hearing about,
+//! such as "any Acme project becomes overdue" or "a planned end moves by more
+//! than a week". Each live project refresh is compared with the previous one
+//! and every rule whose trigger fired for a project matching its filter
+//! expression (the `/` syntax, e.g. `client:Acme`) adds an Inbox item and,
+//! unless the rule says otherwise, a desktop notification. The first refresh
+//! of a run only records the baseline, so restarting does not repeat alerts.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::expr::{self, FilterExpr};
+use crate::models::{ClientDto, ProjectDto, UserDto};
+use crate::tr;
+
+/// Change of a project that fires a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Trigger {
+    /// An open project passed its planned end
+    Overdue,
+    /// The planned end moved by more than `more_than_days`
+    PlannedEndMoved,
+    /// A project appeared
+    Added,
+    /// A project got an actual end date
+    Completed,
+}
+
+fn default_notify() -> bool {
+    true
+}
+
+/// One `[[alerts]]` rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Name shown in the Inbox and the notification
+    pub name: String,
+    pub on: Trigger,
+    /// Filter expression the project has to match (unset: every project)
+    #[serde(default)]
+    pub filter: Option<String>,
+    /// `planned-end-moved` only fires on moves of more than this many days
+    #[serde(default)]
+    pub more_than_days: i64,
+    /// Also show a desktop notification (subject to `[notifications]`)
+    #[serde(default = "default_notify")]
+    pub notify: bool,
+}
+
+impl AlertRule {
+    /// The filter has to parse
+    pub fn validate(&self) -> Result<()> {
+        if let Some(filter) = &self.filter {
+            if let Err(e) = FilterExpr::parse(filter) {
+                bail!("Alert rule \"{}\": invalid filter: {}", self.name, e);
+            }
+        }
+        if self.more_than_days < 0 {
+            bail!("Alert rule \"{}\": more_than_days cannot be negative", self.name);
+        }
+        Ok(())
+    }
+
+    /// What the rule saw in the project's change since the last refresh, if it fires
+    fn fires(&self, old: Option<(&ProjectDto, NaiveDate)>, new: &ProjectDto, today: NaiveDate) -> Option<String> {
+        match (self.on, old) {
+            (Trigger::Added, None) => Some(tr!("alert-rule-added").to_string()),
+            (Trigger::Overdue, old) if new.is_overdue_as_of(today) => {
+                let was_overdue = old.is_some_and(|(old, then)| old.is_overdue_as_of(then));
+                (!was_overdue).then(|| tr!("alert-rule-overdue", date = new.planned_end_date))
+            }
+            (Trigger::PlannedEndMoved, Some((old, _))) => {
+                let days = (new.planned_end_date - old.planned_end_date).num_days();
+                (days.abs() > self.more_than_days && days != 0).then(|| {
+                    tr!("alert-rule-moved", from = old.planned_end_date, to = new.planned_end_date, days = format!("{:+}", days))
+                })
+            }
+            (Trigger::Completed, Some((old, _))) if old.actual_end_date.is_none() => {
+                new.actual_end_date.map(|date| tr!("alert-rule-completed", date = date))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A rule that fired for a project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleHit {
+    pub rule: String,
+    pub project: Uuid,
+    pub name: String,
+    /// What the rule saw
+    pub detail: String,
+    pub notify: bool,
+}
+
+/// Projects of the previous refresh, to compare the next one with
+#[derive(Debug, Default)]
+pub struct RuleWatch {
+    /// `None` until the first refresh, which only records the baseline
+    seen: Option<(NaiveDate, HashMap<Uuid, ProjectDto>)>,
+}
+
+impl RuleWatch {
+    /// Evaluate the rules against the change from the previous refresh
+    pub fn check(
+        &mut self,
+        rules: &[AlertRule],
+        projects: &[ProjectDto],
+        clients: &[ClientDto],
+        users: &[UserDto],
+        today: NaiveDate,
+    ) -> Vec<RuleHit> {
+        let mut hits = Vec::new();
+        if let Some((then, seen)) = &self.seen {
+            for rule in rules {
+                // Validated with the config
+                let filter = rule.filter.as_deref().and_then(|f| FilterExpr::parse(f).ok());
+                for project in projects {
+                    let old = seen.get(&project.id).map(|old| (old, *then));
+                    let Some(detail) = rule.fires(old, project, today) else {
+                        continue;
+                    };
+                    if filter.as_ref().is_some_and(|f| !f.matches(&expr::project_row(project, clients, users, today))) {
+                        continue;
+                    }
+                    hits.push(RuleHit {
+                        rule: rule.name.clone(),
+                        project: project.id,
+                        name: project.display_name().to_string(),
+                        detail,
+                        notify: rule.notify,
+                    });
+                }
+            }
+        }
+        self.seen = Some((today, projects.iter().map(|p| (p.id, p.clone())).collect()));
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sweem_core::fixtures;
+
+    use super::*;
+
+    #[test]
+    fn test_rules_fire_on_changes() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let acme = Uuid::from_u128(1);
+        let clients = [fixtures::client(acme, "Acme")];
+        let project = |n: u128, client_id, end| ProjectDto {
+            id: Uuid::from_u128(n),
+            client_id,
+            name: Some(format!("P{}", n)),
+            ..fixtures::project(date(1), date(end))
+        };
+        let rules: Vec<AlertRule> = toml::from_str::<toml::Table>(
+            "[[alerts]]\nname = \"Acme late\"\non = \"overdue\"\nfilter = \"client:acme\"\n\
+             [[alerts]]\nname = \"Slip\"\non = \"planned-end-moved\"\nmore_than_days = 7\nnotify = false\n",
+        )
+        .unwrap()["alerts"]
+            .clone()
+            .try_into()
+            .unwrap();
+
+        let mut watch = RuleWatch::default();
+        let before = [project(1, acme, 10), project(2, Uuid::nil(), 10), project(3, acme, 20)];
+        assert!(watch.check(&rules, &before, &clients, &[], date(5)).is_empty(), "baseline");
+
+        // P1 and P2 become overdue, only P1 is Acme's; P3 slips by 8 days
+        let after = [project(1, acme, 10), project(2, Uuid::nil(), 10), project(3, acme, 28)];
+        let hits = watch.check(&rules, &after, &clients, &[], date(11));
+        let seen: Vec<_> = hits.iter().map(|h| (h.rule.as_str(), h.name.as_str(), h.notify)).collect();
+        assert_eq!(seen, [("Acme late", "P1", true), ("Slip", "P3", false)]);
+        assert_eq!(hits[1].detail, "planned end 2025-03-20 → 2025-03-28 (+8 days)");
+        assert!(watch.check(&rules, &after, &clients, &[], date(12)).is_empty(), "fired once");
+
+        let mut invalid = rules[0].clone();
+        invalid.filter = Some("(client:acme".into());
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -1,456 +1,39 @@
-//! API client for the SWEeM REST API.
+//! Communication between the TUI and its API worker task.
 //!
-//! This module provides an async HTTP client for communicating with the backend.
-//! All methods are non-blocking and designed to run in a separate Tokio task.
-//! Supports full CRUD operations for Clients, Projects, and Users.
+//! The HTTP client itself lives in `sweem-core`; this module defines the
+//! commands the UI sends to the worker and the messages it gets back.
 
 #![allow(dead_code)]
 
-use anyhow::{Context, Result};
-use reqwest::Client;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub use sweem_core::api::{ApiClient, ConnectionOptions, RateLimit, DEFAULT_BASE_URL, DEFAULT_TIMEOUT};
+pub use sweem_core::error::{ApiError, ApiErrorKind};
+pub use sweem_core::probe::Diagnosis;
+pub use sweem_core::source::BackendStatus;
+
+use crate::console::{ConsoleRequest, ConsoleResponse};
+use crate::prefetch::PrefetchJob;
+use crate::store::StoreJob;
+use crate::timeseries::DailyPoint;
+use crate::undo::OperationId;
+use crate::tr;
 use crate::models::{
-    ClientDto, CreateClientDto, CreateProjectDto, CreateUserDto, PaginatedResult, ProjectDto,
-    UpdateClientDto, UpdateProjectDto, UpdateUserDto, UserDto,
+    AuditEventDto, ClientDto, CreateClientDto, CreateProjectDto, CreateUserDto, DocumentDto, PaginatedResult,
+    ProjectDto, UpdateClientDto, UpdateProjectDto, UpdateUserDto, UserDto,
 };
 
-/// Default API base URL
-pub const DEFAULT_BASE_URL: &str = "http://localhost:5094";
-
-/// API client for the SWEeM backend
-#[derive(Debug, Clone)]
-pub struct ApiClient {
-    client: Client,
-    base_url: String,
-}
-
-impl ApiClient {
-    /// Create a new API client with the specified base URL
-    pub fn new(base_url: impl Into<String>) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .context("Failed to create HTTP client")?;
-
-        Ok(Self {
-            client,
-            base_url: base_url.into(),
-        })
-    }
-
-    /// Create a new API client with the default base URL
-    pub fn with_default_url() -> Result<Self> {
-        Self::new(DEFAULT_BASE_URL)
-    }
-
-    // ============================================
-    // Projects CRUD
-    // ============================================
-
-    /// Fetch all projects with pagination
-    pub async fn fetch_projects(
-        &self,
-        page: i32,
-        page_size: i32,
-    ) -> Result<PaginatedResult<ProjectDto>> {
-        let url = format!(
-            "{}/projects?page={}&pageSize={}",
-            self.base_url, page, page_size
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to send request to projects endpoint")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse projects response")
-    }
-
-    /// Fetch all projects (unpaginated, fetches all pages)
-    pub async fn fetch_all_projects(&self) -> Result<Vec<ProjectDto>> {
-        let mut all_projects = Vec::new();
-        let mut page = 1;
-        let page_size = 100;
-
-        loop {
-            let result = self.fetch_projects(page, page_size).await?;
-            all_projects.extend(result.items().to_vec());
-
-            if !result.has_next {
-                break;
-            }
-            page += 1;
-        }
-
-        Ok(all_projects)
-    }
-
-    /// Create a new project
-    pub async fn create_project(&self, project: &CreateProjectDto) -> Result<Uuid> {
-        let url = format!("{}/projects", self.base_url);
-
-        let response = self
-            .client
-            .post(&url)
-            .json(project)
-            .send()
-            .await
-            .context("Failed to send create project request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse create project response")
-    }
-
-    /// Update an existing project
-    pub async fn update_project(&self, id: Uuid, project: &UpdateProjectDto) -> Result<ProjectDto> {
-        let url = format!("{}/projects/{}", self.base_url, id);
-
-        let response = self
-            .client
-            .put(&url)
-            .json(project)
-            .send()
-            .await
-            .context("Failed to send update project request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse update project response")
-    }
-
-    /// Delete a project
-    pub async fn delete_project(&self, id: Uuid) -> Result<Uuid> {
-        let url = format!("{}/projects/{}", self.base_url, id);
-
-        let response = self
-            .client
-            .delete(&url)
-            .send()
-            .await
-            .context("Failed to send delete project request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse delete project response")
-    }
-
-    // ============================================
-    // Clients CRUD
-    // ============================================
-
-    /// Fetch all clients with pagination
-    pub async fn fetch_clients(
-        &self,
-        page: i32,
-        page_size: i32,
-    ) -> Result<PaginatedResult<ClientDto>> {
-        let url = format!(
-            "{}/clients?page={}&pageSize={}",
-            self.base_url, page, page_size
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to send request to clients endpoint")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse clients response")
-    }
-
-    /// Fetch all clients (unpaginated, fetches all pages)
-    pub async fn fetch_all_clients(&self) -> Result<Vec<ClientDto>> {
-        let mut all_clients = Vec::new();
-        let mut page = 1;
-        let page_size = 100;
-
-        loop {
-            let result = self.fetch_clients(page, page_size).await?;
-            all_clients.extend(result.items().to_vec());
-
-            if !result.has_next {
-                break;
-            }
-            page += 1;
-        }
-
-        Ok(all_clients)
-    }
-
-    /// Create a new client
-    pub async fn create_client(&self, client_dto: &CreateClientDto) -> Result<Uuid> {
-        let url = format!("{}/clients", self.base_url);
-
-        let response = self
-            .client
-            .post(&url)
-            .json(client_dto)
-            .send()
-            .await
-            .context("Failed to send create client request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse create client response")
-    }
-
-    /// Update an existing client
-    pub async fn update_client(&self, id: Uuid, client_dto: &UpdateClientDto) -> Result<ClientDto> {
-        let url = format!("{}/clients/{}", self.base_url, id);
-
-        let response = self
-            .client
-            .put(&url)
-            .json(client_dto)
-            .send()
-            .await
-            .context("Failed to send update client request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse update client response")
-    }
-
-    /// Delete a client
-    pub async fn delete_client(&self, id: Uuid) -> Result<Uuid> {
-        let url = format!("{}/clients/{}", self.base_url, id);
-
-        let response = self
-            .client
-            .delete(&url)
-            .send()
-            .await
-            .context("Failed to send delete client request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse delete client response")
-    }
-
-    // ============================================
-    // Users CRUD
-    // ============================================
-
-    /// Fetch all users with pagination
-    pub async fn fetch_users(&self, page: i32, page_size: i32) -> Result<PaginatedResult<UserDto>> {
-        let url = format!(
-            "{}/users?page={}&pageSize={}",
-            self.base_url, page, page_size
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to send request to users endpoint")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse users response")
-    }
-
-    /// Fetch all users (unpaginated, fetches all pages)
-    pub async fn fetch_all_users(&self) -> Result<Vec<UserDto>> {
-        let mut all_users = Vec::new();
-        let mut page = 1;
-        let page_size = 100;
-
-        loop {
-            let result = self.fetch_users(page, page_size).await?;
-            all_users.extend(result.items().to_vec());
-
-            if !result.has_next {
-                break;
-            }
-            page += 1;
-        }
-
-        Ok(all_users)
-    }
-
-    /// Create a new user
-    pub async fn create_user(&self, user: &CreateUserDto) -> Result<Uuid> {
-        let url = format!("{}/users", self.base_url);
-
-        let response = self
-            .client
-            .post(&url)
-            .json(user)
-            .send()
-            .await
-            .context("Failed to send create user request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse create user response")
-    }
-
-    /// Update an existing user
-    pub async fn update_user(&self, id: Uuid, user: &UpdateUserDto) -> Result<UserDto> {
-        let url = format!("{}/users/{}", self.base_url, id);
-
-        let response = self
-            .client
-            .put(&url)
-            .json(user)
-            .send()
-            .await
-            .context("Failed to send update user request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse update user response")
-    }
-
-    /// Delete a user
-    pub async fn delete_user(&self, id: Uuid) -> Result<Uuid> {
-        let url = format!("{}/users/{}", self.base_url, id);
-
-        let response = self
-            .client
-            .delete(&url)
-            .send()
-            .await
-            .context("Failed to send delete user request")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        response
-            .json()
-            .await
-            .context("Failed to parse delete user response")
-    }
-
-    // ============================================
-    // Utility
-    // ============================================
-
-    /// Health check - attempts to fetch first page of projects
-    pub async fn health_check(&self) -> Result<bool> {
-        match self.fetch_projects(1, 1).await {
-            Ok(_) => Ok(true),
-            Err(_) => Ok(false),
-        }
-    }
-}
+/// How long writes still queued on quit may take to reach the server
+pub const DRAIN_GRACE: Duration = Duration::from_secs(10);
 
 /// Messages sent from API worker to the main TUI thread
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ApiMessage {
     /// Projects data has been loaded
     ProjectsLoaded(Vec<ProjectDto>),
@@ -458,32 +41,164 @@ pub enum ApiMessage {
     ClientsLoaded(Vec<ClientDto>),
     /// Users data has been loaded
     UsersLoaded(Vec<UserDto>),
+    /// A page of the clients has been loaded (lazy lists, see `paging.rs`)
+    ClientsPage(PaginatedResult<ClientDto>),
+    /// A page of the users has been loaded (lazy lists)
+    UsersPage(PaginatedResult<UserDto>),
     /// An error occurred during API communication
-    Error(String),
+    Error(AppError),
     /// API connection status changed
     ConnectionStatus(bool),
+    /// Which backends of an aggregated source answered (`[api] aggregate`)
+    Backends(Vec<BackendStatus>),
+    /// Connection diagnosis of each API URL
+    Diagnosis(Vec<Diagnosis>),
     /// Entity created successfully
     Created(EntityType, Uuid),
     /// Entity updated successfully
     Updated(EntityType),
     /// Entity deleted successfully
     Deleted(EntityType, Uuid),
+    /// Data was fetched ahead of time (see `prefetch.rs`)
+    Prefetched(Prefetched),
+    /// Watch mode saw a new data version on the server (a reload follows)
+    RemoteChanged,
+    /// Watch mode stopped because the server has no `/changes` endpoint
+    WatchUnsupported,
+    /// Watch mode changed its poll interval to fit the rate-limit budget
+    WatchPaced {
+        interval: Duration,
+        /// The `--watch` interval
+        base: Duration,
+        rate_limit: Option<RateLimit>,
+    },
+    /// A batch of project updates finished (per-item failures included)
+    BatchUpdated {
+        succeeded: usize,
+        failed: Vec<(Uuid, String)>,
+    },
+    /// Answer to a request from the API console
+    ConsoleResponse(ConsoleResponse),
+    /// A project's document list (404 when the server has no documents)
+    DocumentsLoaded {
+        project_id: Uuid,
+        result: Result<Vec<DocumentDto>, ApiError>,
+    },
+    /// Bytes of a document download received so far
+    DownloadProgress {
+        document_id: Uuid,
+        received: u64,
+        total: Option<u64>,
+    },
+    /// A document download ended: the saved file or why it failed
+    DownloadFinished {
+        document_id: Uuid,
+        result: Result<PathBuf, String>,
+    },
+    /// A page of the audit log for the given search (404 when the server has none)
+    AuditLoaded {
+        page: i32,
+        search: String,
+        result: Result<PaginatedResult<AuditEventDto>, ApiError>,
+    },
+    /// Outcome of a command sent for an undo history operation (see `undo.rs`)
+    Operation(OperationId, Box<ApiMessage>),
+    /// Quitting: `done` of the `total` writes still queued have been sent
+    Draining { done: usize, total: usize },
+    /// Posting alerts to the `[webhook]` failed
+    WebhookFailed(String),
+    /// A load went into the snapshot history: whether it differed from the
+    /// previous snapshot, or why it could not be recorded
+    SnapshotRecorded {
+        entity: EntityType,
+        result: Result<bool, String>,
+    },
+    /// Daily project counts of a range for the Stats history page
+    HistoryLoaded {
+        range: (NaiveDate, NaiveDate),
+        result: Result<Vec<DailyPoint>, String>,
+    },
+}
+
+impl ApiMessage {
+    /// The message without the operation it belongs to
+    pub fn untagged(&self) -> &ApiMessage {
+        match self {
+            ApiMessage::Operation(_, message) => message.untagged(),
+            message => message,
+        }
+    }
+}
+
+/// Data fetched by the prefetch scheduler before the UI asked for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Prefetched {
+    Documents { project_id: Uuid, documents: Vec<DocumentDto> },
+    ClientsPage(PaginatedResult<ClientDto>),
+    UsersPage(PaginatedResult<UserDto>),
+    Audit { page: i32, search: String, result: PaginatedResult<AuditEventDto> },
+}
+
+/// A failure reported by the API worker
+#[derive(Debug, Clone, thiserror::Error, Serialize, Deserialize)]
+pub enum AppError {
+    /// An API call made for `operation` (e.g. "Create client") failed
+    #[error("{operation} failed: {source}")]
+    Api {
+        operation: Cow<'static, str>,
+        #[source]
+        source: ApiError,
+    },
+    /// The health check could not reach the backend
+    #[error("Cannot connect to API")]
+    Offline,
+}
+
+impl AppError {
+    pub fn api(operation: &'static str, source: ApiError) -> Self {
+        AppError::Api { operation: Cow::Borrowed(operation), source }
+    }
+
+    /// Kind of the underlying API error (`None` when offline)
+    pub fn kind(&self) -> Option<ApiErrorKind> {
+        match self {
+            AppError::Api { source, .. } => Some(source.kind()),
+            AppError::Offline => None,
+        }
+    }
+
+    /// Whether the connection dropped mid-session and should be re-checked
+    pub fn needs_reconnect(&self) -> bool {
+        matches!(self.kind(), Some(ApiErrorKind::Connection | ApiErrorKind::Timeout))
+    }
 }
 
 /// Entity types for CRUD operations
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EntityType {
     Client,
     Project,
     User,
 }
 
+impl EntityType {
+    /// Parse a singular or plural name as used on the command line ("project", "clients", ...)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "projects" | "project" => Some(EntityType::Project),
+            "clients" | "client" => Some(EntityType::Client),
+            "users" | "user" => Some(EntityType::User),
+            _ => None,
+        }
+    }
+}
+
 impl std::fmt::Display for EntityType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            EntityType::Client => write!(f, "Client"),
-            EntityType::Project => write!(f, "Project"),
-            EntityType::User => write!(f, "User"),
+            EntityType::Client => f.write_str(tr!("entity-client")),
+            EntityType::Project => f.write_str(tr!("entity-project")),
+            EntityType::User => f.write_str(tr!("entity-user")),
         }
     }
 }
@@ -516,10 +231,102 @@ pub enum ApiCommand {
     UpdateProject(Uuid, UpdateProjectDto),
     /// Delete a project
     DeleteProject(Uuid),
+    /// Update several projects in one go (sandbox commit)
+    BatchUpdateProjects(Vec<(Uuid, UpdateProjectDto)>),
     /// Create a new user
     CreateUser(CreateUserDto),
     /// Update an existing user
     UpdateUser(Uuid, UpdateUserDto),
     /// Delete a user
     DeleteUser(Uuid),
+    /// Replace the background prefetch queue (jobs with priorities; empty cancels)
+    Prefetch(Vec<(PrefetchJob, u8)>),
+    /// GET typed into the API console
+    Console(ConsoleRequest),
+    /// List the documents of a project
+    FetchDocuments(Uuid),
+    /// Download a project document into a directory
+    DownloadDocument {
+        project_id: Uuid,
+        document: DocumentDto,
+        dir: PathBuf,
+    },
+    /// Load a page of the audit log, filtered on the server (empty search = all)
+    FetchAudit {
+        page: i32,
+        search: String,
+    },
+    /// Load the next page of the Clients or Users list (lazy lists)
+    FetchPage {
+        entity: EntityType,
+        page: i32,
+    },
+    /// Change the watch-mode poll interval (`None` stops polling)
+    SetWatch(Option<Duration>),
+    /// Check DNS, TCP, TLS and HTTP separately for each API URL, with the
+    /// `[api]` connection settings
+    Diagnose(Vec<String>, ConnectionOptions),
+    /// Post alert lines to the `[webhook]` (one message)
+    PostWebhook(Vec<String>),
+    /// A mutation sent for an undo history operation; its outcome comes back
+    /// as `ApiMessage::Operation`
+    Operation(OperationId, Box<ApiCommand>),
+    /// Snapshot history work, run on a blocking thread (see `store.rs`)
+    Store(StoreJob),
+}
+
+impl ApiCommand {
+    /// Whether the command changes data on the server (and must not be dropped on quit)
+    pub fn is_mutation(&self) -> bool {
+        if let ApiCommand::Operation(_, command) = self {
+            return command.is_mutation();
+        }
+        matches!(
+            self,
+            ApiCommand::CreateClient(_)
+                | ApiCommand::UpdateClient(..)
+                | ApiCommand::DeleteClient(_)
+                | ApiCommand::CreateProject(_)
+                | ApiCommand::UpdateProject(..)
+                | ApiCommand::DeleteProject(_)
+                | ApiCommand::BatchUpdateProjects(_)
+                | ApiCommand::CreateUser(_)
+                | ApiCommand::UpdateUser(..)
+                | ApiCommand::DeleteUser(_)
+        )
+    }
+
+    /// Short name for logging (payloads may contain personal data)
+    pub fn name(&self) -> &'static str {
+        match self {
+            ApiCommand::RefreshAll => "refresh_all",
+            ApiCommand::RefreshProjects => "refresh_projects",
+            ApiCommand::RefreshClients => "refresh_clients",
+            ApiCommand::RefreshUsers => "refresh_users",
+            ApiCommand::CheckConnection => "check_connection",
+            ApiCommand::Shutdown => "shutdown",
+            ApiCommand::CreateClient(_) => "create_client",
+            ApiCommand::UpdateClient(..) => "update_client",
+            ApiCommand::DeleteClient(_) => "delete_client",
+            ApiCommand::CreateProject(_) => "create_project",
+            ApiCommand::UpdateProject(..) => "update_project",
+            ApiCommand::DeleteProject(_) => "delete_project",
+            ApiCommand::BatchUpdateProjects(_) => "batch_update_projects",
+            ApiCommand::CreateUser(_) => "create_user",
+            ApiCommand::UpdateUser(..) => "update_user",
+            ApiCommand::DeleteUser(_) => "delete_user",
+            ApiCommand::Prefetch(_) => "prefetch",
+            ApiCommand::Console(_) => "console",
+            ApiCommand::FetchDocuments(_) => "fetch_documents",
+            ApiCommand::DownloadDocument { .. } => "download_document",
+            ApiCommand::FetchAudit { .. } => "fetch_audit",
+            ApiCommand::FetchPage { .. } => "fetch_page",
+            ApiCommand::SetWatch(_) => "set_watch",
+            ApiCommand::Diagnose(..) => "diagnose",
+            ApiCommand::PostWebhook(_) => "post_webhook",
+            ApiCommand::Operation(_, command) => command.name(),
+            ApiCommand::Store(StoreJob::Record(..)) => "record_snapshot",
+            ApiCommand::Store(StoreJob::History(..)) => "load_history",
+        }
+    }
 }
@@ -6,19 +6,76 @@
 
 #![allow(dead_code)]
 
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::time::{Duration, Instant};
 
-use chrono::NaiveDate;
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, Utc};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::Value;
+use sweem_core::aggregate::SOURCE_FIELD;
+use sweem_core::stats;
 use uuid::Uuid;
 
-use crate::api::{ApiCommand, ApiMessage, EntityType};
+use crate::api::{
+    ApiCommand, ApiErrorKind, ApiMessage, AppError, BackendStatus, ConnectionOptions, Diagnosis, EntityType,
+};
+use crate::attachments::{self, Attachments, DocumentList, DownloadProgress};
+use crate::audit::{self, AuditFeed, AuditLog};
 use crate::models::{
-    ClientDto, CreateClientDto, CreateProjectDto, CreateUserDto, ProjectDto, Role,
+    ClientDto, CreateClientDto, CreateProjectDto, CreateUserDto, PaginatedResult, ProjectDto, Role,
     UpdateClientDto, UpdateProjectDto, UpdateUserDto, UserDto,
 };
-use crate::particles::ParticleSystem;
-use crate::timeline::TimelineState;
+use crate::clipboard;
+use crate::config::{Config, StartTab};
+use crate::console::Console;
+use crate::diff::{ChangeReport, Snapshot};
+use crate::dirty::Dirty;
+use crate::due;
+use crate::flash::{self, CellFlash};
+use crate::expr::{self, FilterExpr};
+use crate::export::{self, ClientRankingRow, Filter, RankingColumn};
+use crate::inbox::InboxEvent;
+use crate::inspector::Inspector;
+use crate::ipc::RemoteCommand;
+use crate::jumplist::JumpList;
+use crate::markdown;
+use crate::menu::{ContextMenu, MenuAction};
+use crate::mermaid;
+use crate::pacing::{FrameStats, ResizeDebounce};
+use crate::paging::ListPages;
+use crate::notify::{Alert, AlertKind, DeadlineWatch};
+use crate::plugin::{PluginContext, TabPlugin};
+use crate::reconnect::Backoff;
+use crate::prefetch::{PrefetchCache, PrefetchJob, PRIORITY_NEIGHBOR, PRIORITY_SELECTED};
+use crate::particles::{ParticleEffect, ParticleMode, ParticleSystem};
+use crate::sandbox::Sandbox;
+use crate::script::{self, Scripts};
+use crate::i18n::{self, Locale};
+use crate::search::{Names, Search, SearchDoc, SearchIndex};
+use crate::settings::{self, Setting, SettingsDialog};
+use crate::columns::{self, Column, ColumnLayout};
+use crate::alerts::RuleWatch;
+use crate::sla::BreachWatch;
+use crate::datepicker::{self, DatePicker};
+use crate::inline::InlineEdit;
+use crate::picker::{self, Picker};
+use crate::sort::{self, SortColumn, SortKey};
+use crate::state::{Bookmark, UiState};
+use crate::store::{Load, SnapshotStore, StoreJob};
+use crate::subscriptions::{self, Toast};
+use crate::teams::{self, TeamView, TreeRow};
+use crate::telemetry::{self, Telemetry};
+use crate::theme::colors;
+use crate::timeline::{TimelineState, NAME_COLUMN_WIDTH};
+use crate::timeseries::DailyPoint;
+use crate::tr;
+use crate::undo::{Direction, History, Operation, OperationId};
+
+/// How long timeline animations keep playing after the last key press or API result
+const TIMELINE_IDLE_AFTER: Duration = Duration::from_secs(10);
+
+/// Range lengths (months) the Stats tab cycles through with `+` / `-`
+const STATS_RANGES: [u32; 4] = [3, 6, 12, 24];
 
 /// Active tab in the application
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -28,39 +85,158 @@ pub enum Tab {
     /// Project timeline view (default)
     #[default]
     Timeline,
+    /// Projects as a table with exact dates; shares the timeline's selection
+    Projects,
     /// Users list view
     Users,
+    /// Delivery trend charts
+    Stats,
+    /// Generated alerts with read/unread state
+    Inbox,
+    /// Backend activity feed
+    Audit,
+    /// Hidden API console (`F4`), not part of the tab cycle
+    Console,
+    /// Tab provided by a registered plugin (index into `App::plugins`)
+    Plugin(usize),
 }
 
 impl Tab {
+    /// Built-in tabs in display order
+    pub const BUILT_IN: [Tab; 7] =
+        [Tab::Clients, Tab::Timeline, Tab::Projects, Tab::Users, Tab::Stats, Tab::Inbox, Tab::Audit];
+
+    /// All tabs in display order given the number of registered plugins
+    pub fn all(plugin_count: usize) -> Vec<Tab> {
+        Self::BUILT_IN
+            .into_iter()
+            .chain((0..plugin_count).map(Tab::Plugin))
+            .collect()
+    }
+
     /// Move to the next tab
-    pub fn next(&self) -> Self {
+    pub fn next(&self, plugin_count: usize) -> Self {
+        let tabs = Self::all(plugin_count);
+        let index = tabs.iter().position(|t| t == self).unwrap_or(0);
+        tabs[(index + 1) % tabs.len()]
+    }
+
+    /// Move to the previous tab
+    pub fn previous(&self, plugin_count: usize) -> Self {
+        let tabs = Self::all(plugin_count);
+        let index = tabs.iter().position(|t| t == self).unwrap_or(0);
+        tabs[(index + tabs.len() - 1) % tabs.len()]
+    }
+
+    /// Get the display name of a built-in tab (plugins name themselves)
+    pub fn name(&self) -> &'static str {
         match self {
-            Tab::Clients => Tab::Timeline,
-            Tab::Timeline => Tab::Users,
-            Tab::Users => Tab::Clients,
+            Tab::Clients => tr!("tab-clients"),
+            Tab::Timeline => tr!("tab-timeline"),
+            Tab::Projects => tr!("tab-projects"),
+            Tab::Users => tr!("tab-users"),
+            Tab::Stats => tr!("tab-stats"),
+            Tab::Inbox => tr!("tab-inbox"),
+            Tab::Audit => tr!("tab-audit"),
+            Tab::Console => tr!("tab-console"),
+            Tab::Plugin(_) => tr!("tab-plugin"),
         }
     }
 
-    /// Move to the previous tab
-    pub fn previous(&self) -> Self {
+    /// Entity type listed by the tab, if any
+    pub fn entity_type(&self) -> Option<EntityType> {
         match self {
-            Tab::Clients => Tab::Users,
-            Tab::Timeline => Tab::Clients,
-            Tab::Users => Tab::Timeline,
+            Tab::Clients => Some(EntityType::Client),
+            Tab::Timeline | Tab::Projects => Some(EntityType::Project),
+            Tab::Users => Some(EntityType::User),
+            Tab::Stats | Tab::Inbox | Tab::Audit | Tab::Console | Tab::Plugin(_) => None,
+        }
+    }
+}
+
+impl From<StartTab> for Tab {
+    fn from(tab: StartTab) -> Self {
+        match tab {
+            StartTab::Clients => Tab::Clients,
+            StartTab::Timeline => Tab::Timeline,
+            StartTab::Projects => Tab::Projects,
+            StartTab::Users => Tab::Users,
+            StartTab::Stats => Tab::Stats,
+            StartTab::Inbox => Tab::Inbox,
+            StartTab::Audit => Tab::Audit,
         }
     }
+}
+
+/// Dimension a timeline comparison splits projects by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareBy {
+    /// One pane per client
+    Client,
+    /// One pane per manager
+    Manager,
+}
 
-    /// Get the display name of the tab
+impl CompareBy {
+    /// Get the display name of the dimension
     pub fn name(&self) -> &'static str {
         match self {
-            Tab::Clients => "Clients",
-            Tab::Timeline => "Timeline",
-            Tab::Users => "Users",
+            CompareBy::Client => tr!("compare-client"),
+            CompareBy::Manager => tr!("compare-manager"),
         }
     }
 }
 
+/// Side-by-side timeline comparison state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comparison {
+    /// What the panes are filtered by
+    pub by: CompareBy,
+    /// Index of the client/user shown in the top pane
+    pub top: usize,
+    /// Index of the client/user shown in the bottom pane
+    pub bottom: usize,
+}
+
+/// One filtered pane of a timeline comparison
+#[derive(Debug, Clone)]
+pub struct ComparisonPane {
+    /// Pane title (client or manager name)
+    pub label: String,
+    /// Projects belonging to the pane
+    pub projects: Vec<ProjectDto>,
+    /// Index of the globally selected project within this pane
+    pub selected: Option<usize>,
+}
+
+/// Page shown in the Stats tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatsView {
+    /// Monthly, quarterly and on-time charts
+    #[default]
+    Trends,
+    /// Clients ranked by on-time delivery
+    ClientRanking,
+    /// Active and overdue projects per day, from the snapshot history
+    History,
+}
+
+/// A tab and the entity selected on it, as kept in the navigation history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Place {
+    pub tab: Tab,
+    pub entity: Option<(EntityType, Uuid)>,
+}
+
+/// Client or manager whose burndown chart is open
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Burndown {
+    pub by: CompareBy,
+    pub id: Uuid,
+    /// Client or manager name
+    pub label: String,
+}
+
 /// Input mode for the application
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum InputMode {
@@ -71,6 +247,8 @@ pub enum InputMode {
     Editing,
     /// Confirmation dialog (delete)
     Confirming,
+    /// Single-line text prompt
+    Prompting,
 }
 
 /// Type of form being displayed
@@ -151,19 +329,17 @@ impl FormField {
     /// Get display label for the field
     pub fn label(&self) -> &'static str {
         match self {
-            FormField::ClientName => "Name",
-            FormField::ClientAddress => "Address",
-            FormField::ProjectName => "Name",
-            FormField::ProjectClient => "Client",
-            FormField::ProjectManager => "Manager",
-            FormField::ProjectStartDate => "Start Date",
-            FormField::ProjectEndDate => "End Date",
-            FormField::UserName => "Name",
-            FormField::UserLogin => "Login",
-            FormField::UserPassword => "Password",
-            FormField::UserRole => "Role",
-            FormField::SubmitButton => "Save",
-            FormField::CancelButton => "Cancel",
+            FormField::ClientName | FormField::ProjectName | FormField::UserName => tr!("field-name"),
+            FormField::ClientAddress => tr!("field-address"),
+            FormField::ProjectClient => tr!("field-client"),
+            FormField::ProjectManager => tr!("field-manager"),
+            FormField::ProjectStartDate => tr!("field-start-date"),
+            FormField::ProjectEndDate => tr!("field-end-date"),
+            FormField::UserLogin => tr!("field-login"),
+            FormField::UserPassword => tr!("field-password"),
+            FormField::UserRole => tr!("field-role"),
+            FormField::SubmitButton => tr!("button-save"),
+            FormField::CancelButton => tr!("button-cancel"),
         }
     }
 
@@ -196,6 +372,15 @@ impl FormField {
         )
     }
 
+    /// Entities the searchable picker of this field lists (see `picker.rs`)
+    pub fn picker_entity(&self) -> Option<EntityType> {
+        match self {
+            FormField::ProjectClient => Some(EntityType::Client),
+            FormField::ProjectManager => Some(EntityType::User),
+            _ => None,
+        }
+    }
+
     /// Check if this is a button
     pub fn is_button(&self) -> bool {
         matches!(self, FormField::SubmitButton | FormField::CancelButton)
@@ -227,8 +412,8 @@ pub struct FormState {
     pub user_login: String,
     pub user_password: String,
     pub user_role: Role,
-    /// Whether dropdown is open
-    pub dropdown_open: bool,
+    /// Search list of the focused client or manager field, when open
+    pub picker: Option<Picker>,
 }
 
 impl FormState {
@@ -250,7 +435,7 @@ impl FormState {
             user_login: String::new(),
             user_password: String::new(),
             user_role: Role::Manager,
-            dropdown_open: false,
+            picker: None,
         }
     }
 
@@ -272,13 +457,13 @@ impl FormState {
             user_login: String::new(),
             user_password: String::new(),
             user_role: Role::Manager,
-            dropdown_open: false,
+            picker: None,
         }
     }
 
     /// Create a new project creation form
     pub fn new_create_project() -> Self {
-        let today = chrono::Local::now().date_naive();
+        let today = crate::clock::today();
         let end_date = today + chrono::Duration::days(30);
         Self {
             form_type: FormType::CreateProject,
@@ -296,7 +481,7 @@ impl FormState {
             user_login: String::new(),
             user_password: String::new(),
             user_role: Role::Manager,
-            dropdown_open: false,
+            picker: None,
         }
     }
 
@@ -320,10 +505,19 @@ impl FormState {
             user_login: String::new(),
             user_password: String::new(),
             user_role: Role::Manager,
-            dropdown_open: false,
+            picker: None,
         }
     }
 
+    /// Create a project creation form pre-filled from `project`, its dates moved by `shift`
+    pub fn new_clone_project(project: &ProjectDto, clients: &[ClientDto], users: &[UserDto], shift: chrono::Duration) -> Self {
+        let mut form = Self::new_edit_project(project, clients, users);
+        form.form_type = FormType::CreateProject;
+        form.project_start_date = (project.start_date + shift).format("%Y-%m-%d").to_string();
+        form.project_end_date = (project.planned_end_date + shift).format("%Y-%m-%d").to_string();
+        form
+    }
+
     /// Create a new user creation form
     pub fn new_create_user() -> Self {
         Self {
@@ -342,7 +536,7 @@ impl FormState {
             user_login: String::new(),
             user_password: String::new(),
             user_role: Role::Manager,
-            dropdown_open: false,
+            picker: None,
         }
     }
 
@@ -364,7 +558,7 @@ impl FormState {
             user_login: user.login.clone().unwrap_or_default(),
             user_password: String::new(),
             user_role: user.role,
-            dropdown_open: false,
+            picker: None,
         }
     }
 
@@ -376,13 +570,13 @@ impl FormState {
     /// Move to the next field
     pub fn next_field(&mut self) {
         self.focused_field = (self.focused_field + 1) % self.fields.len();
-        self.dropdown_open = false;
+        self.picker = None;
     }
 
     /// Move to the previous field
     pub fn prev_field(&mut self) {
         self.focused_field = self.focused_field.checked_sub(1).unwrap_or(self.fields.len() - 1);
-        self.dropdown_open = false;
+        self.picker = None;
     }
 
     /// Get mutable reference to the current text field (not date pickers or selectors)
@@ -414,42 +608,25 @@ impl FormState {
         }
     }
 
-    /// Increment the current date field by one day
-    pub fn increment_date(&mut self) {
-        match self.current_field() {
-            FormField::ProjectStartDate => {
-                self.project_start_date = Self::add_days_to_date_string(&self.project_start_date, 1);
-            }
-            FormField::ProjectEndDate => {
-                self.project_end_date = Self::add_days_to_date_string(&self.project_end_date, 1);
-            }
-            _ => {}
-        }
-    }
-
-    /// Decrement the current date field by one day
-    pub fn decrement_date(&mut self) {
-        match self.current_field() {
-            FormField::ProjectStartDate => {
-                self.project_start_date = Self::add_days_to_date_string(&self.project_start_date, -1);
-            }
-            FormField::ProjectEndDate => {
-                self.project_end_date = Self::add_days_to_date_string(&self.project_end_date, -1);
+    /// Move the calendar cursor of the focused date field (see `datepicker.rs`);
+    /// false when no date field is focused or the key does not move the cursor
+    pub fn move_date(&mut self, key: KeyCode) -> bool {
+        let text = match self.current_field() {
+            FormField::ProjectStartDate => &mut self.project_start_date,
+            FormField::ProjectEndDate => &mut self.project_end_date,
+            _ => return false,
+        };
+        // An unreadable date starts from today
+        let date = NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap_or_else(|_| crate::clock::today());
+        match datepicker::step(date, key) {
+            Some(moved) => {
+                *text = moved.format("%Y-%m-%d").to_string();
+                true
             }
-            _ => {}
+            None => false,
         }
     }
 
-    /// Add days to a date string in YYYY-MM-DD format
-    fn add_days_to_date_string(date_str: &str, days: i64) -> String {
-        NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-            .map(|d| (d + chrono::Duration::days(days)).format("%Y-%m-%d").to_string())
-            .unwrap_or_else(|_| {
-                // If parsing fails, use today's date
-                chrono::Local::now().date_naive().format("%Y-%m-%d").to_string()
-            })
-    }
-
     /// Build CreateClientDto from form state
     pub fn build_create_client(&self) -> CreateClientDto {
         CreateClientDto {
@@ -483,7 +660,7 @@ impl FormState {
         let client_id = clients.get(self.project_client_idx).map(|c| c.id).unwrap_or(Uuid::nil());
         let manager_id = users.get(self.project_manager_idx).map(|u| u.id).unwrap_or(Uuid::nil());
         let start_date = NaiveDate::parse_from_str(&self.project_start_date, "%Y-%m-%d")
-            .unwrap_or_else(|_| chrono::Local::now().date_naive());
+            .unwrap_or_else(|_| crate::clock::today());
         let end_date = NaiveDate::parse_from_str(&self.project_end_date, "%Y-%m-%d")
             .unwrap_or_else(|_| start_date + chrono::Duration::days(30));
 
@@ -502,7 +679,7 @@ impl FormState {
         let client_id = clients.get(self.project_client_idx).map(|c| c.id).unwrap_or(Uuid::nil());
         let manager_id = users.get(self.project_manager_idx).map(|u| u.id).unwrap_or(Uuid::nil());
         let start_date = NaiveDate::parse_from_str(&self.project_start_date, "%Y-%m-%d")
-            .unwrap_or_else(|_| chrono::Local::now().date_naive());
+            .unwrap_or_else(|_| crate::clock::today());
         let end_date = NaiveDate::parse_from_str(&self.project_end_date, "%Y-%m-%d")
             .unwrap_or_else(|_| start_date + chrono::Duration::days(30));
 
@@ -541,6 +718,28 @@ impl FormState {
     }
 }
 
+/// Action performed when a confirmation dialog is accepted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmAction {
+    /// Delete an entity
+    Delete(EntityType, Uuid),
+    /// Commit all sandbox schedule changes
+    CommitSandbox,
+    /// Shift the marked projects by the given number of days
+    Reschedule(i64),
+    /// Quit although work is still pending
+    Quit,
+    /// Undo (or, when true, redo) the latest change on a production backend
+    UndoRedo(bool),
+}
+
+impl ConfirmAction {
+    /// Whether the action changes data on the backend
+    pub fn is_destructive(self) -> bool {
+        !matches!(self, ConfirmAction::Quit)
+    }
+}
+
 /// Confirmation dialog state
 #[derive(Debug, Clone)]
 pub struct ConfirmDialog {
@@ -548,22 +747,121 @@ pub struct ConfirmDialog {
     pub title: String,
     /// Message to display
     pub message: String,
-    /// Entity type being deleted
-    pub entity_type: EntityType,
-    /// Entity ID being deleted
-    pub entity_id: Uuid,
+    /// Action to perform on "Yes"
+    pub action: ConfirmAction,
     /// Whether "Yes" is focused (false = "No" is focused)
     pub yes_focused: bool,
+    /// Lines listing what accepting will change
+    pub preview: Vec<String>,
+    /// Name that must be typed to accept (production profiles), instead of yes/no
+    pub confirm_word: Option<String>,
+    /// What was typed so far
+    pub typed: String,
 }
 
 impl ConfirmDialog {
     pub fn new_delete(entity_type: EntityType, entity_id: Uuid, name: &str) -> Self {
         Self {
-            title: format!("Delete {}", entity_type),
-            message: format!("Are you sure you want to delete \"{}\"?\nThis action cannot be undone.", name),
-            entity_type,
-            entity_id,
+            title: tr!("confirm-delete-title", entity = entity_type),
+            message: tr!("confirm-delete-message", name = name),
+            action: ConfirmAction::Delete(entity_type, entity_id),
+            yes_focused: false,
+            preview: Vec::new(),
+            confirm_word: None,
+            typed: String::new(),
+        }
+    }
+
+    pub fn new_commit_sandbox(changes: usize) -> Self {
+        Self {
+            title: tr!("confirm-commit-title").to_string(),
+            message: tr!("confirm-commit-message", count = changes),
+            action: ConfirmAction::CommitSandbox,
+            yes_focused: false,
+            preview: Vec::new(),
+            confirm_word: None,
+            typed: String::new(),
+        }
+    }
+
+    /// Ask before quitting; `pending` lists what would be lost or left unfinished
+    pub fn new_quit(pending: Vec<String>) -> Self {
+        Self {
+            title: tr!("confirm-quit-title").to_string(),
+            message: tr!("confirm-quit-message").to_string(),
+            action: ConfirmAction::Quit,
+            yes_focused: false,
+            preview: pending,
+            confirm_word: None,
+            typed: String::new(),
+        }
+    }
+
+    pub fn new_reschedule(days: i64, preview: Vec<String>) -> Self {
+        Self {
+            title: tr!("confirm-reschedule-title").to_string(),
+            message: tr!("confirm-reschedule-message", count = preview.len(), days = days),
+            action: ConfirmAction::Reschedule(days),
             yes_focused: false,
+            preview,
+            confirm_word: None,
+            typed: String::new(),
+        }
+    }
+
+    pub fn new_undo_redo(redo: bool, label: String) -> Self {
+        let (title, message) = if redo {
+            (tr!("confirm-redo-title"), tr!("confirm-redo-message", label = label))
+        } else {
+            (tr!("confirm-undo-title"), tr!("confirm-undo-message", label = label))
+        };
+        Self {
+            title: title.to_string(),
+            message,
+            action: ConfirmAction::UndoRedo(redo),
+            yes_focused: false,
+            preview: Vec::new(),
+            confirm_word: None,
+            typed: String::new(),
+        }
+    }
+
+    /// Whether what was typed matches the required name (always when none is required)
+    pub fn word_matches(&self) -> bool {
+        self.confirm_word.as_deref().is_none_or(|word| self.typed == word)
+    }
+}
+
+/// What a text prompt's input is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    /// Name for the timeline bookmark in the given slot
+    BookmarkName(u8),
+    /// Filter expression for an entity type (empty = none)
+    Filter(EntityType),
+    /// Server-side search of the audit log (empty = everything)
+    AuditSearch,
+    /// Days to shift the marked projects by
+    Reschedule,
+}
+
+/// Single-line text prompt state
+#[derive(Debug, Clone)]
+pub struct Prompt {
+    /// Title of the prompt
+    pub title: String,
+    /// Current input text
+    pub input: String,
+    /// What the input will be used for
+    pub kind: PromptKind,
+}
+
+impl Prompt {
+    pub fn new(title: impl Into<String>, input: impl Into<String>, kind: PromptKind) -> Self {
+        Self {
+            title: title.into(),
+            input: input.into(),
+            kind,
         }
     }
 }
@@ -650,6 +948,15 @@ impl LogEntry {
     }
 }
 
+/// Rows as loaded, before any filter: the lookup tables for names, pickers and
+/// search, and what the filtered views are built from
+#[derive(Debug, Default)]
+pub struct Loaded {
+    pub projects: Vec<ProjectDto>,
+    pub clients: Vec<ClientDto>,
+    pub users: Vec<UserDto>,
+}
+
 /// Main application state
 #[derive(Debug)]
 pub struct App {
@@ -662,15 +969,18 @@ pub struct App {
     /// Current input mode
     pub input_mode: InputMode,
 
-    /// Projects data
+    /// Projects shown, after the filters
     pub projects: Vec<ProjectDto>,
 
-    /// Clients data
+    /// Clients shown, after the filters
     pub clients: Vec<ClientDto>,
 
-    /// Users data
+    /// Users shown, after the filters
     pub users: Vec<UserDto>,
 
+    /// Everything loaded, unfiltered
+    pub loaded: Loaded,
+
     /// Timeline widget state
     pub timeline_state: TimelineState,
 
@@ -680,12 +990,214 @@ pub struct App {
     /// Current error popup (if any)
     pub error_popup: Option<ErrorPopup>,
 
+    /// Notices about watched entities, oldest first
+    pub toasts: Vec<Toast>,
+
+    /// Users tab grouped by team (`v`)
+    pub team_view: Option<TeamView>,
+
     /// Current form state (if any)
     pub form_state: Option<FormState>,
 
     /// Current confirm dialog (if any)
     pub confirm_dialog: Option<ConfirmDialog>,
 
+    /// Current text prompt (if any)
+    pub prompt: Option<Prompt>,
+
+    /// UI state persisted across sessions (bookmarks, ...)
+    pub ui_state: UiState,
+
+    /// Waiting for a slot digit after `m` (save bookmark)
+    pub pending_bookmark: bool,
+
+    /// Timeline comparison mode (two stacked, filtered timelines)
+    pub comparison: Option<Comparison>,
+
+    /// Burndown chart popup for a client or manager (`b` in Clients/Users)
+    pub burndown: Option<Burndown>,
+
+    /// Login of the person using the TUI (`--user` or the profile's `user`)
+    pub me: Option<String>,
+
+    /// Show only projects managed by `me` (F2)
+    pub my_projects: bool,
+
+    /// Archived projects left out of the latest load (while hiding them)
+    pub archived_hidden: usize,
+
+    /// Number of months shown in the Stats tab
+    pub stats_months: u32,
+
+    /// Months the Stats range ends before the current month (0 or negative)
+    pub stats_offset: i32,
+
+    /// Page shown in the Stats tab (`v` switches)
+    pub stats_view: StatsView,
+
+    /// Client ranking order: column and whether it is reversed
+    pub ranking_sort: (RankingColumn, bool),
+
+    /// Daily points of the Stats history page and the range they cover
+    pub history_points: Option<((NaiveDate, NaiveDate), Vec<DailyPoint>)>,
+
+    /// Range of the history points asked of the worker and not back yet
+    history_requested: Option<(NaiveDate, NaiveDate)>,
+
+    /// Time-travel reference date (None = today)
+    pub as_of: Option<NaiveDate>,
+
+    /// What-if schedule sandbox (local edits, no API calls)
+    pub sandbox: Option<Sandbox>,
+
+    /// Projects marked for batch actions (`Space`)
+    pub marked: BTreeSet<Uuid>,
+
+    /// User configuration (config.toml)
+    pub config: Config,
+
+    /// Undo/redo history of API mutations
+    pub history: History,
+
+    /// Trigram index of the loaded entities for the global search
+    pub search_index: SearchIndex,
+
+    /// Global search popup (`Ctrl+f`)
+    pub search: Option<Search>,
+
+    /// Local snapshot history (`None` when disabled or unavailable)
+    pub store: Option<SnapshotStore>,
+
+    /// When the shown data was last current, if it comes from the snapshot
+    /// history (offline startup, time travel) rather than the API
+    pub recorded_at: Option<DateTime<Utc>>,
+
+    /// Loads are being replayed from the snapshot history
+    loading_recorded: bool,
+
+    /// Registered plugin tabs (shown after the built-in tabs)
+    pub plugins: Vec<Box<dyn TabPlugin>>,
+
+    /// User script (computed columns, row filters, key macros)
+    pub scripts: Option<Scripts>,
+
+    /// Extra commands produced by key macros, sent after the current key
+    pub pending_commands: Vec<ApiCommand>,
+
+    /// Row filters set over the remote-control socket (applied when data loads)
+    pub filters: Vec<(EntityType, Filter)>,
+
+    /// Filter expressions set with `/` (applied when data loads)
+    pub expressions: Vec<(EntityType, FilterExpr)>,
+
+    /// Deadline crossings already alerted
+    pub deadlines: DeadlineWatch,
+    /// Previous refresh for the `[[alerts]]` rules
+    pub rule_watch: RuleWatch,
+    /// SLA breaches already posted to the `[webhook]`
+    pub breach_watch: BreachWatch,
+
+    /// Alerts waiting to be shown as desktop notifications
+    pub pending_notifications: Vec<Alert>,
+
+    /// Text waiting to be copied to the clipboard
+    pub pending_clipboard: Option<String>,
+
+    /// Entity to select once projects have loaded (`--focus`)
+    pub pending_focus: Option<(EntityType, Uuid)>,
+
+    /// Places visited, for `Ctrl+o` / `Ctrl+i`
+    pub jumps: JumpList<Place>,
+
+    /// What the latest loads changed
+    pub changes: ChangeReport,
+
+    /// Cells highlighted because a load changed them
+    pub flash: CellFlash,
+
+    /// Retry schedule while the API is unreachable
+    pub reconnect: Backoff,
+
+    /// Open sort dialog: list tab and highlighted column
+    pub sort_dialog: Option<(Tab, usize)>,
+
+    /// Open column dialog: list tab and highlighted column
+    pub column_dialog: Option<(Tab, usize)>,
+
+    /// Open settings dialog (`,`)
+    pub settings: Option<SettingsDialog>,
+
+    /// Open context menu of the selected row
+    pub context_menu: Option<ContextMenu>,
+
+    /// Pages of the Clients and Users lists loaded so far (`[api] list_page_size`)
+    pub client_pages: ListPages<ClientDto>,
+    pub user_pages: ListPages<UserDto>,
+    /// Prefetched documents and pages, until they are asked for
+    pub prefetched: PrefetchCache,
+    /// Prefetch jobs last sent to the worker
+    pub prefetch_queued: Vec<(PrefetchJob, u8)>,
+
+    /// Scroll position of the open "What changed?" view
+    pub changes_view: Option<u16>,
+
+    /// Open raw JSON inspector
+    pub inspector: Option<Inspector>,
+
+    /// Open documents popup of a project
+    pub attachments: Option<Attachments>,
+
+    /// Document download in progress
+    pub download: Option<DownloadProgress>,
+
+    /// Time-travel date picker popup
+    pub date_picker: Option<DatePicker>,
+
+    /// Column of the cell cursor in the Projects table
+    pub table_column: Column,
+
+    /// Projects table cell being edited in place
+    pub inline_edit: Option<InlineEdit>,
+
+    /// Writes sent and queued in all while quitting (shown on the shutdown screen)
+    pub draining: Option<(usize, usize)>,
+
+    /// Audit log tab state
+    pub audit: AuditLog,
+
+    /// API console tab state
+    pub console: Console,
+
+    /// Tab to return to when leaving the console
+    console_return: Tab,
+
+    /// Whether the debug overlay (FPS etc.) is shown
+    pub show_debug: bool,
+
+    /// Whether the terminal has focus (animations pause when it does not)
+    pub focused: bool,
+
+    /// What changed since the last draw
+    pub dirty: Dirty,
+
+    /// Last key press or API result (timeline animations stop when idle)
+    pub last_activity: Instant,
+
+    /// Status bar text as of the last tick
+    last_status: String,
+
+    /// Frame-rate statistics from the render loop
+    pub frame_stats: FrameStats,
+
+    /// Memory footprint for the telemetry readout
+    pub telemetry: Telemetry,
+
+    /// Last known terminal size (for placing event effects)
+    pub screen_size: (u16, u16),
+
+    /// Debounces terminal resizes before the particle field is rebuilt
+    pub resize: ResizeDebounce,
+
     /// Log messages
     pub logs: Vec<LogEntry>,
     /// Maximum number of log entries to keep
@@ -697,6 +1209,21 @@ pub struct App {
     /// API connection status
     pub api_connected: bool,
 
+    /// Backends of an aggregated source and whether they answered (empty
+    /// without `[api] aggregate`)
+    pub backends: Vec<BackendStatus>,
+
+    /// Base URLs the connection diagnosis checks (empty in demo mode and replays)
+    pub api_urls: Vec<String>,
+    /// `[api]` connection settings the diagnosis' GET uses
+    pub api_connection: ConnectionOptions,
+    /// Name(s) of the production profile(s) connected to; shows the banner
+    /// and makes destructive actions ask for the name to be typed
+    pub production: Option<String>,
+
+    /// Open connection diagnosis (`Ctrl+d`); empty while it runs
+    pub diagnosis: Option<Vec<Diagnosis>>,
+
     /// Last data refresh time
     pub last_refresh: Option<Instant>,
 
@@ -726,28 +1253,109 @@ impl App {
             projects: Vec::new(),
             clients: Vec::new(),
             users: Vec::new(),
+            loaded: Loaded::default(),
             timeline_state: TimelineState::default(),
             particle_system: ParticleSystem::default(),
             error_popup: None,
+            toasts: Vec::new(),
+            team_view: None,
             form_state: None,
             confirm_dialog: None,
+            prompt: None,
+            ui_state: UiState::default(),
+            pending_bookmark: false,
+            comparison: None,
+            burndown: None,
+            me: None,
+            my_projects: false,
+            archived_hidden: 0,
+            stats_months: 12,
+            stats_offset: 0,
+            stats_view: StatsView::default(),
+            history_points: None,
+            history_requested: None,
+            ranking_sort: (RankingColumn::default(), false),
+            as_of: None,
+            sandbox: None,
+            marked: BTreeSet::new(),
+            config: Config::default(),
+            plugins: Vec::new(),
+            history: History::default(),
+            search_index: SearchIndex::default(),
+            search: None,
+            store: None,
+            recorded_at: None,
+            loading_recorded: false,
+            scripts: None,
+            pending_commands: Vec::new(),
+            filters: Vec::new(),
+            expressions: Vec::new(),
+            deadlines: DeadlineWatch::default(),
+            rule_watch: RuleWatch::default(),
+            breach_watch: BreachWatch::default(),
+            pending_notifications: Vec::new(),
+            pending_clipboard: None,
+            pending_focus: None,
+            jumps: JumpList::default(),
+            changes: ChangeReport::default(),
+            flash: CellFlash::default(),
+            reconnect: Backoff::default(),
+            sort_dialog: None,
+            column_dialog: None,
+            settings: None,
+            context_menu: None,
+            client_pages: ListPages::default(),
+            user_pages: ListPages::default(),
+            prefetched: PrefetchCache::default(),
+            prefetch_queued: Vec::new(),
+            changes_view: None,
+            inspector: None,
+            attachments: None,
+            download: None,
+            date_picker: None,
+            table_column: Column::Name,
+            inline_edit: None,
+            draining: None,
+            audit: AuditLog::default(),
+            console: Console::default(),
+            console_return: Tab::default(),
+            show_debug: false,
+            focused: true,
+            frame_stats: FrameStats::default(),
+            telemetry: Telemetry::default(),
+            screen_size: (0, 0),
+            resize: ResizeDebounce::default(),
+            dirty: Dirty::default(),
+            last_activity: Instant::now(),
+            last_status: String::new(),
             logs: Vec::new(),
             max_logs: 100,
             list_selected: 0,
             api_connected: false,
+            backends: Vec::new(),
+            api_urls: Vec::new(),
+            api_connection: ConnectionOptions::default(),
+            production: None,
+            diagnosis: None,
             last_refresh: None,
             is_loading: true,
             frame_count: 0,
             show_help: false,
         };
 
-        app.log(LogEntry::info("SWEeM TUI initialized"));
-        app.log(LogEntry::info("Connecting to API..."));
+        app.log(LogEntry::info(tr!("log-initialized")));
+        app.log(LogEntry::info(tr!("log-connecting")));
         app
     }
 
     /// Add a log entry
     pub fn log(&mut self, entry: LogEntry) {
+        // Mirror the on-screen log into the file log
+        match entry.level {
+            LogLevel::Info | LogLevel::Success => tracing::info!("{}", entry.message),
+            LogLevel::Warning => tracing::warn!("{}", entry.message),
+            LogLevel::Error => tracing::error!("{}", entry.message),
+        }
         self.logs.push(entry);
         if self.logs.len() > self.max_logs {
             self.logs.remove(0);
@@ -771,8 +1379,9 @@ impl App {
     pub fn open_create_form(&mut self) {
         let form = match self.active_tab {
             Tab::Clients => FormState::new_create_client(),
-            Tab::Timeline => FormState::new_create_project(),
+            Tab::Timeline | Tab::Projects => FormState::new_create_project(),
             Tab::Users => FormState::new_create_user(),
+            Tab::Stats | Tab::Inbox | Tab::Audit | Tab::Console | Tab::Plugin(_) => return,
         };
         self.form_state = Some(form);
         self.input_mode = InputMode::Editing;
@@ -788,10 +1397,13 @@ impl App {
                     None
                 }
             }
-            Tab::Timeline => {
+            Tab::Timeline | Tab::Projects => {
+                if self.blocked_by_sandbox() {
+                    return;
+                }
                 if let Some(idx) = self.timeline_state.selected_project {
                     if let Some(project) = self.projects.get(idx) {
-                        Some(FormState::new_edit_project(project, &self.clients, &self.users))
+                        Some(FormState::new_edit_project(project, &self.loaded.clients, &self.loaded.users))
                     } else {
                         None
                     }
@@ -800,12 +1412,9 @@ impl App {
                 }
             }
             Tab::Users => {
-                if let Some(user) = self.users.get(self.list_selected) {
-                    Some(FormState::new_edit_user(user))
-                } else {
-                    None
-                }
+                self.selected_user().map(FormState::new_edit_user)
             }
+            Tab::Stats | Tab::Inbox | Tab::Audit | Tab::Console | Tab::Plugin(_) => None,
         };
 
         if let Some(form) = form {
@@ -814,158 +1423,2199 @@ impl App {
         }
     }
 
-    /// Open delete confirmation dialog
-    pub fn open_delete_confirm(&mut self) {
-        let dialog = match self.active_tab {
-            Tab::Clients => {
-                if let Some(client) = self.clients.get(self.list_selected) {
-                    Some(ConfirmDialog::new_delete(
-                        EntityType::Client,
-                        client.id,
-                        client.display_name(),
-                    ))
-                } else {
-                    None
-                }
-            }
-            Tab::Timeline => {
-                if let Some(idx) = self.timeline_state.selected_project {
-                    if let Some(project) = self.projects.get(idx) {
-                        Some(ConfirmDialog::new_delete(
-                            EntityType::Project,
-                            project.id,
-                            project.display_name(),
-                        ))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            Tab::Users => {
-                if let Some(user) = self.users.get(self.list_selected) {
-                    Some(ConfirmDialog::new_delete(
-                        EntityType::User,
-                        user.id,
-                        user.display_name(),
-                    ))
-                } else {
-                    None
-                }
-            }
+    /// Open the create form pre-filled from the selected project; the copy's dates
+    /// move by `[ui] clone_offset_days`, or so that it starts today
+    fn open_clone_form(&mut self) {
+        if self.blocked_by_sandbox() {
+            return;
+        }
+        let Some(project) = self.timeline_state.selected_project.and_then(|idx| self.projects.get(idx)) else {
+            self.log(LogEntry::warning(tr!("log-clone-nothing")));
+            return;
         };
+        let shift = match self.config.ui.clone_offset_days {
+            Some(days) => chrono::Duration::days(days),
+            None => crate::clock::today() - project.start_date,
+        };
+        let message = tr!("log-clone-form", name = project.display_name(), days = shift.num_days());
+        self.form_state = Some(FormState::new_clone_project(project, &self.loaded.clients, &self.loaded.users, shift));
+        self.input_mode = InputMode::Editing;
+        self.log(LogEntry::info(message));
+    }
 
-        if let Some(dialog) = dialog {
-            self.confirm_dialog = Some(dialog);
-            self.input_mode = InputMode::Confirming;
+    /// Mark or unmark the selected project for batch actions
+    fn toggle_mark(&mut self) {
+        let Some(id) = self.timeline_state.selected_project.and_then(|idx| self.projects.get(idx)).map(|p| p.id) else {
+            return;
+        };
+        if !self.marked.remove(&id) {
+            self.marked.insert(id);
         }
     }
 
-    /// Close the current form
-    pub fn close_form(&mut self) {
-        self.form_state = None;
-        self.input_mode = InputMode::Normal;
+    /// Marked projects that are still loaded, in list order
+    pub fn marked_projects(&self) -> Vec<&ProjectDto> {
+        self.projects.iter().filter(|p| self.marked.contains(&p.id)).collect()
     }
 
-    /// Close the confirm dialog
-    pub fn close_confirm(&mut self) {
-        self.confirm_dialog = None;
-        self.input_mode = InputMode::Normal;
+    /// Ask how many days to shift the marked projects by
+    fn open_reschedule_prompt(&mut self) {
+        let count = self.marked_projects().len();
+        if count == 0 {
+            self.log(LogEntry::warning(tr!("log-reschedule-nothing")));
+        } else if self.sandbox.is_some() {
+            // Sandbox edits are local; `X` commits them instead
+            self.log(LogEntry::warning(tr!("log-reschedule-sandbox")));
+        } else {
+            self.open_prompt(Prompt::new(tr!("prompt-reschedule", count = count), "", PromptKind::Reschedule));
+        }
     }
 
-    /// Handle API messages
-    pub fn handle_api_message(&mut self, message: ApiMessage) {
-        match message {
-            ApiMessage::ProjectsLoaded(projects) => {
-                let count = projects.len();
-                self.projects = projects;
-                self.is_loading = false;
-                self.last_refresh = Some(Instant::now());
-                self.log(LogEntry::success(format!("Loaded {} projects", count)));
-
-                // Auto-center timeline on first project or today when projects are loaded
-                if !self.projects.is_empty() {
-                    // Select first project if none selected
-                    if self.timeline_state.selected_project.is_none() {
-                        self.timeline_state.selected_project = Some(0);
-                    }
-                    // Jump to show the selected (or first) project
-                    self.auto_center_timeline();
-                }
-            }
-            ApiMessage::ClientsLoaded(clients) => {
-                let count = clients.len();
-                self.clients = clients;
-                self.log(LogEntry::success(format!("Loaded {} clients", count)));
-            }
-            ApiMessage::UsersLoaded(users) => {
-                let count = users.len();
-                self.users = users;
-                self.log(LogEntry::success(format!("Loaded {} users", count)));
-            }
-            ApiMessage::Error(error) => {
-                self.is_loading = false;
-                self.show_error("API Error", error);
+    /// The marked projects with their start and planned end moved by `days`
+    fn rescheduled(&self, days: i64) -> Vec<(&ProjectDto, ProjectDto)> {
+        self.marked_projects()
+            .into_iter()
+            .map(|project| {
+                let mut moved = project.clone();
+                Sandbox::shift(&mut moved, days);
+                (project, moved)
+            })
+            .collect()
+    }
+
+    /// Show the new dates of the marked projects before anything is sent
+    fn preview_reschedule(&mut self, input: &str) {
+        let days = match input.strip_prefix('+').unwrap_or(input).parse::<i64>() {
+            Ok(days) if days != 0 => days,
+            _ => {
+                self.log(LogEntry::warning(tr!("log-reschedule-invalid", input = input)));
+                return;
             }
-            ApiMessage::ConnectionStatus(connected) => {
-                let was_connected = self.api_connected;
+        };
+        let preview = self
+            .rescheduled(days)
+            .iter()
+            .map(|(project, moved)| {
+                format!(
+                    "{}: {}..{} → {}..{}",
+                    project.display_name(),
+                    project.start_date,
+                    project.planned_end_date,
+                    moved.start_date,
+                    moved.planned_end_date
+                )
+            })
+            .collect();
+        self.open_confirm(ConfirmDialog::new_reschedule(days, preview));
+    }
+
+    /// Type and id of the selected project, client or user
+    fn selected_entity(&self) -> Option<(EntityType, Uuid)> {
+        match self.active_tab {
+            Tab::Timeline | Tab::Projects => self
+                .timeline_state
+                .selected_project
+                .and_then(|idx| self.projects.get(idx))
+                .map(|p| (EntityType::Project, p.id)),
+            Tab::Clients => self.clients.get(self.list_selected).map(|c| (EntityType::Client, c.id)),
+            Tab::Users => self.selected_user().map(|u| (EntityType::User, u.id)),
+            Tab::Stats | Tab::Inbox | Tab::Audit | Tab::Console | Tab::Plugin(_) => None,
+        }
+    }
+
+    /// Open the JSON inspector on the selected entity
+    fn open_inspector(&mut self) {
+        match self.selected_entity() {
+            Some((entity_type, id)) => self.inspector = Some(Inspector { entity_type, id, scroll: 0 }),
+            None => self.log(LogEntry::warning(tr!("log-inspector-nothing"))),
+        }
+    }
+
+    /// Open the documents popup of the selected project and ask for its documents
+    fn open_attachments(&mut self) -> Option<ApiCommand> {
+        let project = self.timeline_state.selected_project.and_then(|idx| self.projects.get(idx));
+        let Some(project) = project.filter(|_| matches!(self.active_tab, Tab::Timeline | Tab::Projects)) else {
+            self.log(LogEntry::warning(tr!("log-attachments-nothing")));
+            return None;
+        };
+        let project_id = project.id;
+        let prefetched = self.prefetched.take_documents(project_id);
+        self.attachments = Some(Attachments {
+            project_id,
+            label: project.display_name().to_string(),
+            documents: prefetched.clone().map_or(DocumentList::Loading, DocumentList::Loaded),
+            selected: 0,
+        });
+        prefetched.is_none().then_some(ApiCommand::FetchDocuments(project_id))
+    }
+
+    /// Keys of the documents popup
+    fn handle_attachments_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
+        let attachments = self.attachments.as_mut()?;
+        let count = match &attachments.documents {
+            DocumentList::Loaded(documents) => documents.len(),
+            _ => 0,
+        };
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down if attachments.selected + 1 < count => attachments.selected += 1,
+            KeyCode::Char('k') | KeyCode::Up => attachments.selected = attachments.selected.saturating_sub(1),
+            KeyCode::Enter => {
+                let document = attachments.selected_document()?.clone();
+                let project_id = attachments.project_id;
+                if let Some(running) = &self.download {
+                    self.log(LogEntry::warning(tr!("log-download-busy", name = running.file_name.clone())));
+                    return None;
+                }
+                self.download = Some(DownloadProgress {
+                    document_id: document.id,
+                    file_name: document.file_name.clone(),
+                    received: 0,
+                    total: document.size,
+                });
+                let dir = self.config.attachments.download_dir();
+                return Some(ApiCommand::DownloadDocument { project_id, document, dir });
+            }
+            KeyCode::Esc | KeyCode::Char('A') | KeyCode::Char('q') => self.attachments = None,
+            _ => {}
+        }
+        None
+    }
+
+    /// JSON of the inspected entity as received and the fields the models do not know
+    pub fn inspected(&self) -> Option<(Value, Vec<String>)> {
+        let inspector = self.inspector?;
+        let (value, extra) = match inspector.entity_type {
+            EntityType::Project => self.projects.iter().find(|p| p.id == inspector.id).map(|p| (expr::row(p), &p.extra)),
+            EntityType::Client => self.clients.iter().find(|c| c.id == inspector.id).map(|c| (expr::row(c), &c.extra)),
+            EntityType::User => self.users.iter().find(|u| u.id == inspector.id).map(|u| (expr::row(u), &u.extra)),
+        }?;
+        // The backend tag of merged backends is not schema drift
+        Some((value, extra.keys().filter(|key| *key != SOURCE_FIELD).cloned().collect()))
+    }
+
+    /// Copy the selected entity's UUID, or a command that opens the TUI focused on it
+    fn copy_selected(&mut self, command: bool) {
+        let Some((entity_type, id)) = self.selected_entity() else {
+            self.log(LogEntry::warning(tr!("log-copy-nothing")));
+            return;
+        };
+        let text = if command { clipboard::focus_command(entity_type, id) } else { id.to_string() };
+        self.log(LogEntry::info(tr!("log-copied", text = text.as_str())));
+        self.pending_clipboard = Some(text);
+    }
+
+    /// Sort spec and columns of a list tab
+    pub fn sort_spec(&self, tab: Tab) -> (&[SortKey], &'static [SortColumn]) {
+        match tab {
+            Tab::Users => (&self.ui_state.user_sort, &SortColumn::USERS),
+            Tab::Clients => (&self.ui_state.client_sort, &SortColumn::CLIENTS),
+            _ => (&[], &[]),
+        }
+    }
+
+    /// Apply the sort specs to the Clients and Users lists (favorites stay on top),
+    /// keeping the selection on the same entity
+    fn sort_lists(&mut self) {
+        let selected = self.selected_entity();
+        let as_of = self.reference_date();
+        sort::sort_clients(&mut self.clients, &self.ui_state.client_sort, &self.loaded.projects, as_of);
+        pin_favorites(&self.ui_state.favorites, &mut self.clients, |c| c.id);
+        sort::sort_users(&mut self.users, &self.ui_state.user_sort);
+        let index = match selected {
+            Some((EntityType::Client, id)) => self.clients.iter().position(|c| c.id == id),
+            Some((EntityType::User, id)) => self.users.iter().position(|u| u.id == id),
+            _ => None,
+        };
+        if let Some(index) = index {
+            self.list_selected = index;
+        }
+    }
+
+    /// Edit the sort spec in the sort dialog; changes apply right away and persist on close
+    fn handle_sort_key(&mut self, key: KeyEvent) {
+        let Some((tab, selected)) = self.sort_dialog else {
+            return;
+        };
+        let columns = self.sort_spec(tab).1;
+        let spec = match tab {
+            Tab::Users => &mut self.ui_state.user_sort,
+            _ => &mut self.ui_state.client_sort,
+        };
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.sort_dialog = Some((tab, (selected + 1) % columns.len())),
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.sort_dialog = Some((tab, selected.checked_sub(1).unwrap_or(columns.len() - 1)));
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => sort::toggle(spec, columns[selected]),
+            KeyCode::Char('c') => spec.clear(),
+            KeyCode::Esc | KeyCode::Char('s') | KeyCode::Char('q') => {
+                self.sort_dialog = None;
+                if let Err(e) = self.ui_state.save() {
+                    self.log(LogEntry::warning(tr!("log-sort-persist-failed", error = e)));
+                }
+                return;
+            }
+            _ => return,
+        }
+        self.sort_lists();
+    }
+
+    /// Column layout of a list tab: as set in the column dialog, else as
+    /// configured; the source column only exists while backends are merged
+    pub fn column_layout(&self, tab: Tab) -> Option<ColumnLayout> {
+        let entity = tab.entity_type().filter(|_| tab != Tab::Timeline)?;
+        let stored = match entity {
+            EntityType::Project => &self.ui_state.project_columns,
+            EntityType::Client => &self.ui_state.client_columns,
+            EntityType::User => &self.ui_state.user_columns,
+        };
+        let mut layout = match stored {
+            Some(layout) => layout.clone().normalized(entity),
+            None => self.config.columns.layout(entity),
+        };
+        if !self.aggregating() {
+            layout.order.retain(|c| *c != Column::Source);
+        }
+        Some(layout)
+    }
+
+    /// Shown columns of a list tab, in order
+    pub fn columns(&self, tab: Tab) -> Vec<Column> {
+        self.column_layout(tab).map(|layout| layout.shown()).unwrap_or_default()
+    }
+
+    /// Whether entities come from several backends (`[api] aggregate`)
+    pub fn aggregating(&self) -> bool {
+        !self.config.api.aggregate.is_empty()
+    }
+
+    /// Edit the column layout in the column dialog; changes apply right away and persist on close
+    fn handle_column_key(&mut self, key: KeyEvent) {
+        let Some((tab, selected)) = self.column_dialog else {
+            return;
+        };
+        let Some(mut layout) = self.column_layout(tab) else {
+            self.column_dialog = None;
+            return;
+        };
+        let count = layout.order.len();
+        let selected = match key.code {
+            KeyCode::Char('j') | KeyCode::Down => (selected + 1) % count,
+            KeyCode::Char('k') | KeyCode::Up => selected.checked_sub(1).unwrap_or(count - 1),
+            KeyCode::Char('J') | KeyCode::Char('K') => layout.shift(selected, key.code == KeyCode::Char('J')),
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                layout.toggle(layout.order[selected]);
+                selected
+            }
+            KeyCode::Char('r') => {
+                self.set_column_layout(tab, None);
+                self.column_dialog = Some((tab, selected));
+                return;
+            }
+            KeyCode::Esc | KeyCode::Char('C') | KeyCode::Char('q') => {
+                self.column_dialog = None;
+                if let Err(e) = self.ui_state.save() {
+                    self.log(LogEntry::warning(tr!("log-columns-persist-failed", error = e)));
+                }
+                return;
+            }
+            _ => return,
+        };
+        self.set_column_layout(tab, Some(layout));
+        self.column_dialog = Some((tab, selected));
+    }
+
+    /// Open the connection diagnosis and run its checks
+    fn diagnose(&mut self) -> Option<ApiCommand> {
+        if self.api_urls.is_empty() {
+            self.log(LogEntry::info(tr!("log-diagnose-unavailable")));
+            return None;
+        }
+        self.diagnosis = Some(Vec::new());
+        Some(ApiCommand::Diagnose(self.api_urls.clone(), self.api_connection))
+    }
+
+    /// Change settings in the settings dialog; changes apply right away and are saved on close
+    fn handle_settings_key(&mut self, key: KeyEvent) {
+        let Some(dialog) = &mut self.settings else {
+            return;
+        };
+        let count = Setting::ALL.len();
+        let forward = match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                dialog.selected = (dialog.selected + 1) % count;
+                return;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                dialog.selected = dialog.selected.checked_sub(1).unwrap_or(count - 1);
+                return;
+            }
+            KeyCode::Char('l') | KeyCode::Right | KeyCode::Char(' ') | KeyCode::Enter => true,
+            KeyCode::Char('h') | KeyCode::Left => false,
+            KeyCode::Esc | KeyCode::Char(',') | KeyCode::Char('q') => {
+                let changed = dialog.changed;
+                self.settings = None;
+                if changed && !self.ui_state.read_only {
+                    match self.config.save() {
+                        Ok(path) => self.log(LogEntry::info(tr!("log-settings-saved", path = path.display()))),
+                        Err(e) => self.log(LogEntry::warning(tr!("log-settings-persist-failed", error = format!("{:#}", e)))),
+                    }
+                }
+                return;
+            }
+            _ => return,
+        };
+        let setting = dialog.setting();
+        dialog.changed = true;
+        setting.step(&mut self.config, forward);
+        self.apply_setting(setting);
+    }
+
+    /// Make a changed setting take effect (the frame rate is picked up by `update`)
+    fn apply_setting(&mut self, setting: Setting) {
+        match setting {
+            Setting::Language => {
+                i18n::set_locale(self.config.ui.locale.or_else(Locale::from_env).unwrap_or_default());
+            }
+            Setting::Particles => self.particle_system.set_mode(settings::particle_mode(&self.config)),
+            Setting::Density => self.particle_system.set_density(self.config.particles.density),
+            Setting::Watch => {
+                let secs = self.config.api.watch_secs;
+                self.pending_commands.push(ApiCommand::SetWatch((secs > 0).then(|| Duration::from_secs(secs))));
+            }
+            Setting::Zoom => self.timeline_state.days_per_column = self.config.ui.timeline_zoom,
+            Setting::Fps | Setting::StaleAfter | Setting::StartTab | Setting::Notifications => {}
+        }
+    }
+
+    fn set_column_layout(&mut self, tab: Tab, layout: Option<ColumnLayout>) {
+        match tab.entity_type() {
+            Some(EntityType::Project) => self.ui_state.project_columns = layout,
+            Some(EntityType::Client) => self.ui_state.client_columns = layout,
+            Some(EntityType::User) => self.ui_state.user_columns = layout,
+            None => {}
+        }
+    }
+
+    /// Watch or stop watching the selected project or client
+    fn toggle_watch(&mut self) {
+        let (id, name) = match self.active_tab {
+            Tab::Timeline | Tab::Projects => match self.timeline_state.selected_project.and_then(|idx| self.projects.get(idx)) {
+                Some(p) => (p.id, p.display_name().to_string()),
+                None => return,
+            },
+            Tab::Clients => match self.clients.get(self.list_selected) {
+                Some(c) => (c.id, c.display_name().to_string()),
+                None => return,
+            },
+            Tab::Users | Tab::Stats | Tab::Inbox | Tab::Audit | Tab::Console | Tab::Plugin(_) => return,
+        };
+        if self.ui_state.watched.remove(&id) {
+            self.log(LogEntry::info(tr!("log-watch-removed", name = name)));
+        } else {
+            self.ui_state.watched.insert(id);
+            self.log(LogEntry::info(tr!("log-watch-added", name = name)));
+        }
+        if let Err(e) = self.ui_state.save() {
+            self.log(LogEntry::warning(tr!("log-watch-persist-failed", error = e)));
+        }
+    }
+
+    /// Star or unstar the selected project or client and re-pin its view
+    fn toggle_favorite(&mut self) {
+        let (id, name) = match self.active_tab {
+            Tab::Timeline | Tab::Projects => match self.timeline_state.selected_project.and_then(|idx| self.projects.get(idx)) {
+                Some(p) => (p.id, p.display_name().to_string()),
+                None => return,
+            },
+            Tab::Clients => match self.clients.get(self.list_selected) {
+                Some(c) => (c.id, c.display_name().to_string()),
+                None => return,
+            },
+            Tab::Users | Tab::Stats | Tab::Inbox | Tab::Audit | Tab::Console | Tab::Plugin(_) => return,
+        };
+        if self.ui_state.favorites.remove(&id) {
+            self.log(LogEntry::info(tr!("log-favorite-removed", name = name)));
+        } else {
+            self.ui_state.favorites.insert(id);
+            self.log(LogEntry::info(tr!("log-favorite-added", name = name)));
+        }
+        if let Err(e) = self.ui_state.save() {
+            self.log(LogEntry::warning(tr!("log-favorite-persist-failed", error = e)));
+        }
+
+        // The selection stays on the same entity
+        match self.active_tab {
+            Tab::Timeline | Tab::Projects => {
+                pin_favorites(&self.ui_state.favorites, &mut self.projects, |p| p.id);
+                self.timeline_state.selected_project = self.projects.iter().position(|p| p.id == id);
+            }
+            _ => {
+                self.sort_lists();
+                self.list_selected = self.clients.iter().position(|c| c.id == id).unwrap_or(0);
+            }
+        }
+    }
+
+    /// Whether a form, dialog or popup is over the current view
+    pub fn popup_open(&self) -> bool {
+        self.form_state.is_some()
+            || self.confirm_dialog.is_some()
+            || self.prompt.is_some()
+            || self.burndown.is_some()
+            || self.changes_view.is_some()
+            || self.sort_dialog.is_some()
+            || self.column_dialog.is_some()
+            || self.settings.is_some()
+            || self.diagnosis.is_some()
+            || self.context_menu.is_some()
+            || self.inspector.is_some()
+            || self.search.is_some()
+            || self.attachments.is_some()
+            || self.date_picker.is_some()
+            || self.inline_edit.is_some()
+            || self.error_popup.is_some()
+            || self.show_help
+    }
+
+    /// Open the context menu of the selected row (right-click outside of popups)
+    pub fn open_context_menu(&mut self) {
+        let Some((entity_type, id)) = self.selected_entity() else {
+            self.log(LogEntry::warning(tr!("log-menu-nothing")));
+            return;
+        };
+        let as_of = self.reference_date();
+        let (title, completed) = match entity_type {
+            EntityType::Project => match self.projects.iter().find(|p| p.id == id) {
+                Some(p) => (p.display_name().to_string(), p.is_completed_as_of(as_of)),
+                None => return,
+            },
+            EntityType::Client => match self.clients.iter().find(|c| c.id == id) {
+                Some(c) => (c.display_name().to_string(), false),
+                None => return,
+            },
+            EntityType::User => match self.users.iter().find(|u| u.id == id) {
+                Some(u) => (u.display_name().to_string(), false),
+                None => return,
+            },
+        };
+        self.context_menu = Some(ContextMenu::new(entity_type, id, title, completed));
+    }
+
+    /// Keys of the context menu
+    fn handle_context_menu_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
+        let menu = self.context_menu.as_mut()?;
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => menu.select(true),
+            KeyCode::Char('k') | KeyCode::Up => menu.select(false),
+            KeyCode::Enter => {
+                let action = menu.action();
+                self.context_menu = None;
+                return self.run_menu_action(action);
+            }
+            KeyCode::Esc | KeyCode::Char('.') | KeyCode::Char('q') => self.context_menu = None,
+            _ => {}
+        }
+        None
+    }
+
+    /// Do what a context menu entry says to the selected row
+    fn run_menu_action(&mut self, action: MenuAction) -> Option<ApiCommand> {
+        match action {
+            MenuAction::Details => self.open_inspector(),
+            MenuAction::CopyId => self.copy_selected(false),
+            MenuAction::Edit => self.open_edit_form(),
+            MenuAction::Clone => self.open_clone_form(),
+            MenuAction::Complete => return self.complete_selected_project(),
+            MenuAction::FilterByClient => {
+                let client_id = match self.selected_entity()? {
+                    (EntityType::Project, id) => self.projects.iter().find(|p| p.id == id)?.client_id,
+                    (_, id) => id,
+                };
+                let client = self.loaded.clients.iter().find(|c| c.id == client_id)?.display_name().to_string();
+                self.filter_projects_by("client", &client);
+            }
+            MenuAction::FilterByManager => {
+                let user = self.selected_user()?.display_name().to_string();
+                self.filter_projects_by("manager", &user);
+            }
+            MenuAction::Documents => return self.open_attachments(),
+            MenuAction::Burndown => self.open_burndown(),
+            MenuAction::Favorite => self.toggle_favorite(),
+            MenuAction::Watch => self.toggle_watch(),
+            MenuAction::Delete => self.open_delete_confirm(),
+        }
+        None
+    }
+
+    /// Mark the selected project completed today
+    fn complete_selected_project(&mut self) -> Option<ApiCommand> {
+        if self.blocked_by_sandbox() {
+            return None;
+        }
+        let project = self.timeline_state.selected_project.and_then(|idx| self.projects.get(idx))?;
+        let today = crate::clock::today();
+        if today < project.start_date {
+            let message = tr!("log-complete-not-started", name = project.display_name(), date = project.start_date.to_string());
+            self.log(LogEntry::warning(message));
+            return None;
+        }
+        let (id, before) = (project.id, UpdateProjectDto::from_project(project));
+        let mut after = before.clone();
+        after.actual_end_date = Some(today);
+        if let Err(e) = after.validate() {
+            self.log(LogEntry::warning(tr!("log-complete-invalid", error = e)));
+            return None;
+        }
+        self.log(LogEntry::info(tr!("log-updating-project")));
+        let operation = Operation::UpdateProject { id, before, after: after.clone() };
+        Some(self.history.begin(operation, Direction::Do, ApiCommand::UpdateProject(id, after)))
+    }
+
+    /// The highlighted user; none while a team row of the team view is highlighted
+    pub fn selected_user(&self) -> Option<&UserDto> {
+        if let Some(view) = &self.team_view {
+            if !matches!(self.team_rows().get(view.cursor), Some(TreeRow::User(_))) {
+                return None;
+            }
+        }
+        self.users.get(self.list_selected)
+    }
+
+    /// Rows of the Users tab's team view
+    pub fn team_rows(&self) -> Vec<TreeRow> {
+        let collapsed = self.team_view.as_ref().map(|v| &v.collapsed);
+        teams::rows(&self.users, &self.projects, collapsed.unwrap_or(&BTreeSet::new()))
+    }
+
+    /// Group the Users tab by team, or go back to the plain list
+    fn toggle_team_view(&mut self) {
+        if self.team_view.take().is_some() {
+            return;
+        }
+        if !teams::has_teams(&self.users) {
+            self.log(LogEntry::warning(tr!("log-teams-unavailable")));
+            return;
+        }
+        let mut view = TeamView::default();
+        // Keep the highlighted user
+        view.cursor = teams::rows(&self.users, &self.projects, &view.collapsed)
+            .iter()
+            .position(|row| *row == TreeRow::User(self.list_selected))
+            .unwrap_or(0);
+        self.team_view = Some(view);
+    }
+
+    /// Keys of the team view: move through the tree, `Space` folds a team,
+    /// `Enter` on a team shows its projects on the Timeline
+    fn handle_team_key(&mut self, key: KeyEvent) {
+        let rows = self.team_rows();
+        let Some(view) = self.team_view.as_mut() else {
+            return;
+        };
+        if rows.is_empty() {
+            return;
+        }
+        let total = rows.len();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => view.cursor = (view.cursor + 1) % total,
+            KeyCode::Char('k') | KeyCode::Up => view.cursor = view.cursor.checked_sub(1).unwrap_or(total - 1),
+            KeyCode::Char('g') => view.cursor = 0,
+            KeyCode::Char('G') => view.cursor = total - 1,
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                if let Some(TreeRow::Team { name, .. }) = rows.get(view.cursor) {
+                    if key.code == KeyCode::Char(' ') {
+                        if !view.collapsed.remove(name) {
+                            view.collapsed.insert(name.clone());
+                        }
+                    } else if let Some(name) = name.clone() {
+                        self.filter_projects_by("team", &name);
+                    }
+                }
+            }
+            _ => {}
+        }
+        // Rows may have folded away under the cursor
+        let rows = self.team_rows();
+        if let Some(view) = self.team_view.as_mut() {
+            view.cursor = view.cursor.min(rows.len().saturating_sub(1));
+            if let Some(TreeRow::User(index)) = rows.get(view.cursor) {
+                self.list_selected = *index;
+            }
+        }
+    }
+
+    /// Show the projects whose `field` matches `name` on the Timeline
+    fn filter_projects_by(&mut self, field: &str, name: &str) {
+        self.set_expression(EntityType::Project, &format!("{}:\"{}\"", field, name.replace('"', "")));
+        if self.active_tab != Tab::Projects {
+            self.active_tab = Tab::Timeline;
+        }
+    }
+
+    /// Open delete confirmation dialog
+    pub fn open_delete_confirm(&mut self) {
+        let dialog = match self.active_tab {
+            Tab::Clients => {
+                if let Some(client) = self.clients.get(self.list_selected) {
+                    Some(ConfirmDialog::new_delete(
+                        EntityType::Client,
+                        client.id,
+                        client.display_name(),
+                    ))
+                } else {
+                    None
+                }
+            }
+            Tab::Timeline | Tab::Projects => {
+                if self.blocked_by_sandbox() {
+                    return;
+                }
+                if let Some(idx) = self.timeline_state.selected_project {
+                    if let Some(project) = self.projects.get(idx) {
+                        Some(ConfirmDialog::new_delete(
+                            EntityType::Project,
+                            project.id,
+                            project.display_name(),
+                        ))
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            }
+            Tab::Users => {
+                self.selected_user().map(|user| {
+                    ConfirmDialog::new_delete(EntityType::User, user.id, user.display_name())
+                })
+            }
+            Tab::Stats | Tab::Inbox | Tab::Audit | Tab::Console | Tab::Plugin(_) => None,
+        };
+
+        if let Some(dialog) = dialog {
+            self.open_confirm(dialog);
+        }
+    }
+
+    /// Close the current form
+    pub fn close_form(&mut self) {
+        self.form_state = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Show a confirmation dialog; on production, destructive actions need the profile name typed
+    pub fn open_confirm(&mut self, mut dialog: ConfirmDialog) {
+        if dialog.action.is_destructive() {
+            dialog.confirm_word = self.production.clone();
+        }
+        self.confirm_dialog = Some(dialog);
+        self.input_mode = InputMode::Confirming;
+    }
+
+    /// Close the confirm dialog
+    pub fn close_confirm(&mut self) {
+        self.confirm_dialog = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Handle API messages
+    pub fn handle_api_message(&mut self, message: ApiMessage) {
+        for plugin in &mut self.plugins {
+            plugin.on_message(&message);
+        }
+
+        match message {
+            ApiMessage::ProjectsLoaded(projects) => {
+                let live = !self.loading_recorded;
+                if live {
+                    self.record_snapshot(Load::Projects(projects.clone()));
+                    self.update_inbox(&projects);
+                    self.check_deadlines(&projects);
+                    let mut webhook = self.check_alert_rules(&projects);
+                    webhook.extend(self.check_sla_breaches(&projects));
+                    if !webhook.is_empty() {
+                        self.pending_commands.push(ApiCommand::PostWebhook(webhook));
+                    }
+                    self.recorded_at = None;
+                }
+                self.record_changes(EntityType::Project, projects.iter().map(Snapshot::project).collect());
+                // Projects still open before this refresh (for completion bursts)
+                let open_before: HashSet<Uuid> = self
+                    .projects
+                    .iter()
+                    .filter(|p| !p.is_completed())
+                    .map(|p| p.id)
+                    .collect();
+                let counts_before = self.client_project_counts();
+                self.loaded.projects = projects;
+                self.index_search(EntityType::Project);
+                let counts = self.client_project_counts();
+                let now = Instant::now();
+                for (client, count) in &counts {
+                    if counts_before.get(client).is_some_and(|before| before != count) {
+                        self.flash.light_cell(*client, flash::PROJECT_COUNTS, now);
+                    }
+                }
+                let count = self.show_projects();
+                if live {
+                    self.is_loading = false;
+                    self.last_refresh = Some(Instant::now());
+                    self.log(LogEntry::success(tr!("log-loaded-projects", count = count)));
+                    self.particle_system.push_effect(ParticleEffect::Sweep(colors::BLUE_LIGHT));
+                }
+
+                let completed: Vec<usize> = self
+                    .projects
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| p.is_completed() && open_before.contains(&p.id))
+                    .map(|(index, _)| index)
+                    .collect();
+                for index in completed {
+                    self.celebrate_completion(index);
+                }
+                // Client completion rates may have changed
+                self.sort_lists();
+                if let Some((entity_type, id)) = self.pending_focus.take() {
+                    if !self.focus(entity_type, id) {
+                        self.log(LogEntry::warning(tr!("log-focus-not-found", entity = entity_type, id = id)));
+                    }
+                }
+            }
+            ApiMessage::ClientsLoaded(clients) => {
+                self.record_snapshot(Load::Clients(clients.clone()));
+                self.record_changes(EntityType::Client, clients.iter().map(Snapshot::client).collect());
+                let count = clients.len();
+                self.loaded.clients = clients;
+                self.index_search(EntityType::Client);
+                // Projects are found by their client's name too
+                self.index_search(EntityType::Project);
+                self.show_clients();
+                if !self.loading_recorded {
+                    self.log(LogEntry::success(tr!("log-loaded-clients", count = count)));
+                }
+            }
+            ApiMessage::UsersLoaded(users) => {
+                self.record_snapshot(Load::Users(users.clone()));
+                self.record_changes(EntityType::User, users.iter().map(Snapshot::user).collect());
+                let count = users.len();
+                self.loaded.users = users;
+                self.index_search(EntityType::User);
+                self.index_search(EntityType::Project);
+                self.show_users();
+                if !self.loading_recorded {
+                    self.log(LogEntry::success(tr!("log-loaded-users", count = count)));
+                }
+            }
+            ApiMessage::SnapshotRecorded { entity, result } => self.snapshot_recorded(entity, result),
+            ApiMessage::HistoryLoaded { range, result } => self.history_loaded(range, result),
+            ApiMessage::ClientsPage(page) => self.receive_clients_page(page),
+            ApiMessage::UsersPage(page) => self.receive_users_page(page),
+            ApiMessage::Error(error) => {
+                self.is_loading = false;
+                // A failed page can be asked for again
+                self.client_pages.loading = false;
+                self.user_pages.loading = false;
+                self.particle_system.push_effect(ParticleEffect::Flicker(colors::RED));
+                self.handle_error(error);
+            }
+            ApiMessage::ConnectionStatus(connected) => {
+                let was_connected = self.api_connected;
                 self.api_connected = connected;
 
-                if connected && !was_connected {
-                    self.log(LogEntry::success("Connected to API"));
-                } else if !connected && was_connected {
-                    self.log(LogEntry::warning("Disconnected from API"));
+                if connected && !was_connected {
+                    self.log(LogEntry::success(tr!("log-connected")));
+                } else if !connected && was_connected {
+                    self.log(LogEntry::warning(tr!("log-disconnected")));
+                    if !self.api_urls.is_empty() {
+                        self.log(LogEntry::info(tr!("log-diagnose-hint")));
+                    }
+                }
+
+                if !connected {
+                    self.reconnect.failed(Instant::now());
+                } else if self.reconnect.is_active() {
+                    self.reconnect.reset();
+                    // Back after a drop: reload unless a refresh is already under way
+                    if !self.is_loading {
+                        self.is_loading = true;
+                        self.pending_commands.push(ApiCommand::RefreshAll);
+                    }
+                }
+            }
+            ApiMessage::Created(entity_type, id) => {
+                self.log(LogEntry::success(tr!("log-created", entity = entity_type, id = &id.to_string()[..8])));
+                self.close_form();
+            }
+            ApiMessage::Updated(entity_type) => {
+                self.log(LogEntry::success(tr!("log-updated", entity = entity_type)));
+                self.close_form();
+            }
+            ApiMessage::Deleted(entity_type, id) => {
+                self.log(LogEntry::success(tr!("log-deleted", entity = entity_type, id = &id.to_string()[..8])));
+                self.close_confirm();
+                // Older history of the entity is stale; an undone creation is
+                // moved to the redo stack after this (see `ApiMessage::Operation`)
+                self.history.forget(entity_type, id);
+            }
+            ApiMessage::Operation(operation, message) => {
+                let outcome = match &*message {
+                    ApiMessage::Error(_) => None,
+                    ApiMessage::Created(_, id) => Some((Some(*id), Vec::new())),
+                    ApiMessage::BatchUpdated { failed, .. } => Some((None, failed.iter().map(|(id, _)| *id).collect())),
+                    _ => Some((None, Vec::new())),
+                };
+                self.handle_api_message(*message);
+                match outcome {
+                    Some((created, failed)) => self.complete_history(operation, created, &failed),
+                    None => self.history.fail(operation),
+                }
+            }
+            ApiMessage::Prefetched(data) => self.prefetched.store(data),
+            ApiMessage::RemoteChanged => self.log(LogEntry::info(tr!("log-remote-changed"))),
+            ApiMessage::WatchUnsupported => self.log(LogEntry::warning(tr!("log-watch-unsupported"))),
+            ApiMessage::WebhookFailed(error) => self.log(LogEntry::warning(tr!("log-webhook-failed", error = error))),
+            // Only changes are logged, plus backends down from the start
+            // Dropped when the overlay was closed before the checks finished
+            ApiMessage::Diagnosis(reports) => {
+                if let Some(diagnosis) = &mut self.diagnosis {
+                    *diagnosis = reports;
+                }
+            }
+            ApiMessage::Backends(backends) => {
+                for backend in &backends {
+                    let before = self.backends.iter().find(|b| b.name == backend.name).map(|b| b.connected);
+                    match (before, backend.connected) {
+                        (Some(false), true) => self.log(LogEntry::success(tr!("log-backend-up", name = backend.name))),
+                        (Some(true) | None, false) => self.log(LogEntry::warning(tr!("log-backend-down", name = backend.name))),
+                        _ => {}
+                    }
+                }
+                self.backends = backends;
+            }
+            ApiMessage::WatchPaced { interval, base, rate_limit } => {
+                let secs = interval.as_secs();
+                if interval > base {
+                    let remaining = rate_limit.map_or(0, |r| r.remaining);
+                    self.log(LogEntry::warning(tr!("log-watch-slowed", remaining = remaining, secs = secs)));
+                } else {
+                    self.log(LogEntry::info(tr!("log-watch-restored", secs = secs)));
+                }
+            }
+            ApiMessage::ConsoleResponse(response) => self.console.receive(response),
+            ApiMessage::DocumentsLoaded { project_id, result } => {
+                // A server without documents is not an error
+                if let Some(e) = result.as_ref().err().filter(|e| e.status() != Some(404)) {
+                    self.log(LogEntry::error(tr!("log-documents-failed", error = e.message())));
+                }
+                if let Some(attachments) = self.attachments.as_mut().filter(|a| a.project_id == project_id) {
+                    attachments.receive(result);
+                }
+            }
+            ApiMessage::AuditLoaded { page, search, result } => {
+                if let Some(e) = result.as_ref().err().filter(|e| e.status() != Some(404)) {
+                    self.log(LogEntry::error(tr!("log-audit-failed", error = e.message())));
+                }
+                self.audit.receive(page, &search, result);
+                if self.active_tab == Tab::Audit {
+                    self.list_selected = self.list_selected.min(self.audit.events().len().saturating_sub(1));
+                }
+            }
+            ApiMessage::DownloadProgress { document_id, received, total } => {
+                if let Some(download) = self.download.as_mut().filter(|d| d.document_id == document_id) {
+                    download.received = received;
+                    download.total = total;
+                }
+            }
+            ApiMessage::DownloadFinished { document_id, result } => {
+                if self.download.as_ref().is_some_and(|d| d.document_id == document_id) {
+                    self.download = None;
+                }
+                match result {
+                    Ok(path) => self.log(LogEntry::success(tr!("log-download-saved", path = path.display().to_string()))),
+                    Err(error) => self.log(LogEntry::error(tr!("log-download-failed", error = error))),
+                }
+            }
+            ApiMessage::Draining { done, total } => self.draining = Some((done, total)),
+            ApiMessage::BatchUpdated { succeeded, failed } => {
+                if failed.is_empty() {
+                    self.log(LogEntry::success(tr!("log-rescheduled", count = succeeded)));
+                } else {
+                    for (id, error) in &failed {
+                        let name = match self.projects.iter().find(|p| p.id == *id) {
+                            Some(project) => project.display_name().to_string(),
+                            None => id.to_string()[..8].to_string(),
+                        };
+                        self.log(LogEntry::error(tr!("log-reschedule-failed", name = name, error = error)));
+                    }
+                    self.show_error(
+                        tr!("error-batch-title"),
+                        tr!("error-batch-message", succeeded = succeeded, failed = failed.len()),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Prefetch jobs for what the current view is likely to ask for next and
+    /// has not loaded: the documents of the selected project first, then of its
+    /// neighbours; the next page of a lazily loaded list or of the audit log
+    pub fn prefetch_jobs(&self) -> Vec<(PrefetchJob, u8)> {
+        let page_job = |entity, page: Option<i32>| page.map(|page| (PrefetchJob::Page { entity, page }, PRIORITY_NEIGHBOR));
+        let mut jobs = Vec::new();
+        match self.active_tab {
+            Tab::Timeline | Tab::Projects => {
+                if let Some(selected) = self.timeline_state.selected_project.filter(|&i| i < self.projects.len()) {
+                    let neighbors = [selected.checked_sub(1), Some(selected + 1)].into_iter().flatten();
+                    for (index, priority) in [(selected, PRIORITY_SELECTED)].into_iter().chain(neighbors.map(|i| (i, PRIORITY_NEIGHBOR))) {
+                        if let Some(project) = self.projects.get(index) {
+                            jobs.push((PrefetchJob::Documents(project.id), priority));
+                        }
+                    }
+                }
+            }
+            Tab::Clients => jobs.extend(page_job(EntityType::Client, self.client_pages.next_unloaded())),
+            Tab::Users => jobs.extend(page_job(EntityType::User, self.user_pages.next_unloaded())),
+            Tab::Audit => {
+                if let AuditFeed::Loaded(result) = &self.audit.feed {
+                    if result.has_next {
+                        let job = PrefetchJob::Audit { page: self.audit.page + 1, search: self.audit.search.clone() };
+                        jobs.push((job, PRIORITY_NEIGHBOR));
+                    }
+                }
+            }
+            Tab::Stats | Tab::Inbox | Tab::Console | Tab::Plugin(_) => {}
+        }
+        jobs.retain(|(job, _)| !self.prefetched.has(job));
+        jobs
+    }
+
+    /// Surface an API failure according to its kind
+    fn handle_error(&mut self, error: AppError) {
+        let message = match &error {
+            AppError::Api { source, .. } => source.message(),
+            AppError::Offline => error.to_string(),
+        };
+        match error.kind() {
+            // Transient: note it in the log, the connection is re-checked
+            None | Some(ApiErrorKind::Connection | ApiErrorKind::Timeout) => {
+                self.api_connected = false;
+                self.log(LogEntry::error(error.to_string()));
+            }
+            Some(ApiErrorKind::Server) => self.log(LogEntry::error(error.to_string())),
+            Some(ApiErrorKind::Unauthorized) => {
+                self.show_error(tr!("error-auth-title"), tr!("error-auth-message", error = error));
+            }
+            // Validation errors belong next to the fields that caused them
+            Some(ApiErrorKind::Rejected) if self.form_state.is_some() => {
+                self.log(LogEntry::error(error.to_string()));
+                if let Some(form) = &mut self.form_state {
+                    form.error = Some(message);
+                }
+            }
+            Some(ApiErrorKind::Rejected) => self.show_error(tr!("error-rejected-title"), error.to_string()),
+            Some(ApiErrorKind::Decode) => self.show_error(tr!("error-decode-title"), error.to_string()),
+        }
+    }
+
+    /// Move a confirmed operation to the right history stack
+    fn complete_history(&mut self, operation: OperationId, created: Option<Uuid>, failed: &[Uuid]) {
+        match self.history.complete(operation, created, failed) {
+            Some((label, Direction::Undo)) => self.log(LogEntry::success(tr!("undo-undid", label = label))),
+            Some((label, Direction::Redo)) => self.log(LogEntry::success(tr!("undo-redid", label = label))),
+            Some((_, Direction::Do)) | None => {}
+        }
+    }
+
+    /// Undo (or redo) the latest recorded API mutation, asking first on production
+    fn undo_redo(&mut self, redo: bool) -> Option<ApiCommand> {
+        if self.production.is_some() && !self.history.is_busy() {
+            if let Some(label) = self.history.next_label(redo) {
+                self.open_confirm(ConfirmDialog::new_undo_redo(redo, label));
+                return None;
+            }
+        }
+        self.start_undo_redo(redo)
+    }
+
+    fn start_undo_redo(&mut self, redo: bool) -> Option<ApiCommand> {
+        if self.history.is_busy() {
+            self.log(LogEntry::warning(tr!("undo-busy")));
+            return None;
+        }
+        let started = if redo { self.history.start_redo() } else { self.history.start_undo() };
+        match started {
+            Some((label, cmd)) => {
+                let message = if redo {
+                    tr!("undo-redoing", label = label)
+                } else {
+                    tr!("undo-undoing", label = label)
+                };
+                self.log(LogEntry::info(message));
+                Some(cmd)
+            }
+            None => {
+                let message = if redo { tr!("redo-nothing") } else { tr!("undo-nothing") };
+                self.log(LogEntry::warning(message));
+                None
+            }
+        }
+    }
+
+    /// Drop rows rejected by the user script's filter functions
+    fn apply_script_filter<T>(&mut self, items: Vec<T>, what: EntityType, keep: impl Fn(&Scripts, &T) -> bool) -> Vec<T> {
+        let total = items.len();
+        let items = script::filter_loaded(self.scripts.as_ref(), items, keep);
+        let hidden = total - items.len();
+        if hidden > 0 {
+            let message = match what {
+                EntityType::Project => tr!("log-script-filter-projects", count = hidden),
+                EntityType::Client => tr!("log-script-filter-clients", count = hidden),
+                EntityType::User => tr!("log-script-filter-users", count = hidden),
+            };
+            self.log(LogEntry::info(message));
+        }
+        items
+    }
+
+    /// Keep the rows matching every remote-control filter for this entity type
+    fn apply_row_filters<T: serde::Serialize>(&self, items: Vec<T>, what: EntityType) -> Vec<T> {
+        let filters: Vec<&Filter> = self.filters.iter().filter(|(e, _)| *e == what).map(|(_, f)| f).collect();
+        if filters.is_empty() {
+            return items;
+        }
+        items
+            .into_iter()
+            .filter(|item| {
+                let row = serde_json::to_value(item).unwrap_or_default();
+                filters.iter().all(|f| f.matches(&row))
+            })
+            .collect()
+    }
+
+    /// Keep the rows matching the filter expression for this entity type
+    fn apply_expression<T>(&self, items: Vec<T>, what: EntityType, row: impl Fn(&T) -> Value) -> Vec<T> {
+        match self.expression(what) {
+            Some(expr) => items.into_iter().filter(|item| expr.matches(&row(item))).collect(),
+            None => items,
+        }
+    }
+
+    /// Filter expression set for an entity type
+    pub fn expression(&self, what: EntityType) -> Option<&FilterExpr> {
+        self.expressions.iter().find(|(e, _)| *e == what).map(|(_, expr)| expr)
+    }
+
+    /// Set (or with empty input clear) the filter expression of an entity type and apply it
+    fn set_expression(&mut self, entity: EntityType, input: &str) {
+        if input.is_empty() {
+            self.expressions.retain(|(e, _)| *e != entity);
+            self.log(LogEntry::info(tr!("log-filter-cleared", entity = entity)));
+        } else {
+            match FilterExpr::parse(input) {
+                Ok(expr) => {
+                    self.log(LogEntry::info(tr!("log-filter-set", entity = entity, filter = expr.as_str())));
+                    self.expressions.retain(|(e, _)| *e != entity);
+                    self.expressions.push((entity, expr));
+                }
+                Err(e) => {
+                    self.log(LogEntry::warning(tr!("log-filter-invalid", error = e)));
+                    return;
+                }
+            }
+        }
+        self.refilter(entity);
+    }
+
+    /// The user entry matching `me`, if loaded
+    pub fn my_user(&self) -> Option<&UserDto> {
+        let me = self.me.as_deref()?;
+        self.loaded.users.iter().find(|u| u.login.as_deref().is_some_and(|login| login.eq_ignore_ascii_case(me)))
+    }
+
+    /// Keep only the current user's projects while "My projects" is on
+    fn apply_my_projects(&self, projects: Vec<ProjectDto>) -> Vec<ProjectDto> {
+        match self.my_user().filter(|_| self.my_projects) {
+            Some(me) => projects.into_iter().filter(|p| p.manager_id == me.id).collect(),
+            None => projects,
+        }
+    }
+
+    /// Filter, pin and select the loaded projects for the timeline and the
+    /// Projects table, keeping what-if edits on top; returns how many are shown
+    fn show_projects(&mut self) -> usize {
+        let projects = self.apply_script_filter(self.loaded.projects.clone(), EntityType::Project, Scripts::keep_project);
+        let projects = self.apply_row_filters(projects, EntityType::Project);
+        let as_of = self.reference_date();
+        let projects = self.apply_expression(projects, EntityType::Project, |p| {
+            expr::project_row(p, &self.loaded.clients, &self.loaded.users, as_of)
+        });
+        let projects = self.apply_my_projects(projects);
+        let mut projects = self.apply_archive(projects);
+        pin_favorites(&self.ui_state.favorites, &mut projects, |p| p.id);
+        // Keep local what-if edits on top of the fresh data
+        self.projects = match &mut self.sandbox {
+            Some(sandbox) => sandbox.rebase(projects, &self.projects),
+            None => projects,
+        };
+
+        // Auto-center timeline on first project or today when projects are loaded
+        if !self.projects.is_empty() {
+            // Select first project if none selected
+            if self.timeline_state.selected_project.is_none() {
+                self.timeline_state.selected_project = Some(0);
+            }
+            // Jump to show the selected (or first) project
+            self.auto_center_timeline();
+        }
+        self.projects.len()
+    }
+
+    /// Filter, index and sort loaded clients (only the loaded pages of a paged
+    /// list) for the Clients list; returns how many are shown
+    fn show_clients(&mut self) -> usize {
+        let rows = if self.client_pages.is_paged() { &self.client_pages.rows } else { &self.loaded.clients };
+        let clients = self.apply_script_filter(rows.clone(), EntityType::Client, Scripts::keep_client);
+        let clients = self.apply_row_filters(clients, EntityType::Client);
+        self.clients = self.apply_expression(clients, EntityType::Client, expr::row);
+        self.sort_lists();
+        self.clients.len()
+    }
+
+    /// Filter, index and sort loaded users (only the loaded pages of a paged
+    /// list) for the Users list; returns how many are shown
+    fn show_users(&mut self) -> usize {
+        let rows = if self.user_pages.is_paged() { &self.user_pages.rows } else { &self.loaded.users };
+        let users = self.apply_script_filter(rows.clone(), EntityType::User, Scripts::keep_user);
+        let users = self.apply_row_filters(users, EntityType::User);
+        self.users = self.apply_expression(users, EntityType::User, expr::user_row);
+        self.sort_lists();
+        self.users.len()
+    }
+
+    /// Rebuild the search index of an entity type from the unfiltered rows, so
+    /// search also finds what the filters hide
+    fn index_search(&mut self, entity: EntityType) {
+        match entity {
+            EntityType::Project => {
+                let names = Names::new(&self.loaded.clients, &self.loaded.users);
+                let docs = self.loaded.projects.iter().map(|p| SearchDoc::project(p, &names));
+                self.search_index.replace(EntityType::Project, docs);
+            }
+            EntityType::Client => {
+                let rows = if self.client_pages.is_paged() { &self.client_pages.rows } else { &self.loaded.clients };
+                self.search_index.replace(EntityType::Client, rows.iter().map(SearchDoc::client));
+            }
+            EntityType::User => {
+                let rows = if self.user_pages.is_paged() { &self.user_pages.rows } else { &self.loaded.users };
+                self.search_index.replace(EntityType::User, rows.iter().map(SearchDoc::user));
+            }
+        }
+    }
+
+    /// Rebuild the view of an entity type from the loaded rows after its filters changed
+    fn refilter(&mut self, entity: EntityType) {
+        match entity {
+            EntityType::Project => self.show_projects(),
+            EntityType::Client => self.show_clients(),
+            EntityType::User => self.show_users(),
+        };
+    }
+
+    /// Ask for the next page of a lazily loaded list once the selection nears
+    /// the end of the loaded rows; filters and sorting reorder the list, so the
+    /// selected row is looked up among them (with nothing shown, more are needed)
+    fn load_next_page(&mut self, entity: EntityType) {
+        let selected = self.selected_entity().map(|(_, id)| id);
+        let page = match entity {
+            EntityType::Client => {
+                let index = match selected {
+                    Some(id) => self.client_pages.rows.iter().position(|c| c.id == id),
+                    None => self.clients.is_empty().then_some(self.client_pages.rows.len()),
+                };
+                index.and_then(|index| self.client_pages.next_page(index))
+            }
+            EntityType::User => {
+                let index = match selected {
+                    Some(id) => self.user_pages.rows.iter().position(|u| u.id == id),
+                    None => self.users.is_empty().then_some(self.user_pages.rows.len()),
+                };
+                index.and_then(|index| self.user_pages.next_page(index))
+            }
+            EntityType::Project => None,
+        };
+        let Some(page) = page else {
+            return;
+        };
+        // Prefetched while idle
+        match entity {
+            EntityType::Client => {
+                if let Some(rows) = self.prefetched.take_clients_page(page) {
+                    return self.receive_clients_page(rows);
+                }
+            }
+            EntityType::User => {
+                if let Some(rows) = self.prefetched.take_users_page(page) {
+                    return self.receive_users_page(rows);
+                }
+            }
+            EntityType::Project => {}
+        }
+        self.pending_commands.push(ApiCommand::FetchPage { entity, page });
+    }
+
+    /// Show a page of the Clients list; the first one starts the list over
+    fn receive_clients_page(&mut self, page: PaginatedResult<ClientDto>) {
+        if page.page <= 1 {
+            self.prefetched.clear_pages(EntityType::Client);
+        }
+        self.client_pages.receive(page);
+        self.index_search(EntityType::Client);
+        self.show_clients();
+        let (loaded, total) = (self.client_pages.rows.len(), self.client_pages.total_count);
+        self.log(LogEntry::success(tr!("log-loaded-page", entity = EntityType::Client, loaded = loaded, total = total)));
+    }
+
+    /// Show a page of the Users list; the first one starts the list over
+    fn receive_users_page(&mut self, page: PaginatedResult<UserDto>) {
+        if page.page <= 1 {
+            self.prefetched.clear_pages(EntityType::User);
+        }
+        self.user_pages.receive(page);
+        self.index_search(EntityType::User);
+        self.show_users();
+        let (loaded, total) = (self.user_pages.rows.len(), self.user_pages.total_count);
+        self.log(LogEntry::success(tr!("log-loaded-page", entity = EntityType::User, loaded = loaded, total = total)));
+    }
+
+    /// Rows loaded, rows on the server and whether a page is on its way, for a
+    /// list loaded a page at a time
+    pub fn page_status(&self, tab: Tab) -> Option<(usize, usize, bool)> {
+        match tab {
+            Tab::Clients if self.client_pages.is_paged() => Some(self.client_pages.status()),
+            Tab::Users if self.user_pages.is_paged() => Some(self.user_pages.status()),
+            _ => None,
+        }
+    }
+
+    /// Leave out archived projects while they are hidden, counting them
+    fn apply_archive(&mut self, projects: Vec<ProjectDto>) -> Vec<ProjectDto> {
+        if !self.ui_state.hide_archived {
+            self.archived_hidden = 0;
+            return projects;
+        }
+        let as_of = self.reference_date();
+        let cutoff = as_of - chrono::Duration::days(self.config.ui.archive_after_days.into());
+        let total = projects.len();
+        let kept: Vec<ProjectDto> =
+            projects.into_iter().filter(|p| p.actual_end_as_of(as_of).is_none_or(|end| end >= cutoff)).collect();
+        self.archived_hidden = total - kept.len();
+        kept
+    }
+
+    /// Hide or show archived projects
+    fn toggle_archived(&mut self) {
+        self.ui_state.hide_archived = !self.ui_state.hide_archived;
+        if self.ui_state.hide_archived {
+            self.log(LogEntry::info(tr!("log-archived-hidden", days = self.config.ui.archive_after_days)));
+        } else {
+            self.log(LogEntry::info(tr!("log-archived-shown")));
+        }
+        if let Err(e) = self.ui_state.save() {
+            self.log(LogEntry::warning(tr!("log-archive-persist-failed", error = e)));
+        }
+        self.refilter(EntityType::Project);
+    }
+
+    /// Show or hide the telemetry readout in the status bar
+    fn toggle_telemetry(&mut self) {
+        self.ui_state.show_telemetry = !self.ui_state.show_telemetry;
+        if let Err(e) = self.ui_state.save() {
+            self.log(LogEntry::warning(tr!("log-telemetry-persist-failed", error = e)));
+        }
+    }
+
+    /// Telemetry readout for the status bar, while it is switched on
+    pub fn telemetry_text(&self) -> Option<String> {
+        let entities = self.projects.len() + self.clients.len() + self.users.len();
+        self.ui_state.show_telemetry.then(|| telemetry::readout(self.frame_stats, entities, self.telemetry.rss))
+    }
+
+    /// Switch the "My projects" view on or off
+    fn toggle_my_projects(&mut self) {
+        let Some(me) = self.me.clone() else {
+            self.log(LogEntry::warning(tr!("log-my-projects-no-user")));
+            return;
+        };
+        if self.my_user().is_none() {
+            self.log(LogEntry::warning(tr!("log-my-projects-unknown", login = me)));
+            return;
+        }
+        self.my_projects = !self.my_projects;
+        if self.my_projects {
+            self.log(LogEntry::info(tr!("log-my-projects-on", login = me)));
+        } else {
+            self.log(LogEntry::info(tr!("log-my-projects-off")));
+        }
+        self.refilter(EntityType::Project);
+    }
+
+    /// Show an entity on its tab and scroll the timeline to it (a client's or
+    /// manager's earliest project); false when it is not loaded
+    pub fn focus(&mut self, entity_type: EntityType, id: Uuid) -> bool {
+        let (tab, index, owns): (Tab, _, fn(&ProjectDto, Uuid) -> bool) = match entity_type {
+            // Projects stay in the representation that is showing
+            EntityType::Project => {
+                let tab = if self.active_tab == Tab::Projects { Tab::Projects } else { Tab::Timeline };
+                (tab, self.projects.iter().position(|p| p.id == id), |p, id| p.id == id)
+            }
+            EntityType::Client => (Tab::Clients, self.clients.iter().position(|c| c.id == id), |p, id| p.client_id == id),
+            EntityType::User => (Tab::Users, self.users.iter().position(|u| u.id == id), |p, id| p.manager_id == id),
+        };
+        let Some(index) = index else {
+            return false;
+        };
+        self.active_tab = tab;
+        if !matches!(tab, Tab::Timeline | Tab::Projects) {
+            self.list_selected = index;
+        }
+        let first = self
+            .projects
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| owns(p, id))
+            .min_by_key(|(_, p)| p.start_date)
+            .map(|(index, _)| index);
+        if first.is_some() {
+            self.timeline_state.selected_project = first;
+            self.auto_center_timeline();
+        }
+        true
+    }
+
+    /// Completed and total projects per client, as the Clients list shows them
+    fn client_project_counts(&self) -> HashMap<Uuid, (i32, i32)> {
+        let as_of = self.reference_date();
+        let mut counts: HashMap<Uuid, (i32, i32)> = HashMap::new();
+        for project in &self.loaded.projects {
+            let entry = counts.entry(project.client_id).or_default();
+            entry.0 += project.is_completed_as_of(as_of) as i32;
+            entry.1 += 1;
+        }
+        counts
+    }
+
+    /// Compare a load with the previous one and point at the report when something changed
+    fn record_changes(&mut self, entity_type: EntityType, snapshots: Vec<Snapshot>) {
+        let count = self.changes.record(entity_type, snapshots);
+        if count > 0 {
+            self.log(LogEntry::info(tr!("log-changes", entity = entity_type, count = count)));
+            let changes = self.changes.changes.iter().filter(|c| c.entity_type == entity_type);
+            self.flash.light(changes, Instant::now());
+            self.report_watched(entity_type);
+        }
+    }
+
+    /// Toast and file in the Inbox what changed about watched entities
+    fn report_watched(&mut self, entity_type: EntityType) {
+        let changes: Vec<_> = self.changes.changes.iter().filter(|c| c.entity_type == entity_type).cloned().collect();
+        let events = subscriptions::events(&changes, &self.ui_state.watched, &self.loaded.clients, &self.loaded.users);
+        if events.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        for event in &events {
+            self.toasts.push(Toast::new(event.message(), now));
+        }
+        let excess = self.toasts.len().saturating_sub(subscriptions::MAX_TOASTS);
+        self.toasts.drain(..excess);
+        self.ui_state.inbox.add(events, crate::clock::now());
+        self.save_inbox();
+    }
+
+    /// The current tab and its selected entity
+    fn place(&self) -> Place {
+        Place { tab: self.active_tab, entity: self.selected_entity() }
+    }
+
+    /// Go back (or forward) in the navigation history
+    fn walk_jumps(&mut self, back: bool) {
+        let current = self.place();
+        let place = if back { self.jumps.back(current) } else { self.jumps.forward(current) };
+        let Some(place) = place else {
+            return;
+        };
+        self.active_tab = place.tab;
+        // An entity deleted since only leaves the tab
+        match place.entity {
+            Some((EntityType::Project, id)) => {
+                if let Some(index) = self.projects.iter().position(|p| p.id == id) {
+                    self.timeline_state.selected_project = Some(index);
+                    self.auto_center_timeline();
+                }
+            }
+            Some((EntityType::Client, id)) => {
+                self.list_selected = self.clients.iter().position(|c| c.id == id).unwrap_or(0);
+            }
+            Some((EntityType::User, id)) => {
+                self.list_selected = self.users.iter().position(|u| u.id == id).unwrap_or(0);
+            }
+            None => {}
+        }
+    }
+
+    /// Apply a command received over the remote-control socket
+    pub fn handle_remote(&mut self, command: RemoteCommand) -> Option<ApiCommand> {
+        match command {
+            RemoteCommand::Refresh => {
+                self.is_loading = true;
+                self.log(LogEntry::info(tr!("log-refreshing")));
+                Some(ApiCommand::RefreshAll)
+            }
+            RemoteCommand::GotoProject(id) => {
+                let from = self.place();
+                if self.focus(EntityType::Project, id) {
+                    self.jumps.jump(from, self.place());
+                } else {
+                    self.log(LogEntry::warning(tr!("log-remote-no-project", id = id)));
+                }
+                None
+            }
+            RemoteCommand::SetFilter(entity, filters) => {
+                self.filters.retain(|(e, _)| *e != entity);
+                if filters.is_empty() {
+                    self.log(LogEntry::info(tr!("log-filter-cleared", entity = entity)));
+                } else {
+                    let text = filters.iter().map(|f| format!("{}={}", f.field, f.value)).collect::<Vec<_>>().join(" ");
+                    self.log(LogEntry::info(tr!("log-filter-set", entity = entity, filter = text)));
+                }
+                self.filters.extend(filters.into_iter().map(|f| (entity, f)));
+                self.refilter(entity);
+                None
+            }
+        }
+    }
+
+    /// Replay the keys bound to a function key by the user script
+    fn run_macro(&mut self, n: u8) -> bool {
+        let Some(keys) = self.scripts.as_ref().and_then(|s| s.macro_keys(n)).map(<[KeyEvent]>::to_vec) else {
+            return false;
+        };
+        for key in keys {
+            if let Some(cmd) = self.handle_key(key) {
+                self.pending_commands.push(cmd);
+            }
+        }
+        true
+    }
+
+    /// Handle key events and return optional API command
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
+        tracing::trace!(code = ?key.code, modifiers = ?key.modifiers, mode = ?self.input_mode, "key");
+        // Handle error popup dismissal
+        if self.error_popup.is_some() {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char(' ')) {
+                self.dismiss_error();
+            }
+            return None;
+        }
+
+        // Handle burndown popup
+        if self.burndown.is_some() {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('b') | KeyCode::Char('q')) {
+                self.burndown = None;
+            }
+            return None;
+        }
+
+        // Handle the "What changed?" view
+        if let Some(scroll) = &mut self.changes_view {
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => *scroll = scroll.saturating_add(1),
+                KeyCode::Char('k') | KeyCode::Up => *scroll = scroll.saturating_sub(1),
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('w') | KeyCode::Char('q') => self.changes_view = None,
+                _ => {}
+            }
+            return None;
+        }
+
+        // Handle the JSON inspector
+        if let Some(inspector) = &mut self.inspector {
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => inspector.scroll = inspector.scroll.saturating_add(1),
+                KeyCode::Char('k') | KeyCode::Up => inspector.scroll = inspector.scroll.saturating_sub(1),
+                KeyCode::PageDown => inspector.scroll = inspector.scroll.saturating_add(10),
+                KeyCode::PageUp => inspector.scroll = inspector.scroll.saturating_sub(10),
+                KeyCode::Char('y') => {
+                    if let Some((value, _)) = self.inspected() {
+                        self.pending_clipboard = Some(serde_json::to_string_pretty(&value).unwrap_or_default());
+                        self.log(LogEntry::info(tr!("log-inspector-copied")));
+                    }
+                }
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('J') | KeyCode::Char('q') => self.inspector = None,
+                _ => {}
+            }
+            return None;
+        }
+
+        // Handle the time-travel date picker
+        if self.date_picker.is_some() {
+            self.handle_date_picker_key(key);
+            return None;
+        }
+
+        // Handle a Projects table cell edited in place
+        if self.inline_edit.is_some() {
+            return self.handle_inline_key(key);
+        }
+
+        // Handle the documents popup
+        if self.attachments.is_some() {
+            return self.handle_attachments_key(key);
+        }
+
+        // Handle the global search
+        if self.search.is_some() {
+            self.handle_search_key(key);
+            return None;
+        }
+
+        // Handle the sort dialog
+        if self.sort_dialog.is_some() {
+            self.handle_sort_key(key);
+            return None;
+        }
+
+        // Handle the column dialog
+        if self.column_dialog.is_some() {
+            self.handle_column_key(key);
+            return None;
+        }
+
+        // Handle the settings dialog
+        if self.settings.is_some() {
+            self.handle_settings_key(key);
+            return None;
+        }
+
+        // Handle the connection diagnosis
+        if self.diagnosis.is_some() {
+            return match key.code {
+                KeyCode::Char('r') => self.diagnose(),
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                    self.diagnosis = None;
+                    None
+                }
+                _ => None,
+            };
+        }
+
+        // Handle the context menu
+        if self.context_menu.is_some() {
+            return self.handle_context_menu_key(key);
+        }
+
+        // Handle help overlay
+        if self.show_help {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Char('?') | KeyCode::Enter) {
+                self.show_help = false;
+            }
+            return None;
+        }
+
+        // Handle based on input mode
+        match self.input_mode {
+            InputMode::Normal => {
+                if let Some(back) = jump_direction(key) {
+                    self.walk_jumps(back);
+                    return None;
+                }
+                let from = self.place();
+                let cmd = self.handle_normal_key(key);
+                // Switching tabs is a jump
+                if self.active_tab != from.tab {
+                    self.jumps.jump(from, self.place());
+                }
+                cmd
+            }
+            InputMode::Editing => self.handle_editing_key(key),
+            InputMode::Confirming => self.handle_confirming_key(key),
+            InputMode::Prompting => self.handle_prompt_key(key),
+        }
+    }
+
+    /// Open a text prompt
+    pub fn open_prompt(&mut self, prompt: Prompt) {
+        self.prompt = Some(prompt);
+        self.input_mode = InputMode::Prompting;
+    }
+
+    /// Close the current text prompt
+    pub fn close_prompt(&mut self) {
+        self.prompt = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Handle keys while a text prompt is open
+    fn handle_prompt_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
+        let Some(prompt) = &mut self.prompt else {
+            self.input_mode = InputMode::Normal;
+            return None;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.close_prompt(),
+            KeyCode::Enter => {
+                let input = prompt.input.trim().to_string();
+                let kind = prompt.kind;
+                self.close_prompt();
+                self.submit_prompt(kind, input);
+            }
+            KeyCode::Backspace => {
+                prompt.input.pop();
+            }
+            KeyCode::Char(c) => prompt.input.push(c),
+            _ => {}
+        }
+
+        None
+    }
+
+    /// Apply the submitted prompt input
+    fn submit_prompt(&mut self, kind: PromptKind, input: String) {
+        match kind {
+            PromptKind::BookmarkName(slot) => self.save_bookmark(slot, input),
+            PromptKind::Reschedule => self.preview_reschedule(&input),
+            PromptKind::Filter(entity) => self.set_expression(entity, &input),
+            PromptKind::AuditSearch => {
+                self.audit.search = input;
+                self.list_selected = 0;
+                let command = self.audit.request(1);
+                self.pending_commands.push(command);
+            }
+        }
+    }
+
+    /// Date that project statuses are evaluated against (today unless time-traveling)
+    pub fn reference_date(&self) -> NaiveDate {
+        self.as_of.unwrap_or_else(crate::clock::today)
+    }
+
+    /// Write the loaded projects as a Mermaid gantt chart into the working directory
+    fn export_mermaid(&mut self) {
+        let chart = mermaid::gantt(tr!("mermaid-title"), &self.projects, &self.loaded.clients, self.reference_date());
+        let path = format!("sweem-gantt-{}.md", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        match std::fs::write(&path, chart) {
+            Ok(()) => self.log(LogEntry::success(tr!("log-mermaid-exported", count = self.projects.len(), path = path))),
+            Err(e) => self.log(LogEntry::error(tr!("log-mermaid-failed", error = e))),
+        }
+    }
+
+    /// Headers and rows of the current view as shown: filtered, sorted and with its columns
+    pub fn view_table(&self) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+        let as_of = self.reference_date();
+        let star = |id: &Uuid| if self.ui_state.favorites.contains(id) { "★ " } else { "" };
+        let headers = |labels: &[&str]| labels.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+        // The Timeline has no columns of its own and exports them all
+        let columns = match self.active_tab {
+            Tab::Timeline => Column::PROJECTS.into_iter().filter(|c| *c != Column::Source || self.aggregating()).collect(),
+            tab => self.columns(tab),
+        };
+        let column_headers = || columns.iter().map(|c| c.label().to_string()).collect::<Vec<_>>();
+        let (mut headers, rows) = match self.active_tab {
+            Tab::Timeline | Tab::Projects => {
+                let rows = self
+                    .projects
+                    .iter()
+                    .map(|p| {
+                        let cell = |column: &Column| match column {
+                            Column::Name => format!("{}{}", star(&p.id), p.display_name()),
+                            Column::Client => {
+                                self.loaded.clients.iter().find(|c| c.id == p.client_id).map_or("-", |c| c.display_name()).to_string()
+                            }
+                            Column::Manager => {
+                                self.loaded.users.iter().find(|u| u.id == p.manager_id).map_or("-", |u| u.display_name()).to_string()
+                            }
+                            Column::Start => p.start_date.to_string(),
+                            Column::PlannedEnd => p.planned_end_date.to_string(),
+                            Column::ActualEnd => p.actual_end_as_of(as_of).map_or("-".to_string(), |d| d.to_string()),
+                            Column::Due => p.days_left_as_of(as_of).map_or("-".to_string(), due::label),
+                            Column::Status if p.is_completed_as_of(as_of) => tr!("timeline-legend-done").to_string(),
+                            Column::Status if p.is_overdue_as_of(as_of) => tr!("timeline-legend-overdue").to_string(),
+                            Column::Status => tr!("timeline-legend-active").to_string(),
+                            Column::Source => columns::source(&p.extra).to_string(),
+                            _ => String::new(),
+                        };
+                        columns.iter().map(cell).collect()
+                    })
+                    .collect::<Vec<_>>();
+                (column_headers(), rows)
+            }
+            Tab::Clients => {
+                let rows = self
+                    .clients
+                    .iter()
+                    .map(|c| {
+                        let (completed, total) = stats::client_project_counts(&self.projects, c.id, as_of);
+                        let cell = |column: &Column| match column {
+                            Column::Name => format!("{}{}", star(&c.id), c.display_name()),
+                            Column::Address => c.address.as_deref().unwrap_or("-").to_string(),
+                            Column::Projects => format!("{}/{}", completed, total),
+                            Column::Source => columns::source(&c.extra).to_string(),
+                            _ => String::new(),
+                        };
+                        let mut row: Vec<String> = columns.iter().map(cell).collect();
+                        row.extend(self.scripts.as_ref().and_then(|s| s.client_column(c)));
+                        row
+                    })
+                    .collect::<Vec<_>>();
+                (column_headers(), rows)
+            }
+            Tab::Users => {
+                let rows = self
+                    .users
+                    .iter()
+                    .map(|u| {
+                        let cell = |column: &Column| match column {
+                            Column::Name => u.display_name().to_string(),
+                            Column::Login => u.login.as_deref().unwrap_or("-").to_string(),
+                            Column::Role => match u.role {
+                                Role::Admin => tr!("role-admin").to_string(),
+                                Role::Manager => tr!("role-manager").to_string(),
+                            },
+                            Column::Source => columns::source(&u.extra).to_string(),
+                            _ => String::new(),
+                        };
+                        let mut row: Vec<String> = columns.iter().map(cell).collect();
+                        row.extend(self.scripts.as_ref().and_then(|s| s.user_column(u)));
+                        row
+                    })
+                    .collect::<Vec<_>>();
+                (column_headers(), rows)
+            }
+            Tab::Stats if self.stats_view == StatsView::ClientRanking => {
+                let headers = headers(&[
+                    "#",
+                    tr!("ranking-client"),
+                    tr!("ranking-completed"),
+                    tr!("ranking-on-time"),
+                    tr!("ranking-on-time-percent"),
+                    tr!("ranking-overrun"),
+                ]);
+                let optional = |value: Option<f64>, suffix: &str| value.map_or("-".to_string(), |v| format!("{:.1}{}", v, suffix));
+                let rows = self
+                    .client_ranking()
+                    .into_iter()
+                    .map(|row| {
+                        vec![
+                            row.rank.to_string(),
+                            row.client,
+                            row.completed.to_string(),
+                            row.on_time.to_string(),
+                            optional(row.on_time_percent, "%"),
+                            optional(row.average_overrun_days, tr!("stats-days-suffix")),
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+                (headers, rows)
+            }
+            Tab::Stats | Tab::Inbox | Tab::Audit | Tab::Console | Tab::Plugin(_) => return None,
+        };
+        // Script columns have no name of their own
+        if rows.iter().any(|row| row.len() > headers.len()) {
+            headers.push(tr!("markdown-script").to_string());
+        }
+        Some((headers, rows))
+    }
+
+    /// Write the current view as a Markdown table into the working directory, or copy it
+    fn export_markdown(&mut self, to_clipboard: bool) {
+        let Some((headers, rows)) = self.view_table() else {
+            self.log(LogEntry::warning(tr!("log-markdown-no-table")));
+            return;
+        };
+        let table = markdown::table(&headers, &rows);
+        if to_clipboard {
+            self.pending_clipboard = Some(table);
+            self.log(LogEntry::info(tr!("log-markdown-copied", count = rows.len())));
+            return;
+        }
+        let view = match self.active_tab {
+            Tab::Timeline | Tab::Projects => "projects",
+            Tab::Clients => "clients",
+            Tab::Users => "users",
+            _ => "client-ranking",
+        };
+        let path = format!("sweem-{}-{}.md", view, chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        match std::fs::write(&path, table) {
+            Ok(()) => self.log(LogEntry::success(tr!("log-markdown-exported", count = rows.len(), path = path))),
+            Err(e) => self.log(LogEntry::error(tr!("log-markdown-failed", error = e))),
+        }
+    }
+
+    /// Have the worker store a live load in the snapshot history
+    fn record_snapshot(&mut self, load: Load) {
+        if self.loading_recorded {
+            return;
+        }
+        let Some(store) = &self.store else { return };
+        self.pending_commands.push(ApiCommand::Store(StoreJob::Record(store.clone(), Utc::now(), load)));
+    }
+
+    /// The worker recorded a load
+    fn snapshot_recorded(&mut self, entity: EntityType, result: Result<bool, String>) {
+        match result {
+            // The history page includes today
+            Ok(true) if entity == EntityType::Project => {
+                self.history_points = None;
+                self.history_requested = None;
+                self.load_history_points();
+            }
+            Ok(_) => {}
+            // One warning is enough; the session goes on without history
+            Err(e) => {
+                if self.store.take().is_some() {
+                    self.log(LogEntry::warning(tr!("log-history-failed", error = e)));
                 }
             }
-            ApiMessage::Created(entity_type, id) => {
-                self.log(LogEntry::success(format!("{} created ({})", entity_type, &id.to_string()[..8])));
-                self.close_form();
+        }
+    }
+
+    /// Show recorded data: the latest snapshots (startup) or the ones current
+    /// at `at` (time travel). Returns whether there was a projects snapshot
+    pub fn show_recorded(&mut self, at: Option<DateTime<Utc>>) -> bool {
+        let Some(store) = &self.store else { return false };
+        let loaded = store.load::<UserDto>(EntityType::User, at).and_then(|users| {
+            Ok((users, store.load::<ClientDto>(EntityType::Client, at)?, store.load::<ProjectDto>(EntityType::Project, at)?))
+        });
+        let (users, clients, projects) = match loaded {
+            Ok((users, clients, Some(projects))) => (users, clients, projects),
+            Ok(_) => return false,
+            Err(e) => {
+                self.log(LogEntry::warning(tr!("log-history-failed", error = format!("{:#}", e))));
+                return false;
             }
-            ApiMessage::Updated(entity_type) => {
-                self.log(LogEntry::success(format!("{} updated", entity_type)));
-                self.close_form();
+        };
+        // Same pipeline as live loads, but nothing is recorded, alerted or counted as a refresh
+        self.loading_recorded = true;
+        if let Some(users) = users {
+            self.handle_api_message(ApiMessage::UsersLoaded(users.items));
+        }
+        if let Some(clients) = clients {
+            self.handle_api_message(ApiMessage::ClientsLoaded(clients.items));
+        }
+        self.handle_api_message(ApiMessage::ProjectsLoaded(projects.items));
+        self.loading_recorded = false;
+        self.recorded_at = Some(projects.at);
+        let time = projects.at.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+        self.log(LogEntry::info(tr!("log-history-shown", count = self.projects.len(), time = time)));
+        true
+    }
+
+    /// Back from recorded to live data after time travel ends
+    fn leave_recorded(&mut self) {
+        if self.recorded_at.is_some() && self.api_connected && !self.is_loading {
+            self.is_loading = true;
+            self.pending_commands.push(ApiCommand::RefreshAll);
+        }
+    }
+
+    /// Set or clear (None = today) the time-travel reference date
+    fn set_as_of(&mut self, date: Option<NaiveDate>) {
+        let Some(date) = date else {
+            self.as_of = None;
+            self.log(LogEntry::info(tr!("log-as-of-today")));
+            self.leave_recorded();
+            self.load_history_points();
+            return;
+        };
+
+        self.as_of = Some(date);
+        self.log(LogEntry::info(tr!("log-as-of", date = date)));
+        // A past date shows the data as it was recorded then, if there is any
+        let end_of_day = date
+            .and_time(NaiveTime::MIN)
+            .and_local_timezone(Local)
+            .earliest()
+            .map(|start| start.to_utc() + chrono::Duration::days(1));
+        if date >= crate::clock::today() || end_of_day.is_none_or(|end| !self.show_recorded(Some(end))) {
+            self.leave_recorded();
+        }
+        self.timeline_state.center_on_date(date, &self.projects, 100); // Approximate width
+        self.load_history_points();
+    }
+
+    /// Handle keys in the time-travel date picker
+    fn handle_date_picker_key(&mut self, key: KeyEvent) {
+        let Some(picker) = &mut self.date_picker else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.date_picker = None,
+            // Picking today (or Backspace) goes back to the live view
+            KeyCode::Enter => {
+                let date = picker.date;
+                self.date_picker = None;
+                self.set_as_of((date != crate::clock::today()).then_some(date));
             }
-            ApiMessage::Deleted(entity_type, id) => {
-                self.log(LogEntry::success(format!("{} deleted ({})", entity_type, &id.to_string()[..8])));
-                self.close_confirm();
+            KeyCode::Backspace => {
+                self.date_picker = None;
+                self.set_as_of(None);
+            }
+            code => {
+                if let Some(date) = datepicker::step(picker.date, code) {
+                    picker.date = date;
+                }
             }
         }
     }
 
-    /// Handle key events and return optional API command
-    pub fn handle_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
-        // Handle error popup dismissal
-        if self.error_popup.is_some() {
-            if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char(' ')) {
-                self.dismiss_error();
+    /// Default bookmark name derived from the current viewport
+    fn default_bookmark_name(&self) -> String {
+        self.timeline_state
+            .viewport_start(&self.projects)
+            .format("%b %Y")
+            .to_string()
+    }
+
+    /// Save the current timeline position and zoom into a bookmark slot
+    fn save_bookmark(&mut self, slot: u8, name: String) {
+        let name = if name.is_empty() {
+            self.default_bookmark_name()
+        } else {
+            name
+        };
+        let bookmark = Bookmark {
+            name: name.clone(),
+            start_date: self.timeline_state.viewport_start(&self.projects),
+            days_per_column: self.timeline_state.days_per_column,
+        };
+        self.ui_state.bookmarks.insert(slot, bookmark);
+        self.log(LogEntry::success(tr!("log-bookmark-saved", slot = slot, name = name)));
+        if let Err(e) = self.ui_state.save() {
+            self.log(LogEntry::warning(tr!("log-bookmark-persist-failed", error = e)));
+        }
+    }
+
+    /// Cycle comparison mode: off -> by client -> by manager -> off
+    fn cycle_comparison(&mut self) {
+        self.comparison = match self.comparison.map(|c| c.by) {
+            None => Some(CompareBy::Client),
+            Some(CompareBy::Client) => Some(CompareBy::Manager),
+            Some(CompareBy::Manager) => None,
+        }
+        .map(|by| Comparison { by, top: 0, bottom: 1 });
+
+        match self.comparison {
+            Some(c) => self.log(LogEntry::info(tr!("log-comparing", by = c.by.name().to_lowercase()))),
+            None => self.log(LogEntry::info(tr!("log-comparison-off"))),
+        }
+    }
+
+    /// Number of entities available for the given comparison dimension
+    fn comparison_candidates(&self, by: CompareBy) -> usize {
+        match by {
+            CompareBy::Client => self.clients.len(),
+            CompareBy::Manager => self.users.len(),
+        }
+    }
+
+    /// Step the top or bottom comparison pane to the next/previous entity,
+    /// skipping the one the other pane shows
+    fn cycle_comparison_pane(&mut self, bottom: bool, forward: bool) {
+        let Some(mut comparison) = self.comparison else {
+            return;
+        };
+        let total = self.comparison_candidates(comparison.by);
+        if total == 0 {
+            return;
+        }
+        let step = |idx: usize| {
+            if forward {
+                (idx + 1) % total
+            } else {
+                idx.checked_sub(1).unwrap_or(total - 1).min(total - 1)
             }
-            return None;
+        };
+        let (idx, other) = if bottom {
+            (&mut comparison.bottom, comparison.top)
+        } else {
+            (&mut comparison.top, comparison.bottom)
+        };
+        *idx = step(*idx);
+        if *idx == other && total > 1 {
+            *idx = step(*idx);
         }
+        self.comparison = Some(comparison);
+    }
 
-        // Handle help overlay
-        if self.show_help {
-            if matches!(key.code, KeyCode::Esc | KeyCode::Char('?') | KeyCode::Enter) {
-                self.show_help = false;
+    /// Build the two filtered panes for comparison mode
+    /// Open the burndown chart for the selected client or manager
+    fn open_burndown(&mut self) {
+        self.burndown = match self.active_tab {
+            Tab::Clients => self.clients.get(self.list_selected).map(|c| Burndown {
+                by: CompareBy::Client,
+                id: c.id,
+                label: c.display_name().to_string(),
+            }),
+            Tab::Users => self.selected_user().map(|u| Burndown {
+                by: CompareBy::Manager,
+                id: u.id,
+                label: u.display_name().to_string(),
+            }),
+            _ => None,
+        };
+    }
+
+    /// Projects in the scope of the open burndown chart
+    pub fn burndown_projects(&self) -> Vec<ProjectDto> {
+        let Some(burndown) = &self.burndown else {
+            return Vec::new();
+        };
+        self.projects
+            .iter()
+            .filter(|p| match burndown.by {
+                CompareBy::Client => p.client_id == burndown.id,
+                CompareBy::Manager => p.manager_id == burndown.id,
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn comparison_panes(&self) -> Option<[ComparisonPane; 2]> {
+        let comparison = self.comparison?;
+        let selected_id = self
+            .timeline_state
+            .selected_project
+            .and_then(|i| self.projects.get(i))
+            .map(|p| p.id);
+
+        let pane = |idx: usize| {
+            let (label, owner) = match comparison.by {
+                CompareBy::Client => self
+                    .clients
+                    .get(idx)
+                    .map(|c| (c.display_name().to_string(), c.id))
+                    .unzip(),
+                CompareBy::Manager => self
+                    .users
+                    .get(idx)
+                    .map(|u| (u.display_name().to_string(), u.id))
+                    .unzip(),
+            };
+            let projects: Vec<ProjectDto> = self
+                .projects
+                .iter()
+                .filter(|p| match comparison.by {
+                    CompareBy::Client => Some(p.client_id) == owner,
+                    CompareBy::Manager => Some(p.manager_id) == owner,
+                })
+                .cloned()
+                .collect();
+            let selected = projects.iter().position(|p| Some(p.id) == selected_id);
+            ComparisonPane {
+                label: label.unwrap_or_else(|| tr!("compare-none", by = comparison.by.name().to_lowercase())),
+                projects,
+                selected,
             }
-            return None;
+        };
+
+        Some([pane(comparison.top), pane(comparison.bottom)])
+    }
+
+    /// Jump to a saved bookmark
+    fn jump_to_bookmark(&mut self, slot: u8) {
+        match self.ui_state.bookmarks.get(&slot).cloned() {
+            Some(bookmark) => {
+                self.timeline_state.days_per_column = bookmark.days_per_column;
+                self.timeline_state.scroll_to_date(bookmark.start_date, &self.projects);
+                self.log(LogEntry::info(tr!("log-bookmark-jump", slot = slot, name = bookmark.name)));
+            }
+            None => {
+                self.log(LogEntry::warning(tr!("log-bookmark-empty", slot = slot)));
+            }
+        }
+    }
+
+    /// Open the API console, or leave it for the tab it was opened from
+    fn toggle_console(&mut self) {
+        if self.active_tab == Tab::Console {
+            self.active_tab = self.console_return;
+        } else {
+            self.console_return = self.active_tab;
+            self.active_tab = Tab::Console;
         }
+    }
 
-        // Handle based on input mode
-        match self.input_mode {
-            InputMode::Normal => self.handle_normal_key(key),
-            InputMode::Editing => self.handle_editing_key(key),
-            InputMode::Confirming => self.handle_confirming_key(key),
+    /// Keys in the search popup: typing refines the results, `Enter` jumps to the selected one
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        let Some(search) = &mut self.search else { return };
+        match key.code {
+            KeyCode::Esc => self.search = None,
+            KeyCode::Down => search.selected = (search.selected + 1).min(search.results.len().saturating_sub(1)),
+            KeyCode::Up => search.selected = search.selected.saturating_sub(1),
+            KeyCode::Enter => {
+                let hit = search.results.get(search.selected).map(|doc| (doc.entity_type, doc.id, doc.label.clone()));
+                self.search = None;
+                if let Some((entity_type, id, label)) = hit {
+                    let from = self.place();
+                    if self.focus(entity_type, id) {
+                        self.jumps.jump(from, self.place());
+                    } else {
+                        self.log(LogEntry::warning(tr!("log-search-hidden", name = label)));
+                    }
+                }
+            }
+            KeyCode::Backspace | KeyCode::Char(_) => {
+                match key.code {
+                    KeyCode::Char(c) => search.input.push(c),
+                    _ => {
+                        search.input.pop();
+                    }
+                }
+                search.results = self.search_index.query(&search.input).into_iter().cloned().collect();
+                search.selected = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// Keys on the console tab: everything but Ctrl combinations and tab switching edits the request
+    fn handle_console_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
+        let console = &mut self.console;
+        match key.code {
+            KeyCode::Esc | KeyCode::F(4) => self.toggle_console(),
+            KeyCode::Enter if !console.running => match console.submit() {
+                Ok(request) => return Some(ApiCommand::Console(request)),
+                Err(e) => self.log(LogEntry::warning(tr!("log-console-invalid", error = e))),
+            },
+            KeyCode::Up => console.recall(true),
+            KeyCode::Down => console.recall(false),
+            KeyCode::PageDown => console.scroll = console.scroll.saturating_add(10),
+            KeyCode::PageUp => console.scroll = console.scroll.saturating_sub(10),
+            KeyCode::Backspace => {
+                console.input.pop();
+            }
+            KeyCode::Char(c) => console.input.push(c),
+            _ => {}
         }
+        None
     }
 
     /// Handle keys in normal mode
     fn handle_normal_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
+        if self.active_tab == Tab::Console
+            && !key.modifiers.contains(KeyModifiers::CONTROL)
+            && !matches!(key.code, KeyCode::Tab | KeyCode::BackTab)
+        {
+            return self.handle_console_key(key);
+        }
+
         // Global shortcuts
         match key.code {
             KeyCode::Char('q') | KeyCode::Char('Q') => {
-                self.should_quit = true;
-                return Some(ApiCommand::Shutdown);
+                return self.request_quit();
             }
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.should_quit = true;
@@ -975,26 +3625,66 @@ impl App {
                 self.show_help = true;
                 return None;
             }
+            KeyCode::Char(',') => {
+                self.settings = Some(SettingsDialog::default());
+                return None;
+            }
+            KeyCode::F(2) => {
+                self.toggle_my_projects();
+                return None;
+            }
+            KeyCode::F(3) => {
+                self.show_debug = !self.show_debug;
+                return None;
+            }
+            KeyCode::F(4) => {
+                self.toggle_console();
+                return None;
+            }
+            KeyCode::F(n) if self.run_macro(n) => {
+                return None;
+            }
             KeyCode::Char('p') => {
                 self.particle_system.toggle_mode();
                 let mode = self.particle_system.mode().name();
-                self.log(LogEntry::info(format!("Particle mode: {}", mode)));
+                self.log(LogEntry::info(tr!("log-particle-mode", mode = mode)));
+                return None;
+            }
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.search = Some(Search::default());
+                return None;
+            }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return self.undo_redo(true);
+            }
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.toggle_telemetry();
                 return None;
             }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return self.diagnose();
+            }
+            KeyCode::Char('u') => {
+                return self.undo_redo(false);
+            }
+            // The audit log is not part of the regular refresh
+            KeyCode::Char('r') if self.active_tab == Tab::Audit => {
+                return Some(self.audit.request(self.audit.page));
+            }
             KeyCode::Char('r') => {
                 self.is_loading = true;
-                self.log(LogEntry::info("Refreshing data..."));
+                self.log(LogEntry::info(tr!("log-refreshing")));
                 return Some(ApiCommand::RefreshAll);
             }
             KeyCode::Tab => {
-                self.active_tab = self.active_tab.next();
+                self.active_tab = self.active_tab.next(self.plugins.len());
                 self.list_selected = 0;
-                return None;
+                return self.load_audit_once();
             }
             KeyCode::BackTab => {
-                self.active_tab = self.active_tab.previous();
+                self.active_tab = self.active_tab.previous(self.plugins.len());
                 self.list_selected = 0;
-                return None;
+                return self.load_audit_once();
             }
             // CRUD shortcuts
             KeyCode::Char('c') => {
@@ -1005,18 +3695,120 @@ impl App {
                 self.open_edit_form();
                 return None;
             }
+            KeyCode::Char('D') if matches!(self.active_tab, Tab::Timeline | Tab::Projects) => {
+                self.open_clone_form();
+                return None;
+            }
+            // Batch actions on marked projects
+            KeyCode::Char(' ') if matches!(self.active_tab, Tab::Timeline | Tab::Projects) => {
+                self.toggle_mark();
+                return None;
+            }
+            KeyCode::Esc if matches!(self.active_tab, Tab::Timeline | Tab::Projects) && !self.marked.is_empty() => {
+                self.marked.clear();
+                return None;
+            }
+            KeyCode::Char('R') if matches!(self.active_tab, Tab::Timeline | Tab::Projects) => {
+                self.open_reschedule_prompt();
+                return None;
+            }
+            KeyCode::Char('H') if matches!(self.active_tab, Tab::Timeline | Tab::Projects) => {
+                self.toggle_archived();
+                return None;
+            }
             KeyCode::Char('d') | KeyCode::Delete => {
                 self.open_delete_confirm();
                 return None;
             }
+            KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.export_markdown(true);
+                return None;
+            }
+            KeyCode::Char('E') => {
+                self.export_markdown(false);
+                return None;
+            }
+            KeyCode::Char('J') => {
+                self.open_inspector();
+                return None;
+            }
+            KeyCode::Char('A') => {
+                return self.open_attachments();
+            }
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.copy_selected(key.code == KeyCode::Char('Y'));
+                return None;
+            }
+            KeyCode::Char('*') => {
+                self.toggle_favorite();
+                return None;
+            }
+            KeyCode::Char('W') => {
+                self.toggle_watch();
+                return None;
+            }
+            KeyCode::Char('.') => {
+                self.open_context_menu();
+                return None;
+            }
+            KeyCode::Char('w') => {
+                self.changes_view = Some(0);
+                return None;
+            }
+            KeyCode::Char('/') if self.active_tab == Tab::Audit => {
+                let current = self.audit.search.clone();
+                self.open_prompt(Prompt::new(tr!("prompt-audit-search").to_string(), current, PromptKind::AuditSearch));
+                return None;
+            }
+            KeyCode::Char('/') => {
+                if let Some(entity) = self.active_tab.entity_type() {
+                    let current = self.expression(entity).map(|e| e.as_str().to_string()).unwrap_or_default();
+                    self.open_prompt(Prompt::new(tr!("prompt-filter", entity = entity), current, PromptKind::Filter(entity)));
+                }
+                return None;
+            }
             _ => {}
         }
 
         // Tab-specific shortcuts
         match self.active_tab {
             Tab::Timeline => self.handle_timeline_key(key),
-            Tab::Clients => self.handle_list_key(key, self.clients.len()),
-            Tab::Users => self.handle_list_key(key, self.users.len()),
+            Tab::Projects | Tab::Clients | Tab::Users if key.code == KeyCode::Char('C') => {
+                self.column_dialog = Some((self.active_tab, 0));
+            }
+            Tab::Projects => self.handle_projects_key(key),
+            Tab::Clients | Tab::Users if key.code == KeyCode::Char('b') => self.open_burndown(),
+            Tab::Clients | Tab::Users if key.code == KeyCode::Char('s') => self.sort_dialog = Some((self.active_tab, 0)),
+            Tab::Clients => {
+                self.handle_list_key(key, self.clients.len());
+                self.load_next_page(EntityType::Client);
+            }
+            Tab::Users if key.code == KeyCode::Char('v') => self.toggle_team_view(),
+            Tab::Users if self.team_view.is_some() => {
+                self.handle_team_key(key);
+                self.load_next_page(EntityType::User);
+            }
+            Tab::Users => {
+                self.handle_list_key(key, self.users.len());
+                self.load_next_page(EntityType::User);
+            }
+            Tab::Stats => self.handle_stats_key(key),
+            Tab::Inbox => self.handle_inbox_key(key),
+            Tab::Audit => return self.handle_audit_key(key),
+            // Handled before the global shortcuts
+            Tab::Console => {}
+            Tab::Plugin(index) => {
+                let reference_date = self.reference_date();
+                let ctx = PluginContext {
+                    projects: &self.projects,
+                    clients: &self.clients,
+                    users: &self.users,
+                    reference_date,
+                };
+                if let Some(plugin) = self.plugins.get_mut(index) {
+                    return plugin.handle_key(key, &ctx);
+                }
+            }
         }
 
         None
@@ -1024,9 +3816,31 @@ impl App {
 
     /// Handle keys in editing mode (form)
     fn handle_editing_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
-        if self.form_state.is_none() {
+        let Some(form) = &mut self.form_state else {
             self.input_mode = InputMode::Normal;
             return None;
+        };
+        if form.picker.is_some() {
+            return self.handle_picker_key(key);
+        }
+        if form.move_date(key.code) {
+            return None;
+        }
+        // Typing or Enter on the client or manager field opens its picker
+        if let Some(entity) = form.current_field().picker_entity() {
+            if let KeyCode::Char(_) | KeyCode::Enter = key.code {
+                let candidates = picker::candidates(entity, &self.loaded.clients, &self.loaded.users);
+                let current = match entity {
+                    EntityType::Client => form.project_client_idx,
+                    _ => form.project_manager_idx,
+                };
+                let mut opened = Picker::new(&candidates, current);
+                if let KeyCode::Char(c) = key.code {
+                    opened.push(c, &candidates);
+                }
+                form.picker = Some(opened);
+                return None;
+            }
         }
 
         match key.code {
@@ -1042,91 +3856,53 @@ impl App {
             }
             KeyCode::BackTab => {
                 if let Some(form) = &mut self.form_state {
-                    form.prev_field();
-                }
-                return None;
-            }
-            KeyCode::Enter => {
-                return self.handle_form_submit();
-            }
-            KeyCode::Backspace => {
-                if let Some(form) = &mut self.form_state {
-                    form.handle_backspace();
-                }
-                return None;
-            }
-            KeyCode::Up => {
-                if let Some(form) = &mut self.form_state {
-                    let field = form.current_field();
-                    if field.is_date_picker() {
-                        // Date picker: Up increases the date
-                        form.increment_date();
-                    } else {
-                        match field {
-                            FormField::ProjectClient => {
-                                if form.project_client_idx > 0 {
-                                    form.project_client_idx -= 1;
-                                }
-                            }
-                            FormField::ProjectManager => {
-                                if form.project_manager_idx > 0 {
-                                    form.project_manager_idx -= 1;
-                                }
-                            }
-                            FormField::UserRole => {
-                                form.user_role = form.user_role.next();
-                            }
-                            _ => {}
-                        }
-                    }
+                    form.prev_field();
                 }
                 return None;
             }
-            KeyCode::Down => {
+            KeyCode::Enter => {
+                return self.handle_form_submit();
+            }
+            KeyCode::Backspace => {
                 if let Some(form) = &mut self.form_state {
-                    let field = form.current_field();
-                    if field.is_date_picker() {
-                        // Date picker: Down decreases the date
-                        form.decrement_date();
-                    } else {
-                        match field {
-                            FormField::ProjectClient => {
-                                if form.project_client_idx < self.clients.len().saturating_sub(1) {
-                                    form.project_client_idx += 1;
-                                }
-                            }
-                            FormField::ProjectManager => {
-                                if form.project_manager_idx < self.users.len().saturating_sub(1) {
-                                    form.project_manager_idx += 1;
-                                }
-                            }
-                            FormField::UserRole => {
-                                form.user_role = form.user_role.next();
-                            }
-                            _ => {}
-                        }
-                    }
+                    form.handle_backspace();
                 }
                 return None;
             }
-            KeyCode::Left => {
+            KeyCode::Up => {
                 if let Some(form) = &mut self.form_state {
-                    if form.current_field().is_date_picker() {
-                        // Date picker: Left decreases by 7 days (one week)
-                        for _ in 0..7 {
-                            form.decrement_date();
+                    match form.current_field() {
+                        FormField::ProjectClient if form.project_client_idx > 0 => {
+                            form.project_client_idx -= 1;
+                        }
+                        FormField::ProjectManager if form.project_manager_idx > 0 => {
+                            form.project_manager_idx -= 1;
+                        }
+                        FormField::UserRole => {
+                            form.user_role = form.user_role.next();
                         }
+                        _ => {}
                     }
                 }
                 return None;
             }
-            KeyCode::Right => {
+            KeyCode::Down => {
                 if let Some(form) = &mut self.form_state {
-                    if form.current_field().is_date_picker() {
-                        // Date picker: Right increases by 7 days (one week)
-                        for _ in 0..7 {
-                            form.increment_date();
+                    match form.current_field() {
+                        FormField::ProjectClient
+                            if form.project_client_idx < self.loaded.clients.len().saturating_sub(1) =>
+                        {
+                            form.project_client_idx += 1;
+                        }
+                        FormField::ProjectManager
+                            if form.project_manager_idx < self.loaded.users.len().saturating_sub(1) =>
+                        {
+                            form.project_manager_idx += 1;
                         }
+                        FormField::UserRole => {
+                            form.user_role = form.user_role.next();
+                        }
+                        _ => {}
                     }
                 }
                 return None;
@@ -1143,6 +3919,34 @@ impl App {
         None
     }
 
+    /// Handle keys while the client or manager picker of a form is open
+    fn handle_picker_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
+        let form = self.form_state.as_mut()?;
+        let entity = form.current_field().picker_entity()?;
+        let candidates = picker::candidates(entity, &self.loaded.clients, &self.loaded.users);
+        let picker = form.picker.as_mut()?;
+        match key.code {
+            KeyCode::Esc => form.picker = None,
+            KeyCode::Enter => {
+                if let Some(index) = picker.choice() {
+                    match entity {
+                        EntityType::Client => form.project_client_idx = index,
+                        _ => form.project_manager_idx = index,
+                    }
+                    form.next_field();
+                }
+            }
+            KeyCode::Tab => form.next_field(),
+            KeyCode::BackTab => form.prev_field(),
+            KeyCode::Up => picker.select(false),
+            KeyCode::Down => picker.select(true),
+            KeyCode::Backspace => picker.pop(&candidates),
+            KeyCode::Char(c) => picker.push(c, &candidates),
+            _ => {}
+        }
+        None
+    }
+
     /// Handle form submission
     fn handle_form_submit(&mut self) -> Option<ApiCommand> {
         // Extract data we need from form before borrowing self mutably
@@ -1180,8 +3984,9 @@ impl App {
                     }
                     return None;
                 }
-                self.log(LogEntry::info("Creating client..."));
-                Some(ApiCommand::CreateClient(dto))
+                self.log(LogEntry::info(tr!("log-creating-client")));
+                let operation = Operation::CreateClient { id: Uuid::nil(), dto: dto.clone() };
+                Some(self.history.begin(operation, Direction::Do, ApiCommand::CreateClient(dto)))
             }
             FormType::EditClient(id) => {
                 let form = self.form_state.as_ref()?;
@@ -1192,32 +3997,43 @@ impl App {
                     }
                     return None;
                 }
-                self.log(LogEntry::info("Updating client..."));
-                Some(ApiCommand::UpdateClient(id, dto))
+                self.log(LogEntry::info(tr!("log-updating-client")));
+                let command = ApiCommand::UpdateClient(id, dto.clone());
+                let Some(current) = self.loaded.clients.iter().find(|e| e.id == id) else {
+                    return Some(command);
+                };
+                let before = UpdateClientDto::from_client(current);
+                Some(self.history.begin(Operation::UpdateClient { id, before, after: dto }, Direction::Do, command))
             }
             FormType::CreateProject => {
                 let form = self.form_state.as_ref()?;
-                let dto = form.build_create_project(&self.clients, &self.users);
+                let dto = form.build_create_project(&self.loaded.clients, &self.loaded.users);
                 if let Err(e) = dto.validate() {
                     if let Some(f) = &mut self.form_state {
                         f.error = Some(e.to_string());
                     }
                     return None;
                 }
-                self.log(LogEntry::info("Creating project..."));
-                Some(ApiCommand::CreateProject(dto))
+                self.log(LogEntry::info(tr!("log-creating-project")));
+                let operation = Operation::CreateProject { id: Uuid::nil(), dto: dto.clone() };
+                Some(self.history.begin(operation, Direction::Do, ApiCommand::CreateProject(dto)))
             }
             FormType::EditProject(id) => {
                 let form = self.form_state.as_ref()?;
-                let dto = form.build_update_project(&self.clients, &self.users);
+                let dto = form.build_update_project(&self.loaded.clients, &self.loaded.users);
                 if let Err(e) = dto.validate() {
                     if let Some(f) = &mut self.form_state {
                         f.error = Some(e.to_string());
                     }
                     return None;
                 }
-                self.log(LogEntry::info("Updating project..."));
-                Some(ApiCommand::UpdateProject(id, dto))
+                self.log(LogEntry::info(tr!("log-updating-project")));
+                let command = ApiCommand::UpdateProject(id, dto.clone());
+                let Some(current) = self.projects.iter().find(|e| e.id == id) else {
+                    return Some(command);
+                };
+                let before = UpdateProjectDto::from_project(current);
+                Some(self.history.begin(Operation::UpdateProject { id, before, after: dto }, Direction::Do, command))
             }
             FormType::CreateUser => {
                 let form = self.form_state.as_ref()?;
@@ -1228,8 +4044,9 @@ impl App {
                     }
                     return None;
                 }
-                self.log(LogEntry::info("Creating user..."));
-                Some(ApiCommand::CreateUser(dto))
+                self.log(LogEntry::info(tr!("log-creating-user")));
+                let operation = Operation::CreateUser { id: Uuid::nil(), dto: dto.clone() };
+                Some(self.history.begin(operation, Direction::Do, ApiCommand::CreateUser(dto)))
             }
             FormType::EditUser(id) => {
                 let form = self.form_state.as_ref()?;
@@ -1240,8 +4057,13 @@ impl App {
                     }
                     return None;
                 }
-                self.log(LogEntry::info("Updating user..."));
-                Some(ApiCommand::UpdateUser(id, dto))
+                self.log(LogEntry::info(tr!("log-updating-user")));
+                let command = ApiCommand::UpdateUser(id, dto.clone());
+                let Some(current) = self.loaded.users.iter().find(|e| e.id == id) else {
+                    return Some(command);
+                };
+                let before = UpdateUserDto::from_user(current);
+                Some(self.history.begin(Operation::UpdateUser { id, before, after: dto }, Direction::Do, command))
             }
         }
     }
@@ -1252,6 +4074,23 @@ impl App {
             self.input_mode = InputMode::Normal;
             return None;
         }
+        if let Some(dialog) = self.confirm_dialog.as_mut().filter(|d| d.confirm_word.is_some()) {
+            // Typing the name: letters are input, not y/n shortcuts
+            match key.code {
+                KeyCode::Esc => self.close_confirm(),
+                KeyCode::Backspace => {
+                    dialog.typed.pop();
+                }
+                KeyCode::Char(c) => dialog.typed.push(c),
+                KeyCode::Enter if dialog.word_matches() => {
+                    let action = dialog.action;
+                    return self.confirm_action(action);
+                }
+                KeyCode::Enter => self.log(LogEntry::warning(tr!("log-production-mismatch"))),
+                _ => {}
+            }
+            return None;
+        }
 
         match key.code {
             KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
@@ -1267,13 +4106,7 @@ impl App {
             KeyCode::Enter => {
                 if let Some(dialog) = &self.confirm_dialog {
                     if dialog.yes_focused {
-                        let cmd = match dialog.entity_type {
-                            EntityType::Client => ApiCommand::DeleteClient(dialog.entity_id),
-                            EntityType::Project => ApiCommand::DeleteProject(dialog.entity_id),
-                            EntityType::User => ApiCommand::DeleteUser(dialog.entity_id),
-                        };
-                        self.log(LogEntry::info(format!("Deleting {}...", dialog.entity_type)));
-                        return Some(cmd);
+                        return self.confirm_action(dialog.action);
                     } else {
                         self.close_confirm();
                     }
@@ -1282,13 +4115,7 @@ impl App {
             }
             KeyCode::Char('y') | KeyCode::Char('Y') => {
                 if let Some(dialog) = &self.confirm_dialog {
-                    let cmd = match dialog.entity_type {
-                        EntityType::Client => ApiCommand::DeleteClient(dialog.entity_id),
-                        EntityType::Project => ApiCommand::DeleteProject(dialog.entity_id),
-                        EntityType::User => ApiCommand::DeleteUser(dialog.entity_id),
-                    };
-                    self.log(LogEntry::info(format!("Deleting {}...", dialog.entity_type)));
-                    return Some(cmd);
+                    return self.confirm_action(dialog.action);
                 }
                 return None;
             }
@@ -1298,9 +4125,535 @@ impl App {
         None
     }
 
+    /// Add a plugin tab after the built-in tabs
+    pub fn register_plugin(&mut self, plugin: Box<dyn TabPlugin>) {
+        self.log(LogEntry::info(tr!("log-plugin-registered", title = plugin.title())));
+        self.plugins.push(plugin);
+    }
+
+    /// Title of a tab as shown in the tab bar
+    pub fn tab_title(&self, tab: Tab) -> &str {
+        match tab {
+            Tab::Plugin(index) => self.plugins.get(index).map(|p| p.title()).unwrap_or("?"),
+            tab => tab.name(),
+        }
+    }
+
+    /// Read-only data view handed to plugins
+    pub fn plugin_context(&self) -> PluginContext<'_> {
+        PluginContext {
+            projects: &self.projects,
+            clients: &self.clients,
+            users: &self.users,
+            reference_date: self.reference_date(),
+        }
+    }
+
+    /// Apply user configuration, including where the session starts
+    pub fn apply_config(&mut self, config: Config) {
+        self.particle_system.set_density(config.particles.density);
+        if !config.particles.enabled {
+            self.particle_system.set_mode(ParticleMode::None);
+        } else if let Some(mode) = config.particles.mode {
+            self.particle_system.set_mode(mode);
+        }
+        self.active_tab = config.ui.start_tab.into();
+        self.timeline_state.days_per_column = config.ui.timeline_zoom;
+        self.config = config;
+        // Starting on the audit log loads it, as switching to it does
+        let audit = self.load_audit_once();
+        self.pending_commands.extend(audit);
+    }
+
+    /// Log projects that newly crossed a deadline and queue their notifications
+    fn check_deadlines(&mut self, projects: &[ProjectDto]) {
+        let now = crate::clock::now();
+        for alert in self.deadlines.check(projects, now) {
+            self.log(LogEntry::warning(alert.message()));
+            if self.config.notifications.allows(now.time()) {
+                self.pending_notifications.push(alert);
+            }
+        }
+    }
+
+    /// Evaluate the `[[alerts]]` rules against the change since the last refresh:
+    /// fired rules go to the log and the Inbox, and to the desktop if the rule asks;
+    /// returns the lines for the `[webhook]`
+    fn check_alert_rules(&mut self, projects: &[ProjectDto]) -> Vec<String> {
+        let now = crate::clock::now();
+        let hits = self.rule_watch.check(&self.config.alerts, projects, &self.loaded.clients, &self.loaded.users, now.date());
+        if hits.is_empty() {
+            return Vec::new();
+        }
+        let mut events = Vec::new();
+        let mut webhook = Vec::new();
+        for hit in hits {
+            let line = tr!("log-alert-rule", rule = hit.rule, name = hit.name, detail = hit.detail);
+            if self.config.webhook.alerts {
+                webhook.push(line.clone());
+            }
+            self.log(LogEntry::warning(line));
+            if hit.notify && self.config.notifications.allows(now.time()) {
+                self.pending_notifications.push(Alert {
+                    kind: AlertKind::Rule,
+                    project: hit.name.clone(),
+                    rule: Some((hit.rule.clone(), hit.detail.clone())),
+                });
+            }
+            events.push(InboxEvent::Rule { project: hit.project, name: hit.name, rule: hit.rule, detail: hit.detail });
+        }
+        self.ui_state.inbox.add(events, now);
+        self.save_inbox();
+        webhook
+    }
+
+    /// Lines for the `[webhook]` about projects newly breaking an SLA rule
+    fn check_sla_breaches(&mut self, projects: &[ProjectDto]) -> Vec<String> {
+        if self.config.webhook.url.is_none() || !self.config.webhook.sla {
+            return Vec::new();
+        }
+        self.breach_watch
+            .check(&self.config.sla, projects, crate::clock::today())
+            .into_iter()
+            .map(|(rule, project)| tr!("webhook-sla-breach", rule = rule.name, name = project.display_name()))
+            .collect()
+    }
+
+    /// Add inbox alerts for freshly loaded (unfiltered) projects
+    fn update_inbox(&mut self, projects: &[ProjectDto]) {
+        let me = self.my_user().map(|u| u.id);
+        let due_days = self.config.notifications.inbox_due_days;
+        let refreshed = self.ui_state.inbox.refresh(projects, me, crate::clock::now(), due_days);
+        if refreshed.added > 0 {
+            self.log(LogEntry::info(tr!("log-inbox-new", count = refreshed.added)));
+        }
+        // Most refreshes change nothing, and state.json need not be rewritten for them
+        if refreshed.changed {
+            self.save_inbox();
+        }
+    }
+
+    fn save_inbox(&mut self) {
+        if let Err(e) = self.ui_state.save() {
+            self.log(LogEntry::warning(tr!("log-inbox-persist-failed", error = e)));
+        }
+    }
+
+    /// Months shown in the Stats tab: first day of the first month, first day after the range
+    pub fn stats_range(&self) -> (NaiveDate, NaiveDate) {
+        let end = stats::add_months(stats::month_start(self.reference_date()), self.stats_offset + 1);
+        (stats::add_months(end, -(self.stats_months as i32)), end)
+    }
+
+    /// Handle keys in the Stats tab: move and resize the time range
+    fn handle_stats_key(&mut self, key: KeyEvent) {
+        let months = self.stats_months as i32;
+        match key.code {
+            KeyCode::Char('h') | KeyCode::Left => self.stats_offset -= 1,
+            KeyCode::Char('l') | KeyCode::Right => self.stats_offset = (self.stats_offset + 1).min(0),
+            KeyCode::Char('H') => self.stats_offset -= months,
+            KeyCode::Char('L') => self.stats_offset = (self.stats_offset + months).min(0),
+            KeyCode::Char('t') => self.stats_offset = 0,
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.stats_months = STATS_RANGES.iter().rev().copied().find(|&m| m < self.stats_months).unwrap_or(self.stats_months);
+            }
+            KeyCode::Char('-') => {
+                self.stats_months = STATS_RANGES.iter().copied().find(|&m| m > self.stats_months).unwrap_or(self.stats_months);
+            }
+            KeyCode::Char('v') => {
+                self.stats_view = match self.stats_view {
+                    StatsView::Trends => StatsView::ClientRanking,
+                    StatsView::ClientRanking => StatsView::History,
+                    StatsView::History => StatsView::Trends,
+                };
+                self.list_selected = 0;
+            }
+            _ if self.stats_view == StatsView::ClientRanking => match key.code {
+                KeyCode::Char('s') => self.ranking_sort = (self.ranking_sort.0.next(), false),
+                KeyCode::Char('S') => self.ranking_sort.1 = !self.ranking_sort.1,
+                KeyCode::Char('x') => self.export_client_ranking(),
+                _ => self.handle_list_key(key, self.clients.len()),
+            },
+            _ => {}
+        }
+        self.load_history_points();
+    }
+
+    /// Range of the Stats history page: the Stats range, up to the reference date
+    pub fn history_range(&self) -> (NaiveDate, NaiveDate) {
+        let (from, to) = self.stats_range();
+        (from, to.min(self.reference_date() + chrono::Duration::days(1)))
+    }
+
+    /// Ask the worker for the history page's points, unless they are loaded
+    /// or asked for for the current range already
+    fn load_history_points(&mut self) {
+        let range = self.history_range();
+        if self.stats_view != StatsView::History
+            || self.history_points.as_ref().is_some_and(|(loaded, _)| *loaded == range)
+            || self.history_requested == Some(range)
+        {
+            return;
+        }
+        let Some(store) = &self.store else {
+            self.history_points = None;
+            return;
+        };
+        self.history_requested = Some(range);
+        self.pending_commands.push(ApiCommand::Store(StoreJob::History(store.clone(), range)));
+    }
+
+    /// Points of the history page arrived; those of a range no longer shown are dropped
+    fn history_loaded(&mut self, range: (NaiveDate, NaiveDate), result: Result<Vec<DailyPoint>, String>) {
+        if self.history_requested == Some(range) {
+            self.history_requested = None;
+        }
+        if range != self.history_range() {
+            return;
+        }
+        match result {
+            Ok(points) => self.history_points = Some((range, points)),
+            Err(e) => {
+                self.history_points = None;
+                self.log(LogEntry::warning(tr!("log-history-failed", error = e)));
+            }
+        }
+    }
+
+    /// Clients ranked for the Stats tab's range and order
+    pub fn client_ranking(&self) -> Vec<ClientRankingRow> {
+        export::client_ranking(&self.projects, &self.clients, self.stats_range(), self.reference_date(), self.ranking_sort)
+    }
+
+    /// Write the client ranking as CSV to the working directory
+    fn export_client_ranking(&mut self) {
+        let rows = self.client_ranking();
+        let path = format!("sweem-client-ranking-{}.csv", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        let result = serde_json::to_value(&rows)
+            .map_err(anyhow::Error::from)
+            .and_then(|value| {
+                let rows = value.as_array().cloned().unwrap_or_default();
+                let file = std::fs::File::create(&path)?;
+                export::write_csv(&rows, file)
+            });
+        match result {
+            Ok(()) => self.log(LogEntry::success(tr!("log-ranking-exported", count = rows.len(), path = path))),
+            Err(e) => self.log(LogEntry::error(tr!("log-ranking-failed", error = e))),
+        }
+    }
+
+    /// Handle keys in the Inbox tab
+    fn handle_inbox_key(&mut self, key: KeyEvent) {
+        let index = self.list_selected;
+        match key.code {
+            KeyCode::Enter => {
+                let Some(item) = self.ui_state.inbox.items.get_mut(index) else {
+                    return;
+                };
+                item.read = true;
+                let entity = item.event.entity();
+                self.save_inbox();
+                // Show the project on the timeline (or the client), if it is still loaded
+                if let Some((entity_type, id)) = entity {
+                    self.focus(entity_type, id);
+                }
+            }
+            KeyCode::Char(' ') => {
+                self.ui_state.inbox.toggle_read(index);
+                self.save_inbox();
+            }
+            KeyCode::Char('a') => {
+                self.ui_state.inbox.mark_all_read();
+                self.save_inbox();
+            }
+            KeyCode::Char('x') => {
+                self.ui_state.inbox.dismiss(index);
+                self.list_selected = index.min(self.ui_state.inbox.items.len().saturating_sub(1));
+                self.save_inbox();
+            }
+            _ => self.handle_list_key(key, self.ui_state.inbox.items.len()),
+        }
+    }
+
+    /// Keys on the audit tab: paging and jumping to the affected entity
+    fn handle_audit_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
+        match key.code {
+            KeyCode::Char(']') | KeyCode::PageDown => return self.turn_audit_page(true),
+            KeyCode::Char('[') | KeyCode::PageUp => return self.turn_audit_page(false),
+            KeyCode::Enter => {
+                let (entity_type, id) = self.audit.events().get(self.list_selected).and_then(audit::target)?;
+                let from = self.place();
+                if self.focus(entity_type, id) {
+                    self.jumps.jump(from, self.place());
+                } else {
+                    // Deleted since, or not loaded
+                    self.log(LogEntry::warning(tr!("log-focus-not-found", entity = entity_type, id = id)));
+                }
+            }
+            _ => self.handle_list_key(key, self.audit.events().len()),
+        }
+        None
+    }
+
+    /// Next or previous page of the audit log, from the prefetched one if it is there
+    fn turn_audit_page(&mut self, forward: bool) -> Option<ApiCommand> {
+        let command = self.audit.turn(forward);
+        self.list_selected = 0;
+        if let Some(ApiCommand::FetchAudit { page, search }) = &command {
+            if let Some(result) = self.prefetched.take_audit(*page, search) {
+                self.audit.receive(*page, search, Ok(result));
+                return None;
+            }
+        }
+        command
+    }
+
+    /// First page of the audit log when its tab is shown for the first time
+    fn load_audit_once(&mut self) -> Option<ApiCommand> {
+        (self.active_tab == Tab::Audit && matches!(self.audit.feed, AuditFeed::NotLoaded)).then(|| self.audit.request(1))
+    }
+
+    /// Fire a fireworks burst near a project's bar that just got completed
+    fn celebrate_completion(&mut self, index: usize) {
+        let Some(project) = self.projects.get(index).cloned() else {
+            return;
+        };
+        self.log(LogEntry::success(tr!("log-project-completed", name = project.display_name())));
+
+        let (width, height) = self.screen_size;
+        // From the end of the project's bar, where the timeline last drew it
+        let (x, y) = match self.timeline_state.rows_area.get() {
+            Some(rows) if self.active_tab == Tab::Timeline && self.comparison.is_none() && !rows.is_empty() => {
+                let start = self.timeline_state.calculate_timeline_start(&self.projects);
+                let end = project.actual_end_date.unwrap_or(project.planned_end_date);
+                let column = (rows.x + NAME_COLUMN_WIDTH) as i64 + self.timeline_state.date_column(end, start);
+                let x = column.clamp(rows.x as i64, rows.right().saturating_sub(1) as i64) as u16;
+                let y = (rows.y as usize + index).min(rows.bottom().saturating_sub(1) as usize) as u16;
+                (x, y)
+            }
+            _ => (width / 2, height / 2),
+        };
+        self.particle_system.burst(x, y);
+    }
+
+    /// Perform the accepted confirmation action
+    fn confirm_action(&mut self, action: ConfirmAction) -> Option<ApiCommand> {
+        match action {
+            ConfirmAction::Delete(entity_type, id) => {
+                let cmd = match entity_type {
+                    EntityType::Client => ApiCommand::DeleteClient(id),
+                    EntityType::Project => ApiCommand::DeleteProject(id),
+                    EntityType::User => ApiCommand::DeleteUser(id),
+                };
+                self.log(LogEntry::info(tr!("log-deleting", entity = entity_type)));
+                Some(cmd)
+            }
+            ConfirmAction::CommitSandbox => {
+                self.close_confirm();
+                let sandbox = self.sandbox.take()?;
+                let batch = sandbox.commit_batch(&self.projects);
+                let before = batch
+                    .iter()
+                    .filter_map(|(id, _)| sandbox.baseline().iter().find(|p| p.id == *id))
+                    .map(|p| (p.id, UpdateProjectDto::from_project(p)))
+                    .collect();
+                self.log(LogEntry::info(tr!("log-committing", count = batch.len())));
+                let operation = Operation::Reschedule { before, after: batch.clone() };
+                Some(self.history.begin(operation, Direction::Do, ApiCommand::BatchUpdateProjects(batch)))
+            }
+            ConfirmAction::Reschedule(days) => {
+                self.close_confirm();
+                let (before, after): (Vec<_>, Vec<_>) = self
+                    .rescheduled(days)
+                    .into_iter()
+                    .map(|(project, moved)| {
+                        (
+                            (project.id, UpdateProjectDto::from_project(project)),
+                            (project.id, UpdateProjectDto::from_project(&moved)),
+                        )
+                    })
+                    .unzip();
+                if after.is_empty() {
+                    return None;
+                }
+                self.log(LogEntry::info(tr!("log-rescheduling", count = after.len(), days = days)));
+                let operation = Operation::Reschedule { before, after: after.clone() };
+                Some(self.history.begin(operation, Direction::Do, ApiCommand::BatchUpdateProjects(after)))
+            }
+            ConfirmAction::Quit => {
+                self.close_confirm();
+                self.should_quit = true;
+                Some(ApiCommand::Shutdown)
+            }
+            ConfirmAction::UndoRedo(redo) => {
+                self.close_confirm();
+                self.start_undo_redo(redo)
+            }
+        }
+    }
+
+    /// Quit, or ask first while quitting would lose work (`Ctrl+C` always quits)
+    fn request_quit(&mut self) -> Option<ApiCommand> {
+        let pending = self.pending_work();
+        if pending.is_empty() {
+            self.should_quit = true;
+            return Some(ApiCommand::Shutdown);
+        }
+        self.open_confirm(ConfirmDialog::new_quit(pending));
+        None
+    }
+
+    /// Work that quitting now would lose or leave unfinished, one line each:
+    /// a change awaiting the server, a running download, uncommitted sandbox changes
+    pub fn pending_work(&self) -> Vec<String> {
+        let mut pending = Vec::new();
+        for label in self.history.in_flight_labels() {
+            pending.push(tr!("quit-pending-change", what = label));
+        }
+        if let Some(download) = &self.download {
+            let received = attachments::format_size(download.received);
+            pending.push(tr!("quit-pending-download", name = download.file_name.clone(), received = received));
+        }
+        let changes = self.sandbox.as_ref().map_or(0, |sandbox| sandbox.changes(&self.projects).len());
+        if changes > 0 {
+            pending.push(tr!("quit-pending-sandbox", count = changes));
+        }
+        pending
+    }
+
+    /// Enter the what-if sandbox, or ask to commit when already in it
+    fn toggle_sandbox(&mut self) {
+        match &self.sandbox {
+            None => {
+                self.sandbox = Some(Sandbox::new(&self.projects));
+                self.log(LogEntry::info(tr!("log-sandbox-on")));
+            }
+            Some(sandbox) => {
+                let changes = sandbox.changes(&self.projects).len();
+                if changes == 0 {
+                    self.sandbox = None;
+                    self.log(LogEntry::info(tr!("log-sandbox-closed")));
+                } else {
+                    self.open_confirm(ConfirmDialog::new_commit_sandbox(changes));
+                }
+            }
+        }
+    }
+
+    /// Whether project changes have to wait for the sandbox to close, saying so:
+    /// its dates are hypothetical and must not reach the server one by one
+    fn blocked_by_sandbox(&mut self) -> bool {
+        if self.sandbox.is_some() {
+            self.log(LogEntry::warning(tr!("log-sandbox-blocked")));
+        }
+        self.sandbox.is_some()
+    }
+
+    /// Leave the sandbox and restore the baseline schedule
+    fn discard_sandbox(&mut self) {
+        if let Some(sandbox) = self.sandbox.take() {
+            self.projects = sandbox.baseline().to_vec();
+            self.log(LogEntry::warning(tr!("log-sandbox-discarded")));
+        }
+    }
+
+    /// Apply a local edit to the selected project (sandbox only)
+    fn edit_selected_in_sandbox(&mut self, edit: impl FnOnce(&mut ProjectDto)) {
+        if self.sandbox.is_none() {
+            return;
+        }
+        if let Some(project) = self
+            .timeline_state
+            .selected_project
+            .and_then(|idx| self.projects.get_mut(idx))
+        {
+            edit(project);
+        }
+    }
+
     /// Handle timeline-specific key events
     fn handle_timeline_key(&mut self, key: KeyEvent) {
+        // Second key of `m<1-9>`: pick the bookmark slot
+        if self.pending_bookmark {
+            self.pending_bookmark = false;
+            if let KeyCode::Char(c @ '1'..='9') = key.code {
+                let slot = c as u8 - b'0';
+                let name = self
+                    .ui_state
+                    .bookmarks
+                    .get(&slot)
+                    .map(|b| b.name.clone())
+                    .unwrap_or_else(|| self.default_bookmark_name());
+                self.open_prompt(Prompt::new(
+                    tr!("prompt-bookmark", slot = slot),
+                    name,
+                    PromptKind::BookmarkName(slot),
+                ));
+            }
+            return;
+        }
+
         match key.code {
+            KeyCode::Char('m') => {
+                self.pending_bookmark = true;
+                self.log(LogEntry::info(tr!("log-bookmark-pick-slot")));
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                self.jump_to_bookmark(c as u8 - b'0');
+            }
+            KeyCode::Char('v') => {
+                self.cycle_comparison();
+            }
+            KeyCode::Char('T') => {
+                self.active_tab = Tab::Projects;
+            }
+            KeyCode::Char('M') => {
+                self.export_mermaid();
+            }
+            KeyCode::Char('S') => {
+                self.toggle_sandbox();
+            }
+            KeyCode::Char('X') if self.sandbox.is_some() => {
+                self.discard_sandbox();
+            }
+            KeyCode::Char('<') => self.edit_selected_in_sandbox(|p| Sandbox::shift(p, -1)),
+            KeyCode::Char('>') => self.edit_selected_in_sandbox(|p| Sandbox::shift(p, 1)),
+            KeyCode::Char('{') => self.edit_selected_in_sandbox(|p| Sandbox::extend(p, -1)),
+            KeyCode::Char('}') => self.edit_selected_in_sandbox(|p| Sandbox::extend(p, 1)),
+            KeyCode::Char('@') => {
+                self.date_picker = Some(DatePicker { date: self.reference_date() });
+            }
+            KeyCode::Char('n') if self.comparison.is_some() => {
+                self.cycle_comparison_pane(false, true);
+            }
+            KeyCode::Char('N') if self.comparison.is_some() => {
+                self.cycle_comparison_pane(false, false);
+            }
+            KeyCode::Char('b') if self.comparison.is_some() => {
+                self.cycle_comparison_pane(true, true);
+            }
+            KeyCode::Char('B') if self.comparison.is_some() => {
+                self.cycle_comparison_pane(true, false);
+            }
+            // Not Ctrl+h: most terminals send it as Backspace
+            KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::ALT) => {
+                self.timeline_state.snap_left(&self.projects);
+            }
+            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::ALT) => {
+                self.timeline_state.snap_right(&self.projects);
+            }
+            KeyCode::Left if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.timeline_state.snap_left(&self.projects);
+            }
+            KeyCode::Right if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.timeline_state.snap_right(&self.projects);
+            }
+            KeyCode::Char('[') => {
+                self.timeline_state.snap_left(&self.projects);
+            }
+            KeyCode::Char(']') => {
+                self.timeline_state.snap_right(&self.projects);
+            }
             KeyCode::Char('h') | KeyCode::Left => {
                 let amount = if key.modifiers.contains(KeyModifiers::SHIFT) { 7 } else { 1 };
                 self.timeline_state.scroll_left(amount);
@@ -1330,7 +4683,8 @@ impl App {
                 self.timeline_state.zoom_out();
             }
             KeyCode::Char('t') => {
-                self.timeline_state.center_on_today(&self.projects, 100); // Approximate width
+                let date = self.reference_date();
+                self.timeline_state.center_on_date(date, &self.projects, 100); // Approximate width
             }
             KeyCode::Home => {
                 self.timeline_state.scroll_offset = 0;
@@ -1339,6 +4693,82 @@ impl App {
         }
     }
 
+    /// Keys of the Projects table; the selection is the timeline's
+    fn handle_projects_key(&mut self, key: KeyEvent) {
+        let total = self.projects.len();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.timeline_state.select_next(total),
+            KeyCode::Char('k') | KeyCode::Up => self.timeline_state.select_previous(total),
+            KeyCode::Char('g') | KeyCode::Home if total > 0 => self.timeline_state.selected_project = Some(0),
+            KeyCode::Char('G') | KeyCode::End if total > 0 => self.timeline_state.selected_project = Some(total - 1),
+            KeyCode::Char('h') | KeyCode::Left => self.move_table_cell(false),
+            KeyCode::Char('l') | KeyCode::Right => self.move_table_cell(true),
+            KeyCode::Enter if InlineEdit::editable(self.table_cell()) => {
+                if self.blocked_by_sandbox() {
+                    return;
+                }
+                let project = self.timeline_state.selected_project.and_then(|idx| self.projects.get(idx));
+                self.inline_edit = project.and_then(|p| InlineEdit::new(p, self.table_cell()));
+            }
+            // Back to the timeline, scrolled to the selected project
+            KeyCode::Char('T') | KeyCode::Enter => {
+                self.active_tab = Tab::Timeline;
+                self.jump_to_selected_project();
+            }
+            _ => {}
+        }
+    }
+
+    /// Column of the Projects table cell cursor (the first one when its column is hidden)
+    pub fn table_cell(&self) -> Column {
+        let columns = self.columns(Tab::Projects);
+        if columns.contains(&self.table_column) { self.table_column } else { columns.first().copied().unwrap_or(Column::Name) }
+    }
+
+    /// Move the Projects table cell cursor to the next or previous shown column
+    fn move_table_cell(&mut self, forward: bool) {
+        let columns = self.columns(Tab::Projects);
+        let index = columns.iter().position(|c| *c == self.table_cell()).unwrap_or(0);
+        let target = if forward { index + 1 } else { index.wrapping_sub(1) };
+        if let Some(column) = columns.get(target) {
+            self.table_column = *column;
+        }
+    }
+
+    /// Edit the Projects table cell being edited in place
+    fn handle_inline_key(&mut self, key: KeyEvent) -> Option<ApiCommand> {
+        let edit = self.inline_edit.as_mut()?;
+        match key.code {
+            KeyCode::Esc => self.inline_edit = None,
+            KeyCode::Enter => return self.commit_inline_edit(),
+            code => {
+                edit.handle_key(code);
+            }
+        }
+        None
+    }
+
+    /// Send the project with the edited cell's field changed
+    fn commit_inline_edit(&mut self) -> Option<ApiCommand> {
+        let edit = self.inline_edit.take()?;
+        let project = self.projects.iter().find(|p| p.id == edit.id)?;
+        if InlineEdit::new(project, edit.column).as_ref() == Some(&edit) {
+            return None;
+        }
+        let dto = match edit.update(project) {
+            Ok(dto) => dto,
+            Err(e) => {
+                self.log(LogEntry::error(tr!("log-inline-invalid", error = e)));
+                self.inline_edit = Some(edit);
+                return None;
+            }
+        };
+        let before = UpdateProjectDto::from_project(project);
+        self.log(LogEntry::info(tr!("log-updating-project")));
+        let operation = Operation::UpdateProject { id: edit.id, before, after: dto.clone() };
+        Some(self.history.begin(operation, Direction::Do, ApiCommand::UpdateProject(edit.id, dto)))
+    }
+
     /// Jump timeline viewport to show the currently selected project
     fn jump_to_selected_project(&mut self) {
         if let Some(idx) = self.timeline_state.selected_project {
@@ -1393,52 +4823,171 @@ impl App {
         }
     }
 
-    /// Update animations (called every frame)
-    pub fn tick(&mut self, width: u16, height: u16) {
+    /// Update animations (called every frame); marks the app dirty only if
+    /// something visible changed, so an idle screen is not redrawn
+    pub fn tick(&mut self, width: u16, height: u16, animate: bool) {
         self.frame_count = self.frame_count.wrapping_add(1);
+        self.screen_size = (width, height);
+
+        // Animations are skipped when the frame pacer is running behind
+        if animate {
+            // Particles speed up while data is loading
+            self.particle_system.set_warp(self.is_loading);
+
+            // Update particles, rebuilding them only once a resize has settled
+            let (width, height) = self.resize.observe((width, height), Instant::now());
+            self.particle_system.update(width, height);
+
+            // Update timeline animations (goyslop effects!) until the user goes idle
+            let timeline_animating = self.active_tab == Tab::Timeline
+                && self.last_activity.elapsed() < TIMELINE_IDLE_AFTER;
+            if timeline_animating {
+                self.timeline_state.tick();
+            }
+
+            // The debug overlay shows live frame statistics
+            if self.particle_system.is_animating() || timeline_animating || self.show_debug {
+                self.dirty.mark(Dirty::ANIMATION);
+            }
+        }
 
-        // Update particles
-        self.particle_system.update(width, height);
+        // The status bar shows the age of the last refresh
+        let status = self.status_text();
+        if status != self.last_status {
+            self.last_status = status;
+            self.dirty.mark(Dirty::DATA);
+        }
+
+        // The telemetry readout follows its memory samples
+        if self.ui_state.show_telemetry && self.telemetry.sample(Instant::now()) {
+            self.dirty.mark(Dirty::DATA);
+        }
+
+        // Changed cells go back to normal
+        if self.flash.expire(Instant::now()) {
+            self.dirty.mark(Dirty::DATA);
+        }
 
-        // Update timeline animations (goyslop effects!)
-        self.timeline_state.tick();
+        // Toasts disappear on their own
+        let toasts = self.toasts.len();
+        self.toasts.retain(|toast| !toast.expired(Instant::now()));
+        if self.toasts.len() != toasts {
+            self.dirty.mark(Dirty::LAYOUT);
+        }
 
         // Auto-dismiss error popup
         if let Some(ref popup) = self.error_popup {
             if popup.should_dismiss() {
                 self.error_popup = None;
+                self.dirty.mark(Dirty::LAYOUT);
             }
         }
     }
 
+    /// Application state for diagnostics bundles (counts only, no entity data)
+    pub fn diagnostics_summary(&self) -> String {
+        format!(
+            "tab: {:?}\nconnected: {}\nloading: {}\nprojects: {}, clients: {}, users: {}\n\
+             input mode: {:?}\nform open: {}\nsandbox: {}\nas of: {:?}\ncomparison: {:?}\n\
+             particles: {}\nscreen: {}x{}\nframes: {:?}",
+            self.active_tab,
+            self.api_connected,
+            self.is_loading,
+            self.projects.len(),
+            self.clients.len(),
+            self.users.len(),
+            self.input_mode,
+            self.form_state.is_some(),
+            self.sandbox.is_some(),
+            self.as_of,
+            self.comparison.map(|c| c.by),
+            self.particle_system.mode().name(),
+            self.screen_size.0,
+            self.screen_size.1,
+            self.frame_stats,
+        )
+    }
+
+    /// Why the shown data may be out of date: it comes from the snapshot
+    /// history, the connection dropped, or the last refresh is older than
+    /// `[ui] stale_after_minutes`
+    pub fn stale_warning(&self) -> Option<String> {
+        if let Some(at) = self.recorded_at {
+            return Some(tr!("stale-recorded", time = at.with_timezone(&Local).format("%Y-%m-%d %H:%M")));
+        }
+        let age = self.last_refresh?.elapsed();
+        if !self.api_connected {
+            return Some(tr!("stale-disconnected", mins = age.as_secs() / 60));
+        }
+        let threshold = self.config.ui.stale_after_minutes;
+        (threshold > 0 && age >= Duration::from_secs(threshold * 60))
+            .then(|| tr!("stale-age", mins = age.as_secs() / 60))
+    }
+
+    /// Reconnect countdown while the API is unreachable
+    pub fn reconnect_status(&self) -> Option<String> {
+        if !self.reconnect.is_active() {
+            return None;
+        }
+        let attempt = self.reconnect.attempt();
+        Some(match self.reconnect.remaining(Instant::now()) {
+            Some(left) => tr!("status-reconnect-in", secs = left.as_secs_f64().ceil() as u64, attempt = attempt),
+            None => tr!("status-reconnecting", attempt = attempt),
+        })
+    }
+
     /// Get the status bar text
     pub fn status_text(&self) -> String {
-        let connection = if self.api_connected {
-            "Connected"
+        let connection = if !self.backends.is_empty() {
+            let backends: Vec<String> =
+                self.backends.iter().map(|b| format!("{} {}", if b.connected { "●" } else { "○" }, b.name)).collect();
+            tr!("status-backends", backends = backends.join(" "))
+        } else if self.api_connected {
+            tr!("status-connected").to_string()
         } else {
-            "Disconnected"
+            tr!("status-disconnected").to_string()
         };
 
-        let loading = if self.is_loading { " [Loading...]" } else { "" };
+        let loading = if self.is_loading { format!(" {}", tr!("status-loading")) } else { String::new() };
 
         let last_refresh = self
             .last_refresh
             .map(|t| {
                 let secs = t.elapsed().as_secs();
                 if secs < 60 {
-                    format!(" ({}s ago)", secs)
+                    format!(" {}", tr!("status-seconds-ago", secs = secs))
                 } else {
-                    format!(" ({}m ago)", secs / 60)
+                    format!(" {}", tr!("status-minutes-ago", mins = secs / 60))
                 }
             })
             .unwrap_or_default();
 
+        let stale = self.stale_warning().map(|w| format!(" {}", w)).unwrap_or_default();
+        let reconnect = self.reconnect_status().map(|r| format!(" {}", r)).unwrap_or_default();
+
         format!(
-            "{}{}{} | {} | ?: Help | c: Create | e: Edit | d: Delete | q: Quit",
+            "{}{}{}{}{} | {} | {}",
             connection,
             loading,
             last_refresh,
-            self.active_tab.name()
+            stale,
+            reconnect,
+            self.tab_title(self.active_tab),
+            tr!("status-keys")
         )
     }
 }
+
+/// Move favorites to the front, keeping the order within both groups
+fn pin_favorites<T>(favorites: &BTreeSet<Uuid>, items: &mut [T], id: fn(&T) -> Uuid) {
+    items.sort_by_key(|item| !favorites.contains(&id(item)));
+}
+
+/// `Some(true)` for a step back in the navigation history, `Some(false)` for forward
+fn jump_direction(key: KeyEvent) -> Option<bool> {
+    match (key.code, key.modifiers) {
+        (KeyCode::Char('o'), KeyModifiers::CONTROL) | (KeyCode::Left, KeyModifiers::ALT) => Some(true),
+        (KeyCode::Char('i'), KeyModifiers::CONTROL) | (KeyCode::Right, KeyModifiers::ALT) => Some(false),
+        _ => None,
+    }
+}
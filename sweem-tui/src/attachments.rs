@@ -0,0 +1,191 @@
+//! Project documents (`A` on the timeline).
+//!
+//! Lists the files the API keeps for the selected project and downloads one
+//! into the `[attachments] dir` directory. The body is streamed to a `.part`
+//! file in a task of its own, so the worker keeps serving other commands,
+//! and progress messages drive the gauge in the popup. Servers without
+//! document support answer 404, which the popup says instead of failing.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use sweem_core::source::DataSource;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::api::{ApiError, ApiMessage};
+use crate::models::DocumentDto;
+
+/// Progress messages are sent at most this often
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Documents popup state
+#[derive(Debug, Clone)]
+pub struct Attachments {
+    pub project_id: Uuid,
+    /// Project name for the title
+    pub label: String,
+    pub documents: DocumentList,
+    pub selected: usize,
+}
+
+/// What the server said about a project's documents
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentList {
+    Loading,
+    Loaded(Vec<DocumentDto>),
+    /// The server has no document endpoints
+    Unsupported,
+    Failed(String),
+}
+
+impl Attachments {
+    pub fn selected_document(&self) -> Option<&DocumentDto> {
+        match &self.documents {
+            DocumentList::Loaded(documents) => documents.get(self.selected),
+            _ => None,
+        }
+    }
+
+    pub fn receive(&mut self, result: Result<Vec<DocumentDto>, ApiError>) {
+        self.documents = match result {
+            Ok(documents) => DocumentList::Loaded(documents),
+            Err(e) if e.status() == Some(404) => DocumentList::Unsupported,
+            Err(e) => DocumentList::Failed(e.message()),
+        };
+        self.selected = 0;
+    }
+}
+
+/// A running download
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadProgress {
+    pub document_id: Uuid,
+    pub file_name: String,
+    pub received: u64,
+    pub total: Option<u64>,
+}
+
+impl DownloadProgress {
+    /// Share done, when the size is known
+    pub fn ratio(&self) -> Option<f64> {
+        self.total.filter(|&total| total > 0).map(|total| (self.received as f64 / total as f64).min(1.0))
+    }
+}
+
+/// Human-readable byte count
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Where to save `file_name` in `dir`: path separators in the server's name
+/// are replaced and existing files get a ` (n)` suffix instead of being
+/// overwritten
+pub fn target_path(dir: &Path, file_name: &str) -> PathBuf {
+    let name: String = file_name.chars().map(|c| if matches!(c, '/' | '\\' | '\0') { '_' } else { c }).collect();
+    // No hidden files and no `..`
+    let name = match name.trim_start_matches('.') {
+        "" => "document".to_string(),
+        rest => rest.to_string(),
+    };
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem.to_string(), format!(".{}", extension)),
+        _ => (name.clone(), String::new()),
+    };
+    let mut path = dir.join(&name);
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("{} ({}){}", stem, n, extension));
+        n += 1;
+    }
+    path
+}
+
+/// Stream a document to `dir`, reporting progress; runs as its own task
+pub async fn download(
+    source: Arc<dyn DataSource>,
+    tx: mpsc::Sender<ApiMessage>,
+    project_id: Uuid,
+    document: DocumentDto,
+    dir: PathBuf,
+) {
+    let result = save(source.as_ref(), &tx, project_id, &document, &dir).await;
+    if let Err(e) = &result {
+        tracing::warn!(document = %document.id, error = %e, "Document download failed");
+    }
+    tx.send(ApiMessage::DownloadFinished { document_id: document.id, result: result.map_err(|e| format!("{:#}", e)) })
+        .await
+        .ok();
+}
+
+async fn save(
+    source: &dyn DataSource,
+    tx: &mpsc::Sender<ApiMessage>,
+    project_id: Uuid,
+    document: &DocumentDto,
+    dir: &Path,
+) -> Result<PathBuf> {
+    let mut body = source.download_document(project_id, document.id).await?;
+    let total = body.total.or(document.size);
+    tokio::fs::create_dir_all(dir).await.with_context(|| format!("Failed to create {}", dir.display()))?;
+    let target = target_path(dir, &document.file_name);
+    let part = target.with_file_name(format!("{}.part", target.file_name().unwrap_or_default().to_string_lossy()));
+    let mut file = tokio::fs::File::create(&part).await.with_context(|| format!("Failed to create {}", part.display()))?;
+
+    let mut received = 0;
+    let mut last_report = Instant::now();
+    let copied: Result<()> = async {
+        while let Some(chunk) = body.chunk().await? {
+            file.write_all(&chunk).await.context("Failed to write the download")?;
+            received += chunk.len() as u64;
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                last_report = Instant::now();
+                tx.send(ApiMessage::DownloadProgress { document_id: document.id, received, total }).await.ok();
+            }
+        }
+        file.flush().await.context("Failed to write the download")?;
+        Ok(())
+    }
+    .await;
+    if let Err(e) = copied {
+        tokio::fs::remove_file(&part).await.ok();
+        return Err(e);
+    }
+    tokio::fs::rename(&part, &target).await.with_context(|| format!("Failed to move the download to {}", target.display()))?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_path_and_size() {
+        let dir = std::env::temp_dir().join(format!("sweem-attachments-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(target_path(&dir, "spec.pdf"), dir.join("spec.pdf"));
+        std::fs::write(dir.join("spec.pdf"), b"").unwrap();
+        std::fs::write(dir.join("spec (1).pdf"), b"").unwrap();
+        assert_eq!(target_path(&dir, "spec.pdf"), dir.join("spec (2).pdf"), "existing files are kept");
+        assert_eq!(target_path(&dir, "../../etc/passwd"), dir.join("_.._etc_passwd"));
+        assert_eq!(target_path(&dir, ".."), dir.join("document"));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(70_000), "68.4 KB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GB");
+    }
+}
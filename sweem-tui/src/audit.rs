@@ -0,0 +1,133 @@
+//! Audit log tab.
+//!
+//! A page of the backend's activity feed (`GET /audit`): who created,
+//! changed or deleted which project, client or user, and when. Pages are
+//! fetched on demand (when the tab is first shown, on `r` and when paging)
+//! and the search text is sent along, so filtering happens on the server
+//! and covers the whole log, not just the visible page. Servers without an
+//! audit log answer 404, which the tab says instead of failing.
+
+use uuid::Uuid;
+
+use crate::api::{ApiCommand, ApiError, EntityType};
+use crate::models::{AuditEventDto, PaginatedResult};
+
+/// Events per page
+pub const PAGE_SIZE: i32 = 50;
+
+/// Audit tab state
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    /// Server-side filter (actor, action or entity)
+    pub search: String,
+    /// Page shown or being loaded (1-based)
+    pub page: i32,
+    pub feed: AuditFeed,
+}
+
+/// What the server said about the audit log
+#[derive(Debug, Clone, Default)]
+pub enum AuditFeed {
+    /// Not requested yet
+    #[default]
+    NotLoaded,
+    Loading,
+    Loaded(PaginatedResult<AuditEventDto>),
+    /// The server has no audit endpoint
+    Unsupported,
+    Failed(String),
+}
+
+impl AuditLog {
+    /// Start loading `page` with the current search
+    pub fn request(&mut self, page: i32) -> ApiCommand {
+        self.page = page.max(1);
+        self.feed = AuditFeed::Loading;
+        ApiCommand::FetchAudit { page: self.page, search: self.search.clone() }
+    }
+
+    /// Next or previous page, if there is one
+    pub fn turn(&mut self, forward: bool) -> Option<ApiCommand> {
+        let AuditFeed::Loaded(result) = &self.feed else {
+            return None;
+        };
+        let page = match forward {
+            true if result.has_next => self.page + 1,
+            false if result.has_previous => self.page - 1,
+            _ => return None,
+        };
+        Some(self.request(page))
+    }
+
+    /// Take a loaded page; answers to an older page or search are dropped
+    pub fn receive(&mut self, page: i32, search: &str, result: Result<PaginatedResult<AuditEventDto>, ApiError>) {
+        if page != self.page || search != self.search {
+            return;
+        }
+        self.feed = match result {
+            Ok(result) => AuditFeed::Loaded(result),
+            Err(e) if e.status() == Some(404) => AuditFeed::Unsupported,
+            Err(e) => AuditFeed::Failed(e.message()),
+        };
+    }
+
+    pub fn events(&self) -> &[AuditEventDto] {
+        match &self.feed {
+            AuditFeed::Loaded(result) => result.items(),
+            _ => &[],
+        }
+    }
+}
+
+/// The entity an event is about, if it is one the TUI lists
+pub fn target(event: &AuditEventDto) -> Option<(EntityType, Uuid)> {
+    EntityType::from_name(&event.entity_type.to_lowercase()).map(|entity_type| (entity_type, event.entity_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn page(page: i32, has_next: bool) -> PaginatedResult<AuditEventDto> {
+        let event = AuditEventDto {
+            id: Uuid::from_u128(1),
+            timestamp: Utc::now(),
+            actor: Some("alice".into()),
+            action: "Updated".into(),
+            entity_type: "Project".into(),
+            entity_id: Uuid::from_u128(2),
+            entity_name: None,
+            summary: None,
+        };
+        PaginatedResult {
+            items: Some(vec![event]),
+            page,
+            page_size: PAGE_SIZE,
+            total_count: 60,
+            total_pages: 2,
+            has_previous: page > 1,
+            has_next,
+        }
+    }
+
+    #[test]
+    fn test_paging_and_stale_answers() {
+        let mut audit = AuditLog::default();
+        assert!(matches!(audit.request(0), ApiCommand::FetchAudit { page: 1, .. }));
+        assert!(audit.turn(true).is_none(), "nothing to turn while loading");
+        audit.receive(1, "", Ok(page(1, true)));
+        assert_eq!(target(&audit.events()[0]), Some((EntityType::Project, Uuid::from_u128(2))));
+
+        assert!(matches!(audit.turn(true), Some(ApiCommand::FetchAudit { page: 2, .. })));
+        audit.search = "bob".into();
+        audit.receive(2, "", Ok(page(2, false)));
+        assert!(matches!(audit.feed, AuditFeed::Loading), "the search changed meanwhile");
+        audit.receive(2, "bob", Ok(page(2, false)));
+        assert!(audit.turn(true).is_none());
+
+        let missing = ApiError::Status { endpoint: "GET /audit".into(), status: 404, message: String::new() };
+        audit.receive(2, "bob", Err(missing));
+        assert!(matches!(audit.feed, AuditFeed::Unsupported));
+    }
+}
@@ -0,0 +1,82 @@
+//! Headless particle system benchmark (`sweem-tui bench-particles`).
+//!
+//! Runs every animation mode for a number of simulated frames at several
+//! terminal sizes and prints the average update and render time per frame.
+
+use std::time::{Duration, Instant};
+
+use ratatui::{buffer::Buffer, layout::Rect};
+
+use crate::particles::{ParticleMode, ParticleSystem};
+
+/// Terminal sizes benchmarked by default (small pane, laptop, 4K)
+pub const DEFAULT_SIZES: [(u16, u16); 3] = [(80, 24), (200, 60), (400, 120)];
+
+/// Timing for one mode at one size
+#[derive(Debug, Clone, Copy)]
+pub struct BenchResult {
+    pub mode: ParticleMode,
+    pub size: (u16, u16),
+    pub update: Duration,
+    pub render: Duration,
+    pub frames: u32,
+}
+
+impl BenchResult {
+    /// Average time per frame (update + render)
+    pub fn per_frame(&self) -> Duration {
+        (self.update + self.render) / self.frames.max(1)
+    }
+}
+
+/// Run a single mode for `frames` frames at the given size
+pub fn bench_mode(mode: ParticleMode, (width, height): (u16, u16), frames: u32, seed: u64) -> BenchResult {
+    let area = Rect::new(0, 0, width, height);
+    let mut system = ParticleSystem::new(mode, 0);
+    system.reseed(seed);
+    let mut buf = Buffer::empty(area);
+    let mut update = Duration::ZERO;
+    let mut render = Duration::ZERO;
+
+    for frame in 0..frames {
+        let start = Instant::now();
+        system.update(width, height);
+        if frame % 40 == 0 {
+            system.burst(width / 2, height / 2);
+        }
+        update += start.elapsed();
+
+        let start = Instant::now();
+        buf.reset();
+        system.render(area, &mut buf);
+        system.render_bursts(area, &mut buf);
+        render += start.elapsed();
+    }
+
+    BenchResult { mode, size: (width, height), update, render, frames }
+}
+
+/// Run all modes at all sizes and print a table
+pub fn run(frames: u32, sizes: &[(u16, u16)], seed: u64) {
+    println!("{:<14} {:>9} {:>12} {:>12} {:>12}", "mode", "size", "update/f", "render/f", "total/f");
+
+    let mut mode = ParticleMode::default();
+    loop {
+        for &size in sizes {
+            let result = bench_mode(mode, size, frames, seed);
+            let frames = result.frames.max(1);
+            println!(
+                "{:<14} {:>9} {:>12?} {:>12?} {:>12?}",
+                result.mode.name(),
+                format!("{}x{}", result.size.0, result.size.1),
+                result.update / frames,
+                result.render / frames,
+                result.per_frame(),
+            );
+        }
+        mode = mode.next();
+        if mode == ParticleMode::default() {
+            break;
+        }
+    }
+}
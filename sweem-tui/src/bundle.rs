@@ -0,0 +1,165 @@
+//! Shareable setup files (`config export` / `config import`).
+//!
+//! Teams standardise their setup by passing one file around. `config export`
+//! writes the whole configuration (profiles, `[[alerts]]` and `[[sla]]`
+//! rules, columns and the remaining sections) together with the saved views
+//! of the UI state (timeline bookmarks, column layouts and sort orders) as
+//! TOML. `config import` checks such a file like a config file, replaces the
+//! config (the old one is kept as `config.toml.bak`) and takes over the saved
+//! views. Personal state such as the Inbox and favorites is not shared.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::columns::ColumnLayout;
+use crate::config::Config;
+use crate::sort::SortKey;
+use crate::state::{Bookmark, UiState};
+
+/// Format version written to new files
+pub const VERSION: u32 = 1;
+
+/// Views saved in the UI state
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedViews {
+    /// Timeline bookmarks by slot ("1"-"9"; TOML keys are strings)
+    pub bookmarks: BTreeMap<String, Bookmark>,
+    pub client_sort: Vec<SortKey>,
+    pub user_sort: Vec<SortKey>,
+    pub project_columns: Option<ColumnLayout>,
+    pub client_columns: Option<ColumnLayout>,
+    pub user_columns: Option<ColumnLayout>,
+}
+
+impl SavedViews {
+    pub fn from_state(state: &UiState) -> Self {
+        Self {
+            bookmarks: state.bookmarks.iter().map(|(slot, b)| (slot.to_string(), b.clone())).collect(),
+            client_sort: state.client_sort.clone(),
+            user_sort: state.user_sort.clone(),
+            project_columns: state.project_columns.clone(),
+            client_columns: state.client_columns.clone(),
+            user_columns: state.user_columns.clone(),
+        }
+    }
+
+    /// Replace the saved views of the state with these
+    pub fn apply(self, state: &mut UiState) -> Result<()> {
+        let mut bookmarks = BTreeMap::new();
+        for (slot, bookmark) in self.bookmarks {
+            match slot.parse::<u8>() {
+                Ok(n @ 1..=9) => bookmarks.insert(n, bookmark),
+                _ => bail!("Bookmark slot \"{}\" is not 1-9", slot),
+            };
+        }
+        state.bookmarks = bookmarks;
+        state.client_sort = self.client_sort;
+        state.user_sort = self.user_sort;
+        state.project_columns = self.project_columns;
+        state.client_columns = self.client_columns;
+        state.user_columns = self.user_columns;
+        Ok(())
+    }
+}
+
+/// Contents of an exported setup file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bundle {
+    pub version: u32,
+    #[serde(default)]
+    pub config: Config,
+    #[serde(default)]
+    pub views: SavedViews,
+}
+
+impl Bundle {
+    pub fn new(config: Config, state: &UiState) -> Self {
+        Self { version: VERSION, config, views: SavedViews::from_state(state) }
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).context("Failed to serialize the setup")
+    }
+
+    /// Parse and check an exported file
+    pub fn parse(content: &str) -> Result<Self> {
+        let bundle: Self = toml::from_str(content)?;
+        if bundle.version > VERSION {
+            bail!("Written by a newer sweem-tui (format version {}, this one reads up to {})", bundle.version, VERSION);
+        }
+        bundle.config.validate()?;
+        Ok(bundle)
+    }
+}
+
+/// `config export`: the current setup to a file or stdout
+pub fn export(output: Option<&Path>) -> Result<()> {
+    let content = Bundle::new(Config::load()?, &UiState::load()).to_toml()?;
+    match output {
+        Some(path) => fs::write(path, content).with_context(|| format!("Failed to write {}", path.display())),
+        None => io::stdout().write_all(content.as_bytes()).context("Failed to write to stdout"),
+    }
+}
+
+/// `config import`: install an exported setup
+pub fn import(path: &Path) -> Result<()> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let bundle = Bundle::parse(&content).with_context(|| format!("Invalid setup in {}", path.display()))?;
+    let mut state = UiState::load();
+    bundle.views.apply(&mut state)?;
+    let config_path = bundle.config.save()?;
+    state.save()?;
+    println!(
+        "Imported {} profiles, {} alert rules and {} bookmarks into {}",
+        bundle.config.profiles.len(),
+        bundle.config.alerts.len(),
+        state.bookmarks.len(),
+        config_path.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn test_setup_round_trips_through_toml() {
+        let config = Config::parse(
+            "[profiles.staging]\napi_url = \"https://staging.example.com\"\n\
+             [[alerts]]\nname = \"Late\"\non = \"overdue\"\nfilter = \"client:acme\"\n",
+        )
+        .unwrap();
+        let mut state = UiState::default();
+        state.bookmarks.insert(
+            3,
+            Bookmark {
+                name: "Q3".into(),
+                start_date: NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
+                days_per_column: 2.0,
+            },
+        );
+        state.favorites.insert(uuid::Uuid::nil());
+
+        let bundle = Bundle::new(config.clone(), &state);
+        let parsed = Bundle::parse(&bundle.to_toml().unwrap()).unwrap();
+        assert_eq!(parsed, bundle);
+
+        let mut imported = UiState::default();
+        parsed.views.apply(&mut imported).unwrap();
+        assert_eq!(imported.bookmarks, state.bookmarks);
+        assert!(imported.favorites.is_empty(), "personal state is not shared");
+
+        let broken = bundle.to_toml().unwrap().replace("client:acme", "(client:acme");
+        assert!(Bundle::parse(&broken).is_err());
+        assert!(Bundle::parse("version = 99\n").is_err());
+    }
+}
@@ -0,0 +1,262 @@
+//! Command line interface.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use uuid::Uuid;
+use clap::{Parser, Subcommand};
+use clap_complete::Shell;
+
+use crate::api::{self, EntityType};
+use crate::config::Config;
+use crate::export::{ExportEntity, ExportFormat, Filter};
+use crate::expr::FilterExpr;
+use crate::ipc;
+use crate::query::QueryEntity;
+use crate::report::ReportFormat;
+
+/// Terminal user interface for the SWEeM REST API
+#[derive(Debug, Clone, Parser)]
+#[command(name = "sweem-tui", version, about)]
+pub struct Cli {
+    /// Base URL of the SWEeM API [default: the profile's URL, or http://localhost:5094]
+    pub api_url: Option<String>,
+
+    /// Use the API URL of a `[profiles.<name>]` section in the config file
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Your login, for the "My projects" view (F2) [default: the profile's `user`]
+    #[arg(long, value_name = "LOGIN")]
+    pub user: Option<String>,
+
+    /// After the initial load, select an entity (project, client or user) and scroll the timeline to it
+    #[arg(long, num_args = 2, value_names = ["KIND", "UUID"])]
+    pub focus: Option<Vec<String>>,
+
+    /// Seed the particle animation (and the --demo data) so runs produce identical frames
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Run on generated in-memory data instead of the API; changes are lost on exit
+    #[arg(long, conflicts_with = "replay")]
+    pub demo: bool,
+
+    /// Poll the server for changes every SECONDS (default 5) and reload only when data changed;
+    /// overrides `[api] watch_secs`
+    #[arg(long, value_name = "SECONDS", num_args = 0..=1, require_equals = true, default_missing_value = "5",
+          value_parser = clap::value_parser!(u64).range(1..))]
+    pub watch: Option<u64>,
+
+    /// Record key presses and API messages to FILE (JSON Lines) for bug reports and demos
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Re-drive the TUI from a recorded session instead of the API
+    #[arg(long, value_name = "FILE", conflicts_with = "watch")]
+    pub replay: Option<PathBuf>,
+
+    /// Remote-control socket [default: $XDG_RUNTIME_DIR/sweem-tui.sock; a named pipe on Windows]
+    #[arg(long, value_name = "PATH", global = true)]
+    pub socket: Option<PathBuf>,
+
+    /// Log level for the file log (e.g. "info", "debug", "sweem_tui=trace")
+    #[arg(long, default_value = "info")]
+    pub log_level: String,
+
+    /// Write a diagnostics bundle (version, config with secrets redacted, recent log) for bug reports and exit
+    #[arg(long)]
+    pub collect_diagnostics: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Subcommands (the TUI runs when none is given)
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Export projects, clients, users or the client ranking as JSON, CSV or a chart without starting the TUI
+    Export {
+        /// Entity collection to export
+        #[arg(long, value_enum)]
+        entity: ExportEntity,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "json")]
+        format: ExportFormat,
+
+        /// Keep rows whose FIELD contains VALUE, case-insensitive (repeatable, all must match)
+        #[arg(long = "filter", value_name = "FIELD=VALUE", value_parser = Filter::parse)]
+        filters: Vec<Filter>,
+
+        /// Write to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Weeks the capacity report covers, starting with the current one
+        #[arg(long, default_value_t = 12, value_parser = clap::value_parser!(u16).range(1..))]
+        weeks: u16,
+    },
+
+    /// Print the projects, clients or users matching a filter expression (the `/` syntax)
+    Query {
+        /// Filter expression, e.g. 'overdue && client:Acme'
+        #[arg(value_parser = FilterExpr::parse)]
+        expression: FilterExpr,
+
+        /// Entity collection to query
+        #[arg(long, value_enum, default_value = "projects")]
+        entity: QueryEntity,
+
+        /// Output format (the row formats of export)
+        #[arg(long, value_enum, default_value = "json")]
+        format: ExportFormat,
+
+        /// Write to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// Write a weekly status report (completed this week, newly overdue, upcoming deadlines, clients)
+    Report {
+        /// Output format
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: ReportFormat,
+
+        /// minijinja template to render instead of the `[report]` or built-in one of the format
+        #[arg(long, value_name = "FILE")]
+        template: Option<PathBuf>,
+
+        /// Print the built-in template of the format, as a starting point for your own
+        #[arg(long, conflicts_with_all = ["template", "output", "clipboard"])]
+        print_template: bool,
+
+        /// Write to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Copy the report to the clipboard (OSC 52) instead of printing it
+        #[arg(long)]
+        clipboard: bool,
+    },
+
+    /// Serve Prometheus metrics (project counts, overdue counts, refresh time and age) until interrupted
+    #[command(group = clap::ArgGroup::new("target").required(true).multiple(true).args(["listen", "textfile"]))]
+    Metrics {
+        /// Serve `GET /metrics` on this address, e.g. 127.0.0.1:9464
+        #[arg(long, value_name = "ADDR")]
+        listen: Option<std::net::SocketAddr>,
+
+        /// Write the metrics to this file for node_exporter's textfile collector (a `.prom` file)
+        #[arg(long, value_name = "FILE")]
+        textfile: Option<PathBuf>,
+
+        /// Seconds between refreshes
+        #[arg(long, value_name = "SECONDS", default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+
+    /// Print a shell completion script (profile names are completed from the config)
+    Completions {
+        /// Target shell
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// List configured environment profile names, one per line
+    Profiles,
+
+    /// Share the setup (config, alert rules, profiles and saved views) as one file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Send a command to the running TUI: refresh, goto-project <uuid>,
+    /// set-filter <projects|clients|users> [FIELD=VALUE ...]
+    Remote {
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+
+    /// Benchmark the particle system headlessly for every mode
+    #[command(hide = true)]
+    BenchParticles {
+        /// Simulated frames per mode and size
+        #[arg(long, default_value_t = 1000)]
+        frames: u32,
+
+        /// Terminal sizes as WIDTHxHEIGHT (repeatable; defaults to 80x24, 200x60, 400x120)
+        #[arg(long = "size", value_parser = parse_size)]
+        sizes: Vec<(u16, u16)>,
+    },
+}
+
+/// Actions of the `config` subcommand
+#[derive(Debug, Clone, Subcommand)]
+pub enum ConfigAction {
+    /// Write the config and the saved views (bookmarks, columns, sort orders) as TOML
+    Export {
+        /// Write to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// Replace the config and the saved views with an exported file (the old config is kept as config.toml.bak)
+    Import {
+        /// File written by `config export`
+        file: PathBuf,
+    },
+}
+
+impl Cli {
+    /// API base URL: the explicit argument, then the selected profile, then the default
+    pub fn resolve_api_url(&self) -> Result<String> {
+        if let Some(url) = &self.api_url {
+            return Ok(url.clone());
+        }
+        match &self.profile {
+            // Only read the config when a profile is asked for
+            Some(name) => Ok(Config::load()?.profile(name)?.api_url.clone()),
+            None => Ok(api::DEFAULT_BASE_URL.to_string()),
+        }
+    }
+}
+
+impl Cli {
+    /// Login of the person using the TUI: `--user`, then the selected profile's `user`
+    pub fn resolve_user(&self, config: &Config) -> Option<String> {
+        self.user.clone().or_else(|| {
+            self.profile.as_deref().and_then(|name| config.profiles.get(name)).and_then(|p| p.user.clone())
+        })
+    }
+
+    /// Entity given with `--focus KIND UUID`
+    pub fn resolve_focus(&self) -> Result<Option<(EntityType, Uuid)>> {
+        let Some([kind, id]) = self.focus.as_deref() else {
+            return Ok(None);
+        };
+        let Some(entity_type) = EntityType::from_name(kind) else {
+            bail!("--focus: unknown kind \"{}\" (expected project, client or user)", kind);
+        };
+        match id.parse() {
+            Ok(id) => Ok(Some((entity_type, id))),
+            Err(_) => bail!("--focus: invalid {} id \"{}\"", kind, id),
+        }
+    }
+
+    /// Remote-control socket path, if one can be determined
+    pub fn socket_path(&self) -> Option<PathBuf> {
+        self.socket.clone().or_else(ipc::default_path)
+    }
+}
+
+/// Parse a `WIDTHxHEIGHT` terminal size
+fn parse_size(value: &str) -> Result<(u16, u16), String> {
+    let (width, height) = value
+        .split_once('x')
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got \"{}\"", value))?;
+    let width = width.parse().map_err(|_| format!("invalid width \"{}\"", width))?;
+    let height = height.parse().map_err(|_| format!("invalid height \"{}\"", height))?;
+    Ok((width, height))
+}
@@ -0,0 +1,69 @@
+//! Copying to the system clipboard.
+//!
+//! Text is sent to the terminal as an OSC 52 escape sequence, which most
+//! terminal emulators (and tmux with `set-clipboard on`) forward to the
+//! clipboard, also over SSH. Terminals without OSC 52 support ignore it.
+
+use std::io::{self, Write};
+
+use uuid::Uuid;
+
+use crate::api::EntityType;
+
+/// Command line that opens the TUI focused on an entity
+pub fn focus_command(entity_type: EntityType, id: Uuid) -> String {
+    let kind = match entity_type {
+        EntityType::Project => "project",
+        EntityType::Client => "client",
+        EntityType::User => "user",
+    };
+    format!("sweem-tui --focus {} {}", kind, id)
+}
+
+/// Escape sequence that puts `text` on the clipboard
+pub fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+/// Write the OSC 52 sequence to the terminal
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(osc52(text).as_bytes())?;
+    stdout.flush()
+}
+
+/// Standard base64 with padding
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(osc52("hi"), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            focus_command(EntityType::Client, Uuid::from_u128(1)),
+            "sweem-tui --focus client 00000000-0000-0000-0000-000000000001"
+        );
+    }
+}
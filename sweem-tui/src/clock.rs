@@ -0,0 +1,44 @@
+//! Current date for the UI, overridable so rendering can be tested and
+//! recorded sessions replay on the day they were recorded.
+
+use std::cell::Cell;
+use std::sync::OnceLock;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+thread_local! {
+    /// Fixed date used instead of the system clock (tests only)
+    static FIXED_TODAY: Cell<Option<NaiveDate>> = const { Cell::new(None) };
+}
+
+/// Fixed date for the whole process (session replay)
+static FROZEN_TODAY: OnceLock<NaiveDate> = OnceLock::new();
+
+/// The frozen date, if any
+fn fixed() -> Option<NaiveDate> {
+    FIXED_TODAY.with(Cell::get).or_else(|| FROZEN_TODAY.get().copied())
+}
+
+/// Today's local date
+pub fn today() -> NaiveDate {
+    fixed().unwrap_or_else(|| chrono::Local::now().date_naive())
+}
+
+/// Current local date and time (noon of the frozen date, if any)
+pub fn now() -> NaiveDateTime {
+    match fixed() {
+        Some(date) => date.and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default()),
+        None => chrono::Local::now().naive_local(),
+    }
+}
+
+/// Freeze `today()` for all threads (first call wins)
+pub fn freeze(date: NaiveDate) {
+    FROZEN_TODAY.set(date).ok();
+}
+
+/// Freeze `today()` on the current thread
+#[cfg(test)]
+pub fn set_today(date: NaiveDate) {
+    FIXED_TODAY.with(|fixed| fixed.set(Some(date)));
+}
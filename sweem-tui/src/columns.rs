@@ -0,0 +1,215 @@
+//! Column layout of the Projects, Clients and Users views.
+//!
+//! Narrow terminals cannot fit every column, so each view's columns can be
+//! hidden and reordered in the column dialog (`C`). Layouts edited there are
+//! persisted per view with the UI state; until a view has one, the
+//! `[columns]` config section decides (and `r` in the dialog goes back to
+//! it). The name column always stays, so rows can still be told apart. The
+//! source column is only shown while several backends are merged
+//! (`[api] aggregate`).
+
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sweem_core::aggregate::SOURCE_FIELD;
+
+use crate::api::EntityType;
+use crate::tr;
+
+/// A column of a list view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Column {
+    Name,
+    Client,
+    Manager,
+    Start,
+    PlannedEnd,
+    ActualEnd,
+    /// Days to the planned end, or overdue
+    Due,
+    Status,
+    Address,
+    /// Completed and total projects of a client
+    Projects,
+    Login,
+    Role,
+    /// Backend an aggregated entity was loaded from
+    Source,
+}
+
+impl Column {
+    pub const PROJECTS: [Column; 9] = [
+        Column::Name,
+        Column::Client,
+        Column::Manager,
+        Column::Start,
+        Column::PlannedEnd,
+        Column::ActualEnd,
+        Column::Due,
+        Column::Status,
+        Column::Source,
+    ];
+    pub const CLIENTS: [Column; 4] = [Column::Name, Column::Address, Column::Projects, Column::Source];
+    pub const USERS: [Column; 4] = [Column::Name, Column::Login, Column::Role, Column::Source];
+
+    /// Columns of the view listing `entity`, in their default order
+    pub fn all(entity: EntityType) -> &'static [Column] {
+        match entity {
+            EntityType::Project => &Self::PROJECTS,
+            EntityType::Client => &Self::CLIENTS,
+            EntityType::User => &Self::USERS,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Column::Name => tr!("diff-field-name"),
+            Column::Client => tr!("diff-field-client"),
+            Column::Manager => tr!("diff-field-manager"),
+            Column::Start => tr!("diff-field-start"),
+            Column::PlannedEnd => tr!("diff-field-planned-end"),
+            Column::ActualEnd => tr!("diff-field-actual-end"),
+            Column::Due => tr!("column-due"),
+            Column::Status => tr!("markdown-status"),
+            Column::Address => tr!("diff-field-address"),
+            Column::Projects => tr!("markdown-projects"),
+            Column::Login => tr!("diff-field-login"),
+            Column::Role => tr!("diff-field-role"),
+            Column::Source => tr!("column-source"),
+        }
+    }
+}
+
+/// Backend name an entity was tagged with when backends are merged
+pub fn source(extra: &Map<String, Value>) -> &str {
+    extra.get(SOURCE_FIELD).and_then(Value::as_str).unwrap_or("-")
+}
+
+/// Order and visibility of one view's columns
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ColumnLayout {
+    /// Every column of the view, shown or not
+    pub order: Vec<Column>,
+    pub hidden: BTreeSet<Column>,
+}
+
+impl ColumnLayout {
+    /// Layout showing `shown` in that order and hiding the rest (empty shows all)
+    pub fn showing(entity: EntityType, shown: &[Column]) -> Self {
+        let all = Column::all(entity);
+        if shown.is_empty() {
+            return Self { order: all.to_vec(), hidden: BTreeSet::new() };
+        }
+        let hidden: BTreeSet<Column> = all.iter().copied().filter(|c| !shown.contains(c)).collect();
+        let order = shown.iter().chain(hidden.iter()).copied().collect();
+        Self { order, hidden }.normalized(entity)
+    }
+
+    /// Drop columns the view does not have, add missing ones at the end
+    /// (hidden, if the layout hides anything) and keep the name column shown
+    pub fn normalized(mut self, entity: EntityType) -> Self {
+        let all = Column::all(entity);
+        let mut seen = BTreeSet::new();
+        self.order.retain(|c| all.contains(c) && seen.insert(*c));
+        for column in all {
+            if !self.order.contains(column) {
+                self.order.push(*column);
+                if !self.hidden.is_empty() {
+                    self.hidden.insert(*column);
+                }
+            }
+        }
+        self.hidden.retain(|c| all.contains(c) && *c != Column::Name);
+        self
+    }
+
+    /// Shown columns in order
+    pub fn shown(&self) -> Vec<Column> {
+        self.order.iter().copied().filter(|c| !self.hidden.contains(c)).collect()
+    }
+
+    /// Show or hide a column; the name column cannot be hidden
+    pub fn toggle(&mut self, column: Column) {
+        if column != Column::Name && !self.hidden.remove(&column) {
+            self.hidden.insert(column);
+        }
+    }
+
+    /// Move the column at `index` one place up or down; returns its new index
+    pub fn shift(&mut self, index: usize, down: bool) -> usize {
+        let target = if down { index + 1 } else { index.wrapping_sub(1) };
+        if target < self.order.len() && index < self.order.len() {
+            self.order.swap(index, target);
+            target
+        } else {
+            index
+        }
+    }
+}
+
+/// `[columns]` config section: shown columns of each view, in order (unset shows all)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnsConfig {
+    pub projects: Vec<Column>,
+    pub clients: Vec<Column>,
+    pub users: Vec<Column>,
+}
+
+impl ColumnsConfig {
+    /// Configured layout of the view listing `entity`
+    pub fn layout(&self, entity: EntityType) -> ColumnLayout {
+        let shown = match entity {
+            EntityType::Project => &self.projects,
+            EntityType::Client => &self.clients,
+            EntityType::User => &self.users,
+        };
+        ColumnLayout::showing(entity, shown)
+    }
+
+    /// Reject columns a view does not have
+    pub fn validate(&self) -> Result<()> {
+        let views = [
+            ("projects", EntityType::Project, &self.projects),
+            ("clients", EntityType::Client, &self.clients),
+            ("users", EntityType::User, &self.users),
+        ];
+        for (view, entity, shown) in views {
+            if let Some(column) = shown.iter().find(|c| !Column::all(entity).contains(c)) {
+                bail!("[columns] {}: that view has no {:?} column", view, column);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layouts_keep_the_name_and_pick_up_new_columns() {
+        let mut layout = ColumnLayout::showing(EntityType::Project, &[Column::Start, Column::Name]);
+        assert_eq!(layout.shown(), [Column::Start, Column::Name]);
+        layout.toggle(Column::Name);
+        assert_eq!(layout.shown(), [Column::Start, Column::Name], "the name cannot be hidden");
+        assert_eq!(layout.shift(1, false), 0);
+        layout.toggle(Column::Status);
+        assert_eq!(layout.shown(), [Column::Name, Column::Start, Column::Status]);
+        assert_eq!(layout.shift(0, false), 0, "already first");
+
+        // A stored layout from a version with fewer columns
+        let stored = ColumnLayout { order: vec![Column::Login, Column::Name, Column::Address], hidden: BTreeSet::new() };
+        assert_eq!(stored.normalized(EntityType::User).shown(), [Column::Login, Column::Name, Column::Role, Column::Source]);
+
+        let config: ColumnsConfig = toml::from_str("clients = [\"name\", \"projects\"]").unwrap();
+        assert_eq!(config.layout(EntityType::Client).shown(), [Column::Name, Column::Projects]);
+        assert_eq!(config.layout(EntityType::User).shown(), Column::USERS);
+        let config: ColumnsConfig = toml::from_str("users = [\"name\", \"address\"]").unwrap();
+        assert!(config.validate().is_err());
+    }
+}
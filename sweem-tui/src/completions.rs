@@ -0,0 +1,80 @@
+//! Shell completion scripts.
+//!
+//! The static part is generated by clap_complete. Values for `--profile` are
+//! looked up when completing, by calling `sweem-tui profiles`, so profiles
+//! added to the config later complete without regenerating the script
+//! (bash, zsh and fish; other shells get the static script only).
+
+use std::io::Write;
+
+use anyhow::Result;
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+use crate::cli::Cli;
+
+/// Bash: wraps the generated function and answers `--profile` itself
+const BASH_PROFILES: &str = r#"
+_sweem-tui_with_profiles() {
+    if [[ "${COMP_WORDS[COMP_CWORD-1]}" == "--profile" ]]; then
+        COMPREPLY=($(compgen -W "$(sweem-tui profiles 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}"))
+        return 0
+    fi
+    _sweem-tui "$@"
+}
+"#;
+
+/// Zsh: completion function for the `--profile` value
+const ZSH_PROFILES: &str = r#"
+_sweem-tui_profiles() {
+    local -a profiles
+    profiles=(${(f)"$(sweem-tui profiles 2>/dev/null)"})
+    _describe 'profile' profiles
+}
+"#;
+
+/// Fish: an extra rule adds candidates to the generated `--profile` option
+const FISH_PROFILES: &str = "
+complete -c sweem-tui -l profile -f -a '(sweem-tui profiles 2>/dev/null)'
+";
+
+/// Write the completion script for `shell`
+pub fn generate(shell: Shell, out: &mut impl Write) -> Result<()> {
+    let mut command = Cli::command();
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut command, "sweem-tui", &mut script);
+    let script = String::from_utf8(script)?;
+
+    let script = match shell {
+        Shell::Bash => script.replace("complete -F _sweem-tui ", "complete -F _sweem-tui_with_profiles ") + BASH_PROFILES,
+        Shell::Fish => script + FISH_PROFILES,
+        // Point the option's value action at the helper, defined before the
+        // generated code runs `_sweem-tui` at the end of the file
+        Shell::Zsh => script
+            .replace(":NAME:_default'", ":NAME:_sweem-tui_profiles'")
+            .replacen("\n", &format!("\n{}", ZSH_PROFILES), 1),
+        _ => script,
+    };
+    out.write_all(script.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripts_complete_profiles_dynamically() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let mut out = Vec::new();
+            generate(shell, &mut out).unwrap();
+            let script = String::from_utf8(out).unwrap();
+            assert!(script.contains("sweem-tui profiles"), "{shell}");
+            assert!(script.contains("completions"), "{shell} lacks subcommands");
+        }
+
+        let mut out = Vec::new();
+        generate(Shell::Zsh, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains(":NAME:_sweem-tui_profiles'"));
+    }
+}
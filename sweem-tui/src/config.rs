@@ -0,0 +1,461 @@
+//! User configuration.
+//!
+//! Settings are read from `config.toml` under the XDG config directory
+//! (`~/.config/sweem-tui/config.toml` on Linux). Every field is optional;
+//! missing values fall back to the defaults below.
+
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sweem_core::api::{ConnectionOptions, Http2};
+
+use crate::alerts::AlertRule;
+use crate::columns::ColumnsConfig;
+use crate::graphics::GraphicsMode;
+use crate::i18n::Locale;
+use crate::notify::NotifyConfig;
+use crate::particles::ParticleMode;
+use crate::sla::SlaRule;
+use crate::webhook::WebhookConfig;
+
+/// File name of the configuration inside the config directory
+const CONFIG_FILE: &str = "config.toml";
+
+/// Background particle settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParticleConfig {
+    /// Start with the particle background on (`p` still cycles the modes)
+    pub enabled: bool,
+    /// Initial animation mode (`digital-rain`, `starfield`, `plasma`, `snow`, `confetti`, `none`)
+    pub mode: Option<ParticleMode>,
+    /// Particle density multiplier (1.0 = default, 0.0 = empty background)
+    pub density: f32,
+    /// Brightness of particles within lines of text in panels (0.0 hides them, 1.0 disables dimming)
+    pub content_dim: f32,
+}
+
+impl Default for ParticleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mode: None,
+            density: 1.0,
+            content_dim: 0.25,
+        }
+    }
+}
+
+/// Render loop settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RenderConfig {
+    /// Target frames per second (1-120)
+    pub fps: u32,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self { fps: 30 }
+    }
+}
+
+/// Interface settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Interface language (`en`, `ru`); unset follows `LANG`
+    pub locale: Option<Locale>,
+    /// Minutes after the last refresh when data is shown as stale (0 = never)
+    pub stale_after_minutes: u64,
+    /// Inline chart images (`auto`, `kitty`, `iterm`, `off`)
+    pub graphics: GraphicsMode,
+    /// Days a cloned project's dates move; unset starts the copy today
+    pub clone_offset_days: Option<i64>,
+    /// Days after their actual end that completed projects count as archived
+    pub archive_after_days: u32,
+    /// Tab shown at startup
+    pub start_tab: StartTab,
+    /// Initial timeline zoom in days per column (0.25-16)
+    pub timeline_zoom: f64,
+}
+
+/// Tab a session starts on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StartTab {
+    Clients,
+    #[default]
+    Timeline,
+    Projects,
+    Users,
+    Stats,
+    Inbox,
+    Audit,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            locale: None,
+            stale_after_minutes: 10,
+            graphics: GraphicsMode::Auto,
+            clone_offset_days: None,
+            archive_after_days: 30,
+            start_tab: StartTab::Timeline,
+            timeline_zoom: 1.0,
+        }
+    }
+}
+
+/// Local snapshot history (`history.sqlite` in the state directory)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Record every refresh and show the last one at startup
+    pub enabled: bool,
+    /// Days snapshots are kept
+    pub retention_days: u32,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self { enabled: true, retention_days: 365 }
+    }
+}
+
+/// Project document downloads
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttachmentsConfig {
+    /// Download directory; unset uses the platform's downloads folder
+    pub dir: Option<PathBuf>,
+}
+
+impl AttachmentsConfig {
+    /// Directory downloads are saved to
+    pub fn download_dir(&self) -> PathBuf {
+        self.dir.clone().or_else(dirs::download_dir).unwrap_or_else(|| PathBuf::from("."))
+    }
+}
+
+/// Weekly status report templates (`[report]`); unset formats use the built-in ones
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportConfig {
+    /// minijinja template of `report --format markdown`, relative to the config directory
+    pub markdown: Option<PathBuf>,
+    /// minijinja template of `report --format html`, relative to the config directory
+    pub html: Option<PathBuf>,
+    /// minijinja template of `report --format print` and `pdf`, relative to the config directory
+    pub print: Option<PathBuf>,
+}
+
+/// OpenTelemetry trace export (`[otel]`), for builds with the `otel` feature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OtelConfig {
+    /// OTLP/HTTP collector URL, e.g. `http://localhost:4318`; unset (and no
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT`) exports nothing
+    pub endpoint: Option<String>,
+    /// `service.name` of the exported spans
+    pub service_name: String,
+}
+
+impl Default for OtelConfig {
+    fn default() -> Self {
+        Self { endpoint: None, service_name: "sweem-tui".to_string() }
+    }
+}
+
+/// Transport used to load and change entities
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+    #[default]
+    Rest,
+    /// One query per refresh instead of one request per page
+    Graphql,
+}
+
+/// How the backend is reached
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub transport: Transport,
+    /// GraphQL endpoint; unset uses `/graphql` below the API URL
+    pub graphql_url: Option<String>,
+    /// HTTP/2 use (`auto`, `always`, `off`)
+    pub http2: Http2,
+    /// Seconds between keep-alive probes on open connections (0 = none)
+    pub keep_alive_secs: u64,
+    /// Seconds an unused connection stays open for the next request
+    pub idle_timeout_secs: u64,
+    /// Unused connections kept open per host
+    pub max_idle_connections: usize,
+    /// Load the Clients and Users lists a page of this many rows at a time, as
+    /// they are scrolled (0 = load them whole)
+    pub list_page_size: u32,
+    /// Profiles whose backends are loaded together and shown merged (empty:
+    /// just the selected API)
+    pub aggregate: Vec<String>,
+    /// Poll for changes every this many seconds, like `--watch` (0 = off)
+    pub watch_secs: u64,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        let connection = ConnectionOptions::default();
+        Self {
+            transport: Transport::Rest,
+            graphql_url: None,
+            http2: connection.http2,
+            keep_alive_secs: connection.keep_alive.map_or(0, |interval| interval.as_secs()),
+            idle_timeout_secs: connection.idle_timeout.as_secs(),
+            max_idle_connections: connection.max_idle_per_host,
+            list_page_size: 0,
+            aggregate: Vec::new(),
+            watch_secs: 0,
+        }
+    }
+}
+
+impl ApiConfig {
+    /// Connection reuse settings of the HTTP clients
+    pub fn connection(&self) -> ConnectionOptions {
+        ConnectionOptions {
+            http2: self.http2,
+            keep_alive: (self.keep_alive_secs > 0).then(|| Duration::from_secs(self.keep_alive_secs)),
+            idle_timeout: Duration::from_secs(self.idle_timeout_secs),
+            max_idle_per_host: self.max_idle_connections,
+        }
+    }
+
+    /// Key the snapshot history of the backend(s) is kept under
+    pub fn history_key(&self, api_url: &str) -> String {
+        if self.aggregate.is_empty() {
+            api_url.to_string()
+        } else {
+            format!("aggregate:{}", self.aggregate.join(","))
+        }
+    }
+
+    /// GraphQL endpoint for the API at `api_url`
+    pub fn graphql_url(&self, api_url: &str) -> String {
+        self.graphql_url.clone().unwrap_or_else(|| format!("{}/graphql", api_url.trim_end_matches('/')))
+    }
+}
+
+/// Named API environment (`[profiles.<name>]`), selected with `--profile`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Base URL of the SWEeM API for this environment
+    pub api_url: String,
+    /// Your login in this environment, for the "My projects" view
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Production environment: shown with a banner, and destructive actions
+    /// must be confirmed by typing the profile name
+    #[serde(default)]
+    pub production: bool,
+}
+
+/// Top-level configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// `[particles]` section
+    pub particles: ParticleConfig,
+    /// `[render]` section
+    pub render: RenderConfig,
+    /// `[ui]` section
+    pub ui: UiConfig,
+    /// `[columns]` section
+    pub columns: ColumnsConfig,
+    /// `[notifications]` section
+    pub notifications: NotifyConfig,
+    /// `[history]` section
+    pub history: HistoryConfig,
+    /// `[attachments]` section
+    pub attachments: AttachmentsConfig,
+    /// `[report]` section
+    pub report: ReportConfig,
+    /// `[otel]` section
+    pub otel: OtelConfig,
+    /// `[webhook]` section
+    pub webhook: WebhookConfig,
+    /// `[api]` section
+    pub api: ApiConfig,
+    /// `[profiles.<name>]` sections
+    pub profiles: BTreeMap<String, Profile>,
+    /// `[[sla]]` rules
+    pub sla: Vec<SlaRule>,
+    /// `[[alerts]]` rules
+    pub alerts: Vec<AlertRule>,
+}
+
+impl Config {
+    /// Directory holding the config file (`$XDG_CONFIG_HOME/sweem-tui`)
+    pub fn dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("sweem-tui"))
+    }
+
+    /// Full path of the config file
+    pub fn path() -> Option<PathBuf> {
+        Self::dir().map(|dir| dir.join(CONFIG_FILE))
+    }
+
+    /// Load the configuration; a missing file yields the defaults
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid config in {}", path.display()))
+    }
+
+    /// Look up a profile by name
+    pub fn profile(&self, name: &str) -> Result<&Profile> {
+        self.profiles.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            anyhow!("Unknown profile \"{}\" (configured: {})", name, if known.is_empty() { "none".to_string() } else { known.join(", ") })
+        })
+    }
+
+    /// Names of the production profiles in use: the aggregated ones, or the
+    /// ones pointing at `api_url`
+    pub fn production_profiles(&self, api_url: &str) -> Vec<&str> {
+        self.profiles
+            .iter()
+            .filter(|(name, profile)| {
+                profile.production
+                    && if self.api.aggregate.is_empty() {
+                        profile.api_url.trim_end_matches('/') == api_url.trim_end_matches('/')
+                    } else {
+                        self.api.aggregate.contains(name)
+                    }
+            })
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Parse configuration from TOML text
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check what the TOML types alone do not
+    pub fn validate(&self) -> Result<()> {
+        for rule in &self.sla {
+            rule.validate()?;
+        }
+        for rule in &self.alerts {
+            rule.validate()?;
+        }
+        self.columns.validate()?;
+        self.webhook.validate()?;
+        for name in &self.api.aggregate {
+            self.profile(name).context("[api] aggregate")?;
+        }
+        if !(0.25..=16.0).contains(&self.ui.timeline_zoom) {
+            bail!("[ui] timeline_zoom must be between 0.25 and 16 days per column");
+        }
+        Ok(())
+    }
+
+    /// Replace the config file, keeping the previous one as `config.toml.bak`;
+    /// comments of the old file are not carried over
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = Self::path().context("Could not determine config directory")?;
+        fs::create_dir_all(path.parent().unwrap_or(&path)).context("Failed to create config directory")?;
+        if path.exists() {
+            fs::copy(&path, path.with_extension("toml.bak"))
+                .with_context(|| format!("Failed to back up {}", path.display()))?;
+        }
+        let content = toml::to_string(self).context("Failed to serialize the config")?;
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::columns::Column;
+
+    #[test]
+    fn test_config_defaults_and_partial_sections() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+
+        let config = Config::parse("[particles]\ndensity = 0.5\n").unwrap();
+        assert_eq!(config.particles.density, 0.5);
+
+        let config = Config::parse("[particles]\nmode = \"snow\"\n").unwrap();
+        assert_eq!(config.particles.mode, Some(ParticleMode::Snow));
+
+        assert!(Config::parse("[particles]\ndensity = \"lots\"\n").is_err());
+
+        let config = Config::parse("[ui]\nlocale = \"ru\"\n").unwrap();
+        assert_eq!(config.ui.locale, Some(Locale::Ru));
+        assert_eq!(config.ui.stale_after_minutes, 10);
+
+        let config = Config::parse("[notifications]\nquiet_hours = { start = \"22:00\", end = \"07:00\" }\n").unwrap();
+        assert!(config.notifications.enabled);
+        assert!(config.notifications.quiet_hours.is_some());
+
+        let config = Config::parse("[profiles.staging]\napi_url = \"http://staging:5094\"\n").unwrap();
+        assert_eq!(config.profile("staging").unwrap().api_url, "http://staging:5094");
+        assert!(config.profile("prod").is_err());
+
+        let config = Config::parse("[api]\naggregate = [\"eu\", \"us\"]\n[profiles.eu]\napi_url = \"http://eu\"\n[profiles.us]\napi_url = \"http://us\"\n").unwrap();
+        assert_eq!(config.api.history_key("http://eu"), "aggregate:eu,us");
+        assert!(Config::parse("[api]\naggregate = [\"eu\"]\n").is_err(), "unknown profile");
+
+        let config = Config::parse("[profiles.prod]\napi_url = \"https://prod/\"\nproduction = true\n").unwrap();
+        assert_eq!(config.production_profiles("https://prod"), ["prod"]);
+        assert!(config.production_profiles("http://staging:5094").is_empty());
+
+        let config = Config::parse("[[sla]]\nname = \"Overrun\"\nmax_overrun_days = 5\n").unwrap();
+        assert_eq!(config.sla[0].max_overrun_days, Some(5));
+        assert!(Config::parse("[[sla]]\nname = \"Nothing\"\n").is_err());
+
+        let config = Config::parse("[[alerts]]\nname = \"Acme late\"\non = \"overdue\"\nfilter = \"client:Acme\"\n").unwrap();
+        assert!(config.alerts[0].notify);
+        assert!(Config::parse("[[alerts]]\nname = \"Typo\"\non = \"late\"\n").is_err());
+
+        let config = Config::parse("[history]\nretention_days = 90\n").unwrap();
+        assert_eq!(config.history, HistoryConfig { enabled: true, retention_days: 90 });
+
+        let config = Config::parse("[columns]\nprojects = [\"name\", \"planned-end\"]\n").unwrap();
+        assert_eq!(config.columns.projects, [Column::Name, Column::PlannedEnd]);
+        assert!(Config::parse("[columns]\nprojects = [\"login\"]\n").is_err());
+
+        let config = Config::parse("[ui]\nstart_tab = \"inbox\"\ntimeline_zoom = 7\n[particles]\nenabled = false\n").unwrap();
+        assert_eq!((config.ui.start_tab, config.ui.timeline_zoom), (StartTab::Inbox, 7.0));
+        assert!(!config.particles.enabled);
+        assert!(Config::parse("[ui]\nstart_tab = \"console\"\n").is_err());
+        assert!(Config::parse("[ui]\ntimeline_zoom = 30\n").is_err());
+
+        let config = Config::parse("[api]\ntransport = \"graphql\"\n").unwrap();
+        assert_eq!(config.api.transport, Transport::Graphql);
+        assert_eq!(config.api.graphql_url("http://api:5094/"), "http://api:5094/graphql");
+        assert!(Config::parse("[api]\ntransport = \"soap\"\n").is_err());
+        assert_eq!(config.api.connection(), ConnectionOptions::default());
+
+        let config = Config::parse("[api]\nhttp2 = \"always\"\nkeep_alive_secs = 0\n").unwrap();
+        let connection = config.api.connection();
+        assert_eq!((connection.http2, connection.keep_alive), (Http2::Always, None));
+    }
+}
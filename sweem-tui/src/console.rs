@@ -0,0 +1,144 @@
+//! API console (hidden tab, `F4`).
+//!
+//! A curl replacement for poking at the backend: type a path with query
+//! parameters (`/projects?page=2&pageSize=5` or `/projects page=2 pageSize=5`),
+//! `Enter` sends a GET through the worker's data source (same base URL,
+//! timeout and HTTP settings as the rest of the app) and the response is
+//! shown pretty-printed. Only GET is offered, so the console cannot change
+//! data.
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Previous requests kept for `Up` / `Down`
+const MAX_HISTORY: usize = 50;
+
+/// A GET request typed into the console
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsoleRequest {
+    /// Path below the base URL, starting with `/`
+    pub path: String,
+    pub query: Vec<(String, String)>,
+}
+
+impl ConsoleRequest {
+    /// Parse `/path?a=1&b=2`, optionally followed by more space-separated `key=value` pairs
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut words = input.split_whitespace();
+        let target = words.next().ok_or("enter a path such as /projects")?;
+        if target.contains("://") {
+            return Err("enter a path; the base URL is the one the app uses".to_string());
+        }
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let params = query.split('&').filter(|p| !p.is_empty()).chain(words);
+        let query = params
+            .map(|param| match param.split_once('=') {
+                Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+                _ => Err(format!("expected key=value, got \"{}\"", param)),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { path: format!("/{}", path.trim_start_matches('/')), query })
+    }
+}
+
+impl fmt::Display for ConsoleRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GET {}", self.path)?;
+        for (i, (key, value)) in self.query.iter().enumerate() {
+            write!(f, "{}{}={}", if i == 0 { '?' } else { '&' }, key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a console request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsoleResponse {
+    pub request: ConsoleRequest,
+    /// HTTP status; `None` when the request failed before an answer
+    pub status: Option<u16>,
+    /// Response body, or the error when there was no answer
+    pub body: String,
+    pub elapsed: Duration,
+}
+
+/// Console tab state
+#[derive(Debug, Clone, Default)]
+pub struct Console {
+    pub input: String,
+    /// Sent inputs, oldest first
+    history: Vec<String>,
+    /// Position while browsing the history
+    history_pos: Option<usize>,
+    pub response: Option<ConsoleResponse>,
+    /// Whether a request is in flight
+    pub running: bool,
+    pub scroll: u16,
+}
+
+impl Console {
+    /// Parse the input for sending and remember it
+    pub fn submit(&mut self) -> Result<ConsoleRequest, String> {
+        let request = ConsoleRequest::parse(&self.input)?;
+        let input = self.input.trim().to_string();
+        self.history.retain(|entry| *entry != input);
+        self.history.push(input);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.history_pos = None;
+        self.running = true;
+        Ok(request)
+    }
+
+    /// Show the response of the request in flight
+    pub fn receive(&mut self, response: ConsoleResponse) {
+        self.response = Some(response);
+        self.running = false;
+        self.scroll = 0;
+    }
+
+    /// Step through the history: older with `back`, newer without
+    pub fn recall(&mut self, back: bool) {
+        let pos = match (self.history_pos, back) {
+            (None, true) => self.history.len().checked_sub(1),
+            (None, false) => None,
+            (Some(pos), true) => Some(pos.saturating_sub(1)),
+            (Some(pos), false) => Some(pos + 1).filter(|&p| p < self.history.len()),
+        };
+        self.history_pos = pos;
+        self.input = pos.map(|p| self.history[p].clone()).unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_history() {
+        let request = ConsoleRequest::parse("projects?page=2&pageSize=5 sort=name").unwrap();
+        assert_eq!(request.path, "/projects");
+        assert_eq!(request.to_string(), "GET /projects?page=2&pageSize=5&sort=name");
+        assert!(ConsoleRequest::parse("").is_err());
+        assert!(ConsoleRequest::parse("http://evil.example/users").is_err());
+        assert!(ConsoleRequest::parse("/users page").is_err());
+
+        let mut console = Console::default();
+        for input in ["/users", "/clients", "/users"] {
+            console.input = input.to_string();
+            console.submit().unwrap();
+        }
+        console.recall(true);
+        assert_eq!(console.input, "/users", "repeats move to the end");
+        console.recall(true);
+        assert_eq!(console.input, "/clients");
+        console.recall(true);
+        assert_eq!(console.input, "/clients", "stops at the oldest");
+        console.recall(false);
+        console.recall(false);
+        assert_eq!(console.input, "", "back to an empty line");
+    }
+}
@@ -0,0 +1,157 @@
+//! Calendar date picker.
+//!
+//! Typing ISO dates blind is error-prone, so dates are picked on a month
+//! grid: `h`/`l` move a day, `k`/`j` a week, `H`/`L` a month, `[`/`]` a year
+//! and `t` jumps to today; arrow keys and `PageUp`/`PageDown` work too. The
+//! date fields of the project forms are pickers, and time travel (`@`) opens
+//! one in a popup, where `Enter` picks the date and `Backspace` goes back to
+//! the live view.
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use crossterm::event::KeyCode;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::theme::{colors, styles};
+use crate::tr;
+
+/// Width of the calendar popup, borders included
+pub const WIDTH: u16 = 24;
+/// Height of the calendar popup, borders included (a month spans up to six weeks)
+pub const HEIGHT: u16 = 11;
+
+/// The date a key moves the cursor to, or None when the key does not move it
+pub fn step(date: NaiveDate, key: KeyCode) -> Option<NaiveDate> {
+    match key {
+        KeyCode::Char('h') | KeyCode::Left => date.checked_sub_days(Days::new(1)),
+        KeyCode::Char('l') | KeyCode::Right => date.checked_add_days(Days::new(1)),
+        KeyCode::Char('k') | KeyCode::Up => date.checked_sub_days(Days::new(7)),
+        KeyCode::Char('j') | KeyCode::Down => date.checked_add_days(Days::new(7)),
+        KeyCode::Char('H') | KeyCode::PageUp => date.checked_sub_months(Months::new(1)),
+        KeyCode::Char('L') | KeyCode::PageDown => date.checked_add_months(Months::new(1)),
+        KeyCode::Char('[') => date.checked_sub_months(Months::new(12)),
+        KeyCode::Char(']') => date.checked_add_months(Months::new(12)),
+        KeyCode::Char('t') => Some(crate::clock::today()),
+        _ => None,
+    }
+}
+
+/// Open picker popup (time travel)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatePicker {
+    /// Date under the cursor
+    pub date: NaiveDate,
+}
+
+/// Month grid around a date, with the date highlighted
+pub struct Calendar<'a> {
+    date: NaiveDate,
+    title: &'a str,
+    footer: Option<&'a str>,
+}
+
+impl<'a> Calendar<'a> {
+    pub fn new(date: NaiveDate, title: &'a str) -> Self {
+        Self { date, title, footer: None }
+    }
+
+    /// Extra hint line below the movement keys (needs one more row)
+    pub fn footer(mut self, footer: &'a str) -> Self {
+        self.footer = Some(footer);
+        self
+    }
+}
+
+impl Widget for Calendar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let date = self.date;
+        let month_name = tr!("calendar-months").split(' ').nth(date.month0() as usize).unwrap_or_default();
+        let first_of_month = date.with_day(1).unwrap_or(date);
+        let days_in_month = first_of_month
+            .checked_add_months(Months::new(1))
+            .and_then(|next| next.pred_opt())
+            .map_or(31, |last| last.day());
+        // Day of week of the 1st (0 = Monday, 6 = Sunday)
+        let first_weekday = first_of_month.weekday().num_days_from_monday() as usize;
+
+        let mut lines = vec![Line::from(Span::styled(
+            format!("{:^22}", format!("{} {}", month_name, date.year())),
+            Style::default().fg(colors::BLUE).add_modifier(Modifier::BOLD),
+        ))];
+
+        let weekdays: Vec<&str> = tr!("calendar-weekdays").split(' ').collect();
+        let weekday_name = |n: usize| weekdays.get(n).copied().unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", weekdays[..weekdays.len().min(5)].join(" ")), styles::text_dim()),
+            Span::styled(format!("{} ", weekday_name(5)), Style::default().fg(colors::BLUE)),
+            Span::styled(weekday_name(6), Style::default().fg(colors::RED)),
+        ]));
+
+        let today = crate::clock::today();
+        let mut day = 1u32;
+        for week in 0..6 {
+            let week_start = day;
+            let mut spans = vec![Span::raw(" ")];
+            for weekday in 0..7 {
+                if (week == 0 && weekday < first_weekday) || day > days_in_month {
+                    spans.push(Span::raw("   "));
+                    continue;
+                }
+                let style = if day == date.day() {
+                    Style::default().fg(colors::BG_DARK).bg(colors::BLUE).add_modifier(Modifier::BOLD)
+                } else if first_of_month.with_day(day) == Some(today) {
+                    Style::default().fg(colors::YELLOW).add_modifier(Modifier::BOLD)
+                } else if weekday == 5 {
+                    Style::default().fg(colors::BLUE)
+                } else if weekday == 6 {
+                    Style::default().fg(colors::RED)
+                } else {
+                    styles::text()
+                };
+                spans.push(Span::styled(format!("{:2} ", day), style));
+                day += 1;
+            }
+            if week_start <= days_in_month {
+                lines.push(Line::from(spans));
+            }
+        }
+        lines.push(Line::from(Span::styled(tr!("calendar-hint"), styles::text_hint())));
+        if let Some(footer) = self.footer {
+            lines.push(Line::from(Span::styled(footer, styles::text_hint())));
+        }
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!(" {} ", self.title))
+                    .title_style(styles::title())
+                    .borders(Borders::ALL)
+                    .border_style(styles::border_focused())
+                    .style(Style::default().bg(colors::BG_MEDIUM)),
+            )
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_move_around_the_grid() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        let moved = |key| step(date, key).unwrap().to_string();
+        assert_eq!(moved(KeyCode::Char('l')), "2025-02-01");
+        assert_eq!(moved(KeyCode::Up), "2025-01-24");
+        assert_eq!(moved(KeyCode::Char('j')), "2025-02-07");
+        assert_eq!(moved(KeyCode::Char('L')), "2025-02-28", "clamped to the end of a shorter month");
+        assert_eq!(moved(KeyCode::Char('[')), "2024-01-31");
+        assert_eq!(step(date, KeyCode::Char('x')), None);
+    }
+}
@@ -0,0 +1,315 @@
+//! Demo data source (`--demo`).
+//!
+//! Generates clients, users and projects around today's date and keeps them
+//! in memory, so the TUI can be tried, shown or screenshotted without a
+//! backend. Changes work as usual but are gone on exit. The same `--seed`
+//! always generates the same data.
+
+use std::sync::Mutex;
+
+use chrono::{Duration, NaiveDate};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Map;
+use sweem_core::source::{DataSource, SourceFuture};
+use uuid::Uuid;
+
+use crate::api::ApiError;
+use crate::models::{
+    ClientDto, CreateClientDto, CreateProjectDto, CreateUserDto, ProjectDto, Role, UpdateClientDto, UpdateProjectDto,
+    UpdateUserDto, UserDto,
+};
+
+/// Teams the demo users are spread over (sent as the `team` user field)
+const TEAMS: [&str; 3] = ["Platform", "Delivery", "Data"];
+
+const CLIENTS: [(&str, &str); 8] = [
+    ("Acme Corp", "1 Road Runner Way"),
+    ("Globex", "742 Evergreen Terrace"),
+    ("Initech", "4120 Freidrich Lane"),
+    ("Umbrella", "545 S Birdneck Rd"),
+    ("Hooli", "1 Hacker Way"),
+    ("Stark Industries", "10880 Malibu Point"),
+    ("Wayne Enterprises", "1007 Mountain Drive"),
+    ("Soylent", "27 Green Street"),
+];
+
+const USERS: [(&str, &str); 6] = [
+    ("Alice Smith", "alice"),
+    ("Bob Jones", "bob"),
+    ("Carol White", "carol"),
+    ("Dave Brown", "dave"),
+    ("Eve Black", "eve"),
+    ("Frank Green", "frank"),
+];
+
+const PROJECTS: [&str; 10] = [
+    "Website redesign",
+    "Mobile app",
+    "CRM rollout",
+    "Data warehouse",
+    "Billing migration",
+    "Intranet",
+    "Security audit",
+    "Analytics dashboard",
+    "Cloud move",
+    "Support portal",
+];
+
+/// Number of generated projects
+const PROJECT_COUNT: usize = 30;
+
+/// In-memory backend with generated data
+#[derive(Debug)]
+pub struct DemoSource {
+    data: Mutex<DemoData>,
+}
+
+#[derive(Debug, Default)]
+struct DemoData {
+    projects: Vec<ProjectDto>,
+    clients: Vec<ClientDto>,
+    users: Vec<UserDto>,
+}
+
+impl DemoSource {
+    /// Generate data from `seed` around `today`
+    pub fn new(seed: u64, today: NaiveDate) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut id = || Uuid::from_u128(rng.gen());
+        let clients: Vec<ClientDto> = CLIENTS
+            .iter()
+            .map(|(name, address)| ClientDto {
+                id: id(),
+                name: Some(name.to_string()),
+                address: Some(address.to_string()),
+                projects_total: 0,
+                projects_completed: 0,
+                extra: Map::new(),
+            })
+            .collect();
+        let users: Vec<UserDto> = USERS
+            .iter()
+            .enumerate()
+            .map(|(i, (name, login))| UserDto {
+                id: id(),
+                name: Some(name.to_string()),
+                login: Some(login.to_string()),
+                role: if i == 0 { Role::Admin } else { Role::Manager },
+                extra: Map::from_iter([("team".to_string(), TEAMS[i % TEAMS.len()].into())]),
+            })
+            .collect();
+        let projects = (0..PROJECT_COUNT)
+            .map(|i| {
+                let start = today - Duration::days(rng.gen_range(0..240));
+                let planned_end = start + Duration::days(rng.gen_range(20..150));
+                // Most finished projects are done around their deadline, some are still overdue
+                let actual_end = (planned_end < today && rng.gen_bool(0.7))
+                    .then(|| (planned_end + Duration::days(rng.gen_range(-10..20))).clamp(start, today));
+                let phase = i / PROJECTS.len();
+                ProjectDto {
+                    id: Uuid::from_u128(rng.gen()),
+                    client_id: clients[rng.gen_range(0..clients.len())].id,
+                    name: Some(match phase {
+                        0 => PROJECTS[i].to_string(),
+                        _ => format!("{} {}", PROJECTS[i % PROJECTS.len()], phase + 1),
+                    }),
+                    start_date: start,
+                    planned_end_date: planned_end,
+                    actual_end_date: actual_end,
+                    manager_id: users[rng.gen_range(0..users.len())].id,
+                    extra: Map::new(),
+                }
+            })
+            .collect();
+        Self { data: Mutex::new(DemoData { projects, clients, users }) }
+    }
+
+    /// Run `f` on the data
+    fn with<T>(&self, f: impl FnOnce(&mut DemoData) -> Result<T, ApiError>) -> Result<T, ApiError> {
+        f(&mut self.data.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl DemoData {
+    /// Clients with their project counts as the backend would report them
+    fn clients(&self) -> Vec<ClientDto> {
+        self.clients.iter().map(|client| self.counted(client)).collect()
+    }
+
+    fn counted(&self, client: &ClientDto) -> ClientDto {
+        let owned = self.projects.iter().filter(|p| p.client_id == client.id);
+        ClientDto {
+            projects_total: owned.clone().count() as i32,
+            projects_completed: owned.filter(|p| p.actual_end_date.is_some()).count() as i32,
+            ..client.clone()
+        }
+    }
+}
+
+fn not_found(entity: &str, id: Uuid) -> ApiError {
+    ApiError::Status { endpoint: format!("demo {}", entity), status: 404, message: format!("{} {} not found", entity, id) }
+}
+
+/// Answer without waiting
+fn ready<'a, T: Send + 'a>(result: Result<T, ApiError>) -> SourceFuture<'a, T> {
+    Box::pin(std::future::ready(result))
+}
+
+/// Remove the item with `id`, returning `id`
+fn remove<T>(items: &mut Vec<T>, id: Uuid, key: fn(&T) -> Uuid, entity: &str) -> Result<Uuid, ApiError> {
+    let index = items.iter().position(|item| key(item) == id).ok_or_else(|| not_found(entity, id))?;
+    items.remove(index);
+    Ok(id)
+}
+
+impl DataSource for DemoSource {
+    fn health_check(&self) -> SourceFuture<'_, bool> {
+        ready(Ok(true))
+    }
+
+    fn fetch_all_projects(&self) -> SourceFuture<'_, Vec<ProjectDto>> {
+        ready(self.with(|data| Ok(data.projects.clone())))
+    }
+
+    fn fetch_all_clients(&self) -> SourceFuture<'_, Vec<ClientDto>> {
+        ready(self.with(|data| Ok(data.clients())))
+    }
+
+    fn fetch_all_users(&self) -> SourceFuture<'_, Vec<UserDto>> {
+        ready(self.with(|data| Ok(data.users.clone())))
+    }
+
+    fn fetch_project(&self, id: Uuid) -> SourceFuture<'_, ProjectDto> {
+        ready(self.with(|data| data.projects.iter().find(|p| p.id == id).cloned().ok_or_else(|| not_found("project", id))))
+    }
+
+    fn fetch_client(&self, id: Uuid) -> SourceFuture<'_, ClientDto> {
+        ready(self.with(|data| {
+            let client = data.clients.iter().find(|c| c.id == id).ok_or_else(|| not_found("client", id))?;
+            Ok(data.counted(client))
+        }))
+    }
+
+    fn fetch_user(&self, id: Uuid) -> SourceFuture<'_, UserDto> {
+        ready(self.with(|data| data.users.iter().find(|u| u.id == id).cloned().ok_or_else(|| not_found("user", id))))
+    }
+
+    fn create_project<'a>(&'a self, project: &'a CreateProjectDto) -> SourceFuture<'a, Uuid> {
+        ready(self.with(|data| {
+            let id = Uuid::new_v4();
+            data.projects.push(ProjectDto {
+                id,
+                client_id: project.client_id,
+                name: project.name.clone(),
+                start_date: project.start_date,
+                planned_end_date: project.planned_end_date,
+                actual_end_date: project.actual_end_date,
+                manager_id: project.manager_id,
+                extra: Map::new(),
+            });
+            Ok(id)
+        }))
+    }
+
+    fn update_project<'a>(&'a self, id: Uuid, project: &'a UpdateProjectDto) -> SourceFuture<'a, ProjectDto> {
+        ready(self.with(|data| {
+            let stored = data.projects.iter_mut().find(|p| p.id == id).ok_or_else(|| not_found("project", id))?;
+            stored.client_id = project.client_id;
+            stored.name = project.name.clone();
+            stored.start_date = project.start_date;
+            stored.planned_end_date = project.planned_end_date;
+            stored.actual_end_date = project.actual_end_date;
+            stored.manager_id = project.manager_id;
+            Ok(stored.clone())
+        }))
+    }
+
+    fn delete_project(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        ready(self.with(|data| remove(&mut data.projects, id, |p| p.id, "project")))
+    }
+
+    fn create_client<'a>(&'a self, client: &'a CreateClientDto) -> SourceFuture<'a, Uuid> {
+        ready(self.with(|data| {
+            let id = Uuid::new_v4();
+            data.clients.push(ClientDto {
+                id,
+                name: client.name.clone(),
+                address: client.address.clone(),
+                projects_total: 0,
+                projects_completed: 0,
+                extra: Map::new(),
+            });
+            Ok(id)
+        }))
+    }
+
+    fn update_client<'a>(&'a self, id: Uuid, client: &'a UpdateClientDto) -> SourceFuture<'a, ClientDto> {
+        ready(self.with(|data| {
+            let stored = data.clients.iter_mut().find(|c| c.id == id).ok_or_else(|| not_found("client", id))?;
+            stored.name = client.name.clone();
+            stored.address = client.address.clone();
+            let stored = stored.clone();
+            Ok(data.counted(&stored))
+        }))
+    }
+
+    fn delete_client(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        ready(self.with(|data| remove(&mut data.clients, id, |c| c.id, "client")))
+    }
+
+    fn create_user<'a>(&'a self, user: &'a CreateUserDto) -> SourceFuture<'a, Uuid> {
+        ready(self.with(|data| {
+            let id = Uuid::new_v4();
+            data.users.push(UserDto {
+                id,
+                name: user.name.clone(),
+                login: user.login.clone(),
+                role: user.role,
+                extra: Map::new(),
+            });
+            Ok(id)
+        }))
+    }
+
+    fn update_user<'a>(&'a self, id: Uuid, user: &'a UpdateUserDto) -> SourceFuture<'a, UserDto> {
+        ready(self.with(|data| {
+            let stored = data.users.iter_mut().find(|u| u.id == id).ok_or_else(|| not_found("user", id))?;
+            stored.name = user.name.clone();
+            stored.login = user.login.clone();
+            stored.role = user.role;
+            Ok(stored.clone())
+        }))
+    }
+
+    fn delete_user(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        ready(self.with(|data| remove(&mut data.users, id, |u| u.id, "user")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_generated_data_and_changes() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+        let demo = DemoSource::new(7, today);
+        let projects = demo.fetch_all_projects().await.unwrap();
+        assert_eq!(projects.len(), PROJECT_COUNT);
+        assert_eq!(projects, DemoSource::new(7, today).fetch_all_projects().await.unwrap(), "same seed, same data");
+        assert!(projects.iter().all(|p| p.start_date < p.planned_end_date));
+        assert!(projects.iter().filter_map(|p| p.actual_end_date).all(|end| end <= today));
+
+        let clients = demo.fetch_all_clients().await.unwrap();
+        assert_eq!(clients.iter().map(|c| c.projects_total).sum::<i32>(), PROJECT_COUNT as i32);
+
+        let project = &projects[0];
+        let mut change = UpdateProjectDto::from_project(project);
+        change.actual_end_date = Some(today);
+        assert_eq!(demo.update_project(project.id, &change).await.unwrap().actual_end_date, Some(today));
+        assert_eq!(demo.delete_project(project.id).await.unwrap(), project.id);
+        assert_eq!(demo.fetch_project(project.id).await.unwrap_err().status(), Some(404));
+        assert_eq!(demo.fetch_changes().await.unwrap_err().status(), Some(404), "no watch mode");
+    }
+}
@@ -0,0 +1,215 @@
+//! What changed between two loads of the same data.
+//!
+//! Every load of projects, clients or users is reduced to [`Snapshot`]s
+//! (id, name and the compared fields as text) and matched by UUID against
+//! the previous load, giving added, removed and modified entities with
+//! field-level old → new values. Loads are compared before script, row and
+//! "My projects" filters, so changing a filter does not show up as removals.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::api::EntityType;
+use crate::models::{ClientDto, ProjectDto, UserDto};
+use crate::tr;
+
+/// An entity reduced to what the report compares
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub id: Uuid,
+    pub name: String,
+    /// API field name and display value
+    pub fields: Vec<(&'static str, String)>,
+}
+
+/// Shown for empty optional values
+const NONE: &str = "—";
+
+impl Snapshot {
+    /// Client and manager are compared by id, so renaming a client does not touch its projects
+    pub fn project(project: &ProjectDto) -> Self {
+        Self {
+            id: project.id,
+            name: project.display_name().to_string(),
+            fields: vec![
+                ("name", project.display_name().to_string()),
+                ("startDate", project.start_date.to_string()),
+                ("plannedEndDate", project.planned_end_date.to_string()),
+                ("actualEndDate", project.actual_end_date.map_or(NONE.to_string(), |d| d.to_string())),
+                ("clientId", project.client_id.to_string()),
+                ("managerId", project.manager_id.to_string()),
+            ],
+        }
+    }
+
+    /// Project counts are derived from the projects, so they are left out
+    pub fn client(client: &ClientDto) -> Self {
+        Self {
+            id: client.id,
+            name: client.display_name().to_string(),
+            fields: vec![
+                ("name", client.display_name().to_string()),
+                ("address", client.address.clone().unwrap_or_else(|| NONE.to_string())),
+            ],
+        }
+    }
+
+    pub fn user(user: &UserDto) -> Self {
+        Self {
+            id: user.id,
+            name: user.display_name().to_string(),
+            fields: vec![
+                ("name", user.display_name().to_string()),
+                ("login", user.login.clone().unwrap_or_else(|| NONE.to_string())),
+                ("role", user.role.to_string()),
+            ],
+        }
+    }
+}
+
+/// One field with a new value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+impl FieldChange {
+    /// A planned end moved later
+    pub fn is_slip(&self) -> bool {
+        // ISO dates compare as text
+        self.field == "plannedEndDate" && self.new > self.old
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified(Vec<FieldChange>),
+}
+
+/// One entity that changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub entity_type: EntityType,
+    pub id: Uuid,
+    pub name: String,
+    pub kind: ChangeKind,
+}
+
+/// Changes from `old` to `new`: added and modified in the order of `new`, then removed
+pub fn diff(entity_type: EntityType, old: &[Snapshot], new: &[Snapshot]) -> Vec<Change> {
+    let before: HashMap<Uuid, &Snapshot> = old.iter().map(|s| (s.id, s)).collect();
+    let mut changes = Vec::new();
+    for snapshot in new {
+        let kind = match before.get(&snapshot.id) {
+            None => ChangeKind::Added,
+            Some(previous) if *previous == snapshot => continue,
+            Some(previous) => ChangeKind::Modified(
+                previous
+                    .fields
+                    .iter()
+                    .zip(&snapshot.fields)
+                    .filter(|((_, old), (_, new))| old != new)
+                    .map(|((field, old), (_, new))| FieldChange { field, old: old.clone(), new: new.clone() })
+                    .collect(),
+            ),
+        };
+        changes.push(Change { entity_type, id: snapshot.id, name: snapshot.name.clone(), kind });
+    }
+    let after: HashMap<Uuid, &Snapshot> = new.iter().map(|s| (s.id, s)).collect();
+    changes.extend(old.iter().filter(|s| !after.contains_key(&s.id)).map(|s| Change {
+        entity_type,
+        id: s.id,
+        name: s.name.clone(),
+        kind: ChangeKind::Removed,
+    }));
+    changes
+}
+
+/// Changes found by the latest load of each entity type
+#[derive(Debug, Default)]
+pub struct ChangeReport {
+    previous: HashMap<EntityType, Vec<Snapshot>>,
+    pub changes: Vec<Change>,
+}
+
+impl ChangeReport {
+    /// Compare a load with the previous one of its type (the first load
+    /// only sets the baseline); returns the number of changed entities
+    pub fn record(&mut self, entity_type: EntityType, snapshots: Vec<Snapshot>) -> usize {
+        let count = match self.previous.get(&entity_type) {
+            Some(old) => {
+                let changes = diff(entity_type, old, &snapshots);
+                let count = changes.len();
+                self.changes.retain(|c| c.entity_type != entity_type);
+                self.changes.extend(changes);
+                count
+            }
+            None => 0,
+        };
+        self.previous.insert(entity_type, snapshots);
+        count
+    }
+}
+
+/// Field value for display: client and manager ids become names when known
+pub fn display_value(field: &str, value: &str, clients: &[ClientDto], users: &[UserDto]) -> String {
+    let id = value.parse::<Uuid>().ok();
+    let name = match field {
+        "clientId" => clients.iter().find(|c| Some(c.id) == id).map(|c| c.display_name()),
+        "managerId" => users.iter().find(|u| Some(u.id) == id).map(|u| u.display_name()),
+        _ => None,
+    };
+    name.unwrap_or(value).to_string()
+}
+
+/// Display label of a compared field
+pub fn field_label(field: &str) -> &'static str {
+    match field {
+        "name" => tr!("diff-field-name"),
+        "startDate" => tr!("diff-field-start"),
+        "plannedEndDate" => tr!("diff-field-planned-end"),
+        "actualEndDate" => tr!("diff-field-actual-end"),
+        "clientId" => tr!("diff-field-client"),
+        "managerId" => tr!("diff-field-manager"),
+        "address" => tr!("diff-field-address"),
+        "login" => tr!("diff-field-login"),
+        "role" => tr!("diff-field-role"),
+        _ => "?",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use sweem_core::fixtures;
+
+    #[test]
+    fn test_report_lists_field_changes() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let project = |n: u128, end| ProjectDto {
+            id: Uuid::from_u128(n),
+            name: Some(format!("P{}", n)),
+            ..fixtures::project(date(1), date(end))
+        };
+        let load = |projects: &[ProjectDto]| projects.iter().map(Snapshot::project).collect();
+
+        let mut report = ChangeReport::default();
+        assert_eq!(report.record(EntityType::Project, load(&[project(1, 10), project(2, 10)])), 0, "baseline");
+        assert_eq!(report.record(EntityType::Project, load(&[project(2, 10), project(1, 24), project(3, 5)])), 2);
+        let slip = FieldChange { field: "plannedEndDate", old: "2025-03-10".into(), new: "2025-03-24".into() };
+        assert!(slip.is_slip());
+        assert_eq!(report.changes[0].kind, ChangeKind::Modified(vec![slip]));
+        assert_eq!((report.changes[1].id, &report.changes[1].kind), (Uuid::from_u128(3), &ChangeKind::Added));
+
+        report.record(EntityType::Client, Vec::new());
+        report.record(EntityType::Project, load(&[project(1, 24)]));
+        let kinds: Vec<_> = report.changes.iter().map(|c| (c.name.as_str(), &c.kind)).collect();
+        assert_eq!(kinds, [("P2", &ChangeKind::Removed), ("P3", &ChangeKind::Removed)]);
+    }
+}
@@ -0,0 +1,40 @@
+//! Dirty flags: what changed since the last draw.
+//!
+//! Input, data and animation updates each mark the app dirty; the render
+//! pass only draws when something is marked and clears the flags after.
+
+#![allow(dead_code)]
+
+/// Set of reasons the screen needs redrawing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Dirty(u8);
+
+impl Dirty {
+    /// A key press changed UI state
+    pub const INPUT: Dirty = Dirty(1);
+    /// API data or connection state changed
+    pub const DATA: Dirty = Dirty(1 << 1);
+    /// Particles or timeline animations advanced
+    pub const ANIMATION: Dirty = Dirty(1 << 2);
+    /// Terminal size or focus changed
+    pub const LAYOUT: Dirty = Dirty(1 << 3);
+
+    /// Mark the given reasons
+    pub fn mark(&mut self, flags: Dirty) {
+        self.0 |= flags.0;
+    }
+
+    /// Whether any of the given reasons is marked
+    pub fn contains(self, flags: Dirty) -> bool {
+        self.0 & flags.0 != 0
+    }
+
+    pub fn is_clean(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return the marked reasons and clear them
+    pub fn take(&mut self) -> Dirty {
+        std::mem::take(self)
+    }
+}
@@ -0,0 +1,49 @@
+//! Time left until a project's planned end.
+//!
+//! Open projects show "ends in 12d" or "overdue by 5d" next to their dates,
+//! coloured from calm to alarming as the deadline nears, so nobody has to do
+//! date math off the raw dates. Completed projects have nothing due.
+
+use ratatui::style::Color;
+
+use crate::theme::colors;
+use crate::tr;
+
+/// Days left at or below which a deadline is close
+pub const CLOSE_DAYS: i64 = 3;
+
+/// Days left at or below which a deadline is approaching
+pub const APPROACHING_DAYS: i64 = 14;
+
+/// "ends in 12d", "ends today" or "overdue by 5d"
+pub fn label(days_left: i64) -> String {
+    match days_left {
+        0 => tr!("due-today").to_string(),
+        d if d > 0 => tr!("due-in", days = d),
+        d => tr!("due-overdue", days = -d),
+    }
+}
+
+/// Urgency colour: red when overdue, then orange, yellow and green
+pub fn color(days_left: i64) -> Color {
+    match days_left {
+        d if d < 0 => colors::RED,
+        d if d <= CLOSE_DAYS => colors::ORANGE,
+        d if d <= APPROACHING_DAYS => colors::YELLOW,
+        _ => colors::GREEN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_and_urgency() {
+        assert_eq!(label(12), "ends in 12d");
+        assert_eq!(label(0), "ends today");
+        assert_eq!(label(-5), "overdue by 5d");
+        let ramp: Vec<Color> = [-1, 0, CLOSE_DAYS + 1, APPROACHING_DAYS + 1].into_iter().map(color).collect();
+        assert_eq!(ramp, [colors::RED, colors::ORANGE, colors::YELLOW, colors::GREEN]);
+    }
+}
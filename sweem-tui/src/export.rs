@@ -0,0 +1,452 @@
+//! Headless data export (`sweem-tui export`).
+//!
+//! Fetches one entity collection from the data source and writes it as JSON
+//! or CSV to stdout or a file, without starting the TUI. Projects can also
+//! be written as a Mermaid gantt chart (see `mermaid.rs`) or a PNG Gantt
+//! image (see `graphics.rs`), and clients as a ranking by on-time delivery
+//! (also shown in the Stats tab). The capacity report projects how many
+//! projects each manager has running in each of the coming weeks, one column
+//! per week, for resourcing spreadsheets. Any collection can also be written
+//! as a Markdown table.
+
+use std::fs::File;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Weekday};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sweem_core::source::DataSource;
+use sweem_core::stats;
+
+use crate::markdown;
+use crate::models::{ClientDto, ProjectDto, Role, UserDto};
+use crate::graphics::{self, Protocol};
+use crate::mermaid;
+
+/// Width in cells of a PNG Gantt written to a file or pipe
+const PNG_COLUMNS: u16 = 120;
+
+/// Project names next to an inline Gantt are cut to this many characters
+const INLINE_NAME_WIDTH: usize = 32;
+
+/// Output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    /// GitHub-flavored Markdown table
+    Markdown,
+    /// Mermaid gantt block for markdown (projects only)
+    Mermaid,
+    /// Gantt chart image (projects only); shown inline on kitty/iTerm2 terminals
+    Png,
+    /// One id per line
+    Ids,
+    /// Number of rows
+    Count,
+}
+
+impl ExportFormat {
+    /// Whether the format draws a chart of projects rather than writing the rows
+    pub fn is_chart(self) -> bool {
+        matches!(self, ExportFormat::Mermaid | ExportFormat::Png)
+    }
+}
+
+/// Entity collection to export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportEntity {
+    Projects,
+    Clients,
+    Users,
+    /// Clients ranked by on-time completion rate and average overrun
+    ClientRanking,
+    /// Projected running projects per manager for each of the next `--weeks` weeks
+    Capacity,
+}
+
+/// Column the client ranking is ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RankingColumn {
+    #[default]
+    OnTime,
+    Overrun,
+    Completed,
+    Client,
+}
+
+impl RankingColumn {
+    pub const ALL: [RankingColumn; 4] =
+        [RankingColumn::OnTime, RankingColumn::Overrun, RankingColumn::Completed, RankingColumn::Client];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// One client in the ranking
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientRankingRow {
+    pub rank: usize,
+    pub client: String,
+    pub completed: usize,
+    pub on_time: usize,
+    pub on_time_percent: Option<f64>,
+    pub average_overrun_days: Option<f64>,
+}
+
+/// Rank clients by the projects they completed in `[from, to)`
+///
+/// The natural order of every column is "best first" (highest on-time rate,
+/// lowest overrun, most completions, name A-Z); `reverse` flips it. Clients
+/// without completions always come last.
+pub fn client_ranking(
+    projects: &[ProjectDto],
+    clients: &[ClientDto],
+    (from, to): (NaiveDate, NaiveDate),
+    as_of: NaiveDate,
+    (column, reverse): (RankingColumn, bool),
+) -> Vec<ClientRankingRow> {
+    let mut rows: Vec<ClientRankingRow> = clients
+        .iter()
+        .map(|client| {
+            let owned: Vec<ProjectDto> = projects.iter().filter(|p| p.client_id == client.id).cloned().collect();
+            let delivery = stats::on_time_delivery(&owned, from, to, as_of);
+            ClientRankingRow {
+                rank: 0,
+                client: client.display_name().to_string(),
+                completed: delivery.completed,
+                on_time: delivery.on_time,
+                on_time_percent: delivery.percent().map(|p| (p * 10.0).round() / 10.0),
+                average_overrun_days: stats::average_overrun(&owned, from, to, as_of).map(|d| (d * 10.0).round() / 10.0),
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        let by_name = a.client.to_lowercase().cmp(&b.client.to_lowercase());
+        let order = match column {
+            RankingColumn::OnTime => b.on_time_percent.partial_cmp(&a.on_time_percent),
+            RankingColumn::Overrun => a.average_overrun_days.partial_cmp(&b.average_overrun_days),
+            RankingColumn::Completed => Some(b.completed.cmp(&a.completed)),
+            RankingColumn::Client => Some(by_name),
+        }
+        .unwrap_or(std::cmp::Ordering::Equal);
+        let order = if reverse { order.reverse() } else { order };
+        (a.completed == 0).cmp(&(b.completed == 0)).then(order).then(by_name)
+    });
+    for (index, row) in rows.iter_mut().enumerate() {
+        row.rank = index + 1;
+    }
+    rows
+}
+
+/// Capacity report: one row per manager with the projects they have running
+/// in each of `weeks` weeks from `from` (one column per week, named after its
+/// first day) and the busiest week's count
+///
+/// Every manager is listed, idle ones with zeros, as are people who are not
+/// managers but run a project in the period.
+pub fn capacity_rows(projects: &[ProjectDto], users: &[UserDto], from: NaiveDate, weeks: usize) -> Vec<Value> {
+    let load = stats::manager_weekly_load(projects, from, weeks);
+    let mut managers: Vec<(String, &[u64])> = users
+        .iter()
+        .filter(|user| user.role == Role::Manager || load.contains_key(&user.id))
+        .map(|user| (user.display_name().to_string(), load.get(&user.id).map_or(&[][..], Vec::as_slice)))
+        .collect();
+    // Projects whose manager is not among the users still take up capacity
+    for (id, weekly) in &load {
+        if !users.iter().any(|user| user.id == *id) {
+            managers.push((id.to_string(), weekly));
+        }
+    }
+    managers.sort_by_key(|(name, _)| name.to_lowercase());
+
+    managers
+        .into_iter()
+        .map(|(name, weekly)| {
+            let mut row = serde_json::Map::new();
+            row.insert("manager".to_string(), Value::from(name));
+            for week in 0..weeks {
+                let start = from + chrono::Duration::weeks(week as i64);
+                row.insert(start.to_string(), Value::from(weekly.get(week).copied().unwrap_or(0)));
+            }
+            row.insert("peak".to_string(), Value::from(weekly.iter().max().copied().unwrap_or(0)));
+            Value::Object(row)
+        })
+        .collect()
+}
+
+/// A `field=value` filter: keeps rows whose field contains the value (case-insensitive)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Filter {
+    pub field: String,
+    pub value: String,
+}
+
+impl Filter {
+    /// Parse `field=value` (used as a clap value parser)
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let (field, value) = input
+            .split_once('=')
+            .ok_or_else(|| format!("expected FIELD=VALUE, got \"{}\"", input))?;
+        if field.trim().is_empty() {
+            return Err(format!("missing field name in \"{}\"", input));
+        }
+        Ok(Self {
+            field: field.trim().to_string(),
+            value: value.trim().to_lowercase(),
+        })
+    }
+
+    /// Whether a row matches (missing fields never match)
+    pub fn matches(&self, row: &Value) -> bool {
+        row.get(&self.field)
+            .map(|v| cell_text(v).to_lowercase().contains(&self.value))
+            .unwrap_or(false)
+    }
+}
+
+/// Fetch the entity collection as JSON rows
+async fn fetch_rows(source: &dyn DataSource, entity: ExportEntity, weeks: usize) -> Result<Vec<Value>> {
+    let rows = match entity {
+        ExportEntity::Projects => serde_json::to_value(source.fetch_all_projects().await?)?,
+        ExportEntity::Clients => serde_json::to_value(source.fetch_all_clients().await?)?,
+        ExportEntity::Users => serde_json::to_value(source.fetch_all_users().await?)?,
+        ExportEntity::ClientRanking => {
+            let (projects, clients) = tokio::try_join!(source.fetch_all_projects(), source.fetch_all_clients())?;
+            let today = crate::clock::today();
+            let all_time = (NaiveDate::MIN, NaiveDate::MAX);
+            serde_json::to_value(client_ranking(&projects, &clients, all_time, today, Default::default()))?
+        }
+        ExportEntity::Capacity => {
+            let (projects, users) = tokio::try_join!(source.fetch_all_projects(), source.fetch_all_users())?;
+            // Weeks start on Monday, like the spreadsheet's
+            let monday = crate::clock::today().week(Weekday::Mon).first_day();
+            Value::Array(capacity_rows(&projects, &users, monday, weeks))
+        }
+    };
+    match rows {
+        Value::Array(rows) => Ok(rows),
+        _ => anyhow::bail!("Unexpected response shape"),
+    }
+}
+
+/// Run the export
+pub async fn run(
+    source: &dyn DataSource,
+    entity: ExportEntity,
+    format: ExportFormat,
+    filters: &[Filter],
+    output: Option<&Path>,
+    graphics: Option<Protocol>,
+    weeks: usize,
+) -> Result<()> {
+    if format.is_chart() && entity != ExportEntity::Projects {
+        anyhow::bail!("The {:?} format is only available for projects", format);
+    }
+    let inline = output.is_none() && format == ExportFormat::Png && io::stdout().is_terminal();
+    if inline && graphics.is_none() {
+        anyhow::bail!("This terminal cannot show images (kitty or iTerm2 graphics); use --output FILE");
+    }
+    let rows: Vec<Value> = fetch_rows(source, entity, weeks)
+        .await?
+        .into_iter()
+        .filter(|row| filters.iter().all(|f| f.matches(row)))
+        .collect();
+
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(
+            File::create(path).with_context(|| format!("Failed to create {}", path.display()))?,
+        ),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    match format {
+        ExportFormat::Mermaid => {
+            let projects: Vec<ProjectDto> = serde_json::from_value(Value::Array(rows.clone()))?;
+            let clients = source.fetch_all_clients().await?;
+            let chart = mermaid::gantt("Projects", &projects, &clients, crate::clock::today());
+            writer.write_all(chart.as_bytes())?;
+        }
+        ExportFormat::Png => {
+            let mut projects: Vec<ProjectDto> = serde_json::from_value(Value::Array(rows.clone()))?;
+            projects.sort_by_key(|p| p.start_date);
+            let today = crate::clock::today();
+            match graphics.filter(|_| inline) {
+                Some(protocol) => write_inline_gantt(&mut writer, protocol, &projects, today)?,
+                None => writer.write_all(&graphics::gantt(&projects, today, PNG_COLUMNS, graphics::DEFAULT_CELL).png()?)?,
+            }
+        }
+        format => write_rows(&mut writer, &rows, format)?,
+    }
+    writer.flush().context("Failed to flush output")?;
+
+    if let Some(path) = output {
+        eprintln!("Exported {} row(s) to {}", rows.len(), path.display());
+    }
+    Ok(())
+}
+
+/// Gantt image with the project names to its left, one terminal row per
+/// project; taller charts than the screen are shown without names
+fn write_inline_gantt(writer: &mut dyn Write, protocol: Protocol, projects: &[ProjectDto], today: NaiveDate) -> Result<()> {
+    if projects.is_empty() {
+        return Ok(());
+    }
+    let (cols, rows) = crossterm::terminal::size().unwrap_or((PNG_COLUMNS, 0));
+    let height = projects.len() as u16;
+    let names: Vec<String> = projects.iter().map(|p| p.display_name().chars().take(INLINE_NAME_WIDTH).collect()).collect();
+    let name_width = if height < rows { names.iter().map(|n| n.chars().count()).max().unwrap_or(0) } else { 0 };
+    let chart_cols = cols.saturating_sub(name_width as u16 + 1).max(10);
+    let png = graphics::gantt(projects, today, chart_cols, graphics::cell_size()).png()?;
+    let image = graphics::escape(protocol, 1, &png, chart_cols, height);
+    if name_width == 0 {
+        writer.write_all(image.as_bytes())?;
+        writeln!(writer, "{}", "\n".repeat(height as usize - 1))?;
+        return Ok(());
+    }
+    for name in &names {
+        writeln!(writer, "{}", name)?;
+    }
+    // Back up to the first name and place the image right of the longest one
+    write!(writer, "\x1b[{}A\x1b[{}G{}\x1b[{}B\r", height, name_width + 2, image, height)?;
+    Ok(())
+}
+
+/// Write rows in one of the row formats (`query` writes its matches with it too)
+pub fn write_rows(writer: &mut dyn Write, rows: &[Value], format: ExportFormat) -> Result<()> {
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, rows).context("Failed to write JSON")?;
+            writeln!(writer)?;
+        }
+        ExportFormat::Csv => write_csv(rows, &mut *writer)?,
+        ExportFormat::Markdown => writer.write_all(markdown_table(rows).as_bytes())?,
+        ExportFormat::Ids => {
+            for id in rows.iter().filter_map(|row| row.get("id").and_then(Value::as_str)) {
+                writeln!(writer, "{}", id)?;
+            }
+        }
+        ExportFormat::Count => writeln!(writer, "{}", rows.len())?,
+        ExportFormat::Mermaid | ExportFormat::Png => anyhow::bail!("The {:?} format is a chart, not rows", format),
+    }
+    Ok(())
+}
+
+/// Write rows as CSV; columns follow the field order of the first row
+pub fn write_csv(rows: &[Value], writer: impl Write) -> Result<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+    let Some(Value::Object(first)) = rows.first() else {
+        return Ok(());
+    };
+    let columns: Vec<&String> = first.keys().collect();
+    csv.write_record(&columns)?;
+
+    for row in rows {
+        let record = columns
+            .iter()
+            .map(|column| row.get(column.as_str()).map(cell_text).unwrap_or_default());
+        csv.write_record(record)?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// Rows as a Markdown table; columns follow the field order of the first row
+pub fn markdown_table(rows: &[Value]) -> String {
+    let Some(Value::Object(first)) = rows.first() else {
+        return String::new();
+    };
+    let headers: Vec<String> = first.keys().cloned().collect();
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| headers.iter().map(|h| row.get(h.as_str()).map(cell_text).unwrap_or_default()).collect())
+        .collect();
+    markdown::table(&headers, &cells)
+}
+
+/// Render a JSON value as a spreadsheet cell
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use sweem_core::fixtures;
+
+    #[test]
+    fn test_filter_and_csv_output() {
+        let rows = vec![
+            json!({"name": "Acme, Inc.", "actualEndDate": null, "count": 3}),
+            json!({"name": "Globex", "actualEndDate": "2024-01-31", "count": 5}),
+        ];
+
+        let filter = Filter::parse("name=acme").unwrap();
+        assert!(filter.matches(&rows[0]) && !filter.matches(&rows[1]));
+        assert!(Filter::parse("no-separator").is_err());
+
+        let mut out = Vec::new();
+        write_csv(&rows, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "name,actualEndDate,count\n\"Acme, Inc.\",,3\nGlobex,2024-01-31,5\n"
+        );
+    }
+
+    #[test]
+    fn test_client_ranking_order() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        let client = |n: u128, name: &str| fixtures::client(uuid::Uuid::from_u128(n), name);
+        let project = |client: u128, late_days: i64| ProjectDto {
+            client_id: uuid::Uuid::from_u128(client),
+            actual_end_date: Some(date(2, 1) + chrono::Duration::days(late_days)),
+            ..fixtures::project(date(1, 1), date(2, 1))
+        };
+        let clients = [client(1, "Acme"), client(2, "Globex"), client(3, "Initech")];
+        // Acme: 1 of 2 on time (avg 3 days late); Globex: 1 of 1 on time; Initech: nothing completed
+        let projects = [project(1, 0), project(1, 6), project(2, -2)];
+        let period = (date(1, 1), date(4, 1));
+        let names = |sort| {
+            client_ranking(&projects, &clients, period, date(3, 1), sort).into_iter().map(|r| r.client).collect::<Vec<_>>()
+        };
+
+        assert_eq!(names((RankingColumn::OnTime, false)), ["Globex", "Acme", "Initech"]);
+        assert_eq!(names((RankingColumn::Completed, false)), ["Acme", "Globex", "Initech"]);
+        assert_eq!(names((RankingColumn::Client, true)), ["Globex", "Acme", "Initech"]);
+        let rows = client_ranking(&projects, &clients, period, date(3, 1), Default::default());
+        assert_eq!((rows[1].rank, rows[1].on_time_percent, rows[1].average_overrun_days), (2, Some(50.0), Some(3.0)));
+    }
+
+    #[test]
+    fn test_capacity_report() {
+        let monday = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let user = |n: u128, name: &str, role| UserDto { role, ..fixtures::user(uuid::Uuid::from_u128(n), name) };
+        let project = |manager: u128, weeks: i64| ProjectDto {
+            manager_id: uuid::Uuid::from_u128(manager),
+            ..fixtures::project(monday, monday + chrono::Duration::weeks(weeks) - chrono::Duration::days(1))
+        };
+        let users = [user(1, "Zoe", Role::Manager), user(2, "Adam", Role::Manager), user(3, "Root", Role::Admin)];
+        let projects = [project(1, 1), project(1, 2), project(9, 3)];
+
+        let rows = capacity_rows(&projects, &users, monday, 2);
+        let unknown = uuid::Uuid::from_u128(9).to_string();
+        assert_eq!(
+            markdown_table(&rows),
+            format!(
+                "| manager | 2025-03-03 | 2025-03-10 | peak |\n| --- | --- | --- | --- |\n\
+                 | {unknown} | 1 | 1 | 1 |\n| Adam | 0 | 0 | 0 |\n| Zoe | 2 | 1 | 2 |\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,430 @@
+//! Filter expressions for the Timeline, Clients and Users tabs (`/`).
+//!
+//! An expression is a list of terms, all of which must match, combined
+//! with `&&`, `||`, `!` and parentheses:
+//!
+//! ```text
+//! overdue && client:Acme          flag and field substring
+//! duration > 90 || !completed     numeric comparison, negation
+//! /re:^(web|api)/                 regex against any field
+//! manager:/re:^a.*e$/             regex against one field
+//! plannedEndDate < 2025-04-01     ISO dates compare as text
+//! ```
+//!
+//! Expressions are evaluated against an entity's JSON row (the API field
+//! names, matched case-insensitively) plus a few derived fields such as
+//! `client`, `manager`, `team`, `duration` and `overdue` for projects. Text
+//! matching and regexes are case-insensitive; double quotes keep spaces
+//! and operator characters such as `|` or `(` inside a term. A regex runs
+//! from `/re:` to the next `/` (`\/` for a slash in it), so it needs no
+//! quotes; `re:"..."` works as well.
+//!
+//! Parentheses and `!` nest at most [`MAX_DEPTH`] levels and an expression
+//! has at most [`MAX_TERMS`] terms, so pasted garbage is a parse error rather
+//! than a stack overflow.
+
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use chrono::NaiveDate;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::models::{ClientDto, ProjectDto, UserDto};
+use crate::teams;
+
+/// Deepest nesting of parentheses and `!`
+pub const MAX_DEPTH: usize = 32;
+
+/// Most terms in one expression
+pub const MAX_TERMS: usize = 256;
+
+/// Comparison operator of a `field OP value` term
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Not(Box<Node>),
+    /// Bare word: a true flag field, or a substring of any field
+    Word(String),
+    /// `field:value`
+    Contains(String, String),
+    /// `re:pattern` (any field) or `field:re:pattern`
+    Regex(Option<String>, Regex),
+    /// `field OP value`
+    Compare(String, Op, String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+/// A parsed filter expression
+#[derive(Debug, Clone)]
+pub struct FilterExpr {
+    source: String,
+    root: Node,
+}
+
+impl FilterExpr {
+    /// Parse an expression; the error describes what is wrong with it
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err("empty expression".to_string());
+        }
+        let terms = tokens.iter().filter(|t| matches!(t, Token::Word(_))).count();
+        if terms > MAX_TERMS {
+            return Err(format!("more than {} terms", MAX_TERMS));
+        }
+        let mut parser = Parser { tokens, pos: 0, depth: 0 };
+        let root = parser.or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(format!("unexpected {}", describe(token)));
+        }
+        Ok(Self { source: input.trim().to_string(), root })
+    }
+
+    /// The expression as typed
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether a JSON row matches
+    pub fn matches(&self, row: &Value) -> bool {
+        eval(&self.root, row)
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Word(word) => format!("\"{}\"", word),
+        Token::Op(_) => "comparison".to_string(),
+        Token::And => "\"&&\"".to_string(),
+        Token::Or => "\"||\"".to_string(),
+        Token::Not => "\"!\"".to_string(),
+        Token::Open => "\"(\"".to_string(),
+        Token::Close => "\")\"".to_string(),
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        let token = match c {
+            _ if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '(' | ')' => {
+                chars.next();
+                if c == '(' { Token::Open } else { Token::Close }
+            }
+            '&' => pair(&mut chars, '&', Token::And, None)?,
+            '|' => pair(&mut chars, '|', Token::Or, None)?,
+            '!' => pair(&mut chars, '=', Token::Op(Op::Ne), Some(Token::Not))?,
+            '=' => pair(&mut chars, '=', Token::Op(Op::Eq), Some(Token::Op(Op::Eq)))?,
+            '<' => pair(&mut chars, '=', Token::Op(Op::Le), Some(Token::Op(Op::Lt)))?,
+            '>' => pair(&mut chars, '=', Token::Op(Op::Ge), Some(Token::Op(Op::Gt)))?,
+            _ => {
+                // A word runs to whitespace or an operator; double quotes keep both
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '/' && chars.clone().take(4).eq("/re:".chars()) {
+                        chars.nth(3);
+                        word.push_str("re:");
+                        loop {
+                            match chars.next() {
+                                Some('/') => break,
+                                Some('\\') if chars.peek() == Some(&'/') => word.push(chars.next().unwrap_or_default()),
+                                Some(c) => word.push(c),
+                                None => return Err("unterminated /re:.../".to_string()),
+                            }
+                        }
+                    } else if c == '"' {
+                        chars.next();
+                        loop {
+                            match chars.next() {
+                                Some('"') => break,
+                                Some(c) => word.push(c),
+                                None => return Err("unterminated quote".to_string()),
+                            }
+                        }
+                    } else if c.is_whitespace() || "()&|!=<>".contains(c) {
+                        break;
+                    } else {
+                        word.push(c);
+                        chars.next();
+                    }
+                }
+                Token::Word(word)
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Operator of one or two characters: `token` if `second` follows, else `single`
+fn pair(chars: &mut Peekable<Chars>, second: char, token: Token, single: Option<Token>) -> Result<Token, String> {
+    let first = chars.next().unwrap_or_default();
+    if chars.peek() == Some(&second) {
+        chars.next();
+        Ok(token)
+    } else {
+        single.ok_or_else(|| format!("expected \"{}{}\"", first, second))
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Parentheses and `!` around the current position
+    depth: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn or(&mut self) -> Result<Node, String> {
+        let mut node = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            node = Node::Or(Box::new(node), Box::new(self.and()?));
+        }
+        Ok(node)
+    }
+
+    /// Terms next to each other are joined with `&&` as well
+    fn and(&mut self) -> Result<Node, String> {
+        let mut node = self.unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => self.pos += 1,
+                Some(Token::Word(_) | Token::Not | Token::Open) => {}
+                _ => return Ok(node),
+            }
+            node = Node::And(Box::new(node), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Node, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Node::Not(Box::new(self.nested(Self::unary)?))),
+            Some(Token::Open) => {
+                let node = self.nested(Self::or)?;
+                match self.next() {
+                    Some(Token::Close) => Ok(node),
+                    _ => Err("missing \")\"".to_string()),
+                }
+            }
+            Some(Token::Word(word)) => self.term(word),
+            Some(token) => Err(format!("unexpected {}", describe(&token))),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+
+    /// Parse one level deeper, up to `MAX_DEPTH`
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Node, String>) -> Result<Node, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(format!("nested more than {} levels deep", MAX_DEPTH));
+        }
+        self.depth += 1;
+        let node = parse(self);
+        self.depth -= 1;
+        node
+    }
+
+    fn term(&mut self, word: String) -> Result<Node, String> {
+        if let Some(Token::Op(op)) = self.peek() {
+            let op = *op;
+            self.pos += 1;
+            return match self.next() {
+                Some(Token::Word(value)) => Ok(Node::Compare(word, op, value)),
+                _ => Err(format!("missing value after \"{}\"", word)),
+            };
+        }
+        if let Some(pattern) = word.strip_prefix("re:") {
+            return Ok(Node::Regex(None, regex(pattern)?));
+        }
+        match word.split_once(':') {
+            Some(("", _)) => Err(format!("missing field name in \"{}\"", word)),
+            Some((field, value)) => match value.strip_prefix("re:") {
+                Some(pattern) => Ok(Node::Regex(Some(field.to_string()), regex(pattern)?)),
+                None => Ok(Node::Contains(field.to_string(), value.to_lowercase())),
+            },
+            None => Ok(Node::Word(word.to_lowercase())),
+        }
+    }
+}
+
+fn regex(pattern: &str) -> Result<Regex, String> {
+    Regex::new(&format!("(?i){}", pattern)).map_err(|e| format!("invalid regex \"{}\": {}", pattern, e))
+}
+
+/// Field of a row, matched case-insensitively
+fn field<'a>(row: &'a Value, name: &str) -> Option<&'a Value> {
+    row.as_object()?.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, v)| v)
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn values(row: &Value) -> impl Iterator<Item = &Value> {
+    row.as_object().into_iter().flat_map(|map| map.values())
+}
+
+fn eval(node: &Node, row: &Value) -> bool {
+    match node {
+        Node::And(a, b) => eval(a, row) && eval(b, row),
+        Node::Or(a, b) => eval(a, row) || eval(b, row),
+        Node::Not(node) => !eval(node, row),
+        Node::Word(word) => match field(row, word) {
+            Some(Value::Bool(flag)) => *flag,
+            _ => values(row).any(|v| text(v).to_lowercase().contains(word.as_str())),
+        },
+        Node::Contains(name, value) => field(row, name).is_some_and(|v| text(v).to_lowercase().contains(value.as_str())),
+        Node::Regex(Some(name), re) => field(row, name).is_some_and(|v| re.is_match(&text(v))),
+        Node::Regex(None, re) => values(row).any(|v| re.is_match(&text(v))),
+        Node::Compare(name, op, value) => {
+            let Some(actual) = field(row, name) else {
+                return false;
+            };
+            let actual = text(actual);
+            // Numbers compare as numbers, anything else (ISO dates included) as text
+            let order = match (actual.parse::<f64>(), value.parse::<f64>()) {
+                (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                _ => actual.to_lowercase().cmp(&value.to_lowercase()),
+            };
+            match op {
+                Op::Eq => order == Ordering::Equal,
+                Op::Ne => order != Ordering::Equal,
+                Op::Lt => order == Ordering::Less,
+                Op::Le => order != Ordering::Greater,
+                Op::Gt => order == Ordering::Greater,
+                Op::Ge => order != Ordering::Less,
+            }
+        }
+    }
+}
+
+/// JSON row of any entity
+pub fn row<T: Serialize>(item: &T) -> Value {
+    serde_json::to_value(item).unwrap_or_default()
+}
+
+fn extend(mut row: Value, fields: impl IntoIterator<Item = (&'static str, Value)>) -> Value {
+    if let Some(map) = row.as_object_mut() {
+        map.extend(fields.into_iter().map(|(k, v)| (k.to_string(), v)));
+    }
+    row
+}
+
+/// Project row with `client`, `manager`, `team` (the manager's), `duration` (planned days),
+/// `completed`, `overdue` and `daysLeft` (to the planned end; null once completed) as of a date
+pub fn project_row(project: &ProjectDto, clients: &[ClientDto], users: &[UserDto], as_of: NaiveDate) -> Value {
+    let client = clients.iter().find(|c| c.id == project.client_id).map(|c| c.display_name());
+    let manager = users.iter().find(|u| u.id == project.manager_id);
+    let team = manager.and_then(teams::team_of);
+    let manager = manager.map(|u| u.display_name());
+    extend(
+        row(project),
+        [
+            ("client", client.map_or(Value::Null, Value::from)),
+            ("manager", manager.map_or(Value::Null, Value::from)),
+            ("team", team.map_or(Value::Null, Value::from)),
+            ("duration", project.duration_days().into()),
+            ("completed", project.is_completed_as_of(as_of).into()),
+            ("overdue", project.is_overdue_as_of(as_of).into()),
+            ("daysLeft", project.days_left_as_of(as_of).map_or(Value::Null, Value::from)),
+        ],
+    )
+}
+
+/// User row with the role by name, `team` and `admin`/`manager` flags
+pub fn user_row(user: &UserDto) -> Value {
+    extend(
+        row(user),
+        [
+            ("role", user.role.to_string().into()),
+            ("team", teams::team_of(user).map_or(Value::Null, Value::from)),
+            ("admin", (!user.is_manager()).into()),
+            ("manager", user.is_manager().into()),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_expressions() {
+        let rows = [
+            json!({"name": "Website", "client": "Acme Corp", "duration": 120, "overdue": true, "plannedEndDate": "2025-03-01"}),
+            json!({"name": "Mobile App", "client": "Acme Corp", "duration": 30, "overdue": false, "plannedEndDate": "2025-05-01"}),
+            json!({"name": "Migration", "client": "Globex", "duration": 95, "overdue": true, "plannedEndDate": "2025-04-10"}),
+        ];
+        let names = |input: &str| -> Vec<&str> {
+            let expr = FilterExpr::parse(input).unwrap();
+            rows.iter().filter(|r| expr.matches(r)).map(|r| r["name"].as_str().unwrap()).collect()
+        };
+
+        assert_eq!(names("overdue && client:acme"), ["Website"]);
+        assert_eq!(names("duration > 90"), ["Website", "Migration"]);
+        assert_eq!(names("!overdue || Client:globex"), ["Mobile App", "Migration"]);
+        assert_eq!(names("re:^m"), ["Mobile App", "Migration"]);
+        assert_eq!(names("name:re:\"app$\""), ["Mobile App"]);
+        assert_eq!(names("/re:^(web|mig)/ && name:/re:n$/"), ["Migration"], "no quotes needed");
+        assert_eq!(names("client:/re:acme co/"), ["Website", "Mobile App"]);
+        assert_eq!(names("plannedEndDate < 2025-04-15 (duration >= 120 || name == migration)"), ["Website", "Migration"]);
+        assert_eq!(names("glob"), ["Migration"], "bare words search every field");
+
+        assert!(FilterExpr::parse("duration >").is_err());
+        assert!(FilterExpr::parse("(overdue").is_err());
+        assert!(FilterExpr::parse("re:(").is_err());
+        assert!(FilterExpr::parse("a & b").is_err());
+        assert!(FilterExpr::parse("/re:^web").is_err());
+
+        // Deep nesting is refused instead of overflowing the stack
+        let nested = |open: &str, depth| format!("{}overdue{}", open.repeat(depth), ")".repeat(if open == "(" { depth } else { 0 }));
+        assert!(FilterExpr::parse(&nested("(", MAX_DEPTH)).is_ok());
+        assert_eq!(FilterExpr::parse(&nested("(", 10_000)).unwrap_err(), "nested more than 32 levels deep");
+        assert!(FilterExpr::parse(&nested("!", 10_000)).is_err());
+        assert!(FilterExpr::parse(&vec!["a"; MAX_TERMS + 1].join(" || ")).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Changed-cell flash.
+//!
+//! When a load changes entities that are already on screen, the cells
+//! showing the changed fields are highlighted for [`FLASH_DURATION`], so live
+//! updates get noticed instead of silently replacing the lists. Which fields
+//! changed comes from the "What changed?" diff; a new entity lights its
+//! whole row. Client project counts are derived data the diff does not
+//! compare, so the app lights them itself under [`PROJECT_COUNTS`].
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::diff::{Change, ChangeKind};
+
+/// How long a changed cell stays highlighted
+pub const FLASH_DURATION: Duration = Duration::from_millis(1500);
+
+/// Pseudo-field of a client's completed/total project counts
+pub const PROJECT_COUNTS: &str = "projects";
+
+/// Pseudo-field lighting every cell of a row
+const ROW: &str = "*";
+
+/// Highlighted cells and when they were lit
+#[derive(Debug, Default)]
+pub struct CellFlash {
+    cells: HashMap<(Uuid, &'static str), Instant>,
+}
+
+impl CellFlash {
+    /// Light the fields of modified entities and the rows of added ones
+    pub fn light<'a>(&mut self, changes: impl IntoIterator<Item = &'a Change>, now: Instant) {
+        for change in changes {
+            match &change.kind {
+                ChangeKind::Added => self.light_cell(change.id, ROW, now),
+                ChangeKind::Modified(fields) => {
+                    for field in fields {
+                        self.light_cell(change.id, field.field, now);
+                    }
+                }
+                ChangeKind::Removed => {}
+            }
+        }
+    }
+
+    pub fn light_cell(&mut self, id: Uuid, field: &'static str, now: Instant) {
+        self.cells.insert((id, field), now);
+    }
+
+    /// Whether a cell showing any of `fields` of entity `id` is highlighted
+    pub fn is_lit(&self, id: Uuid, fields: &[&'static str]) -> bool {
+        !self.cells.is_empty() && std::iter::once(&ROW).chain(fields).any(|field| self.cells.contains_key(&(id, *field)))
+    }
+
+    /// Drop cells lit longer than [`FLASH_DURATION`] ago; returns whether any went out
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let before = self.cells.len();
+        self.cells.retain(|_, lit| now.duration_since(*lit) < FLASH_DURATION);
+        self.cells.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::EntityType;
+    use crate::diff::FieldChange;
+
+    #[test]
+    fn test_light_and_expire() {
+        let now = Instant::now();
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let change = |id, kind| Change { entity_type: EntityType::User, id, name: String::new(), kind };
+        let role = FieldChange { field: "role", old: "Manager".into(), new: "Admin".into() };
+        let mut flash = CellFlash::default();
+        flash.light(&[change(a, ChangeKind::Modified(vec![role])), change(b, ChangeKind::Added)], now);
+
+        assert!(flash.is_lit(a, &["role"]));
+        assert!(!flash.is_lit(a, &["name", "login"]));
+        assert!(flash.is_lit(b, &["name"]), "new rows light up entirely");
+
+        assert!(!flash.expire(now + FLASH_DURATION / 2));
+        assert!(flash.expire(now + FLASH_DURATION));
+        assert!(!flash.is_lit(a, &["role"]));
+    }
+}
@@ -0,0 +1,345 @@
+//! Inline raster charts (kitty and iTerm2 graphics protocols).
+//!
+//! Terminals that can show images get the Stats tab bar charts as real
+//! pixels on top of the text widgets, which stay rendered underneath as the
+//! fallback (and keep the labels and values). The charts are drawn into a
+//! plain RGB buffer, encoded as PNG and sent after each frame: kitty keeps a
+//! placement per image id until it is replaced or deleted, iTerm2 paints the
+//! image into the cells, so it is sent again whenever the frame was redrawn.
+//! The `png` export format draws the Gantt chart the same way.
+
+use std::io::{self, Write};
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::NaiveDate;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::models::ProjectDto;
+use crate::theme::colors;
+
+/// Base64 payload per kitty escape sequence
+const KITTY_CHUNK: usize = 4096;
+
+/// Cell size assumed when the terminal does not report its pixel size
+pub const DEFAULT_CELL: (u32, u32) = (10, 20);
+
+/// `[ui] graphics` setting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphicsMode {
+    /// Detect the terminal from the environment
+    #[default]
+    Auto,
+    Kitty,
+    Iterm,
+    /// Text charts only
+    Off,
+}
+
+/// Image protocol spoken by the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Kitty,
+    Iterm,
+}
+
+/// Pick the protocol for `mode`, reading environment variables through `env`
+pub fn detect(mode: GraphicsMode, env: impl Fn(&str) -> Option<String>) -> Option<Protocol> {
+    match mode {
+        GraphicsMode::Kitty => Some(Protocol::Kitty),
+        GraphicsMode::Iterm => Some(Protocol::Iterm),
+        GraphicsMode::Off => None,
+        // Multiplexers swallow the escape sequences unless configured for passthrough
+        GraphicsMode::Auto if env("TMUX").is_some() || env("STY").is_some() => None,
+        GraphicsMode::Auto => {
+            let term = env("TERM").unwrap_or_default();
+            let program = env("TERM_PROGRAM").unwrap_or_default();
+            if env("KITTY_WINDOW_ID").is_some() || term.contains("kitty") || program == "ghostty" {
+                Some(Protocol::Kitty)
+            } else if program == "iTerm.app" || program == "WezTerm" || env("LC_TERMINAL").as_deref() == Some("iTerm2") {
+                Some(Protocol::Iterm)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Pixel size of a terminal cell
+pub fn cell_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => {
+            (u32::from(size.width / size.columns).max(1), u32::from(size.height / size.rows).max(1))
+        }
+        _ => DEFAULT_CELL,
+    }
+}
+
+fn rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        _ => [0x80, 0x80, 0x80],
+    }
+}
+
+/// RGB pixel buffer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raster {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Raster {
+    pub fn new(width: u32, height: u32, background: Color) -> Self {
+        let pixels = rgb(background).repeat((width * height) as usize);
+        Self { width, height, pixels }
+    }
+
+    /// Fill a rectangle, clipped to the raster
+    pub fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, color: Color) {
+        let color = rgb(color);
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                let at = ((row * self.width + column) * 3) as usize;
+                self.pixels[at..at + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    pub fn png(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().context("Failed to encode PNG")?;
+        writer.write_image_data(&self.pixels).context("Failed to encode PNG")?;
+        writer.finish().context("Failed to encode PNG")?;
+        Ok(out)
+    }
+}
+
+/// Escape sequence showing `png` over `cols` x `rows` cells at the cursor,
+/// leaving the cursor where it was
+pub fn escape(protocol: Protocol, id: u32, png: &[u8], cols: u16, rows: u16) -> String {
+    let data = BASE64.encode(png);
+    match protocol {
+        Protocol::Kitty => {
+            let chunks: Vec<&str> = data
+                .as_bytes()
+                .chunks(KITTY_CHUNK)
+                .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+                .collect();
+            let mut out = String::new();
+            for (i, chunk) in chunks.iter().enumerate() {
+                let more = u8::from(i + 1 < chunks.len());
+                if i == 0 {
+                    // q=2: no replies on stdin, C=1: keep the cursor, p=1: replace the previous placement
+                    out.push_str(&format!("\x1b_Ga=T,f=100,q=2,C=1,i={},p=1,c={},r={},m={};{}\x1b\\", id, cols, rows, more, chunk));
+                } else {
+                    out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+                }
+            }
+            out
+        }
+        Protocol::Iterm => format!(
+            "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0;doNotMoveCursor=1:{}\x07",
+            png.len(),
+            cols,
+            rows,
+            data
+        ),
+    }
+}
+
+/// Escape sequence removing a kitty image
+pub fn delete_kitty(id: u32) -> String {
+    format!("\x1b_Ga=d,d=I,i={},q=2\x1b\\", id)
+}
+
+/// A bar of a [`ChartImage`], bottom-aligned
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageBar {
+    /// First cell column, relative to the image
+    pub column: u16,
+    /// Width in cells
+    pub width: u16,
+    /// Height in cells; may be fractional
+    pub height: f64,
+    pub color: Color,
+}
+
+/// A bar chart drawn over a screen area
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartImage {
+    /// Kitty image id; one per chart so placements replace each other
+    pub id: u32,
+    pub area: Rect,
+    pub bars: Vec<ImageBar>,
+}
+
+impl ChartImage {
+    pub fn raster(&self, (cell_w, cell_h): (u32, u32)) -> Raster {
+        let width = u32::from(self.area.width) * cell_w;
+        let height = u32::from(self.area.height) * cell_h;
+        let mut raster = Raster::new(width, height, colors::BG_DARK);
+        for bar in &self.bars {
+            let bar_height = ((bar.height * f64::from(cell_h)).round() as u32).min(height);
+            // A one-pixel gap keeps neighbouring bars apart
+            let gap = u32::from(cell_w >= 4);
+            let x = u32::from(bar.column) * cell_w;
+            raster.fill(x, height - bar_height, (u32::from(bar.width) * cell_w).saturating_sub(gap), bar_height, bar.color);
+        }
+        raster
+    }
+}
+
+/// Images currently shown by the terminal
+#[derive(Debug)]
+pub struct ImageLayer {
+    protocol: Protocol,
+    /// Shown images and their encoded escape sequences
+    shown: Vec<(ChartImage, String)>,
+    /// Terminal size the images were placed for
+    screen: Rect,
+}
+
+impl ImageLayer {
+    pub fn new(protocol: Protocol) -> Self {
+        Self { protocol, shown: Vec::new(), screen: Rect::default() }
+    }
+
+    /// Bring the terminal in line with `images` after a frame was drawn
+    pub fn sync(&mut self, images: Vec<ChartImage>, screen: Rect, out: &mut impl Write) -> io::Result<()> {
+        // Clearing on resize drops kitty placements, and iTerm2 images are gone once the cells are redrawn
+        let resized = screen != self.screen;
+        if self.protocol == Protocol::Kitty {
+            for (old, _) in &self.shown {
+                if !images.iter().any(|image| image.id == old.id) {
+                    out.write_all(delete_kitty(old.id).as_bytes())?;
+                }
+            }
+        }
+        let cell = cell_size();
+        let mut shown = Vec::with_capacity(images.len());
+        for image in images {
+            let unchanged = self.shown.iter().find(|(old, _)| !resized && *old == image);
+            let sequence = match unchanged {
+                Some(_) if self.protocol == Protocol::Kitty => {
+                    shown.extend(unchanged.cloned());
+                    continue;
+                }
+                Some((_, sequence)) => sequence.clone(),
+                None => {
+                    let png = image.raster(cell).png().map_err(io::Error::other)?;
+                    escape(self.protocol, image.id, &png, image.area.width, image.area.height)
+                }
+            };
+            crossterm::queue!(out, crossterm::cursor::MoveTo(image.area.x, image.area.y))?;
+            out.write_all(sequence.as_bytes())?;
+            shown.push((image, sequence));
+        }
+        out.flush()?;
+        self.shown = shown;
+        self.screen = screen;
+        Ok(())
+    }
+}
+
+/// Gantt chart, one `cell_h` tall row per project: completed, overdue,
+/// running and upcoming projects in the timeline colors and today as a line
+pub fn gantt(projects: &[ProjectDto], today: NaiveDate, cols: u16, cell: (u32, u32)) -> Raster {
+    match gantt_span(projects) {
+        Some(span) => gantt_in(projects, span, today, cols, cell, colors::BG_DARK),
+        None => Raster::new(u32::from(cols) * cell.0, 0, colors::BG_DARK),
+    }
+}
+
+/// First start and last end of the projects
+pub fn gantt_span(projects: &[ProjectDto]) -> Option<(NaiveDate, NaiveDate)> {
+    let first = projects.iter().map(|p| p.start_date).min()?;
+    let last = projects.iter().map(gantt_end).max()?;
+    Some((first, last))
+}
+
+fn gantt_end(project: &ProjectDto) -> NaiveDate {
+    project.actual_end_date.unwrap_or(project.planned_end_date)
+}
+
+/// [`gantt`] over a given date span and background, so the pages of a
+/// printed chart share one scale
+pub fn gantt_in(
+    projects: &[ProjectDto],
+    (first, last): (NaiveDate, NaiveDate),
+    today: NaiveDate,
+    cols: u16,
+    (cell_w, cell_h): (u32, u32),
+    background: Color,
+) -> Raster {
+    let width = u32::from(cols) * cell_w;
+    let mut raster = Raster::new(width, projects.len() as u32 * cell_h, background);
+    let days = (last - first).num_days().max(0) as f64 + 1.0;
+    let x = |date: NaiveDate| (((date - first).num_days() as f64 / days) * f64::from(width)).round().max(0.0) as u32;
+    for (row, project) in projects.iter().enumerate() {
+        let color = if project.is_completed_as_of(today) {
+            colors::PROJECT_COMPLETED
+        } else if project.is_overdue_as_of(today) {
+            colors::PROJECT_OVERDUE
+        } else if project.start_date <= today {
+            colors::PROJECT_ACTIVE
+        } else {
+            colors::FG_DIM
+        };
+        let (from, to) = (x(project.start_date), x(gantt_end(project) + chrono::Duration::days(1)));
+        let margin = cell_h / 5;
+        raster.fill(from, row as u32 * cell_h + margin, to.saturating_sub(from).max(1), cell_h - 2 * margin, color);
+    }
+    if (first..=last).contains(&today) {
+        raster.fill(x(today), 0, 2, raster.height, colors::TODAY_MARKER);
+    }
+    raster
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(raster: &Raster, x: u32, y: u32) -> [u8; 3] {
+        let at = ((y * raster.width + x) * 3) as usize;
+        [raster.pixels[at], raster.pixels[at + 1], raster.pixels[at + 2]]
+    }
+
+    #[test]
+    fn test_detect_and_draw() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| vars.iter().find(|(k, _)| *k == key).map(|(_, v)| v.to_string())
+        };
+        assert_eq!(detect(GraphicsMode::Auto, env(&[("TERM", "xterm-kitty")])), Some(Protocol::Kitty));
+        assert_eq!(detect(GraphicsMode::Auto, env(&[("TERM_PROGRAM", "iTerm.app")])), Some(Protocol::Iterm));
+        assert_eq!(detect(GraphicsMode::Auto, env(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux")])), None);
+        assert_eq!(detect(GraphicsMode::Auto, env(&[("TERM", "xterm-256color")])), None);
+        assert_eq!(detect(GraphicsMode::Off, env(&[("TERM", "xterm-kitty")])), None);
+
+        let image = ChartImage {
+            id: 1,
+            area: Rect::new(0, 0, 4, 2),
+            bars: vec![ImageBar { column: 1, width: 2, height: 0.5, color: colors::BLUE }],
+        };
+        let raster = image.raster((10, 20));
+        assert_eq!((raster.width, raster.height), (40, 40));
+        assert_eq!(pixel(&raster, 15, 39), rgb(colors::BLUE));
+        assert_eq!(pixel(&raster, 15, 20), rgb(colors::BG_DARK), "half a cell tall");
+        assert_eq!(pixel(&raster, 5, 39), rgb(colors::BG_DARK));
+        assert!(raster.png().unwrap().starts_with(b"\x89PNG"));
+
+        let kitty = escape(Protocol::Kitty, 7, &[0; 4000], 4, 2);
+        assert!(kitty.starts_with("\x1b_Ga=T,f=100,q=2,C=1,i=7,p=1,c=4,r=2,m=1;"));
+        assert_eq!(kitty.matches("\x1b_G").count(), 2, "split into 4096-byte chunks");
+        assert!(kitty.contains("\x1b\\\x1b_Gm=0;") && kitty.ends_with("AA==\x1b\\"));
+        assert!(escape(Protocol::Iterm, 7, &[0; 3], 4, 2).starts_with("\x1b]1337;File=inline=1;size=3;width=4;height=2;"));
+    }
+}
@@ -0,0 +1,189 @@
+//! Localized UI strings.
+//!
+//! Messages live in Fluent-style catalogs under `locales/` that are compiled
+//! into the binary: `key = value` lines, `{ $name }` placeables and indented
+//! continuation lines for multi-line messages. The locale is chosen at
+//! startup (`[ui] locale` in the config, then `LC_ALL`/`LC_MESSAGES`/`LANG`)
+//! and can be switched in the settings dialog; a key missing from a catalog
+//! falls back to English, then to the key itself.
+//!
+//! Use [`tr!`] for lookups: `tr!("tab-clients")` or
+//! `tr!("log-loaded-projects", count = n)`.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+/// Look up a localized message, optionally filling in `{ $name }` placeables
+#[macro_export]
+macro_rules! tr {
+    ($key:literal) => {
+        $crate::i18n::text($key)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format($key, &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+])
+    };
+}
+
+/// Supported UI languages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ru,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Ru];
+
+    /// Name of the language in that language
+    pub fn name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Ru => "Русский",
+        }
+    }
+
+    /// Parse a language tag such as `ru`, `ru-RU` or `ru_RU.UTF-8`
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag.split(['_', '-', '.', '@']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::En),
+            "ru" => Some(Locale::Ru),
+            _ => None,
+        }
+    }
+
+    /// Locale from the POSIX environment variables, in their usual precedence
+    pub fn from_env() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::parse(&value))
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.ftl"),
+            Locale::Ru => include_str!("../locales/ru.ftl"),
+        }
+    }
+}
+
+/// Locale used by [`text`] and [`format`] (index into [`Locale::ALL`])
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Parsed catalogs, one per entry of [`Locale::ALL`]
+static CATALOGS: OnceLock<Vec<HashMap<&'static str, String>>> = OnceLock::new();
+
+/// Switch the UI language
+pub fn set_locale(locale: Locale) {
+    let index = Locale::ALL.iter().position(|l| *l == locale).unwrap_or(0);
+    CURRENT.store(index as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    Locale::ALL[CURRENT.load(Ordering::Relaxed) as usize]
+}
+
+/// Localized text for a key in the current locale
+pub fn text(key: &'static str) -> &'static str {
+    lookup(locale(), key)
+}
+
+/// Localized text with `{ $name }` placeables replaced by the given values
+pub fn format(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = text(key).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{ ${} }}", name), &value.to_string());
+    }
+    message
+}
+
+fn lookup(locale: Locale, key: &'static str) -> &'static str {
+    let catalogs = CATALOGS.get_or_init(|| Locale::ALL.iter().map(|l| parse(l.source())).collect());
+    let index = Locale::ALL.iter().position(|l| *l == locale).unwrap_or(0);
+    catalogs[index]
+        .get(key)
+        .or_else(|| catalogs[0].get(key))
+        .map(String::as_str)
+        .unwrap_or(key)
+}
+
+/// Parse a catalog: `key = value`, indented lines continue the previous value
+fn parse(source: &'static str) -> HashMap<&'static str, String> {
+    let mut messages: HashMap<&'static str, String> = HashMap::new();
+    let mut last: Option<&'static str> = None;
+    for line in source.lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            last = None;
+            continue;
+        }
+        if line.starts_with(' ') {
+            if let Some(message) = last.and_then(|key| messages.get_mut(key)) {
+                if !message.is_empty() {
+                    message.push('\n');
+                }
+                message.push_str(&unescape(line.trim()));
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            messages.insert(key, unescape(value.trim()));
+            last = Some(key);
+        }
+    }
+    messages
+}
+
+/// Resolve Fluent string literals such as `{"{"}` (used for literal braces)
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("{\"") {
+        let Some(len) = rest[start + 2..].find("\"}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        out.push_str(&rest[start + 2..start + 2 + len]);
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogs_are_complete_and_consistent() {
+        let english = parse(Locale::En.source());
+        for locale in Locale::ALL {
+            let catalog = parse(locale.source());
+            for (key, message) in &english {
+                let translated = catalog
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{:?} is missing `{}`", locale, key));
+                // Translations must use the same placeables as the English text
+                for part in message.split("{ $").skip(1) {
+                    let name = part.split(' ').next().unwrap_or_default();
+                    assert!(translated.contains(&format!("{{ ${} }}", name)), "{:?} `{}` lacks ${}", locale, key, name);
+                }
+            }
+            assert_eq!(catalog.len(), english.len(), "{:?} has keys English lacks", locale);
+        }
+
+        assert_eq!(Locale::parse("ru_RU.UTF-8"), Some(Locale::Ru));
+        assert_eq!(Locale::parse("C"), None);
+        assert_eq!(lookup(Locale::Ru, "tab-clients"), "Клиенты");
+        assert_eq!(lookup(Locale::Ru, "no-such-key"), "no-such-key");
+        assert_eq!(unescape(r#"{"{"}/{"}"} resize"#), "{/} resize");
+    }
+}
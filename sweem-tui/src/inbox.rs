@@ -0,0 +1,244 @@
+//! Inbox of generated alerts.
+//!
+//! After every project refresh the inbox collects what deserves attention:
+//! projects that are overdue or due within `[notifications] inbox_due_days`,
+//! projects newly assigned to the `--user` login, and a summary of changes
+//! made since the previous run. `[[alerts]]` rules add their own items (see
+//! `alerts.rs`), and so do changes to watched entities (`subscriptions.rs`). Items keep a read flag and are persisted with the UI state,
+//! so the unread count survives restarts.
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::EntityType;
+use crate::models::ProjectDto;
+use crate::tr;
+
+/// Oldest items are dropped beyond this many
+const MAX_ITEMS: usize = 200;
+
+/// What an inbox item reports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum InboxEvent {
+    /// Past its planned end date and not completed
+    Overdue { project: Uuid, name: String, deadline: NaiveDate },
+    /// Planned end within the configured number of days
+    DueSoon { project: Uuid, name: String, deadline: NaiveDate },
+    /// The project's manager changed to the current user
+    Reassigned { project: Uuid, name: String },
+    /// Projects added, updated or removed since the previous run
+    Changed { added: usize, updated: usize, removed: usize },
+    /// An `[[alerts]]` rule fired for a project
+    Rule { project: Uuid, name: String, rule: String, detail: String },
+    /// A watched project or client changed
+    Watched { entity: EntityType, id: Uuid, name: String, detail: String },
+}
+
+impl InboxEvent {
+    /// Project the item is about, if any
+    pub fn project(&self) -> Option<Uuid> {
+        match self {
+            InboxEvent::Overdue { project, .. }
+            | InboxEvent::DueSoon { project, .. }
+            | InboxEvent::Reassigned { project, .. }
+            | InboxEvent::Rule { project, .. } => Some(*project),
+            InboxEvent::Watched { entity: EntityType::Project, id, .. } => Some(*id),
+            InboxEvent::Changed { .. } | InboxEvent::Watched { .. } => None,
+        }
+    }
+
+    /// Entity the item is about, if any
+    pub fn entity(&self) -> Option<(EntityType, Uuid)> {
+        match self {
+            InboxEvent::Watched { entity, id, .. } => Some((*entity, *id)),
+            _ => self.project().map(|id| (EntityType::Project, id)),
+        }
+    }
+
+    /// Short label shown in the kind column
+    pub fn label(&self) -> &'static str {
+        match self {
+            InboxEvent::Overdue { .. } => tr!("inbox-kind-overdue"),
+            InboxEvent::DueSoon { .. } => tr!("inbox-kind-due-soon"),
+            InboxEvent::Reassigned { .. } => tr!("inbox-kind-reassigned"),
+            InboxEvent::Changed { .. } => tr!("inbox-kind-changed"),
+            InboxEvent::Rule { .. } => tr!("inbox-kind-rule"),
+            InboxEvent::Watched { .. } => tr!("inbox-kind-watched"),
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            InboxEvent::Overdue { name, deadline, .. } => tr!("inbox-overdue", name = name, date = deadline),
+            InboxEvent::DueSoon { name, deadline, .. } => tr!("inbox-due-soon", name = name, date = deadline),
+            InboxEvent::Reassigned { name, .. } => tr!("inbox-reassigned", name = name),
+            InboxEvent::Changed { added, updated, removed } => {
+                tr!("inbox-changed", added = added, updated = updated, removed = removed)
+            }
+            InboxEvent::Rule { name, rule, detail, .. } => tr!("inbox-rule", rule = rule, name = name, detail = detail),
+            InboxEvent::Watched { name, detail, .. } => tr!("inbox-watched", name = name, detail = detail),
+        }
+    }
+
+    /// Deadline alerts are raised once per project and deadline
+    fn is_repeat_of(&self, other: &InboxEvent) -> bool {
+        match (self, other) {
+            (
+                InboxEvent::Overdue { project: a, deadline: da, .. },
+                InboxEvent::Overdue { project: b, deadline: db, .. },
+            )
+            | (
+                InboxEvent::DueSoon { project: a, deadline: da, .. },
+                InboxEvent::DueSoon { project: b, deadline: db, .. },
+            ) => a == b && da == db,
+            _ => false,
+        }
+    }
+}
+
+/// One alert in the inbox
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InboxItem {
+    /// Local time the alert was raised
+    pub at: NaiveDateTime,
+    pub event: InboxEvent,
+    pub read: bool,
+}
+
+/// What a refresh did to the inbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Refreshed {
+    /// Alerts added
+    pub added: usize,
+    /// Whether anything that is saved changed (alerts or the projects seen)
+    pub changed: bool,
+}
+
+/// Alerts, newest first, plus what is needed to detect the next ones
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Inbox {
+    pub items: Vec<InboxItem>,
+    /// Projects as of the last refresh
+    seen: BTreeMap<Uuid, ProjectDto>,
+    /// Set after the first refresh of a run, which reports changes since the previous run
+    #[serde(skip)]
+    compared: bool,
+}
+
+impl Inbox {
+    pub fn unread_count(&self) -> usize {
+        self.items.iter().filter(|item| !item.read).count()
+    }
+
+    /// Raise alerts for freshly loaded projects
+    pub fn refresh(&mut self, projects: &[ProjectDto], me: Option<Uuid>, now: NaiveDateTime, due_days: i64) -> Refreshed {
+        let today = now.date();
+        let mut events = Vec::new();
+        for project in projects.iter().filter(|p| !p.is_completed_as_of(today)) {
+            let (project_id, name, deadline) = (project.id, project.display_name().to_string(), project.planned_end_date);
+            if project.is_overdue_as_of(today) {
+                events.push(InboxEvent::Overdue { project: project_id, name, deadline });
+            } else if deadline - today <= Duration::days(due_days) {
+                events.push(InboxEvent::DueSoon { project: project_id, name, deadline });
+            }
+        }
+        if let Some(me) = me {
+            events.extend(
+                projects
+                    .iter()
+                    .filter(|p| p.manager_id == me && self.seen.get(&p.id).is_some_and(|old| old.manager_id != me))
+                    .map(|p| InboxEvent::Reassigned { project: p.id, name: p.display_name().to_string() }),
+            );
+        }
+        // Later refreshes mostly reflect this run's own edits, so only the first one compares
+        if !self.compared && !self.seen.is_empty() {
+            let added = projects.iter().filter(|p| !self.seen.contains_key(&p.id)).count();
+            let updated = projects.iter().filter(|p| self.seen.get(&p.id).is_some_and(|old| old != *p)).count();
+            let removed = self.seen.keys().filter(|id| !projects.iter().any(|p| p.id == **id)).count();
+            if added + updated + removed > 0 {
+                events.push(InboxEvent::Changed { added, updated, removed });
+            }
+        }
+        self.compared = true;
+        let seen: BTreeMap<_, _> = projects.iter().map(|p| (p.id, p.clone())).collect();
+        let moved = seen != self.seen;
+        self.seen = seen;
+        let added = self.add(events, now);
+        Refreshed { added, changed: moved || added > 0 }
+    }
+
+    /// Add unread items, skipping repeated deadline alerts; returns how many were added
+    pub fn add(&mut self, mut events: Vec<InboxEvent>, now: NaiveDateTime) -> usize {
+        events.retain(|event| !self.items.iter().any(|item| event.is_repeat_of(&item.event)));
+        let count = events.len();
+        for event in events {
+            self.items.insert(0, InboxItem { at: now, event, read: false });
+        }
+        self.items.truncate(MAX_ITEMS);
+        count
+    }
+
+    /// Flip the read flag of one item
+    pub fn toggle_read(&mut self, index: usize) {
+        if let Some(item) = self.items.get_mut(index) {
+            item.read = !item.read;
+        }
+    }
+
+    pub fn mark_all_read(&mut self) {
+        self.items.iter_mut().for_each(|item| item.read = true);
+    }
+
+    /// Remove one item
+    pub fn dismiss(&mut self, index: usize) {
+        if index < self.items.len() {
+            self.items.remove(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sweem_core::fixtures;
+
+    use super::*;
+
+    #[test]
+    fn test_alerts_are_raised_once() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let now = date(10).and_hms_opt(9, 0, 0).unwrap();
+        let (me, other) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let project = |n: u128, end, manager| ProjectDto {
+            id: Uuid::from_u128(100 + n),
+            manager_id: manager,
+            name: Some(format!("P{}", n)),
+            ..fixtures::project(date(1), date(end))
+        };
+        let mut inbox = Inbox::default();
+        let projects = vec![project(1, 8, other), project(2, 12, other), project(3, 30, other)];
+        assert_eq!(inbox.refresh(&projects, Some(me), now, 3), Refreshed { added: 2, changed: true });
+        assert!(matches!(inbox.items[1].event, InboxEvent::Overdue { deadline, .. } if deadline == date(8)));
+        assert!(matches!(inbox.items[0].event, InboxEvent::DueSoon { .. }));
+        let unchanged = Refreshed { added: 0, changed: false };
+        assert_eq!(inbox.refresh(&projects, Some(me), now, 3), unchanged, "deadline alerts are not repeated");
+
+        let projects = vec![project(1, 8, other), project(2, 12, other), project(3, 30, me)];
+        assert_eq!(inbox.refresh(&projects, Some(me), now, 3).added, 1);
+        assert_eq!(inbox.items[0].event, InboxEvent::Reassigned { project: Uuid::from_u128(103), name: "P3".into() });
+
+        // A new run compares with the persisted projects
+        let mut inbox: Inbox = serde_json::from_str(&serde_json::to_string(&inbox).unwrap()).unwrap();
+        inbox.toggle_read(0);
+        assert_eq!(inbox.unread_count(), 2);
+        let projects = vec![project(2, 20, other), project(3, 30, me), project(4, 30, me)];
+        inbox.refresh(&projects, Some(me), now, 3);
+        assert_eq!(inbox.items[0].event, InboxEvent::Changed { added: 1, updated: 1, removed: 1 });
+        inbox.mark_all_read();
+        assert_eq!(inbox.unread_count(), 0);
+    }
+}
@@ -0,0 +1,114 @@
+//! Inline editing of the Projects table (`Enter` on a name or planned end cell).
+//!
+//! Renaming a project or moving its deadline should not take the whole edit
+//! dialog. `h`/`l` move the cell cursor of the Projects table across the
+//! shown columns, and `Enter` on an editable cell turns it into an input: the
+//! name is typed, the planned end moves with the calendar keys of the date
+//! picker. `Enter` sends the project with that one field changed (the API
+//! only takes whole projects, so it is the usual update), `Esc` leaves the
+//! cell as it was. Inside the sandbox the change stays local.
+
+use crossterm::event::KeyCode;
+use uuid::Uuid;
+
+use crate::columns::Column;
+use crate::datepicker;
+use crate::models::{ProjectDto, UpdateProjectDto};
+
+/// Cell being edited in place
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineEdit {
+    pub id: Uuid,
+    pub column: Column,
+    /// Text of the cell as edited
+    pub input: String,
+}
+
+impl InlineEdit {
+    /// Whether cells of a column can be edited in place
+    pub fn editable(column: Column) -> bool {
+        matches!(column, Column::Name | Column::PlannedEnd)
+    }
+
+    /// Start editing a cell of the project, if the column is editable
+    pub fn new(project: &ProjectDto, column: Column) -> Option<Self> {
+        let input = match column {
+            Column::Name => project.display_name().to_string(),
+            Column::PlannedEnd => project.planned_end_date.to_string(),
+            _ => return None,
+        };
+        Some(Self { id: project.id, column, input })
+    }
+
+    /// Edit the input with a key; false when the key is not an edit
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        match self.column {
+            Column::PlannedEnd => {
+                let Ok(date) = self.input.parse() else {
+                    return false;
+                };
+                match datepicker::step(date, key) {
+                    Some(moved) => self.input = moved.to_string(),
+                    None => return false,
+                }
+            }
+            _ => match key {
+                KeyCode::Char(c) => self.input.push(c),
+                KeyCode::Backspace => {
+                    self.input.pop();
+                }
+                _ => return false,
+            },
+        }
+        true
+    }
+
+    /// The project's update with the edited field, or why it cannot be sent
+    pub fn update(&self, project: &ProjectDto) -> Result<UpdateProjectDto, String> {
+        let mut dto = UpdateProjectDto::from_project(project);
+        match self.column {
+            Column::Name => dto.name = Some(self.input.trim().to_string()),
+            _ => dto.planned_end_date = self.input.parse().map_err(|e: chrono::ParseError| e.to_string())?,
+        }
+        dto.validate()?;
+        Ok(dto)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use sweem_core::fixtures;
+
+    use super::*;
+
+    #[test]
+    fn test_edits_change_one_field() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let project = ProjectDto {
+            id: Uuid::from_u128(1),
+            name: Some("Website".into()),
+            client_id: Uuid::from_u128(2),
+            manager_id: Uuid::from_u128(3),
+            ..fixtures::project(date(1), date(10))
+        };
+        assert!(InlineEdit::new(&project, Column::Status).is_none());
+
+        let mut name = InlineEdit::new(&project, Column::Name).unwrap();
+        assert!(name.handle_key(KeyCode::Char('!')));
+        assert!(!name.handle_key(KeyCode::Down));
+        let dto = name.update(&project).unwrap();
+        assert_eq!((dto.name.as_deref(), dto.planned_end_date), (Some("Website!"), date(10)));
+        name.input = "  ".into();
+        assert!(name.update(&project).is_err());
+
+        let mut end = InlineEdit::new(&project, Column::PlannedEnd).unwrap();
+        assert!(end.handle_key(KeyCode::Char('j')));
+        assert!(!end.handle_key(KeyCode::Char('x')), "dates are not typed");
+        assert_eq!(end.update(&project).unwrap().planned_end_date, date(17));
+        for _ in 0..3 {
+            end.handle_key(KeyCode::Char('k'));
+        }
+        assert_eq!(end.update(&project).unwrap_err(), "End date must be after start date");
+    }
+}
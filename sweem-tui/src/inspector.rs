@@ -0,0 +1,102 @@
+//! Raw JSON inspector (`J`).
+//!
+//! Shows the selected project, client or user as the API sent it: the known
+//! fields in schema order followed by the fields the models do not know
+//! (captured in their `extra` map), which are flagged so schema mismatches
+//! stand out without reaching for curl.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use serde_json::Value;
+
+use crate::api::EntityType;
+use crate::theme::{colors, styles};
+use crate::tr;
+
+/// Inspected entity and scroll position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inspector {
+    pub entity_type: EntityType,
+    pub id: uuid::Uuid,
+    pub scroll: u16,
+}
+
+/// Pretty-printed, highlighted lines of a JSON value; top-level keys listed
+/// in `unknown` are flagged
+pub fn lines(value: &Value, unknown: &[String]) -> Vec<Line<'static>> {
+    let mut out = Vec::new();
+    push(&mut out, 0, None, value, false, unknown);
+    out
+}
+
+fn punctuation(text: impl Into<String>) -> Span<'static> {
+    Span::styled(text.into(), Style::default().fg(colors::FG_DIM))
+}
+
+fn push(out: &mut Vec<Line<'static>>, indent: usize, key: Option<&str>, value: &Value, comma: bool, unknown: &[String]) {
+    let mut line = vec![Span::raw(" ".repeat(indent))];
+    let is_unknown = indent == 2 && key.is_some_and(|k| unknown.iter().any(|u| u == k));
+    if let Some(key) = key {
+        let style = if is_unknown { styles::warning().add_modifier(Modifier::BOLD) } else { Style::default().fg(colors::BLUE_LIGHT) };
+        line.push(Span::styled(Value::from(key).to_string(), style));
+        line.push(punctuation(": "));
+    }
+    let end = if comma { "," } else { "" };
+    let (open, close, children): (&str, &str, Vec<(Option<&str>, &Value)>) = match value {
+        Value::Object(map) if !map.is_empty() => ("{", "}", map.iter().map(|(k, v)| (Some(k.as_str()), v)).collect()),
+        Value::Array(items) if !items.is_empty() => ("[", "]", items.iter().map(|v| (None, v)).collect()),
+        scalar => {
+            let style = match scalar {
+                Value::String(_) => Style::default().fg(colors::GREEN_LIGHT),
+                Value::Number(_) => Style::default().fg(colors::ORANGE),
+                _ => Style::default().fg(colors::PURPLE),
+            };
+            line.push(Span::styled(scalar.to_string(), style));
+            line.push(punctuation(end));
+            if is_unknown {
+                line.push(Span::styled(format!("  ← {}", tr!("inspector-unknown")), styles::text_hint()));
+            }
+            out.push(Line::from(line));
+            return;
+        }
+    };
+    line.push(punctuation(open));
+    if is_unknown {
+        line.push(Span::styled(format!("  ← {}", tr!("inspector-unknown")), styles::text_hint()));
+    }
+    out.push(Line::from(line));
+    let count = children.len();
+    for (i, (key, child)) in children.into_iter().enumerate() {
+        push(out, indent + 2, key, child, i + 1 < count, unknown);
+    }
+    out.push(Line::from(vec![Span::raw(" ".repeat(indent)), punctuation(format!("{}{}", close, end))]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_lines() {
+        let value = json!({"name": "Acme", "tags": [1, true], "email": null, "meta": {}});
+        let text: Vec<String> = lines(&value, &["email".to_string()])
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            text,
+            [
+                "{",
+                "  \"name\": \"Acme\",",
+                "  \"tags\": [",
+                "    1,",
+                "    true",
+                "  ],",
+                "  \"email\": null,  ← unknown field",
+                "  \"meta\": {}",
+                "}",
+            ]
+        );
+    }
+}
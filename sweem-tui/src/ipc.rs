@@ -0,0 +1,226 @@
+//! Remote control over a local socket.
+//!
+//! While the TUI runs it listens on a Unix socket (a named pipe on Windows)
+//! for one-line commands, so scripts, tmux key bindings and editors can
+//! drive it:
+//!
+//! ```text
+//! refresh
+//! goto-project <uuid>
+//! set-filter <projects|clients|users> [FIELD=VALUE ...]
+//! ```
+//!
+//! Each line is answered with `ok` or `error: <reason>`. `sweem-tui remote
+//! <command...>` sends one command and prints the answer. Commands reach the
+//! app as [`Msg::Remote`], so they are recorded in sessions like key presses.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::api::EntityType;
+use crate::export::Filter;
+use crate::update::Msg;
+
+/// A command received over the socket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RemoteCommand {
+    /// Reload all data, like `r`
+    Refresh,
+    /// Show the timeline with this project selected
+    GotoProject(Uuid),
+    /// Replace the row filters of one entity type (none clears them)
+    SetFilter(EntityType, Vec<Filter>),
+}
+
+impl RemoteCommand {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut words = line.split_whitespace();
+        let command = words.next().ok_or("empty command")?;
+        let command = match command {
+            "refresh" => RemoteCommand::Refresh,
+            "goto-project" => {
+                let id = words.next().ok_or("usage: goto-project <uuid>")?;
+                RemoteCommand::GotoProject(id.parse().map_err(|_| format!("invalid project id \"{}\"", id))?)
+            }
+            "set-filter" => {
+                let Some(entity) = words.next().and_then(EntityType::from_name) else {
+                    return Err("usage: set-filter <projects|clients|users> [FIELD=VALUE ...]".to_string());
+                };
+                let filters = words.map(Filter::parse).collect::<Result<_, _>>()?;
+                return Ok(RemoteCommand::SetFilter(entity, filters));
+            }
+            other => return Err(format!("unknown command \"{}\" (expected refresh, goto-project or set-filter)", other)),
+        };
+        match words.next() {
+            Some(extra) => Err(format!("unexpected argument \"{}\"", extra)),
+            None => Ok(command),
+        }
+    }
+}
+
+/// Default socket: `$XDG_RUNTIME_DIR/sweem-tui.sock` (state directory as a fallback)
+#[cfg(unix)]
+pub fn default_path() -> Option<PathBuf> {
+    dirs::runtime_dir()
+        .or_else(crate::state::UiState::dir)
+        .map(|dir| dir.join("sweem-tui.sock"))
+}
+
+/// Default named pipe
+#[cfg(windows)]
+pub fn default_path() -> Option<PathBuf> {
+    Some(PathBuf::from(r"\\.\pipe\sweem-tui"))
+}
+
+/// Answer one connection: a reply line per command line
+async fn handle_connection(stream: impl AsyncRead + AsyncWrite, tx: mpsc::Sender<Msg>) {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = match RemoteCommand::parse(&line) {
+            Ok(command) => {
+                tracing::info!(?command, "Remote command");
+                if tx.send(Msg::Remote(command)).await.is_err() {
+                    break;
+                }
+                "ok\n".to_string()
+            }
+            Err(e) => format!("error: {}\n", e),
+        };
+        if writer.write_all(reply.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Removes the socket file when the TUI exits
+#[cfg(unix)]
+pub struct Listener {
+    listener: tokio::net::UnixListener,
+    path: PathBuf,
+}
+
+#[cfg(unix)]
+impl Drop for Listener {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.path).ok();
+    }
+}
+
+/// Bind the socket; fails if another instance is already listening on it
+#[cfg(unix)]
+pub fn bind(path: &Path) -> Result<Listener> {
+    if std::os::unix::net::UnixStream::connect(path).is_ok() {
+        bail!("{} is in use by another sweem-tui", path.display());
+    }
+    // Left behind by a crashed instance
+    std::fs::remove_file(path).ok();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+    let listener = tokio::net::UnixListener::bind(path).with_context(|| format!("Failed to listen on {}", path.display()))?;
+    Ok(Listener { listener, path: path.to_path_buf() })
+}
+
+/// Accept connections until the task is dropped
+#[cfg(unix)]
+pub async fn serve(listener: Listener, tx: mpsc::Sender<Msg>) {
+    loop {
+        match listener.listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_connection(stream, tx.clone()));
+            }
+            Err(e) => {
+                tracing::warn!("Remote control socket failed: {}", e);
+                break;
+            }
+        }
+    }
+}
+
+/// First instance of the named pipe
+#[cfg(windows)]
+pub struct Listener {
+    server: tokio::net::windows::named_pipe::NamedPipeServer,
+    path: PathBuf,
+}
+
+/// Create the named pipe; fails if another instance already owns it
+#[cfg(windows)]
+pub fn bind(path: &Path) -> Result<Listener> {
+    let server = tokio::net::windows::named_pipe::ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(path)
+        .with_context(|| format!("{} is in use by another sweem-tui", path.display()))?;
+    Ok(Listener { server, path: path.to_path_buf() })
+}
+
+/// Accept connections until the task is dropped
+#[cfg(windows)]
+pub async fn serve(listener: Listener, tx: mpsc::Sender<Msg>) {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let Listener { mut server, path } = listener;
+    loop {
+        if let Err(e) = server.connect().await {
+            tracing::warn!("Remote control pipe failed: {}", e);
+            break;
+        }
+        // A fresh instance waits for the next client while this one is served
+        let next = match ServerOptions::new().create(&path) {
+            Ok(next) => next,
+            Err(e) => {
+                tracing::warn!("Remote control pipe failed: {}", e);
+                break;
+            }
+        };
+        tokio::spawn(handle_connection(std::mem::replace(&mut server, next), tx.clone()));
+    }
+}
+
+/// Send one command to a running TUI and return its answer
+pub async fn send(path: &Path, command: &str) -> Result<String> {
+    #[cfg(unix)]
+    let stream = tokio::net::UnixStream::connect(path).await;
+    #[cfg(windows)]
+    let stream = tokio::net::windows::named_pipe::ClientOptions::new().open(path);
+    let stream = stream.with_context(|| format!("No sweem-tui is listening on {}", path.display()))?;
+
+    let (reader, mut writer) = tokio::io::split(stream);
+    writer.write_all(format!("{}\n", command).as_bytes()).await?;
+    let reply = BufReader::new(reader).lines().next_line().await?.unwrap_or_default();
+    match reply.strip_prefix("error: ") {
+        Some(message) => bail!("{}", message),
+        None => Ok(reply),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        let id = Uuid::from_u128(5);
+        assert_eq!(RemoteCommand::parse(" refresh "), Ok(RemoteCommand::Refresh));
+        assert_eq!(RemoteCommand::parse(&format!("goto-project {}", id)), Ok(RemoteCommand::GotoProject(id)));
+        assert_eq!(
+            RemoteCommand::parse("set-filter clients name=Acme"),
+            Ok(RemoteCommand::SetFilter(EntityType::Client, vec![Filter::parse("name=acme").unwrap()]))
+        );
+        assert_eq!(RemoteCommand::parse("set-filter users"), Ok(RemoteCommand::SetFilter(EntityType::User, vec![])));
+
+        assert!(RemoteCommand::parse("goto-project nope").is_err());
+        assert!(RemoteCommand::parse("refresh now").is_err());
+        assert!(RemoteCommand::parse("set-filter projects name").is_err());
+        assert!(RemoteCommand::parse("launch").unwrap_err().contains("unknown command"));
+    }
+}
@@ -0,0 +1,87 @@
+//! Navigation history.
+//!
+//! Like an editor jump list: every jump to another tab (or to an entity
+//! over the remote-control socket) is recorded, and `Ctrl+o` / `Ctrl+i`
+//! walk back and forth through the places visited. A place remembers the
+//! selection it was left with, so returning to it does not lose the spot.
+
+/// Oldest places are dropped beyond this many
+const MAX_PLACES: usize = 100;
+
+/// Visited places with a cursor at the current one
+#[derive(Debug, Clone)]
+pub struct JumpList<T> {
+    places: Vec<T>,
+    cursor: usize,
+}
+
+impl<T> Default for JumpList<T> {
+    fn default() -> Self {
+        Self { places: Vec::new(), cursor: 0 }
+    }
+}
+
+impl<T: Clone + PartialEq> JumpList<T> {
+    /// Record a jump; places ahead of the cursor are forgotten
+    pub fn jump(&mut self, from: T, to: T) {
+        if from == to {
+            return;
+        }
+        self.places.truncate(self.cursor + 1);
+        match self.places.last_mut() {
+            Some(last) => *last = from,
+            None => self.places.push(from),
+        }
+        self.places.push(to);
+        if self.places.len() > MAX_PLACES {
+            self.places.remove(0);
+        }
+        self.cursor = self.places.len() - 1;
+    }
+
+    /// Step back, leaving `current` to return to
+    pub fn back(&mut self, current: T) -> Option<T> {
+        if self.cursor == 0 {
+            return None;
+        }
+        self.places[self.cursor] = current;
+        self.cursor -= 1;
+        Some(self.places[self.cursor].clone())
+    }
+
+    /// Step forward again after going back
+    pub fn forward(&mut self, current: T) -> Option<T> {
+        if self.cursor + 1 >= self.places.len() {
+            return None;
+        }
+        self.places[self.cursor] = current;
+        self.cursor += 1;
+        Some(self.places[self.cursor].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_back_and_forward() {
+        let mut jumps = JumpList::default();
+        assert_eq!(jumps.back("a"), None);
+        jumps.jump("a", "b");
+        jumps.jump("b2", "c");
+        assert_eq!(jumps.back("c2"), Some("b2"), "the place is left as it was");
+        assert_eq!(jumps.back("b2"), Some("a"));
+        assert_eq!(jumps.back("a"), None);
+        assert_eq!(jumps.forward("a"), Some("b2"));
+        assert_eq!(jumps.forward("b2"), Some("c2"));
+        assert_eq!(jumps.forward("c2"), None);
+
+        // A new jump drops the places ahead
+        jumps.back("c2");
+        jumps.jump("b3", "d");
+        assert_eq!(jumps.forward("d"), None);
+        assert_eq!(jumps.back("d"), Some("b3"));
+        assert_eq!(jumps.back("b3"), Some("a"));
+    }
+}
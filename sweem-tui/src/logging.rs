@@ -0,0 +1,61 @@
+//! Structured file logging.
+//!
+//! Diagnostics go to a daily rolling file under the state directory
+//! (`$XDG_STATE_HOME/sweem-tui/logs`), never to the terminal, so they don't
+//! corrupt the TUI. The level comes from `--log-level` (`RUST_LOG` syntax).
+//! The last lines are also kept in memory for diagnostics bundles. With an
+//! OTLP collector configured, spans are exported to it as well (see `otel`).
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::fmt::writer::MakeWriterExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+use crate::config::OtelConfig;
+use crate::diagnostics::RingWriter;
+use crate::otel::{self, OtelGuard};
+
+use crate::state::UiState;
+
+/// File name prefix of the rolling log files
+const LOG_FILE_PREFIX: &str = "sweem-tui.log";
+
+/// Directory holding the log files
+pub fn log_dir() -> Option<PathBuf> {
+    UiState::dir().map(|dir| dir.join("logs"))
+}
+
+/// Flushes the log file and exported spans when dropped
+pub struct LogGuard {
+    _file: WorkerGuard,
+    _otel: Option<OtelGuard>,
+}
+
+/// Install the global subscriber; keep the guard alive until exit to flush logs
+pub fn init(level: &str, otel: &OtelConfig) -> Result<LogGuard> {
+    let filter = EnvFilter::try_new(level).with_context(|| format!("Invalid log level \"{}\"", level))?;
+    let dir = log_dir().context("Could not determine log directory")?;
+    std::fs::create_dir_all(&dir).context("Failed to create log directory")?;
+
+    let appender = tracing_appender::rolling::daily(&dir, LOG_FILE_PREFIX);
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let fmt = tracing_subscriber::fmt::layer()
+        .with_writer(writer.and(|| RingWriter))
+        .with_ansi(false)
+        .with_target(true)
+        .with_filter(filter);
+    let (otel_layer, otel_guard) = otel::layer(otel)?.unzip();
+    tracing_subscriber::registry()
+        .with(otel_layer)
+        .with(fmt)
+        .try_init()
+        .map_err(|e| anyhow::anyhow!(e))
+        .context("Failed to install log subscriber")?;
+
+    Ok(LogGuard { _file: guard, _otel: otel_guard })
+}
@@ -3,31 +3,106 @@
 //! A modern TUI frontend with Kanagawa Dragon theme aesthetic,
 //! featuring floating ash particles and full CRUD operations.
 
+mod alerts;
 mod api;
 mod app;
-mod models;
+mod attachments;
+mod audit;
+mod bench;
+mod bundle;
+mod cli;
+mod clipboard;
+mod clock;
+mod columns;
+mod completions;
+mod config;
+mod console;
+mod datepicker;
+mod demo;
+mod diagnostics;
+mod diff;
+mod dirty;
+mod due;
+mod expr;
+mod export;
+mod flash;
+mod graphics;
+mod i18n;
+mod inbox;
+mod inline;
+mod inspector;
+mod ipc;
+mod jumplist;
+mod logging;
+mod markdown;
+mod menu;
+mod mermaid;
+mod metrics;
+mod notify;
+mod otel;
+mod pacing;
+mod paging;
 mod particles;
+mod picker;
+mod plugin;
+mod prefetch;
+mod query;
+mod reconnect;
+mod report;
+mod sandbox;
+mod script;
+mod search;
+mod session;
+mod settings;
+mod sla;
+mod sort;
+mod state;
+mod store;
+mod subscriptions;
+mod teams;
+mod telemetry;
+mod timeseries;
 mod theme;
 mod timeline;
 mod ui;
+#[cfg(test)]
+mod snapshot_tests;
+mod undo;
+mod update;
+mod watch;
+mod webhook;
 
 use std::io::{self, stdout};
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
+use clap::Parser;
 use crossterm::{
-    event::{self, Event, KeyEventKind},
+    event::{self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Notify};
 
-use api::{ApiClient, ApiCommand, ApiMessage, EntityType};
-use app::App;
+use sweem_core::aggregate::Aggregate;
+use sweem_core::graphql::GraphQlClient;
+use sweem_core::models;
+use sweem_core::probe;
+use sweem_core::source::{DataSource, Fallback, Refresh};
 
-/// Frame rate for animations (approximately 30 FPS)
-const FRAME_DURATION: Duration = Duration::from_millis(33);
+use api::{ApiClient, ApiCommand, ApiMessage, AppError, EntityType, Prefetched};
+use app::App;
+use dirty::Dirty;
+use cli::{Cli, Command, ConfigAction};
+use export::ExportFormat;
+use console::ConsoleResponse;
+use pacing::FramePacer;
+use prefetch::{PrefetchJob, PrefetchQueue, PREFETCH_IDLE};
+use update::{update, Effect, Msg};
+use watch::{poll_interval, ChangeWatch, PollOutcome};
+use webhook::Webhook;
 
 /// Main entry point
 #[tokio::main]
@@ -35,23 +110,108 @@ async fn main() -> Result<()> {
     // Initialize error handling
     color_eyre::install().ok();
 
-    // Parse command line arguments for API URL
-    let args: Vec<String> = std::env::args().collect();
-    let api_url = args
-        .get(1)
-        .map(|s| s.as_str())
-        .unwrap_or(api::DEFAULT_BASE_URL);
+    // Parse command line arguments (API URL, options)
+    let cli = Cli::parse();
 
-    // Run the TUI
-    run_tui(api_url).await
+    if cli.collect_diagnostics {
+        let path = diagnostics::write_bundle(&std::env::current_dir()?, None)?;
+        println!("Diagnostics written to {}", path.display());
+        return Ok(());
+    }
+
+    match &cli.command {
+        Some(Command::BenchParticles { frames, sizes }) => {
+            let sizes = if sizes.is_empty() { bench::DEFAULT_SIZES.to_vec() } else { sizes.clone() };
+            bench::run(*frames, &sizes, cli.seed.unwrap_or(0));
+            return Ok(());
+        }
+        Some(Command::Completions { shell }) => {
+            return completions::generate(*shell, &mut io::stdout());
+        }
+        Some(Command::Profiles) => {
+            for name in config::Config::load()?.profiles.keys() {
+                println!("{}", name);
+            }
+            return Ok(());
+        }
+        Some(Command::Config { action }) => {
+            return match action {
+                ConfigAction::Export { output } => bundle::export(output.as_deref()),
+                ConfigAction::Import { file } => bundle::import(file),
+            };
+        }
+        Some(Command::Remote { command }) => {
+            let path = cli.socket_path().context("Could not determine the socket path")?;
+            println!("{}", ipc::send(&path, &command.join(" ")).await?);
+            return Ok(());
+        }
+        Some(Command::Export { entity, format, filters, output, weeks }) => {
+            let config = config::Config::load()?;
+            let source = headless_source(&cli, &config)?;
+            // Only images need the config's graphics setting
+            let graphics = match format {
+                ExportFormat::Png => graphics::detect(config.ui.graphics, |key| std::env::var(key).ok()),
+                _ => None,
+            };
+            return export::run(source.as_ref(), *entity, *format, filters, output.as_deref(), graphics, *weeks as usize).await;
+        }
+        Some(Command::Query { expression, entity, format, output }) => {
+            let config = config::Config::load()?;
+            let source = headless_source(&cli, &config)?;
+            return query::run(source.as_ref(), *entity, expression, *format, output.as_deref()).await;
+        }
+        Some(Command::Report { format, template, print_template, output, clipboard }) => {
+            if *print_template {
+                print!("{}", format.builtin());
+                return Ok(());
+            }
+            let config = config::Config::load()?;
+            let source = headless_source(&cli, &config)?;
+            return report::run(source.as_ref(), &config.report, *format, template.as_deref(), output.as_deref(), *clipboard)
+                .await;
+        }
+        Some(Command::Metrics { listen, textfile, interval }) => {
+            let config = config::Config::load()?;
+            let api_url = cli.resolve_api_url()?;
+            // Metrics are about the live API: no history fallback
+            let source = data_source(&cli, &config, &api_url)?;
+            return metrics::run(source.as_ref(), *listen, textfile.clone(), Duration::from_secs(*interval)).await;
+        }
+        None => {}
+    }
+    let api_url = cli.resolve_api_url()?;
+
+    // Log to a rolling file (and export spans if configured); the TUI still runs if that fails
+    let otel = config::Config::load().map(|c| c.otel).unwrap_or_default();
+    let log_guard = logging::init(&cli.log_level, &otel);
+    if let Err(e) = &log_guard {
+        eprintln!("File logging disabled: {:#}", e);
+    }
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), api_url = %api_url, "Starting sweem-tui");
+
+    // Run the TUI; a panic restores the terminal and leaves a diagnostics bundle
+    diagnostics::install_panic_hook();
+    let result = run_tui(&cli, &api_url).await;
+    if let Err(e) = &result {
+        tracing::error!("Exited with error: {:#}", e);
+    }
+    result
 }
 
 /// Run the TUI application
-async fn run_tui(api_url: &str) -> Result<()> {
+async fn run_tui(cli: &Cli, api_url: &str) -> Result<()> {
+    // A replayed session runs on the day it was recorded
+    let replay = cli.replay.as_deref().map(session::Session::load).transpose()?;
+    if let Some(session) = &replay {
+        clock::freeze(session.header.date);
+    }
+    let focus = cli.resolve_focus()?;
+
     // Setup terminal
     enable_raw_mode().context("Failed to enable raw mode")?;
     let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen).context("Failed to enter alternate screen")?;
+    // Mouse capture is for the right-click context menu
+    execute!(stdout, EnterAlternateScreen, EnableFocusChange, EnableMouseCapture).context("Failed to enter alternate screen")?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend).context("Failed to create terminal")?;
 
@@ -59,260 +219,872 @@ async fn run_tui(api_url: &str) -> Result<()> {
     let (api_tx, mut api_rx) = mpsc::channel::<ApiMessage>(32);
     let (cmd_tx, mut cmd_rx) = mpsc::channel::<ApiCommand>(32);
 
-    // Create API client and spawn worker task
-    let api_client = ApiClient::new(api_url)?;
-    let api_client_clone = api_client.clone();
-    let api_task = tokio::spawn(async move {
-        run_api_worker(api_client_clone, api_tx, &mut cmd_rx).await
-    });
+    // The UI language has to be known before the first log line is written
+    let config = match &replay {
+        Some(session) => Ok(session.header.config.clone()),
+        None => config::Config::load(),
+    };
+
+    // Cuts a read short when quitting
+    let cancel_reads = Arc::new(Notify::new());
+
+    // A replay gets its API messages from the recording, so commands go nowhere
+    let api_task = match &replay {
+        Some(_) => tokio::spawn(async move {
+            while let Some(cmd) = cmd_rx.recv().await {
+                if matches!(cmd, ApiCommand::Shutdown) {
+                    break;
+                }
+            }
+        }),
+        None => {
+            let source_config = config.as_ref().cloned().unwrap_or_default();
+            let source = data_source(cli, &source_config, api_url)?;
+            let api_tx = api_tx.clone();
+            let watch = cli.watch.or((source_config.api.watch_secs > 0).then_some(source_config.api.watch_secs));
+            let watch = watch.map(Duration::from_secs);
+            let list_page_size = (source_config.api.list_page_size > 0).then_some(source_config.api.list_page_size as i32);
+            let webhook = webhook::Webhook::new(&source_config.webhook)?;
+            let cancel_reads = cancel_reads.clone();
+            tokio::spawn(async move {
+                run_api_worker(source, api_tx, &mut cmd_rx, &cancel_reads, watch, list_page_size, webhook).await
+            })
+        }
+    };
 
     // Send initial refresh command
     cmd_tx.send(ApiCommand::RefreshAll).await.ok();
 
-    // Create application state
+    let locale = config.as_ref().ok().and_then(|c| c.ui.locale).or_else(i18n::Locale::from_env);
+    i18n::set_locale(locale.unwrap_or_default());
+
+    // Create application state, restoring persisted UI state (bookmarks)
     let mut app = App::new();
+    app.ui_state = match &replay {
+        Some(session) => state::UiState { read_only: true, ..session.header.ui_state.clone() },
+        None => state::UiState::load(),
+    };
+    let mut config = config.unwrap_or_else(|e| {
+        app.log(app::LogEntry::warning(format!("{:#}; using defaults", e)));
+        config::Config::default()
+    });
+    // The settings dialog shows the interval in use
+    if let Some(secs) = cli.watch {
+        config.api.watch_secs = secs;
+    }
+    app.me = match &replay {
+        Some(session) => session.header.user.clone(),
+        None => cli.resolve_user(&config),
+    };
+    app.pending_focus = match &replay {
+        Some(session) => session.header.focus,
+        None => focus,
+    };
+    if replay.is_none() {
+        app.api_urls = api_urls(cli, &config, api_url);
+        app.api_connection = config.api.connection();
+        let production = config.production_profiles(api_url);
+        if !cli.demo && !production.is_empty() {
+            app.production = Some(production.join("+"));
+        }
+    }
+    app.apply_config(config);
+    // Recorded data fills the screen until the first refresh arrives (or when it never does)
+    if replay.is_none() && !cli.demo && app.config.history.enabled {
+        match store::SnapshotStore::open_default(&app.config.api.history_key(api_url), app.config.history.retention_days) {
+            Ok(store) => {
+                app.store = Some(store);
+                app.show_recorded(None);
+            }
+            Err(e) => app.log(app::LogEntry::warning(tr!("log-history-failed", error = format!("{:#}", e)))),
+        }
+    }
+    plugin::register_plugins(&mut app);
+    match script::Scripts::load() {
+        Ok(scripts) => app.scripts = scripts,
+        Err(e) => app.log(app::LogEntry::warning(format!("{:#}", e))),
+    }
+    // Sessions pin the particle seed so a replay draws the same frames
+    let seed = match &replay {
+        Some(session) => Some(session.header.seed),
+        None if cli.record.is_some() => Some(cli.seed.unwrap_or_else(rand::random)),
+        None => cli.seed,
+    };
+    if let Some(seed) = seed {
+        app.particle_system.reseed(seed);
+    }
+
+    let size = terminal.size().context("Failed to read terminal size")?;
+    let mut recorder = None;
+    if let Some(path) = &cli.record {
+        let mut config = app.config.clone();
+        config.ui.locale = Some(i18n::locale());
+        let header = session::SessionHeader::new(
+            clock::today(),
+            seed.unwrap_or_default(),
+            (size.width, size.height),
+            config,
+            app.ui_state.clone(),
+            app.me.clone(),
+            app.pending_focus,
+        );
+        match session::Recorder::create(path, &header) {
+            Ok(r) => {
+                app.log(app::LogEntry::info(tr!("log-recording", path = path.display())));
+                recorder = Some(r);
+            }
+            Err(e) => app.log(app::LogEntry::warning(format!("{:#}", e))),
+        }
+    }
+
+    let (input_tx, mut input_rx) = mpsc::channel::<Msg>(64);
+    spawn_input_reader(input_tx.clone());
+
+    // Remote control (not while replaying, where recorded commands are fed instead)
+    let mut remote = None;
+    if let (None, Some(path)) = (&replay, cli.socket_path()) {
+        match ipc::bind(&path) {
+            Ok(listener) => {
+                tracing::info!(path = %path.display(), "Remote control listening");
+                remote = Some(tokio::spawn(ipc::serve(listener, input_tx)).abort_handle());
+            }
+            Err(e) => app.log(app::LogEntry::warning(tr!("log-remote-unavailable", error = format!("{:#}", e)))),
+        }
+    }
+
+    if let Some(session) = replay {
+        let header = &session.header;
+        app.log(app::LogEntry::info(tr!(
+            "log-replay-started",
+            events = session.events.len(),
+            seconds = session.duration().as_secs()
+        )));
+        if (header.width, header.height) != (size.width, size.height) {
+            app.log(app::LogEntry::warning(tr!(
+                "log-replay-size",
+                recorded = format!("{}x{}", header.width, header.height),
+                current = format!("{}x{}", size.width, size.height)
+            )));
+        }
+        input_rx = session::spawn_replay(session.events, input_rx);
+    }
+
+    // Termination signals end the loop like `q` does, so the terminal is restored
+    let (signal_tx, mut signal_rx) = mpsc::channel::<&'static str>(1);
+    tokio::spawn(forward_signals(signal_tx));
 
     // Main event loop
-    let result = run_event_loop(&mut terminal, &mut app, &mut api_rx, &cmd_tx).await;
+    let result = run_event_loop(&mut terminal, &mut app, &mut input_rx, &mut api_rx, &mut signal_rx, &cmd_tx, &mut recorder).await;
 
-    // Cleanup
+    // Cleanup (stopping the listener removes the socket file)
+    if let Some(remote) = remote {
+        remote.abort();
+    }
+    cmd_tx.send(ApiCommand::Shutdown).await.ok();
+    cancel_reads.notify_one();
+    let drained = drain_api_worker(&mut terminal, &mut app, &mut api_rx, api_task).await;
     disable_raw_mode().context("Failed to disable raw mode")?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)
+    execute!(terminal.backend_mut(), DisableMouseCapture, DisableFocusChange, LeaveAlternateScreen)
         .context("Failed to leave alternate screen")?;
     terminal.show_cursor().context("Failed to show cursor")?;
 
-    // Wait for API task to finish
-    api_task.abort();
+    result.and(drained)
+}
 
-    result
+/// Wait for the API worker to send the writes still queued, showing their
+/// progress, for at most `DRAIN_GRACE`
+async fn drain_api_worker(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    api_rx: &mut mpsc::Receiver<ApiMessage>,
+    api_task: tokio::task::JoinHandle<()>,
+) -> Result<()> {
+    let abort = api_task.abort_handle();
+    let deadline = tokio::time::sleep(api::DRAIN_GRACE);
+    tokio::pin!(api_task, deadline);
+    loop {
+        tokio::select! {
+            _ = &mut api_task => return Ok(()),
+            Some(message) = api_rx.recv() => app.handle_api_message(message),
+            _ = &mut deadline => {
+                tracing::warn!(progress = ?app.draining, "API worker did not drain in time; aborting");
+                abort.abort();
+                return Ok(());
+            }
+        }
+        if app.draining.is_some() {
+            terminal.draw(|frame| ui::render(frame, app))?;
+        }
+    }
+}
+
+/// Forward termination signals to the event loop
+#[cfg(unix)]
+async fn forward_signals(tx: mpsc::Sender<&'static str>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let (Ok(mut term), Ok(mut hup), Ok(mut int)) = (
+        signal(SignalKind::terminate()),
+        signal(SignalKind::hangup()),
+        signal(SignalKind::interrupt()),
+    ) else {
+        tracing::warn!("Failed to install signal handlers");
+        return;
+    };
+    let name = tokio::select! {
+        _ = term.recv() => "SIGTERM",
+        _ = hup.recv() => "SIGHUP",
+        _ = int.recv() => "SIGINT",
+    };
+    tx.send(name).await.ok();
+}
+
+/// Forward termination signals to the event loop
+#[cfg(not(unix))]
+async fn forward_signals(tx: mpsc::Sender<&'static str>) {
+    if tokio::signal::ctrl_c().await.is_ok() {
+        tx.send("Ctrl+C").await.ok();
+    }
+}
+
+/// REST client for `api_url` with the `[api]` connection settings
+fn api_client(config: &config::ApiConfig, api_url: &str) -> Result<ApiClient> {
+    ApiClient::with_options(api_url, api::DEFAULT_TIMEOUT, &config.connection())
+}
+
+/// Where data is loaded from and saved to: generated with `--demo`, the
+/// profiles of `[api] aggregate` merged, otherwise the API at `api_url`
+fn data_source(cli: &Cli, config: &config::Config, api_url: &str) -> Result<Arc<dyn DataSource>> {
+    if cli.demo {
+        tracing::info!("Using generated demo data");
+        return Ok(Arc::new(demo::DemoSource::new(cli.seed.unwrap_or_default(), clock::today())));
+    }
+    if !config.api.aggregate.is_empty() {
+        // Each backend has its GraphQL endpoint below its own URL
+        let api = config::ApiConfig { graphql_url: None, ..config.api.clone() };
+        let mut backends = Vec::new();
+        for name in &config.api.aggregate {
+            let url = &config.profile(name)?.api_url;
+            backends.push((name.clone(), backend(&api, url)?));
+        }
+        tracing::info!(backends = ?config.api.aggregate, "Aggregating backends");
+        return Ok(Arc::new(Aggregate::new(backends)));
+    }
+    backend(&config.api, api_url)
+}
+
+/// Data source of the commands that run without the TUI; without the
+/// network they read what the TUI last recorded
+fn headless_source(cli: &Cli, config: &config::Config) -> Result<Arc<dyn DataSource>> {
+    let api_url = cli.resolve_api_url()?;
+    let source = data_source(cli, config, &api_url)?;
+    if !cli.demo && config.history.enabled {
+        let key = config.api.history_key(&api_url);
+        if let Ok(store) = store::SnapshotStore::open_default(&key, config.history.retention_days) {
+            return Ok(Arc::new(Fallback::new(source, Arc::new(store::HistorySource::new(store)))));
+        }
+    }
+    Ok(source)
+}
+
+/// Base URLs the connection diagnosis checks: every aggregated backend, or
+/// the one API (none in demo mode)
+fn api_urls(cli: &Cli, config: &config::Config, api_url: &str) -> Vec<String> {
+    if cli.demo {
+        return Vec::new();
+    }
+    if config.api.aggregate.is_empty() {
+        return vec![api_url.to_string()];
+    }
+    config.api.aggregate.iter().filter_map(|name| config.profile(name).ok()).map(|p| p.api_url.clone()).collect()
+}
+
+/// The API at `api_url` over the transport configured in `[api]`
+fn backend(config: &config::ApiConfig, api_url: &str) -> Result<Arc<dyn DataSource>> {
+    let client = api_client(config, api_url)?;
+    Ok(match config.transport {
+        config::Transport::Rest => Arc::new(client),
+        config::Transport::Graphql => {
+            let url = config.graphql_url(api_url);
+            tracing::info!(%url, "Using the GraphQL transport");
+            Arc::new(GraphQlClient::with_options(url, &config.connection())?.with_rest(client))
+        }
+    })
 }
 
 /// Run the API worker task
 async fn run_api_worker(
-    client: ApiClient,
+    source: Arc<dyn DataSource>,
     tx: mpsc::Sender<ApiMessage>,
     rx: &mut mpsc::Receiver<ApiCommand>,
+    cancel_reads: &Notify,
+    mut watch: Option<Duration>,
+    list_page_size: Option<i32>,
+    webhook: Option<Webhook>,
 ) {
+    let mut prefetch = PrefetchQueue::default();
+    let mut poll = watch.map(|period| poll_timer(tokio::time::Instant::now(), period));
+    let mut changes = ChangeWatch::default();
+    let mut pace = watch;
     loop {
         tokio::select! {
-            Some(cmd) = rx.recv() => {
+            // User commands always run before prefetching
+            biased;
+            cmd = rx.recv() => {
+                let Some(cmd) = cmd else { break };
+                tracing::debug!(command = cmd.name(), "API command");
                 match cmd {
-                    ApiCommand::RefreshAll => {
-                        // Check connection
-                        let connected = client.health_check().await.unwrap_or(false);
-                        tx.send(ApiMessage::ConnectionStatus(connected)).await.ok();
-
-                        if connected {
-                            // Fetch all data concurrently
-                            let (projects, clients, users) = tokio::join!(
-                                client.fetch_all_projects(),
-                                client.fetch_all_clients(),
-                                client.fetch_all_users()
-                            );
-
-                            // Send results
-                            match projects {
-                                Ok(data) => { tx.send(ApiMessage::ProjectsLoaded(data)).await.ok(); }
-                                Err(e) => { tx.send(ApiMessage::Error(e.to_string())).await.ok(); }
-                            }
-                            match clients {
-                                Ok(data) => { tx.send(ApiMessage::ClientsLoaded(data)).await.ok(); }
-                                Err(e) => { tx.send(ApiMessage::Error(e.to_string())).await.ok(); }
-                            }
-                            match users {
-                                Ok(data) => { tx.send(ApiMessage::UsersLoaded(data)).await.ok(); }
-                                Err(e) => { tx.send(ApiMessage::Error(e.to_string())).await.ok(); }
-                            }
-                        } else {
-                            tx.send(ApiMessage::Error("Cannot connect to API".to_string())).await.ok();
-                        }
-                    }
-                    ApiCommand::RefreshProjects => {
-                        match client.fetch_all_projects().await {
-                            Ok(data) => { tx.send(ApiMessage::ProjectsLoaded(data)).await.ok(); }
-                            Err(e) => { tx.send(ApiMessage::Error(e.to_string())).await.ok(); }
-                        }
-                    }
-                    ApiCommand::RefreshClients => {
-                        match client.fetch_all_clients().await {
-                            Ok(data) => { tx.send(ApiMessage::ClientsLoaded(data)).await.ok(); }
-                            Err(e) => { tx.send(ApiMessage::Error(e.to_string())).await.ok(); }
-                        }
-                    }
-                    ApiCommand::RefreshUsers => {
-                        match client.fetch_all_users().await {
-                            Ok(data) => { tx.send(ApiMessage::UsersLoaded(data)).await.ok(); }
-                            Err(e) => { tx.send(ApiMessage::Error(e.to_string())).await.ok(); }
-                        }
-                    }
-                    ApiCommand::CheckConnection => {
-                        let connected = client.health_check().await.unwrap_or(false);
-                        tx.send(ApiMessage::ConnectionStatus(connected)).await.ok();
-                    }
                     ApiCommand::Shutdown => {
+                        drain_writes(&source, &tx, rx, list_page_size).await;
                         break;
                     }
-                    // CRUD operations for Clients
-                    ApiCommand::CreateClient(dto) => {
-                        match client.create_client(&dto).await {
-                            Ok(id) => {
-                                tx.send(ApiMessage::Created(EntityType::Client, id)).await.ok();
-                            }
-                            Err(e) => {
-                                tx.send(ApiMessage::Error(format!("Create client failed: {}", e))).await.ok();
-                            }
-                        }
+                    // Set in the settings dialog; the first poll comes one period from now
+                    ApiCommand::SetWatch(period) => {
+                        tracing::info!(?period, "Watch interval changed");
+                        watch = period;
+                        pace = period;
+                        poll = period.map(|period| poll_timer(tokio::time::Instant::now() + period, period));
                     }
-                    ApiCommand::UpdateClient(id, dto) => {
-                        match client.update_client(id, &dto).await {
-                            Ok(_) => {
-                                tx.send(ApiMessage::Updated(EntityType::Client)).await.ok();
-                            }
-                            Err(e) => {
-                                tx.send(ApiMessage::Error(format!("Update client failed: {}", e))).await.ok();
-                            }
+                    // Posted in the background, so a slow chat server never delays a refresh
+                    ApiCommand::PostWebhook(lines) => {
+                        if let Some(webhook) = webhook.clone() {
+                            let tx = tx.clone();
+                            tokio::spawn(async move {
+                                if let Err(e) = webhook.post(&lines).await {
+                                    tracing::warn!("Webhook post failed: {:#}", e);
+                                    tx.send(ApiMessage::WebhookFailed(format!("{:#}", e))).await.ok();
+                                }
+                            });
                         }
                     }
-                    ApiCommand::DeleteClient(id) => {
-                        match client.delete_client(id).await {
-                            Ok(deleted_id) => {
-                                tx.send(ApiMessage::Deleted(EntityType::Client, deleted_id)).await.ok();
-                            }
-                            Err(e) => {
-                                tx.send(ApiMessage::Error(format!("Delete client failed: {}", e))).await.ok();
+                    // SQLite runs on a blocking thread and never holds up a refresh
+                    ApiCommand::Store(job) => {
+                        let tx = tx.clone();
+                        tokio::spawn(async move {
+                            if let Ok(message) = tokio::task::spawn_blocking(move || job.run()).await {
+                                tx.send(message).await.ok();
                             }
-                        }
+                        });
                     }
-                    // CRUD operations for Projects
-                    ApiCommand::CreateProject(dto) => {
-                        match client.create_project(&dto).await {
-                            Ok(id) => {
-                                tx.send(ApiMessage::Created(EntityType::Project, id)).await.ok();
-                            }
-                            Err(e) => {
-                                tx.send(ApiMessage::Error(format!("Create project failed: {}", e))).await.ok();
-                            }
+                    // A new prefetch request replaces (cancels) whatever was queued
+                    ApiCommand::Prefetch(jobs) => {
+                        prefetch.clear();
+                        for (job, priority) in jobs {
+                            prefetch.push(job, priority);
                         }
                     }
-                    ApiCommand::UpdateProject(id, dto) => {
-                        match client.update_project(id, &dto).await {
-                            Ok(_) => {
-                                tx.send(ApiMessage::Updated(EntityType::Project)).await.ok();
-                            }
-                            Err(e) => {
-                                tx.send(ApiMessage::Error(format!("Update project failed: {}", e))).await.ok();
-                            }
-                        }
+                    cmd if cmd.is_mutation() => {
+                        prefetch.cancel_covered_by(&cmd);
+                        handle_api_command(&source, &tx, cmd, list_page_size).await;
                     }
-                    ApiCommand::DeleteProject(id) => {
-                        match client.delete_project(id).await {
-                            Ok(deleted_id) => {
-                                tx.send(ApiMessage::Deleted(EntityType::Project, deleted_id)).await.ok();
-                            }
-                            Err(e) => {
-                                tx.send(ApiMessage::Error(format!("Delete project failed: {}", e))).await.ok();
-                            }
-                        }
-                    }
-                    // CRUD operations for Users
-                    ApiCommand::CreateUser(dto) => {
-                        match client.create_user(&dto).await {
-                            Ok(id) => {
-                                tx.send(ApiMessage::Created(EntityType::User, id)).await.ok();
-                            }
-                            Err(e) => {
-                                tx.send(ApiMessage::Error(format!("Create user failed: {}", e))).await.ok();
+                    // Reads are given up when the app quits
+                    cmd => {
+                        prefetch.cancel_covered_by(&cmd);
+                        tokio::select! {
+                            _ = handle_api_command(&source, &tx, cmd, list_page_size) => {}
+                            _ = cancel_reads.notified() => {
+                                drain_writes(&source, &tx, rx, list_page_size).await;
+                                break;
                             }
                         }
                     }
-                    ApiCommand::UpdateUser(id, dto) => {
-                        match client.update_user(id, &dto).await {
-                            Ok(_) => {
-                                tx.send(ApiMessage::Updated(EntityType::User)).await.ok();
-                            }
-                            Err(e) => {
-                                tx.send(ApiMessage::Error(format!("Update user failed: {}", e))).await.ok();
-                            }
-                        }
+                }
+            }
+            // Watch mode: reload only when the server reports new data
+            _ = next_poll(&mut poll) => {
+                let result = source.fetch_changes().await;
+                match changes.observe(result.as_ref().map(|c| c.version)) {
+                    PollOutcome::Unchanged => {}
+                    PollOutcome::Changed => {
+                        tx.send(ApiMessage::RemoteChanged).await.ok();
+                        handle_api_command(&source, &tx, ApiCommand::RefreshAll, list_page_size).await;
                     }
-                    ApiCommand::DeleteUser(id) => {
-                        match client.delete_user(id).await {
-                            Ok(deleted_id) => {
-                                tx.send(ApiMessage::Deleted(EntityType::User, deleted_id)).await.ok();
-                            }
-                            Err(e) => {
-                                tx.send(ApiMessage::Error(format!("Delete user failed: {}", e))).await.ok();
-                            }
-                        }
+                    PollOutcome::Unsupported => {
+                        tracing::warn!("Server has no /changes endpoint; watch mode disabled");
+                        poll = None;
+                        tx.send(ApiMessage::WatchUnsupported).await.ok();
                     }
                 }
             }
+            // Prefetch once no command has arrived for a moment
+            _ = tokio::time::sleep(PREFETCH_IDLE), if !prefetch.is_empty() => {
+                if let Some(job) = prefetch.pop() {
+                    run_prefetch(source.as_ref(), &tx, job, list_page_size).await;
+                }
+            }
+        }
+        // Keep polling within the rate-limit budget left by interactive requests
+        if let (Some(base), Some(timer), Some(current)) = (watch, poll.as_mut(), pace) {
+            let rate_limit = source.rate_limit();
+            let interval = poll_interval(base, rate_limit);
+            if interval != current {
+                tracing::info!(?interval, ?rate_limit, "Watch interval adjusted");
+                *timer = poll_timer(tokio::time::Instant::now() + interval, interval);
+                pace = Some(interval);
+                tx.send(ApiMessage::WatchPaced { interval, base, rate_limit }).await.ok();
+            }
+        }
+    }
+}
+
+/// Send the writes still queued behind `Shutdown`, reporting progress; the
+/// queued reads are dropped
+async fn drain_writes(
+    source: &Arc<dyn DataSource>,
+    tx: &mpsc::Sender<ApiMessage>,
+    rx: &mut mpsc::Receiver<ApiCommand>,
+    list_page_size: Option<i32>,
+) {
+    let writes: Vec<ApiCommand> = std::iter::from_fn(|| rx.try_recv().ok()).filter(ApiCommand::is_mutation).collect();
+    let total = writes.len();
+    if total == 0 {
+        return;
+    }
+    tracing::info!(count = total, "Sending queued writes before quitting");
+    for (done, cmd) in writes.into_iter().enumerate() {
+        tx.send(ApiMessage::Draining { done, total }).await.ok();
+        handle_api_command(source, tx, cmd, list_page_size).await;
+    }
+    tx.send(ApiMessage::Draining { done: total, total }).await.ok();
+}
+
+/// Watch-mode timer first firing at `start`
+fn poll_timer(start: tokio::time::Instant, period: Duration) -> tokio::time::Interval {
+    let mut interval = tokio::time::interval_at(start, period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    interval
+}
+
+/// Wait for the next watch-mode poll; never completes when watching is off
+async fn next_poll(poll: &mut Option<tokio::time::Interval>) {
+    match poll {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Fetch data ahead of time; failures are only logged
+async fn run_prefetch(source: &dyn DataSource, tx: &mpsc::Sender<ApiMessage>, job: PrefetchJob, list_page_size: Option<i32>) {
+    tracing::debug!(?job, "Prefetch");
+    let result = match job {
+        PrefetchJob::Documents(project_id) => source
+            .fetch_project_documents(project_id)
+            .await
+            .map(|documents| Prefetched::Documents { project_id, documents }),
+        PrefetchJob::Page { entity, page } => {
+            let Some(page_size) = list_page_size else { return };
+            match entity {
+                EntityType::Client => source.fetch_clients_page(page, page_size).await.map(Prefetched::ClientsPage),
+                EntityType::User => source.fetch_users_page(page, page_size).await.map(Prefetched::UsersPage),
+                EntityType::Project => return,
+            }
+        }
+        PrefetchJob::Audit { page, search } => source
+            .fetch_audit(page, audit::PAGE_SIZE, Some(&search))
+            .await
+            .map(|result| Prefetched::Audit { page, search, result }),
+    };
+    match result {
+        Ok(entity) => {
+            tx.send(ApiMessage::Prefetched(entity)).await.ok();
+        }
+        Err(e) => tracing::debug!("Prefetch failed: {}", e),
+    }
+}
+
+/// Load one page of the Clients or Users list (`[api] list_page_size`)
+async fn send_page(source: &dyn DataSource, tx: &mpsc::Sender<ApiMessage>, entity: EntityType, page: i32, page_size: i32) {
+    let message = match entity {
+        EntityType::Client => source
+            .fetch_clients_page(page, page_size)
+            .await
+            .map(ApiMessage::ClientsPage)
+            .unwrap_or_else(|e| ApiMessage::Error(AppError::api("Load clients", e))),
+        EntityType::User => source
+            .fetch_users_page(page, page_size)
+            .await
+            .map(ApiMessage::UsersPage)
+            .unwrap_or_else(|e| ApiMessage::Error(AppError::api("Load users", e))),
+        // Projects are always loaded whole
+        EntityType::Project => return,
+    };
+    tx.send(message).await.ok();
+}
+
+/// Tell the UI which backends of an aggregated source answered
+async fn report_backends(source: &dyn DataSource, tx: &mpsc::Sender<ApiMessage>) {
+    let backends = source.backends();
+    if !backends.is_empty() {
+        tx.send(ApiMessage::Backends(backends)).await.ok();
+    }
+}
+
+/// Execute one command from the UI and report the result; with `list_page_size`,
+/// refreshes also send the first page of the Clients and Users lists
+async fn handle_api_command(
+    source: &Arc<dyn DataSource>,
+    tx: &mpsc::Sender<ApiMessage>,
+    cmd: ApiCommand,
+    list_page_size: Option<i32>,
+) {
+    match cmd {
+        ApiCommand::RefreshAll => {
+            // Check connection
+            let connected = source.health_check().await.unwrap_or(false);
+            tx.send(ApiMessage::ConnectionStatus(connected)).await.ok();
+
+            if connected {
+                // The lists show their first page while the whole lists load for the lookups
+                if let Some(page_size) = list_page_size {
+                    send_page(source.as_ref(), tx, EntityType::User, 1, page_size).await;
+                    send_page(source.as_ref(), tx, EntityType::Client, 1, page_size).await;
+                }
+                let Refresh { projects, clients, users } = source.refresh().await;
+
+                // Send results (users first: project handling looks up the current user)
+                match users {
+                    Ok(data) => { tx.send(ApiMessage::UsersLoaded(data)).await.ok(); }
+                    Err(e) => { tx.send(ApiMessage::Error(AppError::api("Load users", e))).await.ok(); }
+                }
+                match clients {
+                    Ok(data) => { tx.send(ApiMessage::ClientsLoaded(data)).await.ok(); }
+                    Err(e) => { tx.send(ApiMessage::Error(AppError::api("Load clients", e))).await.ok(); }
+                }
+                match projects {
+                    Ok(data) => { tx.send(ApiMessage::ProjectsLoaded(data)).await.ok(); }
+                    Err(e) => { tx.send(ApiMessage::Error(AppError::api("Load projects", e))).await.ok(); }
+                }
+            } else {
+                tx.send(ApiMessage::Error(AppError::Offline)).await.ok();
+            }
+            report_backends(source.as_ref(), tx).await;
+        }
+        ApiCommand::RefreshProjects => {
+            match source.fetch_all_projects().await {
+                Ok(data) => { tx.send(ApiMessage::ProjectsLoaded(data)).await.ok(); }
+                Err(e) => { tx.send(ApiMessage::Error(AppError::api("Load projects", e))).await.ok(); }
+            }
+        }
+        ApiCommand::RefreshClients => {
+            if let Some(page_size) = list_page_size {
+                send_page(source.as_ref(), tx, EntityType::Client, 1, page_size).await;
+            }
+            match source.fetch_all_clients().await {
+                Ok(data) => { tx.send(ApiMessage::ClientsLoaded(data)).await.ok(); }
+                Err(e) => { tx.send(ApiMessage::Error(AppError::api("Load clients", e))).await.ok(); }
+            }
+        }
+        ApiCommand::RefreshUsers => {
+            if let Some(page_size) = list_page_size {
+                send_page(source.as_ref(), tx, EntityType::User, 1, page_size).await;
+            }
+            match source.fetch_all_users().await {
+                Ok(data) => { tx.send(ApiMessage::UsersLoaded(data)).await.ok(); }
+                Err(e) => { tx.send(ApiMessage::Error(AppError::api("Load users", e))).await.ok(); }
+            }
+        }
+        ApiCommand::FetchPage { entity, page } => {
+            if let Some(page_size) = list_page_size {
+                send_page(source.as_ref(), tx, entity, page, page_size).await;
+            }
+        }
+        ApiCommand::Diagnose(urls, options) => {
+            let mut reports = Vec::new();
+            for url in urls {
+                let diagnosis = probe::diagnose(&url, &options, |name| std::env::var(name).ok()).await;
+                tracing::info!(url = %url, failed = ?diagnosis.failed(), "Connection diagnosed");
+                reports.push(diagnosis);
+            }
+            tx.send(ApiMessage::Diagnosis(reports)).await.ok();
+        }
+        ApiCommand::CheckConnection => {
+            let connected = source.health_check().await.unwrap_or(false);
+            tx.send(ApiMessage::ConnectionStatus(connected)).await.ok();
+            report_backends(source.as_ref(), tx).await;
+        }
+        // Handled by the worker loop
+        ApiCommand::Shutdown
+        | ApiCommand::Prefetch(_)
+        | ApiCommand::SetWatch(_)
+        | ApiCommand::PostWebhook(_)
+        | ApiCommand::Store(_) => {}
+        // CRUD operations for Clients
+        ApiCommand::CreateClient(dto) => {
+            match source.create_client(&dto).await {
+                Ok(id) => {
+                    tx.send(ApiMessage::Created(EntityType::Client, id)).await.ok();
+                }
+                Err(e) => {
+                    tx.send(ApiMessage::Error(AppError::api("Create client", e))).await.ok();
+                }
+            }
+        }
+        ApiCommand::UpdateClient(id, dto) => {
+            match source.update_client(id, &dto).await {
+                Ok(_) => {
+                    tx.send(ApiMessage::Updated(EntityType::Client)).await.ok();
+                }
+                Err(e) => {
+                    tx.send(ApiMessage::Error(AppError::api("Update client", e))).await.ok();
+                }
+            }
+        }
+        ApiCommand::DeleteClient(id) => {
+            match source.delete_client(id).await {
+                Ok(deleted_id) => {
+                    tx.send(ApiMessage::Deleted(EntityType::Client, deleted_id)).await.ok();
+                }
+                Err(e) => {
+                    tx.send(ApiMessage::Error(AppError::api("Delete client", e))).await.ok();
+                }
+            }
+        }
+        // CRUD operations for Projects
+        ApiCommand::CreateProject(dto) => {
+            match source.create_project(&dto).await {
+                Ok(id) => {
+                    tx.send(ApiMessage::Created(EntityType::Project, id)).await.ok();
+                }
+                Err(e) => {
+                    tx.send(ApiMessage::Error(AppError::api("Create project", e))).await.ok();
+                }
+            }
+        }
+        ApiCommand::UpdateProject(id, dto) => {
+            match source.update_project(id, &dto).await {
+                Ok(_) => {
+                    tx.send(ApiMessage::Updated(EntityType::Project)).await.ok();
+                }
+                Err(e) => {
+                    tx.send(ApiMessage::Error(AppError::api("Update project", e))).await.ok();
+                }
+            }
+        }
+        ApiCommand::DeleteProject(id) => {
+            match source.delete_project(id).await {
+                Ok(deleted_id) => {
+                    tx.send(ApiMessage::Deleted(EntityType::Project, deleted_id)).await.ok();
+                }
+                Err(e) => {
+                    tx.send(ApiMessage::Error(AppError::api("Delete project", e))).await.ok();
+                }
+            }
+        }
+        ApiCommand::BatchUpdateProjects(batch) => {
+            let mut succeeded = 0;
+            let mut failed = Vec::new();
+            for (id, dto) in batch {
+                match source.update_project(id, &dto).await {
+                    Ok(_) => succeeded += 1,
+                    Err(e) => failed.push((id, e.to_string())),
+                }
+            }
+            tx.send(ApiMessage::BatchUpdated { succeeded, failed }).await.ok();
+        }
+        ApiCommand::Console(request) => {
+            let started = Instant::now();
+            let (status, body) = match source.get_raw(&request.path, &request.query).await {
+                Ok(response) => (Some(response.status), response.body),
+                Err(e) => (None, e.to_string()),
+            };
+            let response = ConsoleResponse { request, status, body, elapsed: started.elapsed() };
+            tx.send(ApiMessage::ConsoleResponse(response)).await.ok();
+        }
+        // The outcome of an undoable command is tagged with its operation
+        ApiCommand::Operation(operation, command) => {
+            let (inner_tx, mut inner_rx) = mpsc::channel(8);
+            let run = async move {
+                Box::pin(handle_api_command(source, &inner_tx, *command, list_page_size)).await;
+            };
+            let forward = async {
+                while let Some(message) = inner_rx.recv().await {
+                    tx.send(ApiMessage::Operation(operation, Box::new(message))).await.ok();
+                }
+            };
+            tokio::join!(run, forward);
+        }
+        ApiCommand::FetchDocuments(project_id) => {
+            let result = source.fetch_project_documents(project_id).await;
+            tx.send(ApiMessage::DocumentsLoaded { project_id, result }).await.ok();
+        }
+        // Downloads run on their own so a large file does not hold up other commands
+        ApiCommand::DownloadDocument { project_id, document, dir } => {
+            tokio::spawn(attachments::download(source.clone(), tx.clone(), project_id, document, dir));
+        }
+        ApiCommand::FetchAudit { page, search } => {
+            let result = source.fetch_audit(page, audit::PAGE_SIZE, Some(&search)).await;
+            tx.send(ApiMessage::AuditLoaded { page, search, result }).await.ok();
+        }
+        // CRUD operations for Users
+        ApiCommand::CreateUser(dto) => {
+            match source.create_user(&dto).await {
+                Ok(id) => {
+                    tx.send(ApiMessage::Created(EntityType::User, id)).await.ok();
+                }
+                Err(e) => {
+                    tx.send(ApiMessage::Error(AppError::api("Create user", e))).await.ok();
+                }
+            }
+        }
+        ApiCommand::UpdateUser(id, dto) => {
+            match source.update_user(id, &dto).await {
+                Ok(_) => {
+                    tx.send(ApiMessage::Updated(EntityType::User)).await.ok();
+                }
+                Err(e) => {
+                    tx.send(ApiMessage::Error(AppError::api("Update user", e))).await.ok();
+                }
+            }
+        }
+        ApiCommand::DeleteUser(id) => {
+            match source.delete_user(id).await {
+                Ok(deleted_id) => {
+                    tx.send(ApiMessage::Deleted(EntityType::User, deleted_id)).await.ok();
+                }
+                Err(e) => {
+                    tx.send(ApiMessage::Error(AppError::api("Delete user", e))).await.ok();
+                }
+            }
         }
     }
 }
 
 /// Run the main event loop
+///
+/// Input is read on its own thread, so a slow draw never delays key
+/// handling: queued events are all applied before the next draw. Animation
+/// advances on the frame pacer's cadence, and the screen is only drawn when
+/// something marked the app dirty. On terminals with an image protocol the
+/// chart images are brought up to date after each draw.
 async fn run_event_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
+    input_rx: &mut mpsc::Receiver<Msg>,
     api_rx: &mut mpsc::Receiver<ApiMessage>,
+    signal_rx: &mut mpsc::Receiver<&'static str>,
     cmd_tx: &mpsc::Sender<ApiCommand>,
+    recorder: &mut Option<session::Recorder>,
 ) -> Result<()> {
+    let mut pacer = FramePacer::new(app.config.render.fps, Instant::now());
+    let mut images = graphics::detect(app.config.ui.graphics, |key| std::env::var(key).ok()).map(graphics::ImageLayer::new);
+    app.dirty.mark(Dirty::LAYOUT);
+
     loop {
-        // Get terminal size for particle updates
-        let size = terminal.size()?;
+        // Animation tick
+        let now = Instant::now();
+        if pacer.is_due(now) {
+            // Get terminal size for particle updates
+            let size = terminal.size()?;
 
-        // Update animations
-        app.tick(size.width, size.height);
+            // Update animations unless the previous frames ran late or nobody is looking
+            let animate = pacer.begin_frame(now) && app.focused;
+            update(app, Msg::Tick { width: size.width, height: size.height, animate });
+        }
 
-        // Render the UI
-        terminal.draw(|frame| ui::render(frame, app))?;
+        // Render pass
+        let dirty = app.dirty.take();
+        if dirty.contains(Dirty::INPUT) || dirty.contains(Dirty::DATA) {
+            diagnostics::record_state(app.diagnostics_summary());
+        }
+        if !dirty.is_clean() {
+            let started = Instant::now();
+            let screen = terminal.draw(|frame| ui::render(frame, app))?.area;
+            if let Some(layer) = images.as_mut() {
+                layer.sync(ui::chart_images(app, screen), screen, terminal.backend_mut())?;
+            }
+            pacer.record_draw(started, Instant::now());
+            app.frame_stats = pacer.stats();
+        }
 
-        // Check for API messages (non-blocking)
-        while let Ok(msg) = api_rx.try_recv() {
-            // After CRUD operations, refresh the relevant data
-            let should_refresh = match &msg {
-                ApiMessage::Created(entity_type, _) | ApiMessage::Deleted(entity_type, _) => {
-                    Some(*entity_type)
-                }
-                ApiMessage::Updated(entity_type) => Some(*entity_type),
-                _ => None,
-            };
+        if app.should_quit {
+            break;
+        }
 
-            app.handle_api_message(msg);
-
-            // Trigger data refresh after mutations
-            if let Some(entity_type) = should_refresh {
-                let refresh_cmd = match entity_type {
-                    EntityType::Client => ApiCommand::RefreshClients,
-                    EntityType::Project => ApiCommand::RefreshProjects,
-                    EntityType::User => ApiCommand::RefreshUsers,
-                };
-                cmd_tx.send(refresh_cmd).await.ok();
-                // Also refresh related entities for project dropdown updates
-                if entity_type == EntityType::Client || entity_type == EntityType::User {
-                    cmd_tx.send(ApiCommand::RefreshProjects).await.ok();
-                }
+        // Wait for input, API results or a signal, at most until the next frame is due
+        let msg = tokio::select! {
+            Some(msg) = input_rx.recv() => Some(msg),
+            Some(message) = api_rx.recv() => Some(Msg::Api(message)),
+            Some(signal) = signal_rx.recv() => Some(Msg::Terminate(signal)),
+            _ = tokio::time::sleep(pacer.time_until_next(Instant::now())) => None,
+        };
+        let mut pending: Vec<Msg> = msg.into_iter().collect();
+
+        // Apply everything else that is already queued before drawing again
+        while let Ok(msg) = input_rx.try_recv() {
+            pending.push(msg);
+        }
+        while let Ok(message) = api_rx.try_recv() {
+            pending.push(Msg::Api(message));
+        }
+        for msg in pending {
+            if let Some(Err(e)) = recorder.as_mut().map(|r| r.record(&msg)) {
+                app.log(app::LogEntry::warning(tr!("log-recording-failed", error = format!("{:#}", e))));
+                *recorder = None;
             }
+            let effects = update(app, msg);
+            apply_effects(effects, cmd_tx, &mut pacer).await;
         }
+    }
 
-        // Handle input events with timeout for animation
-        if event::poll(FRAME_DURATION)? {
-            if let Event::Key(key) = event::read()? {
-                // Only handle key press events (not release)
-                if key.kind == KeyEventKind::Press {
-                    if let Some(cmd) = app.handle_key(key) {
-                        cmd_tx.send(cmd).await.ok();
-                    }
-                }
+    Ok(())
+}
+
+/// Read terminal events on a dedicated thread
+fn spawn_input_reader(tx: mpsc::Sender<Msg>) {
+    std::thread::spawn(move || {
+        while let Ok(event) = event::read() {
+            let Some(msg) = Msg::from_event(event) else { continue };
+            if tx.blocking_send(msg).is_err() {
+                break;
             }
         }
+    });
+}
 
-        // Check if we should quit
-        if app.should_quit {
-            break;
+/// Perform side effects from `update`
+async fn apply_effects(effects: Vec<Effect>, cmd_tx: &mpsc::Sender<ApiCommand>, pacer: &mut FramePacer) {
+    for effect in effects {
+        match effect {
+            Effect::Send(cmd) => {
+                cmd_tx.send(cmd).await.ok();
+            }
+            Effect::SetFps(fps) => pacer.set_fps(fps, Instant::now()),
+            Effect::Notify(alert) => notify::show(alert),
+            Effect::Copy(text) => {
+                if let Err(e) = clipboard::copy(&text) {
+                    tracing::warn!("Failed to copy to the clipboard: {}", e);
+                }
+            }
         }
     }
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_quitting_drains_writes_and_drops_reads() {
+        let source: Arc<dyn DataSource> = Arc::new(demo::DemoSource::new(1, chrono::NaiveDate::from_ymd_opt(2025, 3, 14).unwrap()));
+        let project = source.fetch_all_projects().await.unwrap()[0].id;
+        let (cmd_tx, mut cmd_rx) = mpsc::channel(8);
+        let (tx, mut rx) = mpsc::channel(64);
+        for cmd in [ApiCommand::Shutdown, ApiCommand::RefreshProjects, ApiCommand::DeleteProject(project), ApiCommand::RefreshAll] {
+            cmd_tx.send(cmd).await.unwrap();
+        }
+        run_api_worker(source.clone(), tx, &mut cmd_rx, &Notify::new(), None, None, None).await;
+
+        let messages: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).map(|m| format!("{:?}", m)).collect();
+        assert_eq!(
+            messages,
+            [
+                "Draining { done: 0, total: 1 }".to_string(),
+                format!("Deleted(Project, {})", project),
+                "Draining { done: 1, total: 1 }".to_string(),
+            ]
+        );
+        assert!(source.fetch_project(project).await.is_err());
+    }
 }
@@ -0,0 +1,40 @@
+//! GitHub-flavored Markdown tables.
+//!
+//! `E` writes the rows of the current view (after filters and sorting, with
+//! the columns the view shows) as a Markdown table into the working
+//! directory, `Ctrl+y` copies it to the clipboard for pasting into status
+//! updates.
+
+/// Escape a cell: pipes would end the cell and newlines the row
+pub fn escape(cell: &str) -> String {
+    cell.replace('\\', "\\\\").replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Render a table with a header row; short rows are padded with empty cells
+pub fn table(headers: &[String], rows: &[Vec<String>]) -> String {
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let mut out = line(headers.iter().map(|h| escape(h)).collect());
+    out.push_str(&line(headers.iter().map(|_| "---".to_string()).collect()));
+    for row in rows {
+        out.push_str(&line((0..headers.len()).map(|i| row.get(i).map_or(String::new(), |c| escape(c))).collect()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let headers = vec!["Name".to_string(), "Address".to_string()];
+        let rows = vec![
+            vec!["Acme | Co".to_string(), "1 Main St\nSpringfield".to_string()],
+            vec!["Globex".to_string()],
+        ];
+        assert_eq!(
+            table(&headers, &rows),
+            "| Name | Address |\n| --- | --- |\n| Acme \\| Co | 1 Main St Springfield |\n| Globex |  |\n"
+        );
+    }
+}
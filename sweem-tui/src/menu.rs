@@ -0,0 +1,125 @@
+//! Context menu of the selected row (`.` or right-click).
+//!
+//! Lists what can be done with the selected project, client or user, so the
+//! actions can be found without knowing their keys. Entries show the key that
+//! does the same from the list, which is how the keys get learned.
+
+use uuid::Uuid;
+
+use crate::api::EntityType;
+use crate::tr;
+
+/// An entry of the context menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    /// Raw JSON inspector
+    Details,
+    CopyId,
+    Edit,
+    /// Create form pre-filled from the project
+    Clone,
+    /// Set the project's actual end to today
+    Complete,
+    /// Show only the projects of the client (of the selected project)
+    FilterByClient,
+    /// Show only the projects the selected user manages
+    FilterByManager,
+    Documents,
+    Burndown,
+    Favorite,
+    Watch,
+    Delete,
+}
+
+impl MenuAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            MenuAction::Details => tr!("menu-details"),
+            MenuAction::CopyId => tr!("menu-copy-id"),
+            MenuAction::Edit => tr!("menu-edit"),
+            MenuAction::Clone => tr!("menu-clone"),
+            MenuAction::Complete => tr!("menu-complete"),
+            MenuAction::FilterByClient => tr!("menu-filter-client"),
+            MenuAction::FilterByManager => tr!("menu-filter-manager"),
+            MenuAction::Documents => tr!("menu-documents"),
+            MenuAction::Burndown => tr!("menu-burndown"),
+            MenuAction::Favorite => tr!("menu-favorite"),
+            MenuAction::Watch => tr!("menu-watch"),
+            MenuAction::Delete => tr!("menu-delete"),
+        }
+    }
+
+    /// Key doing the same outside the menu, if any
+    pub fn key(self) -> &'static str {
+        match self {
+            MenuAction::Details => "J",
+            MenuAction::CopyId => "y",
+            MenuAction::Edit => "e",
+            MenuAction::Clone => "D",
+            MenuAction::Documents => "A",
+            MenuAction::Burndown => "b",
+            MenuAction::Favorite => "*",
+            MenuAction::Watch => "W",
+            MenuAction::Delete => "d",
+            MenuAction::Complete | MenuAction::FilterByClient | MenuAction::FilterByManager => "",
+        }
+    }
+}
+
+/// Open context menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextMenu {
+    pub entity_type: EntityType,
+    pub id: Uuid,
+    /// Name of the entity, for the title
+    pub title: String,
+    pub actions: Vec<MenuAction>,
+    pub selected: usize,
+}
+
+impl ContextMenu {
+    /// Menu for an entity; `completed` drops "mark complete" from a finished project
+    pub fn new(entity_type: EntityType, id: Uuid, title: String, completed: bool) -> Self {
+        use MenuAction::*;
+        let actions = match entity_type {
+            EntityType::Project => {
+                let mut actions = vec![Details, CopyId, Edit, Clone, Complete, FilterByClient, Documents, Favorite, Watch, Delete];
+                actions.retain(|a| *a != Complete || !completed);
+                actions
+            }
+            EntityType::Client => vec![Details, CopyId, Edit, FilterByClient, Burndown, Favorite, Watch, Delete],
+            EntityType::User => vec![Details, CopyId, Edit, FilterByManager, Burndown, Delete],
+        };
+        Self { entity_type, id, title, actions, selected: 0 }
+    }
+
+    /// Move the highlight, wrapping around
+    pub fn select(&mut self, forward: bool) {
+        let count = self.actions.len();
+        self.selected = if forward { (self.selected + 1) % count } else { (self.selected + count - 1) % count };
+    }
+
+    pub fn action(&self) -> MenuAction {
+        self.actions[self.selected]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_actions_fit_the_entity() {
+        let open = ContextMenu::new(EntityType::Project, Uuid::nil(), "Website".into(), false);
+        assert!(open.actions.contains(&MenuAction::Complete));
+        let done = ContextMenu::new(EntityType::Project, Uuid::nil(), "Website".into(), true);
+        assert!(!done.actions.contains(&MenuAction::Complete));
+
+        let mut user = ContextMenu::new(EntityType::User, Uuid::nil(), "Alice".into(), false);
+        assert!(!user.actions.contains(&MenuAction::Favorite), "users cannot be starred");
+        user.select(false);
+        assert_eq!(user.action(), MenuAction::Delete, "wraps to the last entry");
+        user.select(true);
+        assert_eq!(user.action(), MenuAction::Details);
+    }
+}
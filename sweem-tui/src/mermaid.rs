@@ -0,0 +1,109 @@
+//! Gantt charts in Mermaid syntax.
+//!
+//! Produces a fenced ` ```mermaid ` `gantt` block that renders as-is in
+//! GitLab and GitHub markdown: one section per client, one task per project.
+//! Completed projects are `done`, running ones `active`, overdue ones `crit`.
+
+use std::fmt::Write as _;
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::models::{ClientDto, ProjectDto};
+
+/// Render projects as a Mermaid gantt block, grouped by client
+pub fn gantt(title: &str, projects: &[ProjectDto], clients: &[ClientDto], today: NaiveDate) -> String {
+    let client_name = |id: Uuid| {
+        clients
+            .iter()
+            .find(|c| c.id == id)
+            .map(|c| c.display_name().to_string())
+            .unwrap_or_else(|| format!("Client {}", &id.to_string()[..8]))
+    };
+    let mut sections: Vec<(String, Vec<(usize, &ProjectDto)>)> = Vec::new();
+    for (index, project) in projects.iter().enumerate() {
+        let section = client_name(project.client_id);
+        match sections.iter_mut().find(|(name, _)| *name == section) {
+            Some((_, tasks)) => tasks.push((index, project)),
+            None => sections.push((section, vec![(index, project)])),
+        }
+    }
+    sections.sort_by_key(|(name, _)| name.to_lowercase());
+
+    let mut out = String::from("```mermaid\ngantt\n");
+    let _ = writeln!(out, "    title {}", escape(title));
+    out.push_str("    dateFormat YYYY-MM-DD\n");
+    for (section, tasks) in sections {
+        let _ = writeln!(out, "    section {}", escape(&section));
+        for (index, project) in tasks {
+            // Mermaid end dates are exclusive
+            let end = project.actual_end_date.unwrap_or(project.planned_end_date) + chrono::Duration::days(1);
+            let tags = match status_tag(project, today) {
+                Some(tag) => format!("{}, ", tag),
+                None => String::new(),
+            };
+            let _ = writeln!(
+                out,
+                "    {} :{}p{}, {}, {}",
+                escape(project.display_name()),
+                tags,
+                index + 1,
+                project.start_date.format("%Y-%m-%d"),
+                end.format("%Y-%m-%d"),
+            );
+        }
+    }
+    out.push_str("```\n");
+    out
+}
+
+/// Mermaid task flag for the project's status on `today`
+fn status_tag(project: &ProjectDto, today: NaiveDate) -> Option<&'static str> {
+    if project.is_completed_as_of(today) {
+        Some("done")
+    } else if project.is_overdue_as_of(today) {
+        Some("crit")
+    } else if project.start_date <= today {
+        Some("active")
+    } else {
+        None
+    }
+}
+
+/// Task and section names end at `:` and comments start at `#` or `%%`
+fn escape(text: &str) -> String {
+    text.replace([':', ';', '#', '%'], " ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use sweem_core::fixtures;
+
+    use super::*;
+
+    #[test]
+    fn test_gantt_sections_and_flags() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let client = |n: u128, name: &str| fixtures::client(Uuid::from_u128(n), name);
+        let project = |client: u128, name: &str, start, end, actual: Option<u32>| ProjectDto {
+            client_id: Uuid::from_u128(client),
+            name: Some(name.to_string()),
+            actual_end_date: actual.map(date),
+            ..fixtures::project(date(start), date(end))
+        };
+        let projects = [
+            project(2, "Website: v2", 1, 5, None),
+            project(1, "Audit", 1, 4, Some(3)),
+            project(2, "Launch", 20, 25, None),
+        ];
+        let clients = [client(1, "Acme"), client(2, "Globex")];
+
+        assert_eq!(
+            gantt("Projects", &projects, &clients, date(10)),
+            "```mermaid\ngantt\n    title Projects\n    dateFormat YYYY-MM-DD\n\
+             \x20   section Acme\n    Audit :done, p2, 2025-03-01, 2025-03-04\n\
+             \x20   section Globex\n    Website v2 :crit, p1, 2025-03-01, 2025-03-06\n\
+             \x20   Launch :p3, 2025-03-20, 2025-03-26\n```\n"
+        );
+    }
+}
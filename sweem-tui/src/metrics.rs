@@ -0,0 +1,287 @@
+//! Prometheus metrics (`sweem-tui metrics`).
+//!
+//! A headless mode for monitoring: it loads all entities every `--interval`
+//! and publishes the project, client and user counts, how long the load
+//! took and how old the last successful one is, in the Prometheus text
+//! format. `--listen` serves them on `GET /metrics`; `--textfile` writes them
+//! for node_exporter's textfile collector (replaced atomically, so the
+//! collector never reads half a file). Alert on `sweem_up == 0` or a growing
+//! `sweem_refresh_age_seconds` to notice when the API stops answering.
+
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use sweem_core::source::DataSource;
+use sweem_core::stats;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+use crate::models::{ClientDto, ProjectDto, UserDto};
+
+/// Content type of the text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// A client has this long to send its request before the connection is closed
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest request or header line read; a client sending a longer one is dropped
+const MAX_LINE: usize = 8 * 1024;
+
+/// Pause after a failed `accept` (out of file descriptors, say), doubled
+/// while it keeps failing
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(50);
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Counts from one successful load
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Counts {
+    pub projects: usize,
+    pub open: usize,
+    pub overdue: usize,
+    pub completed: usize,
+    pub clients: usize,
+    pub users: usize,
+}
+
+impl Counts {
+    pub fn new(projects: &[ProjectDto], clients: &[ClientDto], users: &[UserDto], today: NaiveDate) -> Self {
+        let completed = projects.iter().filter(|p| p.is_completed_as_of(today)).count();
+        Self {
+            projects: projects.len(),
+            open: projects.len() - completed,
+            overdue: stats::overdue_count(projects, today),
+            completed,
+            clients: clients.len(),
+            users: users.len(),
+        }
+    }
+}
+
+/// What the metrics are rendered from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsState {
+    /// Counts of the last successful load and when it finished
+    pub last: Option<(Counts, Instant)>,
+    /// Duration of the last load, successful or not
+    pub duration: Option<Duration>,
+    /// Whether the last load succeeded
+    pub up: bool,
+    pub failures: u64,
+}
+
+impl MetricsState {
+    /// Record a load that took `duration`
+    pub fn record(&mut self, result: Result<Counts>, duration: Duration, now: Instant) {
+        self.duration = Some(duration);
+        self.up = result.is_ok();
+        match result {
+            Ok(counts) => self.last = Some((counts, now)),
+            Err(_) => self.failures += 1,
+        }
+    }
+
+    /// The metrics in the Prometheus text format
+    pub fn render(&self, now: Instant) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, f64)]| {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} {}", name, help, name, kind);
+            for (labels, value) in samples {
+                let _ = writeln!(out, "{}{} {}", name, labels, value);
+            }
+        };
+        metric("sweem_up", "gauge", "Whether the last refresh succeeded", &[("", f64::from(u8::from(self.up)))]);
+        metric("sweem_refresh_failures_total", "counter", "Refreshes that failed", &[("", self.failures as f64)]);
+        if let Some(duration) = self.duration {
+            metric(
+                "sweem_api_refresh_duration_seconds",
+                "gauge",
+                "Time the last refresh took to load all entities",
+                &[("", duration.as_secs_f64())],
+            );
+        }
+        let Some((counts, at)) = self.last else {
+            return out;
+        };
+        metric(
+            "sweem_refresh_age_seconds",
+            "gauge",
+            "Seconds since the last successful refresh",
+            &[("", now.saturating_duration_since(at).as_secs_f64().round())],
+        );
+        metric(
+            "sweem_projects",
+            "gauge",
+            "Projects by status (overdue projects are also open)",
+            &[
+                ("{status=\"all\"}", counts.projects as f64),
+                ("{status=\"open\"}", counts.open as f64),
+                ("{status=\"overdue\"}", counts.overdue as f64),
+                ("{status=\"completed\"}", counts.completed as f64),
+            ],
+        );
+        metric("sweem_clients", "gauge", "Clients", &[("", counts.clients as f64)]);
+        metric("sweem_users", "gauge", "Users", &[("", counts.users as f64)]);
+        out
+    }
+}
+
+/// Load everything once and count it
+async fn load(source: &dyn DataSource) -> Result<Counts> {
+    let (projects, clients, users) =
+        tokio::try_join!(source.fetch_all_projects(), source.fetch_all_clients(), source.fetch_all_users())?;
+    Ok(Counts::new(&projects, &clients, &users, crate::clock::today()))
+}
+
+/// Replace `path` with `text` through a temporary file next to it
+fn write_textfile(path: &Path, text: &str) -> Result<()> {
+    let temp = path.with_extension("prom.tmp");
+    std::fs::write(&temp, text).with_context(|| format!("Failed to write {}", temp.display()))?;
+    std::fs::rename(&temp, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// Run the `metrics` command until interrupted
+pub async fn run(source: &dyn DataSource, listen: Option<SocketAddr>, textfile: Option<PathBuf>, interval: Duration) -> Result<()> {
+    let state = Arc::new(Mutex::new(MetricsState::default()));
+    if let Some(address) = listen {
+        let listener = TcpListener::bind(address).await.with_context(|| format!("Failed to listen on {}", address))?;
+        eprintln!("Serving metrics on http://{}/metrics", listener.local_addr()?);
+        tokio::spawn(serve(listener, state.clone()));
+    }
+    let mut timer = tokio::time::interval(interval);
+    timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        timer.tick().await;
+        let started = Instant::now();
+        let result = load(source).await;
+        if let Err(e) = &result {
+            eprintln!("Refresh failed: {:#}", e);
+        }
+        let text = {
+            let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+            state.record(result, started.elapsed(), Instant::now());
+            state.render(Instant::now())
+        };
+        if let Some(path) = &textfile {
+            if let Err(e) = write_textfile(path, &text) {
+                eprintln!("{:#}", e);
+            }
+        }
+    }
+}
+
+/// Answer scrapes; a failing `accept` is retried after a pause, so the
+/// endpoint survives running out of file descriptors for a while
+async fn serve(listener: TcpListener, state: Arc<Mutex<MetricsState>>) {
+    let mut backoff = ACCEPT_BACKOFF;
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                backoff = ACCEPT_BACKOFF;
+                tokio::spawn(handle_connection(stream, state.clone(), REQUEST_TIMEOUT));
+            }
+            Err(e) => {
+                eprintln!("Metrics listener failed: {} (retrying in {:?})", e, backoff);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_ACCEPT_BACKOFF);
+            }
+        }
+    }
+}
+
+/// One line without its ending; `None` at the end of the stream or for a
+/// line longer than `MAX_LINE`
+async fn read_line(reader: &mut (impl AsyncBufRead + Unpin)) -> Option<String> {
+    let mut line = Vec::new();
+    reader.take(MAX_LINE as u64 + 1).read_until(b'\n', &mut line).await.ok()?;
+    if line.pop() != Some(b'\n') {
+        return None;
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    String::from_utf8(line).ok()
+}
+
+/// One HTTP/1.x request: `GET /metrics` gets the metrics, anything else a 404;
+/// a client that does not send its request within `timeout` is dropped
+async fn handle_connection(stream: impl AsyncRead + AsyncWrite, state: Arc<Mutex<MetricsState>>, timeout: Duration) {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = BufReader::new(reader);
+    let read_request = async {
+        let request = read_line(&mut reader).await?;
+        // Skip the headers; there is no body to a GET
+        while !read_line(&mut reader).await?.is_empty() {}
+        Some(request)
+    };
+    let Ok(Some(request)) = tokio::time::timeout(timeout, read_request).await else {
+        return;
+    };
+    let mut words = request.split_whitespace();
+    let response = match (words.next(), words.next().map(|path| path.split('?').next().unwrap_or(path))) {
+        (Some("GET"), Some("/metrics")) => {
+            let body = state.lock().unwrap_or_else(|e| e.into_inner()).render(Instant::now());
+            format!("HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", CONTENT_TYPE, body.len(), body)
+        }
+        _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+    let _ = writer.write_all(response.as_bytes()).await;
+    let _ = writer.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metrics_are_served() {
+        let start = Instant::now();
+        let mut state = MetricsState::default();
+        assert!(state.render(start).starts_with("# HELP sweem_up Whether the last refresh succeeded\n# TYPE sweem_up gauge\nsweem_up 0\n"));
+
+        let counts = Counts { projects: 5, open: 3, overdue: 1, completed: 2, clients: 2, users: 4 };
+        state.record(Ok(counts), Duration::from_millis(250), start);
+        state.record(Err(anyhow::anyhow!("timed out")), Duration::from_secs(5), start + Duration::from_secs(60));
+        let text = state.render(start + Duration::from_secs(90));
+        for line in [
+            "sweem_up 0",
+            "sweem_refresh_failures_total 1",
+            "sweem_api_refresh_duration_seconds 5",
+            "sweem_refresh_age_seconds 90",
+            "sweem_projects{status=\"overdue\"} 1",
+            "sweem_users 4",
+        ] {
+            assert!(text.lines().any(|l| l == line), "{} missing from\n{}", line, text);
+        }
+
+        let state = Arc::new(Mutex::new(state));
+        let (mut client, server) = tokio::io::duplex(4096);
+        let served = tokio::spawn(handle_connection(server, state.clone(), REQUEST_TIMEOUT));
+        client.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        served.await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n") && response.contains("sweem_clients 2\n"), "{}", response);
+
+        // A client that never sends its request is dropped
+        let (mut silent, server) = tokio::io::duplex(4096);
+        handle_connection(server, state.clone(), Duration::from_millis(50)).await;
+        let mut response = String::new();
+        silent.read_to_string(&mut response).await.unwrap();
+        assert!(response.is_empty(), "{}", response);
+
+        // So is one whose request line does not end
+        let (mut endless, server) = tokio::io::duplex(4096);
+        let served = tokio::spawn(handle_connection(server, state, REQUEST_TIMEOUT));
+        let line = format!("GET /{} HTTP/1.1\r\n", "a".repeat(MAX_LINE));
+        endless.write_all(line.as_bytes()).await.ok();
+        tokio::time::timeout(Duration::from_secs(1), served).await.expect("dropped without waiting").unwrap();
+        let mut response = String::new();
+        endless.read_to_string(&mut response).await.ok();
+        assert!(response.is_empty(), "{}", response);
+    }
+}
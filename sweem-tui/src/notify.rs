@@ -0,0 +1,199 @@
+//! Deadline alerts and desktop notifications.
+//!
+//! After every project refresh, [`DeadlineWatch`] reports projects that
+//! newly became overdue or whose planned end is now less than 24 hours away.
+//! Alerts always go to the log; they are also shown as OS desktop
+//! notifications unless disabled in `[notifications]` or inside quiet hours.
+
+use std::collections::HashSet;
+
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::ProjectDto;
+use crate::tr;
+
+/// How far ahead a deadline counts as "due soon"
+const DUE_SOON: Duration = Duration::hours(24);
+
+/// `[notifications]` config section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotifyConfig {
+    /// Show desktop notifications for deadline alerts
+    pub enabled: bool,
+    /// Local time window without desktop notifications (alerts are still logged)
+    pub quiet_hours: Option<QuietHours>,
+    /// The Inbox lists open projects whose planned end is at most this many days away
+    pub inbox_due_days: i64,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            quiet_hours: None,
+            inbox_due_days: 3,
+        }
+    }
+}
+
+impl NotifyConfig {
+    /// Whether a desktop notification may be shown at the given local time
+    pub fn allows(&self, now: NaiveTime) -> bool {
+        self.enabled && !self.quiet_hours.is_some_and(|quiet| quiet.contains(now))
+    }
+}
+
+/// Daily window such as 22:00-07:00 (may wrap past midnight)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Why a project is being reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    /// Past its planned end date and not completed
+    Overdue,
+    /// Planned end is less than 24 hours away
+    DueSoon,
+    /// An `[[alerts]]` rule fired
+    Rule,
+}
+
+/// A project that crossed a deadline threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub project: String,
+    /// Rule name and what it saw (rule alerts only)
+    pub rule: Option<(String, String)>,
+}
+
+impl Alert {
+    pub fn title(&self) -> &'static str {
+        match self.kind {
+            AlertKind::Overdue => tr!("alert-overdue-title"),
+            AlertKind::DueSoon => tr!("alert-due-soon-title"),
+            AlertKind::Rule => tr!("alert-rule-title"),
+        }
+    }
+
+    /// One-line description for the log
+    pub fn message(&self) -> String {
+        match self.kind {
+            AlertKind::Overdue => tr!("alert-overdue", name = self.project),
+            AlertKind::DueSoon => tr!("alert-due-soon", name = self.project),
+            AlertKind::Rule => {
+                let (rule, detail) = self.rule.clone().unwrap_or_default();
+                tr!("inbox-rule", rule = rule, name = self.project, detail = detail)
+            }
+        }
+    }
+}
+
+/// Projects already reported, so each crossing is only alerted once
+#[derive(Debug, Default)]
+pub struct DeadlineWatch {
+    /// `None` until the first refresh, which only records the baseline
+    seen: Option<(HashSet<Uuid>, HashSet<Uuid>)>,
+}
+
+impl DeadlineWatch {
+    /// Compare freshly loaded projects with the previous refresh
+    pub fn check(&mut self, projects: &[ProjectDto], now: NaiveDateTime) -> Vec<Alert> {
+        let open = || projects.iter().filter(|p| !p.is_completed());
+        let overdue: HashSet<Uuid> = open()
+            .filter(|p| p.is_overdue_as_of(now.date()))
+            .map(|p| p.id)
+            .collect();
+        let due_soon: HashSet<Uuid> = open()
+            .filter(|p| {
+                // Overdue starts the day after the planned end date
+                let deadline = (p.planned_end_date + Duration::days(1)).and_time(NaiveTime::MIN);
+                now < deadline && deadline - now <= DUE_SOON
+            })
+            .map(|p| p.id)
+            .collect();
+
+        let alerts = match &self.seen {
+            None => Vec::new(),
+            Some((seen_overdue, seen_due_soon)) => open()
+                .filter_map(|p| {
+                    let kind = if overdue.contains(&p.id) && !seen_overdue.contains(&p.id) {
+                        AlertKind::Overdue
+                    } else if due_soon.contains(&p.id) && !seen_due_soon.contains(&p.id) {
+                        AlertKind::DueSoon
+                    } else {
+                        return None;
+                    };
+                    Some(Alert { kind, project: p.display_name().to_string(), rule: None })
+                })
+                .collect(),
+        };
+        self.seen = Some((overdue, due_soon));
+        alerts
+    }
+}
+
+/// Show a desktop notification without blocking the caller
+pub fn show(alert: Alert) {
+    std::thread::spawn(move || {
+        let result = notify_rust::Notification::new()
+            .appname("sweem-tui")
+            .summary(alert.title())
+            .body(&alert.message())
+            .show();
+        if let Err(e) = result {
+            tracing::warn!("Desktop notification failed: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use sweem_core::fixtures;
+
+    fn project(n: u128, end: NaiveDate) -> ProjectDto {
+        ProjectDto { id: Uuid::from_u128(n), name: Some(format!("P{}", n)), ..fixtures::project(end - Duration::days(30), end) }
+    }
+
+    #[test]
+    fn test_alerts_on_crossing_only() {
+        let day = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let at = |date: NaiveDate, hour| date.and_hms_opt(hour, 0, 0).unwrap();
+        let projects = [project(1, day), project(2, day + Duration::days(5))];
+        let mut watch = DeadlineWatch::default();
+
+        // The first refresh is only a baseline
+        assert!(watch.check(&projects, at(day - Duration::days(2), 12)).is_empty());
+        // Project 1 ends today: due within 24h
+        let alerts = watch.check(&projects, at(day, 9));
+        assert_eq!(alerts.iter().map(|a| (a.kind, a.project.as_str())).collect::<Vec<_>>(), [(AlertKind::DueSoon, "P1")]);
+        assert!(watch.check(&projects, at(day, 10)).is_empty());
+        // ... and overdue the next day
+        let alerts = watch.check(&projects, at(day + Duration::days(1), 8));
+        assert_eq!(alerts[0].kind, AlertKind::Overdue);
+
+        let quiet = QuietHours { start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(), end: NaiveTime::from_hms_opt(7, 0, 0).unwrap() };
+        let config = NotifyConfig { quiet_hours: Some(quiet), ..NotifyConfig::default() };
+        assert!(!config.allows(NaiveTime::from_hms_opt(23, 30, 0).unwrap()));
+        assert!(!config.allows(NaiveTime::from_hms_opt(6, 59, 0).unwrap()));
+        assert!(config.allows(NaiveTime::from_hms_opt(12, 0, 0).unwrap()));
+    }
+}
@@ -0,0 +1,105 @@
+//! OpenTelemetry trace export, enabled with the `otel` feature.
+//!
+//! With an OTLP/HTTP collector configured (`[otel] endpoint` or the standard
+//! `OTEL_EXPORTER_OTLP_ENDPOINT`), the spans of the API client go to it next
+//! to the file log: one span per `ApiClient` / `GraphQlClient` method with a
+//! client span per HTTP request below it (see `sweem-core/src/trace.rs`),
+//! whose trace context is also sent to the backend as a `traceparent`
+//! header. A slow refresh can then be followed into the backend's traces.
+//! Export is off by default and without the feature.
+
+use crate::config::OtelConfig;
+
+#[cfg(feature = "otel")]
+pub use imp::{layer, OtelGuard};
+#[cfg(not(feature = "otel"))]
+pub use stub::{layer, OtelGuard};
+
+/// Whether a collector is configured, in the config or the environment
+pub fn enabled(config: &OtelConfig, env: impl Fn(&str) -> Option<String>) -> bool {
+    config.endpoint.is_some() || env("OTEL_EXPORTER_OTLP_ENDPOINT").is_some_and(|v| !v.is_empty())
+}
+
+#[cfg(feature = "otel")]
+mod imp {
+    use anyhow::{Context, Result};
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+    use opentelemetry_sdk::Resource;
+    use tracing_subscriber::filter::{EnvFilter, Filtered};
+    use tracing_subscriber::{Layer, Registry};
+
+    use crate::config::OtelConfig;
+
+    /// Spans exported: the API clients' debug spans and the app's own
+    const EXPORT_FILTER: &str = "sweem_core=debug,sweem_tui=info";
+
+    pub type OtelLayer = Filtered<tracing_opentelemetry::OpenTelemetryLayer<Registry, SdkTracer>, EnvFilter, Registry>;
+
+    /// Flushes the spans still queued when dropped
+    pub struct OtelGuard(SdkTracerProvider);
+
+    impl Drop for OtelGuard {
+        fn drop(&mut self) {
+            let _ = self.0.shutdown();
+        }
+    }
+
+    /// Layer exporting spans to the configured collector, if there is one
+    pub fn layer(config: &OtelConfig) -> Result<Option<(OtelLayer, OtelGuard)>> {
+        if !super::enabled(config, |key| std::env::var(key).ok()) {
+            return Ok(None);
+        }
+        let mut exporter = SpanExporter::builder().with_http();
+        if let Some(endpoint) = &config.endpoint {
+            exporter = exporter.with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')));
+        }
+        let exporter = exporter.build().context("Failed to create the OTLP exporter")?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(Resource::builder().with_service_name(config.service_name.clone()).build())
+            .build();
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let tracer = provider.tracer("sweem-tui");
+        let layer = tracing_opentelemetry::layer().with_tracer(tracer).with_filter(EnvFilter::new(EXPORT_FILTER));
+        Ok(Some((layer, OtelGuard(provider))))
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+mod stub {
+    use anyhow::Result;
+
+    use crate::config::OtelConfig;
+
+    pub type OtelLayer = tracing_subscriber::layer::Identity;
+
+    /// Export is compiled out; nothing to flush
+    pub struct OtelGuard;
+
+    /// Export is compiled out: a configured collector only gets a warning
+    pub fn layer(config: &OtelConfig) -> Result<Option<(OtelLayer, OtelGuard)>> {
+        if super::enabled(config, |key| std::env::var(key).ok()) {
+            tracing::warn!("An OTLP endpoint is configured, but this build has no `otel` feature; spans are not exported");
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_is_off_by_default() {
+        let config = OtelConfig::default();
+        assert!(!enabled(&config, |_| None));
+        assert!(!enabled(&config, |_| Some(String::new())));
+        assert!(enabled(&config, |key| (key == "OTEL_EXPORTER_OTLP_ENDPOINT").then(|| "http://collector:4318".to_string())));
+        let config = OtelConfig { endpoint: Some("http://localhost:4318".to_string()), ..config };
+        assert!(enabled(&config, |_| None));
+    }
+}
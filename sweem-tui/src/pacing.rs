@@ -0,0 +1,182 @@
+//! Frame pacing for the render loop.
+//!
+//! The loop targets a fixed frame rate. When the terminal falls behind
+//! (slow SSH links, tmux), animation updates are dropped for that frame
+//! while input handling and drawing carry on, so the UI stays responsive.
+//! Terminal size changes are debounced the same way, so dragging a tmux
+//! pane border does not rebuild the particle field on every event.
+
+use std::time::{Duration, Instant};
+
+/// Frame-rate statistics shown in the debug overlay
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    /// Configured target frame rate
+    pub target_fps: u32,
+    /// Measured frames per second (smoothed)
+    pub fps: f32,
+    /// Animation updates skipped because a frame ran late
+    pub dropped_updates: u64,
+    /// Time the last draw took, in milliseconds
+    pub render_ms: f32,
+}
+
+/// Keeps frames on a fixed cadence
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    /// Duration of one frame
+    frame: Duration,
+    /// When the next frame is due
+    next_frame: Instant,
+    /// When the last frame was drawn
+    last_draw: Option<Instant>,
+    /// Current statistics
+    stats: FrameStats,
+}
+
+impl FramePacer {
+    /// Create a pacer for the given frame rate (clamped to 1..=120)
+    pub fn new(fps: u32, now: Instant) -> Self {
+        let fps = fps.clamp(1, 120);
+        Self {
+            frame: Duration::from_secs(1) / fps,
+            next_frame: now,
+            last_draw: None,
+            stats: FrameStats {
+                target_fps: fps,
+                ..FrameStats::default()
+            },
+        }
+    }
+
+    /// Change the target frame rate (e.g. throttle while unfocused)
+    pub fn set_fps(&mut self, fps: u32, now: Instant) {
+        let fps = fps.clamp(1, 120);
+        self.frame = Duration::from_secs(1) / fps;
+        self.next_frame = self.next_frame.min(now + self.frame);
+    }
+
+    /// Time left until the next frame is due
+    pub fn time_until_next(&self, now: Instant) -> Duration {
+        self.next_frame.saturating_duration_since(now)
+    }
+
+    /// Whether the next frame is due
+    pub fn is_due(&self, now: Instant) -> bool {
+        now >= self.next_frame
+    }
+
+    /// Start a frame; returns false when animation updates should be dropped
+    pub fn begin_frame(&mut self, now: Instant) -> bool {
+        if now > self.next_frame + self.frame {
+            // More than a whole frame behind: resync instead of catching up
+            self.next_frame = now + self.frame;
+            self.stats.dropped_updates += 1;
+            false
+        } else {
+            self.next_frame += self.frame;
+            true
+        }
+    }
+
+    /// Record that a frame started at `started` was drawn (updates the measured rate)
+    pub fn record_draw(&mut self, started: Instant, now: Instant) {
+        self.stats.render_ms = now.duration_since(started).as_secs_f32() * 1000.0;
+        if let Some(last) = self.last_draw {
+            let elapsed = now.duration_since(last).as_secs_f32();
+            if elapsed > 0.0 {
+                let instant_fps = 1.0 / elapsed;
+                self.stats.fps = if self.stats.fps == 0.0 {
+                    instant_fps
+                } else {
+                    self.stats.fps * 0.9 + instant_fps * 0.1
+                };
+            }
+        }
+        self.last_draw = Some(now);
+    }
+
+    /// Current statistics
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+}
+
+/// How long the terminal size must stay unchanged before it is applied
+pub const RESIZE_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Settles terminal size changes before expensive rebuilds
+#[derive(Debug, Clone, Default)]
+pub struct ResizeDebounce {
+    /// Size that rebuilds are based on
+    settled: Option<(u16, u16)>,
+    /// Latest observed size and when it was first seen
+    pending: Option<((u16, u16), Instant)>,
+}
+
+impl ResizeDebounce {
+    /// Report the current size; returns the size to build for
+    pub fn observe(&mut self, size: (u16, u16), now: Instant) -> (u16, u16) {
+        let Some(settled) = self.settled else {
+            // The first size is used right away
+            self.settled = Some(size);
+            return size;
+        };
+        if size == settled {
+            self.pending = None;
+            return settled;
+        }
+        match self.pending {
+            Some((pending, since)) if pending == size => {
+                if now.duration_since(since) >= RESIZE_DEBOUNCE {
+                    self.settled = Some(size);
+                    self.pending = None;
+                    return size;
+                }
+            }
+            _ => self.pending = Some((size, now)),
+        }
+        settled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pacer_drops_updates_when_behind() {
+        let start = Instant::now();
+        let mut pacer = FramePacer::new(10, start);
+        assert!(pacer.is_due(start));
+        assert!(pacer.begin_frame(start));
+        assert_eq!(pacer.time_until_next(start), Duration::from_millis(100));
+
+        // On time: next frame is scheduled one period later
+        assert!(pacer.begin_frame(start + Duration::from_millis(100)));
+
+        // Far behind: updates are dropped and the schedule resyncs
+        let late = start + Duration::from_millis(1000);
+        assert!(!pacer.begin_frame(late));
+        assert_eq!(pacer.stats().dropped_updates, 1);
+        assert_eq!(pacer.time_until_next(late), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_resize_storm_settles_once() {
+        let start = Instant::now();
+        let mut resize = ResizeDebounce::default();
+        assert_eq!(resize.observe((80, 24), start), (80, 24));
+
+        // Rapid pane drags keep the old size
+        for (i, width) in (81..90).enumerate() {
+            let now = start + Duration::from_millis(20 * i as u64);
+            assert_eq!(resize.observe((width, 24), now), (80, 24));
+        }
+
+        // Once the size stops changing it is applied
+        let last = start + Duration::from_millis(200);
+        assert_eq!(resize.observe((89, 24), last), (80, 24));
+        assert_eq!(resize.observe((89, 24), last + RESIZE_DEBOUNCE), (89, 24));
+    }
+}
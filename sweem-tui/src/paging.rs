@@ -0,0 +1,108 @@
+//! Lazy loading of the Clients and Users lists (`[api] list_page_size`).
+//!
+//! With very large datasets, filtering, sorting and drawing the whole of
+//! `/clients` and `/users` in their lists is slow. With a page size set, the
+//! Clients and Users tabs show only the pages loaded so far, starting with the
+//! first, and the next one is asked for when the selection comes within
+//! [`PREFETCH_ROWS`] of the last loaded row. The list title shows how many
+//! rows are loaded out of the envelope's total, and a "loading next page" row
+//! stays at the end while a page is on its way.
+//!
+//! The pages only feed those two lists: refreshes still load clients and
+//! users whole, since project rows, pickers, search and "My projects" look
+//! names up in them. Projects are always loaded whole.
+
+use crate::models::PaginatedResult;
+
+/// The next page is requested this many rows before the end of the loaded ones
+pub const PREFETCH_ROWS: usize = 10;
+
+/// Pages of one list loaded so far
+#[derive(Debug, Clone)]
+pub struct ListPages<T> {
+    /// Rows of the loaded pages, as received
+    pub rows: Vec<T>,
+    /// Number of the last loaded page (0 = the list is not paged)
+    pub loaded: i32,
+    /// Rows on the server, from the envelope
+    pub total_count: usize,
+    pub has_next: bool,
+    /// A page has been requested and not arrived yet
+    pub loading: bool,
+}
+
+impl<T> Default for ListPages<T> {
+    fn default() -> Self {
+        Self { rows: Vec::new(), loaded: 0, total_count: 0, has_next: false, loading: false }
+    }
+}
+
+impl<T> ListPages<T> {
+    /// Take in a page; the first one starts the list over, and one that does
+    /// not follow the loaded pages (asked for twice, or from before a refresh)
+    /// is dropped
+    pub fn receive(&mut self, page: PaginatedResult<T>) {
+        if page.page <= 1 {
+            self.rows.clear();
+        } else if page.page != self.loaded + 1 {
+            return;
+        }
+        self.loaded = page.page.max(1);
+        self.total_count = page.total_count.max(0) as usize;
+        self.has_next = page.has_next;
+        self.loading = false;
+        self.rows.extend(page.items.unwrap_or_default());
+    }
+
+    /// Whether the list is loaded a page at a time
+    pub fn is_paged(&self) -> bool {
+        self.loaded > 0
+    }
+
+    /// Rows loaded, rows on the server and whether a page is on its way
+    pub fn status(&self) -> (usize, usize, bool) {
+        (self.rows.len(), self.total_count, self.loading)
+    }
+
+    /// The page after the loaded ones, if there is one and it is not on its way
+    pub fn next_unloaded(&self) -> Option<i32> {
+        (self.has_next && !self.loading).then_some(self.loaded + 1)
+    }
+
+    /// Page to request now that loaded row `selected` is selected, if one is
+    /// needed and none is on its way
+    pub fn next_page(&mut self, selected: usize) -> Option<i32> {
+        if !self.has_next || self.loading || selected + PREFETCH_ROWS < self.rows.len() {
+            return None;
+        }
+        self.loading = true;
+        Some(self.loaded + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pages_are_requested_near_the_end() {
+        let mut pages = ListPages::default();
+        assert_eq!(pages.next_page(0), None, "not paged");
+        pages.receive(PaginatedResult::slice((0..45).collect(), 1, 20));
+        assert_eq!((pages.rows.len(), pages.total_count), (20, 45));
+        assert_eq!(pages.next_page(5), None, "far from the end");
+        assert_eq!(pages.next_page(10), Some(2));
+        assert_eq!(pages.next_page(11), None, "already on its way");
+
+        pages.receive(PaginatedResult::slice((0..45).collect(), 2, 20));
+        assert_eq!(pages.next_unloaded(), Some(3));
+        pages.receive(PaginatedResult::slice((0..45).collect(), 2, 20));
+        assert_eq!(pages.rows.len(), 40, "a page that came twice is taken once");
+        pages.receive(PaginatedResult::slice((0..45).collect(), 3, 20));
+        assert_eq!(pages.rows.len(), 45);
+        assert_eq!(pages.next_page(44), None, "last page");
+
+        pages.receive(PaginatedResult::slice((0..45).collect(), 1, 20));
+        assert_eq!(pages.rows.len(), 20, "a refresh starts over");
+    }
+}
@@ -1,24 +1,40 @@
 //! Particle system for background animations.
 //!
 //! This module implements a lightweight particle system that creates
-//! a "Digital Rain" or "Starfield" effect in the background of the TUI.
+//! a "Digital Rain", "Starfield", plasma, snow or confetti effect in the
+//! background of the TUI,
+//! plus short-lived foreground bursts (fireworks) triggered by events.
 
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Color, Style},
     widgets::Widget,
 };
+use serde::{Deserialize, Serialize};
+
+use crate::theme::colors;
+
+/// Terminal cells per particle at density 1.0 (~100 particles on 120x40)
+const CELLS_PER_PARTICLE: f32 = 48.0;
 
 /// Types of background animations
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum ParticleMode {
     /// Matrix-style digital rain effect
     #[default]
     DigitalRain,
     /// Space starfield effect
     Starfield,
+    /// Slowly shifting sine-wave color field
+    Plasma,
+    /// Drifting snowflakes with horizontal sway
+    Snow,
+    /// Multicolored falling confetti pieces
+    Confetti,
     /// No particles (static background)
     None,
 }
@@ -28,7 +44,10 @@ impl ParticleMode {
     pub fn next(&self) -> Self {
         match self {
             ParticleMode::DigitalRain => ParticleMode::Starfield,
-            ParticleMode::Starfield => ParticleMode::None,
+            ParticleMode::Starfield => ParticleMode::Plasma,
+            ParticleMode::Plasma => ParticleMode::Snow,
+            ParticleMode::Snow => ParticleMode::Confetti,
+            ParticleMode::Confetti => ParticleMode::None,
             ParticleMode::None => ParticleMode::DigitalRain,
         }
     }
@@ -38,6 +57,9 @@ impl ParticleMode {
         match self {
             ParticleMode::DigitalRain => "Digital Rain",
             ParticleMode::Starfield => "Starfield",
+            ParticleMode::Plasma => "Plasma",
+            ParticleMode::Snow => "Snow",
+            ParticleMode::Confetti => "Confetti",
             ParticleMode::None => "None",
         }
     }
@@ -60,60 +82,116 @@ pub struct Particle {
     pub brightness: f32,
     /// Fade rate
     pub fade_rate: f32,
+    /// Downward acceleration per update (fireworks sparks)
+    pub gravity: f32,
+    /// Fixed base color (overrides the mode palette)
+    pub color: Option<Color>,
+    /// Horizontal sway amplitude (snow, confetti)
+    pub sway: f32,
+    /// Sway phase in radians
+    pub phase: f32,
 }
 
 impl Particle {
-    /// Create a new digital rain particle
-    pub fn new_rain(x: u16, _max_y: u16) -> Self {
-        let mut rng = rand::thread_rng();
+    /// Create a new starfield particle
+    pub fn new_star(width: u16, height: u16, rng: &mut impl Rng) -> Self {
+        Self {
+            x: rng.gen_range(0.0..width as f32),
+            y: rng.gen_range(0.0..height as f32),
+            vy: 0.0,
+            vx: rng.gen_range(0.1..0.8),
+            char: Self::random_star_char(rng),
+            brightness: rng.gen_range(0.3..1.0),
+            fade_rate: rng.gen_range(0.005..0.02),
+            gravity: 0.0,
+            color: None,
+            sway: 0.0,
+            phase: 0.0,
+        }
+    }
+
+    /// Create a snowflake just above the top edge
+    pub fn new_snowflake(width: u16, rng: &mut impl Rng) -> Self {
+        let chars = ['·', '•', '*', '❄', '❅'];
+        let shade = rng.gen_range(170..=235);
         Self {
-            x: x as f32,
+            x: rng.gen_range(0.0..width as f32),
             y: 0.0,
-            vy: rng.gen_range(0.3..1.5),
+            vy: rng.gen_range(0.08..0.3),
             vx: 0.0,
-            char: Self::random_rain_char(),
+            char: chars[rng.gen_range(0..chars.len())],
             brightness: 1.0,
-            fade_rate: rng.gen_range(0.01..0.05),
+            fade_rate: 0.0,
+            gravity: 0.0,
+            color: Some(Color::Rgb(shade, shade, shade.saturating_add(15))),
+            sway: rng.gen_range(0.05..0.2),
+            phase: rng.gen_range(0.0..std::f32::consts::TAU),
         }
     }
 
-    /// Create a new starfield particle
-    pub fn new_star(width: u16, height: u16) -> Self {
-        let mut rng = rand::thread_rng();
+    /// Create a confetti piece just above the top edge
+    pub fn new_confetti(width: u16, rng: &mut impl Rng) -> Self {
+        let chars = ['▪', '▬', '▮', '◆', '●'];
+        let palette = [colors::YELLOW, colors::MAGENTA, colors::GREEN_LIGHT, colors::BLUE_LIGHT, colors::ORANGE, colors::RED_LIGHT];
         Self {
             x: rng.gen_range(0.0..width as f32),
-            y: rng.gen_range(0.0..height as f32),
-            vy: 0.0,
-            vx: rng.gen_range(0.1..0.8),
-            char: Self::random_star_char(),
-            brightness: rng.gen_range(0.3..1.0),
-            fade_rate: rng.gen_range(0.005..0.02),
+            y: 0.0,
+            vy: rng.gen_range(0.2..0.6),
+            vx: 0.0,
+            char: chars[rng.gen_range(0..chars.len())],
+            brightness: 1.0,
+            fade_rate: 0.0,
+            gravity: 0.0,
+            color: Some(palette[rng.gen_range(0..palette.len())]),
+            sway: rng.gen_range(0.2..0.5),
+            phase: rng.gen_range(0.0..std::f32::consts::TAU),
+        }
+    }
+
+    /// Create a fireworks spark flying out from (x, y)
+    pub fn new_spark(x: f32, y: f32, color: Color, rng: &mut impl Rng) -> Self {
+        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let speed = rng.gen_range(0.4..1.4);
+        let chars = ['*', '+', '·', '•', '✦', '×'];
+        Self {
+            x,
+            y,
+            // Terminal cells are about twice as tall as wide
+            vx: angle.cos() * speed,
+            vy: angle.sin() * speed * 0.5,
+            char: chars[rng.gen_range(0..chars.len())],
+            brightness: 1.0,
+            fade_rate: rng.gen_range(0.04..0.08),
+            gravity: 0.04,
+            color: Some(color),
+            sway: 0.0,
+            phase: 0.0,
         }
     }
 
     /// Get a random character for digital rain
-    fn random_rain_char() -> char {
-        let mut rng = rand::thread_rng();
+    pub fn random_rain_char(rng: &mut impl Rng) -> char {
         let chars: Vec<char> = "01アイウエオカキクケコサシスセソタチツテト".chars().collect();
         chars[rng.gen_range(0..chars.len())]
     }
 
     /// Get a random character for starfield
-    fn random_star_char() -> char {
-        let mut rng = rand::thread_rng();
+    fn random_star_char(rng: &mut impl Rng) -> char {
         let chars = ['·', '•', '∙', '○', '◦', '*', '+', '×'];
         chars[rng.gen_range(0..chars.len())]
     }
 
-    /// Update particle position and state
-    pub fn update(&mut self) {
-        self.y += self.vy;
-        self.x += self.vx;
+    /// Update particle position and state (`speed` scales the velocity)
+    pub fn update(&mut self, speed: f32, rng: &mut impl Rng) {
+        self.y += self.vy * speed;
+        self.x += self.vx * speed + self.sway * self.phase.sin();
+        self.phase += 0.15 * speed;
+        self.vy += self.gravity;
         self.brightness -= self.fade_rate;
 
         // Occasionally change the character (for rain effect)
-        if rand::thread_rng().gen_ratio(1, 10) {
-            self.char = Self::random_rain_char();
+        if self.color.is_none() && rng.gen_ratio(1, 10) {
+            self.char = Self::random_rain_char(rng);
         }
     }
 
@@ -124,6 +202,10 @@ impl Particle {
 
     /// Get the color based on brightness
     pub fn get_color(&self, mode: ParticleMode) -> Color {
+        if let Some(Color::Rgb(r, g, b)) = self.color {
+            let scale = |c: u8| (c as f32 * self.brightness.clamp(0.0, 1.0)) as u8;
+            return Color::Rgb(scale(r), scale(g), scale(b));
+        }
         match mode {
             ParticleMode::DigitalRain => {
                 let intensity = (self.brightness * 255.0) as u8;
@@ -133,22 +215,126 @@ impl Particle {
                 let intensity = (self.brightness * 255.0) as u8;
                 Color::Rgb(intensity, intensity, intensity)
             }
-            ParticleMode::None => Color::Reset,
+            ParticleMode::Plasma | ParticleMode::Snow | ParticleMode::Confetti | ParticleMode::None => Color::Reset,
         }
     }
 }
 
+/// A falling column of digital rain: bright head with a fading tail
+#[derive(Debug, Clone)]
+pub struct Streamer {
+    /// Column
+    pub x: u16,
+    /// Row of the head (fractional for smooth speed)
+    pub head: f32,
+    /// Rows advanced per update
+    pub speed: f32,
+    /// Glyphs from head (index 0) to tail end
+    pub glyphs: Vec<char>,
+}
+
+impl Streamer {
+    /// Create a new streamer above the top edge of the given column
+    pub fn new(x: u16, height: u16, rng: &mut impl Rng) -> Self {
+        let max_len = (height / 2).max(4);
+        let length = rng.gen_range(3..=max_len) as usize;
+        Self {
+            x,
+            head: 0.0,
+            speed: rng.gen_range(0.3..1.2),
+            glyphs: (0..length).map(|_| Particle::random_rain_char(rng)).collect(),
+        }
+    }
+
+    /// Advance the head and occasionally mutate a tail glyph
+    pub fn update(&mut self, speed: f32, rng: &mut impl Rng) {
+        let previous_row = self.head as u16;
+        self.head += self.speed * speed;
+
+        // Each time the head enters a new row, the trail shifts down by one
+        if self.head as u16 != previous_row {
+            self.glyphs.rotate_right(1);
+            self.glyphs[0] = Particle::random_rain_char(rng);
+        }
+
+        if rng.gen_ratio(1, 8) {
+            let i = rng.gen_range(0..self.glyphs.len());
+            self.glyphs[i] = Particle::random_rain_char(rng);
+        }
+    }
+
+    /// Check if any part of the trail is still on screen
+    pub fn is_alive(&self, height: u16) -> bool {
+        (self.head as usize).saturating_sub(self.glyphs.len()) < height as usize
+    }
+
+    /// Color of the trail cell `offset` rows behind the head
+    pub fn color_at(&self, offset: usize) -> Color {
+        if offset == 0 {
+            // Bright, nearly white head
+            return Color::Rgb(200, 255, 210);
+        }
+        let fade = 1.0 - offset as f32 / self.glyphs.len() as f32;
+        let intensity = (fade * 220.0) as u8;
+        Color::Rgb(0, intensity, intensity / 3)
+    }
+}
+
+/// Velocity multiplier while warp (loading) is active
+const WARP_SPEED: f32 = 3.0;
+
+/// One-shot effects reacting to application events
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParticleEffect {
+    /// A colored band sweeping left to right (e.g. successful refresh)
+    Sweep(Color),
+    /// Particles flickering in a color (e.g. API error)
+    Flicker(Color),
+}
+
+impl ParticleEffect {
+    /// Length of the effect in frames
+    pub fn duration(&self) -> u32 {
+        match self {
+            ParticleEffect::Sweep(_) => 24,
+            ParticleEffect::Flicker(_) => 12,
+        }
+    }
+}
+
+/// An effect from the queue together with its progress
+#[derive(Debug, Clone, Copy)]
+struct ActiveEffect {
+    effect: ParticleEffect,
+    /// Frames since the effect started
+    elapsed: u32,
+}
+
 /// The particle system managing all particles
 #[derive(Debug, Clone)]
 pub struct ParticleSystem {
     /// All active particles
     particles: Vec<Particle>,
+    /// Digital rain columns (one entry renders a whole trail)
+    streamers: Vec<Streamer>,
     /// Current animation mode
     mode: ParticleMode,
-    /// Maximum number of particles
+    /// Maximum number of particles (derived from the area and density)
     max_particles: usize,
+    /// Density multiplier from the config (1.0 = default)
+    density: f32,
+    /// Area the particle budget was last computed for
+    area: (u16, u16),
     /// Frame counter for spawn timing
     frame_count: u64,
+    /// Foreground burst sparks (rendered on top, independent of mode)
+    bursts: Vec<Particle>,
+    /// Random source for all particles (seedable for reproducible frames)
+    rng: StdRng,
+    /// Queued event effects, played concurrently
+    effects: Vec<ActiveEffect>,
+    /// Speed particles up (e.g. while loading)
+    warp: bool,
 }
 
 impl Default for ParticleSystem {
@@ -162,17 +348,116 @@ impl ParticleSystem {
     pub fn new(mode: ParticleMode, max_particles: usize) -> Self {
         Self {
             particles: Vec::with_capacity(max_particles),
+            streamers: Vec::new(),
             mode,
             max_particles,
+            density: 1.0,
+            area: (0, 0),
             frame_count: 0,
+            bursts: Vec::new(),
+            rng: StdRng::from_entropy(),
+            effects: Vec::new(),
+            warp: false,
         }
     }
 
+    /// Queue an event effect; it plays over the next frames
+    pub fn push_effect(&mut self, effect: ParticleEffect) {
+        // Restart rather than stack identical effects
+        self.effects.retain(|e| e.effect != effect);
+        self.effects.push(ActiveEffect { effect, elapsed: 0 });
+    }
+
+    /// Speed particles up while something is in progress
+    pub fn set_warp(&mut self, warp: bool) {
+        self.warp = warp;
+    }
+
+    /// Color override for particles from an active flicker, if any
+    fn flicker_tint(&self) -> Option<Color> {
+        self.effects.iter().find_map(|e| match e.effect {
+            // Alternate every other pair of frames
+            ParticleEffect::Flicker(color) if (e.elapsed / 2).is_multiple_of(2) => Some(color),
+            _ => None,
+        })
+    }
+
+    /// Render active sweep bands
+    fn render_sweeps(&self, area: Rect, buf: &mut Buffer) {
+        for active in &self.effects {
+            let ParticleEffect::Sweep(color) = active.effect else {
+                continue;
+            };
+            let progress = active.elapsed as f32 / active.effect.duration() as f32;
+            let center = (progress * (area.width as f32 + 8.0)) as i32 - 4;
+            for dx in -3i32..=3 {
+                let x = center + dx;
+                if x < 0 || x >= area.width as i32 {
+                    continue;
+                }
+                let ch = if dx.abs() <= 1 { '▒' } else { '░' };
+                for y in 0..area.height {
+                    let pos = (area.x + x as u16, area.y + y);
+                    buf[pos].set_char(ch);
+                    buf[pos].set_style(Style::default().fg(color));
+                }
+            }
+        }
+    }
+
+    /// Reseed the random source so the same seed produces identical frames
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Fire a fireworks burst centred on the given cell
+    pub fn burst(&mut self, x: u16, y: u16) {
+        let palette = [colors::YELLOW, colors::MAGENTA, colors::GREEN_LIGHT, colors::BLUE_LIGHT, colors::ORANGE];
+        for _ in 0..24 {
+            let color = palette[self.rng.gen_range(0..palette.len())];
+            self.bursts.push(Particle::new_spark(x as f32, y as f32, color, &mut self.rng));
+        }
+    }
+
+    /// Whether the next update changes what is drawn (effects only play with a background mode)
+    pub fn is_animating(&self) -> bool {
+        self.mode != ParticleMode::None || !self.bursts.is_empty()
+    }
+
+    /// Number of live background particles (a rain streamer counts once)
+    pub fn particle_count(&self) -> usize {
+        self.particles.len() + self.streamers.len() + self.bursts.len()
+    }
+
+    /// Set the density multiplier (takes effect on the next update)
+    pub fn set_density(&mut self, density: f32) {
+        self.density = density.max(0.0);
+        self.area = (0, 0);
+    }
+
+    /// Particle budget for an area: roughly one particle per 48 cells at density 1.0
+    pub fn budget_for(width: u16, height: u16, density: f32) -> usize {
+        let cells = width as f32 * height as f32;
+        (cells / CELLS_PER_PARTICLE * density).round() as usize
+    }
+
+    /// Recompute the budget and re-seed when the terminal size changed
+    fn resize(&mut self, width: u16, height: u16) {
+        if self.area == (width, height) {
+            return;
+        }
+        self.area = (width, height);
+        self.max_particles = Self::budget_for(width, height, self.density);
+        self.particles.clear();
+        self.streamers.clear();
+    }
+
     /// Set the animation mode
     pub fn set_mode(&mut self, mode: ParticleMode) {
         if self.mode != mode {
             self.mode = mode;
             self.particles.clear();
+            self.streamers.clear();
         }
     }
 
@@ -189,19 +474,40 @@ impl ParticleSystem {
     /// Update all particles and spawn new ones
     pub fn update(&mut self, width: u16, height: u16) {
         self.frame_count = self.frame_count.wrapping_add(1);
+        self.resize(width, height);
+
+        // Bursts are event effects and play regardless of the background mode
+        for spark in &mut self.bursts {
+            spark.update(1.0, &mut self.rng);
+        }
+        self.bursts
+            .retain(|p| p.is_alive(height, width) && p.x >= 0.0 && p.y >= 0.0);
 
         if self.mode == ParticleMode::None {
             return;
         }
 
+        // Advance queued effects, dropping finished ones
+        for effect in &mut self.effects {
+            effect.elapsed += 1;
+        }
+        self.effects.retain(|e| e.elapsed < e.effect.duration());
+
         // Update existing particles
+        let speed = if self.warp { WARP_SPEED } else { 1.0 };
         for particle in &mut self.particles {
-            particle.update();
+            particle.update(speed, &mut self.rng);
+        }
+
+        for streamer in &mut self.streamers {
+            streamer.update(speed, &mut self.rng);
         }
 
         // Remove dead particles
         self.particles
-            .retain(|p| p.is_alive(height, width));
+            .retain(|p| p.is_alive(height, width) && p.x >= 0.0);
+        self.streamers
+            .retain(|s| s.is_alive(height) && s.x < width);
 
         // Spawn new particles
         self.spawn_particles(width, height);
@@ -209,26 +515,77 @@ impl ParticleSystem {
 
     /// Spawn new particles based on mode
     fn spawn_particles(&mut self, width: u16, height: u16) {
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         match self.mode {
             ParticleMode::DigitalRain => {
-                // Spawn a few new rain drops each frame
-                if self.frame_count % 3 == 0 && self.particles.len() < self.max_particles {
-                    let num_new = rng.gen_range(1..=3).min(self.max_particles - self.particles.len());
+                // A streamer is a whole trail, so budget a few cells per column
+                let max_streamers = self.max_particles / 4;
+                if self.frame_count.is_multiple_of(3) && self.streamers.len() < max_streamers && width > 0 {
+                    let num_new = rng.gen_range(1..=2).min(max_streamers - self.streamers.len());
                     for _ in 0..num_new {
                         let x = rng.gen_range(0..width);
-                        self.particles.push(Particle::new_rain(x, height));
+                        self.streamers.push(Streamer::new(x, height, rng));
                     }
                 }
             }
             ParticleMode::Starfield => {
                 // Maintain a steady number of stars
                 while self.particles.len() < self.max_particles / 2 {
-                    self.particles.push(Particle::new_star(width, height));
+                    self.particles.push(Particle::new_star(width, height, rng));
+                }
+            }
+            ParticleMode::Snow | ParticleMode::Confetti => {
+                // Trickle new pieces in at the top until the budget is reached
+                let target = self.max_particles / 2;
+                if width > 0 && self.particles.len() < target {
+                    let num_new = rng.gen_range(0..=2).min(target - self.particles.len());
+                    for _ in 0..num_new {
+                        let piece = if self.mode == ParticleMode::Snow {
+                            Particle::new_snowflake(width, rng)
+                        } else {
+                            Particle::new_confetti(width, rng)
+                        };
+                        self.particles.push(piece);
+                    }
+                }
+            }
+            // The plasma field is computed at render time, nothing to spawn
+            ParticleMode::Plasma | ParticleMode::None => {}
+        }
+    }
+
+    /// Plasma field value in 0.0..=1.0 for a cell at time `t`
+    fn plasma_value(x: f32, y: f32, t: f32) -> f32 {
+        // Cells are roughly twice as tall as wide, so stretch y
+        let y = y * 2.0;
+        let v = (x / 9.0 + t).sin()
+            + (y / 7.0 - t * 0.7).sin()
+            + ((x + y) / 11.0 + t * 0.5).sin()
+            + ((x * x + y * y).sqrt() / 8.0 - t * 0.9).sin();
+        (v / 4.0 + 1.0) / 2.0
+    }
+
+    /// Render the plasma background
+    fn render_plasma(&self, area: Rect, buf: &mut Buffer) {
+        const SHADES: [char; 4] = [' ', '░', '▒', '▓'];
+        let t = self.frame_count as f32 * 0.03;
+
+        for y in 0..area.height {
+            for x in 0..area.width {
+                let v = Self::plasma_value(x as f32, y as f32, t);
+                let shade = ((v * SHADES.len() as f32) as usize).min(SHADES.len() - 1);
+                if shade == 0 {
+                    continue;
                 }
+                // Muted purple -> blue palette to keep the foreground readable
+                let r = (40.0 + 50.0 * (1.0 - v)) as u8;
+                let g = (30.0 + 40.0 * v) as u8;
+                let b = (60.0 + 60.0 * v) as u8;
+                let pos = (area.x + x, area.y + y);
+                buf[pos].set_char(SHADES[shade]);
+                buf[pos].set_style(Style::default().fg(Color::Rgb(r, g, b)));
             }
-            ParticleMode::None => {}
         }
     }
 
@@ -238,33 +595,209 @@ impl ParticleSystem {
             return;
         }
 
+        if self.mode == ParticleMode::Plasma {
+            self.render_plasma(area, buf);
+            self.render_sweeps(area, buf);
+            return;
+        }
+
+        let tint = self.flicker_tint();
         for particle in &self.particles {
             let x = particle.x as u16;
             let y = particle.y as u16;
 
             if x < area.width && y < area.height {
                 let pos = (area.x + x, area.y + y);
-                let color = particle.get_color(self.mode);
+                let color = tint.unwrap_or_else(|| particle.get_color(self.mode));
                 buf[pos].set_char(particle.char);
                 buf[pos].set_style(Style::default().fg(color));
             }
         }
+
+        for streamer in &self.streamers {
+            if streamer.x >= area.width {
+                continue;
+            }
+            let head = streamer.head as i32;
+            for (offset, glyph) in streamer.glyphs.iter().enumerate() {
+                let y = head - offset as i32;
+                if y < 0 || y >= area.height as i32 {
+                    continue;
+                }
+                let pos = (area.x + streamer.x, area.y + y as u16);
+                buf[pos].set_char(*glyph);
+                buf[pos].set_style(Style::default().fg(tint.unwrap_or_else(|| streamer.color_at(offset))));
+            }
+        }
+
+        self.render_sweeps(area, buf);
+    }
+
+    /// Render foreground burst sparks (keeps the cell background)
+    pub fn render_bursts(&self, area: Rect, buf: &mut Buffer) {
+        for spark in &self.bursts {
+            let x = spark.x as u16;
+            let y = spark.y as u16;
+
+            if x < area.width && y < area.height {
+                let pos = (area.x + x, area.y + y);
+                buf[pos].set_char(spark.char);
+                buf[pos].set_fg(spark.get_color(self.mode));
+            }
+        }
+    }
+}
+
+/// Scale an RGB color's brightness
+fn scale_color(color: Color, factor: f32) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => Color::Rgb(
+            (r as f32 * factor) as u8,
+            (g as f32 * factor) as u8,
+            (b as f32 * factor) as u8,
+        ),
+        other => other,
     }
 }
 
 /// Widget wrapper for the particle system
 pub struct ParticleWidget<'a> {
     system: &'a ParticleSystem,
+    /// Render the foreground bursts instead of the background animation
+    effects: bool,
+    /// Panel interiors; on each row of one, particles between the first and
+    /// the last character of text are dimmed
+    content: &'a [Rect],
+    /// Brightness of particles within lines of text (0.0 hides them)
+    content_dim: f32,
 }
 
 impl<'a> ParticleWidget<'a> {
     pub fn new(system: &'a ParticleSystem) -> Self {
-        Self { system }
+        Self { system, effects: false, content: &[], content_dim: 1.0 }
+    }
+
+    /// Widget drawing only the foreground bursts (render after the content)
+    pub fn effects(system: &'a ParticleSystem) -> Self {
+        Self { system, effects: true, content: &[], content_dim: 1.0 }
+    }
+
+    /// Dim particles within the lines of text of these panels, keeping full
+    /// brightness in the background space around them
+    pub fn content_regions(mut self, regions: &'a [Rect], dim: f32) -> Self {
+        self.content = regions;
+        self.content_dim = dim.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Cells of `area` that lie within a line of text already drawn into
+    /// `buf`: from the first to the last non-blank cell of each row of a panel
+    fn text_mask(&self, area: Rect, buf: &Buffer) -> Vec<bool> {
+        let mut mask = vec![false; area.area() as usize];
+        for region in self.content {
+            let region = region.intersection(area);
+            for y in region.top()..region.bottom() {
+                let mut text = (region.left()..region.right()).filter(|&x| buf[(x, y)].symbol() != " ");
+                let Some(first) = text.next() else { continue };
+                let last = text.next_back().unwrap_or(first);
+                let row = (y - area.y) as usize * area.width as usize;
+                mask[row + (first - area.x) as usize..=row + (last - area.x) as usize].fill(true);
+            }
+        }
+        mask
+    }
+
+    /// Draw the background animation into the cells the content left blank
+    /// (render after the content)
+    fn fill_background(&self, area: Rect, buf: &mut Buffer) {
+        let mut layer = Buffer::empty(area);
+        self.system.render(area, &mut layer);
+        let mask = if self.content_dim < 1.0 { self.text_mask(area, buf) } else { Vec::new() };
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let glyph = &layer[(x, y)];
+                let cell = &mut buf[(x, y)];
+                if glyph.symbol() == " " || cell.symbol() != " " {
+                    continue;
+                }
+                let within_text = mask.get((y - area.y) as usize * area.width as usize + (x - area.x) as usize) == Some(&true);
+                let brightness = if within_text { self.content_dim } else { 1.0 };
+                if brightness <= 0.0 {
+                    continue;
+                }
+                cell.set_symbol(glyph.symbol());
+                cell.fg = if brightness < 1.0 { scale_color(glyph.fg, brightness) } else { glyph.fg };
+            }
+        }
     }
 }
 
 impl Widget for ParticleWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        self.system.render(area, buf);
+        if self.effects {
+            self.system.render_bursts(area, buf);
+        } else {
+            self.fill_background(area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_particle_budget_scales_with_area() {
+        assert_eq!(ParticleSystem::budget_for(120, 40, 1.0), 100);
+        assert_eq!(ParticleSystem::budget_for(240, 80, 1.0), 400);
+        assert_eq!(ParticleSystem::budget_for(120, 40, 0.5), 50);
+        assert_eq!(ParticleSystem::budget_for(120, 40, 0.0), 0);
+    }
+
+    #[test]
+    fn test_seeded_systems_render_identical_frames() {
+        let area = Rect::new(0, 0, 60, 20);
+        let render = |mode: ParticleMode| {
+            let mut system = ParticleSystem::new(mode, 0);
+            system.reseed(42);
+            system.burst(30, 10);
+            let mut buf = Buffer::empty(area);
+            for _ in 0..50 {
+                system.update(area.width, area.height);
+            }
+            system.render(area, &mut buf);
+            system.render_bursts(area, &mut buf);
+            buf
+        };
+
+        for mode in [ParticleMode::DigitalRain, ParticleMode::Starfield] {
+            assert_eq!(render(mode), render(mode));
+        }
+    }
+
+    #[test]
+    fn test_particles_hidden_behind_content_regions() {
+        let area = Rect::new(0, 0, 40, 20);
+        let mut system = ParticleSystem::new(ParticleMode::Starfield, 0);
+        system.reseed(7);
+        for _ in 0..5 {
+            system.update(area.width, area.height);
+        }
+
+        // Every row of the left panel holds text from column 2 to column 17
+        let content = [Rect::new(0, 0, 20, 20)];
+        let mut buf = Buffer::empty(area);
+        for y in 0..20 {
+            buf.set_string(2, y, "a", Style::default());
+            buf.set_string(17, y, "b", Style::default());
+        }
+        ParticleWidget::new(&system).content_regions(&content, 0.0).render(area, &mut buf);
+
+        let visible = |x_range: std::ops::Range<u16>| {
+            x_range.flat_map(|x| (0..20).map(move |y| (x, y))).filter(|&pos| buf[pos].symbol() != " ").count()
+        };
+        assert_eq!(visible(3..17), 0, "hidden between the text");
+        assert!(visible(20..40) > 0);
+        assert!((0..20).all(|y| buf[(2, y)].symbol() == "a" && buf[(17, y)].symbol() == "b"), "text stays on top");
     }
 }
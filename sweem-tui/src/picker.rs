@@ -0,0 +1,125 @@
+//! Searchable picker for the client and manager fields of the project forms.
+//!
+//! Cycling through hundreds of clients with `Up`/`Down` is slow, so typing on
+//! one of these fields (or `Enter`) opens a list under it that is filtered as
+//! you type: every word of the query has to appear in the name (or, for
+//! users, the login), case ignored. `Up`/`Down` move the highlight, `Enter`
+//! takes it and `Esc` closes the list, keeping the previous choice. Only the
+//! loaded clients and users are searched.
+
+use crate::api::EntityType;
+use crate::models::{ClientDto, UserDto};
+
+/// Rows of the list shown at once
+pub const VISIBLE_ROWS: usize = 6;
+
+/// Text of each client or user as listed (and searched) by the picker
+pub fn candidates(entity: EntityType, clients: &[ClientDto], users: &[UserDto]) -> Vec<String> {
+    match entity {
+        EntityType::User => users
+            .iter()
+            .map(|u| match &u.login {
+                Some(login) => format!("{} ({})", u.display_name(), login),
+                None => u.display_name().to_string(),
+            })
+            .collect(),
+        _ => clients.iter().map(|c| c.display_name().to_string()).collect(),
+    }
+}
+
+/// Open picker list of a selector field
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Picker {
+    pub query: String,
+    /// Indices of the matching candidates, in list order
+    pub matches: Vec<usize>,
+    /// Highlighted row of `matches`
+    pub selected: usize,
+}
+
+impl Picker {
+    /// Picker over `candidates` with the current choice highlighted
+    pub fn new(candidates: &[String], current: usize) -> Self {
+        let mut picker = Self::default();
+        picker.filter(candidates);
+        picker.selected = picker.matches.iter().position(|&i| i == current).unwrap_or(0);
+        picker
+    }
+
+    /// Recompute the matches after the query changed, keeping the highlighted
+    /// candidate when it still matches
+    pub fn filter(&mut self, candidates: &[String]) {
+        let highlighted = self.choice();
+        let words: Vec<String> = self.query.split_whitespace().map(str::to_lowercase).collect();
+        self.matches = candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, text)| {
+                let text = text.to_lowercase();
+                words.iter().all(|w| text.contains(w.as_str()))
+            })
+            .map(|(i, _)| i)
+            .collect();
+        self.selected = highlighted.and_then(|h| self.matches.iter().position(|&i| i == h)).unwrap_or(0);
+    }
+
+    pub fn push(&mut self, c: char, candidates: &[String]) {
+        self.query.push(c);
+        self.filter(candidates);
+    }
+
+    pub fn pop(&mut self, candidates: &[String]) {
+        self.query.pop();
+        self.filter(candidates);
+    }
+
+    /// Move the highlight, stopping at either end
+    pub fn select(&mut self, forward: bool) {
+        self.selected = if forward {
+            (self.selected + 1).min(self.matches.len().saturating_sub(1))
+        } else {
+            self.selected.saturating_sub(1)
+        };
+    }
+
+    /// Index of the highlighted candidate, if anything matches
+    pub fn choice(&self) -> Option<usize> {
+        self.matches.get(self.selected).copied()
+    }
+
+    /// First row of `matches` to show so the highlight stays in view
+    pub fn offset(&self) -> usize {
+        (self.selected + 1).saturating_sub(VISIBLE_ROWS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typing_narrows_the_list() {
+        let candidates: Vec<String> = ["Acme Corp", "Globex", "Acme Labs", "Initech"].map(String::from).to_vec();
+        let mut picker = Picker::new(&candidates, 2);
+        assert_eq!((picker.matches.len(), picker.choice()), (4, Some(2)));
+
+        picker.push('a', &candidates);
+        picker.push('C', &candidates);
+        assert_eq!(picker.matches, [0, 2]);
+        assert_eq!(picker.choice(), Some(2), "the highlighted client still matches");
+        for c in " corp".chars() {
+            picker.push(c, &candidates);
+        }
+        assert_eq!((picker.matches.as_slice(), picker.choice()), (&[0][..], Some(0)));
+        picker.push('x', &candidates);
+        assert_eq!(picker.choice(), None);
+
+        picker.query.clear();
+        picker.filter(&candidates);
+        picker.select(true);
+        picker.select(true);
+        picker.select(true);
+        picker.select(true);
+        assert_eq!(picker.choice(), Some(3), "stops at the end");
+    }
+}
@@ -0,0 +1,102 @@
+//! Compile-time plugin tabs.
+//!
+//! A [`TabPlugin`] adds a tab after the built-in Clients/Timeline/Users tabs
+//! without touching the `Tab` matches in app.rs and ui.rs. Plugins get
+//! read-only access to the loaded data, keys not handled globally while
+//! their tab is active, and every API message.
+//!
+//! To add a tab, implement the trait and register it in [`register_plugins`].
+
+#![allow(dead_code)]
+
+use chrono::NaiveDate;
+use crossterm::event::KeyEvent;
+use ratatui::{layout::Rect, Frame};
+
+use crate::api::{ApiCommand, ApiMessage};
+use crate::app::App;
+use crate::models::{ClientDto, ProjectDto, UserDto};
+
+/// Read-only view of the application data handed to plugins
+#[derive(Debug, Clone, Copy)]
+pub struct PluginContext<'a> {
+    pub projects: &'a [ProjectDto],
+    pub clients: &'a [ClientDto],
+    pub users: &'a [UserDto],
+    /// Date statuses are evaluated against (today or the time-travel date)
+    pub reference_date: NaiveDate,
+}
+
+/// An extra tab compiled into the application
+pub trait TabPlugin: std::fmt::Debug {
+    /// Title shown in the tab bar
+    fn title(&self) -> &str;
+
+    /// Draw the tab's content into the main area
+    fn render(&self, frame: &mut Frame, area: Rect, ctx: &PluginContext);
+
+    /// Handle a key press while the tab is active; may ask the API worker for something
+    fn handle_key(&mut self, _key: KeyEvent, _ctx: &PluginContext) -> Option<ApiCommand> {
+        None
+    }
+
+    /// Observe an API message (called for every message, whichever tab is active)
+    fn on_message(&mut self, _message: &ApiMessage) {}
+}
+
+/// Register the plugins compiled into this build
+pub fn register_plugins(_app: &mut App) {
+    // app.register_plugin(Box::new(MyTab::default()));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::app::Tab;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    /// Counts what it is handed; the test keeps clones of the counters
+    #[derive(Debug, Default, Clone)]
+    struct CounterTab {
+        keys: Rc<Cell<usize>>,
+        messages: Rc<Cell<usize>>,
+    }
+
+    impl TabPlugin for CounterTab {
+        fn title(&self) -> &str {
+            "Counter"
+        }
+
+        fn render(&self, _frame: &mut Frame, _area: Rect, _ctx: &PluginContext) {}
+
+        fn handle_key(&mut self, _key: KeyEvent, _ctx: &PluginContext) -> Option<ApiCommand> {
+            self.keys.set(self.keys.get() + 1);
+            None
+        }
+
+        fn on_message(&mut self, _message: &ApiMessage) {
+            self.messages.set(self.messages.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_plugin_tab_receives_keys_and_messages() {
+        let mut app = App::new();
+        let counter = CounterTab::default();
+        app.register_plugin(Box::new(counter.clone()));
+
+        // The plugin tab comes after the built-in ones and wraps around to Clients
+        assert_eq!(Tab::Audit.next(app.plugins.len()), Tab::Plugin(0));
+        assert_eq!(Tab::Plugin(0).next(app.plugins.len()), Tab::Clients);
+        assert_eq!(app.tab_title(Tab::Plugin(0)), "Counter");
+
+        app.active_tab = Tab::Plugin(0);
+        app.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        app.handle_api_message(ApiMessage::ConnectionStatus(true));
+
+        assert_eq!((counter.keys.get(), counter.messages.get()), (1, 1));
+    }
+}
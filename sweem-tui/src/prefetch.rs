@@ -0,0 +1,215 @@
+//! Background prefetching in the API worker.
+//!
+//! The UI queues data it is likely to ask for next and has not loaded: the
+//! documents of the selected project and its neighbours, the next page of a
+//! lazily loaded list and the next page of the audit log. The worker fetches
+//! them only after it has been idle for a moment, highest priority first, and
+//! the answers wait in a [`PrefetchCache`] until the view asks for them, so
+//! opening the documents or turning the page shows them without a request.
+//! Moving the selection replaces the queue, and user commands always run
+//! before any queued prefetch.
+
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::api::{ApiCommand, EntityType, Prefetched};
+use crate::models::{AuditEventDto, ClientDto, DocumentDto, PaginatedResult, UserDto};
+
+/// How long the worker must be idle before it starts prefetching
+pub const PREFETCH_IDLE: Duration = Duration::from_millis(300);
+
+/// Maximum number of queued jobs; the lowest priority ones are dropped first
+const MAX_QUEUED: usize = 32;
+
+/// Document lists kept in the cache; the oldest are dropped first
+const MAX_CACHED_DOCUMENTS: usize = 16;
+
+/// Priority for the item the user is looking at
+pub const PRIORITY_SELECTED: u8 = 10;
+/// Priority for items one step away from the selection
+pub const PRIORITY_NEIGHBOR: u8 = 5;
+
+/// Something worth fetching before the user asks for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrefetchJob {
+    /// The document list of a project
+    Documents(Uuid),
+    /// A page of the Clients or Users list (`[api] list_page_size`)
+    Page { entity: EntityType, page: i32 },
+    /// A page of the audit log for a search
+    Audit { page: i32, search: String },
+}
+
+/// Pending prefetch jobs ordered by priority
+#[derive(Debug, Default)]
+pub struct PrefetchQueue {
+    /// Jobs with their priority; later pushes of equal priority run later
+    jobs: Vec<(PrefetchJob, u8)>,
+}
+
+impl PrefetchQueue {
+    /// Queue a job, raising its priority if it is already queued
+    pub fn push(&mut self, job: PrefetchJob, priority: u8) {
+        match self.jobs.iter_mut().find(|(queued, _)| *queued == job) {
+            Some((_, queued_priority)) => *queued_priority = (*queued_priority).max(priority),
+            None => self.jobs.push((job, priority)),
+        }
+        if self.jobs.len() > MAX_QUEUED {
+            if let Some(lowest) = self.lowest_index() {
+                self.jobs.remove(lowest);
+            }
+        }
+    }
+
+    /// Take the highest-priority job (first queued wins ties)
+    pub fn pop(&mut self) -> Option<PrefetchJob> {
+        let index = self
+            .jobs
+            .iter()
+            .enumerate()
+            .max_by(|(a_index, (_, a)), (b_index, (_, b))| a.cmp(b).then(b_index.cmp(a_index)))
+            .map(|(index, _)| index)?;
+        Some(self.jobs.remove(index).0)
+    }
+
+    /// Drop all queued jobs
+    pub fn clear(&mut self) {
+        self.jobs.clear();
+    }
+
+    /// Drop jobs made redundant by a user command (a refresh starts the lists over)
+    pub fn cancel_covered_by(&mut self, cmd: &ApiCommand) {
+        let covered = match cmd {
+            ApiCommand::RefreshAll => None,
+            ApiCommand::RefreshClients => Some(EntityType::Client),
+            ApiCommand::RefreshUsers => Some(EntityType::User),
+            _ => return,
+        };
+        self.jobs.retain(|(job, _)| match job {
+            PrefetchJob::Page { entity, .. } => covered.is_some_and(|covered| *entity != covered),
+            PrefetchJob::Documents(_) | PrefetchJob::Audit { .. } => true,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    fn lowest_index(&self) -> Option<usize> {
+        self.jobs
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (_, priority))| *priority)
+            .map(|(index, _)| index)
+    }
+}
+
+/// Prefetched data the views have not asked for yet; each entry is used once,
+/// so the next time it is asked for it is fetched fresh
+#[derive(Debug, Default)]
+pub struct PrefetchCache {
+    /// Document lists by project, oldest first
+    documents: Vec<(Uuid, Vec<DocumentDto>)>,
+    client_page: Option<PaginatedResult<ClientDto>>,
+    user_page: Option<PaginatedResult<UserDto>>,
+    /// Page and search it was loaded for, with the page
+    audit: Option<(i32, String, PaginatedResult<AuditEventDto>)>,
+}
+
+impl PrefetchCache {
+    /// Keep what the worker fetched
+    pub fn store(&mut self, data: Prefetched) {
+        match data {
+            Prefetched::Documents { project_id, documents } => {
+                self.documents.retain(|(id, _)| *id != project_id);
+                if self.documents.len() >= MAX_CACHED_DOCUMENTS {
+                    self.documents.remove(0);
+                }
+                self.documents.push((project_id, documents));
+            }
+            Prefetched::ClientsPage(page) => self.client_page = Some(page),
+            Prefetched::UsersPage(page) => self.user_page = Some(page),
+            Prefetched::Audit { page, search, result } => self.audit = Some((page, search, result)),
+        }
+    }
+
+    /// Whether a job's answer is already waiting
+    pub fn has(&self, job: &PrefetchJob) -> bool {
+        match job {
+            PrefetchJob::Documents(project_id) => self.documents.iter().any(|(id, _)| id == project_id),
+            PrefetchJob::Page { entity: EntityType::Client, page } => self.client_page.as_ref().is_some_and(|p| p.page == *page),
+            PrefetchJob::Page { entity: EntityType::User, page } => self.user_page.as_ref().is_some_and(|p| p.page == *page),
+            PrefetchJob::Page { entity: EntityType::Project, .. } => false,
+            PrefetchJob::Audit { page, search } => self.audit.as_ref().is_some_and(|(p, s, _)| p == page && s == search),
+        }
+    }
+
+    pub fn take_documents(&mut self, project_id: Uuid) -> Option<Vec<DocumentDto>> {
+        let index = self.documents.iter().position(|(id, _)| *id == project_id)?;
+        Some(self.documents.remove(index).1)
+    }
+
+    pub fn take_clients_page(&mut self, page: i32) -> Option<PaginatedResult<ClientDto>> {
+        self.client_page.take_if(|p| p.page == page)
+    }
+
+    pub fn take_users_page(&mut self, page: i32) -> Option<PaginatedResult<UserDto>> {
+        self.user_page.take_if(|p| p.page == page)
+    }
+
+    pub fn take_audit(&mut self, page: i32, search: &str) -> Option<PaginatedResult<AuditEventDto>> {
+        self.audit.take_if(|(p, s, _)| *p == page && s == search).map(|(_, _, result)| result)
+    }
+
+    /// Forget the pages of a list that starts over
+    pub fn clear_pages(&mut self, entity: EntityType) {
+        match entity {
+            EntityType::Client => self.client_page = None,
+            EntityType::User => self.user_page = None,
+            EntityType::Project => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_priority_and_cancel() {
+        let page = |entity, page| PrefetchJob::Page { entity, page };
+        let mut queue = PrefetchQueue::default();
+        queue.push(PrefetchJob::Documents(Uuid::from_u128(1)), PRIORITY_NEIGHBOR);
+        queue.push(PrefetchJob::Documents(Uuid::from_u128(2)), PRIORITY_SELECTED);
+        queue.push(page(EntityType::Client, 2), PRIORITY_NEIGHBOR);
+        // Re-queueing raises the priority instead of duplicating
+        queue.push(page(EntityType::Client, 2), PRIORITY_SELECTED);
+
+        assert_eq!(queue.pop(), Some(PrefetchJob::Documents(Uuid::from_u128(2))));
+        assert_eq!(queue.pop(), Some(page(EntityType::Client, 2)));
+
+        queue.push(page(EntityType::User, 3), PRIORITY_NEIGHBOR);
+        queue.cancel_covered_by(&ApiCommand::RefreshAll);
+        assert_eq!(queue.pop(), Some(PrefetchJob::Documents(Uuid::from_u128(1))), "documents outlive a refresh");
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_cache_answers_once() {
+        let mut cache = PrefetchCache::default();
+        let job = PrefetchJob::Page { entity: EntityType::User, page: 2 };
+        assert!(!cache.has(&job));
+        cache.store(Prefetched::UsersPage(PaginatedResult::slice(Vec::new(), 2, 10)));
+        assert!(cache.has(&job));
+        assert!(cache.take_users_page(3).is_none(), "another page");
+        assert!(cache.take_users_page(2).is_some());
+        assert!(cache.take_users_page(2).is_none(), "used once");
+
+        for n in 0..=MAX_CACHED_DOCUMENTS as u128 {
+            cache.store(Prefetched::Documents { project_id: Uuid::from_u128(n), documents: Vec::new() });
+        }
+        assert!(!cache.has(&PrefetchJob::Documents(Uuid::from_u128(0))), "the oldest is dropped");
+        assert!(cache.take_documents(Uuid::from_u128(1)).is_some());
+    }
+}
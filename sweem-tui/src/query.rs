@@ -0,0 +1,106 @@
+//! Filter expression queries (`sweem-tui query`).
+//!
+//! Evaluates the expression language of the `/` filter (see `expr.rs`)
+//! against freshly fetched projects, clients or users and prints the rows
+//! that match, with the same derived fields the UI sees (`client`,
+//! `overdue`, `daysLeft`, ...). Meant for cron jobs and shell pipelines:
+//! the row formats of `export` are available, so `--format ids` prints one
+//! UUID per line and `--format count` just the number of matches.
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde_json::Value;
+use sweem_core::source::DataSource;
+
+use crate::export::{self, ExportFormat};
+use crate::expr::{self, FilterExpr};
+
+/// Entity collection to query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum QueryEntity {
+    Projects,
+    Clients,
+    Users,
+}
+
+/// Rows of the entity matching the expression, as the UI filters them today
+pub async fn matching(source: &dyn DataSource, entity: QueryEntity, expression: &FilterExpr) -> Result<Vec<Value>> {
+    let rows: Vec<Value> = match entity {
+        QueryEntity::Projects => {
+            let (projects, clients, users) =
+                tokio::try_join!(source.fetch_all_projects(), source.fetch_all_clients(), source.fetch_all_users())?;
+            let today = crate::clock::today();
+            projects.iter().map(|p| expr::project_row(p, &clients, &users, today)).collect()
+        }
+        QueryEntity::Clients => source.fetch_all_clients().await?.iter().map(expr::row).collect(),
+        QueryEntity::Users => source.fetch_all_users().await?.iter().map(expr::user_row).collect(),
+    };
+    Ok(rows.into_iter().filter(|row| expression.matches(row)).collect())
+}
+
+fn is_broken_pipe(error: &anyhow::Error) -> bool {
+    error.chain().any(|e| {
+        let kind = match e.downcast_ref::<serde_json::Error>() {
+            Some(json) => json.io_error_kind(),
+            None => e.downcast_ref::<io::Error>().map(io::Error::kind),
+        };
+        kind == Some(io::ErrorKind::BrokenPipe)
+    })
+}
+
+/// Run the query
+pub async fn run(
+    source: &dyn DataSource,
+    entity: QueryEntity,
+    expression: &FilterExpr,
+    format: ExportFormat,
+    output: Option<&Path>,
+) -> Result<()> {
+    if format.is_chart() {
+        bail!("query writes rows; use the {:?} format with export", format);
+    }
+    let rows = matching(source, entity, expression).await?;
+
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(File::create(path).with_context(|| format!("Failed to create {}", path.display()))?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let written = export::write_rows(&mut writer, &rows, format).and_then(|()| writer.flush().context("Failed to flush output"));
+    match written {
+        // `| head` closing the pipe early is not a failure
+        Err(e) if is_broken_pipe(&e) => return Ok(()),
+        result => result?,
+    }
+
+    if let Some(path) = output {
+        eprintln!("Wrote {} matching row(s) to {}", rows.len(), path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::demo::DemoSource;
+
+    #[tokio::test]
+    async fn test_query_matches_derived_fields() {
+        let source = DemoSource::new(7, NaiveDate::from_ymd_opt(2025, 3, 14).unwrap());
+        let query = |input: &str| FilterExpr::parse(input).unwrap();
+        let all = source.fetch_all_projects().await.unwrap();
+        let overdue = matching(&source, QueryEntity::Projects, &query("overdue")).await.unwrap();
+        let rest = matching(&source, QueryEntity::Projects, &query("!overdue")).await.unwrap();
+        assert!(!all.is_empty());
+        assert_eq!(overdue.len() + rest.len(), all.len());
+        assert!(overdue.iter().all(|row| row["overdue"] == Value::Bool(true) && row["client"].is_string()));
+
+        let managers = matching(&source, QueryEntity::Users, &query("manager")).await.unwrap();
+        assert!(managers.iter().all(|row| row["manager"] == Value::Bool(true)));
+    }
+}
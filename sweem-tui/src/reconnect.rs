@@ -0,0 +1,93 @@
+//! Reconnecting after the API connection drops.
+//!
+//! Every failed connection check schedules the next one with exponential
+//! backoff (2s, 4s, 8s, ... up to a minute). Frame ticks fire the check when
+//! it is due, the top bar counts down to it, and the first successful check
+//! reloads everything.
+
+use std::time::{Duration, Instant};
+
+/// Delay before the first retry
+const INITIAL_DELAY: Duration = Duration::from_secs(2);
+
+/// Longest delay between retries
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Retry schedule while disconnected
+#[derive(Debug, Clone, Default)]
+pub struct Backoff {
+    /// Failed checks since the connection was last up
+    failures: u32,
+    /// When the next check is due; `None` while one is in flight
+    next_at: Option<Instant>,
+}
+
+impl Backoff {
+    /// Delay after the given number of consecutive failures
+    pub fn delay(failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+        INITIAL_DELAY.saturating_mul(factor).min(MAX_DELAY)
+    }
+
+    /// A check failed: schedule the next one
+    pub fn failed(&mut self, now: Instant) {
+        self.failures += 1;
+        self.next_at = Some(now + Self::delay(self.failures));
+    }
+
+    /// The connection is back (or was never lost)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether reconnecting is in progress
+    pub fn is_active(&self) -> bool {
+        self.failures > 0
+    }
+
+    /// Number of the next attempt
+    pub fn attempt(&self) -> u32 {
+        self.failures
+    }
+
+    /// Time left until the next check; `None` while a check is in flight
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.next_at.map(|at| at.saturating_duration_since(now))
+    }
+
+    /// Whether the next check is due; it then counts as in flight
+    pub fn take_due(&mut self, now: Instant) -> bool {
+        match self.next_at {
+            Some(at) if at <= now => {
+                self.next_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_schedule() {
+        let delays: Vec<u64> = (1..=7).map(|n| Backoff::delay(n).as_secs()).collect();
+        assert_eq!(delays, [2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(Backoff::delay(100), MAX_DELAY);
+
+        let start = Instant::now();
+        let mut backoff = Backoff::default();
+        assert!(!backoff.is_active());
+        backoff.failed(start);
+        backoff.failed(start);
+        assert_eq!((backoff.attempt(), backoff.remaining(start)), (2, Some(Duration::from_secs(4))));
+        assert!(!backoff.take_due(start + Duration::from_secs(3)));
+        assert!(backoff.take_due(start + Duration::from_secs(4)));
+        assert_eq!(backoff.remaining(start), None, "in flight");
+        assert!(!backoff.take_due(start + Duration::from_secs(5)));
+        backoff.reset();
+        assert!(!backoff.is_active());
+    }
+}
@@ -0,0 +1,456 @@
+//! Weekly status report (`sweem-tui report`).
+//!
+//! Sums up the current week (Monday to today) from the loaded data: the
+//! projects completed this week, those that became overdue this week, the
+//! deadlines of the next two weeks and a per-client summary. The report is
+//! rendered from a Markdown or HTML template and written to stdout, a file
+//! or the clipboard, ready to paste into a status update.
+//!
+//! The built-in templates live in `templates/`. Teams with their own status
+//! format point `[report] markdown` / `html` (or `--template`) at a minijinja
+//! template of their own; it sees the report sections, the loaded entity
+//! lists and a few quarter-to-date statistics (see [`ReportContext`]).
+//!
+//! The `print` format is for stakeholders who never open a terminal: an HTML
+//! page laid out for paper with the KPIs, the Gantt chart split into pages
+//! on one time scale, and the overdue list. `pdf` prints that page through
+//! headless Chromium (or wkhtmltopdf) when one is installed.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{Duration, NaiveDate, Weekday};
+use clap::ValueEnum;
+use minijinja::Environment;
+use ratatui::style::Color;
+use serde::Serialize;
+use sweem_core::source::DataSource;
+
+use crate::clipboard;
+use crate::config::{Config, ReportConfig};
+use crate::due;
+use crate::graphics;
+use crate::markdown;
+use crate::models::{ClientDto, ProjectDto, UserDto};
+
+/// Output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Markdown,
+    /// Standalone HTML page
+    Html,
+    /// HTML laid out for paper: KPIs, the Gantt chart in pages and the overdue list
+    Print,
+    /// The `print` page as PDF (needs `--output` and Chromium or wkhtmltopdf)
+    Pdf,
+}
+
+impl ReportFormat {
+    /// Template name; the extension picks minijinja's escaping
+    fn template(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "report.md",
+            ReportFormat::Html => "report.html",
+            ReportFormat::Print | ReportFormat::Pdf => "print.html",
+        }
+    }
+
+    /// Whether the template gets the Gantt chart pages
+    fn printable(self) -> bool {
+        matches!(self, ReportFormat::Print | ReportFormat::Pdf)
+    }
+
+    /// Built-in template source
+    pub fn builtin(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => include_str!("../templates/report.md"),
+            ReportFormat::Html => include_str!("../templates/report.html"),
+            ReportFormat::Print | ReportFormat::Pdf => include_str!("../templates/print.html"),
+        }
+    }
+
+    /// Template file configured for this format, if any
+    fn configured(self, config: &ReportConfig) -> Option<PathBuf> {
+        let path = match self {
+            ReportFormat::Markdown => config.markdown.as_ref(),
+            ReportFormat::Html => config.html.as_ref(),
+            ReportFormat::Print | ReportFormat::Pdf => config.print.as_ref(),
+        }?;
+        Some(Config::dir().map_or_else(|| path.clone(), |dir| dir.join(path)))
+    }
+}
+
+/// A project as listed in the report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProjectLine {
+    pub name: String,
+    pub client: String,
+    pub manager: String,
+    pub start: NaiveDate,
+    pub planned_end: NaiveDate,
+    pub actual_end: Option<NaiveDate>,
+    /// Days from today to the planned end, negative when overdue
+    pub days_left: Option<i64>,
+}
+
+impl ProjectLine {
+    fn new(project: &ProjectDto, clients: &[ClientDto], users: &[UserDto], today: NaiveDate) -> Self {
+        Self {
+            name: project.display_name().to_string(),
+            client: clients
+                .iter()
+                .find(|c| c.id == project.client_id)
+                .map_or_else(|| project.client_id.to_string(), |c| c.display_name().to_string()),
+            manager: users.iter().find(|u| u.id == project.manager_id).map_or_else(String::new, |u| u.display_name().to_string()),
+            start: project.start_date,
+            planned_end: project.planned_end_date,
+            actual_end: project.actual_end_as_of(today),
+            days_left: project.days_left_as_of(today),
+        }
+    }
+}
+
+/// Project counts of one client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClientSummary {
+    pub client: String,
+    pub open: usize,
+    pub completed: usize,
+    pub overdue: usize,
+}
+
+/// Everything a report template can show
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WeeklyReport {
+    /// Monday of the reported week
+    pub week_start: NaiveDate,
+    pub today: NaiveDate,
+    pub completed: Vec<ProjectLine>,
+    pub newly_overdue: Vec<ProjectLine>,
+    /// Every open project past its planned end, most overdue first
+    pub overdue: Vec<ProjectLine>,
+    pub upcoming: Vec<ProjectLine>,
+    /// Clients with open projects or completions this week, by name
+    pub client_summaries: Vec<ClientSummary>,
+    pub open_total: usize,
+    pub overdue_total: usize,
+}
+
+/// Compute the report for the week containing `today`
+pub fn weekly(projects: &[ProjectDto], clients: &[ClientDto], users: &[UserDto], today: NaiveDate) -> WeeklyReport {
+    let week_start = today.week(Weekday::Mon).first_day();
+    let line = |p: &ProjectDto| ProjectLine::new(p, clients, users, today);
+    let completed_this_week = |p: &ProjectDto| p.actual_end_as_of(today).is_some_and(|end| end >= week_start);
+
+    let mut completed: Vec<&ProjectDto> = projects.iter().filter(|p| completed_this_week(p)).collect();
+    completed.sort_by_key(|p| p.actual_end_date);
+    // Overdue since a day of this week: the planned end was the day before at the earliest
+    let mut newly_overdue: Vec<&ProjectDto> = projects
+        .iter()
+        .filter(|p| p.is_overdue_as_of(today) && p.planned_end_date >= week_start - Duration::days(1))
+        .collect();
+    newly_overdue.sort_by_key(|p| p.planned_end_date);
+    let mut overdue: Vec<&ProjectDto> = projects.iter().filter(|p| p.is_overdue_as_of(today)).collect();
+    overdue.sort_by_key(|p| p.planned_end_date);
+    let upcoming: Vec<&ProjectDto> = sweem_core::stats::upcoming_deadlines(projects, today)
+        .into_iter()
+        .filter(|p| (0..=due::APPROACHING_DAYS).contains(&(p.planned_end_date - today).num_days()))
+        .collect();
+
+    let mut summaries: Vec<ClientSummary> = clients
+        .iter()
+        .map(|client| {
+            let owned: Vec<&ProjectDto> = projects.iter().filter(|p| p.client_id == client.id).collect();
+            ClientSummary {
+                client: client.display_name().to_string(),
+                open: owned.iter().filter(|p| !p.is_completed_as_of(today)).count(),
+                completed: owned.iter().filter(|p| completed_this_week(p)).count(),
+                overdue: owned.iter().filter(|p| p.is_overdue_as_of(today)).count(),
+            }
+        })
+        .filter(|s| s.open + s.completed > 0)
+        .collect();
+    summaries.sort_by_key(|s| s.client.to_lowercase());
+
+    WeeklyReport {
+        week_start,
+        today,
+        completed: completed.into_iter().map(line).collect(),
+        newly_overdue: newly_overdue.into_iter().map(line).collect(),
+        overdue: overdue.into_iter().map(line).collect(),
+        upcoming: upcoming.into_iter().map(line).collect(),
+        client_summaries: summaries,
+        open_total: projects.iter().filter(|p| !p.is_completed_as_of(today)).count(),
+        overdue_total: sweem_core::stats::overdue_count(projects, today),
+    }
+}
+
+/// Figures beyond the weekly sections, for custom templates
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    /// First day of the current quarter
+    pub quarter_start: NaiveDate,
+    /// Projects completed this quarter, and how many by their planned end
+    pub completed_quarter: usize,
+    pub on_time_quarter: usize,
+    pub on_time_percent: Option<f64>,
+    /// Average days completions this quarter ran past their planned end
+    pub average_overrun_days: Option<f64>,
+    /// Running projects in each of the next 8 weeks, starting with this one
+    pub weekly_load: Vec<u64>,
+}
+
+/// Weeks of [`Stats::weekly_load`]
+const LOAD_WEEKS: usize = 8;
+
+/// Projects per printed Gantt page
+const GANTT_PAGE_ROWS: usize = 28;
+/// Width of the printed Gantt image in cells of [`graphics::DEFAULT_CELL`]
+const GANTT_PRINT_COLUMNS: u16 = 100;
+/// Background of printed charts
+const PAPER: Color = Color::Rgb(0xff, 0xff, 0xff);
+
+/// One printed page of the Gantt chart
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GanttPage {
+    /// PNG as a `data:` URL, one [`graphics::DEFAULT_CELL`] tall row per project
+    pub image: String,
+    pub projects: Vec<ProjectLine>,
+}
+
+/// The Gantt chart of all projects by start date, cut into pages that share
+/// one time scale
+pub fn gantt_pages(projects: &[ProjectDto], clients: &[ClientDto], users: &[UserDto], today: NaiveDate) -> Result<Vec<GanttPage>> {
+    let Some(span) = graphics::gantt_span(projects) else {
+        return Ok(Vec::new());
+    };
+    let mut sorted: Vec<ProjectDto> = projects.to_vec();
+    sorted.sort_by_key(|p| p.start_date);
+    sorted
+        .chunks(GANTT_PAGE_ROWS)
+        .map(|page| {
+            let png = graphics::gantt_in(page, span, today, GANTT_PRINT_COLUMNS, graphics::DEFAULT_CELL, PAPER).png()?;
+            Ok(GanttPage {
+                image: format!("data:image/png;base64,{}", BASE64.encode(png)),
+                projects: page.iter().map(|p| ProjectLine::new(p, clients, users, today)).collect(),
+            })
+        })
+        .collect()
+}
+
+/// What a template is rendered with: the [`WeeklyReport`] fields at the top
+/// level, the entity lists as the API sends them, and [`Stats`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportContext<'a> {
+    #[serde(flatten)]
+    pub report: WeeklyReport,
+    pub stats: Stats,
+    pub projects: &'a [ProjectDto],
+    pub clients: &'a [ClientDto],
+    pub users: &'a [UserDto],
+    /// Only filled for the `print` and `pdf` formats
+    pub gantt_pages: Vec<GanttPage>,
+    /// Dates at the left and right edge of the Gantt images
+    pub gantt_span: Option<(NaiveDate, NaiveDate)>,
+}
+
+impl<'a> ReportContext<'a> {
+    pub fn new(projects: &'a [ProjectDto], clients: &'a [ClientDto], users: &'a [UserDto], today: NaiveDate) -> Self {
+        let report = weekly(projects, clients, users, today);
+        let quarter_start = sweem_core::stats::quarter_start(today);
+        let tomorrow = today + Duration::days(1);
+        let delivery = sweem_core::stats::on_time_delivery(projects, quarter_start, tomorrow, today);
+        let stats = Stats {
+            quarter_start,
+            completed_quarter: delivery.completed,
+            on_time_quarter: delivery.on_time,
+            on_time_percent: delivery.percent().map(|p| (p * 10.0).round() / 10.0),
+            average_overrun_days: sweem_core::stats::average_overrun(projects, quarter_start, tomorrow, today)
+                .map(|d| (d * 10.0).round() / 10.0),
+            weekly_load: sweem_core::stats::weekly_load(projects, report.week_start, LOAD_WEEKS),
+        };
+        Self { report, stats, projects, clients, users, gantt_pages: Vec::new(), gantt_span: None }
+    }
+
+    /// Add the Gantt chart pages
+    pub fn with_gantt(mut self) -> Result<Self> {
+        self.gantt_pages = gantt_pages(self.projects, self.clients, self.users, self.report.today)?;
+        self.gantt_span = graphics::gantt_span(self.projects);
+        Ok(self)
+    }
+}
+
+/// Render a template; `name` is shown in errors and its extension picks the
+/// escaping (`.html` escapes HTML)
+pub fn render(context: &ReportContext, name: &str, source: &str) -> Result<String> {
+    let mut env = Environment::new();
+    env.set_keep_trailing_newline(true);
+    env.add_filter("due", |days: i64| due::label(days));
+    env.add_filter("cell", |text: &str| markdown::escape(text));
+    env.add_template(name, source).with_context(|| format!("Invalid template {}", name))?;
+    let template = env.get_template(name)?;
+    template.render(context).with_context(|| format!("Failed to render {}", name))
+}
+
+/// Run the `report` command: `template` overrides the configured and the
+/// built-in template of `format`
+pub async fn run(
+    source: &dyn DataSource,
+    config: &ReportConfig,
+    format: ReportFormat,
+    template: Option<&Path>,
+    output: Option<&Path>,
+    to_clipboard: bool,
+) -> Result<()> {
+    if format == ReportFormat::Pdf && output.is_none() {
+        bail!("The pdf format needs --output FILE");
+    }
+    let (projects, clients, users) =
+        tokio::try_join!(source.fetch_all_projects(), source.fetch_all_clients(), source.fetch_all_users())?;
+    let mut context = ReportContext::new(&projects, &clients, &users, crate::clock::today());
+    if format.printable() {
+        context = context.with_gantt()?;
+    }
+    let text = match template.map(Path::to_path_buf).or_else(|| format.configured(config)) {
+        Some(path) => {
+            let template = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+            render(&context, &path.display().to_string(), &template)?
+        }
+        None => render(&context, format.template(), format.builtin())?,
+    };
+
+    if let Some(path) = output {
+        if format == ReportFormat::Pdf {
+            print_pdf(&text, path)?;
+        } else {
+            fs::write(path, &text).with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        eprintln!("Wrote the report to {}", path.display());
+    }
+    if to_clipboard {
+        clipboard::copy(&text).context("Failed to copy the report")?;
+        eprintln!("Copied the report to the clipboard");
+    }
+    if output.is_none() && !to_clipboard {
+        print!("{}", text);
+    }
+    Ok(())
+}
+
+/// Programs that can print HTML to PDF, tried in order
+const PDF_PRINTERS: [&str; 5] = ["chromium", "chromium-browser", "google-chrome", "microsoft-edge", "wkhtmltopdf"];
+
+/// Command line of a [`PDF_PRINTERS`] program printing `input` to `output`
+fn printer_args(program: &str, input: &Path, output: &Path) -> Vec<String> {
+    if program == "wkhtmltopdf" {
+        return vec![input.display().to_string(), output.display().to_string()];
+    }
+    vec![
+        "--headless".to_string(),
+        "--disable-gpu".to_string(),
+        "--no-pdf-header-footer".to_string(),
+        format!("--print-to-pdf={}", output.display()),
+        format!("file://{}", input.display()),
+    ]
+}
+
+/// Print an HTML page to a PDF file with the first installed printer
+fn print_pdf(html: &str, output: &Path) -> Result<()> {
+    // A fresh file only we can read, removed when dropped; the printers need the suffix
+    let mut page = tempfile::Builder::new()
+        .prefix("sweem-report-")
+        .suffix(".html")
+        .tempfile()
+        .context("Failed to create a temporary file")?;
+    page.write_all(html.as_bytes()).and_then(|()| page.flush()).context("Failed to write the page to print")?;
+    let input = page.path();
+    // Chromium resolves the output against its own working directory
+    let output = std::path::absolute(output)?;
+    let result = PDF_PRINTERS.iter().find_map(|program| {
+        match Command::new(program).args(printer_args(program, input, &output)).output() {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => Some(Err(anyhow::Error::new(e).context(format!("Failed to run {}", program)))),
+            Ok(run) if run.status.success() => Some(Ok(())),
+            Ok(run) => Some(Err(anyhow::anyhow!(
+                "{} failed ({}): {}",
+                program,
+                run.status,
+                String::from_utf8_lossy(&run.stderr).trim()
+            ))),
+        }
+    });
+    match result {
+        Some(result) => result,
+        None => bail!("Printing to PDF needs Chromium, Chrome, Edge or wkhtmltopdf on the PATH; use --format print and print the page from a browser instead"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sweem_core::fixtures;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_weekly_report() {
+        // Wednesday
+        let today = NaiveDate::from_ymd_opt(2025, 3, 5).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let client = fixtures::client(Uuid::from_u128(1), "Acme");
+        let project = |name: &str, planned, actual: Option<u32>| ProjectDto {
+            client_id: client.id,
+            name: Some(name.to_string()),
+            actual_end_date: actual.map(day),
+            ..fixtures::project(day(1), day(planned))
+        };
+        let projects = [
+            project("Shipped", 4, Some(3)),
+            project("Late", 2, None),
+            project("Long late", 1, None),
+            project("Soon", 10, None),
+            project("Later", 31, None),
+            project("Future delivery", 19, Some(6)),
+        ];
+        let report = weekly(&projects, std::slice::from_ref(&client), &[], today);
+        let names = |lines: &[ProjectLine]| lines.iter().map(|l| l.name.clone()).collect::<Vec<_>>();
+
+        assert_eq!(report.week_start, day(3));
+        assert_eq!(names(&report.completed), ["Shipped"]);
+        assert_eq!(names(&report.newly_overdue), ["Late"]);
+        assert_eq!(names(&report.upcoming), ["Soon", "Future delivery"]);
+        assert_eq!(report.client_summaries, [ClientSummary { client: "Acme".into(), open: 5, completed: 1, overdue: 2 }]);
+
+        let context = ReportContext::new(&projects, std::slice::from_ref(&client), &[], today);
+        assert_eq!(context.report, report);
+        let markdown = render(&context, "report.md", ReportFormat::Markdown.builtin()).unwrap();
+        assert!(markdown.contains("| Late | Acme |  | 2025-03-02 | overdue by 3d |") && markdown.ends_with("\n"), "{}", markdown);
+        let html = render(&context, "report.html", ReportFormat::Html.builtin()).unwrap();
+        assert!(html.contains("<td>Shipped</td>"), "{}", html);
+        let mut empty = ReportContext::new(&projects, std::slice::from_ref(&client), &[], today);
+        empty.report.client_summaries.clear();
+        let html = render(&empty, "report.html", ReportFormat::Html.builtin()).unwrap();
+        assert!(html.contains("<p>No clients with open projects.</p>"), "{}", html);
+
+        // Custom templates see the entity lists and the statistics, and HTML ones are escaped
+        let custom = "{{ projects | length }} projects, {{ stats.on_time_percent }}% on time <{{ clients[0].name }}>";
+        assert_eq!(render(&context, "team.md", custom).unwrap(), "6 projects, 100.0% on time <Acme>");
+        assert_eq!(render(&context, "team.html", "{{ clients[0].name ~ ' & co' }}").unwrap(), "Acme &amp; co");
+        assert_eq!(context.report.overdue.iter().map(|l| l.name.as_str()).collect::<Vec<_>>(), ["Long late", "Late"]);
+
+        // The printable page gets the chart on one scale, one page per GANTT_PAGE_ROWS projects
+        let many: Vec<ProjectDto> = (0..GANTT_PAGE_ROWS + 1).map(|_| projects[0].clone()).collect();
+        let printable = ReportContext::new(&many, &[], &[], today).with_gantt().unwrap();
+        assert_eq!(printable.gantt_pages.iter().map(|p| p.projects.len()).collect::<Vec<_>>(), [GANTT_PAGE_ROWS, 1]);
+        assert!(printable.gantt_pages[0].image.starts_with("data:image/png;base64,"));
+        let print = render(&printable, "print.html", ReportFormat::Print.builtin()).unwrap();
+        assert!(print.contains("Gantt chart (2/2)") && print.contains("No project is overdue."), "{}", print);
+        let error = render(&context, "broken.md", "{% for %}").unwrap_err();
+        assert!(format!("{:#}", error).starts_with("Invalid template broken.md"));
+    }
+}
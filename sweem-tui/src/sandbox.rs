@@ -0,0 +1,130 @@
+//! What-if schedule simulation sandbox.
+//!
+//! While the sandbox is active, project bars can be shifted and extended
+//! locally without any API calls. The original schedule is kept as a
+//! baseline so the changes can be discarded or committed as a batch.
+
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
+use uuid::Uuid;
+
+use crate::models::{ProjectDto, UpdateProjectDto};
+
+/// Local schedule edits on top of a baseline snapshot
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    /// Projects as they were when the sandbox was entered (or last refreshed)
+    baseline: Vec<ProjectDto>,
+}
+
+impl Sandbox {
+    /// Start a sandbox session from the current projects
+    pub fn new(projects: &[ProjectDto]) -> Self {
+        Self {
+            baseline: projects.to_vec(),
+        }
+    }
+
+    /// Projects as they were before any sandbox edits
+    pub fn baseline(&self) -> &[ProjectDto] {
+        &self.baseline
+    }
+
+    /// Move a project (start and planned end) by the given number of days
+    pub fn shift(project: &mut ProjectDto, days: i64) {
+        project.start_date += Duration::days(days);
+        project.planned_end_date += Duration::days(days);
+    }
+
+    /// Extend (or shrink) a project's planned end, never before its start
+    pub fn extend(project: &mut ProjectDto, days: i64) {
+        let end = project.planned_end_date + Duration::days(days);
+        project.planned_end_date = end.max(project.start_date);
+    }
+
+    /// Projects whose schedule differs from the baseline
+    pub fn changes<'a>(&self, projects: &'a [ProjectDto]) -> Vec<&'a ProjectDto> {
+        let baseline: HashMap<Uuid, (NaiveDate, NaiveDate)> = self
+            .baseline
+            .iter()
+            .map(|p| (p.id, (p.start_date, p.planned_end_date)))
+            .collect();
+
+        projects
+            .iter()
+            .filter(|p| {
+                baseline
+                    .get(&p.id)
+                    .is_some_and(|dates| *dates != (p.start_date, p.planned_end_date))
+            })
+            .collect()
+    }
+
+    /// Update DTOs for committing all changes as a batch
+    pub fn commit_batch(&self, projects: &[ProjectDto]) -> Vec<(Uuid, UpdateProjectDto)> {
+        self.changes(projects)
+            .into_iter()
+            .map(|p| (p.id, UpdateProjectDto::from_project(p)))
+            .collect()
+    }
+
+    /// Replace the baseline with freshly loaded projects and re-apply local edits
+    pub fn rebase(&mut self, fresh: Vec<ProjectDto>, current: &[ProjectDto]) -> Vec<ProjectDto> {
+        let edits: HashMap<Uuid, (NaiveDate, NaiveDate)> = self
+            .changes(current)
+            .into_iter()
+            .map(|p| (p.id, (p.start_date, p.planned_end_date)))
+            .collect();
+
+        self.baseline = fresh.clone();
+        fresh
+            .into_iter()
+            .map(|mut p| {
+                if let Some((start, end)) = edits.get(&p.id) {
+                    p.start_date = *start;
+                    p.planned_end_date = *end;
+                }
+                p
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sweem_core::fixtures;
+
+    use super::*;
+
+    fn project(start: (i32, u32, u32), end: (i32, u32, u32)) -> ProjectDto {
+        let date = |(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        ProjectDto {
+            client_id: Uuid::new_v4(),
+            manager_id: Uuid::new_v4(),
+            name: Some("Test".to_string()),
+            ..fixtures::project(date(start), date(end))
+        }
+    }
+
+    #[test]
+    fn test_sandbox_changes_and_rebase() {
+        let mut projects = vec![project((2024, 1, 1), (2024, 1, 31)), project((2024, 2, 1), (2024, 2, 10))];
+        let mut sandbox = Sandbox::new(&projects);
+        assert!(sandbox.changes(&projects).is_empty());
+
+        Sandbox::shift(&mut projects[0], 3);
+        Sandbox::extend(&mut projects[1], -100);
+        assert_eq!(projects[0].planned_end_date, NaiveDate::from_ymd_opt(2024, 2, 3).unwrap());
+        assert_eq!(projects[1].planned_end_date, projects[1].start_date);
+        assert_eq!(sandbox.commit_batch(&projects).len(), 2);
+
+        // A refresh brings a renamed project; the local edit survives
+        let mut fresh = sandbox.baseline().to_vec();
+        fresh[0].name = Some("Renamed".to_string());
+        let rebased = sandbox.rebase(fresh, &projects);
+        assert_eq!(rebased[0].name.as_deref(), Some("Renamed"));
+        assert_eq!(rebased[0].start_date, projects[0].start_date);
+        assert_eq!(sandbox.changes(&rebased).len(), 2);
+    }
+}
@@ -0,0 +1,261 @@
+//! Optional user scripting (Rhai), enabled with the `scripting` feature.
+//!
+//! `$XDG_CONFIG_HOME/sweem-tui/script.rhai` may define any of:
+//!
+//! ```rhai
+//! fn keep_project(p) { p.name != () }          // row filters, applied on load
+//! fn keep_client(c) { true }
+//! fn keep_user(u) { u.role == 0 }
+//! fn client_column(c) { c.address.len() }     // computed column in the Clients list
+//! fn user_column(u) { u.login.sub_string(0, 3) }
+//! fn macros() { #{ "F5": ["Tab", "Tab", "r"] } } // keybinding macros on F5-F12
+//! ```
+//!
+//! Entities are passed as maps with the API's camelCase field names. Missing
+//! functions are simply skipped; runtime errors are logged and ignored.
+
+#![allow(dead_code)]
+
+use crossterm::event::KeyEvent;
+
+#[cfg(feature = "scripting")]
+pub use imp::Scripts;
+#[cfg(not(feature = "scripting"))]
+pub use stub::Scripts;
+
+/// File name of the script inside the config directory
+pub const SCRIPT_FILE: &str = "script.rhai";
+
+/// Parse a macro key name ("j", "Tab", "Enter", ...) into a key event
+pub fn parse_key(name: &str) -> Option<KeyEvent> {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let code = match name {
+        "Tab" => KeyCode::Tab,
+        "BackTab" => KeyCode::BackTab,
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Home" => KeyCode::Home,
+        "Space" => KeyCode::Char(' '),
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+    Some(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+#[cfg(feature = "scripting")]
+mod imp {
+    use std::collections::HashMap;
+
+    use anyhow::{Context, Result};
+    use crossterm::event::KeyEvent;
+    use rhai::{Dynamic, Engine, Scope, AST};
+    use serde::Serialize;
+
+    use super::{parse_key, SCRIPT_FILE};
+    use crate::config::Config;
+    use crate::models::{ClientDto, ProjectDto, UserDto};
+
+    /// A compiled user script
+    #[derive(Debug)]
+    pub struct Scripts {
+        engine: Engine,
+        ast: AST,
+        /// Function key number -> keys to replay
+        macros: HashMap<u8, Vec<KeyEvent>>,
+    }
+
+    impl Scripts {
+        /// Compile the user script, if there is one
+        pub fn load() -> Result<Option<Self>> {
+            let Some(path) = Config::dir().map(|dir| dir.join(SCRIPT_FILE)) else {
+                return Ok(None);
+            };
+            if !path.exists() {
+                return Ok(None);
+            }
+            let source = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            Self::compile(&source)
+                .with_context(|| format!("Invalid script {}", path.display()))
+                .map(Some)
+        }
+
+        /// Compile a script from source
+        pub fn compile(source: &str) -> Result<Self> {
+            let mut engine = Engine::new();
+            engine.set_max_operations(100_000);
+            let ast = engine.compile(source).map_err(|e| anyhow::anyhow!("{}", e))?;
+
+            let mut scripts = Self { engine, ast, macros: HashMap::new() };
+            if let Some(map) = scripts.call("macros", ()).and_then(|m| m.try_cast::<rhai::Map>()) {
+                for (key, keys) in map {
+                    let Some(n) = key.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) else {
+                        continue;
+                    };
+                    let keys = keys
+                        .into_array()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(|k| k.into_string().ok())
+                        .filter_map(|k| parse_key(&k))
+                        .collect();
+                    scripts.macros.insert(n, keys);
+                }
+            }
+            Ok(scripts)
+        }
+
+        /// Call a script function if it is defined; errors are logged
+        fn call(&self, name: &str, args: impl rhai::FuncArgs) -> Option<Dynamic> {
+            if !self.ast.iter_functions().any(|f| f.name == name) {
+                return None;
+            }
+            match self.engine.call_fn::<Dynamic>(&mut Scope::new(), &self.ast, name, args) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    tracing::warn!("Script function {} failed: {}", name, e);
+                    None
+                }
+            }
+        }
+
+        fn call_with(&self, name: &str, entity: &impl Serialize) -> Option<Dynamic> {
+            let value = rhai::serde::to_dynamic(entity).ok()?;
+            self.call(name, (value,))
+        }
+
+        fn keep(&self, name: &str, entity: &impl Serialize) -> bool {
+            self.call_with(name, entity)
+                .and_then(|v| v.as_bool().ok())
+                .unwrap_or(true)
+        }
+
+        fn column(&self, name: &str, entity: &impl Serialize) -> Option<String> {
+            self.call_with(name, entity)
+                .filter(|v| !v.is_unit())
+                .map(|v| v.to_string())
+        }
+
+        pub fn keep_project(&self, project: &ProjectDto) -> bool {
+            self.keep("keep_project", project)
+        }
+
+        pub fn keep_client(&self, client: &ClientDto) -> bool {
+            self.keep("keep_client", client)
+        }
+
+        pub fn keep_user(&self, user: &UserDto) -> bool {
+            self.keep("keep_user", user)
+        }
+
+        pub fn client_column(&self, client: &ClientDto) -> Option<String> {
+            self.column("client_column", client)
+        }
+
+        pub fn user_column(&self, user: &UserDto) -> Option<String> {
+            self.column("user_column", user)
+        }
+
+        /// Keys bound to function key `n` by `macros()`
+        pub fn macro_keys(&self, n: u8) -> Option<&[KeyEvent]> {
+            self.macros.get(&n).map(Vec::as_slice)
+        }
+    }
+}
+
+#[cfg(not(feature = "scripting"))]
+mod stub {
+    use anyhow::Result;
+    use crossterm::event::KeyEvent;
+
+    use crate::models::{ClientDto, ProjectDto, UserDto};
+
+    /// Scripting is compiled out; nothing is ever loaded
+    #[derive(Debug)]
+    pub struct Scripts;
+
+    impl Scripts {
+        pub fn load() -> Result<Option<Self>> {
+            Ok(None)
+        }
+
+        pub fn keep_project(&self, _project: &ProjectDto) -> bool {
+            true
+        }
+
+        pub fn keep_client(&self, _client: &ClientDto) -> bool {
+            true
+        }
+
+        pub fn keep_user(&self, _user: &UserDto) -> bool {
+            true
+        }
+
+        pub fn client_column(&self, _client: &ClientDto) -> Option<String> {
+            None
+        }
+
+        pub fn user_column(&self, _user: &UserDto) -> Option<String> {
+            None
+        }
+
+        pub fn macro_keys(&self, _n: u8) -> Option<&[KeyEvent]> {
+            None
+        }
+    }
+}
+
+/// Apply the script's row filters to freshly loaded entities
+pub fn filter_loaded<T>(scripts: Option<&Scripts>, items: Vec<T>, keep: impl Fn(&Scripts, &T) -> bool) -> Vec<T> {
+    match scripts {
+        Some(scripts) => items.into_iter().filter(|item| keep(scripts, item)).collect(),
+        None => items,
+    }
+}
+
+#[cfg(all(test, feature = "scripting"))]
+mod tests {
+    use super::*;
+    use crate::models::UserDto;
+    use chrono::NaiveDate;
+    use crossterm::event::KeyCode;
+    use sweem_core::fixtures;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_script_filters_columns_and_macros() {
+        let scripts = Scripts::compile(
+            r#"
+            fn keep_project(p) { p.name != () }
+            fn client_column(c) { c.name.len() }
+            fn macros() { #{ "F5": ["Tab", "r"] } }
+            "#,
+        )
+        .unwrap();
+
+        let mut project =
+            fixtures::project(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2024, 1, 31).unwrap());
+        assert!(!scripts.keep_project(&project));
+        project.name = Some("Apollo".to_string());
+        assert!(scripts.keep_project(&project));
+
+        let client = fixtures::client(Uuid::new_v4(), "Acme");
+        assert_eq!(scripts.client_column(&client).as_deref(), Some("4"));
+        let user = UserDto { name: None, ..fixtures::user(Uuid::new_v4(), "") };
+        assert!(scripts.user_column(&user).is_none());
+
+        let keys = scripts.macro_keys(5).unwrap();
+        assert_eq!(keys[0].code, KeyCode::Tab);
+        assert_eq!(keys[1].code, KeyCode::Char('r'));
+    }
+}
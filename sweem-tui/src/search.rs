@@ -0,0 +1,186 @@
+//! Global search (`Ctrl+f`).
+//!
+//! A trigram index over the loaded projects, clients and users: every
+//! three-character window of an entity's names maps to the entities that
+//! contain it, so a query only scores the entities sharing trigrams with it
+//! instead of scanning tens of thousands of rows per keystroke. Matching a
+//! third of the query's trigrams is enough to be a hit, which tolerates
+//! typos ("platfrom" finds "Data Platform"). The index is rebuilt from local
+//! data on every load, recorded snapshots included, so search works offline.
+//! It holds every loaded row, also those the filters hide.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::api::EntityType;
+use crate::models::{ClientDto, ProjectDto, UserDto};
+
+/// Results shown at most
+pub const MAX_RESULTS: usize = 50;
+
+/// A searchable entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchDoc {
+    pub entity_type: EntityType,
+    pub id: Uuid,
+    /// Display name
+    pub label: String,
+    /// Secondary text (client and manager, address, login)
+    pub detail: String,
+    /// Normalized label and detail that trigrams are taken from
+    text: String,
+}
+
+impl SearchDoc {
+    fn new(entity_type: EntityType, id: Uuid, label: &str, detail: String) -> Self {
+        let text = normalize(&format!("{} {}", label, detail));
+        Self { entity_type, id, label: label.to_string(), detail, text }
+    }
+
+    pub fn project(project: &ProjectDto, names: &Names) -> Self {
+        let client = names.clients.get(&project.client_id).copied().unwrap_or("");
+        let manager = names.users.get(&project.manager_id).copied().unwrap_or("");
+        Self::new(EntityType::Project, project.id, project.display_name(), format!("{} · {}", client, manager))
+    }
+
+    pub fn client(client: &ClientDto) -> Self {
+        Self::new(EntityType::Client, client.id, client.display_name(), client.address.clone().unwrap_or_default())
+    }
+
+    pub fn user(user: &UserDto) -> Self {
+        Self::new(EntityType::User, user.id, user.display_name(), user.login.clone().unwrap_or_default())
+    }
+}
+
+/// Client and user names by id, built once per load for [`SearchDoc::project`]
+#[derive(Debug, Default)]
+pub struct Names<'a> {
+    clients: HashMap<Uuid, &'a str>,
+    users: HashMap<Uuid, &'a str>,
+}
+
+impl<'a> Names<'a> {
+    pub fn new(clients: &'a [ClientDto], users: &'a [UserDto]) -> Self {
+        Self {
+            clients: clients.iter().map(|c| (c.id, c.display_name())).collect(),
+            users: users.iter().map(|u| (u.id, u.display_name())).collect(),
+        }
+    }
+}
+
+/// Lowercase words separated by single spaces
+fn normalize(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Trigrams of every word, padded so short words and word starts count too
+fn trigrams(text: &str) -> Vec<[char; 3]> {
+    let mut out = Vec::new();
+    for word in text.split(' ').filter(|w| !w.is_empty()) {
+        let chars: Vec<char> = std::iter::once(' ').chain(word.chars()).chain(std::iter::once(' ')).collect();
+        out.extend(chars.windows(3).map(|w| [w[0], w[1], w[2]]));
+    }
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+/// Search popup state
+#[derive(Debug, Clone, Default)]
+pub struct Search {
+    pub input: String,
+    pub selected: usize,
+    pub results: Vec<SearchDoc>,
+}
+
+/// Trigram index over all loaded entities
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    docs: Vec<SearchDoc>,
+    postings: HashMap<[char; 3], Vec<u32>>,
+}
+
+impl SearchIndex {
+    /// Replace the entities of one type
+    pub fn replace(&mut self, entity_type: EntityType, docs: impl IntoIterator<Item = SearchDoc>) {
+        self.docs.retain(|doc| doc.entity_type != entity_type);
+        self.docs.extend(docs);
+        self.postings.clear();
+        for (index, doc) in self.docs.iter().enumerate() {
+            for trigram in trigrams(&doc.text) {
+                self.postings.entry(trigram).or_default().push(index as u32);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    /// Best matches first
+    pub fn query(&self, query: &str) -> Vec<&SearchDoc> {
+        let query = normalize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let grams = trigrams(&query);
+        let mut hits: HashMap<u32, usize> = HashMap::new();
+        for gram in &grams {
+            for &index in self.postings.get(gram).into_iter().flatten() {
+                *hits.entry(index).or_default() += 1;
+            }
+        }
+        let needed = grams.len().div_ceil(3);
+        let mut scored: Vec<(usize, &SearchDoc)> = hits
+            .into_iter()
+            .filter(|&(_, count)| count >= needed)
+            .map(|(index, count)| {
+                let doc = &self.docs[index as usize];
+                let label = doc.label.to_lowercase();
+                // Exact text outranks any number of shared trigrams, names outrank details
+                let bonus = if label.starts_with(&query) {
+                    3000
+                } else if label.contains(&query) {
+                    2000
+                } else if doc.text.contains(&query) {
+                    1000
+                } else {
+                    0
+                };
+                (bonus + count * 100 / grams.len(), doc)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.label.cmp(&b.1.label)));
+        scored.into_iter().take(MAX_RESULTS).map(|(_, doc)| doc).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_ranks_and_tolerates_typos() {
+        let doc = |entity_type, label: &str, detail: &str| SearchDoc::new(entity_type, Uuid::new_v4(), label, detail.to_string());
+        let mut index = SearchIndex::default();
+        index.replace(
+            EntityType::Project,
+            [
+                doc(EntityType::Project, "Data Platform", "Acme Corp · Alice"),
+                doc(EntityType::Project, "Website", "Acme Corp · Bob"),
+            ],
+        );
+        index.replace(EntityType::Client, [doc(EntityType::Client, "Acme Corp", "1 Main St")]);
+        let labels = |index: &SearchIndex, query| index.query(query).iter().map(|d| d.label.clone()).collect::<Vec<_>>();
+
+        assert_eq!(labels(&index, "acme"), ["Acme Corp", "Data Platform", "Website"], "name matches first");
+        assert_eq!(labels(&index, "platfrom"), ["Data Platform"]);
+        assert_eq!(labels(&index, "bob"), ["Website"]);
+        assert!(labels(&index, "zz").is_empty());
+
+        index.replace(EntityType::Client, []);
+        assert_eq!(index.len(), 2);
+        assert_eq!(labels(&index, "main st"), Vec::<String>::new());
+    }
+}
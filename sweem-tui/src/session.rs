@@ -0,0 +1,221 @@
+//! Session recording and deterministic replay.
+//!
+//! `--record FILE` writes every message the event loop applies (key presses,
+//! focus and resize events, API results) with its time since startup, as
+//! JSON Lines: a header with everything else the app depends on (date,
+//! particle seed, terminal size, effective config and UI state), then one
+//! line per message. `--replay FILE` runs the TUI without an API worker and
+//! feeds the recorded messages back at their original times, so the same
+//! keys meet the same data on the same day. Terminal input is ignored while
+//! replaying (except Ctrl+C) and takes over once the recording has ended.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::api::EntityType;
+use crate::config::Config;
+use crate::state::UiState;
+use crate::update::Msg;
+
+/// Version of the file format, bumped on incompatible changes
+const FORMAT_VERSION: u32 = 1;
+
+/// First line of a session file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionHeader {
+    pub version: u32,
+    /// `clock::today()` when the session was recorded
+    pub date: NaiveDate,
+    /// Particle system seed
+    pub seed: u64,
+    /// Terminal size at startup
+    pub width: u16,
+    pub height: u16,
+    /// Effective configuration, with the locale resolved
+    pub config: Config,
+    /// Persisted UI state (bookmarks) at startup
+    pub ui_state: UiState,
+    /// Login used for the "My projects" view
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Entity selected after the initial load (`--focus`)
+    #[serde(default)]
+    pub focus: Option<(EntityType, Uuid)>,
+}
+
+impl SessionHeader {
+    pub fn new(
+        date: NaiveDate,
+        seed: u64,
+        (width, height): (u16, u16),
+        config: Config,
+        ui_state: UiState,
+        user: Option<String>,
+        focus: Option<(EntityType, Uuid)>,
+    ) -> Self {
+        Self { version: FORMAT_VERSION, date, seed, width, height, config, ui_state, user, focus }
+    }
+}
+
+/// One recorded message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionEvent {
+    /// Milliseconds since the session started
+    pub at_ms: u64,
+    pub msg: Msg,
+}
+
+/// Appends messages to a session file as they happen
+pub struct Recorder {
+    out: BufWriter<File>,
+    started: Instant,
+}
+
+impl Recorder {
+    /// Create the file and write the header
+    pub fn create(path: &Path, header: &SessionHeader) -> Result<Self> {
+        let file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut recorder = Self { out: BufWriter::new(file), started: Instant::now() };
+        recorder.write_line(header)?;
+        Ok(recorder)
+    }
+
+    /// Record a message (frame ticks and signals are skipped)
+    pub fn record(&mut self, msg: &Msg) -> Result<()> {
+        if !msg.is_recorded() {
+            return Ok(());
+        }
+        let at_ms = self.started.elapsed().as_millis() as u64;
+        self.write_line(&SessionEvent { at_ms, msg: msg.clone() })
+    }
+
+    /// Write one line and flush it, so a crash loses nothing
+    fn write_line(&mut self, value: &impl Serialize) -> Result<()> {
+        serde_json::to_writer(&mut self.out, value)?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// A session loaded for replay
+#[derive(Debug)]
+pub struct Session {
+    pub header: SessionHeader,
+    pub events: Vec<SessionEvent>,
+}
+
+impl Session {
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut lines = BufReader::new(file).lines();
+        let first = lines.next().transpose()?.unwrap_or_default();
+        let header: SessionHeader = serde_json::from_str(&first)
+            .with_context(|| format!("{} is not a session recording", path.display()))?;
+        if header.version != FORMAT_VERSION {
+            bail!("{} has session format version {}, expected {}", path.display(), header.version, FORMAT_VERSION);
+        }
+        let mut events = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = serde_json::from_str(&line)
+                .with_context(|| format!("{}:{}: invalid session event", path.display(), index + 2))?;
+            events.push(event);
+        }
+        Ok(Self { header, events })
+    }
+
+    /// Length of the recording
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.events.last().map_or(0, |e| e.at_ms))
+    }
+}
+
+/// Feed recorded messages at their original times, then pass terminal input through
+pub fn spawn_replay(events: Vec<SessionEvent>, mut terminal: mpsc::Receiver<Msg>) -> mpsc::Receiver<Msg> {
+    let (tx, rx) = mpsc::channel(64);
+    tokio::spawn(async move {
+        let started = tokio::time::Instant::now();
+        for event in events {
+            let due = started + Duration::from_millis(event.at_ms);
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep_until(due) => break,
+                    Some(msg) = terminal.recv() => {
+                        if is_interrupt(&msg) {
+                            tx.send(Msg::Terminate("Ctrl+C")).await.ok();
+                            return;
+                        }
+                    }
+                }
+            }
+            if tx.send(event.msg).await.is_err() {
+                return;
+            }
+        }
+        while let Some(msg) = terminal.recv().await {
+            if tx.send(msg).await.is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+fn is_interrupt(msg: &Msg) -> bool {
+    matches!(msg, Msg::Key(key) if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ApiMessage, AppError, ApiError};
+    use crossterm::event::KeyEvent;
+
+    #[test]
+    fn test_recording_round_trips() {
+        let path = std::env::temp_dir().join(format!("sweem-session-{}.json", std::process::id()));
+        let header = SessionHeader::new(
+            NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+            42,
+            (100, 30),
+            Config::default(),
+            UiState::default(),
+            Some("alice".to_string()),
+            None,
+        );
+        let timeout = ApiError::Timeout { endpoint: "GET /projects".into() };
+        let mut recorder = Recorder::create(&path, &header).unwrap();
+        for msg in [
+            Msg::Key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE)),
+            Msg::Tick { width: 100, height: 30, animate: true },
+            Msg::Api(ApiMessage::Error(AppError::api("Load projects", timeout))),
+            Msg::Resize,
+        ] {
+            recorder.record(&msg).unwrap();
+        }
+
+        let session = Session::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!((session.header.seed, session.header.width), (42, 100));
+        assert_eq!(session.events.len(), 3, "ticks are not recorded");
+        assert!(matches!(&session.events[0].msg, Msg::Key(key) if key.code == KeyCode::Char('j')));
+        match &session.events[1].msg {
+            Msg::Api(ApiMessage::Error(error)) => assert_eq!(error.to_string(), "Load projects failed: GET /projects timed out"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(session.events.windows(2).all(|w| w[0].at_ms <= w[1].at_ms));
+    }
+}
@@ -0,0 +1,187 @@
+//! Settings dialog (`,`).
+//!
+//! The config values people tune most can be changed without editing TOML
+//! and restarting. `j`/`k` pick a setting and `h`/`l` step through its
+//! values; each step applies right away (the language switches, the
+//! background and frame rate change, watch mode polls at the new interval).
+//! Closing the dialog writes the config file when anything changed, keeping
+//! the previous one as `config.toml.bak`.
+
+use crate::app::Tab;
+use crate::config::{Config, StartTab};
+use crate::i18n::Locale;
+use crate::particles::ParticleMode;
+use crate::tr;
+
+const LANGUAGES: [Option<Locale>; 3] = [None, Some(Locale::En), Some(Locale::Ru)];
+const PARTICLE_MODES: [ParticleMode; 6] = [
+    ParticleMode::DigitalRain,
+    ParticleMode::Starfield,
+    ParticleMode::Plasma,
+    ParticleMode::Snow,
+    ParticleMode::Confetti,
+    ParticleMode::None,
+];
+const DENSITIES: [f32; 6] = [0.0, 0.25, 0.5, 1.0, 1.5, 2.0];
+const FRAME_RATES: [u32; 6] = [10, 15, 20, 30, 60, 120];
+/// Watch-mode poll intervals in seconds (0 = off)
+const WATCH_SECS: [u64; 6] = [0, 5, 10, 30, 60, 300];
+const STALE_MINUTES: [u64; 5] = [0, 5, 10, 30, 60];
+const START_TABS: [StartTab; 7] = [
+    StartTab::Timeline,
+    StartTab::Projects,
+    StartTab::Clients,
+    StartTab::Users,
+    StartTab::Stats,
+    StartTab::Inbox,
+    StartTab::Audit,
+];
+const ZOOMS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+
+/// A value the dialog can change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    Language,
+    Particles,
+    Density,
+    Fps,
+    Watch,
+    StaleAfter,
+    StartTab,
+    Zoom,
+    Notifications,
+}
+
+impl Setting {
+    /// Rows of the dialog, in order
+    pub const ALL: [Setting; 9] = [
+        Setting::Language,
+        Setting::Particles,
+        Setting::Density,
+        Setting::Fps,
+        Setting::Watch,
+        Setting::StaleAfter,
+        Setting::StartTab,
+        Setting::Zoom,
+        Setting::Notifications,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Setting::Language => tr!("settings-language"),
+            Setting::Particles => tr!("settings-particles"),
+            Setting::Density => tr!("settings-density"),
+            Setting::Fps => tr!("settings-fps"),
+            Setting::Watch => tr!("settings-watch"),
+            Setting::StaleAfter => tr!("settings-stale"),
+            Setting::StartTab => tr!("settings-start-tab"),
+            Setting::Zoom => tr!("settings-zoom"),
+            Setting::Notifications => tr!("settings-notifications"),
+        }
+    }
+
+    /// The setting's current value as shown in the dialog
+    pub fn value(self, config: &Config) -> String {
+        match self {
+            Setting::Language => match config.ui.locale {
+                Some(locale) => locale.name().to_string(),
+                None => tr!("settings-auto").to_string(),
+            },
+            Setting::Particles => particle_mode(config).name().to_string(),
+            Setting::Density => format!("{:.2}", config.particles.density),
+            Setting::Fps => tr!("settings-fps-value", fps = config.render.fps),
+            Setting::Watch => match config.api.watch_secs {
+                0 => tr!("settings-off").to_string(),
+                secs => tr!("settings-seconds", secs = secs),
+            },
+            Setting::StaleAfter => match config.ui.stale_after_minutes {
+                0 => tr!("settings-never").to_string(),
+                minutes => tr!("settings-minutes", minutes = minutes),
+            },
+            Setting::StartTab => Tab::from(config.ui.start_tab).name().to_string(),
+            Setting::Zoom => tr!("settings-zoom-value", days = config.ui.timeline_zoom),
+            Setting::Notifications => {
+                if config.notifications.enabled { tr!("settings-on") } else { tr!("settings-off") }.to_string()
+            }
+        }
+    }
+
+    /// Move the setting to its next (or previous) value, wrapping around
+    pub fn step(self, config: &mut Config, forward: bool) {
+        match self {
+            Setting::Language => config.ui.locale = cycle(&LANGUAGES, config.ui.locale, forward),
+            Setting::Particles => {
+                let mode = cycle(&PARTICLE_MODES, particle_mode(config), forward);
+                config.particles.enabled = mode != ParticleMode::None;
+                config.particles.mode = Some(mode);
+            }
+            Setting::Density => config.particles.density = cycle(&DENSITIES, config.particles.density, forward),
+            Setting::Fps => config.render.fps = cycle(&FRAME_RATES, config.render.fps, forward),
+            Setting::Watch => config.api.watch_secs = cycle(&WATCH_SECS, config.api.watch_secs, forward),
+            Setting::StaleAfter => {
+                config.ui.stale_after_minutes = cycle(&STALE_MINUTES, config.ui.stale_after_minutes, forward)
+            }
+            Setting::StartTab => config.ui.start_tab = cycle(&START_TABS, config.ui.start_tab, forward),
+            Setting::Zoom => config.ui.timeline_zoom = cycle(&ZOOMS, config.ui.timeline_zoom, forward),
+            Setting::Notifications => config.notifications.enabled = !config.notifications.enabled,
+        }
+    }
+}
+
+/// Background mode the config starts with
+pub fn particle_mode(config: &Config) -> ParticleMode {
+    if config.particles.enabled { config.particles.mode.unwrap_or_default() } else { ParticleMode::None }
+}
+
+/// The choice after (or before) `current`; a value set in the config file
+/// that is not one of the choices moves to the first or last one
+fn cycle<T: Copy + PartialEq>(choices: &[T], current: T, forward: bool) -> T {
+    let index = match (choices.iter().position(|c| *c == current), forward) {
+        (Some(i), true) => (i + 1) % choices.len(),
+        (Some(i), false) => i.checked_sub(1).unwrap_or(choices.len() - 1),
+        (None, true) => 0,
+        (None, false) => choices.len() - 1,
+    };
+    choices[index]
+}
+
+/// Open settings dialog
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SettingsDialog {
+    /// Highlighted row of [`Setting::ALL`]
+    pub selected: usize,
+    /// Whether a value changed since the dialog opened
+    pub changed: bool,
+}
+
+impl SettingsDialog {
+    pub fn setting(&self) -> Setting {
+        Setting::ALL[self.selected]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_cycle_through_choices() {
+        let mut config = Config::default();
+        Setting::Fps.step(&mut config, true);
+        assert_eq!(config.render.fps, 60);
+        Setting::Watch.step(&mut config, false);
+        assert_eq!(config.api.watch_secs, 300, "wraps around");
+
+        // Digital rain is the default mode; off turns the background off
+        for _ in 0..5 {
+            Setting::Particles.step(&mut config, true);
+        }
+        assert!(!config.particles.enabled);
+        assert_eq!(particle_mode(&config), ParticleMode::None);
+
+        config.ui.timeline_zoom = 3.0;
+        Setting::Zoom.step(&mut config, true);
+        assert_eq!(config.ui.timeline_zoom, 0.25, "odd values from the file restart the list");
+        config.validate().unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+//! Service level rules.
+//!
+//! `[[sla]]` entries in the config set limits projects are expected to stay
+//! within, such as "no more than 5 days past the planned end". Breaching
+//! projects are flagged on the timeline and counted per rule in the Stats
+//! tab. Open projects are measured as if they ended on the reference date,
+//! so a breach shows up as soon as it is certain, not only on completion.
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::ProjectDto;
+
+/// One `[[sla]]` rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlaRule {
+    /// Name shown in the SLA summary
+    pub name: String,
+    /// Days a project may run past its planned end date
+    #[serde(default)]
+    pub max_overrun_days: Option<i64>,
+    /// Days a project may take from start to (actual) end
+    #[serde(default)]
+    pub max_duration_days: Option<i64>,
+}
+
+impl SlaRule {
+    /// A rule has to limit something
+    pub fn validate(&self) -> Result<()> {
+        if self.max_overrun_days.is_none() && self.max_duration_days.is_none() {
+            bail!("SLA rule \"{}\" sets neither max_overrun_days nor max_duration_days", self.name);
+        }
+        Ok(())
+    }
+
+    /// Whether the project breaks the rule as of a date
+    pub fn is_breached_by(&self, project: &ProjectDto, as_of: NaiveDate) -> bool {
+        if project.start_date > as_of {
+            return false;
+        }
+        let end = project.actual_end_as_of(as_of).unwrap_or(as_of);
+        let overrun = (end - project.planned_end_date).num_days();
+        let duration = (end - project.start_date).num_days();
+        self.max_overrun_days.is_some_and(|max| overrun > max) || self.max_duration_days.is_some_and(|max| duration > max)
+    }
+}
+
+/// Whether any rule is breached by the project
+pub fn is_breached(rules: &[SlaRule], project: &ProjectDto, as_of: NaiveDate) -> bool {
+    rules.iter().any(|rule| rule.is_breached_by(project, as_of))
+}
+
+/// Number of breaching projects per rule, in config order
+pub fn summary<'a>(rules: &'a [SlaRule], projects: &[ProjectDto], as_of: NaiveDate) -> Vec<(&'a SlaRule, usize)> {
+    rules
+        .iter()
+        .map(|rule| (rule, projects.iter().filter(|p| rule.is_breached_by(p, as_of)).count()))
+        .collect()
+}
+
+/// Breaches of the previous refresh, to report only new ones (`[webhook] sla`)
+#[derive(Debug, Default)]
+pub struct BreachWatch {
+    /// Rule index and project of each breach; `None` until the first refresh
+    seen: Option<HashSet<(usize, Uuid)>>,
+}
+
+impl BreachWatch {
+    /// Breaches that were not there on the previous refresh; the first one only records them
+    pub fn check<'a>(
+        &mut self,
+        rules: &'a [SlaRule],
+        projects: &'a [ProjectDto],
+        as_of: NaiveDate,
+    ) -> Vec<(&'a SlaRule, &'a ProjectDto)> {
+        let mut breaches = Vec::new();
+        let mut now = HashSet::new();
+        for (index, rule) in rules.iter().enumerate() {
+            for project in projects.iter().filter(|p| rule.is_breached_by(p, as_of)) {
+                now.insert((index, project.id));
+                if self.seen.as_ref().is_some_and(|seen| !seen.contains(&(index, project.id))) {
+                    breaches.push((rule, project));
+                }
+            }
+        }
+        self.seen = Some(now);
+        breaches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sweem_core::fixtures;
+
+    use super::*;
+
+    #[test]
+    fn test_breaches() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let project = |start, planned, actual: Option<u32>| ProjectDto {
+            actual_end_date: actual.map(date),
+            ..fixtures::project(date(start), date(planned))
+        };
+        let rules: Vec<SlaRule> = toml::from_str::<toml::Table>(
+            "[[sla]]\nname = \"Overrun\"\nmax_overrun_days = 5\n[[sla]]\nname = \"Length\"\nmax_duration_days = 20\n",
+        )
+        .unwrap()["sla"]
+            .clone()
+            .try_into()
+            .unwrap();
+
+        // Finished 6 days late; still open 6 days past the end; 5 days late; 25 days long
+        let projects = [project(1, 10, Some(16)), project(1, 4, None), project(1, 10, Some(15)), project(1, 26, Some(26))];
+        let overrun: Vec<bool> = projects.iter().map(|p| rules[0].is_breached_by(p, date(10))).collect();
+        assert_eq!(overrun, [false, true, false, false], "completions after the reference date do not count yet");
+        let counts: Vec<usize> = summary(&rules, &projects, date(31)).into_iter().map(|(_, n)| n).collect();
+        assert_eq!(counts, [2, 2]);
+        assert!(!is_breached(&rules, &project(20, 25, None), date(10)), "not started yet");
+        assert!(SlaRule { name: "Empty".into(), max_overrun_days: None, max_duration_days: None }.validate().is_err());
+
+        // Only breaches new since the last refresh are reported
+        let mut watch = BreachWatch::default();
+        assert!(watch.check(&rules, &projects, date(10)).is_empty(), "the first refresh is the baseline");
+        let new: Vec<(&str, Uuid)> = watch.check(&rules, &projects, date(31)).into_iter().map(|(r, p)| (r.name.as_str(), p.id)).collect();
+        assert_eq!(new, [("Overrun", projects[0].id), ("Length", projects[1].id), ("Length", projects[3].id)]);
+        assert!(watch.check(&rules, &projects, date(31)).is_empty());
+    }
+}
@@ -0,0 +1,1290 @@
+//! Buffer snapshots of the whole UI rendered against a `TestBackend`.
+//!
+//! Fixture data, a frozen clock and a seeded particle RNG keep the output
+//! deterministic. After an intentional layout change, review and accept the
+//! new snapshots with `cargo insta review` (or `INSTA_UPDATE=always cargo test`).
+
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use sweem_core::aggregate::SOURCE_FIELD;
+use sweem_core::fixtures;
+use sweem_core::probe::{Outcome, Stage, StageReport};
+use uuid::Uuid;
+
+use crate::api::{ApiCommand, ApiError, ApiMessage, BackendStatus, Diagnosis, EntityType, Prefetched};
+use crate::attachments::DownloadProgress;
+use crate::app::{App, ConfirmDialog, FormField, FormType, InputMode, Loaded, StatsView, Tab};
+use crate::clock;
+use crate::columns::Column;
+use crate::config::Config;
+use crate::console::ConsoleResponse;
+use crate::graphics::{ImageLayer, Protocol};
+use crate::ipc::RemoteCommand;
+use crate::models::{AuditEventDto, ClientDto, DocumentDto, PaginatedResult, ProjectDto, Role, UserDto};
+use crate::particles::ParticleMode;
+use crate::sla::SlaRule;
+use crate::store::SnapshotStore;
+use crate::ui;
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 32;
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+/// A command without the undo history operation it was sent for
+fn untagged(command: Option<ApiCommand>) -> Option<ApiCommand> {
+    match command {
+        Some(ApiCommand::Operation(_, command)) => Some(*command),
+        command => command,
+    }
+}
+
+/// Run the snapshot store jobs the app queued, as the worker does, and hand
+/// it the answers (which may queue more)
+fn run_store_jobs(app: &mut App) {
+    while let Some(index) = app.pending_commands.iter().position(|cmd| matches!(cmd, ApiCommand::Store(_))) {
+        let ApiCommand::Store(job) = app.pending_commands.remove(index) else { unreachable!() };
+        app.handle_api_message(job.run());
+    }
+}
+
+/// App with two clients, three users, four projects around "today" (2025-03-14)
+/// and an Inbox alert for the overdue one; the DTOs come from the shared
+/// `sweem_core::fixtures` builders the unit tests use too
+fn fixture() -> App {
+    clock::set_today(date(3, 14));
+
+    let mut app = App::new();
+    app.particle_system.reseed(0);
+
+    let acme = Uuid::from_u128(1);
+    let globex = Uuid::from_u128(2);
+    let alice = Uuid::from_u128(10);
+    let bob = Uuid::from_u128(11);
+    app.clients = vec![
+        ClientDto {
+            address: Some("1 Main St".to_string()),
+            projects_total: 3,
+            projects_completed: 1,
+            ..fixtures::client(acme, "Acme Corp")
+        },
+        ClientDto { projects_total: 1, ..fixtures::client(globex, "Globex") },
+    ];
+    let user = |id, name: &str, role| UserDto { login: Some(name.to_lowercase()), role, ..fixtures::user(id, name) };
+    app.users = vec![user(alice, "Alice", Role::Manager), user(bob, "Bob", Role::Manager), user(Uuid::from_u128(12), "Root", Role::Admin)];
+    let project = |n: u128, name: &str, client_id, manager_id, start, end, actual| ProjectDto {
+        id: Uuid::from_u128(100 + n),
+        client_id,
+        manager_id,
+        name: Some(name.to_string()),
+        actual_end_date: actual,
+        ..fixtures::project(start, end)
+    };
+    app.projects = vec![
+        project(1, "Website", acme, alice, date(2, 1), date(3, 1), Some(date(2, 27))),
+        project(2, "Mobile App", acme, bob, date(2, 20), date(3, 10), None),
+        project(3, "Data Platform", acme, alice, date(3, 5), date(4, 15), None),
+        project(4, "Migration", globex, bob, date(3, 12), date(3, 28), None),
+    ];
+    app.loaded = Loaded { projects: app.projects.clone(), clients: app.clients.clone(), users: app.users.clone() };
+    app.timeline_state.selected_project = Some(1);
+    app.timeline_state.center_on_today(&app.projects, WIDTH);
+    app.ui_state.inbox.refresh(&app.projects, Some(alice), date(3, 14).and_hms_opt(9, 30, 0).unwrap(), 3);
+    app
+}
+
+fn render(app: &App) -> String {
+    render_sized(app, WIDTH, HEIGHT)
+}
+
+fn render_sized(app: &App, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|frame| ui::render(frame, app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..height)
+        .map(|y| {
+            let line: String = (0..width).map(|x| buffer[(x, y)].symbol()).collect();
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_snapshot_tabs() {
+    let mut app = fixture();
+    for tab in Tab::BUILT_IN {
+        app.active_tab = tab;
+        insta::assert_snapshot!(format!("tab_{:?}", tab).to_lowercase(), render(&app));
+    }
+}
+
+#[test]
+fn test_projects_table_shares_the_timeline_selection() {
+    let mut app = fixture();
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    app.timeline_state.selected_project = Some(0);
+    app.handle_key(key(KeyCode::Char('T')));
+    assert_eq!(app.active_tab, Tab::Projects);
+    app.handle_key(key(KeyCode::Char('j')));
+    assert!(render(&app).contains(&app.projects[1].planned_end_date.to_string()), "exact dates are shown");
+
+    app.handle_key(key(KeyCode::Char('d')));
+    let dialog = app.confirm_dialog.take().expect("delete the selected project");
+    assert!(dialog.message.contains(app.projects[1].display_name()));
+    app.input_mode = InputMode::Normal;
+
+    // Enter edits the name cell in place, so `T` goes back
+    app.handle_key(key(KeyCode::Char('T')));
+    assert_eq!((app.active_tab, app.timeline_state.selected_project), (Tab::Timeline, Some(1)));
+}
+
+#[test]
+fn test_timeline_remembers_where_rows_are_drawn() {
+    let mut app = fixture();
+    app.active_tab = Tab::Timeline;
+    let screen = render(&app);
+    let rows = app.timeline_state.rows_area.get().expect("the main timeline records its rows");
+    let lines: Vec<&str> = screen.lines().collect();
+    for (index, project) in app.projects.iter().enumerate().take(rows.height as usize) {
+        let line: String = lines[rows.y as usize + index].chars().skip(rows.x as usize).collect();
+        assert!(line.contains(project.display_name()), "{} not on row {}: {}", project.display_name(), index, line);
+    }
+}
+
+#[test]
+fn test_snapshot_aggregated_backends() {
+    let mut app = fixture();
+    app.active_tab = Tab::Projects;
+    assert!(!app.columns(Tab::Projects).contains(&Column::Source), "only shown when merging backends");
+    app.config.api.aggregate = vec!["eu".to_string(), "us".to_string()];
+    app.config.columns.projects = vec![Column::Name, Column::Client, Column::PlannedEnd, Column::Status, Column::Source];
+    for (n, project) in app.projects.iter_mut().enumerate() {
+        let backend = if n % 2 == 0 { "eu" } else { "us" };
+        project.extra.insert(SOURCE_FIELD.to_string(), backend.into());
+    }
+    let backends = |us| vec![BackendStatus { name: "eu".into(), connected: true }, BackendStatus { name: "us".into(), connected: us }];
+    app.handle_api_message(ApiMessage::Backends(backends(true)));
+    app.handle_api_message(ApiMessage::Backends(backends(false)));
+    assert_eq!(app.logs.last().map(|l| l.message.as_str()), Some("Backend us is not answering; its data is left out"));
+    assert!(app.status_text().starts_with("Backends: ● eu ○ us"));
+    insta::assert_snapshot!("projects_aggregated", render(&app));
+}
+
+#[test]
+fn test_inline_edit_of_table_cells() {
+    let mut app = fixture();
+    app.active_tab = Tab::Projects;
+    app.timeline_state.selected_project = Some(1);
+    let press = |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+
+    // Rename Mobile App
+    press(&mut app, KeyCode::Enter);
+    for c in " 2".chars() {
+        press(&mut app, KeyCode::Char(c));
+    }
+    insta::assert_snapshot!("projects_inline_edit", render(&app));
+    let Some(ApiCommand::UpdateProject(id, dto)) = untagged(press(&mut app, KeyCode::Enter)) else {
+        panic!("the rename is sent");
+    };
+    assert_eq!((id, dto.name.as_deref()), (app.projects[1].id, Some("Mobile App 2")));
+    assert_eq!(dto.planned_end_date, app.projects[1].planned_end_date, "other fields are kept");
+
+    // The planned end moves with the calendar keys; Esc leaves it be
+    for _ in 0..4 {
+        press(&mut app, KeyCode::Char('l'));
+    }
+    assert_eq!(app.table_cell(), Column::PlannedEnd);
+    press(&mut app, KeyCode::Enter);
+    press(&mut app, KeyCode::Char('j'));
+    press(&mut app, KeyCode::Esc);
+    assert!(app.inline_edit.is_none());
+    press(&mut app, KeyCode::Enter);
+    press(&mut app, KeyCode::Char('j'));
+    let Some(ApiCommand::UpdateProject(_, dto)) = untagged(press(&mut app, KeyCode::Enter)) else {
+        panic!("the new date is sent");
+    };
+    assert_eq!(dto.planned_end_date, date(3, 17));
+
+    // Cells that cannot be edited keep Enter for the timeline
+    press(&mut app, KeyCode::Char('l'));
+    assert!(press(&mut app, KeyCode::Enter).is_none());
+    assert_eq!(app.active_tab, Tab::Timeline);
+}
+
+#[test]
+fn test_snapshot_timeline_zoom() {
+    let mut app = fixture();
+    for days_per_column in [0.5, 4.0, 14.0] {
+        app.timeline_state.days_per_column = days_per_column;
+        app.timeline_state.center_on_today(&app.projects, WIDTH);
+        insta::assert_snapshot!(format!("timeline_zoom_{}", days_per_column), render(&app));
+    }
+}
+
+#[test]
+fn test_snap_keys_agree() {
+    let mut app = fixture();
+    app.active_tab = Tab::Timeline;
+    let offset_after = |app: &mut App, key: KeyEvent| {
+        app.timeline_state.center_on_today(&app.projects, WIDTH);
+        app.handle_key(key);
+        app.timeline_state.scroll_offset
+    };
+    let bracket = offset_after(&mut app, KeyEvent::new(KeyCode::Char('['), KeyModifiers::NONE));
+    assert_eq!(offset_after(&mut app, KeyEvent::new(KeyCode::Char('h'), KeyModifiers::ALT)), bracket);
+    assert_eq!(offset_after(&mut app, KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL)), bracket);
+    let bracket = offset_after(&mut app, KeyEvent::new(KeyCode::Char(']'), KeyModifiers::NONE));
+    assert_eq!(offset_after(&mut app, KeyEvent::new(KeyCode::Char('l'), KeyModifiers::ALT)), bracket);
+}
+
+#[test]
+fn test_comparison_panes_wrap_and_stay_apart() {
+    let mut app = fixture();
+    app.active_tab = Tab::Timeline;
+    let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+    let panes = |app: &App| app.comparison.map(|c| (c.top, c.bottom));
+    app.handle_key(key('v'));
+    insta::assert_snapshot!("timeline_comparison", render(&app));
+
+    // Three managers: the top pane skips the bottom one's and wraps both ways
+    app.handle_key(key('v'));
+    assert_eq!(panes(&app), Some((0, 1)));
+    app.handle_key(key('n'));
+    assert_eq!(panes(&app), Some((2, 1)));
+    app.handle_key(key('n'));
+    assert_eq!(panes(&app), Some((0, 1)));
+    app.handle_key(key('N'));
+    assert_eq!(panes(&app), Some((2, 1)));
+    app.handle_key(key('N'));
+    assert_eq!(panes(&app), Some((0, 1)));
+    for c in "nnNbbBnbBNbn".chars() {
+        app.handle_key(key(c));
+        let (top, bottom) = panes(&app).unwrap();
+        assert_ne!(top, bottom, "after {}", c);
+    }
+
+    // Two clients: neither pane can move without landing on the other
+    app.handle_key(key('v'));
+    app.handle_key(key('v'));
+    assert_eq!(panes(&app), Some((0, 1)));
+    app.handle_key(key('b'));
+    app.handle_key(key('N'));
+    assert_eq!(panes(&app), Some((0, 1)));
+}
+
+#[test]
+fn test_snapshot_popups() {
+    let mut app = fixture();
+    app.show_help = true;
+    insta::assert_snapshot!("popup_help", render(&app));
+
+    let mut app = fixture();
+    app.show_error("API Error", "Cannot connect to API");
+    insta::assert_snapshot!("popup_error", render(&app));
+
+    let mut app = fixture();
+    app.open_create_form();
+    insta::assert_snapshot!("popup_create_project", render(&app));
+
+    let mut app = fixture();
+    app.active_tab = Tab::Users;
+    app.open_edit_form();
+    insta::assert_snapshot!("popup_edit_user", render(&app));
+
+    let mut app = fixture();
+    app.confirm_dialog = Some(ConfirmDialog::new_delete(EntityType::Client, Uuid::from_u128(2), "Globex"));
+    app.input_mode = InputMode::Confirming;
+    insta::assert_snapshot!("popup_confirm_delete", render(&app));
+
+    let mut app = fixture();
+    app.active_tab = Tab::Clients;
+    app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE));
+    insta::assert_snapshot!("popup_burndown", render(&app));
+
+    // A refresh where one project slipped, one was added and a client was removed
+    let mut app = fixture();
+    app.api_connected = true;
+    let mut projects = app.projects.clone();
+    app.handle_api_message(ApiMessage::ClientsLoaded(app.clients.clone()));
+    app.handle_api_message(ApiMessage::ProjectsLoaded(projects.clone()));
+    projects[1].planned_end_date = date(3, 24);
+    projects[2].manager_id = Uuid::from_u128(11);
+    projects.push(ProjectDto { id: Uuid::from_u128(105), name: Some("Audit".to_string()), ..projects[3].clone() });
+    app.handle_api_message(ApiMessage::ClientsLoaded(app.clients[..1].to_vec()));
+    app.handle_api_message(ApiMessage::ProjectsLoaded(projects));
+    app.handle_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE));
+    insta::assert_snapshot!("popup_changes", render(&app));
+}
+
+#[test]
+fn test_snapshot_my_projects() {
+    let mut app = fixture();
+    app.me = Some("alice".to_string());
+    let cmd = app.handle_key(KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE));
+    assert!(cmd.is_none(), "applied to the loaded projects");
+    let alice = Uuid::from_u128(10);
+    assert!(app.my_projects && app.projects.iter().all(|p| p.manager_id == alice));
+    app.timeline_state.selected_project = Some(0);
+    insta::assert_snapshot!("timeline_my_projects", render(&app));
+
+    app.handle_key(KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE));
+    assert_eq!(app.projects.len(), 4);
+}
+
+#[test]
+fn test_snapshot_client_ranking() {
+    let mut app = fixture();
+    app.active_tab = Tab::Stats;
+    app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE));
+    insta::assert_snapshot!("stats_client_ranking", render(&app));
+}
+
+#[test]
+fn test_stats_chart_images() {
+    let mut app = fixture();
+    let screen = ratatui::layout::Rect::new(0, 0, WIDTH, HEIGHT);
+    assert!(ui::chart_images(&app, screen).is_empty(), "only the Stats tab has raster charts");
+    app.active_tab = Tab::Stats;
+    let images = ui::chart_images(&app, screen);
+    assert_eq!(images.iter().map(|i| i.id).collect::<Vec<_>>(), [1, 2]);
+    let monthly = &images[0];
+    assert_eq!(monthly.bars.len(), 2 * app.stats_months as usize);
+    let tallest = monthly.bars.iter().map(|b| b.height).fold(0.0, f64::max);
+    assert_eq!(tallest, f64::from(monthly.area.height), "the busiest month reaches the top");
+
+    let mut layer = ImageLayer::new(Protocol::Kitty);
+    let mut out = Vec::new();
+    layer.sync(images.clone(), screen, &mut out).unwrap();
+    assert_eq!(String::from_utf8_lossy(&out).matches("a=T").count(), 2);
+    out.clear();
+    layer.sync(images, screen, &mut out).unwrap();
+    assert!(out.is_empty(), "unchanged images are not sent again");
+
+    app.show_help = true;
+    layer.sync(ui::chart_images(&app, screen), screen, &mut out).unwrap();
+    assert_eq!(String::from_utf8_lossy(&out).matches("a=d").count(), 2, "popups hide the images");
+}
+
+#[test]
+fn test_snapshot_favorites() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    app.handle_key(KeyEvent::new(KeyCode::Char('*'), KeyModifiers::NONE));
+    assert_eq!(app.projects[0].name.as_deref(), Some("Mobile App"), "the starred project is pinned first");
+    assert_eq!(app.timeline_state.selected_project, Some(0));
+    insta::assert_snapshot!("timeline_favorites", render(&app));
+
+    app.active_tab = Tab::Clients;
+    app.list_selected = 1;
+    app.handle_key(KeyEvent::new(KeyCode::Char('*'), KeyModifiers::NONE));
+    assert_eq!((app.clients[0].id, app.list_selected), (Uuid::from_u128(2), 0));
+    insta::assert_snapshot!("clients_favorites", render(&app));
+}
+
+#[test]
+fn test_snapshot_stale_data() {
+    let mut app = fixture();
+    app.api_connected = true;
+    app.last_refresh = std::time::Instant::now().checked_sub(std::time::Duration::from_secs(12 * 60));
+    insta::assert_snapshot!("timeline_stale", render(&app));
+
+    app.config.ui.stale_after_minutes = 0;
+    assert_eq!(app.stale_warning(), None, "the age check is off");
+    app.api_connected = false;
+    assert!(app.stale_warning().is_some(), "a dropped connection always counts");
+}
+
+#[test]
+fn test_snapshot_sla_breaches() {
+    let mut app = fixture();
+    app.config.sla = vec![SlaRule { name: "Overrun over 2 days".to_string(), max_overrun_days: Some(2), max_duration_days: None }];
+    insta::assert_snapshot!("sla_timeline", render(&app));
+    app.active_tab = Tab::Stats;
+    insta::assert_snapshot!("sla_stats", render(&app));
+}
+
+#[test]
+fn test_snapshot_sort_dialog() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    app.active_tab = Tab::Users;
+    app.list_selected = 1;
+    let press = |app: &mut App, code| {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    };
+    press(&mut app, KeyCode::Char('s'));
+    // Role ascending, then name descending
+    for code in [KeyCode::Char('j'), KeyCode::Char('j'), KeyCode::Char(' '), KeyCode::Char('k'), KeyCode::Char('k')] {
+        press(&mut app, code);
+    }
+    press(&mut app, KeyCode::Char(' '));
+    press(&mut app, KeyCode::Char(' '));
+    let names: Vec<_> = app.users.iter().map(|u| u.display_name()).collect();
+    assert_eq!(names, ["Bob", "Alice", "Root"]);
+    assert_eq!(app.list_selected, 0, "the selection follows Bob");
+    insta::assert_snapshot!("users_sort_dialog", render(&app));
+
+    press(&mut app, KeyCode::Esc);
+    insta::assert_snapshot!("users_sorted", render(&app));
+}
+
+#[test]
+fn test_snapshot_context_menu() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    let press = |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    // Mobile App is open, so it can be completed
+    press(&mut app, KeyCode::Char('.'));
+    insta::assert_snapshot!("timeline_context_menu", render(&app));
+    for _ in 0..4 {
+        press(&mut app, KeyCode::Char('j'));
+    }
+    let cmd = untagged(press(&mut app, KeyCode::Enter));
+    assert!(matches!(cmd, Some(ApiCommand::UpdateProject(id, dto)) if id == Uuid::from_u128(102) && dto.actual_end_date == Some(date(3, 14))));
+    assert!(app.context_menu.is_none());
+
+    // A right click on a client, then "show this client's projects"
+    app.active_tab = Tab::Clients;
+    app.list_selected = 1;
+    crate::update::update(&mut app, crate::update::Msg::ContextMenu);
+    for _ in 0..3 {
+        press(&mut app, KeyCode::Char('j'));
+    }
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.active_tab, Tab::Timeline);
+    assert_eq!(app.expression(EntityType::Project).unwrap().as_str(), "client:\"Globex\"");
+
+    // Clicks do not open a menu over a form
+    press(&mut app, KeyCode::Char('e'));
+    crate::update::update(&mut app, crate::update::Msg::ContextMenu);
+    assert!(app.context_menu.is_none());
+}
+
+#[test]
+fn test_clone_prefills_the_create_form() {
+    let mut app = fixture();
+    // Mobile App runs 2025-02-20 to 2025-03-10; by default the copy starts today
+    app.handle_key(KeyEvent::new(KeyCode::Char('D'), KeyModifiers::NONE));
+    let form = app.form_state.as_ref().unwrap();
+    assert_eq!(form.form_type, FormType::CreateProject);
+    assert_eq!(form.project_name, "Mobile App");
+    assert_eq!((form.project_start_date.as_str(), form.project_end_date.as_str()), ("2025-03-14", "2025-04-01"));
+    let dto = form.build_create_project(&app.clients, &app.users);
+    assert_eq!((dto.client_id, dto.manager_id), (Uuid::from_u128(1), Uuid::from_u128(11)));
+
+    app.close_form();
+    app.config.ui.clone_offset_days = Some(7);
+    app.handle_key(KeyEvent::new(KeyCode::Char('D'), KeyModifiers::NONE));
+    assert_eq!(app.form_state.as_ref().unwrap().project_start_date, "2025-02-27");
+}
+
+#[test]
+fn test_batch_reschedule_of_marked_projects() {
+    let mut app = fixture();
+    let press = |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    // Mark Mobile App and Data Platform, then shift both by a week
+    for code in [KeyCode::Char(' '), KeyCode::Char('j'), KeyCode::Char(' '), KeyCode::Char('R'), KeyCode::Char('7')] {
+        press(&mut app, code);
+    }
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.input_mode, InputMode::Confirming, "previewed before anything is sent");
+    insta::assert_snapshot!("reschedule_preview", render(&app));
+
+    let Some(ApiCommand::BatchUpdateProjects(batch)) = untagged(press(&mut app, KeyCode::Char('y'))) else {
+        panic!("expected a batch update");
+    };
+    let moved: Vec<_> = batch.iter().map(|(id, dto)| (*id, dto.start_date, dto.planned_end_date)).collect();
+    assert_eq!(
+        moved,
+        [(Uuid::from_u128(102), date(2, 27), date(3, 17)), (Uuid::from_u128(103), date(3, 12), date(4, 22))]
+    );
+
+    // Each failed update is reported by name
+    app.handle_api_message(ApiMessage::BatchUpdated {
+        succeeded: 1,
+        failed: vec![(Uuid::from_u128(103), "conflict".to_string())],
+    });
+    assert!(app.logs.iter().any(|entry| entry.message == "Rescheduling Data Platform failed: conflict"));
+    press(&mut app, KeyCode::Esc);
+    press(&mut app, KeyCode::Esc);
+    assert!(app.marked.is_empty(), "Esc clears the marks");
+}
+
+#[test]
+fn test_quit_asks_while_work_is_pending() {
+    let mut app = fixture();
+    let press = |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    // A batch reschedule is sent and not answered yet
+    for code in [KeyCode::Char(' '), KeyCode::Char('R'), KeyCode::Char('3'), KeyCode::Enter, KeyCode::Char('y')] {
+        press(&mut app, code);
+    }
+    app.download = Some(DownloadProgress {
+        document_id: Uuid::from_u128(900),
+        file_name: "spec.pdf".to_string(),
+        received: 2048,
+        total: Some(8192),
+    });
+
+    assert!(press(&mut app, KeyCode::Char('q')).is_none());
+    assert!(!app.should_quit);
+    insta::assert_snapshot!("quit_pending", render(&app));
+    press(&mut app, KeyCode::Char('n'));
+    assert!(!app.should_quit, "staying is the default");
+
+    assert!(press(&mut app, KeyCode::Char('q')).is_none());
+    assert!(matches!(press(&mut app, KeyCode::Char('y')), Some(ApiCommand::Shutdown)));
+    assert!(app.should_quit);
+
+    // Nothing pending: quits right away
+    let mut app = fixture();
+    assert!(matches!(press(&mut app, KeyCode::Char('q')), Some(ApiCommand::Shutdown)));
+}
+
+#[test]
+fn test_snapshot_shutdown_screen() {
+    let mut app = fixture();
+    app.handle_api_message(ApiMessage::Draining { done: 1, total: 3 });
+    insta::assert_snapshot!("shutdown_draining", render(&app));
+}
+
+#[test]
+fn test_snapshot_date_picker() {
+    let mut app = fixture();
+    let press = |app: &mut App, code| {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    };
+    press(&mut app, KeyCode::Char('@'));
+    press(&mut app, KeyCode::Char('H'));
+    press(&mut app, KeyCode::Char('j'));
+    insta::assert_snapshot!("date_picker", render(&app));
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.as_of, Some(date(2, 21)));
+    assert!(app.date_picker.is_none());
+    // Picking today goes back to the live view
+    press(&mut app, KeyCode::Char('@'));
+    press(&mut app, KeyCode::Char('t'));
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.as_of, None);
+
+    // Form date fields move the same way
+    press(&mut app, KeyCode::Char('D'));
+    for _ in 0..3 {
+        press(&mut app, KeyCode::Tab);
+    }
+    press(&mut app, KeyCode::Char('L'));
+    press(&mut app, KeyCode::Char('h'));
+    assert_eq!(app.form_state.as_ref().unwrap().project_start_date, "2025-04-13");
+}
+
+#[test]
+fn test_snapshot_entity_picker() {
+    let mut app = fixture();
+    let press = |app: &mut App, code| {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    };
+    press(&mut app, KeyCode::Char('c'));
+    press(&mut app, KeyCode::Tab);
+    press(&mut app, KeyCode::Tab);
+    // Typing on the manager field searches names and logins
+    press(&mut app, KeyCode::Char('o'));
+    insta::assert_snapshot!("form_manager_picker", render(&app));
+    press(&mut app, KeyCode::Down);
+    press(&mut app, KeyCode::Enter);
+    let form = app.form_state.as_ref().unwrap();
+    assert_eq!(form.current_field(), FormField::ProjectStartDate, "picking moves on");
+    assert_eq!(app.users[form.project_manager_idx].display_name(), "Root");
+
+    // Esc closes the list and keeps the choice
+    press(&mut app, KeyCode::BackTab);
+    press(&mut app, KeyCode::Enter);
+    press(&mut app, KeyCode::Char('b'));
+    press(&mut app, KeyCode::Esc);
+    let form = app.form_state.as_ref().unwrap();
+    assert!(form.picker.is_none());
+    assert_eq!(app.users[form.project_manager_idx].display_name(), "Root");
+}
+
+#[test]
+fn test_alert_rules_fill_the_inbox() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    app.config = Config::parse(
+        "[[alerts]]\nname = \"Acme slip\"\non = \"planned-end-moved\"\nfilter = \"client:acme\"\nmore_than_days = 7\n",
+    )
+    .unwrap();
+    let unread = app.ui_state.inbox.unread_count();
+    app.handle_api_message(ApiMessage::ProjectsLoaded(app.projects.clone()));
+    assert!(app.pending_notifications.is_empty(), "the first refresh is the baseline");
+
+    // Data Platform (Acme) slips by two weeks, Migration (Globex) by a month
+    let mut projects = app.projects.clone();
+    projects[2].planned_end_date += chrono::Duration::days(14);
+    projects[3].planned_end_date += chrono::Duration::days(30);
+    app.handle_api_message(ApiMessage::ProjectsLoaded(projects));
+    assert_eq!(app.ui_state.inbox.unread_count(), unread + 1);
+    assert_eq!(
+        app.ui_state.inbox.items[0].event.message(),
+        "Acme slip: Data Platform planned end 2025-04-15 → 2025-04-29 (+14 days)"
+    );
+    assert_eq!(app.pending_notifications.len(), 1);
+    app.active_tab = Tab::Inbox;
+    assert!(render(&app).contains("Rule"));
+}
+
+#[test]
+fn test_remote_filters_keep_the_lookup_data() {
+    let mut app = fixture();
+    app.active_tab = Tab::Projects;
+    let filter = |spec| vec![crate::export::Filter::parse(spec).unwrap()];
+    let cmd = app.handle_remote(RemoteCommand::SetFilter(EntityType::Client, filter("name=globex")));
+    assert!(cmd.is_none(), "filtering needs no refetch");
+    assert_eq!(app.clients.len(), 1);
+    // Acme projects still show their client
+    assert!(render(&app).contains("Acme Corp"));
+
+    app.handle_remote(RemoteCommand::SetFilter(EntityType::Project, filter("name=migration")));
+    assert_eq!(app.projects.len(), 1);
+    app.handle_remote(RemoteCommand::SetFilter(EntityType::Project, Vec::new()));
+    assert_eq!(app.projects.len(), 4);
+}
+
+#[test]
+fn test_deadline_alerts_ignore_filters() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    let projects = app.projects.clone();
+    app.expressions.push((EntityType::Project, crate::expr::FilterExpr::parse("client:globex").unwrap()));
+    app.handle_api_message(ApiMessage::ProjectsLoaded(projects.clone()));
+    assert_eq!(app.projects.len(), 1);
+
+    // Data Platform, hidden by the filter, was due yesterday
+    let mut slipped = projects;
+    slipped[2].planned_end_date = date(3, 13);
+    app.handle_api_message(ApiMessage::ProjectsLoaded(slipped.clone()));
+    assert_eq!(app.pending_notifications.len(), 1);
+
+    app.expressions.clear();
+    app.handle_api_message(ApiMessage::ProjectsLoaded(slipped));
+    assert_eq!(app.pending_notifications.len(), 1, "clearing the filter alerts nothing new");
+}
+
+#[test]
+fn test_sandbox_blocks_project_changes() {
+    let mut app = fixture();
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    app.handle_key(key(KeyCode::Char('S')));
+    app.handle_key(key(KeyCode::Char('>')));
+    assert!(app.sandbox.is_some());
+    for code in [KeyCode::Char('e'), KeyCode::Char('d'), KeyCode::Char('D')] {
+        assert!(app.handle_key(key(code)).is_none());
+        assert!(app.form_state.is_none() && app.confirm_dialog.is_none(), "{:?} with shifted dates", code);
+    }
+    app.active_tab = Tab::Projects;
+    app.handle_key(key(KeyCode::Enter));
+    assert!(app.inline_edit.is_none(), "no rename sending the shifted dates along");
+    assert_eq!(
+        app.logs.last().map(|l| l.message.as_str()),
+        Some("Close the sandbox before changing projects (S commits, X discards)")
+    );
+}
+
+#[test]
+fn test_hiding_archived_projects() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    let projects = app.projects.clone();
+    // Website was completed 15 days ago
+    app.config.ui.archive_after_days = 14;
+    let cmd = app.handle_key(KeyEvent::new(KeyCode::Char('H'), KeyModifiers::NONE));
+    assert!(cmd.is_none(), "applied to the loaded projects");
+    assert_eq!(app.projects.len(), 3);
+    assert_eq!(app.archived_hidden, 1);
+
+    app.config.ui.archive_after_days = 30;
+    app.handle_api_message(ApiMessage::ProjectsLoaded(projects.clone()));
+    assert_eq!((app.projects.len(), app.archived_hidden), (4, 0), "not archived yet");
+
+    app.config.ui.archive_after_days = 14;
+    app.handle_api_message(ApiMessage::ProjectsLoaded(projects));
+    assert_eq!(app.projects.len(), 3);
+    app.handle_key(KeyEvent::new(KeyCode::Char('H'), KeyModifiers::NONE));
+    assert_eq!(app.projects.len(), 4, "shown again");
+}
+
+#[test]
+fn test_tab_badges_fit_the_width() {
+    let app = fixture();
+    let tab_bar = |width| render_sized(&app, width, HEIGHT).lines().nth(1).unwrap().to_string();
+    let wide = tab_bar(160);
+    assert!(wide.contains("Timeline (4 / 1 overdue)"), "{}", wide);
+    assert!(wide.contains("Clients (2)") && wide.contains("Users (3)"), "{}", wide);
+    let narrow = tab_bar(116);
+    assert!(narrow.contains("Timeline (4/1!)"), "shortened: {}", narrow);
+    assert!(!tab_bar(WIDTH).contains("(4"), "no room");
+}
+
+#[test]
+fn test_telemetry_readout_in_the_status_bar() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    app.frame_stats.fps = 29.8;
+    app.frame_stats.render_ms = 2.04;
+    app.telemetry.rss = Some(12 * 1024 * 1024);
+    assert!(!render(&app).contains("fps"));
+
+    app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+    let status_bar = render(&app).lines().nth(2).unwrap().to_string();
+    assert!(status_bar.ends_with(" 30 fps · 2.0 ms · 9 loaded · RSS 12.0 MiB ┘"), "{}", status_bar);
+}
+
+#[test]
+fn test_startup_view_from_config() {
+    let mut app = fixture();
+    let config = Config::parse("[ui]\nstart_tab = \"audit\"\ntimeline_zoom = 4\n[particles]\nenabled = false\nmode = \"snow\"\n");
+    app.apply_config(config.unwrap());
+    assert_eq!(app.active_tab, Tab::Audit);
+    assert_eq!(app.timeline_state.days_per_column, 4.0);
+    assert_eq!(app.particle_system.mode(), ParticleMode::None, "disabled wins over the mode");
+    assert!(matches!(app.pending_commands[..], [ApiCommand::FetchAudit { .. }]), "{:?}", app.pending_commands);
+}
+
+#[test]
+fn test_snapshot_lazy_user_pages() {
+    let mut app = fixture();
+    app.active_tab = Tab::Users;
+    let users = app.users.clone();
+    app.handle_api_message(ApiMessage::UsersPage(PaginatedResult::slice(users.clone(), 1, 2)));
+    app.handle_api_message(ApiMessage::UsersLoaded(users.clone()));
+    assert_eq!(app.users.len(), 2, "the list shows the loaded pages");
+    assert_eq!(app.loaded.users.len(), 3, "names are looked up in the whole list");
+
+    // Moving near the end of the loaded rows asks for the next page, once
+    app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE));
+    assert!(matches!(
+        app.pending_commands[..],
+        [ApiCommand::FetchPage { entity: EntityType::User, page: 2 }]
+    ));
+    insta::assert_snapshot!("users_loading_page", render(&app));
+
+    app.handle_api_message(ApiMessage::UsersPage(PaginatedResult::slice(users, 2, 2)));
+    assert_eq!(app.page_status(Tab::Users), Some((3, 3, false)));
+    assert_eq!(app.users.len(), 3);
+}
+
+#[test]
+fn test_prefetched_data_is_shown_without_a_request() {
+    use crate::attachments::DocumentList;
+    use crate::prefetch::PrefetchJob;
+
+    let mut app = fixture();
+    app.active_tab = Tab::Projects;
+    app.timeline_state.selected_project = Some(1);
+    let selected = app.projects[1].id;
+    let jobs: Vec<_> = app.prefetch_jobs().into_iter().map(|(job, _)| job).collect();
+    assert_eq!(jobs[0], PrefetchJob::Documents(selected), "the selected project first");
+    assert_eq!(jobs.len(), 3, "then its neighbours");
+
+    app.handle_api_message(ApiMessage::Prefetched(Prefetched::Documents { project_id: selected, documents: Vec::new() }));
+    assert!(!app.prefetch_jobs().iter().any(|(job, _)| *job == PrefetchJob::Documents(selected)), "not fetched twice");
+    assert!(app.handle_key(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::NONE)).is_none());
+    assert_eq!(app.attachments.as_ref().unwrap().documents, DocumentList::Loaded(Vec::new()));
+    app.attachments = None;
+    let fetch = app.handle_key(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::NONE));
+    assert!(matches!(fetch, Some(ApiCommand::FetchDocuments(id)) if id == selected), "used once");
+
+    // The next page of a lazy list waits until the selection gets near it
+    app.attachments = None;
+    app.active_tab = Tab::Users;
+    let users = app.users.clone();
+    app.handle_api_message(ApiMessage::UsersPage(PaginatedResult::slice(users.clone(), 1, 2)));
+    assert_eq!(app.prefetch_jobs(), [(PrefetchJob::Page { entity: EntityType::User, page: 2 }, 5)]);
+    app.handle_api_message(ApiMessage::Prefetched(Prefetched::UsersPage(PaginatedResult::slice(users, 2, 2))));
+    assert!(app.prefetch_jobs().is_empty());
+    assert_eq!(app.users.len(), 2);
+    app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+    assert!(app.pending_commands.is_empty(), "{:?}", app.pending_commands);
+    assert_eq!(app.users.len(), 3);
+}
+
+#[test]
+fn test_snapshot_column_dialog() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    app.active_tab = Tab::Clients;
+    let press = |app: &mut App, code| {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    };
+    press(&mut app, KeyCode::Char('C'));
+    // Hide the address, then move the project counts in front of the name
+    for code in [KeyCode::Char('j'), KeyCode::Char(' '), KeyCode::Char('j'), KeyCode::Char('K'), KeyCode::Char('K')] {
+        press(&mut app, code);
+    }
+    assert_eq!(app.columns(Tab::Clients), [Column::Projects, Column::Name]);
+    insta::assert_snapshot!("clients_column_dialog", render(&app));
+
+    press(&mut app, KeyCode::Esc);
+    let (headers, _) = app.view_table().unwrap();
+    assert_eq!(headers, ["Projects", "Name"], "exports follow the layout");
+    insta::assert_snapshot!("clients_columns", render(&app));
+
+    press(&mut app, KeyCode::Char('C'));
+    press(&mut app, KeyCode::Char('r'));
+    assert_eq!(app.columns(Tab::Clients), Column::CLIENTS[..3], "back to the configured layout");
+}
+
+#[test]
+fn test_snapshot_settings_dialog() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    let press = |app: &mut App, code| {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    };
+    press(&mut app, KeyCode::Char(','));
+    // Watch every 5 seconds, then zoom the timeline out to 2 days per column
+    for code in [KeyCode::Char('j'), KeyCode::Char('j'), KeyCode::Char('j'), KeyCode::Char('j'), KeyCode::Char('l')] {
+        press(&mut app, code);
+    }
+    assert!(matches!(app.pending_commands.last(), Some(ApiCommand::SetWatch(Some(d))) if *d == Duration::from_secs(5)));
+    for code in [KeyCode::Char('j'), KeyCode::Char('j'), KeyCode::Char('j'), KeyCode::Char('l')] {
+        press(&mut app, code);
+    }
+    assert_eq!(app.timeline_state.days_per_column, 2.0, "applies right away");
+    insta::assert_snapshot!("settings_dialog", render(&app));
+
+    press(&mut app, KeyCode::Esc);
+    assert!(app.settings.is_none());
+    assert_eq!((app.config.api.watch_secs, app.config.ui.timeline_zoom), (5, 2.0));
+}
+
+#[test]
+fn test_snapshot_connection_diagnosis() {
+    let mut app = fixture();
+    let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+    assert!(app.handle_key(ctrl_d).is_none(), "nothing to check without an API");
+    assert!(app.diagnosis.is_none());
+
+    app.api_urls = vec!["https://sweem.example.com".to_string()];
+    assert!(matches!(app.handle_key(ctrl_d), Some(ApiCommand::Diagnose(urls, _)) if urls == app.api_urls));
+    let report = |stage, outcome, ms| StageReport { stage, outcome, elapsed: Duration::from_millis(ms) };
+    app.handle_api_message(ApiMessage::Diagnosis(vec![Diagnosis {
+        url: "https://sweem.example.com".to_string(),
+        proxy: Some("http://proxy.corp:3128".to_string()),
+        stages: vec![
+            report(Stage::Dns, Outcome::Passed("10.0.4.7 (proxy)".to_string()), 12),
+            report(Stage::Tcp, Outcome::Passed("connected to 10.0.4.7:3128 (proxy), tunnel to sweem.example.com:443".to_string()), 31),
+            report(Stage::Tls, Outcome::Failed("certificate verify failed: self-signed certificate in chain".to_string()), 44),
+            report(Stage::Http, Outcome::Skipped, 0),
+        ],
+    }]));
+    insta::assert_snapshot!("connection_diagnosis", render(&app));
+
+    app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.diagnosis.is_none());
+}
+
+#[test]
+fn test_snapshot_filter_expression() {
+    let mut app = fixture();
+    app.api_connected = true;
+    app.handle_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
+    for c in "overdue || client:globex".chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(app.pending_commands.is_empty(), "applied to the loaded projects");
+    let names: Vec<_> = app.projects.iter().map(|p| p.display_name()).collect();
+    assert_eq!(names, ["Mobile App", "Migration"]);
+    insta::assert_snapshot!("timeline_filter", render(&app));
+
+    // The prompt starts from the current filter; an invalid edit keeps it
+    app.handle_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Char('('), KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(app.expression(EntityType::Project).map(|e| e.as_str()), Some("overdue || client:globex"));
+}
+
+#[test]
+fn test_markdown_export() {
+    let mut app = fixture();
+    app.active_tab = Tab::Clients;
+    app.ui_state.favorites.insert(Uuid::from_u128(2));
+    app.handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL));
+    assert_eq!(
+        app.pending_clipboard.as_deref(),
+        Some("| Name | Address | Projects |\n| --- | --- | --- |\n| Acme Corp | 1 Main St | 1/3 |\n| ★ Globex | - | 0/1 |\n")
+    );
+
+    app.active_tab = Tab::Timeline;
+    let (headers, rows) = app.view_table().unwrap();
+    assert_eq!(headers.len(), 8);
+    assert_eq!(rows[1], ["Mobile App", "Acme Corp", "Bob", "2025-02-20", "2025-03-10", "-", "overdue by 4d", "Overdue"]);
+    assert_eq!(rows[2][6], "ends in 32d");
+    app.active_tab = Tab::Inbox;
+    assert!(app.view_table().is_none());
+}
+
+#[test]
+fn test_snapshot_inspector() {
+    let mut app = fixture();
+    app.active_tab = Tab::Users;
+    app.users[0].extra.insert("email".to_string(), "alice@example.com".into());
+    app.handle_key(KeyEvent::new(KeyCode::Char('J'), KeyModifiers::NONE));
+    insta::assert_snapshot!("popup_inspector", render(&app));
+
+    app.handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+    assert!(app.pending_clipboard.as_deref().is_some_and(|json| json.contains("\"email\": \"alice@example.com\"")));
+    app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.inspector.is_none());
+}
+
+#[test]
+fn test_snapshot_console() {
+    let mut app = fixture();
+    app.api_connected = true;
+    app.handle_key(KeyEvent::new(KeyCode::F(4), KeyModifiers::NONE));
+    assert_eq!(app.active_tab, Tab::Console);
+    for c in "/clients?page=1 pageSize=2".chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    let Some(ApiCommand::Console(request)) = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)) else {
+        panic!("expected a console request");
+    };
+    assert_eq!(request.to_string(), "GET /clients?page=1&pageSize=2");
+
+    app.handle_api_message(ApiMessage::ConsoleResponse(ConsoleResponse {
+        request,
+        status: Some(200),
+        body: r#"{"items":[{"id":"00000000-0000-0000-0000-000000000001","name":"Acme Corp"}],"totalCount":2}"#.to_string(),
+        elapsed: Duration::from_millis(42),
+    }));
+    insta::assert_snapshot!("console_response", render(&app));
+
+    app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert_eq!(app.active_tab, Tab::Timeline);
+}
+
+#[test]
+fn test_snapshot_attachments() {
+    let mut app = fixture();
+    app.config.attachments.dir = Some("/tmp/sweem-downloads".into());
+    let Some(ApiCommand::FetchDocuments(project_id)) = app.handle_key(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::NONE)) else {
+        panic!("expected a document request");
+    };
+    assert_eq!(project_id, app.projects[1].id);
+    let document = |n, name: &str, size| DocumentDto {
+        id: Uuid::from_u128(n),
+        file_name: name.to_string(),
+        size,
+        content_type: None,
+        uploaded_at: Some(date(2, 20).and_hms_opt(9, 0, 0).unwrap().and_utc()),
+    };
+    let documents = vec![document(1, "Statement of work.pdf", Some(182_000)), document(2, "Wireframes.zip", Some(4_500_000))];
+    app.handle_api_message(ApiMessage::DocumentsLoaded { project_id, result: Ok(documents) });
+
+    app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+    let Some(ApiCommand::DownloadDocument { document, dir, .. }) = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)) else {
+        panic!("expected a download");
+    };
+    assert_eq!((document.file_name.as_str(), dir.to_str()), ("Wireframes.zip", Some("/tmp/sweem-downloads")));
+    assert!(app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)).is_none(), "one download at a time");
+    app.handle_api_message(ApiMessage::DownloadProgress { document_id: document.id, received: 1_800_000, total: Some(4_500_000) });
+    insta::assert_snapshot!("popup_attachments", render(&app));
+
+    app.handle_api_message(ApiMessage::DownloadFinished { document_id: document.id, result: Ok("/tmp/sweem-downloads/Wireframes.zip".into()) });
+    assert!(app.download.is_none());
+    assert!(app.logs.iter().any(|entry| entry.message.contains("Wireframes.zip")));
+
+    // Servers without document support
+    app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::NONE));
+    let missing = ApiError::Status { endpoint: "GET /projects/{id}/documents".to_string(), status: 404, message: String::new() };
+    app.handle_api_message(ApiMessage::DocumentsLoaded { project_id, result: Err(missing) });
+    assert!(render(&app).contains("This server does not provide project documents"));
+}
+
+#[test]
+fn test_snapshot_audit() {
+    let mut app = fixture();
+    app.active_tab = Tab::Inbox;
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    let Some(ApiCommand::FetchAudit { page: 1, search }) = app.handle_key(key(KeyCode::Tab)) else {
+        panic!("expected the first audit page");
+    };
+    assert_eq!(app.active_tab, Tab::Audit);
+    let event = |n, actor: Option<&str>, action: &str, entity_type: &str, entity_id, name: Option<&str>, summary: Option<&str>| AuditEventDto {
+        id: Uuid::from_u128(n),
+        timestamp: date(3, 1).and_hms_opt(9, n as u32, 0).unwrap().and_utc(),
+        actor: actor.map(String::from),
+        action: action.to_string(),
+        entity_type: entity_type.to_string(),
+        entity_id,
+        entity_name: name.map(String::from),
+        summary: summary.map(String::from),
+    };
+    let events = vec![
+        event(2, Some("alice"), "Updated", "Project", app.projects[1].id, Some("Mobile App"), Some("deadline 2025-03-10 → 2025-04-01")),
+        event(1, None, "Deleted", "Client", Uuid::from_u128(99), Some("Initech"), None),
+    ];
+    let page = PaginatedResult {
+        items: Some(events),
+        page: 1,
+        page_size: 50,
+        total_count: 52,
+        total_pages: 2,
+        has_previous: false,
+        has_next: true,
+    };
+    app.handle_api_message(ApiMessage::AuditLoaded { page: 1, search, result: Ok(page.clone()) });
+    insta::assert_snapshot!("audit_feed", render(&app));
+
+    // Enter shows the affected entity; deleted ones are only logged
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!((app.active_tab, app.timeline_state.selected_project), (Tab::Timeline, Some(1)));
+    app.active_tab = Tab::Audit;
+    app.list_selected = 1;
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.active_tab, Tab::Audit);
+
+    assert!(matches!(app.handle_key(key(KeyCode::Char(']'))), Some(ApiCommand::FetchAudit { page: 2, .. })));
+    app.handle_key(key(KeyCode::Char('/')));
+    for c in "alice".chars() {
+        app.handle_key(key(KeyCode::Char(c)));
+    }
+    app.handle_key(key(KeyCode::Enter));
+    let Some(ApiCommand::FetchAudit { page: 1, search }) = app.pending_commands.pop() else {
+        panic!("expected a new search");
+    };
+    assert_eq!(search, "alice");
+    // The answer to the superseded page is dropped
+    app.handle_api_message(ApiMessage::AuditLoaded { page: 2, search: String::new(), result: Ok(page) });
+    assert!(render(&app).contains("Loading the audit log"));
+
+    let missing = ApiError::Status { endpoint: "GET /audit".to_string(), status: 404, message: String::new() };
+    app.handle_api_message(ApiMessage::AuditLoaded { page: 1, search, result: Err(missing) });
+    assert!(render(&app).contains("This server does not provide an audit log"));
+}
+
+#[test]
+fn test_history_store() {
+    let path = std::env::temp_dir().join(format!("sweem-history-{}.sqlite", Uuid::new_v4()));
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    app.api_connected = true;
+    let store = SnapshotStore::open(&path, "http://test").unwrap();
+    let january = date(1, 15).and_hms_opt(12, 0, 0).unwrap().and_utc();
+    store.record(EntityType::Project, january, &app.projects[..1]).unwrap();
+    app.store = Some(store);
+    app.handle_api_message(ApiMessage::ProjectsLoaded(app.projects.clone()));
+    assert!(matches!(app.pending_commands[..], [ApiCommand::Store(_)]), "recorded by the worker");
+    run_store_jobs(&mut app);
+
+    // A restart shows the latest recorded data until the API answers
+    let mut restarted = App::new();
+    restarted.store = Some(SnapshotStore::open(&path, "http://test").unwrap());
+    assert!(restarted.show_recorded(None));
+    assert_eq!(restarted.projects.len(), 4);
+    assert!(restarted.last_refresh.is_none());
+    assert!(restarted.stale_warning().is_some_and(|w| w.starts_with("RECORDED")));
+
+    // Time travel to a recorded day shows that day's data, leaving it reloads
+    app.handle_key(KeyEvent::new(KeyCode::Char('@'), KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Char('H'), KeyModifiers::NONE));
+    for _ in 0..13 {
+        app.handle_key(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE));
+    }
+    assert_eq!(app.date_picker.map(|p| p.date), Some(date(2, 1)));
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let names: Vec<_> = app.projects.iter().map(|p| p.display_name()).collect();
+    assert_eq!(names, ["Website"]);
+    assert_eq!(app.recorded_at, Some(january));
+    app.handle_key(KeyEvent::new(KeyCode::Char('@'), KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    assert!(app.date_picker.is_none());
+    assert!(matches!(app.pending_commands[..], [ApiCommand::RefreshAll]));
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_snapshot_stats_history() {
+    let path = std::env::temp_dir().join(format!("sweem-history-{}.sqlite", Uuid::new_v4()));
+    let mut app = fixture();
+    app.active_tab = Tab::Stats;
+    let press = |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    press(&mut app, KeyCode::Char('v'));
+    press(&mut app, KeyCode::Char('v'));
+    assert_eq!(app.stats_view, StatsView::History);
+    assert!(render(&app).contains("The snapshot history is off"));
+
+    // Mobile App was recorded before it started, Migration only showed up later
+    let store = SnapshotStore::open(&path, "http://test").unwrap();
+    let noon = |month, day| date(month, day).and_hms_opt(12, 0, 0).unwrap().and_utc();
+    store.record(EntityType::Project, noon(2, 10), &app.projects[..2]).unwrap();
+    store.record(EntityType::Project, noon(3, 1), &app.projects[..3]).unwrap();
+    store.record(EntityType::Project, noon(3, 12), &app.projects).unwrap();
+    app.store = Some(store);
+    press(&mut app, KeyCode::Char('+'));
+    press(&mut app, KeyCode::Char('+'));
+    assert!(app.history_points.is_none(), "loaded by the worker");
+    run_store_jobs(&mut app);
+    let (range, points) = app.history_points.clone().unwrap();
+    assert_eq!(range, (date(1, 1), date(3, 15)), "the range ends today");
+    assert_eq!(points.first().map(|p| (p.day, p.active)), Some((date(2, 10), 1)));
+    assert_eq!(points.last().map(|p| (p.active, p.overdue)), Some((3, 1)));
+    insta::assert_snapshot!("stats_history", render(&app));
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_snapshot_search() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    app.api_connected = true;
+    app.handle_api_message(ApiMessage::UsersLoaded(app.users.clone()));
+    app.handle_api_message(ApiMessage::ClientsLoaded(app.clients.clone()));
+    app.handle_api_message(ApiMessage::ProjectsLoaded(app.projects.clone()));
+    app.handle_key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL));
+    for c in "acme".chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    insta::assert_snapshot!("popup_search", render(&app));
+
+    // A typo still finds the project; Enter jumps to it
+    for _ in 0..4 {
+        app.handle_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    }
+    for c in "migartion".chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(app.search.is_none());
+    assert_eq!(app.active_tab, Tab::Timeline);
+    assert_eq!(app.timeline_state.selected_project.map(|i| app.projects[i].display_name()), Some("Migration"));
+
+    // Rows hidden by a filter are still found, and picking one says why it is not shown
+    app.handle_remote(RemoteCommand::SetFilter(EntityType::Project, vec![crate::export::Filter::parse("name=website").unwrap()]));
+    app.handle_key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL));
+    for c in "migration".chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    assert_eq!(app.search.as_ref().unwrap().results[0].label, "Migration");
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(app.logs.last().unwrap().message, "Migration is hidden by the current filters");
+}
+
+#[test]
+fn test_changed_cells_flash() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    app.handle_api_message(ApiMessage::UsersLoaded(app.users.clone()));
+    app.handle_api_message(ApiMessage::ProjectsLoaded(app.projects.clone()));
+    let (bob, acme) = (Uuid::from_u128(11), Uuid::from_u128(1));
+
+    let mut users = app.users.clone();
+    users[1].role = Role::Admin;
+    app.handle_api_message(ApiMessage::UsersLoaded(users));
+    assert!(app.flash.is_lit(bob, &["role"]));
+    assert!(!app.flash.is_lit(bob, &["name", "login"]));
+
+    // Completing a project changes its client's counts, which the diff does not compare
+    let mut projects = app.projects.clone();
+    let mobile = projects.iter_mut().find(|p| p.display_name() == "Mobile App").unwrap();
+    mobile.actual_end_date = Some(date(3, 13));
+    let mobile = mobile.id;
+    app.handle_api_message(ApiMessage::ProjectsLoaded(projects));
+    assert!(app.flash.is_lit(mobile, &["actualEndDate"]));
+    assert!(app.flash.is_lit(acme, &[crate::flash::PROJECT_COUNTS]));
+
+    app.active_tab = Tab::Users;
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| ui::render(frame, &app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let row = (0..HEIGHT).find(|&y| buffer[(1, y)].symbol() == "B").unwrap();
+    let role = (0..WIDTH).find(|&x| buffer[(x, row)].symbol() == "A").unwrap();
+    assert_eq!(buffer[(role, row)].bg, crate::theme::colors::YELLOW);
+    assert_ne!(buffer[(1, row)].bg, crate::theme::colors::YELLOW);
+}
+
+#[test]
+fn test_snapshot_production_profile() {
+    let mut app = fixture();
+    app.production = Some("prod".to_string());
+    let press = |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.timeline_state.selected_project = Some(0);
+    press(&mut app, KeyCode::Char('d'));
+    for code in [KeyCode::Char('y'), KeyCode::Backspace, KeyCode::Char('p'), KeyCode::Char('r'), KeyCode::Char('o')] {
+        assert!(press(&mut app, code).is_none(), "y is typed, not a shortcut");
+    }
+    insta::assert_snapshot!("production_confirm_delete", render(&app));
+
+    assert!(press(&mut app, KeyCode::Enter).is_none(), "the name is incomplete");
+    assert_eq!(app.input_mode, InputMode::Confirming);
+    press(&mut app, KeyCode::Char('d'));
+    assert!(matches!(press(&mut app, KeyCode::Enter), Some(ApiCommand::DeleteProject(id)) if id == app.projects[0].id));
+
+    // Quitting changes nothing on the server, so yes/no still does
+    app.close_confirm();
+    app.open_confirm(ConfirmDialog::new_quit(vec!["pending".to_string()]));
+    assert!(matches!(press(&mut app, KeyCode::Char('y')), Some(ApiCommand::Shutdown)));
+}
+
+#[test]
+fn test_snapshot_watched_project_changes() {
+    let mut app = fixture();
+    app.ui_state.read_only = true;
+    app.handle_api_message(ApiMessage::ProjectsLoaded(app.projects.clone()));
+    app.timeline_state.selected_project = Some(1);
+    app.handle_key(KeyEvent::new(KeyCode::Char('W'), KeyModifiers::NONE));
+    let mobile = app.projects[1].id;
+    assert!(app.ui_state.watched.contains(&mobile));
+
+    let unread = app.ui_state.inbox.unread_count();
+    let mut projects = app.projects.clone();
+    projects[1].planned_end_date = date(3, 20);
+    projects[2].planned_end_date = date(5, 1);
+    app.active_tab = Tab::Clients;
+    app.handle_api_message(ApiMessage::ProjectsLoaded(projects));
+    assert_eq!(app.ui_state.inbox.unread_count(), unread + 1, "only the watched project is reported");
+    assert_eq!(app.ui_state.inbox.items[0].event.entity(), Some((EntityType::Project, mobile)));
+    app.last_refresh = None;
+    insta::assert_snapshot!("watched_project_toast", render(&app));
+
+    app.toasts[0].shown_at -= crate::subscriptions::TOAST_DURATION;
+    app.tick(WIDTH, HEIGHT, false);
+    assert!(app.toasts.is_empty());
+}
+
+#[test]
+fn test_snapshot_team_view() {
+    let mut app = fixture();
+    let press = |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.active_tab = Tab::Users;
+    press(&mut app, KeyCode::Char('v'));
+    assert!(app.team_view.is_none(), "no team fields loaded");
+
+    app.users[0].extra.insert("team".to_string(), "Platform".into());
+    app.users[1].extra.insert("department".to_string(), serde_json::json!({"name": "Delivery"}));
+    app.loaded.users = app.users.clone();
+    app.list_selected = 1;
+    press(&mut app, KeyCode::Char('v'));
+    assert_eq!(app.selected_user().map(|u| u.display_name()), Some("Bob"), "the highlighted user stays");
+    press(&mut app, KeyCode::Char('j'));
+    assert!(app.selected_user().is_none(), "on the Platform row");
+    press(&mut app, KeyCode::Char(' '));
+    insta::assert_snapshot!("users_team_view", render(&app));
+
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.active_tab, Tab::Timeline);
+    assert_eq!(app.expression(EntityType::Project).unwrap().as_str(), "team:\"Platform\"");
+    let names: Vec<_> = app.projects.iter().map(|p| p.display_name()).collect();
+    assert_eq!(names, ["Website", "Data Platform"], "Alice's projects");
+}
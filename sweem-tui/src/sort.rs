@@ -0,0 +1,153 @@
+//! Multi-column sorting of the Clients and Users lists.
+//!
+//! A sort spec is a list of keys in priority order, e.g. role then name for
+//! users; later keys only break ties of the earlier ones. Specs are edited in
+//! the sort dialog (`s`) and persisted per tab with the UI state.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sweem_core::stats;
+use uuid::Uuid;
+
+use crate::models::{ClientDto, ProjectDto, UserDto};
+use crate::tr;
+
+/// Column a list can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortColumn {
+    Name,
+    Address,
+    /// Completed share of the client's projects
+    CompletionRate,
+    /// Number of the client's projects
+    Total,
+    Completed,
+    Login,
+    Role,
+}
+
+impl SortColumn {
+    pub const CLIENTS: [SortColumn; 5] =
+        [SortColumn::Name, SortColumn::Address, SortColumn::CompletionRate, SortColumn::Total, SortColumn::Completed];
+    pub const USERS: [SortColumn; 3] = [SortColumn::Name, SortColumn::Login, SortColumn::Role];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortColumn::Name => tr!("sort-column-name"),
+            SortColumn::Address => tr!("sort-column-address"),
+            SortColumn::CompletionRate => tr!("sort-column-completion"),
+            SortColumn::Total => tr!("sort-column-total"),
+            SortColumn::Completed => tr!("sort-column-completed"),
+            SortColumn::Login => tr!("sort-column-login"),
+            SortColumn::Role => tr!("sort-column-role"),
+        }
+    }
+}
+
+/// One key of a sort spec
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SortKey {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+impl SortKey {
+    pub fn arrow(self) -> &'static str {
+        if self.descending { "↓" } else { "↑" }
+    }
+}
+
+/// Cycle a column: unsorted → ascending (as the last key) → descending → unsorted
+pub fn toggle(spec: &mut Vec<SortKey>, column: SortColumn) {
+    match spec.iter().position(|k| k.column == column) {
+        None => spec.push(SortKey { column, descending: false }),
+        Some(index) if !spec[index].descending => spec[index].descending = true,
+        Some(index) => {
+            spec.remove(index);
+        }
+    }
+}
+
+/// Spec as shown in list headers, e.g. "Role ↑, Name ↑"
+pub fn describe(spec: &[SortKey]) -> String {
+    spec.iter().map(|k| format!("{} {}", k.column.label(), k.arrow())).collect::<Vec<_>>().join(", ")
+}
+
+/// Compare by every key in turn
+fn compare<T>(spec: &[SortKey], a: &T, b: &T, by: impl Fn(SortColumn, &T, &T) -> Ordering) -> Ordering {
+    spec.iter().fold(Ordering::Equal, |order, key| {
+        order.then_with(|| {
+            let order = by(key.column, a, b);
+            if key.descending { order.reverse() } else { order }
+        })
+    })
+}
+
+fn by_text(a: &str, b: &str) -> Ordering {
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+/// Sort clients; project counts are evaluated as of a date, like the list shows them
+pub fn sort_clients(clients: &mut [ClientDto], spec: &[SortKey], projects: &[ProjectDto], as_of: NaiveDate) {
+    if spec.is_empty() {
+        return;
+    }
+    let counts: HashMap<Uuid, (i32, i32)> =
+        clients.iter().map(|c| (c.id, stats::client_project_counts(projects, c.id, as_of))).collect();
+    // Clients without projects have no rate and sort below 0%
+    let rate = |c: &ClientDto| {
+        let (completed, total) = counts[&c.id];
+        if total > 0 { completed as f64 / total as f64 } else { -1.0 }
+    };
+    clients.sort_by(|a, b| {
+        compare(spec, a, b, |column, a, b| match column {
+            SortColumn::Name => by_text(a.display_name(), b.display_name()),
+            SortColumn::Address => by_text(a.address.as_deref().unwrap_or(""), b.address.as_deref().unwrap_or("")),
+            SortColumn::CompletionRate => rate(a).total_cmp(&rate(b)),
+            SortColumn::Total => counts[&a.id].1.cmp(&counts[&b.id].1),
+            SortColumn::Completed => counts[&a.id].0.cmp(&counts[&b.id].0),
+            SortColumn::Login | SortColumn::Role => Ordering::Equal,
+        })
+    });
+}
+
+pub fn sort_users(users: &mut [UserDto], spec: &[SortKey]) {
+    users.sort_by(|a, b| {
+        compare(spec, a, b, |column, a, b| match column {
+            SortColumn::Name => by_text(a.display_name(), b.display_name()),
+            SortColumn::Login => by_text(a.login.as_deref().unwrap_or(""), b.login.as_deref().unwrap_or("")),
+            SortColumn::Role => (a.role as i32).cmp(&(b.role as i32)),
+            _ => Ordering::Equal,
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use sweem_core::fixtures;
+
+    use super::*;
+    use crate::models::Role;
+
+    #[test]
+    fn test_secondary_keys_break_ties() {
+        let user = |name: &str, role| UserDto { login: Some(name.to_lowercase()), role, ..fixtures::user(Uuid::new_v4(), name) };
+        let mut users = vec![user("carol", Role::Manager), user("Bob", Role::Admin), user("alice", Role::Manager)];
+
+        let mut spec = Vec::new();
+        toggle(&mut spec, SortColumn::Role);
+        toggle(&mut spec, SortColumn::Role);
+        toggle(&mut spec, SortColumn::Name);
+        assert_eq!(describe(&spec), "Role ↓, Name ↑");
+        sort_users(&mut users, &spec);
+        let names: Vec<_> = users.iter().map(|u| u.display_name()).collect();
+        assert_eq!(names, ["Bob", "alice", "carol"]);
+
+        toggle(&mut spec, SortColumn::Role);
+        assert_eq!(spec, [SortKey { column: SortColumn::Name, descending: false }], "a third toggle removes the key");
+    }
+}
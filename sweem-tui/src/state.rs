@@ -0,0 +1,92 @@
+//! Persisted UI state.
+//!
+//! Small pieces of interface state (timeline bookmarks and the like) that
+//! should survive restarts are stored as JSON under the XDG state directory.
+
+#![allow(dead_code)]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::columns::ColumnLayout;
+use crate::inbox::Inbox;
+use crate::sort::SortKey;
+
+/// File name of the persisted state inside the state directory
+const STATE_FILE: &str = "state.json";
+
+/// A saved timeline position and zoom level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bookmark {
+    /// User-provided name (e.g. "Q3 release window")
+    pub name: String,
+    /// Date shown at the left edge of the timeline
+    pub start_date: NaiveDate,
+    /// Zoom level (days per column)
+    pub days_per_column: f64,
+}
+
+/// UI state persisted across sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UiState {
+    /// Timeline bookmarks keyed by slot (1-9)
+    pub bookmarks: BTreeMap<u8, Bookmark>,
+    /// Inbox alerts with their read state
+    pub inbox: Inbox,
+    /// Starred projects and clients, pinned to the top of their views
+    pub favorites: BTreeSet<Uuid>,
+    /// Projects and clients whose changes raise a toast and an Inbox item
+    pub watched: BTreeSet<Uuid>,
+    /// Sort spec of the Clients list
+    pub client_sort: Vec<SortKey>,
+    /// Sort spec of the Users list
+    pub user_sort: Vec<SortKey>,
+    /// Column layouts set in the column dialog; unset uses `[columns]` from the config
+    pub project_columns: Option<ColumnLayout>,
+    pub client_columns: Option<ColumnLayout>,
+    pub user_columns: Option<ColumnLayout>,
+    /// Leave archived projects (completed over `[ui] archive_after_days` ago) out of the views
+    pub hide_archived: bool,
+    /// Show the telemetry readout in the status bar
+    pub show_telemetry: bool,
+    /// Never write to disk (replayed sessions must not touch the real state)
+    #[serde(skip)]
+    pub read_only: bool,
+}
+
+impl UiState {
+    /// Directory holding the state file (`$XDG_STATE_HOME/sweem-tui`)
+    pub fn dir() -> Option<PathBuf> {
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .map(|dir| dir.join("sweem-tui"))
+    }
+
+    /// Load the persisted state, falling back to defaults if missing or unreadable
+    pub fn load() -> Self {
+        Self::dir()
+            .map(|dir| dir.join(STATE_FILE))
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the state to disk
+    pub fn save(&self) -> Result<()> {
+        if self.read_only {
+            return Ok(());
+        }
+        let dir = Self::dir().context("Could not determine state directory")?;
+        fs::create_dir_all(&dir).context("Failed to create state directory")?;
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize UI state")?;
+        fs::write(dir.join(STATE_FILE), content).context("Failed to write UI state")
+    }
+}
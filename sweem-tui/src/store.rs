@@ -0,0 +1,389 @@
+//! Local snapshot history (SQLite).
+//!
+//! Every refresh stores the loaded projects, clients and users in
+//! `$XDG_STATE_HOME/sweem-tui/history.sqlite`, keyed by API base URL and
+//! timestamp. A load identical to the previous one only moves that
+//! snapshot's `seen_at` forward, so an idle backend does not grow the file.
+//! The latest snapshots are shown at startup until the API answers (or when
+//! it does not), and time travel (`@`) to a past date shows the snapshot that
+//! was current on that day instead of today's data, and the Stats tab charts
+//! how the projects developed from day to day.
+//!
+//! Recording a load and reading the Stats history are [`StoreJob`]s that the
+//! API worker runs on a blocking thread, so neither SQLite nor serializing
+//! whole snapshots holds up the UI.
+//!
+//! [`HistorySource`] serves the latest snapshots as a read-only data source,
+//! so `export` still works offline when wrapped in a `Fallback`.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sweem_core::source::{DataSource, SourceFuture};
+use uuid::Uuid;
+
+use crate::api::{ApiError, ApiMessage, EntityType};
+use crate::models::{
+    ClientDto, CreateClientDto, CreateProjectDto, CreateUserDto, ProjectDto, UpdateClientDto, UpdateProjectDto,
+    UpdateUserDto, UserDto,
+};
+use crate::state::UiState;
+use crate::timeseries;
+
+/// File name of the database inside the state directory
+const STORE_FILE: &str = "history.sqlite";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS snapshots (
+        id       INTEGER PRIMARY KEY,
+        source   TEXT NOT NULL,
+        entity   TEXT NOT NULL,
+        taken_at TEXT NOT NULL,
+        seen_at  TEXT NOT NULL,
+        data     TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS snapshots_lookup ON snapshots (source, entity, taken_at);
+";
+
+/// Entities of one type as they were loaded at some point
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recorded<T> {
+    /// Last time the API returned exactly this data (capped at the requested point in time)
+    pub at: DateTime<Utc>,
+    pub items: Vec<T>,
+}
+
+/// Snapshot database for one API base URL; clones share the connection
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    conn: Arc<Mutex<Connection>>,
+    source: String,
+}
+
+fn entity_key(entity: EntityType) -> &'static str {
+    match entity {
+        EntityType::Project => "project",
+        EntityType::Client => "client",
+        EntityType::User => "user",
+    }
+}
+
+/// Timestamps are stored as fixed-width UTC text, so they compare as strings
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn parse_timestamp(text: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(text).with_context(|| format!("Bad timestamp {:?} in history", text))?.to_utc())
+}
+
+impl SnapshotStore {
+    /// Open (or create) the database in the state directory, dropping
+    /// snapshots older than `retention_days`
+    pub fn open_default(source: &str, retention_days: u32) -> Result<Self> {
+        let dir = UiState::dir().context("Could not determine state directory")?;
+        std::fs::create_dir_all(&dir).context("Failed to create state directory")?;
+        let store = Self::open(&dir.join(STORE_FILE), source)?;
+        let removed = store.prune(Utc::now() - Duration::days(retention_days.into()))?;
+        if removed > 0 {
+            tracing::debug!(removed, "Pruned old snapshots");
+        }
+        Ok(store)
+    }
+
+    pub fn open(path: &Path, source: &str) -> Result<Self> {
+        let conn = Connection::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        Self::with_connection(conn, source)
+    }
+
+    fn with_connection(conn: Connection, source: &str) -> Result<Self> {
+        conn.execute_batch(SCHEMA).context("Failed to create the history schema")?;
+        Ok(Self { conn: Arc::new(Mutex::new(conn)), source: source.trim_end_matches('/').to_string() })
+    }
+
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Store a load; returns `false` when it matched the previous snapshot
+    pub fn record<T: Serialize>(&self, entity: EntityType, at: DateTime<Utc>, items: &[T]) -> Result<bool> {
+        let data = serde_json::to_string(items).context("Failed to serialize snapshot")?;
+        let conn = self.conn();
+        let latest: Option<(i64, String)> = conn
+            .query_row(
+                "SELECT id, data FROM snapshots WHERE source = ?1 AND entity = ?2 ORDER BY taken_at DESC LIMIT 1",
+                params![self.source, entity_key(entity)],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let stamp = timestamp(at);
+        if let Some((id, _)) = latest.filter(|(_, previous)| *previous == data) {
+            conn.execute("UPDATE snapshots SET seen_at = ?1 WHERE id = ?2", params![stamp, id])?;
+            return Ok(false);
+        }
+        conn.execute(
+            "INSERT INTO snapshots (source, entity, taken_at, seen_at, data) VALUES (?1, ?2, ?3, ?3, ?4)",
+            params![self.source, entity_key(entity), stamp, data],
+        )?;
+        Ok(true)
+    }
+
+    /// The snapshot current at `at` (the latest one when `None`)
+    pub fn load<T: DeserializeOwned>(&self, entity: EntityType, at: Option<DateTime<Utc>>) -> Result<Option<Recorded<T>>> {
+        let cutoff = at.map(timestamp).unwrap_or_else(|| "9999".to_string());
+        let row: Option<(String, String)> = self
+            .conn()
+            .query_row(
+                "SELECT seen_at, data FROM snapshots WHERE source = ?1 AND entity = ?2 AND taken_at <= ?3
+                 ORDER BY taken_at DESC LIMIT 1",
+                params![self.source, entity_key(entity), cutoff],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((seen_at, data)) = row else {
+            return Ok(None);
+        };
+        let seen_at = parse_timestamp(&seen_at)?;
+        let items = serde_json::from_str(&data).context("Failed to read snapshot")?;
+        Ok(Some(Recorded { at: at.map_or(seen_at, |at| seen_at.min(at)), items }))
+    }
+
+    /// Snapshots in effect between `from` and `to`, oldest first: the one
+    /// current at `from` and every one taken after it up to `to`
+    pub fn series<T: DeserializeOwned>(
+        &self,
+        entity: EntityType,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<(DateTime<Utc>, Vec<T>)>> {
+        let conn = self.conn();
+        let mut statement = conn.prepare(
+            "SELECT taken_at, data FROM snapshots WHERE source = ?1 AND entity = ?2 AND taken_at <= ?4
+             AND taken_at >= COALESCE(
+                 (SELECT MAX(taken_at) FROM snapshots WHERE source = ?1 AND entity = ?2 AND taken_at <= ?3), ?3)
+             ORDER BY taken_at",
+        )?;
+        let rows = statement.query_map(params![self.source, entity_key(entity), timestamp(from), timestamp(to)], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        rows.map(|row| {
+            let (taken_at, data) = row?;
+            Ok((parse_timestamp(&taken_at)?, serde_json::from_str(&data).context("Failed to read snapshot")?))
+        })
+        .collect()
+    }
+
+    /// Delete snapshots last seen before `cutoff`, keeping the latest of each
+    /// entity type so offline startup still has something to show
+    pub fn prune(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        Ok(self.conn().execute(
+            "DELETE FROM snapshots WHERE seen_at < ?1
+             AND id NOT IN (SELECT MAX(id) FROM snapshots GROUP BY source, entity)",
+            params![timestamp(cutoff)],
+        )?)
+    }
+}
+
+/// A live load to record, kept typed so it is serialized on the worker
+#[derive(Debug, Clone)]
+pub enum Load {
+    Projects(Vec<ProjectDto>),
+    Clients(Vec<ClientDto>),
+    Users(Vec<UserDto>),
+}
+
+/// Snapshot store work for the API worker
+#[derive(Debug, Clone)]
+pub enum StoreJob {
+    /// Record a load taken at the given time
+    Record(SnapshotStore, DateTime<Utc>, Load),
+    /// Count the active and overdue projects of each day in `from..to` for
+    /// the Stats history page
+    History(SnapshotStore, (NaiveDate, NaiveDate)),
+}
+
+impl StoreJob {
+    /// Do the (blocking) work; the answer is a message for the UI
+    pub fn run(self) -> ApiMessage {
+        match self {
+            StoreJob::Record(store, at, load) => {
+                let (entity, result) = match &load {
+                    Load::Projects(items) => (EntityType::Project, store.record(EntityType::Project, at, items)),
+                    Load::Clients(items) => (EntityType::Client, store.record(EntityType::Client, at, items)),
+                    Load::Users(items) => (EntityType::User, store.record(EntityType::User, at, items)),
+                };
+                ApiMessage::SnapshotRecorded { entity, result: result.map_err(|e| format!("{:#}", e)) }
+            }
+            StoreJob::History(store, (from, to)) => {
+                let instant = |day: NaiveDate| day.and_time(NaiveTime::MIN).and_local_timezone(Local).earliest().map(|at| at.to_utc());
+                let result = match (instant(from), instant(to)) {
+                    (Some(start), Some(end)) => store
+                        .series::<ProjectDto>(EntityType::Project, start, end)
+                        .map(|snapshots| timeseries::daily(&snapshots, from, to))
+                        .map_err(|e| format!("{:#}", e)),
+                    _ => Ok(Vec::new()),
+                };
+                ApiMessage::HistoryLoaded { range: (from, to), result }
+            }
+        }
+    }
+}
+
+/// The latest snapshots as a [`DataSource`]; changes are refused
+#[derive(Debug)]
+pub struct HistorySource {
+    store: SnapshotStore,
+}
+
+impl HistorySource {
+    pub fn new(store: SnapshotStore) -> Self {
+        Self { store }
+    }
+
+    fn latest<T: DeserializeOwned>(&self, entity: EntityType) -> Result<Vec<T>, ApiError> {
+        let endpoint = format!("history {}", entity_key(entity));
+        match self.store.load(entity, None) {
+            Ok(Some(recorded)) => Ok(recorded.items),
+            Ok(None) => Err(ApiError::Status { endpoint, status: 404, message: "Nothing recorded yet".to_string() }),
+            Err(e) => Err(ApiError::Decode { endpoint, message: format!("{:#}", e) }),
+        }
+    }
+
+    /// One entity of the latest snapshot
+    fn find<T: DeserializeOwned>(&self, entity: EntityType, id: Uuid, key: fn(&T) -> Uuid) -> Result<T, ApiError> {
+        self.latest(entity)?.into_iter().find(|item| key(item) == id).ok_or_else(|| ApiError::Status {
+            endpoint: format!("history {}", entity_key(entity)),
+            status: 404,
+            message: format!("{} {} is not in the history", entity, id),
+        })
+    }
+}
+
+fn read_only<'a, T: Send + 'a>() -> SourceFuture<'a, T> {
+    let error = ApiError::Status {
+        endpoint: "history".to_string(),
+        status: 405,
+        message: "The local history is read-only".to_string(),
+    };
+    Box::pin(std::future::ready(Err(error)))
+}
+
+impl DataSource for HistorySource {
+    fn health_check(&self) -> SourceFuture<'_, bool> {
+        Box::pin(std::future::ready(Ok(true)))
+    }
+
+    fn fetch_all_projects(&self) -> SourceFuture<'_, Vec<ProjectDto>> {
+        Box::pin(std::future::ready(self.latest(EntityType::Project)))
+    }
+
+    fn fetch_all_clients(&self) -> SourceFuture<'_, Vec<ClientDto>> {
+        Box::pin(std::future::ready(self.latest(EntityType::Client)))
+    }
+
+    fn fetch_all_users(&self) -> SourceFuture<'_, Vec<UserDto>> {
+        Box::pin(std::future::ready(self.latest(EntityType::User)))
+    }
+
+    fn fetch_project(&self, id: Uuid) -> SourceFuture<'_, ProjectDto> {
+        Box::pin(std::future::ready(self.find(EntityType::Project, id, |p: &ProjectDto| p.id)))
+    }
+
+    fn fetch_client(&self, id: Uuid) -> SourceFuture<'_, ClientDto> {
+        Box::pin(std::future::ready(self.find(EntityType::Client, id, |c: &ClientDto| c.id)))
+    }
+
+    fn fetch_user(&self, id: Uuid) -> SourceFuture<'_, UserDto> {
+        Box::pin(std::future::ready(self.find(EntityType::User, id, |u: &UserDto| u.id)))
+    }
+
+    fn create_project<'a>(&'a self, _project: &'a CreateProjectDto) -> SourceFuture<'a, Uuid> {
+        read_only()
+    }
+
+    fn update_project<'a>(&'a self, _id: Uuid, _project: &'a UpdateProjectDto) -> SourceFuture<'a, ProjectDto> {
+        read_only()
+    }
+
+    fn delete_project(&self, _id: Uuid) -> SourceFuture<'_, Uuid> {
+        read_only()
+    }
+
+    fn create_client<'a>(&'a self, _client: &'a CreateClientDto) -> SourceFuture<'a, Uuid> {
+        read_only()
+    }
+
+    fn update_client<'a>(&'a self, _id: Uuid, _client: &'a UpdateClientDto) -> SourceFuture<'a, ClientDto> {
+        read_only()
+    }
+
+    fn delete_client(&self, _id: Uuid) -> SourceFuture<'_, Uuid> {
+        read_only()
+    }
+
+    fn create_user<'a>(&'a self, _user: &'a CreateUserDto) -> SourceFuture<'a, Uuid> {
+        read_only()
+    }
+
+    fn update_user<'a>(&'a self, _id: Uuid, _user: &'a UpdateUserDto) -> SourceFuture<'a, UserDto> {
+        read_only()
+    }
+
+    fn delete_user(&self, _id: Uuid) -> SourceFuture<'_, Uuid> {
+        read_only()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use sweem_core::fixtures;
+
+    #[test]
+    fn test_record_load_and_prune() {
+        let store = SnapshotStore::with_connection(Connection::open_in_memory().unwrap(), "http://api/").unwrap();
+        let day = |d| Utc.with_ymd_and_hms(2025, 3, d, 12, 0, 0).unwrap();
+        assert!(store.load::<String>(EntityType::Client, None).unwrap().is_none());
+
+        assert!(store.record(EntityType::Client, day(1), &["Acme"]).unwrap());
+        assert!(!store.record(EntityType::Client, day(2), &["Acme"]).unwrap(), "unchanged loads are merged");
+        assert!(store.record(EntityType::Client, day(5), &["Acme", "Globex"]).unwrap());
+        store.record(EntityType::User, day(5), &["Alice"]).unwrap();
+
+        let latest = store.load::<String>(EntityType::Client, None).unwrap().unwrap();
+        assert_eq!((latest.at, latest.items.len()), (day(5), 2));
+        let then = store.load::<String>(EntityType::Client, Some(day(3))).unwrap().unwrap();
+        assert_eq!((then.at, then.items), (day(2), vec!["Acme".to_string()]), "valid until it was last seen");
+        assert!(store.load::<String>(EntityType::Client, Some(day(1) - Duration::hours(1))).unwrap().is_none());
+
+        let series = store.series::<String>(EntityType::Client, day(3), day(30)).unwrap();
+        let counts: Vec<_> = series.iter().map(|(at, items)| (*at, items.len())).collect();
+        assert_eq!(counts, [(day(1), 1), (day(5), 2)], "starts with the snapshot current at the start");
+        assert!(store.series::<String>(EntityType::Client, day(1) - Duration::days(9), day(1) - Duration::days(2)).unwrap().is_empty());
+
+        assert_eq!(store.prune(day(30)).unwrap(), 1, "the latest snapshot of each type survives");
+        assert!(store.load::<String>(EntityType::Client, Some(day(3))).unwrap().is_none());
+
+        let other = SnapshotStore { conn: store.conn, source: "http://other".to_string() };
+        assert!(other.load::<String>(EntityType::User, None).unwrap().is_none(), "backends are kept apart");
+    }
+
+    #[tokio::test]
+    async fn test_history_source_serves_the_latest_snapshot() {
+        let store = SnapshotStore::with_connection(Connection::open_in_memory().unwrap(), "http://api").unwrap();
+        let user = UserDto { login: Some("alice".into()), role: Default::default(), ..fixtures::user(Uuid::from_u128(1), "Alice") };
+        store.record(EntityType::User, Utc::now(), std::slice::from_ref(&user)).unwrap();
+        let history = HistorySource::new(store);
+
+        assert_eq!(history.fetch_user(user.id).await.unwrap().login, user.login);
+        assert_eq!(history.fetch_user(Uuid::from_u128(2)).await.unwrap_err().status(), Some(404));
+        assert_eq!(history.fetch_all_projects().await.unwrap_err().status(), Some(404), "nothing recorded");
+        assert_eq!(history.delete_user(user.id).await.unwrap_err().status(), Some(405));
+    }
+}
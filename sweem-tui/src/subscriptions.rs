@@ -0,0 +1,102 @@
+//! Watched projects and clients.
+//!
+//! `W` subscribes to the selected project or client. Whenever a load finds
+//! it changed (the same diff as the change report, so edits made elsewhere
+//! show up on the next refresh or watch-mode reload), the change lands in
+//! the Inbox and pops up as a toast over whatever tab is open. The watch
+//! list is kept with the UI state.
+
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::diff::{self, Change, ChangeKind};
+use crate::inbox::InboxEvent;
+use crate::models::{ClientDto, UserDto};
+use crate::tr;
+
+/// How long a toast stays up
+pub const TOAST_DURATION: Duration = Duration::from_secs(8);
+/// Toasts shown at once; older ones make room
+pub const MAX_TOASTS: usize = 3;
+
+/// Inbox events for the changes that touch watched entities
+pub fn events(changes: &[Change], watched: &BTreeSet<Uuid>, clients: &[ClientDto], users: &[UserDto]) -> Vec<InboxEvent> {
+    changes
+        .iter()
+        .filter(|change| watched.contains(&change.id))
+        .map(|change| InboxEvent::Watched {
+            entity: change.entity_type,
+            id: change.id,
+            name: change.name.clone(),
+            detail: describe(&change.kind, clients, users),
+        })
+        .collect()
+}
+
+/// What changed, in one line: `Planned end: 2025-03-01 → 2025-03-08, …`
+fn describe(kind: &ChangeKind, clients: &[ClientDto], users: &[UserDto]) -> String {
+    match kind {
+        ChangeKind::Added => tr!("watch-added").to_string(),
+        ChangeKind::Removed => tr!("watch-removed").to_string(),
+        ChangeKind::Modified(fields) => fields
+            .iter()
+            .map(|f| {
+                let value = |v: &str| diff::display_value(f.field, v, clients, users);
+                format!("{}: {} → {}", diff::field_label(f.field), value(&f.old), value(&f.new))
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+/// A short-lived notice drawn over the current tab
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    pub message: String,
+    pub shown_at: Instant,
+}
+
+impl Toast {
+    pub fn new(message: String, now: Instant) -> Self {
+        Self { message, shown_at: now }
+    }
+
+    pub fn expired(&self, now: Instant) -> bool {
+        now.duration_since(self.shown_at) >= TOAST_DURATION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::EntityType;
+    use crate::diff::FieldChange;
+
+    #[test]
+    fn test_only_watched_changes_raise_events() {
+        let change = |n: u128, kind| Change { entity_type: EntityType::Project, id: Uuid::from_u128(n), name: format!("P{}", n), kind };
+        let changes = [
+            change(
+                1,
+                ChangeKind::Modified(vec![FieldChange {
+                    field: "plannedEndDate",
+                    old: "2025-03-01".into(),
+                    new: "2025-03-08".into(),
+                }]),
+            ),
+            change(2, ChangeKind::Removed),
+            change(3, ChangeKind::Removed),
+        ];
+        let watched = BTreeSet::from([Uuid::from_u128(1), Uuid::from_u128(2)]);
+        let events = events(&changes, &watched, &[], &[]);
+        let messages: Vec<String> = events.iter().map(InboxEvent::message).collect();
+        assert_eq!(messages, ["P1: Planned end: 2025-03-01 → 2025-03-08", "P2: removed"]);
+        assert_eq!(events[0].project(), Some(Uuid::from_u128(1)));
+
+        let toast = Toast::new(messages[0].clone(), Instant::now());
+        assert!(!toast.expired(toast.shown_at));
+        assert!(toast.expired(toast.shown_at + TOAST_DURATION));
+    }
+}
@@ -0,0 +1,126 @@
+//! Users grouped by team (`v` on the Users tab).
+//!
+//! The API has no teams endpoint, but backends that know about teams or
+//! departments send them as an extra user field (`team` or `department`,
+//! either a name or an object with a `name`). The team view turns the Users
+//! list into a tree: one collapsible row per team with its member and
+//! project counts, the members below it, and users without a team last.
+//! Projects get a derived `team` field (their manager's) for filters such as
+//! `team:Platform`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+
+use crate::models::{ProjectDto, UserDto};
+
+/// Extra user fields read as the team, in order of preference
+pub const TEAM_FIELDS: [&str; 2] = ["team", "department"];
+
+/// Team or department of a user, if the API sent one
+pub fn team_of(user: &UserDto) -> Option<&str> {
+    TEAM_FIELDS.iter().find_map(|field| match user.extra.get(*field)? {
+        Value::String(name) => Some(name.as_str()),
+        Value::Object(team) => team.get("name")?.as_str(),
+        _ => None,
+    })
+    .filter(|name| !name.is_empty())
+}
+
+/// Whether any loaded user has a team
+pub fn has_teams(users: &[UserDto]) -> bool {
+    users.iter().any(|user| team_of(user).is_some())
+}
+
+/// One row of the team tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeRow {
+    /// Team header; `None` collects the users without a team
+    Team { name: Option<String>, members: usize, projects: usize, collapsed: bool },
+    /// Index into the users list
+    User(usize),
+}
+
+/// Team tree over `users` (in their list order), teams by name; members of
+/// collapsed teams are left out
+pub fn rows(users: &[UserDto], projects: &[ProjectDto], collapsed: &BTreeSet<Option<String>>) -> Vec<TreeRow> {
+    let mut teams: BTreeMap<Option<&str>, Vec<usize>> = BTreeMap::new();
+    for (index, user) in users.iter().enumerate() {
+        teams.entry(team_of(user)).or_default().push(index);
+    }
+    // BTreeMap puts `None` first; users without a team go last
+    let mut teams: Vec<_> = teams.into_iter().collect();
+    if teams.first().is_some_and(|(name, _)| name.is_none()) {
+        teams.rotate_left(1);
+    }
+
+    let mut rows = Vec::new();
+    for (name, members) in teams {
+        let name = name.map(str::to_string);
+        let projects = projects.iter().filter(|p| members.iter().any(|&i| users[i].id == p.manager_id)).count();
+        let collapsed = collapsed.contains(&name);
+        rows.push(TreeRow::Team { name, members: members.len(), projects, collapsed });
+        if !collapsed {
+            rows.extend(members.into_iter().map(TreeRow::User));
+        }
+    }
+    rows
+}
+
+/// Open team view of the Users tab
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TeamView {
+    /// Highlighted row of [`rows`]
+    pub cursor: usize,
+    /// Teams shown without their members
+    pub collapsed: BTreeSet<Option<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use serde_json::json;
+    use sweem_core::fixtures;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_users_are_grouped_by_team() {
+        let user = |n: u128, team: Value| UserDto {
+            extra: [("team".to_string(), team)].into_iter().filter(|(_, v)| !v.is_null()).collect(),
+            ..fixtures::user(Uuid::from_u128(n), &format!("U{}", n))
+        };
+        let users = vec![
+            user(1, json!("Platform")),
+            user(2, Value::Null),
+            user(3, json!({"id": 7, "name": "Data"})),
+            user(4, json!("Platform")),
+        ];
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let project = |manager: u128| ProjectDto { manager_id: Uuid::from_u128(manager), ..fixtures::project(date, date) };
+        let projects = vec![project(1), project(4), project(4), project(2)];
+        let team = |name: Option<&str>, members, projects, collapsed| TreeRow::Team {
+            name: name.map(str::to_string),
+            members,
+            projects,
+            collapsed,
+        };
+
+        assert_eq!(team_of(&users[2]), Some("Data"));
+        assert_eq!(
+            rows(&users, &projects, &BTreeSet::new()),
+            [
+                team(Some("Data"), 1, 0, false),
+                TreeRow::User(2),
+                team(Some("Platform"), 2, 3, false),
+                TreeRow::User(0),
+                TreeRow::User(3),
+                team(None, 1, 1, false),
+                TreeRow::User(1),
+            ]
+        );
+        let collapsed = BTreeSet::from([Some("Platform".to_string())]);
+        assert_eq!(rows(&users, &projects, &collapsed)[2..4], [team(Some("Platform"), 2, 3, true), team(None, 1, 1, false)]);
+    }
+}
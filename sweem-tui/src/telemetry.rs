@@ -0,0 +1,75 @@
+//! Compact telemetry readout in the status bar (`Ctrl+t`).
+//!
+//! On small shared servers it helps to see at a glance that the TUI is
+//! behaving: the measured frame rate, how long the last draw took, how many
+//! entities are loaded and roughly how much memory the process holds. The
+//! resident set size is read from `/proc/self/statm` at most once per
+//! [`SAMPLE_EVERY`], assuming 4 KiB pages; elsewhere it is left out.
+
+use std::time::{Duration, Instant};
+
+use crate::pacing::FrameStats;
+use crate::tr;
+
+/// How often the memory footprint is sampled
+pub const SAMPLE_EVERY: Duration = Duration::from_secs(1);
+
+/// Page size assumed when converting `statm` pages to bytes
+const PAGE_SIZE: u64 = 4096;
+
+/// Memory footprint samples
+#[derive(Debug, Clone, Default)]
+pub struct Telemetry {
+    /// Resident set size in bytes, if it can be read
+    pub rss: Option<u64>,
+    sampled_at: Option<Instant>,
+}
+
+impl Telemetry {
+    /// Sample the memory footprint if the last sample is old enough; returns
+    /// whether it did (and the readout should be redrawn)
+    pub fn sample(&mut self, now: Instant) -> bool {
+        if self.sampled_at.is_some_and(|at| now.duration_since(at) < SAMPLE_EVERY) {
+            return false;
+        }
+        self.sampled_at = Some(now);
+        self.rss = std::fs::read_to_string("/proc/self/statm").ok().and_then(|statm| resident_bytes(&statm));
+        true
+    }
+}
+
+/// Resident set size from the contents of `/proc/self/statm`
+fn resident_bytes(statm: &str) -> Option<u64> {
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * PAGE_SIZE)
+}
+
+/// One-line readout: frame rate, last draw time, loaded entities and memory
+pub fn readout(stats: FrameStats, entities: usize, rss: Option<u64>) -> String {
+    let rss = match rss {
+        Some(bytes) => format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+        None => tr!("telemetry-rss-unknown").to_string(),
+    };
+    tr!(
+        "telemetry-readout",
+        fps = format!("{:.0}", stats.fps),
+        render = format!("{:.1}", stats.render_ms),
+        entities = entities,
+        rss = rss,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readout() {
+        assert_eq!(resident_bytes("5000 2560 300 10 0 900 0\n"), Some(10 * 1024 * 1024));
+        assert_eq!(resident_bytes("garbage"), None);
+
+        let stats = FrameStats { target_fps: 30, fps: 29.6, dropped_updates: 0, render_ms: 1.24 };
+        assert_eq!(readout(stats, 9, Some(10 * 1024 * 1024)), "30 fps · 1.2 ms · 9 loaded · RSS 10.0 MiB");
+        assert_eq!(readout(stats, 9, None), "30 fps · 1.2 ms · 9 loaded · RSS n/a");
+    }
+}
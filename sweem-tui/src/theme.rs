@@ -142,6 +142,22 @@ pub mod styles {
         Style::default().fg(colors::RED)
     }
 
+    /// Banner warning that the shown data is stale
+    pub fn stale() -> Style {
+        Style::default()
+            .fg(colors::BG_DARK)
+            .bg(colors::ORANGE)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Banner of a production profile
+    pub fn production() -> Style {
+        Style::default()
+            .fg(colors::BG_DARK)
+            .bg(colors::RED_LIGHT)
+            .add_modifier(Modifier::BOLD)
+    }
+
     /// Style for warning messages
     pub fn warning() -> Style {
         Style::default().fg(colors::YELLOW)
@@ -152,6 +168,41 @@ pub mod styles {
         Style::default().fg(colors::BLUE)
     }
 
+    /// Star of favorite projects and clients
+    pub fn favorite() -> Style {
+        Style::default()
+            .fg(colors::YELLOW)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Mark of watched projects and clients
+    pub fn watched() -> Style {
+        Style::default()
+            .fg(colors::YELLOW)
+    }
+
+    /// Flag of projects marked for a batch action
+    pub fn marked() -> Style {
+        Style::default()
+            .fg(colors::BLUE_LIGHT)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Style for projects breaching an SLA rule
+    pub fn sla_breach() -> Style {
+        Style::default()
+            .fg(colors::MAGENTA)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    }
+
+    /// Cells whose value just changed
+    pub fn flash() -> Style {
+        Style::default()
+            .fg(colors::BG_DARK)
+            .bg(colors::YELLOW)
+            .add_modifier(Modifier::BOLD)
+    }
+
     /// Style for selected/highlighted items
     pub fn selected() -> Style {
         Style::default()
@@ -9,6 +9,9 @@
 
 #![allow(dead_code)]
 
+use std::cell::Cell;
+use std::collections::BTreeSet;
+
 use chrono::{Datelike, Duration, NaiveDate};
 use ratatui::{
     buffer::Buffer,
@@ -16,9 +19,14 @@ use ratatui::{
     style::{Modifier, Style},
     widgets::{Block, Borders, Widget},
 };
+use uuid::Uuid;
 
+use crate::due;
+use crate::flash::CellFlash;
 use crate::models::ProjectDto;
+use crate::sla::{self, SlaRule};
 use crate::theme::{colors, styles, get_project_color};
+use crate::tr;
 
 /// Unicode block characters for smooth rendering
 const BLOCK_FULL: char = '█';
@@ -34,6 +42,15 @@ const BLOCK_DARK: char = '▓';
 const STATUS_COMPLETED: char = '✓';
 const STATUS_OVERDUE: char = '!';
 const STATUS_ACTIVE: char = '●';
+const SLA_BREACH: char = '⚑';
+const FAVORITE: char = '★';
+const WATCHED: char = '◉';
+const MARKED: char = '◆';
+
+/// Columns left of the bars: status and marks (3), name (21), spacing (2)
+pub const NAME_COLUMN_WIDTH: u16 = 26;
+/// Rows of the time axis above the first project
+const AXIS_HEIGHT: u16 = 2;
 
 /// Modern border characters
 const BORDER_TL: char = '╭';
@@ -43,6 +60,46 @@ const BORDER_BR: char = '╯';
 const BORDER_H: char = '─';
 const BORDER_V: char = '│';
 
+/// Calendar boundary the timeline snaps to when scrolling coarsely
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapUnit {
+    /// Snap to Mondays
+    Week,
+    /// Snap to the first day of the month
+    Month,
+}
+
+impl SnapUnit {
+    /// First boundary strictly after the given date
+    pub fn next_boundary(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            SnapUnit::Week => {
+                let days_to_monday = 7 - date.weekday().num_days_from_monday() as i64;
+                date + Duration::days(days_to_monday)
+            }
+            SnapUnit::Month => {
+                let (year, month) = if date.month() == 12 {
+                    (date.year() + 1, 1)
+                } else {
+                    (date.year(), date.month() + 1)
+                };
+                NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(date)
+            }
+        }
+    }
+
+    /// Last boundary strictly before the given date
+    pub fn previous_boundary(&self, date: NaiveDate) -> NaiveDate {
+        let day_before = date - Duration::days(1);
+        match self {
+            SnapUnit::Week => {
+                day_before - Duration::days(day_before.weekday().num_days_from_monday() as i64)
+            }
+            SnapUnit::Month => day_before.with_day(1).unwrap_or(day_before),
+        }
+    }
+}
+
 /// Timeline widget state
 #[derive(Debug, Clone)]
 pub struct TimelineState {
@@ -54,6 +111,9 @@ pub struct TimelineState {
     pub days_per_column: f64,
     /// Animation frame counter for effects
     pub animation_frame: u64,
+    /// Screen area of the project rows as last drawn (bars start
+    /// `NAME_COLUMN_WIDTH` in); `None` before the first draw and while comparing
+    pub rows_area: Cell<Option<Rect>>,
 }
 
 impl Default for TimelineState {
@@ -63,6 +123,7 @@ impl Default for TimelineState {
             selected_project: None,
             days_per_column: 1.0,
             animation_frame: 0,
+            rows_area: Cell::new(None),
         }
     }
 }
@@ -78,6 +139,47 @@ impl TimelineState {
         self.scroll_offset = self.scroll_offset.saturating_add(amount);
     }
 
+    /// Column of a date right of the name column, negative before the viewport
+    pub fn date_column(&self, date: NaiveDate, start: NaiveDate) -> i64 {
+        let days_with_offset = (date - start).num_days() - self.scroll_offset;
+        (days_with_offset as f64 / self.days_per_column) as i64
+    }
+
+    /// Date shown at the left edge of the viewport
+    pub fn viewport_start(&self, projects: &[ProjectDto]) -> NaiveDate {
+        self.calculate_timeline_start(projects) + Duration::days(self.scroll_offset)
+    }
+
+    /// Scroll so that the given date is at the left edge of the viewport
+    pub fn scroll_to_date(&mut self, date: NaiveDate, projects: &[ProjectDto]) {
+        self.scroll_offset = (date - self.calculate_timeline_start(projects)).num_days();
+    }
+
+    /// Calendar unit used for snap scrolling at the current zoom level
+    pub fn snap_unit(&self) -> SnapUnit {
+        if self.days_per_column >= 4.0 {
+            SnapUnit::Month
+        } else {
+            SnapUnit::Week
+        }
+    }
+
+    /// Snap left to the previous week/month boundary
+    pub fn snap_left(&mut self, projects: &[ProjectDto]) {
+        let start = self.calculate_timeline_start(projects);
+        let current = start + Duration::days(self.scroll_offset);
+        let target = self.snap_unit().previous_boundary(current);
+        self.scroll_offset = (target - start).num_days();
+    }
+
+    /// Snap right to the next week/month boundary
+    pub fn snap_right(&mut self, projects: &[ProjectDto]) {
+        let start = self.calculate_timeline_start(projects);
+        let current = start + Duration::days(self.scroll_offset);
+        let target = self.snap_unit().next_boundary(current);
+        self.scroll_offset = (target - start).num_days();
+    }
+
     /// Move selection up
     pub fn select_previous(&mut self, total: usize) {
         if total == 0 {
@@ -120,9 +222,13 @@ impl TimelineState {
 
     /// Center the timeline on today
     pub fn center_on_today(&mut self, projects: &[ProjectDto], width: u16) {
-        let today = chrono::Local::now().date_naive();
+        self.center_on_date(crate::clock::today(), projects, width);
+    }
+
+    /// Center the timeline on a specific date
+    pub fn center_on_date(&mut self, date: NaiveDate, projects: &[ProjectDto], width: u16) {
         let start = self.calculate_timeline_start(projects);
-        let days_from_start = (today - start).num_days();
+        let days_from_start = (date - start).num_days();
         // Account for name column width (~26 chars) in viewport calculation
         let effective_width = width.saturating_sub(26) as i64;
         let center_offset = (effective_width / 2) * self.days_per_column as i64;
@@ -148,12 +254,12 @@ impl TimelineState {
     }
 
     /// Calculate the start date of the timeline
-    fn calculate_timeline_start(&self, projects: &[ProjectDto]) -> NaiveDate {
+    pub fn calculate_timeline_start(&self, projects: &[ProjectDto]) -> NaiveDate {
         projects
             .iter()
             .map(|p| p.start_date)
             .min()
-            .unwrap_or_else(|| chrono::Local::now().date_naive() - Duration::days(30))
+            .unwrap_or_else(|| crate::clock::today() - Duration::days(30))
     }
 
     /// Advance animation frame
@@ -167,6 +273,26 @@ pub struct TimelineWidget<'a> {
     projects: &'a [ProjectDto],
     state: &'a TimelineState,
     title: &'a str,
+    /// Shared start date (keeps several timelines in the same date window)
+    start: Option<NaiveDate>,
+    /// Index of the highlighted project within `projects`
+    selected: Option<usize>,
+    /// Date project statuses and the "today" marker are evaluated against
+    reference_date: NaiveDate,
+    /// Reserve the bottom row for a capacity (concurrent projects) lane
+    capacity_lane: bool,
+    /// SLA rules; breaching projects are flagged
+    sla_rules: &'a [SlaRule],
+    /// Starred projects, marked before their name
+    favorites: Option<&'a BTreeSet<Uuid>>,
+    /// Watched projects, marked after the star
+    watched: Option<&'a BTreeSet<Uuid>>,
+    /// Projects marked for batch actions, flagged in the leftmost column
+    marked: Option<&'a BTreeSet<Uuid>>,
+    /// Recently changed cells (names and bars)
+    flash: Option<&'a CellFlash>,
+    /// Store the drawn rows in `TimelineState::rows_area` (the main timeline)
+    remember_rows: bool,
 }
 
 impl<'a> TimelineWidget<'a> {
@@ -175,28 +301,99 @@ impl<'a> TimelineWidget<'a> {
             projects,
             state,
             title: " Project Timeline ",
+            start: None,
+            selected: state.selected_project,
+            reference_date: crate::clock::today(),
+            capacity_lane: false,
+            sla_rules: &[],
+            favorites: None,
+            watched: None,
+            marked: None,
+            flash: None,
+            remember_rows: false,
         }
     }
 
+    /// Record where the project rows were drawn, for effects placed on a row
+    pub fn remember_rows(mut self) -> Self {
+        self.remember_rows = true;
+        self
+    }
+
+    /// Highlight names and bars that a recent load changed
+    pub fn flash(mut self, flash: &'a CellFlash) -> Self {
+        self.flash = Some(flash);
+        self
+    }
+
+    /// Mark these projects as favorites
+    pub fn favorites(mut self, favorites: &'a BTreeSet<Uuid>) -> Self {
+        self.favorites = Some(favorites);
+        self
+    }
+
+    /// Mark these projects as watched
+    pub fn watched(mut self, watched: &'a BTreeSet<Uuid>) -> Self {
+        self.watched = Some(watched);
+        self
+    }
+
+    /// Flag these projects as marked for a batch action
+    pub fn marked(mut self, marked: &'a BTreeSet<Uuid>) -> Self {
+        self.marked = Some(marked);
+        self
+    }
+
+    /// Flag projects that breach any of these rules
+    pub fn sla_rules(mut self, rules: &'a [SlaRule]) -> Self {
+        self.sla_rules = rules;
+        self
+    }
+
+    /// Show how many projects run concurrently in each column
+    pub fn capacity_lane(mut self, show: bool) -> Self {
+        self.capacity_lane = show;
+        self
+    }
+
+    /// Evaluate statuses against a date other than today (time-travel view)
+    pub fn reference_date(mut self, date: NaiveDate) -> Self {
+        self.reference_date = date;
+        self
+    }
+
     pub fn title(mut self, title: &'a str) -> Self {
         self.title = title;
         self
     }
 
+    /// Use a fixed timeline start date instead of the earliest project start
+    pub fn start_date(mut self, start: NaiveDate) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Override which project is highlighted
+    pub fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
     /// Calculate the timeline start date
     fn calculate_timeline_start(&self) -> NaiveDate {
+        if let Some(start) = self.start {
+            return start;
+        }
         self.projects
             .iter()
             .map(|p| p.start_date)
             .min()
-            .unwrap_or_else(|| chrono::Local::now().date_naive() - Duration::days(30))
+            .unwrap_or_else(|| crate::clock::today() - Duration::days(30))
     }
 
     /// Convert a date to a column position (returns i64 for full range)
     fn date_to_column_raw(&self, date: NaiveDate, start: NaiveDate) -> i64 {
-        let days_from_start = (date - start).num_days();
-        let days_with_offset = days_from_start - self.state.scroll_offset;
-        (days_with_offset as f64 / self.state.days_per_column) as i64
+        self.state.date_column(date, start)
     }
 
     /// Convert a date to a visible column position (clamped to viewport)
@@ -212,7 +409,7 @@ impl<'a> TimelineWidget<'a> {
 
     /// Render the modern time axis (header)
     fn render_time_axis(&self, area: Rect, buf: &mut Buffer, start: NaiveDate) {
-        let today = chrono::Local::now().date_naive();
+        let today = self.reference_date;
 
         // Draw month markers and day numbers
         for col in 0..area.width {
@@ -272,18 +469,21 @@ impl<'a> TimelineWidget<'a> {
         let color = get_project_color(index);
         let name_width = 24.min(area.width.saturating_sub(1) as usize);
 
+        let is_completed = project.is_completed_as_of(self.reference_date);
+        let is_overdue = project.is_overdue_as_of(self.reference_date);
+
         // Status indicator with animation for selected items
-        let status_char = if project.is_completed() {
+        let status_char = if is_completed {
             STATUS_COMPLETED
-        } else if project.is_overdue() {
+        } else if is_overdue {
             STATUS_OVERDUE
         } else {
             STATUS_ACTIVE
         };
 
-        let status_color = if project.is_completed() {
+        let status_color = if is_completed {
             colors::GREEN
-        } else if project.is_overdue() {
+        } else if is_overdue {
             colors::RED
         } else {
             color
@@ -293,27 +493,53 @@ impl<'a> TimelineWidget<'a> {
         let status_style = Style::default()
             .fg(status_color)
             .add_modifier(if is_selected { Modifier::BOLD | Modifier::SLOW_BLINK } else { Modifier::BOLD });
-        buf.set_string(area.x + 1, area.y + row, &status_char.to_string(), status_style);
-        buf.set_string(area.x + 2, area.y + row, " ", Style::default());
+        if self.marked.is_some_and(|m| m.contains(&project.id)) {
+            buf.set_string(area.x, area.y + row, MARKED.to_string(), styles::marked());
+        }
+        buf.set_string(area.x + 1, area.y + row, status_char.to_string(), status_style);
+        let breached = sla::is_breached(self.sla_rules, project, self.reference_date);
+        if breached {
+            buf.set_string(area.x + 2, area.y + row, SLA_BREACH.to_string(), styles::sla_breach());
+        } else {
+            buf.set_string(area.x + 2, area.y + row, " ", Style::default());
+        }
 
-        // Render project name (left column) with modern styling
+        // Render project name (left column) with modern styling, after the star of favorites
+        // and the mark of watched projects
+        let (mut name_x, mut text_width) = (area.x + 3, name_width - 4);
+        let marks = [
+            (self.favorites, FAVORITE, styles::favorite()),
+            (self.watched, WATCHED, styles::watched()),
+        ];
+        for (set, mark, style) in marks {
+            if set.is_some_and(|s| s.contains(&project.id)) {
+                buf.set_string(name_x, area.y + row, mark.to_string(), style);
+                name_x += 2;
+                text_width = text_width.saturating_sub(2);
+            }
+        }
         let name = project.display_name();
-        let display_name: String = if name.len() > name_width - 4 {
-            format!("{}…", &name[..name_width - 5])
+        let display_name: String = if name.len() > text_width {
+            format!("{}…", &name[..text_width - 1])
         } else {
-            format!("{:width$}", name, width = name_width - 4)
+            format!("{:width$}", name, width = text_width)
         };
 
-        let name_style = if is_selected {
+        let lit = |fields| self.flash.is_some_and(|f| f.is_lit(project.id, fields));
+        let name_style = if lit(&["name", "clientId", "managerId"]) {
+            styles::flash()
+        } else if is_selected {
             Style::default()
                 .fg(colors::BG_DARK)
                 .bg(color)
                 .add_modifier(Modifier::BOLD)
+        } else if breached {
+            styles::sla_breach()
         } else {
             Style::default().fg(colors::FG_PRIMARY)
         };
 
-        buf.set_string(area.x + 3, area.y + row, &display_name, name_style);
+        buf.set_string(name_x, area.y + row, &display_name, name_style);
 
         // Calculate bar positions
         let bar_area_start = area.x + name_width as u16 + 2;
@@ -324,7 +550,9 @@ impl<'a> TimelineWidget<'a> {
         }
 
         // Draw the project bar with gradient effect
-        let project_end_date = project.actual_end_date.unwrap_or(project.planned_end_date);
+        let project_end_date = project
+            .actual_end_as_of(self.reference_date)
+            .unwrap_or(project.planned_end_date);
 
         // Get raw column positions (can be negative or beyond width)
         let start_col_raw = self.date_to_column_raw(project.start_date, start);
@@ -344,6 +572,7 @@ impl<'a> TimelineWidget<'a> {
         }
 
         // Draw the bar with gradient-like effect (goyslop!)
+        let bar_lit = lit(&["startDate", "plannedEndDate", "actualEndDate"]);
         let bar_length = (visible_end - visible_start + 1).max(1);
 
         for col in visible_start..=visible_end {
@@ -372,9 +601,9 @@ impl<'a> TimelineWidget<'a> {
             };
 
             // Color based on status with gradient
-            let bar_color = if project.is_completed() {
+            let bar_color = if is_completed {
                 Self::blend_colors(color, colors::GREEN, 0.4 + relative_pos * 0.2)
-            } else if project.is_overdue() {
+            } else if is_overdue {
                 // Pulsing red effect for overdue (goyslop!)
                 let pulse = ((self.state.animation_frame % 20) as f32 / 20.0 * std::f32::consts::PI).sin() * 0.3;
                 Self::blend_colors(color, colors::RED, 0.5 + pulse)
@@ -387,7 +616,9 @@ impl<'a> TimelineWidget<'a> {
                 }
             };
 
-            let bar_style = if is_selected {
+            let bar_style = if bar_lit {
+                Style::default().fg(colors::YELLOW).add_modifier(Modifier::BOLD)
+            } else if is_selected {
                 Style::default()
                     .fg(bar_color)
                     .add_modifier(Modifier::BOLD)
@@ -400,7 +631,7 @@ impl<'a> TimelineWidget<'a> {
         }
 
         // Draw today marker on top if it falls within this project
-        let today = chrono::Local::now().date_naive();
+        let today = self.reference_date;
         if let Some(today_col) = self.date_to_column(today, start, bar_area_width) {
             if today_col >= visible_start && today_col <= visible_end {
                 let pos = (bar_area_start + today_col, area.y + row);
@@ -444,7 +675,7 @@ impl<'a> TimelineWidget<'a> {
 
     /// Render the "today" vertical line with glow effect
     fn render_today_line(&self, area: Rect, buf: &mut Buffer, start: NaiveDate, name_width: u16) {
-        let today = chrono::Local::now().date_naive();
+        let today = self.reference_date;
         let bar_area_start = area.x + name_width + 2;
         let bar_area_width = area.width.saturating_sub(name_width + 3);
 
@@ -467,6 +698,46 @@ impl<'a> TimelineWidget<'a> {
         }
     }
 
+    /// Render the capacity lane: one sparkline cell per column
+    fn render_capacity_lane(&self, area: Rect, buf: &mut Buffer, start: NaiveDate, name_width: u16) {
+        const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        let bar_area_start = area.x + name_width + 2;
+        let bar_area_width = area.width.saturating_sub(name_width + 3);
+        buf.set_string(area.x + 1, area.y, tr!("timeline-capacity"), styles::text_dim());
+
+        let counts: Vec<usize> = (0..bar_area_width)
+            .map(|col| {
+                let days_offset = self.state.scroll_offset + (col as f64 * self.state.days_per_column) as i64;
+                let date = start + Duration::days(days_offset);
+                self.projects
+                    .iter()
+                    .filter(|p| {
+                        let end = p.actual_end_as_of(self.reference_date).unwrap_or(p.planned_end_date);
+                        p.start_date <= date && date <= end
+                    })
+                    .count()
+            })
+            .collect();
+
+        let peak = counts.iter().copied().max().unwrap_or(0);
+        if peak == 0 {
+            return;
+        }
+
+        for (col, count) in counts.into_iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let level = ((count * LEVELS.len()).div_ceil(peak)).clamp(1, LEVELS.len()) - 1;
+            let color = if count == peak { colors::ORANGE } else { colors::BLUE_LIGHT };
+            let pos = (bar_area_start + col as u16, area.y);
+            buf[pos].set_char(LEVELS[level]);
+            buf[pos].set_style(Style::default().fg(color));
+        }
+        buf.set_string(area.x + 10, area.y, tr!("timeline-capacity-max", peak = peak), styles::text_hint());
+    }
+
     /// Render the modern legend with icons
     fn render_legend(&self, area: Rect, buf: &mut Buffer) {
         let legend_y = area.y + area.height - 1;
@@ -474,14 +745,18 @@ impl<'a> TimelineWidget<'a> {
 
         // Status legend with modern icons
         let legend_items = [
-            (STATUS_ACTIVE, "Active", colors::BLUE),
-            (STATUS_COMPLETED, "Done", colors::GREEN),
-            (STATUS_OVERDUE, "Overdue", colors::RED),
-            ('│', "Today", colors::YELLOW),
+            (STATUS_ACTIVE, tr!("timeline-legend-active"), colors::BLUE),
+            (STATUS_COMPLETED, tr!("timeline-legend-done"), colors::GREEN),
+            (STATUS_OVERDUE, tr!("timeline-legend-overdue"), colors::RED),
+            ('│', tr!("timeline-legend-today"), colors::YELLOW),
+            (SLA_BREACH, tr!("timeline-legend-sla"), colors::MAGENTA),
         ];
+        // The SLA entry only matters when rules are configured
+        let shown = if self.sla_rules.is_empty() { legend_items.len() - 1 } else { legend_items.len() };
 
-        for (icon, label, color) in legend_items {
-            if x + label.len() as u16 + 4 > area.x + area.width - 6 {
+        for (icon, label, color) in legend_items.into_iter().take(shown) {
+            let width = label.chars().count() as u16;
+            if x + width + 4 > area.x + area.width - 6 {
                 break;
             }
 
@@ -489,11 +764,18 @@ impl<'a> TimelineWidget<'a> {
                 Style::default().fg(color).add_modifier(Modifier::BOLD));
             x += 1;
             buf.set_string(x, legend_y, label, styles::text_hint());
-            x += label.len() as u16 + 2;
+            x += width + 2;
         }
     }
 }
 
+impl TimelineWidget<'_> {
+    /// Area of the project rows inside the border, below the axis and above the capacity lane
+    fn rows_area(inner: Rect, lane_rows: u16) -> Rect {
+        Rect::new(inner.x, inner.y + AXIS_HEIGHT, inner.width, inner.height.saturating_sub(AXIS_HEIGHT + lane_rows))
+    }
+}
+
 impl Widget for TimelineWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         // Draw the modern block border with rounded corners
@@ -514,7 +796,7 @@ impl Widget for TimelineWidget<'_> {
         }
 
         let start = self.calculate_timeline_start();
-        let name_col_width: u16 = 26; // Color indicator (3) + name (21) + spacing (2)
+        let name_col_width = NAME_COLUMN_WIDTH;
 
         // Render time axis (top 2 rows)
         if inner.height >= 3 {
@@ -528,14 +810,23 @@ impl Widget for TimelineWidget<'_> {
         // Render today vertical line
         self.render_today_line(inner, buf, start, name_col_width - 2);
 
-        // Render project bars
-        let projects_area = Rect::new(inner.x, inner.y + 2, inner.width, inner.height.saturating_sub(2));
+        // Render project bars (leaving the last row for the capacity lane if enabled)
+        let lane_rows = if self.capacity_lane && inner.height > 4 { 1 } else { 0 };
+        let projects_area = Self::rows_area(inner, lane_rows);
+        if self.remember_rows {
+            self.state.rows_area.set(Some(projects_area));
+        }
+        if lane_rows > 0 {
+            let lane = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
+            buf.set_style(lane, Style::default().bg(colors::BG_DARK));
+            self.render_capacity_lane(lane, buf, start, name_col_width - 2);
+        }
         for (index, project) in self.projects.iter().enumerate() {
             if index >= projects_area.height as usize {
                 break;
             }
 
-            let is_selected = self.state.selected_project == Some(index);
+            let is_selected = self.selected == Some(index);
             self.render_project_bar(
                 projects_area,
                 buf,
@@ -574,6 +865,7 @@ impl Widget for TimelineWidget<'_> {
 pub struct TimelineStatusWidget<'a> {
     state: &'a TimelineState,
     project_count: usize,
+    due: Option<i64>,
 }
 
 impl<'a> TimelineStatusWidget<'a> {
@@ -581,27 +873,72 @@ impl<'a> TimelineStatusWidget<'a> {
         Self {
             state,
             project_count,
+            due: None,
         }
     }
+
+    /// Days left until the selected project's planned end (None when completed)
+    pub fn due(mut self, days_left: Option<i64>) -> Self {
+        self.due = days_left;
+        self
+    }
 }
 
 impl Widget for TimelineStatusWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let zoom_level = format!("⚲ {:.1}d/col", self.state.days_per_column);
-        let project_info = format!("{} projects", self.project_count);
+        let project_info = tr!("timeline-projects", count = self.project_count);
         let selected_info = self
             .state
             .selected_project
             .map(|i| format!("▸ #{}", i + 1))
-            .unwrap_or_else(|| "▸ none".to_string());
+            .unwrap_or_else(|| format!("▸ {}", tr!("timeline-no-selection")));
+
+        // The selected project's deadline sits next to it, coloured by urgency
+        let dim = Style::default().fg(colors::FG_DIM);
+        let mut parts = vec![(format!("{}  {}", project_info, selected_info), dim)];
+        if let Some(days) = self.due {
+            parts.push((format!(" · {}", due::label(days)), Style::default().fg(due::color(days))));
+        }
+        parts.push((format!("  {}", zoom_level), dim));
 
-        let status = format!("{}  {}  {}", project_info, selected_info, zoom_level);
+        let mut x = area.x;
+        for (text, style) in parts {
+            x = buf.set_stringn(x, area.y, &text, area.right().saturating_sub(x) as usize, style).0;
+        }
+    }
+}
 
-        buf.set_string(
-            area.x,
-            area.y,
-            &status,
-            Style::default().fg(colors::FG_DIM),
-        );
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_week_boundaries() {
+        // 2024-03-13 is a Wednesday
+        assert_eq!(SnapUnit::Week.next_boundary(date(2024, 3, 13)), date(2024, 3, 18));
+        assert_eq!(SnapUnit::Week.previous_boundary(date(2024, 3, 13)), date(2024, 3, 11));
+        // Already on a Monday: move a full week
+        assert_eq!(SnapUnit::Week.next_boundary(date(2024, 3, 11)), date(2024, 3, 18));
+        assert_eq!(SnapUnit::Week.previous_boundary(date(2024, 3, 11)), date(2024, 3, 4));
+    }
+
+    #[test]
+    fn test_month_boundaries() {
+        assert_eq!(SnapUnit::Month.next_boundary(date(2024, 12, 15)), date(2025, 1, 1));
+        assert_eq!(SnapUnit::Month.previous_boundary(date(2024, 3, 15)), date(2024, 3, 1));
+        assert_eq!(SnapUnit::Month.previous_boundary(date(2024, 3, 1)), date(2024, 2, 1));
+    }
+
+    #[test]
+    fn test_snap_unit_follows_zoom() {
+        let mut state = TimelineState::default();
+        assert_eq!(state.snap_unit(), SnapUnit::Week);
+        state.days_per_column = 4.0;
+        assert_eq!(state.snap_unit(), SnapUnit::Month);
     }
 }
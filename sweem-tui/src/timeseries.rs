@@ -0,0 +1,82 @@
+//! Project counts over time, from the local snapshot history.
+//!
+//! The third page of the Stats tab (`v`) charts how many projects were
+//! active and overdue on each day of the selected range, and the share of
+//! active projects that were on track (not overdue). Each day is evaluated
+//! with the last projects snapshot taken on or before it, as of that day, so
+//! only days the TUI has seen are charted; days before the first snapshot
+//! are left out.
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::ProjectDto;
+
+/// Counts of one day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyPoint {
+    pub day: NaiveDate,
+    /// Started and not yet completed
+    pub active: usize,
+    pub overdue: usize,
+}
+
+impl DailyPoint {
+    /// Percent of the active projects that were not overdue
+    pub fn on_track_percent(&self) -> Option<f64> {
+        (self.active > 0).then(|| (self.active - self.overdue.min(self.active)) as f64 * 100.0 / self.active as f64)
+    }
+}
+
+/// Points of the days `from..to` that have a snapshot (oldest first, as
+/// returned by `SnapshotStore::series`)
+pub fn daily(snapshots: &[(DateTime<Utc>, Vec<ProjectDto>)], from: NaiveDate, to: NaiveDate) -> Vec<DailyPoint> {
+    let mut points = Vec::new();
+    let mut current: Option<&[ProjectDto]> = None;
+    let mut next = 0;
+    for day in from.iter_days().take_while(|day| *day < to) {
+        while let Some((_, projects)) = snapshots.get(next).filter(|(at, _)| at.with_timezone(&Local).date_naive() <= day) {
+            current = Some(projects);
+            next += 1;
+        }
+        let Some(projects) = current else { continue };
+        let active = projects.iter().filter(|p| p.start_date <= day && !p.is_completed_as_of(day));
+        points.push(DailyPoint {
+            day,
+            active: active.clone().count(),
+            overdue: active.filter(|p| p.is_overdue_as_of(day)).count(),
+        });
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use sweem_core::fixtures;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_days_use_the_latest_snapshot() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let project = |n: u128, start, end, done: Option<u32>| ProjectDto {
+            id: Uuid::from_u128(n),
+            actual_end_date: done.map(date),
+            ..fixtures::project(date(start), date(end))
+        };
+        let taken = |d| Local.from_local_datetime(&date(d).and_hms_opt(12, 0, 0).unwrap()).unwrap().to_utc();
+        let snapshots = [
+            (taken(2), vec![project(1, 1, 4, None), project(2, 3, 20, None)]),
+            (taken(6), vec![project(1, 1, 4, Some(6)), project(2, 3, 20, None)]),
+        ];
+
+        let points = daily(&snapshots, date(1), date(8));
+        let seen: Vec<_> = points.iter().map(|p| (p.day.format("%d").to_string(), p.active, p.overdue)).collect();
+        let expected = [("02", 1, 0), ("03", 2, 0), ("04", 2, 0), ("05", 2, 1), ("06", 1, 0), ("07", 1, 0)];
+        assert_eq!(seen, expected.map(|(d, a, o)| (d.to_string(), a, o)), "nothing before the first snapshot");
+        assert_eq!(points[3].on_track_percent(), Some(50.0));
+        assert_eq!(DailyPoint { day: date(1), active: 0, overdue: 0 }.on_track_percent(), None);
+    }
+}
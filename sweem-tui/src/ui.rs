@@ -5,18 +5,43 @@
 
 use chrono::{Datelike, NaiveDate};
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Tabs, Wrap},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span, Text},
+    symbols::Marker,
+    widgets::{
+        Axis, Bar, BarChart, BarGroup, Block, Borders, Cell, Chart, Clear, Dataset, Gauge, GraphType, List, ListItem,
+        Paragraph, Row, Sparkline, Table, TableState, Tabs, Wrap,
+    },
     Frame,
 };
-
-use crate::app::{App, FormField, FormState, FormType, LogLevel, Tab};
-use crate::models::Role;
+use sweem_core::stats;
+use uuid::Uuid;
+
+use crate::api::EntityType;
+use crate::attachments::{self, DocumentList};
+use crate::audit::AuditFeed;
+use crate::app::{App, FormField, FormState, FormType, LogLevel, StatsView, Tab};
+use crate::columns::{self, Column};
+use crate::diff::{self, ChangeKind};
+use crate::due;
+use crate::export::RankingColumn;
+use crate::flash;
+use crate::graphics::{ChartImage, ImageBar};
+use crate::datepicker::{self, Calendar};
+use crate::picker::{self, Picker};
+use crate::settings::Setting;
+use sweem_core::probe::{Outcome, Stage};
+use crate::inbox::InboxEvent;
+use crate::inspector;
+use crate::models::{ProjectDto, Role, UserDto};
 use crate::particles::ParticleWidget;
+use crate::sort;
+use crate::teams::TreeRow;
 use crate::theme::{colors, styles};
 use crate::timeline::{TimelineStatusWidget, TimelineWidget};
+use crate::timeseries::DailyPoint;
+use crate::tr;
 
 /// Render the entire UI
 pub fn render(frame: &mut Frame, app: &App) {
@@ -26,24 +51,37 @@ pub fn render(frame: &mut Frame, app: &App) {
     let bg_block = Block::default().style(Style::default().bg(colors::BG_DARK));
     frame.render_widget(bg_block, area);
 
-    // Render background particles
-    frame.render_widget(ParticleWidget::new(&app.particle_system), area);
-
-    // Create main layout
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),  // Status bar / tabs
-            Constraint::Min(10),    // Main content
-            Constraint::Length(5),  // Log area
-        ])
-        .split(area);
+    let chunks = main_layout(area);
 
     // Render components
     render_tabs(frame, app, chunks[0]);
     render_main_content(frame, app, chunks[1]);
     render_logs(frame, app, chunks[2]);
 
+    // Background particles fill the cells the panels left blank, dimmed
+    // within each line of text
+    let content: Vec<Rect> = chunks.iter().map(|c| c.inner(Margin::new(1, 1))).collect();
+    frame.render_widget(
+        ParticleWidget::new(&app.particle_system)
+            .content_regions(&content, app.config.particles.content_dim),
+        area,
+    );
+    // Old numbers should not look current
+    if app.stale_warning().is_some() {
+        frame.buffer_mut().set_style(chunks[1], Style::default().add_modifier(Modifier::DIM));
+    }
+
+    // Event bursts (fireworks) go on top of the content but under overlays
+    frame.render_widget(ParticleWidget::effects(&app.particle_system), area);
+
+    if app.show_debug {
+        render_debug_overlay(frame, app, area);
+    }
+
+    if !app.toasts.is_empty() {
+        render_toasts(frame, app, chunks[1]);
+    }
+
     // Render overlays (modals, dialogs)
     if app.form_state.is_some() {
         render_form_modal(frame, app, area);
@@ -53,6 +91,54 @@ pub fn render(frame: &mut Frame, app: &App) {
         render_confirm_dialog(frame, app, area);
     }
 
+    if app.prompt.is_some() {
+        render_prompt(frame, app, area);
+    }
+
+    if app.burndown.is_some() {
+        render_burndown_popup(frame, app, area);
+    }
+
+    if app.changes_view.is_some() {
+        render_changes_popup(frame, app, area);
+    }
+
+    if app.sort_dialog.is_some() {
+        render_sort_dialog(frame, app, area);
+    }
+
+    if app.column_dialog.is_some() {
+        render_column_dialog(frame, app, area);
+    }
+
+    if app.settings.is_some() {
+        render_settings_dialog(frame, app, area);
+    }
+
+    if app.diagnosis.is_some() {
+        render_diagnosis(frame, app, area);
+    }
+
+    if app.context_menu.is_some() {
+        render_context_menu(frame, app, area);
+    }
+
+    if app.inspector.is_some() {
+        render_inspector(frame, app, area);
+    }
+
+    if app.search.is_some() {
+        render_search(frame, app, area);
+    }
+
+    if app.attachments.is_some() {
+        render_attachments(frame, app, area);
+    }
+
+    if app.date_picker.is_some() {
+        render_date_picker(frame, app, area);
+    }
+
     if app.error_popup.is_some() {
         render_error_popup(frame, app, area);
     }
@@ -60,36 +146,177 @@ pub fn render(frame: &mut Frame, app: &App) {
     if app.show_help {
         render_help_overlay(frame, area);
     }
+
+    if app.draining.is_some() {
+        render_shutdown(frame, app, area);
+    }
 }
 
-/// Render the tab bar
-fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
-    let titles: Vec<Line> = [Tab::Clients, Tab::Timeline, Tab::Users]
-        .iter()
-        .map(|tab| {
-            let style = if *tab == app.active_tab {
-                styles::tab_active()
-            } else {
-                styles::tab_inactive()
-            };
-            Line::from(Span::styled(format!(" {} ", tab.name()), style))
+/// Tab bar, main content and log areas
+fn main_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Status bar / tabs
+            Constraint::Min(10),    // Main content
+            Constraint::Length(5),  // Log area
+        ])
+        .split(area)
+}
+
+/// Raster versions of the Stats tab bar charts, drawn over the text ones on
+/// terminals with an image protocol. None while a popup is open (kitty
+/// images sit above the text) or while the data is stale (the text is dimmed).
+pub fn chart_images(app: &App, area: Rect) -> Vec<ChartImage> {
+    if app.active_tab != Tab::Stats || app.stats_view != StatsView::Trends || app.popup_open() || app.stale_warning().is_some() {
+        return Vec::new();
+    }
+    let (monthly_area, bottom) = stats_layout(main_layout(area)[1]);
+    let (from, to) = app.stats_range();
+    let as_of = app.reference_date();
+
+    let months = stats::monthly_delivery(&app.projects, from, app.stats_months, as_of);
+    let bar_width = monthly_bar_width(monthly_area, app.stats_months);
+    let monthly = months.iter().enumerate().flat_map(|(i, month)| {
+        let column = i as u16 * (2 * bar_width + 1);
+        [(column, bar_width, month.started, colors::BLUE), (column + bar_width, bar_width, month.completed, colors::GREEN)]
+    });
+    let quarters = stats::quarterly_overrun(&app.projects, from, to, as_of);
+    let overrun = quarters.iter().enumerate().map(|(i, q)| {
+        let days = q.average_days.unwrap_or(0.0);
+        (i as u16 * 9, 7, days.round() as u64, overrun_color(days))
+    });
+    [bar_image(1, monthly_area, monthly.collect()), bar_image(2, bottom[0], overrun.collect())].into_iter().flatten().collect()
+}
+
+/// Image over the bars of a bordered `BarChart` with one label row, given
+/// `(column, width, value, color)` per bar. The bottom bar row is left to the
+/// text chart, which prints the values there.
+fn bar_image(id: u32, chart_area: Rect, bars: Vec<(u16, u16, u64, Color)>) -> Option<ChartImage> {
+    let inner = chart_area.inner(Margin::new(1, 1));
+    let bar_rows = inner.height.saturating_sub(1);
+    let area = Rect { height: bar_rows.saturating_sub(1), ..inner };
+    if area.is_empty() {
+        return None;
+    }
+    let max = bars.iter().map(|(_, _, value, _)| *value).max().unwrap_or(0).max(1);
+    let bars = bars
+        .into_iter()
+        .filter(|(column, width, _, _)| column + width <= area.width)
+        .map(|(column, width, value, color)| ImageBar {
+            column,
+            width,
+            height: (value as f64 / max as f64 * f64::from(bar_rows) - 1.0).max(0.0),
+            color,
         })
         .collect();
+    Some(ChartImage { id, area, bars })
+}
+
+/// Render the debug overlay (frame rate and render stats) in the top-right corner
+fn render_debug_overlay(frame: &mut Frame, app: &App, area: Rect) {
+    let stats = app.frame_stats;
+    let width = 26.min(area.width);
+    let overlay = Rect::new(area.x + area.width - width, area.y, width, 5.min(area.height));
+
+    let fps_style = if stats.fps + 1.0 < stats.target_fps as f32 * 0.8 {
+        Style::default().fg(colors::ORANGE)
+    } else {
+        styles::success()
+    };
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{:9}", tr!("debug-fps")), styles::text_dim()),
+            Span::styled(format!("{:5.1} / {}", stats.fps, stats.target_fps), fps_style),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{:9}", tr!("debug-dropped")), styles::text_dim()),
+            Span::styled(stats.dropped_updates.to_string(), styles::text()),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{:9}", tr!("debug-particles")), styles::text_dim()),
+            Span::styled(format!(" {}", app.particle_system.particle_count()), styles::text()),
+        ]),
+    ];
+
+    frame.render_widget(Clear, overlay);
+    let block = Block::default()
+        .title(format!(" {} ", tr!("debug-title")))
+        .title_style(styles::title())
+        .borders(Borders::ALL)
+        .border_style(styles::border_dim())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+    frame.render_widget(Paragraph::new(lines).block(block), overlay);
+}
+
+/// Render the tab bar
+fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
+    let mut tabs = Tab::all(app.plugins.len());
+    // The console only shows up while it is open
+    if app.active_tab == Tab::Console {
+        tabs.push(Tab::Console);
+    }
+    let titles = |badges: Option<bool>| -> Vec<Line> {
+        tabs.iter()
+            .map(|tab| {
+                let style = if *tab == app.active_tab {
+                    styles::tab_active()
+                } else {
+                    styles::tab_inactive()
+                };
+                let mut title = Line::from(Span::styled(format!(" {} ", app.tab_title(*tab)), style));
+                let unread = app.ui_state.inbox.unread_count();
+                if *tab == Tab::Inbox && unread > 0 {
+                    title.push_span(Span::styled(format!("({}) ", unread), styles::warning().add_modifier(Modifier::BOLD)));
+                }
+                if let Some((badge, style)) = badges.and_then(|compact| tab_badge(app, *tab, compact)) {
+                    title.push_span(Span::styled(format!("({}) ", badge), style));
+                }
+                title
+            })
+            .collect()
+    };
+    // Counts in full where they fit, then shortened, then none; each tab is padded
+    // by a space on both sides and followed by a 3-column divider
+    let fits = |titles: &[Line]| {
+        titles.iter().map(|t| t.width() + 5).sum::<usize>() <= area.width.saturating_sub(2) as usize + 3
+    };
+    let titles = [Some(false), Some(true), None]
+        .into_iter()
+        .map(titles)
+        .find(|titles| fits(titles))
+        .unwrap_or_else(|| titles(None));
+
+    let title = match app.as_of {
+        Some(date) => format!(" {} ", tr!("app-title-as-of", date = date)),
+        None => format!(" {} ", tr!("app-title")),
+    };
+
+    let mut block = Block::default()
+        .title(title)
+        .title_style(styles::title())
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+    // Production gets a red frame and a banner no matter which tab is shown
+    if let Some(profile) = &app.production {
+        block = block
+            .border_style(Style::default().fg(colors::RED_LIGHT))
+            .title(Span::styled(format!(" ⚠ {} ", tr!("production-banner", profile = profile.clone())), styles::production()));
+    }
+    if let Some(status) = app.reconnect_status() {
+        block = block.title(Line::styled(format!(" {} ", status), styles::warning()).right_aligned());
+    }
+    if let Some(warning) = app.stale_warning() {
+        block = block.title(Line::styled(format!(" ⚠ {} ", warning), styles::stale()).right_aligned());
+    }
+    if let Some(readout) = app.telemetry_text() {
+        block = block.title_bottom(Line::styled(format!(" {} ", readout), styles::text_dim()).right_aligned());
+    }
 
     let tabs = Tabs::new(titles)
-        .block(
-            Block::default()
-                .title(" SWEeM Management Console ")
-                .title_style(styles::title())
-                .borders(Borders::ALL)
-                .border_style(styles::border())
-                .style(Style::default().bg(colors::BG_MEDIUM)),
-        )
-        .select(match app.active_tab {
-            Tab::Clients => 0,
-            Tab::Timeline => 1,
-            Tab::Users => 2,
-        })
+        .block(block)
+        .select(tabs.iter().position(|t| *t == app.active_tab).unwrap_or(0))
         .style(styles::text())
         .highlight_style(styles::tab_active())
         .divider(Span::styled(" | ", styles::border_dim()));
@@ -97,34 +324,227 @@ fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(tabs, area);
 }
 
+/// Live count next to a tab's name: projects (and how many are overdue), clients and users
+fn tab_badge(app: &App, tab: Tab, compact: bool) -> Option<(String, Style)> {
+    let plain = |count: usize| Some((count.to_string(), styles::text_dim()));
+    match tab {
+        Tab::Timeline => {
+            let count = app.projects.len();
+            let overdue = app.projects.iter().filter(|p| p.is_overdue_as_of(app.reference_date())).count();
+            match (overdue, compact) {
+                (0, _) => plain(count),
+                (_, false) => Some((tr!("tab-badge-overdue", count = count, overdue = overdue), styles::error())),
+                (_, true) => Some((format!("{}/{}!", count, overdue), styles::error())),
+            }
+        }
+        Tab::Projects => plain(app.projects.len()),
+        // Lazily loaded lists count what the server has
+        Tab::Clients => plain(app.page_status(tab).map_or(app.clients.len(), |(_, total, _)| total)),
+        Tab::Users => plain(app.page_status(tab).map_or(app.users.len(), |(_, total, _)| total)),
+        _ => None,
+    }
+}
+
 /// Render the main content area based on active tab
 fn render_main_content(frame: &mut Frame, app: &App, area: Rect) {
     match app.active_tab {
         Tab::Clients => render_clients_view(frame, app, area),
         Tab::Timeline => render_timeline_view(frame, app, area),
+        Tab::Projects => render_projects_view(frame, app, area),
         Tab::Users => render_users_view(frame, app, area),
+        Tab::Stats => render_stats_view(frame, app, area),
+        Tab::Inbox => render_inbox_view(frame, app, area),
+        Tab::Audit => render_audit_view(frame, app, area),
+        Tab::Console => render_console_view(frame, app, area),
+        Tab::Plugin(index) => {
+            if let Some(plugin) = app.plugins.get(index) {
+                plugin.render(frame, area, &app.plugin_context());
+            }
+        }
     }
 }
 
 /// Render the timeline view
 fn render_timeline_view(frame: &mut Frame, app: &App, area: Rect) {
+    // "My projects" keeps a personal panel next to the timeline
+    let area = if app.my_projects {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(40), Constraint::Length(34)])
+            .split(area);
+        render_my_panel(frame, app, columns[1]);
+        columns[0]
+    } else {
+        area
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(5), Constraint::Length(1)])
         .split(area);
 
-    // Render timeline
-    let timeline = TimelineWidget::new(&app.projects, &app.timeline_state);
-    frame.render_widget(timeline, chunks[0]);
+    // Render timeline (or two stacked, filtered timelines in comparison mode)
+    if let Some(panes) = app.comparison_panes() {
+        let pane_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[0]);
+        // Both panes share the full data set's start date so they stay aligned
+        app.timeline_state.rows_area.set(None);
+        let start = app.timeline_state.calculate_timeline_start(&app.projects);
+        let by = app.comparison.map(|c| c.by.name()).unwrap_or_default();
+
+        for (pane, pane_area) in panes.iter().zip(pane_chunks.iter()) {
+            let title = format!(" {}: {} ", by, pane.label);
+            let timeline = TimelineWidget::new(&pane.projects, &app.timeline_state)
+                .title(&title)
+                .start_date(start)
+                .selected(pane.selected)
+                .reference_date(app.reference_date())
+                .sla_rules(&app.config.sla)
+                .favorites(&app.ui_state.favorites)
+                .watched(&app.ui_state.watched)
+                .marked(&app.marked)
+                .flash(&app.flash);
+            frame.render_widget(timeline, *pane_area);
+        }
+    } else {
+        let mut title = match app.as_of {
+            Some(date) => format!(" {} ", tr!("timeline-title-as-of", date = date)),
+            None => format!(" {} ", tr!("timeline-title")),
+        };
+        if app.sandbox.is_some() {
+            title.push_str(tr!("timeline-sandbox-tag"));
+            title.push(' ');
+        }
+        if app.my_projects {
+            title.push_str(tr!("timeline-mine-tag"));
+            title.push(' ');
+        }
+        if let Some(expr) = app.expression(EntityType::Project) {
+            title.push_str(&tr!("list-filter-tag", filter = expr.as_str()));
+            title.push(' ');
+        }
+        if app.archived_hidden > 0 {
+            title.push_str(&tr!("list-archived-tag", count = app.archived_hidden));
+            title.push(' ');
+        }
+        if !app.marked.is_empty() {
+            title.push_str(&tr!("list-marked-tag", count = app.marked_projects().len()));
+            title.push(' ');
+        }
+        let timeline = TimelineWidget::new(&app.projects, &app.timeline_state)
+            .title(&title)
+            .reference_date(app.reference_date())
+            .capacity_lane(app.sandbox.is_some())
+            .sla_rules(&app.config.sla)
+            .favorites(&app.ui_state.favorites)
+            .watched(&app.ui_state.watched)
+            .marked(&app.marked)
+            .flash(&app.flash)
+            .remember_rows();
+        frame.render_widget(timeline, chunks[0]);
+    }
+
+    // Render status (sandbox summary replaces the usual status line)
+    if app.sandbox.is_some() {
+        render_sandbox_status(frame, app, chunks[1]);
+    } else {
+        let due = app
+            .timeline_state
+            .selected_project
+            .and_then(|idx| app.projects.get(idx))
+            .and_then(|p| p.days_left_as_of(app.reference_date()));
+        let status = TimelineStatusWidget::new(&app.timeline_state, app.projects.len()).due(due);
+        frame.render_widget(status, chunks[1]);
+    }
+}
+
+/// Number of weeks in the "My projects" workload sparkline
+const MY_LOAD_WEEKS: usize = 12;
+
+/// Render the "My projects" panel: upcoming deadlines and weekly workload
+fn render_my_panel(frame: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(4), Constraint::Length(5)])
+        .split(area);
+    let today = app.reference_date();
+
+    let items: Vec<ListItem> = stats::upcoming_deadlines(&app.projects, today)
+        .into_iter()
+        .map(|project| {
+            let days = (project.planned_end_date - today).num_days();
+            let (label, style) = match days {
+                d if d < 0 => (tr!("deadline-late", days = -d), styles::error()),
+                0 => (tr!("deadline-today").to_string(), styles::warning()),
+                d if d <= 7 => (tr!("deadline-in", days = d), styles::warning()),
+                d => (tr!("deadline-in", days = d), styles::text_dim()),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:>8} ", label), style),
+                Span::styled(project.display_name().to_string(), styles::text()),
+            ]))
+        })
+        .collect();
+    let title = match app.me.as_deref() {
+        Some(me) => format!(" {} ", tr!("my-deadlines-title", login = me)),
+        None => format!(" {} ", tr!("my-deadlines-title", login = "-")),
+    };
+    let list = List::new(items).block(
+        Block::default()
+            .title(title)
+            .title_style(styles::title_accent())
+            .borders(Borders::ALL)
+            .border_style(styles::border()),
+    );
+    frame.render_widget(list, chunks[0]);
+
+    let load: Vec<u64> = stats::weekly_load(&app.projects, today, MY_LOAD_WEEKS);
+    let peak = load.iter().copied().max().unwrap_or(0);
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!(" {} ", tr!("my-load-title", weeks = MY_LOAD_WEEKS, peak = peak)))
+                .title_style(styles::title())
+                .borders(Borders::ALL)
+                .border_style(styles::border_dim()),
+        )
+        .data(&load)
+        .max(peak.max(1))
+        .style(Style::default().fg(colors::BLUE));
+    frame.render_widget(sparkline, chunks[1]);
+}
+
+/// Render the what-if sandbox summary line
+fn render_sandbox_status(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(sandbox) = &app.sandbox else {
+        return;
+    };
+    let as_of = app.reference_date();
+    let before = stats::overdue_count(sandbox.baseline(), as_of);
+    let after = stats::overdue_count(&app.projects, as_of);
+    let after_style = match after.cmp(&before) {
+        std::cmp::Ordering::Less => styles::success(),
+        std::cmp::Ordering::Greater => styles::error(),
+        std::cmp::Ordering::Equal => styles::text(),
+    };
 
-    // Render status
-    let status = TimelineStatusWidget::new(&app.timeline_state, app.projects.len());
-    frame.render_widget(status, chunks[1]);
+    let line = Line::from(vec![
+        Span::styled(format!(" {} ", tr!("sandbox-badge")), Style::default().fg(colors::BG_DARK).bg(colors::ORANGE).add_modifier(Modifier::BOLD)),
+        Span::styled(format!(" {}", tr!("sandbox-changed", count = sandbox.changes(&app.projects).len())), styles::text()),
+        Span::styled(" │ ", styles::border_dim()),
+        Span::styled(format!("{} ", tr!("sandbox-overdue", before = before)), styles::text_dim()),
+        Span::styled(after.to_string(), after_style),
+        Span::styled(" │ ", styles::border_dim()),
+        Span::styled(tr!("sandbox-hint"), styles::text_hint()),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
 }
 
 /// Render the clients list view
 fn render_clients_view(frame: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = app
+    let columns = app.columns(Tab::Clients);
+    let mut items: Vec<ListItem> = app
         .clients
         .iter()
         .enumerate()
@@ -140,7 +560,8 @@ fn render_clients_view(frame: &mut Frame, app: &App, area: Rect) {
             };
 
             // Calculate project counts from actual projects data
-            let (completed, total) = calculate_client_project_counts(&app.projects, client.id);
+            let (completed, total) =
+                stats::client_project_counts(&app.projects, client.id, app.reference_date());
 
             // Create a visual progress bar for projects
             let progress_bar = if total > 0 {
@@ -164,33 +585,55 @@ fn render_clients_view(frame: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(colors::ORANGE)
             };
 
-            let content = Line::from(vec![
-                Span::styled(
-                    format!("{:20}", client.display_name()),
-                    style,
-                ),
-                Span::styled(" │ ", styles::border_dim()),
-                Span::styled(
-                    format!("{:30}", client.address.as_deref().unwrap_or("-")),
-                    if is_selected { style } else { styles::text_dim() },
-                ),
-                Span::styled(" │ ", styles::border_dim()),
-                Span::styled(progress_bar, progress_style),
-                Span::styled(" ", Style::default()),
-                Span::styled(
-                    format!("{}/{}", completed, total),
-                    progress_style,
-                ),
-            ]);
+            let star = if app.ui_state.favorites.contains(&client.id) { "★ " } else { "  " };
+            let mut content = Line::from(Span::styled(star, styles::favorite()));
+            if app.ui_state.watched.contains(&client.id) {
+                content.push_span(Span::styled("◉ ", styles::watched()));
+            }
+            for (n, column) in columns.iter().enumerate() {
+                if n > 0 {
+                    content.push_span(Span::styled(" │ ", styles::border_dim()));
+                }
+                match column {
+                    Column::Name => content.push_span(Span::styled(
+                        format!("{:20}", client.display_name()),
+                        flashed(app, client.id, &["name"], style),
+                    )),
+                    Column::Address => content.push_span(Span::styled(
+                        format!("{:30}", client.address.as_deref().unwrap_or("-")),
+                        flashed(app, client.id, &["address"], if is_selected { style } else { styles::text_dim() }),
+                    )),
+                    Column::Projects => {
+                        content.push_span(Span::styled(progress_bar.clone(), progress_style));
+                        content.push_span(Span::styled(" ", Style::default()));
+                        content.push_span(Span::styled(
+                            format!("{}/{}", completed, total),
+                            flashed(app, client.id, &[flash::PROJECT_COUNTS], progress_style),
+                        ));
+                    }
+                    Column::Source => content.push_span(Span::styled(
+                        format!("{:8}", columns::source(&client.extra)),
+                        if is_selected { style } else { styles::info() },
+                    )),
+                    _ => {}
+                }
+            }
+            if let Some(extra) = app.scripts.as_ref().and_then(|s| s.client_column(client)) {
+                content.push_span(Span::styled(" │ ", styles::border_dim()));
+                content.push_span(Span::styled(extra, if is_selected { style } else { styles::info() }));
+            }
 
             ListItem::new(content)
         })
         .collect();
+    if app.page_status(Tab::Clients).is_some_and(|(_, _, loading)| loading) {
+        items.push(ListItem::new(Span::styled(tr!("list-loading-page"), styles::text_dim())));
+    }
 
     let list = List::new(items)
         .block(
             Block::default()
-                .title(" Clients ")
+                .title(list_title(app, Tab::Clients, tr!("clients-title")))
                 .title_style(styles::title_accent())
                 .borders(Borders::ALL)
                 .border_style(styles::border())
@@ -202,57 +645,195 @@ fn render_clients_view(frame: &mut Frame, app: &App, area: Rect) {
 
     // Render empty state
     if app.clients.is_empty() {
-        render_empty_state(frame, area, "No clients found", app.is_loading);
+        render_empty_state(frame, area, tr!("clients-empty"), app.is_loading);
     }
 }
 
-/// Render the users list view
-fn render_users_view(frame: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = app
-        .users
+/// Render the projects table; the selection is the timeline's
+fn render_projects_view(frame: &mut Frame, app: &App, area: Rect) {
+    let as_of = app.reference_date();
+    let columns = app.columns(Tab::Projects);
+    let cursor = app.table_cell();
+    let header = Row::new(columns.iter().map(|c| Cell::from(c.label()).style(styles::form_label())));
+    let rows: Vec<Row> = app
+        .projects
         .iter()
         .enumerate()
-        .map(|(i, user)| {
-            let is_selected = i == app.list_selected;
-            let style = if is_selected {
-                Style::default()
-                    .fg(colors::BG_DARK)
-                    .bg(colors::PURPLE)
-                    .add_modifier(Modifier::BOLD)
+        .map(|(i, project)| {
+            if app.timeline_state.selected_project != Some(i) {
+                return Row::new(columns.iter().map(|column| project_cell(app, project, *column, as_of)));
+            }
+            // The selected row shows the cell cursor, or the cell being edited in place
+            let edit = app.inline_edit.as_ref().filter(|edit| edit.id == project.id);
+            Row::new(columns.iter().map(|column| match edit {
+                Some(edit) if edit.column == *column => {
+                    Cell::from(format!("{}▏", edit.input)).style(styles::form_input_focused())
+                }
+                _ if *column == cursor => project_cell(app, project, *column, as_of).underlined(),
+                _ => project_cell(app, project, *column, as_of),
+            }))
+            .style(styles::selected())
+        })
+        .collect();
+    let widths = columns.iter().map(|column| match column {
+        Column::Name => Constraint::Min(14),
+        Column::Client | Column::Manager => Constraint::Length(11),
+        Column::Due => Constraint::Length(14),
+        Column::Status | Column::Source => Constraint::Length(8),
+        _ => Constraint::Length(11),
+    });
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .title(list_title(app, Tab::Projects, tr!("projects-title")))
+                .title_style(styles::title_accent())
+                .borders(Borders::ALL)
+                .border_style(styles::border())
+                .style(Style::default().bg(colors::BG_DARK)),
+        )
+        .style(styles::text());
+    // The state only scrolls the selected row into view; rows style themselves
+    let mut state = TableState::default().with_selected(app.timeline_state.selected_project);
+    frame.render_stateful_widget(table, area, &mut state);
+
+    if app.projects.is_empty() {
+        render_empty_state(frame, area, tr!("projects-empty"), app.is_loading);
+    }
+}
+
+/// Cell of a project in a column of the Projects table
+fn project_cell(app: &App, project: &ProjectDto, column: Column, as_of: NaiveDate) -> Cell<'static> {
+    let cell = |text: String, field: &'static str, style: Style| {
+        Cell::from(text).style(flashed(app, project.id, &[field], style))
+    };
+    match column {
+        Column::Name => {
+            let mark = if app.marked.contains(&project.id) { "◆ " } else { "" };
+            let star = if app.ui_state.favorites.contains(&project.id) { "★ " } else { "" };
+            let watched = if app.ui_state.watched.contains(&project.id) { "◉ " } else { "" };
+            cell(format!("{}{}{}{}", mark, star, watched, project.display_name()), "name", styles::text())
+        }
+        Column::Client => {
+            let client = app.loaded.clients.iter().find(|c| c.id == project.client_id).map_or("-", |c| c.display_name());
+            cell(client.to_string(), "clientId", styles::text_dim())
+        }
+        Column::Manager => {
+            let manager = app.loaded.users.iter().find(|u| u.id == project.manager_id).map_or("-", |u| u.display_name());
+            cell(manager.to_string(), "managerId", styles::text_dim())
+        }
+        Column::Start => cell(project.start_date.to_string(), "startDate", styles::text()),
+        Column::PlannedEnd => cell(project.planned_end_date.to_string(), "plannedEndDate", styles::text()),
+        Column::ActualEnd => cell(
+            project.actual_end_as_of(as_of).map_or("-".to_string(), |d| d.to_string()),
+            "actualEndDate",
+            styles::text(),
+        ),
+        Column::Due => match project.days_left_as_of(as_of) {
+            Some(days) => cell(due::label(days), "plannedEndDate", Style::default().fg(due::color(days))),
+            None => Cell::from("-").style(styles::text_dim()),
+        },
+        Column::Status => {
+            let (status, status_color) = if project.is_completed_as_of(as_of) {
+                (tr!("timeline-legend-done"), colors::GREEN)
+            } else if project.is_overdue_as_of(as_of) {
+                (tr!("timeline-legend-overdue"), colors::RED)
             } else {
-                styles::text()
+                (tr!("timeline-legend-active"), colors::BLUE)
             };
+            Cell::from(status).style(Style::default().fg(status_color))
+        }
+        Column::Source => Cell::from(columns::source(&project.extra).to_string()).style(styles::info()),
+        _ => Cell::default(),
+    }
+}
 
-            let role_color = match user.role {
-                Role::Admin => colors::YELLOW,
-                Role::Manager => colors::GREEN,
-            };
+/// Highlight a cell whose value a recent load changed
+fn flashed(app: &App, id: Uuid, fields: &[&'static str], style: Style) -> Style {
+    if app.flash.is_lit(id, fields) { styles::flash() } else { style }
+}
+
+/// Render the users list view
+fn render_users_view(frame: &mut Frame, app: &App, area: Rect) {
+    let columns = app.columns(Tab::Users);
+    let user_line = |user: &UserDto, is_selected: bool| {
+        let style = if is_selected {
+            Style::default()
+                .fg(colors::BG_DARK)
+                .bg(colors::PURPLE)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            styles::text()
+        };
+
+        let role_color = match user.role {
+            Role::Admin => colors::YELLOW,
+            Role::Manager => colors::GREEN,
+        };
 
-            let content = Line::from(vec![
-                Span::styled(
+        let mut content = Line::default();
+        for (n, column) in columns.iter().enumerate() {
+            if n > 0 {
+                content.push_span(Span::styled(" | ", styles::border_dim()));
+            }
+            let span = match column {
+                Column::Name => Span::styled(
                     format!("{:20}", user.display_name()),
-                    style,
+                    flashed(app, user.id, &["name"], style),
                 ),
-                Span::styled(" | ", styles::border_dim()),
-                Span::styled(
+                Column::Login => Span::styled(
                     format!("{:20}", user.login.as_deref().unwrap_or("-")),
-                    if is_selected { style } else { styles::text_dim() },
+                    flashed(app, user.id, &["login"], if is_selected { style } else { styles::text_dim() }),
                 ),
-                Span::styled(" | ", styles::border_dim()),
-                Span::styled(
-                    format!("{:10}", user.role),
-                    if is_selected { style } else { Style::default().fg(role_color) },
+                Column::Role => Span::styled(
+                    format!("{:10}", role_label(user.role)),
+                    flashed(app, user.id, &["role"], if is_selected { style } else { Style::default().fg(role_color) }),
                 ),
-            ]);
-
-            ListItem::new(content)
-        })
-        .collect();
+                Column::Source => {
+                    Span::styled(format!("{:8}", columns::source(&user.extra)), if is_selected { style } else { styles::info() })
+                }
+                _ => continue,
+            };
+            content.push_span(span);
+        }
+        if let Some(extra) = app.scripts.as_ref().and_then(|s| s.user_column(user)) {
+            content.push_span(Span::styled(" | ", styles::border_dim()));
+            content.push_span(Span::styled(extra, if is_selected { style } else { styles::info() }));
+        }
+        content
+    };
+    let mut items: Vec<ListItem> = match &app.team_view {
+        None => app.users.iter().enumerate().map(|(i, user)| ListItem::new(user_line(user, i == app.list_selected))).collect(),
+        // Team rows, then the members indented below each open team
+        Some(view) => app
+            .team_rows()
+            .into_iter()
+            .enumerate()
+            .map(|(row, tree_row)| match tree_row {
+                TreeRow::Team { name, members, projects, collapsed } => {
+                    let style = if row == view.cursor { styles::selected() } else { styles::title_accent() };
+                    let name = name.unwrap_or_else(|| tr!("teams-none").to_string());
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{} {}", if collapsed { "▸" } else { "▾" }, name), style),
+                        Span::styled(format!("  {}", tr!("teams-counts", members = members, projects = projects)), styles::text_dim()),
+                    ]))
+                }
+                TreeRow::User(index) => {
+                    let mut line = user_line(&app.users[index], row == view.cursor);
+                    line.spans.insert(0, Span::raw("  "));
+                    ListItem::new(line)
+                }
+            })
+            .collect(),
+    };
+    if app.page_status(Tab::Users).is_some_and(|(_, _, loading)| loading) {
+        items.push(ListItem::new(Span::styled(tr!("list-loading-page"), styles::text_dim())));
+    }
 
     let list = List::new(items)
         .block(
             Block::default()
-                .title(" Users ")
+                .title(list_title(app, Tab::Users, tr!("users-title")))
                 .title_style(styles::title_accent())
                 .borders(Borders::ALL)
                 .border_style(styles::border())
@@ -264,40 +845,481 @@ fn render_users_view(frame: &mut Frame, app: &App, area: Rect) {
 
     // Render empty state
     if app.users.is_empty() {
-        render_empty_state(frame, area, "No users found", app.is_loading);
+        render_empty_state(frame, area, tr!("users-empty"), app.is_loading);
     }
 }
 
-/// Render the log area
-fn render_logs(frame: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = app
-        .logs
+/// Stats tab areas: the monthly chart on top, then overrun, on-time and SLA panels
+fn stats_layout(area: Rect) -> (Rect, std::rc::Rc<[Rect]>) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(8), Constraint::Length(10)])
+        .split(area);
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(27), Constraint::Percentage(28)])
+        .split(chunks[1]);
+    (chunks[0], bottom)
+}
+
+/// Width of each monthly bar so two bars and a gap per month fill the chart
+fn monthly_bar_width(area: Rect, months: u32) -> u16 {
+    let group_width = area.width.saturating_sub(2) / months.max(1) as u16;
+    (group_width.saturating_sub(1) / 2).max(1)
+}
+
+fn overrun_color(days: f64) -> Color {
+    if days > 7.0 {
+        colors::RED
+    } else if days > 0.0 {
+        colors::YELLOW
+    } else {
+        colors::GREEN
+    }
+}
+
+/// Render the Stats tab: monthly started/completed bars, quarterly overrun and on-time delivery
+fn render_stats_view(frame: &mut Frame, app: &App, area: Rect) {
+    match app.stats_view {
+        StatsView::ClientRanking => return render_client_ranking(frame, app, area),
+        StatsView::History => return render_history_charts(frame, app, area),
+        StatsView::Trends => {}
+    }
+    let (from, to) = app.stats_range();
+    let as_of = app.reference_date();
+    let (monthly_area, bottom) = stats_layout(area);
+
+    // Started vs completed, two bars per month sized to fill the width
+    let months = stats::monthly_delivery(&app.projects, from, app.stats_months, as_of);
+    let bar_width = monthly_bar_width(monthly_area, app.stats_months);
+    let last = stats::add_months(to, -1);
+    let title = Line::from(vec![
+        Span::styled(format!(" {} ", tr!("stats-monthly-title", from = from.format("%Y-%m"), to = last.format("%Y-%m"))), styles::title_accent()),
+        Span::styled("■ ", Style::default().fg(colors::BLUE)),
+        Span::styled(format!("{} ", tr!("stats-started")), styles::text_dim()),
+        Span::styled("■ ", Style::default().fg(colors::GREEN)),
+        Span::styled(format!("{} ", tr!("stats-completed")), styles::text_dim()),
+    ]);
+    let mut monthly = BarChart::default()
+        .block(
+            Block::default()
+                .title(title)
+                .title_bottom(Line::styled(format!(" {} ", tr!("stats-keys")), styles::text_hint()))
+                .borders(Borders::ALL)
+                .border_style(styles::border()),
+        )
+        .bar_width(bar_width)
+        .bar_gap(0)
+        .group_gap(1)
+        .value_style(Style::default().fg(colors::BG_DARK))
+        .label_style(styles::text_dim());
+    for month in &months {
+        let bars = [
+            Bar::default().value(month.started).style(Style::default().fg(colors::BLUE)),
+            Bar::default().value(month.completed).style(Style::default().fg(colors::GREEN)),
+        ];
+        let label = month.month.format(if bar_width >= 3 { "%m/%y" } else { "%m" }).to_string();
+        monthly = monthly.data(BarGroup::default().label(Line::from(label)).bars(&bars));
+    }
+    frame.render_widget(monthly, monthly_area);
+
+    // Average days late per quarter
+    let quarters = stats::quarterly_overrun(&app.projects, from, to, as_of);
+    let bars: Vec<Bar> = quarters
         .iter()
-        .rev()
-        .take(area.height.saturating_sub(2) as usize)
-        .map(|entry| {
-            let (prefix, color) = match entry.level {
-                LogLevel::Info => ("i", colors::BLUE),
-                LogLevel::Success => ("+", colors::GREEN),
-                LogLevel::Warning => ("!", colors::YELLOW),
-                LogLevel::Error => ("x", colors::RED),
+        .map(|q| {
+            let days = q.average_days.unwrap_or(0.0);
+            let text = match q.average_days {
+                Some(days) => format!("{:.1}{}", days, tr!("stats-days-suffix")),
+                None => "-".to_string(),
             };
-
-            ListItem::new(Line::from(vec![
-                Span::styled(format!("[{}] ", prefix), Style::default().fg(color)),
-                Span::styled(&entry.message, styles::text_dim()),
-            ]))
+            let color = overrun_color(days);
+            Bar::default()
+                .value(days.round() as u64)
+                .text_value(text)
+                .label(Line::from(format!("Q{} {}", q.quarter.month0() / 3 + 1, q.quarter.format("%y"))))
+                .style(Style::default().fg(color))
+                .value_style(Style::default().fg(colors::BG_DARK).bg(color))
         })
         .collect();
-
-    let list = List::new(items)
+    let overrun = BarChart::default()
         .block(
             Block::default()
-                .title(" System Log ")
-                .title_style(Style::default().fg(colors::FG_DIM))
+                .title(format!(" {} ", tr!("stats-overrun-title")))
+                .title_style(styles::title())
                 .borders(Borders::ALL)
-                .border_style(styles::border_dim())
-                .style(Style::default().bg(colors::BG_DARK)),
+                .border_style(styles::border()),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(7)
+        .bar_gap(2)
+        .label_style(styles::text_dim());
+    frame.render_widget(overrun, bottom[0]);
+
+    // On-time delivery share for the whole range
+    let on_time = stats::on_time_delivery(&app.projects, from, to, as_of);
+    let block = Block::default()
+        .title(format!(" {} ", tr!("stats-on-time-title")))
+        .title_style(styles::title())
+        .borders(Borders::ALL)
+        .border_style(styles::border());
+    let inner = block.inner(bottom[1]);
+    frame.render_widget(block, bottom[1]);
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(1)])
+        .split(inner);
+    let percent = on_time.percent();
+    let color = match percent {
+        Some(p) if p >= 80.0 => colors::GREEN,
+        Some(p) if p >= 50.0 => colors::YELLOW,
+        Some(_) => colors::RED,
+        None => colors::FG_DIM,
+    };
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(color).bg(colors::BG_MEDIUM))
+        .ratio(percent.unwrap_or(0.0) / 100.0)
+        .label(match percent {
+            Some(p) => format!("{:.0}%", p),
+            None => "-".to_string(),
+        });
+    frame.render_widget(gauge, parts[0]);
+    let started: u64 = months.iter().map(|m| m.started).sum();
+    let summary = vec![
+        Line::styled(tr!("stats-on-time", on_time = on_time.on_time, completed = on_time.completed), styles::text()),
+        Line::styled(tr!("stats-started-total", count = started), styles::text_dim()),
+        Line::styled(tr!("stats-overdue-now", count = stats::overdue_count(&app.projects, as_of)), styles::text_dim()),
+    ];
+    frame.render_widget(Paragraph::new(summary), parts[2]);
+
+    render_sla_summary(frame, app, bottom[2]);
+}
+
+/// Render breaching project counts per configured SLA rule
+fn render_sla_summary(frame: &mut Frame, app: &App, area: Rect) {
+    let lines: Vec<Line> = if app.config.sla.is_empty() {
+        vec![Line::styled(tr!("sla-none"), styles::text_dim())]
+    } else {
+        crate::sla::summary(&app.config.sla, &app.projects, app.reference_date())
+            .into_iter()
+            .map(|(rule, breaches)| {
+                let (mark, style) = if breaches == 0 { ("✓", styles::success()) } else { ("⚑", styles::sla_breach()) };
+                Line::from(vec![
+                    Span::styled(format!("{} {:>3} ", mark, breaches), style),
+                    Span::styled(rule.name.clone(), styles::text()),
+                ])
+            })
+            .collect()
+    };
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .title(format!(" {} ", tr!("sla-title")))
+            .title_style(styles::title())
+            .borders(Borders::ALL)
+            .border_style(styles::border()),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+/// Render the client ranking page of the Stats tab
+fn render_client_ranking(frame: &mut Frame, app: &App, area: Rect) {
+    let (from, to) = app.stats_range();
+    let (sort_column, reverse) = app.ranking_sort;
+    let header_cell = |column: RankingColumn, text: &str| {
+        if column == sort_column {
+            Cell::from(format!("{} {}", text, if reverse { "▲" } else { "▼" })).style(styles::title_accent())
+        } else {
+            Cell::from(text.to_string()).style(styles::form_label())
+        }
+    };
+    let header = Row::new(vec![
+        Cell::from("#").style(styles::form_label()),
+        header_cell(RankingColumn::Client, tr!("ranking-client")),
+        header_cell(RankingColumn::Completed, tr!("ranking-completed")),
+        Cell::from(tr!("ranking-on-time")).style(styles::form_label()),
+        header_cell(RankingColumn::OnTime, tr!("ranking-on-time-percent")),
+        header_cell(RankingColumn::Overrun, tr!("ranking-overrun")),
+    ]);
+
+    let rows: Vec<Row> = app
+        .client_ranking()
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let percent_style = match row.on_time_percent {
+                Some(p) if p >= 80.0 => styles::success(),
+                Some(p) if p >= 50.0 => styles::warning(),
+                Some(_) => styles::error(),
+                None => styles::text_dim(),
+            };
+            let optional = |value: Option<f64>, suffix: &str| value.map_or("-".to_string(), |v| format!("{:.1}{}", v, suffix));
+            let cells = vec![
+                Cell::from(row.rank.to_string()).style(styles::text_dim()),
+                Cell::from(row.client).style(styles::text()),
+                Cell::from(row.completed.to_string()),
+                Cell::from(row.on_time.to_string()),
+                Cell::from(optional(row.on_time_percent, "%")).style(percent_style),
+                Cell::from(optional(row.average_overrun_days, tr!("stats-days-suffix"))),
+            ];
+            let row = Row::new(cells);
+            if i == app.list_selected {
+                row.style(styles::selected())
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let last = stats::add_months(to, -1);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(4),
+            Constraint::Min(20),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(14),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(format!(" {} ", tr!("ranking-title", from = from.format("%Y-%m"), to = last.format("%Y-%m"))))
+            .title_style(styles::title_accent())
+            .title_bottom(Line::styled(format!(" {} ", tr!("ranking-keys")), styles::text_hint()))
+            .borders(Borders::ALL)
+            .border_style(styles::border()),
+    )
+    .style(styles::text());
+    frame.render_widget(table, area);
+
+    if app.clients.is_empty() {
+        render_empty_state(frame, area, tr!("clients-empty"), app.is_loading);
+    }
+}
+
+/// Render the API console: request line, status and the pretty-printed response
+fn render_console_view(frame: &mut Frame, app: &App, area: Rect) {
+    let console = &app.console;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(area);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("GET ", styles::title_accent()),
+        Span::styled(console.input.clone(), styles::text()),
+        Span::styled("█", Style::default().fg(colors::BLUE)),
+    ]))
+    .block(
+        Block::default()
+            .title(format!(" {} ", tr!("console-title")))
+            .title_style(styles::title_accent())
+            .title_bottom(Line::styled(format!(" {} ", tr!("console-hint")), styles::text_hint()))
+            .borders(Borders::ALL)
+            .border_style(styles::border_focused())
+            .style(Style::default().bg(colors::BG_DARK)),
+    );
+    frame.render_widget(input, chunks[0]);
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_DARK));
+    let Some(response) = &console.response else {
+        let message = if console.running { tr!("console-running") } else { tr!("console-empty") };
+        frame.render_widget(Paragraph::new(message).style(styles::text_dim()).alignment(Alignment::Center).block(block), chunks[1]);
+        return;
+    };
+    let elapsed = response.elapsed.as_millis();
+    let title = match response.status {
+        Some(status) => Line::from(vec![
+            Span::styled(
+                format!(" {} ", status),
+                if status < 400 { styles::success() } else { styles::error() }.add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{} ", tr!("console-status", request = response.request.to_string(), ms = elapsed)),
+                styles::text_dim(),
+            ),
+        ]),
+        None => Line::styled(format!(" {} ", tr!("console-failed", request = response.request.to_string(), ms = elapsed)), styles::error()),
+    };
+    block = block.title(title);
+    if console.running {
+        block = block.title(Line::styled(format!(" {} ", tr!("console-running")), styles::warning()).right_aligned());
+    }
+    // JSON is pretty-printed and highlighted like the inspector, anything else shown as it came
+    let lines = match serde_json::from_str::<serde_json::Value>(&response.body) {
+        Ok(value) => inspector::lines(&value, &[]),
+        Err(_) => response.body.lines().map(|line| Line::styled(line.to_string(), styles::text())).collect(),
+    };
+    let scroll = console.scroll.min(lines.len().saturating_sub(1) as u16);
+    frame.render_widget(Paragraph::new(lines).block(block).scroll((scroll, 0)), chunks[1]);
+}
+
+/// Render the Inbox tab: alerts, newest first, unread ones bold
+fn render_inbox_view(frame: &mut Frame, app: &App, area: Rect) {
+    let inbox = &app.ui_state.inbox;
+    let items: Vec<ListItem> = inbox
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let is_selected = i == app.list_selected;
+            let mut style = if is_selected {
+                Style::default().fg(colors::BG_DARK).bg(colors::PURPLE)
+            } else if item.read {
+                styles::text_dim()
+            } else {
+                styles::text()
+            };
+            if !item.read {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            let kind_style = match item.event {
+                InboxEvent::Overdue { .. } => styles::error(),
+                InboxEvent::DueSoon { .. } | InboxEvent::Rule { .. } | InboxEvent::Watched { .. } => styles::warning(),
+                InboxEvent::Reassigned { .. } | InboxEvent::Changed { .. } => styles::info(),
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(if item.read { "  " } else { "● " }, styles::info()),
+                Span::styled(format!("{} ", item.at.format("%Y-%m-%d %H:%M")), if is_selected { style } else { styles::text_dim() }),
+                Span::styled(format!("{:12}", item.event.label()), if is_selected { style } else { kind_style }),
+                Span::styled(" | ", styles::border_dim()),
+                Span::styled(item.event.message(), style),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(" {} ", tr!("inbox-title", unread = inbox.unread_count())))
+                .title_style(styles::title_accent())
+                .borders(Borders::ALL)
+                .border_style(styles::border())
+                .style(Style::default().bg(colors::BG_DARK)),
+        )
+        .style(styles::text());
+
+    frame.render_widget(list, area);
+
+    if inbox.items.is_empty() {
+        render_empty_state(frame, area, tr!("inbox-empty"), false);
+    }
+}
+
+/// Render the audit log: one page of events, newest first
+fn render_audit_view(frame: &mut Frame, app: &App, area: Rect) {
+    let audit = &app.audit;
+    let (pages, total) = match &audit.feed {
+        AuditFeed::Loaded(result) => (result.total_pages.max(1), result.total_count),
+        _ => (audit.page.max(1), 0),
+    };
+    let mut block = Block::default()
+        .title(format!(" {} ", tr!("audit-title", page = audit.page.max(1), pages = pages, total = total)))
+        .title_style(styles::title_accent())
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_DARK));
+    if !audit.search.is_empty() {
+        block = block.title_bottom(Line::styled(format!(" {} ", tr!("audit-search", search = audit.search.clone())), styles::text_hint()));
+    }
+
+    let header = Row::new(vec![
+        Cell::from(tr!("audit-column-time")).style(styles::form_label()),
+        Cell::from(tr!("audit-column-actor")).style(styles::form_label()),
+        Cell::from(tr!("audit-column-action")).style(styles::form_label()),
+        Cell::from(tr!("audit-column-entity")).style(styles::form_label()),
+        Cell::from(tr!("audit-column-summary")).style(styles::form_label()),
+    ]);
+    let rows: Vec<Row> = audit
+        .events()
+        .iter()
+        .enumerate()
+        .map(|(i, event)| {
+            let action_style = match event.action.to_lowercase().as_str() {
+                "created" => styles::success(),
+                "deleted" => styles::error(),
+                _ => styles::info(),
+            };
+            let entity = match &event.entity_name {
+                Some(name) => format!("{} {}", event.entity_type, name),
+                None => format!("{} {}", event.entity_type, event.entity_id),
+            };
+            let row = Row::new(vec![
+                Cell::from(event.timestamp.format("%Y-%m-%d %H:%M").to_string()).style(styles::text_dim()),
+                Cell::from(event.actor.clone().unwrap_or_else(|| tr!("audit-system").to_string())),
+                Cell::from(event.action.clone()).style(action_style),
+                Cell::from(entity),
+                Cell::from(event.summary.clone().unwrap_or_default()).style(styles::text_dim()),
+            ]);
+            if i == app.list_selected {
+                row.style(styles::selected())
+            } else {
+                row
+            }
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(16),
+            Constraint::Length(14),
+            Constraint::Length(10),
+            Constraint::Min(24),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header)
+    .block(block)
+    .style(styles::text());
+    // The state only scrolls the selected row into view; rows style themselves
+    let mut state = TableState::default().with_selected(Some(app.list_selected));
+    frame.render_stateful_widget(table, area, &mut state);
+
+    match &audit.feed {
+        AuditFeed::NotLoaded | AuditFeed::Loading => render_empty_state(frame, area, tr!("audit-loading"), false),
+        AuditFeed::Unsupported => render_empty_state(frame, area, tr!("audit-unsupported"), false),
+        AuditFeed::Failed(error) => render_empty_state(frame, area, &tr!("audit-failed", error = error.clone()), false),
+        AuditFeed::Loaded(_) if audit.events().is_empty() => render_empty_state(frame, area, tr!("audit-empty"), false),
+        AuditFeed::Loaded(_) => {}
+    }
+}
+
+/// Render the log area
+fn render_logs(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .logs
+        .iter()
+        .rev()
+        .take(area.height.saturating_sub(2) as usize)
+        .map(|entry| {
+            let (prefix, color) = match entry.level {
+                LogLevel::Info => ("i", colors::BLUE),
+                LogLevel::Success => ("+", colors::GREEN),
+                LogLevel::Warning => ("!", colors::YELLOW),
+                LogLevel::Error => ("x", colors::RED),
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("[{}] ", prefix), Style::default().fg(color)),
+                Span::styled(&entry.message, styles::text_dim()),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(" {} ", tr!("log-title")))
+                .title_style(Style::default().fg(colors::FG_DIM))
+                .borders(Borders::ALL)
+                .border_style(styles::border_dim())
+                .style(Style::default().bg(colors::BG_DARK)),
         );
 
     frame.render_widget(list, area);
@@ -306,7 +1328,7 @@ fn render_logs(frame: &mut Frame, app: &App, area: Rect) {
 /// Render empty state message
 fn render_empty_state(frame: &mut Frame, area: Rect, message: &str, is_loading: bool) {
     let text = if is_loading {
-        "Loading..."
+        tr!("loading")
     } else {
         message
     };
@@ -345,16 +1367,16 @@ fn render_form_modal(frame: &mut Frame, app: &App, area: Rect) {
 
     // Form title
     let title = match &form.form_type {
-        FormType::CreateClient => " New Client ",
-        FormType::EditClient(_) => " Edit Client ",
-        FormType::CreateProject => " New Project ",
-        FormType::EditProject(_) => " Edit Project ",
-        FormType::CreateUser => " New User ",
-        FormType::EditUser(_) => " Edit User ",
+        FormType::CreateClient => tr!("form-new-client"),
+        FormType::EditClient(_) => tr!("form-edit-client"),
+        FormType::CreateProject => tr!("form-new-project"),
+        FormType::EditProject(_) => tr!("form-edit-project"),
+        FormType::CreateUser => tr!("form-new-user"),
+        FormType::EditUser(_) => tr!("form-edit-user"),
     };
 
     let block = Block::default()
-        .title(title)
+        .title(format!(" {} ", title))
         .title_style(styles::title())
         .borders(Borders::ALL)
         .border_style(styles::border_focused())
@@ -412,7 +1434,7 @@ fn render_client_form(frame: &mut Frame, form: &FormState, area: Rect) {
     // Name field
     render_text_field(
         frame,
-        "Name:",
+        &field_label(FormField::ClientName),
         &form.client_name,
         form.current_field() == FormField::ClientName,
         false,
@@ -422,7 +1444,7 @@ fn render_client_form(frame: &mut Frame, form: &FormState, area: Rect) {
     // Address field
     render_text_field(
         frame,
-        "Address:",
+        &field_label(FormField::ClientAddress),
         &form.client_address,
         form.current_field() == FormField::ClientAddress,
         false,
@@ -457,7 +1479,7 @@ fn render_project_form(frame: &mut Frame, form: &FormState, app: &App, area: Rec
     // Name field
     render_text_field(
         frame,
-        "Name:",
+        &field_label(FormField::ProjectName),
         &form.project_name,
         form.current_field() == FormField::ProjectName,
         false,
@@ -465,26 +1487,26 @@ fn render_project_form(frame: &mut Frame, form: &FormState, app: &App, area: Rec
     );
 
     // Client selector
-    let client_name = app.clients
+    let client_name = app.loaded.clients
         .get(form.project_client_idx)
         .map(|c| c.display_name().to_string())
-        .unwrap_or_else(|| "(Select client)".to_string());
+        .unwrap_or_else(|| tr!("select-client").to_string());
     render_selector_field(
         frame,
-        "Client:",
+        &field_label(FormField::ProjectClient),
         &client_name,
         form.current_field() == FormField::ProjectClient,
         chunks[1],
     );
 
     // Manager selector
-    let manager_name = app.users
+    let manager_name = app.loaded.users
         .get(form.project_manager_idx)
         .map(|u| u.display_name().to_string())
-        .unwrap_or_else(|| "(Select manager)".to_string());
+        .unwrap_or_else(|| tr!("select-manager").to_string());
     render_selector_field(
         frame,
-        "Manager:",
+        &field_label(FormField::ProjectManager),
         &manager_name,
         form.current_field() == FormField::ProjectManager,
         chunks[2],
@@ -493,7 +1515,7 @@ fn render_project_form(frame: &mut Frame, form: &FormState, app: &App, area: Rec
     // Start Date field (date picker)
     render_date_picker_field(
         frame,
-        "Start Date:",
+        &field_label(FormField::ProjectStartDate),
         &form.project_start_date,
         form.current_field() == FormField::ProjectStartDate,
         chunks[3],
@@ -502,7 +1524,7 @@ fn render_project_form(frame: &mut Frame, form: &FormState, app: &App, area: Rec
     // End Date field (date picker)
     render_date_picker_field(
         frame,
-        "End Date:",
+        &field_label(FormField::ProjectEndDate),
         &form.project_end_date,
         form.current_field() == FormField::ProjectEndDate,
         chunks[4],
@@ -515,6 +1537,52 @@ fn render_project_form(frame: &mut Frame, form: &FormState, app: &App, area: Rec
         form.current_field() == FormField::CancelButton,
         chunks[6],
     );
+
+    // Search list of the client or manager field, over the fields below it
+    if let (Some(picker), Some(entity)) = (&form.picker, form.current_field().picker_entity()) {
+        let field_area = if entity == EntityType::Client { chunks[1] } else { chunks[2] };
+        let candidates = picker::candidates(entity, &app.loaded.clients, &app.loaded.users);
+        render_entity_picker(frame, picker, &candidates, field_area);
+    }
+}
+
+/// Render the open picker list below its selector field
+fn render_entity_picker(frame: &mut Frame, picker: &Picker, candidates: &[String], field_area: Rect) {
+    let screen = frame.area();
+    let top = field_area.bottom().saturating_sub(1);
+    let rows = picker.matches.len().clamp(1, picker::VISIBLE_ROWS) as u16;
+    let list_area = Rect {
+        x: field_area.x + 14,
+        y: top,
+        width: field_area.width.saturating_sub(14),
+        height: (rows + 3).min(screen.bottom().saturating_sub(top)),
+    };
+    frame.render_widget(Clear, list_area);
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(" ⌕ ", styles::text_hint()),
+        Span::styled(format!("{}▏", picker.query), styles::text()),
+    ])];
+    if picker.matches.is_empty() {
+        lines.push(Line::styled(format!(" {}", tr!("picker-no-matches")), styles::text_dim()));
+    }
+    let width = list_area.width.saturating_sub(2) as usize;
+    for (row, &index) in picker.matches.iter().enumerate().skip(picker.offset()).take(picker::VISIBLE_ROWS) {
+        let style = if row == picker.selected { styles::selected() } else { styles::text() };
+        let text = format!(" {}", candidates[index]);
+        let padding = width.saturating_sub(text.chars().count());
+        lines.push(Line::styled(format!("{}{}", text, " ".repeat(padding)), style));
+    }
+
+    let block = Block::default()
+        .title_bottom(Line::styled(
+            format!(" {} ", tr!("picker-count", shown = picker.matches.len(), total = candidates.len())),
+            styles::text_hint(),
+        ))
+        .borders(Borders::ALL)
+        .border_style(styles::border_focused())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+    frame.render_widget(Paragraph::new(lines).block(block), list_area);
 }
 
 /// Render user form fields
@@ -535,7 +1603,7 @@ fn render_user_form(frame: &mut Frame, form: &FormState, area: Rect) {
     // Name field
     render_text_field(
         frame,
-        "Name:",
+        &field_label(FormField::UserName),
         &form.user_name,
         form.current_field() == FormField::UserName,
         false,
@@ -545,7 +1613,7 @@ fn render_user_form(frame: &mut Frame, form: &FormState, area: Rect) {
     // Login field
     render_text_field(
         frame,
-        "Login:",
+        &field_label(FormField::UserLogin),
         &form.user_login,
         form.current_field() == FormField::UserLogin,
         false,
@@ -555,7 +1623,7 @@ fn render_user_form(frame: &mut Frame, form: &FormState, area: Rect) {
     // Password field (masked)
     render_text_field(
         frame,
-        "Password:",
+        &field_label(FormField::UserPassword),
         &form.user_password,
         form.current_field() == FormField::UserPassword,
         true,
@@ -565,8 +1633,8 @@ fn render_user_form(frame: &mut Frame, form: &FormState, area: Rect) {
     // Role selector
     render_selector_field(
         frame,
-        "Role:",
-        &form.user_role.to_string(),
+        &field_label(FormField::UserRole),
+        role_label(form.user_role),
         form.current_field() == FormField::UserRole,
         chunks[3],
     );
@@ -657,7 +1725,7 @@ fn render_date_picker_field(
     };
 
     // Show navigation hints when focused, plus calendar icon
-    let hint = if is_focused { " ◀-7 ▲+1 ▼-1 +7▶" } else { "" };
+    let hint = if is_focused { " ◀▶±1d ▲▼±7d" } else { "" };
     let calendar_icon = "📅";
     let display = format!(" {} {}{}", calendar_icon, value, hint);
 
@@ -675,160 +1743,852 @@ fn render_date_picker_field(
     frame.render_widget(input, chunks[1]);
 }
 
-/// Render a selector/dropdown field
-fn render_selector_field(
-    frame: &mut Frame,
-    label: &str,
-    value: &str,
-    is_focused: bool,
-    area: Rect,
-) {
-    // Use 14 characters for label column to match text fields
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Length(14), Constraint::Min(10)])
-        .split(area);
-
-    // Label
-    let label_text = Paragraph::new(label)
-        .style(styles::form_label())
-        .alignment(Alignment::Right);
-    frame.render_widget(label_text, chunks[0]);
+/// Render a selector/dropdown field
+fn render_selector_field(
+    frame: &mut Frame,
+    label: &str,
+    value: &str,
+    is_focused: bool,
+    area: Rect,
+) {
+    // Use 14 characters for label column to match text fields
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(14), Constraint::Min(10)])
+        .split(area);
+
+    // Label
+    let label_text = Paragraph::new(label)
+        .style(styles::form_label())
+        .alignment(Alignment::Right);
+    frame.render_widget(label_text, chunks[0]);
+
+    // Selector display with arrows indicators
+    let input_style = if is_focused {
+        styles::form_input_focused()
+    } else {
+        styles::form_input()
+    };
+
+    let arrows = if is_focused { " ▲▼ ⌕" } else { " ▼" };
+    let input = Paragraph::new(format!(" {}{}", value, arrows))
+        .style(input_style)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(if is_focused {
+                    styles::border_focused()
+                } else {
+                    styles::border_dim()
+                }),
+        );
+    frame.render_widget(input, chunks[1]);
+}
+
+/// Render form buttons
+fn render_form_buttons(
+    frame: &mut Frame,
+    save_focused: bool,
+    cancel_focused: bool,
+    area: Rect,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Length(12),
+            Constraint::Length(2),
+            Constraint::Length(12),
+            Constraint::Percentage(30),
+        ])
+        .split(area);
+
+    // Save button
+    let save_style = if save_focused {
+        styles::button_focused()
+    } else {
+        styles::button()
+    };
+    let save_btn = Paragraph::new(format!("  [ {} ]  ", tr!("button-save")))
+        .style(save_style)
+        .alignment(Alignment::Center);
+    frame.render_widget(save_btn, chunks[1]);
+
+    // Cancel button
+    let cancel_style = if cancel_focused {
+        styles::button_danger()
+    } else {
+        styles::button()
+    };
+    let cancel_btn = Paragraph::new(format!(" [ {} ] ", tr!("button-cancel")))
+        .style(cancel_style)
+        .alignment(Alignment::Center);
+    frame.render_widget(cancel_btn, chunks[3]);
+}
+
+/// Preview lines shown in a confirm dialog before the rest are summed up
+const MAX_CONFIRM_PREVIEW: usize = 12;
+
+/// Render confirmation dialog
+fn render_confirm_dialog(frame: &mut Frame, app: &App, area: Rect) {
+    let dialog = match &app.confirm_dialog {
+        Some(d) => d,
+        None => return,
+    };
+
+    // Room for the preview lines, if the action has any
+    let preview_rows = dialog.preview.len().min(MAX_CONFIRM_PREVIEW) as u16;
+    let width = if dialog.preview.is_empty() && dialog.confirm_word.is_none() { 45 } else { 72 };
+    let popup_area = centered_rect(width, 10 + preview_rows, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", dialog.title))
+        .title_style(Style::default().fg(colors::RED).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(colors::RED))
+        .style(Style::default().bg(colors::BG_MEDIUM));
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),               // Message
+            Constraint::Length(preview_rows), // Preview
+            Constraint::Length(1),            // Spacer
+            Constraint::Length(1),            // Buttons
+        ])
+        .margin(1)
+        .split(inner);
+
+    // Message
+    let mut message = Text::from(dialog.message.as_str());
+    if let Some(word) = &dialog.confirm_word {
+        message.push_line(Line::styled(tr!("confirm-production", profile = word.clone()), styles::error()));
+    }
+    let message = Paragraph::new(message)
+        .style(styles::text())
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center);
+    frame.render_widget(message, chunks[0]);
+
+    let mut preview: Vec<Line> = dialog.preview.iter().map(|line| Line::from(line.as_str())).collect();
+    if preview.len() > MAX_CONFIRM_PREVIEW {
+        let more = preview.len() - MAX_CONFIRM_PREVIEW + 1;
+        preview.truncate(MAX_CONFIRM_PREVIEW - 1);
+        preview.push(Line::from(tr!("confirm-preview-more", count = more)));
+    }
+    frame.render_widget(Paragraph::new(preview).style(styles::text_dim()), chunks[1]);
+
+    // On production the name is typed instead of choosing a button
+    if dialog.confirm_word.is_some() {
+        let style = if dialog.word_matches() { styles::button_danger() } else { styles::form_input_focused() };
+        let input = Line::from(vec![
+            Span::styled(format!("{} ", tr!("confirm-production-input")), styles::form_label()),
+            Span::styled(format!("{}▏", dialog.typed), style),
+        ]);
+        frame.render_widget(Paragraph::new(input).alignment(Alignment::Center), chunks[3]);
+        return;
+    }
+
+    // Buttons
+    let button_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(25),
+            Constraint::Length(10),
+            Constraint::Percentage(10),
+            Constraint::Length(10),
+            Constraint::Percentage(25),
+        ])
+        .split(chunks[3]);
+
+    let no_style = if !dialog.yes_focused {
+        styles::button_focused()
+    } else {
+        styles::button()
+    };
+    let no_btn = Paragraph::new(format!("  [ {} ]  ", tr!("button-no")))
+        .style(no_style)
+        .alignment(Alignment::Center);
+    frame.render_widget(no_btn, button_chunks[1]);
+
+    let yes_style = if dialog.yes_focused {
+        styles::button_danger()
+    } else {
+        styles::button()
+    };
+    let yes_btn = Paragraph::new(format!(" [ {} ]  ", tr!("button-yes")))
+        .style(yes_style)
+        .alignment(Alignment::Center);
+    frame.render_widget(yes_btn, button_chunks[3]);
+}
+
+/// Render a single-line text prompt
+fn render_prompt(frame: &mut Frame, app: &App, area: Rect) {
+    let prompt = match &app.prompt {
+        Some(p) => p,
+        None => return,
+    };
+
+    let popup_area = centered_rect(50, 6, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", prompt.title))
+        .title_style(styles::title())
+        .borders(Borders::ALL)
+        .border_style(styles::border_focused())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let input = Paragraph::new(format!(" {}█", prompt.input))
+        .style(styles::form_input_focused())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(styles::border_focused()),
+        );
+    frame.render_widget(input, Rect::new(inner.x, inner.y, inner.width, 3));
+
+    let hint = Paragraph::new(tr!("prompt-hint"))
+        .style(styles::text_hint())
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, Rect::new(inner.x, inner.y + 3, inner.width, 1));
+}
+
+/// Render the burndown/burnup chart of the selected client or manager
+/// Render the "What changed?" report of the latest loads
+fn render_changes_popup(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_area = centered_rect(area.width.saturating_sub(8).max(40), area.height.saturating_sub(6).max(12), area);
+    frame.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(format!(" {} ", tr!("diff-title")))
+        .title_style(styles::title())
+        .title_bottom(Line::styled(format!(" {} ", tr!("diff-close")), styles::text_hint()))
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+
+    if app.changes.changes.is_empty() {
+        frame.render_widget(
+            Paragraph::new(tr!("diff-empty")).style(styles::text_dim()).alignment(Alignment::Center).block(block),
+            popup_area,
+        );
+        return;
+    }
+
+    let mut lines = Vec::new();
+    for change in &app.changes.changes {
+        let (marker, style) = match change.kind {
+            ChangeKind::Added => ("+", styles::success()),
+            ChangeKind::Removed => ("-", Style::default().fg(colors::RED)),
+            ChangeKind::Modified(_) => ("~", Style::default().fg(colors::YELLOW)),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", marker), style.add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{:8} ", change.entity_type.to_string()), styles::text_dim()),
+            Span::styled(change.name.clone(), styles::text()),
+        ]));
+        if let ChangeKind::Modified(fields) = &change.kind {
+            for field in fields {
+                let value = |v: &str| diff::display_value(field.field, v, &app.loaded.clients, &app.loaded.users);
+                let new_style = if field.is_slip() { Style::default().fg(colors::RED).add_modifier(Modifier::BOLD) } else { styles::text() };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("     {:14} ", diff::field_label(field.field)), styles::text_hint()),
+                    Span::styled(value(&field.old), styles::text_dim()),
+                    Span::styled(" → ", styles::text_hint()),
+                    Span::styled(value(&field.new), new_style),
+                ]));
+            }
+        }
+    }
+    let scroll = app.changes_view.unwrap_or(0).min(lines.len().saturating_sub(1) as u16);
+    frame.render_widget(Paragraph::new(lines).block(block).scroll((scroll, 0)), popup_area);
+}
+
+fn render_inspector(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(inspector) = app.inspector else {
+        return;
+    };
+    let popup_area = centered_rect(area.width.saturating_sub(8).max(40), area.height.saturating_sub(6).max(12), area);
+    frame.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(format!(" {} ", tr!("inspector-title", entity = inspector.entity_type, id = inspector.id)))
+        .title_style(styles::title())
+        .title_bottom(Line::styled(format!(" {} ", tr!("inspector-close")), styles::text_hint()))
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+
+    let Some((value, unknown)) = app.inspected() else {
+        frame.render_widget(
+            Paragraph::new(tr!("inspector-gone")).style(styles::text_dim()).alignment(Alignment::Center).block(block),
+            popup_area,
+        );
+        return;
+    };
+    let lines = inspector::lines(&value, &unknown);
+    let scroll = inspector.scroll.min(lines.len().saturating_sub(1) as u16);
+    frame.render_widget(Paragraph::new(lines).block(block).scroll((scroll, 0)), popup_area);
+}
+
+/// Documents of a project: file list and the progress of a running download
+fn render_attachments(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(popup) = &app.attachments else {
+        return;
+    };
+    let popup_area = centered_rect(area.width.saturating_sub(8).clamp(40, 80), 14.min(area.height), area);
+    frame.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(format!(" {} ", tr!("attachments-title", name = popup.label.clone())))
+        .title_style(styles::title())
+        .title_bottom(Line::styled(format!(" {} ", tr!("attachments-hint")), styles::text_hint()))
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(if app.download.is_some() { 2 } else { 0 })])
+        .split(inner);
+
+    let message = |text: String| Paragraph::new(text).style(styles::text_dim()).alignment(Alignment::Center).wrap(Wrap { trim: true });
+    match &popup.documents {
+        DocumentList::Loading => frame.render_widget(message(tr!("attachments-loading").to_string()), chunks[0]),
+        DocumentList::Unsupported => frame.render_widget(message(tr!("attachments-unsupported").to_string()), chunks[0]),
+        DocumentList::Failed(error) => frame.render_widget(message(tr!("attachments-failed", error = error.clone())), chunks[0]),
+        DocumentList::Loaded(documents) if documents.is_empty() => {
+            frame.render_widget(message(tr!("attachments-empty").to_string()), chunks[0]);
+        }
+        DocumentList::Loaded(documents) => {
+            let rows = documents.iter().enumerate().map(|(i, document)| {
+                let style = if i == popup.selected { styles::selected() } else { styles::text() };
+                Row::new(vec![
+                    Cell::from(document.file_name.clone()),
+                    Cell::from(document.size.map(attachments::format_size).unwrap_or_default()).style(styles::text_dim()),
+                    Cell::from(document.uploaded_at.map(|at| at.format("%Y-%m-%d").to_string()).unwrap_or_default())
+                        .style(styles::text_dim()),
+                ])
+                .style(style)
+            });
+            let table = Table::new(rows, [Constraint::Min(20), Constraint::Length(10), Constraint::Length(10)]);
+            frame.render_widget(table, chunks[0]);
+        }
+    }
+
+    if let Some(download) = &app.download {
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(1)])
+            .split(chunks[1]);
+        let received = attachments::format_size(download.received);
+        let label = match download.total {
+            Some(total) => tr!("attachments-progress", name = download.file_name.clone(), received = received, total = attachments::format_size(total)),
+            None => tr!("attachments-progress-unknown", name = download.file_name.clone(), received = received),
+        };
+        frame.render_widget(Paragraph::new(label).style(styles::text_dim()), parts[0]);
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(colors::BLUE).bg(colors::BG_HIGHLIGHT))
+            .ratio(download.ratio().unwrap_or(0.0))
+            .label(download.ratio().map(|r| format!("{:.0}%", r * 100.0)).unwrap_or_default());
+        frame.render_widget(gauge, parts[1]);
+    }
+}
+
+/// Global search: query line and the best matches with their type and details
+fn render_search(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(search) = &app.search else {
+        return;
+    };
+    let popup_area = centered_rect(area.width.saturating_sub(8).clamp(40, 80), 16.min(area.height), area);
+    frame.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(format!(" {} ", tr!("search-title")))
+        .title_style(styles::title())
+        .title_bottom(Line::styled(format!(" {} ", tr!("search-hint")), styles::text_hint()))
+        .borders(Borders::ALL)
+        .border_style(styles::border_focused())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("› ", styles::title_accent()),
+            Span::styled(search.input.clone(), styles::text()),
+            Span::styled("█", Style::default().fg(colors::BLUE)),
+        ]),
+        Line::styled(
+            tr!("search-count", shown = search.results.len(), total = app.search_index.len()),
+            styles::text_dim(),
+        ),
+    ];
+    let visible = inner.height.saturating_sub(2) as usize;
+    let skip = search.selected.saturating_sub(visible.saturating_sub(1));
+    let kind = |entity_type| match entity_type {
+        EntityType::Project => tr!("search-kind-project"),
+        EntityType::Client => tr!("search-kind-client"),
+        EntityType::User => tr!("search-kind-user"),
+    };
+    let kind_width = [EntityType::Project, EntityType::Client, EntityType::User]
+        .map(|entity_type| kind(entity_type).chars().count())
+        .into_iter()
+        .max()
+        .unwrap_or_default();
+    for (i, doc) in search.results.iter().enumerate().skip(skip).take(visible) {
+        let style = if i == search.selected { styles::selected() } else { styles::text() };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:<width$} ", kind(doc.entity_type), width = kind_width), Style::default().fg(colors::PURPLE)),
+            Span::styled(doc.label.clone(), style),
+            Span::styled(format!("  {}", doc.detail), styles::text_dim()),
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// List title with the sort spec and filter expression, if any
+fn list_title(app: &App, tab: Tab, title: &str) -> String {
+    let mut title = format!(" {} ", title);
+    let (spec, _) = app.sort_spec(tab);
+    if !spec.is_empty() {
+        title.push_str(&format!("· {} ", tr!("list-sorted-by", spec = sort::describe(spec))));
+    }
+    if let Some(expr) = tab.entity_type().and_then(|entity| app.expression(entity)) {
+        title.push_str(&tr!("list-filter-tag", filter = expr.as_str()));
+        title.push(' ');
+    }
+    if let Some((loaded, total, _)) = app.page_status(tab) {
+        title.push_str(&tr!("list-paged-tag", loaded = loaded, total = total));
+        title.push(' ');
+    }
+    if tab == Tab::Projects && app.archived_hidden > 0 {
+        title.push_str(&tr!("list-archived-tag", count = app.archived_hidden));
+        title.push(' ');
+    }
+    if tab == Tab::Projects && !app.marked.is_empty() {
+        title.push_str(&tr!("list-marked-tag", count = app.marked_projects().len()));
+        title.push(' ');
+    }
+    title
+}
+
+fn render_sort_dialog(frame: &mut Frame, app: &App, area: Rect) {
+    let Some((tab, selected)) = app.sort_dialog else {
+        return;
+    };
+    let (spec, columns) = app.sort_spec(tab);
+    let popup_area = centered_rect(50, columns.len() as u16 + 2, area);
+    frame.render_widget(Clear, popup_area);
+
+    let lines: Vec<Line> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            // Priority and direction of the column, if it is part of the spec
+            let marker = match spec.iter().position(|k| k.column == *column) {
+                Some(priority) => format!("{} {}", priority + 1, spec[priority].arrow()),
+                None => String::new(),
+            };
+            let style = if i == selected { styles::selected() } else { styles::text() };
+            Line::from(vec![
+                Span::styled(format!(" {:4}", marker), styles::title_accent()),
+                Span::styled(column.label().to_string(), style),
+            ])
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(format!(" {} ", tr!("sort-title")))
+        .title_style(styles::title())
+        .title_bottom(Line::styled(format!(" {} ", tr!("sort-hint")), styles::text_hint()))
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+    frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+fn render_column_dialog(frame: &mut Frame, app: &App, area: Rect) {
+    let Some((tab, selected)) = app.column_dialog else {
+        return;
+    };
+    let Some(layout) = app.column_layout(tab) else {
+        return;
+    };
+    let popup_area = centered_rect(56, layout.order.len() as u16 + 2, area);
+    frame.render_widget(Clear, popup_area);
+
+    let lines: Vec<Line> = layout
+        .order
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let shown = !layout.hidden.contains(column);
+            let style = if i == selected {
+                styles::selected()
+            } else if shown {
+                styles::text()
+            } else {
+                styles::text_dim()
+            };
+            Line::from(vec![
+                Span::styled(if shown { " [x] " } else { " [ ] " }, styles::title_accent()),
+                Span::styled(column.label().to_string(), style),
+            ])
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(format!(" {} ", tr!("columns-title")))
+        .title_style(styles::title())
+        .title_bottom(Line::styled(format!(" {} ", tr!("columns-hint")), styles::text_hint()))
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+    frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+/// Settings with their current values; the highlighted value shows it can be stepped
+fn render_settings_dialog(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(dialog) = &app.settings else {
+        return;
+    };
+    let popup_area = centered_rect(56, Setting::ALL.len() as u16 + 2, area);
+    frame.render_widget(Clear, popup_area);
+
+    let label_width = Setting::ALL.iter().map(|s| s.label().chars().count()).max().unwrap_or(0);
+    let lines: Vec<Line> = Setting::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, setting)| {
+            let label = format!(" {:<width$}  ", setting.label(), width = label_width);
+            let value = setting.value(&app.config);
+            if i == dialog.selected {
+                Line::from(vec![
+                    Span::styled(label, styles::selected()),
+                    Span::styled(format!("‹ {} ›", value), styles::selected()),
+                ])
+            } else {
+                Line::from(vec![Span::styled(label, styles::text()), Span::styled(format!("  {}", value), styles::title_accent())])
+            }
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(format!(" {} ", tr!("settings-title")))
+        .title_style(styles::title())
+        .title_bottom(Line::styled(format!(" {} ", tr!("settings-hint")), styles::text_hint()))
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+    frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+/// Stages of the connection diagnosis per API URL, with what to check for the failed one
+fn render_diagnosis(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(reports) = &app.diagnosis else {
+        return;
+    };
+    let mut lines = Vec::new();
+    if reports.is_empty() {
+        lines.push(Line::styled(format!(" {}", tr!("diagnose-running")), styles::text_dim()));
+    }
+    for (i, diagnosis) in reports.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::raw(""));
+        }
+        let mut title = vec![Span::styled(format!(" {}", diagnosis.url), styles::title_accent())];
+        if let Some(proxy) = &diagnosis.proxy {
+            title.push(Span::styled(format!("  {}", tr!("diagnose-proxy", proxy = proxy)), styles::text_dim()));
+        }
+        lines.push(Line::from(title));
+        for report in &diagnosis.stages {
+            let name = match report.stage {
+                Stage::Dns => "DNS ",
+                Stage::Tcp => "TCP ",
+                Stage::Tls => "TLS ",
+                Stage::Http => "HTTP",
+            };
+            let elapsed = format!("  {} ms", report.elapsed.as_millis());
+            let (mark, detail, style, elapsed) = match &report.outcome {
+                Outcome::Passed(detail) => ("✓", detail.clone(), Style::default().fg(colors::GREEN), elapsed),
+                Outcome::Failed(error) => ("✗", error.clone(), Style::default().fg(colors::RED), elapsed),
+                Outcome::Skipped => ("–", tr!("diagnose-skipped").to_string(), styles::text_dim(), String::new()),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("   {} {}  ", mark, name), style),
+                Span::styled(detail, styles::text()),
+                Span::styled(elapsed, styles::text_dim()),
+            ]));
+        }
+        let advice = match diagnosis.failed() {
+            Some(Stage::Dns) => tr!("diagnose-hint-dns"),
+            Some(Stage::Tcp) => tr!("diagnose-hint-tcp"),
+            Some(Stage::Tls) => tr!("diagnose-hint-tls"),
+            Some(Stage::Http) => tr!("diagnose-hint-http"),
+            None => tr!("diagnose-ok"),
+        };
+        lines.push(Line::styled(format!("   {}", advice), styles::warning()));
+    }
+
+    let width = area.width.saturating_sub(8).min(90);
+    // Long errors wrap
+    let inner = width.saturating_sub(2).max(1) as usize;
+    let height: usize = lines.iter().map(|line| line.width().div_ceil(inner).max(1)).sum();
+    let popup_area = centered_rect(width, height as u16 + 2, area);
+    frame.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(format!(" {} ", tr!("diagnose-title")))
+        .title_style(styles::title())
+        .title_bottom(Line::styled(format!(" {} ", tr!("diagnose-keys")), styles::text_hint()))
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+    frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), popup_area);
+}
+
+/// Actions of the selected row, with the keys that do the same
+fn render_context_menu(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(menu) = &app.context_menu else {
+        return;
+    };
+    let popup_area = centered_rect(44, menu.actions.len() as u16 + 2, area);
+    frame.render_widget(Clear, popup_area);
+
+    let width = popup_area.width.saturating_sub(2) as usize;
+    let lines: Vec<Line> = menu
+        .actions
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let style = if i == menu.selected { styles::selected() } else { styles::text() };
+            let label = format!(" {}", action.label());
+            let key = format!("{} ", action.key());
+            let padding = width.saturating_sub(label.chars().count() + key.chars().count());
+            Line::from(vec![
+                Span::styled(format!("{}{}", label, " ".repeat(padding)), style),
+                Span::styled(key, if i == menu.selected { style } else { styles::text_hint() }),
+            ])
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(format!(" {} ", menu.title))
+        .title_style(styles::title())
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_MEDIUM));
+    frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+fn render_burndown_popup(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(burndown) = &app.burndown else {
+        return;
+    };
+    let popup_area = centered_rect(area.width.saturating_sub(8).max(40), area.height.saturating_sub(6).max(12), area);
+    frame.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(format!(" {} ", tr!("burndown-title", name = burndown.label)))
+        .title_style(styles::title())
+        .title_bottom(Line::styled(format!(" {} ", tr!("burndown-close")), styles::text_hint()))
+        .borders(Borders::ALL)
+        .border_style(styles::border())
+        .style(Style::default().bg(colors::BG_MEDIUM));
 
-    // Selector display with arrows indicators
-    let input_style = if is_focused {
-        styles::form_input_focused()
-    } else {
-        styles::form_input()
+    let projects = app.burndown_projects();
+    let points = stats::burndown(&projects, app.reference_date());
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        frame.render_widget(
+            Paragraph::new(tr!("burndown-empty")).style(styles::text_dim()).alignment(Alignment::Center).block(block),
+            popup_area,
+        );
+        return;
     };
 
-    let arrows = if is_focused { " ▲▼" } else { " ▼" };
-    let input = Paragraph::new(format!(" {}{}", value, arrows))
-        .style(input_style)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(if is_focused {
-                    styles::border_focused()
-                } else {
-                    styles::border_dim()
-                }),
+    let day = |date: NaiveDate| (date - first.date).num_days() as f64;
+    let series = |value: fn(&stats::BurnPoint) -> Option<i64>| -> Vec<(f64, f64)> {
+        points.iter().filter_map(|p| value(p).map(|v| (day(p.date), v as f64))).collect()
+    };
+    let planned = series(|p| Some(p.planned_remaining));
+    let remaining = series(|p| p.remaining);
+    let completed = series(|p| p.completed);
+    let total = first.planned_remaining;
+    let now = app.reference_date().clamp(first.date, last.date);
+    let today = [(day(now), 0.0), (day(now), total as f64)];
+
+    let datasets = vec![
+        Dataset::default()
+            .name(tr!("burndown-planned"))
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(colors::FG_DIM))
+            .data(&planned),
+        Dataset::default()
+            .name(tr!("burndown-remaining"))
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(colors::BLUE))
+            .data(&remaining),
+        Dataset::default()
+            .name(tr!("burndown-completed"))
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(colors::GREEN))
+            .data(&completed),
+        Dataset::default()
+            .name(tr!("timeline-legend-today"))
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(colors::YELLOW))
+            .data(&today),
+    ];
+    let middle = first.date + chrono::Duration::days((last.date - first.date).num_days() / 2);
+    let x_labels: Vec<Line> = [first.date, middle, last.date].iter().map(|d| Line::from(d.format("%Y-%m-%d").to_string())).collect();
+    let y_labels: Vec<Line> = [0, total / 2, total].iter().map(|v| Line::from(v.to_string())).collect();
+    let chart = Chart::new(datasets)
+        .block(block)
+        .x_axis(
+            Axis::default()
+                .style(styles::text_dim())
+                .bounds([0.0, day(last.date).max(1.0)])
+                .labels(x_labels),
+        )
+        .y_axis(
+            Axis::default()
+                .title(tr!("burndown-axis"))
+                .style(styles::text_dim())
+                .bounds([0.0, total.max(1) as f64])
+                .labels(y_labels),
         );
-    frame.render_widget(input, chunks[1]);
+    frame.render_widget(chart, popup_area);
 }
 
-/// Render form buttons
-fn render_form_buttons(
-    frame: &mut Frame,
-    save_focused: bool,
-    cancel_focused: bool,
-    area: Rect,
-) {
+/// Render the Stats history page: active and overdue projects per day and the on-track share
+fn render_history_charts(frame: &mut Frame, app: &App, area: Rect) {
+    let (from, to) = app.history_range();
+    let last = to.pred_opt().unwrap_or(to);
+    let block = |title: Line<'static>| Block::default().title(title).borders(Borders::ALL).border_style(styles::border());
+    let legend = |color, name: &'static str| {
+        [Span::styled("■ ", Style::default().fg(color)), Span::styled(format!("{} ", name), styles::text_dim())]
+    };
+    let title = [Span::styled(format!(" {} ", tr!("history-title", from = from, to = last)), styles::title_accent())]
+        .into_iter()
+        .chain(legend(colors::BLUE, tr!("history-active")))
+        .chain(legend(colors::RED, tr!("history-overdue")))
+        .collect::<Vec<_>>();
+    let counts_block = block(Line::from(title))
+        .title_bottom(Line::styled(format!(" {} ", tr!("history-keys")), styles::text_hint()));
+
+    let points = app.history_points.as_ref().map(|(_, points)| points.as_slice()).unwrap_or_default();
+    if points.is_empty() {
+        let text = if app.store.is_some() { tr!("history-empty") } else { tr!("history-no-store") };
+        frame.render_widget(Paragraph::new(text).style(styles::text_dim()).alignment(Alignment::Center).block(counts_block), area);
+        return;
+    }
+
     let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(30),
-            Constraint::Length(12),
-            Constraint::Length(2),
-            Constraint::Length(12),
-            Constraint::Percentage(30),
-        ])
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Min(6)])
         .split(area);
-
-    // Save button
-    let save_style = if save_focused {
-        styles::button_focused()
-    } else {
-        styles::button()
+    let day = |date: NaiveDate| (date - from).num_days() as f64;
+    let series = |value: fn(&DailyPoint) -> Option<f64>| -> Vec<(f64, f64)> {
+        points.iter().filter_map(|p| value(p).map(|v| (day(p.day), v))).collect()
     };
-    let save_btn = Paragraph::new("  [ Save ]  ")
-        .style(save_style)
-        .alignment(Alignment::Center);
-    frame.render_widget(save_btn, chunks[1]);
-
-    // Cancel button
-    let cancel_style = if cancel_focused {
-        styles::button_danger()
-    } else {
-        styles::button()
+    let active = series(|p| Some(p.active as f64));
+    let overdue = series(|p| Some(p.overdue as f64));
+    let on_track = series(DailyPoint::on_track_percent);
+    let middle = from + chrono::Duration::days((last - from).num_days() / 2);
+    let x_axis = || {
+        let labels: Vec<Line> = [from, middle, last].iter().map(|d| Line::from(d.format("%Y-%m-%d").to_string())).collect();
+        Axis::default().style(styles::text_dim()).bounds([0.0, day(last).max(1.0)]).labels(labels)
     };
-    let cancel_btn = Paragraph::new(" [ Cancel ] ")
-        .style(cancel_style)
-        .alignment(Alignment::Center);
-    frame.render_widget(cancel_btn, chunks[3]);
+    let line = |color, data| Dataset::default().marker(Marker::Braille).graph_type(GraphType::Line).style(Style::default().fg(color)).data(data);
+
+    let top = points.iter().map(|p| p.active).max().unwrap_or(0).max(1);
+    let counts = Chart::new(vec![
+        line(colors::BLUE, &active),
+        line(colors::RED, &overdue),
+    ])
+    .block(counts_block)
+    .x_axis(x_axis())
+    .y_axis(
+        Axis::default()
+            .style(styles::text_dim())
+            .bounds([0.0, top as f64])
+            .labels([0, top].iter().map(|v| Line::from(v.to_string())).collect::<Vec<_>>()),
+    );
+    frame.render_widget(counts, chunks[0]);
+
+    let share = Chart::new(vec![line(colors::GREEN, &on_track)])
+        .block(block(Line::styled(format!(" {} ", tr!("history-on-track-title")), styles::title())))
+        .x_axis(x_axis())
+        .y_axis(
+            Axis::default()
+                .style(styles::text_dim())
+                .bounds([0.0, 100.0])
+                .labels(["0%", "50%", "100%"].map(Line::from).to_vec()),
+        );
+    frame.render_widget(share, chunks[1]);
 }
 
-/// Render confirmation dialog
-fn render_confirm_dialog(frame: &mut Frame, app: &App, area: Rect) {
-    let dialog = match &app.confirm_dialog {
-        Some(d) => d,
-        None => return,
+/// Render error popup
+/// Render the shutdown screen: writes still being sent before quitting
+fn render_shutdown(frame: &mut Frame, app: &App, area: Rect) {
+    let Some((done, total)) = app.draining else {
+        return;
     };
-
-    let popup_area = centered_rect(45, 10, area);
+    let popup_area = centered_rect(56.min(area.width), 7, area);
     frame.render_widget(Clear, popup_area);
-
     let block = Block::default()
-        .title(format!(" {} ", dialog.title))
-        .title_style(Style::default().fg(colors::RED).add_modifier(Modifier::BOLD))
+        .title(format!(" {} ", tr!("shutdown-title")))
+        .title_style(styles::title())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(colors::RED))
+        .border_style(styles::border())
         .style(Style::default().bg(colors::BG_MEDIUM));
-
-    let inner = block.inner(popup_area);
+    let inner = block.inner(popup_area).inner(Margin::new(1, 0));
     frame.render_widget(block, popup_area);
 
-    let chunks = Layout::default()
+    let rows = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(3),    // Message
-            Constraint::Length(1), // Spacer
-            Constraint::Length(1), // Buttons
-        ])
-        .margin(1)
+        .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Length(1), Constraint::Length(1), Constraint::Min(0)])
         .split(inner);
+    let message = tr!("shutdown-sending", done = (done + 1).min(total), total = total);
+    frame.render_widget(Paragraph::new(message).style(styles::text()), rows[0]);
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(colors::BLUE).bg(colors::BG_HIGHLIGHT))
+        .ratio(if total == 0 { 1.0 } else { done as f64 / total as f64 })
+        .label(format!("{} / {}", done, total));
+    frame.render_widget(gauge, rows[2]);
+    let hint = tr!("shutdown-grace", secs = crate::api::DRAIN_GRACE.as_secs());
+    frame.render_widget(Paragraph::new(hint).style(styles::text_hint()), rows[4]);
+}
 
-    // Message
-    let message = Paragraph::new(dialog.message.as_str())
-        .style(styles::text())
-        .wrap(Wrap { trim: true })
-        .alignment(Alignment::Center);
-    frame.render_widget(message, chunks[0]);
-
-    // Buttons
-    let button_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(25),
-            Constraint::Length(10),
-            Constraint::Percentage(10),
-            Constraint::Length(10),
-            Constraint::Percentage(25),
-        ])
-        .split(chunks[2]);
-
-    let no_style = if !dialog.yes_focused {
-        styles::button_focused()
-    } else {
-        styles::button()
-    };
-    let no_btn = Paragraph::new("  [ No ]  ")
-        .style(no_style)
-        .alignment(Alignment::Center);
-    frame.render_widget(no_btn, button_chunks[1]);
-
-    let yes_style = if dialog.yes_focused {
-        styles::button_danger()
-    } else {
-        styles::button()
-    };
-    let yes_btn = Paragraph::new(" [ Yes ]  ")
-        .style(yes_style)
-        .alignment(Alignment::Center);
-    frame.render_widget(yes_btn, button_chunks[3]);
+/// Stack the watch toasts in the top right corner of the content, newest on top
+fn render_toasts(frame: &mut Frame, app: &App, area: Rect) {
+    let width = area.width.saturating_sub(4).min(50);
+    let mut y = area.y + 1;
+    for toast in app.toasts.iter().rev() {
+        let toast_area = Rect::new(area.right().saturating_sub(width + 2), y, width, 4).intersection(area);
+        if toast_area.height < 4 {
+            break;
+        }
+        frame.render_widget(Clear, toast_area);
+        let block = Block::default()
+            .title(Span::styled(format!(" {} ", tr!("toast-watched-title")), styles::flash()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(colors::YELLOW))
+            .style(Style::default().bg(colors::BG_HIGHLIGHT));
+        let message = Paragraph::new(toast.message.as_str()).style(styles::text()).wrap(Wrap { trim: true });
+        frame.render_widget(message.block(block), toast_area);
+        y += 4;
+    }
 }
 
-/// Render error popup
 fn render_error_popup(frame: &mut Frame, app: &App, area: Rect) {
     let popup = app.error_popup.as_ref().unwrap();
 
@@ -863,7 +2623,7 @@ fn render_error_popup(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(text, inner);
 
     // Dismiss hint
-    let hint = Paragraph::new("Press ESC or ENTER to dismiss")
+    let hint = Paragraph::new(tr!("error-dismiss-hint"))
         .style(styles::text_hint())
         .alignment(Alignment::Center);
 
@@ -879,100 +2639,248 @@ fn render_error_popup(frame: &mut Frame, app: &App, area: Rect) {
 /// Render help overlay
 fn render_help_overlay(frame: &mut Frame, area: Rect) {
     let popup_width = 60;
-    let popup_height = 29;
+    let popup_height = 52;
     let popup_area = centered_rect(popup_width, popup_height, area);
 
     frame.render_widget(Clear, popup_area);
 
     let help_text = vec![
         Line::from(Span::styled(
-            "Keyboard Shortcuts",
+            tr!("help-heading"),
             Style::default()
                 .fg(colors::BLUE)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Navigation", Style::default().fg(colors::PURPLE).add_modifier(Modifier::BOLD)),
+            Span::styled(tr!("help-navigation"), Style::default().fg(colors::PURPLE).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(vec![
             Span::styled("  Tab/Shift+Tab ", Style::default().fg(colors::BLUE)),
-            Span::raw("Switch tabs / form fields"),
+            Span::raw(tr!("help-switch-tabs")),
         ]),
         Line::from(vec![
             Span::styled("  j/k or Up/Down", Style::default().fg(colors::BLUE)),
-            Span::raw("Move up/down in lists"),
+            Span::raw(tr!("help-move")),
         ]),
         Line::from(vec![
             Span::styled("  h/l or Left/Right", Style::default().fg(colors::BLUE)),
-            Span::raw("Scroll timeline"),
+            Span::raw(tr!("help-scroll")),
+        ]),
+        Line::from(vec![
+            Span::styled("  [ / ] or Alt+h/l", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-snap")),
+        ]),
+        Line::from(vec![
+            Span::styled("  T             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-projects-table")),
+        ]),
+        Line::from(vec![
+            Span::styled("  h/l, Enter    ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-inline-edit")),
+        ]),
+        Line::from(vec![
+            Span::styled("  v             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-compare")),
+        ]),
+        Line::from(vec![
+            Span::styled("  n/N  b/B      ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-compare-cycle")),
+        ]),
+        Line::from(vec![
+            Span::styled("  @             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-as-of")),
+        ]),
+        Line::from(vec![
+            Span::styled("  M             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-mermaid")),
+        ]),
+        Line::from(vec![
+            Span::styled("  S             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-sandbox")),
+        ]),
+        Line::from(vec![
+            Span::styled("  < >  { }  X   ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-sandbox-edit")),
+        ]),
+        Line::from(vec![
+            Span::styled("  m + 1-9       ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-bookmark-save")),
+        ]),
+        Line::from(vec![
+            Span::styled("  1-9           ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-bookmark-jump")),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("CRUD Operations", Style::default().fg(colors::PURPLE).add_modifier(Modifier::BOLD)),
+            Span::styled(tr!("help-crud"), Style::default().fg(colors::PURPLE).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(vec![
             Span::styled("  c             ", Style::default().fg(colors::BLUE)),
-            Span::raw("Create new item"),
+            Span::raw(tr!("help-create")),
         ]),
         Line::from(vec![
             Span::styled("  e             ", Style::default().fg(colors::BLUE)),
-            Span::raw("Edit selected item"),
+            Span::raw(tr!("help-edit")),
+        ]),
+        Line::from(vec![
+            Span::styled("  D             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-clone")),
+        ]),
+        Line::from(vec![
+            Span::styled("  Space         ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-mark")),
+        ]),
+        Line::from(vec![
+            Span::styled("  R             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-reschedule")),
+        ]),
+        Line::from(vec![
+            Span::styled("  H             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-archived")),
         ]),
         Line::from(vec![
             Span::styled("  d / Delete    ", Style::default().fg(colors::BLUE)),
-            Span::raw("Delete selected item"),
+            Span::raw(tr!("help-delete")),
+        ]),
+        Line::from(vec![
+            Span::styled("  u / Ctrl+r    ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-undo")),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Form Editing", Style::default().fg(colors::PURPLE).add_modifier(Modifier::BOLD)),
+            Span::styled(tr!("help-form"), Style::default().fg(colors::PURPLE).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(vec![
             Span::styled("  Tab           ", Style::default().fg(colors::BLUE)),
-            Span::raw("Move to next field"),
+            Span::raw(tr!("help-next-field")),
         ]),
         Line::from(vec![
             Span::styled("  Up/Down       ", Style::default().fg(colors::BLUE)),
-            Span::raw("Change dropdown/date (+/-1 day)"),
+            Span::raw(tr!("help-dropdown")),
         ]),
         Line::from(vec![
-            Span::styled("  Left/Right    ", Style::default().fg(colors::BLUE)),
-            Span::raw("Date picker: +/-7 days"),
+            Span::styled("  hl jk HL t    ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-date-picker")),
         ]),
         Line::from(vec![
             Span::styled("  Type text     ", Style::default().fg(colors::BLUE)),
-            Span::raw("Edit text fields directly"),
+            Span::raw(tr!("help-type")),
         ]),
         Line::from(vec![
             Span::styled("  Enter         ", Style::default().fg(colors::BLUE)),
-            Span::raw("Next field / Submit on button"),
+            Span::raw(tr!("help-enter")),
         ]),
         Line::from(vec![
             Span::styled("  Esc           ", Style::default().fg(colors::BLUE)),
-            Span::raw("Cancel / Close form"),
+            Span::raw(tr!("help-esc")),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("General", Style::default().fg(colors::PURPLE).add_modifier(Modifier::BOLD)),
+            Span::styled(tr!("help-general"), Style::default().fg(colors::PURPLE).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(vec![
             Span::styled("  r             ", Style::default().fg(colors::BLUE)),
-            Span::raw("Refresh data"),
+            Span::raw(tr!("help-refresh")),
         ]),
         Line::from(vec![
             Span::styled("  p             ", Style::default().fg(colors::BLUE)),
-            Span::raw("Toggle particles"),
+            Span::raw(tr!("help-particles")),
+        ]),
+        Line::from(vec![
+            Span::styled("  b             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-burndown")),
+        ]),
+        Line::from(vec![
+            Span::styled("  s             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-sort")),
+        ]),
+        Line::from(vec![
+            Span::styled("  C             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-columns")),
+        ]),
+        Line::from(vec![
+            Span::styled("  v             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-teams")),
+        ]),
+        Line::from(vec![
+            Span::styled("  .             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-menu")),
+        ]),
+        Line::from(vec![
+            Span::styled("  /             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-filter")),
+        ]),
+        Line::from(vec![
+            Span::styled("  E / Ctrl+y    ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-markdown")),
+        ]),
+        Line::from(vec![
+            Span::styled("  J             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-inspector")),
+        ]),
+        Line::from(vec![
+            Span::styled("  A             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-attachments")),
+        ]),
+        Line::from(vec![
+            Span::styled("  y / Y         ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-copy-id")),
+        ]),
+        Line::from(vec![
+            Span::styled("  *             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-favorite")),
+        ]),
+        Line::from(vec![
+            Span::styled("  W             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-watch")),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+o/Ctrl+i ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-jumps")),
+        ]),
+        Line::from(vec![
+            Span::styled("  w             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-changes")),
+        ]),
+        Line::from(vec![
+            Span::styled("  F2            ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-my-projects")),
+        ]),
+        Line::from(vec![
+            Span::styled("  F3            ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-debug")),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+t        ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-telemetry")),
+        ]),
+        Line::from(vec![
+            Span::styled("  ,             ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-settings")),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+d        ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-diagnose")),
+        ]),
+        Line::from(vec![
+            Span::styled("  F4            ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-console")),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+f        ", Style::default().fg(colors::BLUE)),
+            Span::raw(tr!("help-search")),
         ]),
         Line::from(vec![
             Span::styled("  q/Ctrl+C      ", Style::default().fg(colors::BLUE)),
-            Span::raw("Quit"),
+            Span::raw(tr!("help-quit")),
         ]),
     ];
 
     let paragraph = Paragraph::new(help_text)
         .block(
             Block::default()
-                .title(" Help ")
+                .title(format!(" {} ", tr!("help-title")))
                 .title_style(styles::title())
                 .borders(Borders::ALL)
                 .border_style(styles::border())
@@ -983,6 +2891,19 @@ fn render_help_overlay(frame: &mut Frame, area: Rect) {
     frame.render_widget(paragraph, popup_area);
 }
 
+/// Form label for a field ("Name:")
+fn field_label(field: FormField) -> String {
+    format!("{}:", field.label())
+}
+
+/// Localized role name
+fn role_label(role: Role) -> &'static str {
+    match role {
+        Role::Admin => tr!("role-admin"),
+        Role::Manager => tr!("role-manager"),
+    }
+}
+
 /// Helper to create a centered rectangle
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let x = area.x + (area.width.saturating_sub(width)) / 2;
@@ -990,15 +2911,10 @@ fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     Rect::new(x, y, width.min(area.width), height.min(area.height))
 }
 
-/// Render a mini calendar popup next to the form
+/// Render the calendar of the focused form date field next to the form
 fn render_mini_calendar(frame: &mut Frame, date_str: &str, screen_area: Rect, form_area: Rect) {
-    // Parse the date string
-    let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-        .unwrap_or_else(|_| chrono::Local::now().date_naive());
-
-    // Calendar dimensions
-    let cal_width = 24;
-    let cal_height = 10;
+    let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d").unwrap_or_else(|_| crate::clock::today());
+    let (cal_width, cal_height) = (datepicker::WIDTH, datepicker::HEIGHT);
 
     // Position calendar to the right of the form if space, otherwise to the left
     let cal_x = if form_area.x + form_area.width + cal_width + 2 < screen_area.width {
@@ -1017,119 +2933,15 @@ fn render_mini_calendar(frame: &mut Frame, date_str: &str, screen_area: Rect, fo
         cal_width,
         cal_height,
     );
-
-    frame.render_widget(Clear, cal_area);
-
-    // Build calendar lines
-    let month_names = [
-        "January", "February", "March", "April", "May", "June",
-        "July", "August", "September", "October", "November", "December",
-    ];
-    let month_name = month_names[date.month0() as usize];
-    let year = date.year();
-
-    // Get first day of month and number of days
-    let first_of_month = NaiveDate::from_ymd_opt(year, date.month(), 1).unwrap();
-    let days_in_month = if date.month() == 12 {
-        NaiveDate::from_ymd_opt(year + 1, 1, 1)
-    } else {
-        NaiveDate::from_ymd_opt(year, date.month() + 1, 1)
-    }.unwrap().pred_opt().unwrap().day();
-
-    // Day of week for first day (0 = Monday, 6 = Sunday)
-    let first_weekday = first_of_month.weekday().num_days_from_monday() as usize;
-
-    let mut lines = Vec::new();
-
-    // Header with month/year
-    let header = format!("{} {}", month_name, year);
-    lines.push(Line::from(vec![
-        Span::styled(
-            format!("{:^22}", header),
-            Style::default().fg(colors::BLUE).add_modifier(Modifier::BOLD),
-        ),
-    ]));
-
-    // Day of week headers
-    lines.push(Line::from(vec![
-        Span::styled(" Mo Tu We Th Fr ", styles::text_dim()),
-        Span::styled("Sa ", Style::default().fg(colors::BLUE)),
-        Span::styled("Su", Style::default().fg(colors::RED)),
-    ]));
-
-    // Build week rows
-    let mut day = 1u32;
-    let selected_day = date.day();
-    let today = chrono::Local::now().date_naive();
-    let today_day = if today.year() == year && today.month() == date.month() {
-        Some(today.day())
-    } else {
-        None
-    };
-
-    for week in 0..6 {
-        let mut spans = Vec::new();
-        spans.push(Span::raw(" "));
-
-        for weekday in 0..7 {
-            if (week == 0 && weekday < first_weekday) || day > days_in_month {
-                spans.push(Span::raw("   "));
-            } else {
-                let is_selected = day == selected_day;
-                let is_today = today_day == Some(day);
-                let is_weekend = weekday >= 5;
-
-                let style = if is_selected {
-                    Style::default()
-                        .fg(colors::BG_DARK)
-                        .bg(colors::BLUE)
-                        .add_modifier(Modifier::BOLD)
-                } else if is_today {
-                    Style::default()
-                        .fg(colors::YELLOW)
-                        .add_modifier(Modifier::BOLD)
-                } else if is_weekend {
-                    if weekday == 5 {
-                        Style::default().fg(colors::BLUE)
-                    } else {
-                        Style::default().fg(colors::RED)
-                    }
-                } else {
-                    styles::text()
-                };
-
-                spans.push(Span::styled(format!("{:2} ", day), style));
-                day += 1;
-            }
-        }
-
-        if day <= days_in_month || week == 0 {
-            lines.push(Line::from(spans));
-        }
-    }
-
-    // Instructions
-    lines.push(Line::from(vec![
-        Span::styled("▲▼±1d  ◀▶±7d", styles::text_hint()),
-    ]));
-
-    let calendar = Paragraph::new(lines)
-        .block(
-            Block::default()
-                .title(" Calendar ")
-                .title_style(styles::title())
-                .borders(Borders::ALL)
-                .border_style(styles::border_focused())
-                .style(Style::default().bg(colors::BG_MEDIUM)),
-        );
-
-    frame.render_widget(calendar, cal_area);
+    frame.render_widget(Calendar::new(date, tr!("calendar-title")), cal_area);
 }
 
-/// Calculate the number of projects (completed/total) for a client
-fn calculate_client_project_counts(projects: &[crate::models::ProjectDto], client_id: uuid::Uuid) -> (i32, i32) {
-    let client_projects: Vec<_> = projects.iter().filter(|p| p.client_id == client_id).collect();
-    let total = client_projects.len() as i32;
-    let completed = client_projects.iter().filter(|p| p.is_completed()).count() as i32;
-    (completed, total)
+/// Render the time-travel date picker
+fn render_date_picker(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(picker) = app.date_picker else {
+        return;
+    };
+    let popup_area = centered_rect(datepicker::WIDTH, datepicker::HEIGHT + 1, area);
+    let calendar = Calendar::new(picker.date, tr!("calendar-as-of")).footer(tr!("calendar-as-of-hint"));
+    frame.render_widget(calendar, popup_area);
 }
@@ -0,0 +1,333 @@
+//! Undo/redo history for mutating API operations.
+//!
+//! Each successful create/update is recorded together with the command that
+//! reverses it. Undoing sends the inverse command; once the server confirms
+//! it, the operation moves to the redo stack (and back again on redo).
+//! Deletes are not recorded: the API has no way to restore a deleted entity.
+//!
+//! Commands sent for an operation carry its [`OperationId`]
+//! (`ApiCommand::Operation`), and the worker tags their outcome with it, so
+//! only that operation's own answer completes or fails it; a failed refresh
+//! or an unrelated delete leaves it alone. Several operations may be on their
+//! way at once; they are confirmed in the order they were sent.
+
+use std::collections::VecDeque;
+
+use uuid::Uuid;
+
+use crate::api::{ApiCommand, EntityType};
+use crate::models::{
+    CreateClientDto, CreateProjectDto, CreateUserDto, UpdateClientDto, UpdateProjectDto,
+    UpdateUserDto,
+};
+use crate::tr;
+
+/// Maximum number of operations kept for undo
+pub const HISTORY_LIMIT: usize = 50;
+
+/// Links a command sent for an operation to its outcome
+pub type OperationId = u64;
+
+/// A reversible mutation
+#[derive(Debug, Clone)]
+pub enum Operation {
+    UpdateClient { id: Uuid, before: UpdateClientDto, after: UpdateClientDto },
+    UpdateProject { id: Uuid, before: UpdateProjectDto, after: UpdateProjectDto },
+    UpdateUser { id: Uuid, before: UpdateUserDto, after: UpdateUserDto },
+    /// Several projects rescheduled at once (sandbox commit)
+    Reschedule { before: Vec<(Uuid, UpdateProjectDto)>, after: Vec<(Uuid, UpdateProjectDto)> },
+    CreateClient { id: Uuid, dto: CreateClientDto },
+    CreateProject { id: Uuid, dto: CreateProjectDto },
+    CreateUser { id: Uuid, dto: CreateUserDto },
+}
+
+impl Operation {
+    /// Short description for log messages
+    pub fn label(&self) -> String {
+        match self {
+            Operation::UpdateClient { .. } => tr!("undo-label-client-update").to_string(),
+            Operation::UpdateProject { .. } => tr!("undo-label-project-update").to_string(),
+            Operation::UpdateUser { .. } => tr!("undo-label-user-update").to_string(),
+            Operation::Reschedule { after, .. } => tr!("undo-label-reschedule", count = after.len()),
+            Operation::CreateClient { .. } => tr!("undo-label-client-creation").to_string(),
+            Operation::CreateProject { .. } => tr!("undo-label-project-creation").to_string(),
+            Operation::CreateUser { .. } => tr!("undo-label-user-creation").to_string(),
+        }
+    }
+
+    /// Command that reverts this operation
+    pub fn undo_command(&self) -> ApiCommand {
+        match self {
+            Operation::UpdateClient { id, before, .. } => ApiCommand::UpdateClient(*id, before.clone()),
+            Operation::UpdateProject { id, before, .. } => ApiCommand::UpdateProject(*id, before.clone()),
+            Operation::UpdateUser { id, before, .. } => ApiCommand::UpdateUser(*id, before.clone()),
+            Operation::Reschedule { before, .. } => ApiCommand::BatchUpdateProjects(before.clone()),
+            Operation::CreateClient { id, .. } => ApiCommand::DeleteClient(*id),
+            Operation::CreateProject { id, .. } => ApiCommand::DeleteProject(*id),
+            Operation::CreateUser { id, .. } => ApiCommand::DeleteUser(*id),
+        }
+    }
+
+    /// Command that re-applies this operation
+    pub fn redo_command(&self) -> ApiCommand {
+        match self {
+            Operation::UpdateClient { id, after, .. } => ApiCommand::UpdateClient(*id, after.clone()),
+            Operation::UpdateProject { id, after, .. } => ApiCommand::UpdateProject(*id, after.clone()),
+            Operation::UpdateUser { id, after, .. } => ApiCommand::UpdateUser(*id, after.clone()),
+            Operation::Reschedule { after, .. } => ApiCommand::BatchUpdateProjects(after.clone()),
+            Operation::CreateClient { dto, .. } => ApiCommand::CreateClient(dto.clone()),
+            Operation::CreateProject { dto, .. } => ApiCommand::CreateProject(dto.clone()),
+            Operation::CreateUser { dto, .. } => ApiCommand::CreateUser(dto.clone()),
+        }
+    }
+
+    /// Record the server-assigned id of a (re-)created entity
+    pub fn set_created_id(&mut self, new_id: Uuid) {
+        if let Operation::CreateClient { id, .. }
+        | Operation::CreateProject { id, .. }
+        | Operation::CreateUser { id, .. } = self
+        {
+            *id = new_id;
+        }
+    }
+
+    /// Drop projects whose update failed from a batch operation
+    pub fn without_failed(mut self, failed: &[Uuid]) -> Self {
+        if let Operation::Reschedule { before, after } = &mut self {
+            before.retain(|(id, _)| !failed.contains(id));
+            after.retain(|(id, _)| !failed.contains(id));
+        }
+        self
+    }
+
+    /// Whether the operation touches the given entity
+    fn touches(&self, entity: EntityType, target: Uuid) -> bool {
+        match self {
+            Operation::UpdateClient { id, .. } | Operation::CreateClient { id, .. } => {
+                entity == EntityType::Client && *id == target
+            }
+            Operation::UpdateProject { id, .. } | Operation::CreateProject { id, .. } => {
+                entity == EntityType::Project && *id == target
+            }
+            Operation::UpdateUser { id, .. } | Operation::CreateUser { id, .. } => {
+                entity == EntityType::User && *id == target
+            }
+            Operation::Reschedule { after, .. } => {
+                entity == EntityType::Project && after.iter().any(|(id, _)| *id == target)
+            }
+        }
+    }
+}
+
+/// Which way an in-flight operation is going
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// A new user action
+    Do,
+    Undo,
+    Redo,
+}
+
+/// Bounded undo and redo stacks
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    undo: VecDeque<Operation>,
+    redo: Vec<Operation>,
+    /// Operations sent to the API and awaiting confirmation, oldest first
+    in_flight: Vec<(OperationId, Operation, Direction)>,
+    /// Id of the next operation sent
+    next_id: OperationId,
+}
+
+impl History {
+    /// Whether an operation is still awaiting the server's answer
+    pub fn is_busy(&self) -> bool {
+        !self.in_flight.is_empty()
+    }
+
+    /// Descriptions of the operations awaiting the server's answer
+    pub fn in_flight_labels(&self) -> Vec<String> {
+        self.in_flight.iter().map(|(_, operation, _)| operation.label()).collect()
+    }
+
+    /// Remember an operation about to be sent; returns `command` tagged with
+    /// the operation, to be sent as is
+    pub fn begin(&mut self, operation: Operation, direction: Direction, command: ApiCommand) -> ApiCommand {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.in_flight.push((id, operation, direction));
+        ApiCommand::Operation(id, Box::new(command))
+    }
+
+    /// Description of what undo (or redo) would change next
+    pub fn next_label(&self, redo: bool) -> Option<String> {
+        let next = if redo { self.redo.last() } else { self.undo.back() };
+        next.map(Operation::label)
+    }
+
+    /// Start undoing the latest operation, returning its inverse command
+    pub fn start_undo(&mut self) -> Option<(String, ApiCommand)> {
+        let operation = self.undo.pop_back()?;
+        let (label, command) = (operation.label(), operation.undo_command());
+        Some((label, self.begin(operation, Direction::Undo, command)))
+    }
+
+    /// Start redoing the latest undone operation
+    pub fn start_redo(&mut self) -> Option<(String, ApiCommand)> {
+        let operation = self.redo.pop()?;
+        let (label, command) = (operation.label(), operation.redo_command());
+        Some((label, self.begin(operation, Direction::Redo, command)))
+    }
+
+    /// Take an operation off the in-flight list
+    fn finish(&mut self, id: OperationId) -> Option<(Operation, Direction)> {
+        let index = self.in_flight.iter().position(|(in_flight, ..)| *in_flight == id)?;
+        let (_, operation, direction) = self.in_flight.remove(index);
+        Some((operation, direction))
+    }
+
+    /// Operation `id` succeeded; `created` is the id of a new entity
+    pub fn complete(&mut self, id: OperationId, created: Option<Uuid>, failed: &[Uuid]) -> Option<(String, Direction)> {
+        let (mut operation, direction) = self.finish(id)?;
+        if let Some(id) = created {
+            operation.set_created_id(id);
+        }
+        let operation = operation.without_failed(failed);
+        let label = operation.label();
+        match direction {
+            Direction::Do => {
+                self.redo.clear();
+                self.push_undo(operation);
+            }
+            Direction::Undo => self.redo.push(operation),
+            Direction::Redo => self.push_undo(operation),
+        }
+        Some((label, direction))
+    }
+
+    /// Operation `id` failed. An undo/redo stays where it was so it can be retried
+    pub fn fail(&mut self, id: OperationId) {
+        match self.finish(id) {
+            Some((operation, Direction::Undo)) => self.push_undo(operation),
+            Some((operation, Direction::Redo)) => self.redo.push(operation),
+            _ => {}
+        }
+    }
+
+    /// Forget operations on an entity that no longer exists
+    pub fn forget(&mut self, entity: EntityType, id: Uuid) {
+        self.undo.retain(|op| !op.touches(entity, id));
+        self.redo.retain(|op| !op.touches(entity, id));
+    }
+
+    fn push_undo(&mut self, operation: Operation) {
+        if self.undo.len() == HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(operation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(name: &str) -> UpdateClientDto {
+        UpdateClientDto {
+            name: Some(name.to_string()),
+            address: None,
+            projects_total: 0,
+            projects_completed: 0,
+        }
+    }
+
+    fn update(id: Uuid, before: &str, after: &str) -> Operation {
+        Operation::UpdateClient { id, before: client(before), after: client(after) }
+    }
+
+    /// Start a new operation with the command that applies it
+    fn begin(history: &mut History, operation: Operation) -> OperationId {
+        let command = operation.redo_command();
+        sent(history.begin(operation, Direction::Do, command))
+    }
+
+    /// Operation id of a command sent for it
+    fn sent(command: ApiCommand) -> OperationId {
+        match command {
+            ApiCommand::Operation(id, _) => id,
+            other => panic!("untagged {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_undo_redo_cycle_and_limit() {
+        let id = Uuid::new_v4();
+        let mut history = History::default();
+        let operation = begin(&mut history, update(id, "Old", "New"));
+        assert!(history.is_busy());
+        history.complete(operation, None, &[]);
+
+        let (_, cmd) = history.start_undo().unwrap();
+        assert!(matches!(&cmd, ApiCommand::Operation(_, cmd) if matches!(&**cmd, ApiCommand::UpdateClient(_, dto) if dto.name.as_deref() == Some("Old"))));
+        // A failed undo can be retried
+        history.fail(sent(cmd));
+        assert_eq!(history.undo.len(), 1);
+        let (_, cmd) = history.start_undo().unwrap();
+        history.complete(sent(cmd), None, &[]);
+        assert!(history.undo.is_empty());
+        assert_eq!(history.redo.len(), 1);
+
+        let (_, cmd) = history.start_redo().unwrap();
+        assert!(matches!(&cmd, ApiCommand::Operation(_, cmd) if matches!(&**cmd, ApiCommand::UpdateClient(_, dto) if dto.name.as_deref() == Some("New"))));
+        history.complete(sent(cmd), None, &[]);
+
+        for _ in 0..HISTORY_LIMIT + 5 {
+            let operation = begin(&mut history, update(Uuid::new_v4(), "a", "b"));
+            history.complete(operation, None, &[]);
+        }
+        assert_eq!(history.undo.len(), HISTORY_LIMIT);
+        assert!(history.redo.is_empty());
+    }
+
+    #[test]
+    fn test_outcomes_only_settle_their_own_operation() {
+        let mut history = History::default();
+        let first = begin(&mut history, update(Uuid::new_v4(), "a", "b"));
+        history.complete(first, None, &[]);
+        let (_, undo) = history.start_undo().unwrap();
+        let second = begin(&mut history, update(Uuid::new_v4(), "c", "d"));
+        assert_ne!(sent(undo.clone()), second);
+        assert_eq!(history.in_flight_labels().len(), 2, "a new change does not replace the undo on its way");
+
+        // The new change fails: the undo is still awaited, and then goes through
+        history.fail(second);
+        assert!(history.is_busy());
+        history.fail(second);
+        assert_eq!(history.complete(sent(undo), None, &[]).map(|(_, direction)| direction), Some(Direction::Undo));
+        assert_eq!((history.undo.len(), history.redo.len()), (0, 1));
+        assert!(!history.is_busy());
+    }
+
+    #[test]
+    fn test_recreated_entity_gets_new_id() {
+        let mut history = History::default();
+        let dto = CreateClientDto {
+            name: Some("Acme".to_string()),
+            address: None,
+            projects_total: 0,
+            projects_completed: 0,
+        };
+        let operation = begin(&mut history, Operation::CreateClient { id: Uuid::nil(), dto });
+        let first = Uuid::new_v4();
+        history.complete(operation, Some(first), &[]);
+
+        let (_, cmd) = history.start_undo().unwrap();
+        assert!(matches!(&cmd, ApiCommand::Operation(_, cmd) if matches!(**cmd, ApiCommand::DeleteClient(id) if id == first)));
+        history.complete(sent(cmd), None, &[]);
+        let (_, cmd) = history.start_redo().unwrap();
+        let second = Uuid::new_v4();
+        history.complete(sent(cmd), Some(second), &[]);
+        let (_, cmd) = history.start_undo().unwrap();
+        assert!(matches!(&cmd, ApiCommand::Operation(_, cmd) if matches!(**cmd, ApiCommand::DeleteClient(id) if id == second)));
+    }
+}
@@ -0,0 +1,285 @@
+//! Elm-style update function.
+//!
+//! Everything that happens to the application (input, frame ticks, API
+//! results, focus changes) is a [`Msg`]. [`update`] applies it to the
+//! [`App`] and returns the side effects for the event loop to perform, so
+//! the state logic can be driven without a terminal or an API worker.
+
+use std::time::Instant;
+
+use crossterm::event::{Event, KeyEvent, KeyEventKind, MouseButton, MouseEventKind};
+use serde::{Deserialize, Serialize};
+
+use crate::api::{ApiCommand, ApiMessage, EntityType};
+use crate::app::{App, InputMode};
+use crate::dirty::Dirty;
+use crate::ipc::RemoteCommand;
+use crate::notify::Alert;
+
+/// Frame rate while the terminal window is unfocused (keeps API updates visible)
+pub const UNFOCUSED_FPS: u32 = 2;
+
+/// Something that happened to the application
+///
+/// Everything except frame ticks and signals can be recorded (see `session.rs`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Msg {
+    /// A key was pressed
+    Key(KeyEvent),
+    /// Right mouse button: the context menu of the selected row
+    ContextMenu,
+    /// A frame is due; `animate` is false when animation updates are skipped
+    #[serde(skip)]
+    Tick { width: u16, height: u16, animate: bool },
+    /// The API worker reported a result
+    Api(ApiMessage),
+    /// The terminal window gained or lost focus
+    Focus(bool),
+    /// The terminal was resized
+    Resize,
+    /// A command arrived over the remote-control socket
+    Remote(RemoteCommand),
+    /// The process received a termination signal (SIGTERM, SIGHUP, ...)
+    #[serde(skip)]
+    Terminate(#[serde(skip)] &'static str),
+}
+
+impl Msg {
+    /// Translate a terminal event (key releases and mouse events other than a right click are ignored)
+    pub fn from_event(event: Event) -> Option<Self> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => Some(Msg::Key(key)),
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Right) => Some(Msg::ContextMenu),
+            Event::FocusLost => Some(Msg::Focus(false)),
+            Event::FocusGained => Some(Msg::Focus(true)),
+            Event::Resize(..) => Some(Msg::Resize),
+            _ => None,
+        }
+    }
+
+    /// Whether a session recording keeps this message
+    pub fn is_recorded(&self) -> bool {
+        !matches!(self, Msg::Tick { .. } | Msg::Terminate(_))
+    }
+}
+
+/// Side effect requested by [`update`]
+#[derive(Debug, Clone)]
+pub enum Effect {
+    /// Send a command to the API worker
+    Send(ApiCommand),
+    /// Change the target frame rate
+    SetFps(u32),
+    /// Show a desktop notification
+    Notify(Alert),
+    /// Put text on the clipboard
+    Copy(String),
+}
+
+/// Apply a message to the application state
+pub fn update(app: &mut App, msg: Msg) -> Vec<Effect> {
+    let mut effects = Vec::new();
+    match msg {
+        Msg::Key(key) => {
+            let fps_before = app.config.render.fps;
+            if let Some(cmd) = app.handle_key(key) {
+                effects.push(Effect::Send(cmd));
+            }
+            // Changed in the settings dialog
+            if app.config.render.fps != fps_before {
+                effects.push(Effect::SetFps(app.config.render.fps));
+            }
+            // Commands produced by key macros
+            effects.extend(app.pending_commands.drain(..).map(Effect::Send));
+            effects.extend(app.pending_clipboard.take().map(Effect::Copy));
+
+            queue_prefetch(app, &mut effects);
+            app.dirty.mark(Dirty::INPUT);
+            app.last_activity = Instant::now();
+        }
+        Msg::ContextMenu => {
+            // Keys go to open forms and popups; so does a click
+            if app.input_mode == InputMode::Normal && !app.popup_open() {
+                app.open_context_menu();
+            }
+            app.dirty.mark(Dirty::INPUT);
+            app.last_activity = Instant::now();
+        }
+        Msg::Tick { width, height, animate } => {
+            app.tick(width, height, animate);
+            if app.reconnect.take_due(Instant::now()) {
+                effects.push(Effect::Send(ApiCommand::CheckConnection));
+            }
+        }
+        Msg::Api(message) => {
+            let mut commands = refresh_after(&message);
+            // A dropped connection is re-checked right away
+            if matches!(message.untagged(), ApiMessage::Error(e) if e.needs_reconnect()) {
+                commands.push(ApiCommand::CheckConnection);
+            }
+            app.handle_api_message(message);
+            effects.extend(app.pending_notifications.drain(..).map(Effect::Notify));
+            effects.extend(app.pending_commands.drain(..).map(Effect::Send));
+            app.dirty.mark(Dirty::DATA);
+            app.last_activity = Instant::now();
+            effects.extend(commands.into_iter().map(Effect::Send));
+            queue_prefetch(app, &mut effects);
+        }
+        Msg::Remote(command) => {
+            if let Some(cmd) = app.handle_remote(command) {
+                effects.push(Effect::Send(cmd));
+            }
+            app.dirty.mark(Dirty::INPUT);
+            app.last_activity = Instant::now();
+        }
+        Msg::Resize => app.dirty.mark(Dirty::LAYOUT),
+        Msg::Terminate(signal) => {
+            tracing::info!(signal, "Shutting down on signal");
+            app.should_quit = true;
+            effects.push(Effect::Send(ApiCommand::Shutdown));
+        }
+        Msg::Focus(focused) => {
+            // Pause animations and throttle frames while the terminal is in the background
+            app.focused = focused;
+            app.dirty.mark(Dirty::LAYOUT);
+            if focused {
+                effects.push(Effect::SetFps(app.config.render.fps));
+            } else {
+                effects.push(Effect::SetFps(UNFOCUSED_FPS));
+            }
+        }
+    }
+    effects
+}
+
+/// A new selection or a new page gets what comes next fetched in the background
+fn queue_prefetch(app: &mut App, effects: &mut Vec<Effect>) {
+    let prefetch = app.prefetch_jobs();
+    if prefetch != app.prefetch_queued {
+        app.prefetch_queued = prefetch.clone();
+        effects.push(Effect::Send(ApiCommand::Prefetch(prefetch)));
+    }
+}
+
+/// Data to reload after a mutation
+fn refresh_after(message: &ApiMessage) -> Vec<ApiCommand> {
+    let entity_type = match message.untagged() {
+        ApiMessage::Created(entity_type, _) | ApiMessage::Deleted(entity_type, _) => *entity_type,
+        ApiMessage::Updated(entity_type) => *entity_type,
+        ApiMessage::BatchUpdated { .. } => EntityType::Project,
+        _ => return Vec::new(),
+    };
+    match entity_type {
+        EntityType::Project => vec![ApiCommand::RefreshProjects],
+        // Projects too, for project dropdown updates
+        EntityType::Client => vec![ApiCommand::RefreshClients, ApiCommand::RefreshProjects],
+        EntityType::User => vec![ApiCommand::RefreshUsers, ApiCommand::RefreshProjects],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use sweem_core::fixtures;
+    use uuid::Uuid;
+
+    fn sent(effects: &[Effect]) -> Vec<&'static str> {
+        effects
+            .iter()
+            .filter_map(|e| match e {
+                Effect::Send(cmd) => Some(cmd.name()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_update_emits_effects() {
+        let mut app = App::new();
+
+        let effects = update(&mut app, Msg::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE)));
+        assert_eq!(sent(&effects), ["refresh_all"]);
+        assert!(app.is_loading);
+        assert!(app.dirty.take().contains(Dirty::INPUT));
+
+        let effects = update(&mut app, Msg::Api(ApiMessage::Created(EntityType::Client, Uuid::new_v4())));
+        assert_eq!(sent(&effects), ["refresh_clients", "refresh_projects"]);
+
+        // The frame rate set in the settings dialog goes to the render loop
+        app.ui_state.read_only = true;
+        for code in [KeyCode::Char(','), KeyCode::Char('j'), KeyCode::Char('j'), KeyCode::Char('j')] {
+            update(&mut app, Msg::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+        }
+        let effects = update(&mut app, Msg::Key(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE)));
+        assert!(matches!(effects[..], [Effect::SetFps(60)]));
+        update(&mut app, Msg::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)));
+
+        let effects = update(&mut app, Msg::Focus(false));
+        assert!(!app.focused);
+        assert!(matches!(effects[..], [Effect::SetFps(UNFOCUSED_FPS)]));
+    }
+
+    #[test]
+    fn test_focus_after_initial_load() {
+        use crate::app::Tab;
+        use crate::models::{ClientDto, ProjectDto};
+
+        let date = |d| chrono::NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let client = Uuid::from_u128(1);
+        let project = |n: u128, start| ProjectDto {
+            id: Uuid::from_u128(100 + n),
+            client_id: if n == 1 { Uuid::nil() } else { client },
+            ..fixtures::project(date(start), date(start + 5))
+        };
+        let mut app = App::new();
+        app.pending_focus = Some((EntityType::Client, client));
+        let client_dto = |id| ClientDto { name: None, ..fixtures::client(id, "") };
+        let clients = vec![client_dto(Uuid::from_u128(2)), client_dto(client)];
+        update(&mut app, Msg::Api(ApiMessage::ClientsLoaded(clients)));
+        assert_eq!(app.active_tab, Tab::Timeline, "focus waits for the projects");
+        update(&mut app, Msg::Api(ApiMessage::ProjectsLoaded(vec![project(1, 1), project(2, 12), project(3, 8)])));
+        assert_eq!((app.active_tab, app.list_selected), (Tab::Clients, 1));
+        assert_eq!(app.timeline_state.selected_project, Some(2), "the client's earliest project");
+        assert!(app.pending_focus.is_none());
+    }
+
+    #[test]
+    fn test_reconnect_refreshes_when_back() {
+        let mut app = App::new();
+        // The startup refresh failed
+        app.is_loading = false;
+        let effects = update(&mut app, Msg::Api(ApiMessage::ConnectionStatus(false)));
+        assert!(sent(&effects).is_empty());
+        assert!(app.reconnect_status().unwrap().contains("attempt 1"));
+
+        // Not due yet on the next frame
+        let effects = update(&mut app, Msg::Tick { width: 80, height: 24, animate: true });
+        assert!(sent(&effects).is_empty());
+
+        let effects = update(&mut app, Msg::Api(ApiMessage::ConnectionStatus(true)));
+        assert_eq!(sent(&effects), ["refresh_all"]);
+        assert_eq!(app.reconnect_status(), None);
+        let effects = update(&mut app, Msg::Api(ApiMessage::ConnectionStatus(true)));
+        assert!(sent(&effects).is_empty(), "only a recovery reloads");
+    }
+
+    #[test]
+    fn test_idle_ticks_skip_redraw() {
+        let mut app = App::new();
+        app.particle_system.set_mode(crate::particles::ParticleMode::None);
+        app.last_activity = Instant::now() - std::time::Duration::from_secs(60);
+        let tick = Msg::Tick { width: 80, height: 24, animate: true };
+
+        update(&mut app, tick.clone());
+        app.dirty.take();
+        update(&mut app, tick.clone());
+        assert!(app.dirty.is_clean());
+
+        // A key press wakes the timeline animations up again
+        update(&mut app, Msg::Key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE)));
+        app.dirty.take();
+        update(&mut app, tick);
+        assert!(app.dirty.contains(Dirty::ANIMATION));
+    }
+}
@@ -0,0 +1,103 @@
+//! Watch mode: change-driven refreshes.
+//!
+//! With `--watch`, the API worker polls `GET /changes` every few seconds.
+//! That endpoint only returns a data version, so polling is cheap; the full
+//! reload runs only when the version differs from the last one seen. The
+//! first poll records the baseline, and servers without the endpoint turn
+//! watch mode off with a warning.
+//!
+//! When the server sends `X-RateLimit-*` headers, polling only spends the
+//! budget above a reserve kept for interactive requests: once the remaining
+//! budget runs low the interval stretches so the rest lasts until the reset.
+
+use std::time::Duration;
+
+use crate::api::{ApiError, RateLimit};
+
+/// Share of the rate limit (1/n) kept for interactive requests
+const RESERVE_SHARE: u32 = 5;
+
+/// Requests always kept for interactive use
+const RESERVE_MIN: u32 = 10;
+
+/// Slowdown when the reserve is reached and the server gives no reset time
+const NO_RESET_FACTOR: u32 = 4;
+
+/// What the worker should do after a poll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollOutcome {
+    /// Nothing changed (or this was the baseline)
+    Unchanged,
+    /// The server data changed: reload everything
+    Changed,
+    /// The server has no `/changes` endpoint: stop polling
+    Unsupported,
+}
+
+/// Last data version seen by the worker
+#[derive(Debug, Default)]
+pub struct ChangeWatch {
+    version: Option<i64>,
+}
+
+impl ChangeWatch {
+    /// Interpret the result of `GET /changes`
+    pub fn observe(&mut self, result: Result<i64, &ApiError>) -> PollOutcome {
+        match result {
+            Ok(version) => match self.version.replace(version) {
+                Some(previous) if previous != version => PollOutcome::Changed,
+                _ => PollOutcome::Unchanged,
+            },
+            Err(e) if matches!(e.status(), Some(404 | 405)) => PollOutcome::Unsupported,
+            // A dropped connection is noticed by the regular connection checks
+            Err(_) => PollOutcome::Unchanged,
+        }
+    }
+}
+
+/// Poll interval for a rate-limit budget: `base` while the budget above the
+/// reserve covers polling at that pace until the reset, slower otherwise
+pub fn poll_interval(base: Duration, rate_limit: Option<RateLimit>) -> Duration {
+    let Some(rate) = rate_limit else {
+        return base;
+    };
+    let reserve = rate.limit.map_or(RESERVE_MIN, |limit| (limit / RESERVE_SHARE).max(RESERVE_MIN));
+    let budget = rate.remaining.saturating_sub(reserve);
+    match rate.reset {
+        Some(reset) => base.max(reset / budget.max(1)),
+        None if budget == 0 => base * NO_RESET_FACTOR,
+        None => base,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_only_on_new_version() {
+        let mut watch = ChangeWatch::default();
+        assert_eq!(watch.observe(Ok(7)), PollOutcome::Unchanged);
+        assert_eq!(watch.observe(Ok(7)), PollOutcome::Unchanged);
+        assert_eq!(watch.observe(Ok(9)), PollOutcome::Changed);
+
+        let timeout = ApiError::Timeout { endpoint: "GET /changes".into() };
+        assert_eq!(watch.observe(Err(&timeout)), PollOutcome::Unchanged);
+        assert_eq!(watch.observe(Ok(9)), PollOutcome::Unchanged);
+
+        let missing = ApiError::Status { endpoint: "GET /changes".into(), status: 404, message: "Not Found".into() };
+        assert_eq!(watch.observe(Err(&missing)), PollOutcome::Unsupported);
+    }
+
+    #[test]
+    fn test_poll_interval_stretches_when_budget_runs_low() {
+        let base = Duration::from_secs(5);
+        let rate = |remaining, reset: Option<u64>| Some(RateLimit { remaining, limit: Some(100), reset: reset.map(Duration::from_secs) });
+        assert_eq!(poll_interval(base, None), base);
+        assert_eq!(poll_interval(base, rate(90, Some(60))), base, "plenty left");
+        // 20 are reserved; 10 polls left for 100 seconds
+        assert_eq!(poll_interval(base, rate(30, Some(100))), Duration::from_secs(10));
+        assert_eq!(poll_interval(base, rate(15, Some(100))), Duration::from_secs(100), "wait for the reset");
+        assert_eq!(poll_interval(base, rate(15, None)), Duration::from_secs(20));
+    }
+}
@@ -0,0 +1,123 @@
+//! Chat webhook for alerts.
+//!
+//! With `[webhook] url` set to a Slack or Mattermost incoming webhook, the
+//! `[[alerts]]` rules that fire on a refresh and projects newly breaking an
+//! `[[sla]]` rule are also posted to the channel, one message per refresh.
+//! The app finds them; the API worker posts them next to its refresh loop,
+//! so a slow chat server never holds up the UI or the next load.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::api::DEFAULT_TIMEOUT;
+
+fn default_true() -> bool {
+    true
+}
+
+/// `[webhook]` section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    /// Incoming webhook URL (unset: nothing is posted)
+    pub url: Option<String>,
+    /// Channel to post to instead of the webhook's own
+    pub channel: Option<String>,
+    /// Name to post as instead of the webhook's own
+    pub username: Option<String>,
+    /// Post alert rule hits
+    #[serde(default = "default_true")]
+    pub alerts: bool,
+    /// Post projects newly breaking an SLA rule
+    #[serde(default = "default_true")]
+    pub sla: bool,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self { url: None, channel: None, username: None, alerts: true, sla: true }
+    }
+}
+
+impl WebhookConfig {
+    /// The URL has to be an http(s) URL
+    pub fn validate(&self) -> Result<()> {
+        if let Some(url) = &self.url {
+            let parsed = reqwest::Url::parse(url).with_context(|| format!("[webhook] url \"{}\" is not a URL", url))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                bail!("[webhook] url \"{}\" has to be an http or https URL", url);
+            }
+        }
+        Ok(())
+    }
+
+    /// Message body for the lines of one refresh; Slack and Mattermost both take `text`
+    pub fn payload(&self, lines: &[String]) -> Value {
+        let mut payload = json!({ "text": lines.join("\n") });
+        if let Some(channel) = &self.channel {
+            payload["channel"] = json!(channel);
+        }
+        if let Some(username) = &self.username {
+            payload["username"] = json!(username);
+        }
+        payload
+    }
+}
+
+/// Poster of webhook messages, owned by the API worker
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    client: reqwest::Client,
+    url: String,
+    config: WebhookConfig,
+}
+
+impl Webhook {
+    /// `None` when no URL is configured
+    pub fn new(config: &WebhookConfig) -> Result<Option<Self>> {
+        let Some(url) = &config.url else {
+            return Ok(None);
+        };
+        let client = reqwest::Client::builder().timeout(DEFAULT_TIMEOUT).build().context("Failed to create the webhook client")?;
+        Ok(Some(Self { client, url: url.clone(), config: config.clone() }))
+    }
+
+    /// Post one message
+    pub async fn post(&self, lines: &[String]) -> Result<()> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&self.config.payload(lines))
+            .send()
+            .await
+            .context("Webhook request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("Webhook answered {}: {}", status, body.trim());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_and_validation() {
+        let config: WebhookConfig = toml::from_str("url = \"https://chat.example.com/hooks/abc\"\nusername = \"SWEeM\"\n").unwrap();
+        assert!(config.alerts && config.sla);
+        assert!(config.validate().is_ok());
+        let lines = ["Acme overdue: Website overdue".to_string(), "SLA At most 5 days late: Portal".to_string()];
+        assert_eq!(
+            config.payload(&lines),
+            json!({ "text": "Acme overdue: Website overdue\nSLA At most 5 days late: Portal", "username": "SWEeM" })
+        );
+
+        assert!(WebhookConfig { url: Some("hooks/abc".into()), ..Default::default() }.validate().is_err());
+        assert!(WebhookConfig { url: Some("ftp://chat.example.com".into()), ..Default::default() }.validate().is_err());
+        assert!(Webhook::new(&WebhookConfig::default()).unwrap().is_none());
+    }
+}
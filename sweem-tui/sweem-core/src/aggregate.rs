@@ -0,0 +1,337 @@
+//! Several backends shown as one.
+//!
+//! Organisations running one SWEeM instance per region want to see them
+//! side by side. [`Aggregate`] loads from every backend concurrently, tags
+//! each entity with the name of the backend it came from (the [`SOURCE_FIELD`]
+//! entry of its `extra` map, so it shows up in filters and exports) and
+//! returns the merged lists. A backend that cannot be reached only leaves its
+//! entities out; [`DataSource::backends`] tells which ones answered.
+//!
+//! Changes go to the backend the entity was loaded from. New projects go to
+//! their client's backend, new clients and users to the first backend, and
+//! the audit log and raw GETs are read from the first backend only.
+//!
+//! Watch mode polls `/changes` on every backend at once and compares each
+//! backend's version with the one it reported last; the version of the
+//! aggregate is a counter that moves whenever any of them changed, or a
+//! backend answered for the first time since the first poll.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{Map, Value};
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+use crate::api::{Download, RateLimit, RawResponse};
+use crate::error::ApiError;
+use crate::models::{
+    AuditEventDto, ChangesDto, ClientDto, CreateClientDto, CreateProjectDto, CreateUserDto, DocumentDto,
+    PaginatedResult, ProjectDto, UpdateClientDto, UpdateProjectDto, UpdateUserDto, UserDto,
+};
+use crate::source::{BackendStatus, DataSource, SourceFuture};
+
+/// Key of the backend name in the `extra` map of aggregated entities
+pub const SOURCE_FIELD: &str = "source";
+
+/// Entities that can be tagged with their backend
+trait Tagged {
+    fn id(&self) -> Uuid;
+    fn extra_mut(&mut self) -> &mut Map<String, Value>;
+}
+
+impl Tagged for ProjectDto {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn extra_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.extra
+    }
+}
+
+impl Tagged for ClientDto {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn extra_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.extra
+    }
+}
+
+impl Tagged for UserDto {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn extra_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.extra
+    }
+}
+
+/// Named backends read together
+pub struct Aggregate {
+    backends: Vec<(String, Arc<dyn DataSource>)>,
+    /// Backend (index) each loaded or created entity belongs to
+    owners: Mutex<HashMap<Uuid, usize>>,
+    /// Whether each backend answered its last request
+    connected: Mutex<Vec<bool>>,
+    versions: Mutex<Versions>,
+}
+
+/// What `/changes` said so far
+#[derive(Debug, Default)]
+struct Versions {
+    /// Last version of each backend (`None` = never answered)
+    seen: Vec<Option<i64>>,
+    polled: bool,
+    /// Version reported for the aggregate
+    counter: i64,
+}
+
+impl Aggregate {
+    /// Combine backends in order of precedence; the first one takes new clients and users
+    pub fn new(backends: Vec<(String, Arc<dyn DataSource>)>) -> Self {
+        let connected = Mutex::new(vec![false; backends.len()]);
+        let versions = Mutex::new(Versions { seen: vec![None; backends.len()], ..Default::default() });
+        Self { backends, owners: Mutex::default(), connected, versions }
+    }
+
+    fn set_connected(&self, index: usize, connected: bool) {
+        if let Some(status) = self.connected.lock().unwrap_or_else(|e| e.into_inner()).get_mut(index) {
+            *status = connected;
+        }
+    }
+
+    /// Backend an entity belongs to; the first one for entities never loaded
+    fn owner(&self, id: Uuid) -> usize {
+        self.owners.lock().unwrap_or_else(|e| e.into_inner()).get(&id).copied().unwrap_or(0)
+    }
+
+    fn source_of(&self, id: Uuid) -> &dyn DataSource {
+        self.backends[self.owner(id)].1.as_ref()
+    }
+
+    /// Remember where an entity came from and put the backend's name on it
+    fn tag<T: Tagged>(&self, index: usize, mut item: T) -> T {
+        self.owners.lock().unwrap_or_else(|e| e.into_inner()).insert(item.id(), index);
+        item.extra_mut().insert(SOURCE_FIELD.to_string(), self.backends[index].0.clone().into());
+        item
+    }
+
+    /// Load a whole list from every backend at once and merge the answers;
+    /// fails only when no backend answered
+    fn read_all<T: Tagged + Send + 'static>(
+        &self,
+        read: fn(&dyn DataSource) -> SourceFuture<'_, Vec<T>>,
+    ) -> SourceFuture<'_, Vec<T>> {
+        Box::pin(async move {
+            let mut tasks = JoinSet::new();
+            for (index, (_, source)) in self.backends.iter().enumerate() {
+                let source = source.clone();
+                tasks.spawn(async move { (index, read(source.as_ref()).await) });
+            }
+            let mut results: Vec<_> = tasks.join_all().await;
+            results.sort_by_key(|(index, _)| *index);
+
+            let mut merged = Vec::new();
+            let mut first_error = None;
+            let mut answered = false;
+            for (index, result) in results {
+                self.set_connected(index, result.is_ok());
+                match result {
+                    Ok(items) => {
+                        answered = true;
+                        merged.extend(items.into_iter().map(|item| self.tag(index, item)));
+                    }
+                    Err(e) => {
+                        tracing::warn!(backend = %self.backends[index].0, error = %e, "Backend left out of the merged list");
+                        first_error.get_or_insert(e);
+                    }
+                }
+            }
+            match first_error {
+                Some(e) if !answered => Err(e),
+                _ => Ok(merged),
+            }
+        })
+    }
+
+    /// Run a request on the backend at `index`, tagging what it returns
+    fn tagged<'a, T: Tagged + Send + 'a>(&'a self, index: usize, request: SourceFuture<'a, T>) -> SourceFuture<'a, T> {
+        Box::pin(async move { Ok(self.tag(index, request.await?)) })
+    }
+
+    /// Run a create on the backend at `index`, remembering the new id there
+    fn created<'a>(&'a self, index: usize, request: SourceFuture<'a, Uuid>) -> SourceFuture<'a, Uuid> {
+        Box::pin(async move {
+            let id = request.await?;
+            self.owners.lock().unwrap_or_else(|e| e.into_inner()).insert(id, index);
+            Ok(id)
+        })
+    }
+}
+
+impl DataSource for Aggregate {
+    /// Checks every backend; connected while at least one answers
+    fn health_check(&self) -> SourceFuture<'_, bool> {
+        Box::pin(async move {
+            let mut tasks = JoinSet::new();
+            for (index, (_, source)) in self.backends.iter().enumerate() {
+                let source = source.clone();
+                tasks.spawn(async move { (index, source.health_check().await.unwrap_or(false)) });
+            }
+            let mut any = false;
+            for (index, connected) in tasks.join_all().await {
+                self.set_connected(index, connected);
+                any |= connected;
+            }
+            Ok(any)
+        })
+    }
+
+    fn fetch_all_projects(&self) -> SourceFuture<'_, Vec<ProjectDto>> {
+        self.read_all(|source| source.fetch_all_projects())
+    }
+
+    fn fetch_all_clients(&self) -> SourceFuture<'_, Vec<ClientDto>> {
+        self.read_all(|source| source.fetch_all_clients())
+    }
+
+    fn fetch_all_users(&self) -> SourceFuture<'_, Vec<UserDto>> {
+        self.read_all(|source| source.fetch_all_users())
+    }
+
+    fn fetch_project(&self, id: Uuid) -> SourceFuture<'_, ProjectDto> {
+        self.tagged(self.owner(id), self.source_of(id).fetch_project(id))
+    }
+
+    fn fetch_client(&self, id: Uuid) -> SourceFuture<'_, ClientDto> {
+        self.tagged(self.owner(id), self.source_of(id).fetch_client(id))
+    }
+
+    fn fetch_user(&self, id: Uuid) -> SourceFuture<'_, UserDto> {
+        self.tagged(self.owner(id), self.source_of(id).fetch_user(id))
+    }
+
+    fn create_project<'a>(&'a self, project: &'a CreateProjectDto) -> SourceFuture<'a, Uuid> {
+        let index = self.owner(project.client_id);
+        self.created(index, self.backends[index].1.create_project(project))
+    }
+
+    fn update_project<'a>(&'a self, id: Uuid, project: &'a UpdateProjectDto) -> SourceFuture<'a, ProjectDto> {
+        self.tagged(self.owner(id), self.source_of(id).update_project(id, project))
+    }
+
+    fn delete_project(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        self.source_of(id).delete_project(id)
+    }
+
+    fn create_client<'a>(&'a self, client: &'a CreateClientDto) -> SourceFuture<'a, Uuid> {
+        self.created(0, self.backends[0].1.create_client(client))
+    }
+
+    fn update_client<'a>(&'a self, id: Uuid, client: &'a UpdateClientDto) -> SourceFuture<'a, ClientDto> {
+        self.tagged(self.owner(id), self.source_of(id).update_client(id, client))
+    }
+
+    fn delete_client(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        self.source_of(id).delete_client(id)
+    }
+
+    fn create_user<'a>(&'a self, user: &'a CreateUserDto) -> SourceFuture<'a, Uuid> {
+        self.created(0, self.backends[0].1.create_user(user))
+    }
+
+    fn update_user<'a>(&'a self, id: Uuid, user: &'a UpdateUserDto) -> SourceFuture<'a, UserDto> {
+        self.tagged(self.owner(id), self.source_of(id).update_user(id, user))
+    }
+
+    fn delete_user(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        self.source_of(id).delete_user(id)
+    }
+
+    /// Polls every backend at once; the version moves when any backend's own
+    /// version changed. Backends that do not answer are skipped, and it fails
+    /// only when none answered
+    fn fetch_changes(&self) -> SourceFuture<'_, ChangesDto> {
+        Box::pin(async move {
+            let mut tasks = JoinSet::new();
+            for (index, (_, source)) in self.backends.iter().enumerate() {
+                let source = source.clone();
+                tasks.spawn(async move { (index, source.fetch_changes().await) });
+            }
+            let mut results: Vec<_> = tasks.join_all().await;
+            results.sort_by_key(|(index, _)| *index);
+
+            let mut versions = self.versions.lock().unwrap_or_else(|e| e.into_inner());
+            let mut last_modified = None;
+            let mut first_error = None;
+            let mut changed = false;
+            for (index, result) in results {
+                match result {
+                    Ok(changes) => {
+                        let previous = versions.seen[index].replace(changes.version);
+                        changed |= match previous {
+                            Some(previous) => previous != changes.version,
+                            None => versions.polled,
+                        };
+                        last_modified = last_modified.max(Some(changes.last_modified));
+                    }
+                    Err(e) => {
+                        tracing::debug!(backend = %self.backends[index].0, error = %e, "Backend skipped in the change poll");
+                        first_error.get_or_insert(e);
+                    }
+                }
+            }
+            let Some(last_modified) = last_modified else {
+                return Err(first_error.unwrap_or_else(|| ApiError::Status {
+                    endpoint: "GET /changes".to_string(),
+                    status: 404,
+                    message: "No backends".to_string(),
+                }));
+            };
+            versions.polled = true;
+            if changed {
+                versions.counter += 1;
+            }
+            Ok(ChangesDto { version: versions.counter, last_modified })
+        })
+    }
+
+    fn fetch_project_documents(&self, project_id: Uuid) -> SourceFuture<'_, Vec<DocumentDto>> {
+        self.source_of(project_id).fetch_project_documents(project_id)
+    }
+
+    fn download_document(&self, project_id: Uuid, document_id: Uuid) -> SourceFuture<'_, Download> {
+        self.source_of(project_id).download_document(project_id, document_id)
+    }
+
+    fn fetch_audit<'a>(
+        &'a self,
+        page: i32,
+        page_size: i32,
+        search: Option<&'a str>,
+    ) -> SourceFuture<'a, PaginatedResult<AuditEventDto>> {
+        self.backends[0].1.fetch_audit(page, page_size, search)
+    }
+
+    fn get_raw<'a>(&'a self, path: &'a str, query: &'a [(String, String)]) -> SourceFuture<'a, RawResponse> {
+        self.backends[0].1.get_raw(path, query)
+    }
+
+    /// The tightest budget of any backend
+    fn rate_limit(&self) -> Option<RateLimit> {
+        self.backends.iter().filter_map(|(_, source)| source.rate_limit()).min_by_key(|r| r.remaining)
+    }
+
+    fn backends(&self) -> Vec<BackendStatus> {
+        let connected = self.connected.lock().unwrap_or_else(|e| e.into_inner());
+        self.backends
+            .iter()
+            .zip(connected.iter())
+            .map(|((name, _), connected)| BackendStatus { name: name.clone(), connected: *connected })
+            .collect()
+    }
+}
@@ -0,0 +1,136 @@
+//! Response bodies: compression and streamed JSON parsing.
+//!
+//! Clients ask for gzip or deflate (`Accept-Encoding`), which shrinks the
+//! JSON of large project lists several times over slow links. Bodies larger
+//! than [`STREAM_THRESHOLD`] (or of unknown size) are not buffered: chunks
+//! are handed to a blocking task as they arrive, decompressed there and
+//! parsed straight into the DTOs, so parsing overlaps the transfer and the
+//! raw body never sits in memory as a whole.
+
+use std::io::{self, BufReader, Read};
+
+use bytes::{Buf, Bytes};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING};
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+use tokio::sync::mpsc;
+
+use crate::error::{ApiError, ApiResult};
+
+/// Encodings offered to the server
+pub const SUPPORTED_ENCODINGS: &str = "gzip, deflate";
+
+/// Bodies up to this size (as sent) are read in one piece and parsed in place
+pub const STREAM_THRESHOLD: u64 = 64 * 1024;
+
+/// Chunks received but not yet parsed
+const CHUNK_BUFFER: usize = 16;
+
+/// Default headers of the HTTP clients
+pub(crate) fn default_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT_ENCODING, HeaderValue::from_static(SUPPORTED_ENCODINGS));
+    headers
+}
+
+/// `Content-Encoding` of a response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Identity,
+    Gzip,
+    /// zlib-wrapped, as HTTP defines it
+    Deflate,
+}
+
+impl Encoding {
+    fn of(response: &Response) -> Self {
+        let value = response.headers().get(CONTENT_ENCODING).and_then(|v| v.to_str().ok()).unwrap_or_default();
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Encoding::Gzip,
+            "deflate" => Encoding::Deflate,
+            _ => Encoding::Identity,
+        }
+    }
+
+    fn reader<'a>(self, body: impl Read + Send + 'a) -> Box<dyn Read + Send + 'a> {
+        match self {
+            Encoding::Identity => Box::new(body),
+            Encoding::Gzip => Box::new(GzDecoder::new(body)),
+            Encoding::Deflate => Box::new(ZlibDecoder::new(body)),
+        }
+    }
+}
+
+/// The whole body, decompressed
+pub(crate) async fn bytes(endpoint: &str, response: Response) -> ApiResult<Vec<u8>> {
+    let encoding = Encoding::of(&response);
+    let body = response.bytes().await.map_err(|e| ApiError::from_reqwest(endpoint, e))?;
+    if encoding == Encoding::Identity {
+        return Ok(body.to_vec());
+    }
+    let mut decoded = Vec::new();
+    encoding
+        .reader(&body[..])
+        .read_to_end(&mut decoded)
+        .map_err(|e| ApiError::Decode { endpoint: endpoint.to_string(), message: e.to_string() })?;
+    Ok(decoded)
+}
+
+/// The body as text, decompressed
+pub(crate) async fn text(endpoint: &str, response: Response) -> ApiResult<String> {
+    Ok(String::from_utf8_lossy(&bytes(endpoint, response).await?).into_owned())
+}
+
+/// Parse a JSON body, streaming it unless it is small
+pub(crate) async fn decode<T: DeserializeOwned + Send + 'static>(endpoint: &str, response: Response) -> ApiResult<T> {
+    let decode_error = |message: String| ApiError::Decode { endpoint: endpoint.to_string(), message };
+    if response.content_length().is_some_and(|length| length <= STREAM_THRESHOLD) {
+        let body = bytes(endpoint, response).await?;
+        return serde_json::from_slice(&body).map_err(|e| decode_error(e.to_string()));
+    }
+
+    let encoding = Encoding::of(&response);
+    let (tx, rx) = mpsc::channel(CHUNK_BUFFER);
+    let parser = tokio::task::spawn_blocking(move || {
+        let body = BufReader::new(encoding.reader(ChunkReader { rx, current: Bytes::new() }));
+        serde_json::from_reader::<_, T>(body)
+    });
+    let received = pump(endpoint, response, tx).await;
+    let parsed = parser.await.map_err(|e| decode_error(e.to_string()))?;
+    // A broken transfer also breaks the parse; report the cause
+    received?;
+    parsed.map_err(|e| decode_error(e.to_string()))
+}
+
+/// Forward body chunks to the parser until the body ends or the parser stops
+async fn pump(endpoint: &str, mut response: Response, tx: mpsc::Sender<Bytes>) -> ApiResult<()> {
+    while let Some(chunk) = response.chunk().await.map_err(|e| ApiError::from_reqwest(endpoint, e))? {
+        if tx.send(chunk).await.is_err() {
+            // The parser failed early; its error says why
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Blocking reader over the chunks of a body
+struct ChunkReader {
+    rx: mpsc::Receiver<Bytes>,
+    current: Bytes,
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.current.is_empty() {
+            match self.rx.blocking_recv() {
+                Some(chunk) => self.current = chunk,
+                None => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.current.len());
+        buf[..n].copy_from_slice(&self.current[..n]);
+        self.current.advance(n);
+        Ok(n)
+    }
+}
@@ -0,0 +1,101 @@
+//! Structured API errors.
+//!
+//! Every failed request says which endpoint it hit and what kind of failure
+//! it was, so callers can tell a backend that is down (worth retrying) from
+//! a request the server rejected (worth showing to the user).
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Result of an API call
+pub type ApiResult<T> = Result<T, ApiError>;
+
+/// A failed API request
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
+pub enum ApiError {
+    /// The server could not be reached
+    #[error("Cannot reach {endpoint}: {message}")]
+    Connection { endpoint: String, message: String },
+    /// The server did not answer in time
+    #[error("{endpoint} timed out")]
+    Timeout { endpoint: String },
+    /// The server answered with a non-success status
+    #[error("API error {status} on {endpoint}: {message}")]
+    Status { endpoint: String, status: u16, message: String },
+    /// The response body was not what the client expected
+    #[error("Failed to parse {endpoint} response: {message}")]
+    Decode { endpoint: String, message: String },
+}
+
+/// Broad category of an [`ApiError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    Connection,
+    Timeout,
+    /// 401/403: credentials are missing or not accepted
+    Unauthorized,
+    /// Any other 4xx: the request itself was rejected (validation, not found)
+    Rejected,
+    /// 5xx
+    Server,
+    Decode,
+}
+
+impl ApiError {
+    /// Classify a transport error from reqwest
+    pub fn from_reqwest(endpoint: &str, error: reqwest::Error) -> Self {
+        let endpoint = endpoint.to_string();
+        if error.is_timeout() {
+            ApiError::Timeout { endpoint }
+        } else if error.is_decode() {
+            ApiError::Decode { endpoint, message: error.to_string() }
+        } else {
+            ApiError::Connection { endpoint, message: error.to_string() }
+        }
+    }
+
+    pub fn kind(&self) -> ApiErrorKind {
+        match self {
+            ApiError::Connection { .. } => ApiErrorKind::Connection,
+            ApiError::Timeout { .. } => ApiErrorKind::Timeout,
+            ApiError::Status { status: 401 | 403, .. } => ApiErrorKind::Unauthorized,
+            ApiError::Status { status: 400..=499, .. } => ApiErrorKind::Rejected,
+            ApiError::Status { .. } => ApiErrorKind::Server,
+            ApiError::Decode { .. } => ApiErrorKind::Decode,
+        }
+    }
+
+    /// Endpoint the failed request was sent to, e.g. `GET /projects`
+    pub fn endpoint(&self) -> &str {
+        match self {
+            ApiError::Connection { endpoint, .. }
+            | ApiError::Timeout { endpoint }
+            | ApiError::Status { endpoint, .. }
+            | ApiError::Decode { endpoint, .. } => endpoint,
+        }
+    }
+
+    /// HTTP status, if the server answered
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ApiError::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Whether sending the same request again may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind(),
+            ApiErrorKind::Connection | ApiErrorKind::Timeout | ApiErrorKind::Server
+        ) || self.status() == Some(429)
+    }
+
+    /// Human-readable reason without the endpoint (for forms and popups)
+    pub fn message(&self) -> String {
+        match self {
+            ApiError::Status { message, .. } => message.clone(),
+            other => other.to_string(),
+        }
+    }
+}
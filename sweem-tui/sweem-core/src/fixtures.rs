@@ -0,0 +1,48 @@
+//! DTOs for tests.
+//!
+//! Every field a test does not care about gets a plain value here, and the
+//! test sets the rest with struct update syntax:
+//!
+//! ```ignore
+//! let late = ProjectDto { actual_end_date: Some(date(12)), ..fixtures::project(date(1), date(10)) };
+//! ```
+//!
+//! Used by this crate's tests and, through the `fixtures` feature, by the
+//! TUI's, so a field added to a model only needs a value here.
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::models::{ClientDto, ProjectDto, Role, UserDto};
+
+/// An open project from `start` to `planned_end`, with a fresh id and no
+/// name, client or manager
+pub fn project(start: NaiveDate, planned_end: NaiveDate) -> ProjectDto {
+    ProjectDto {
+        id: Uuid::new_v4(),
+        client_id: Uuid::nil(),
+        name: None,
+        start_date: start,
+        planned_end_date: planned_end,
+        actual_end_date: None,
+        manager_id: Uuid::nil(),
+        extra: Default::default(),
+    }
+}
+
+/// A client without address or project counts
+pub fn client(id: Uuid, name: &str) -> ClientDto {
+    ClientDto {
+        id,
+        name: Some(name.to_string()),
+        address: None,
+        projects_total: 0,
+        projects_completed: 0,
+        extra: Default::default(),
+    }
+}
+
+/// A manager without login
+pub fn user(id: Uuid, name: &str) -> UserDto {
+    UserDto { id, name: Some(name.to_string()), login: None, role: Role::Manager, extra: Default::default() }
+}
@@ -0,0 +1,305 @@
+//! GraphQL transport.
+//!
+//! Newer SWEeM backends serve a GraphQL endpoint. A refresh is then a single
+//! query returning projects, clients and users together, instead of one
+//! request per page and entity type, which is what makes refreshes slow over
+//! high-latency links. Fields are named and typed as in the REST JSON, and
+//! mutations take the REST request bodies as their `input` variable.
+//!
+//! GraphQL reports errors next to a 200 status; they become
+//! [`ApiError::Status`] with 400, and a `null` result becomes 404, so callers
+//! handle both transports alike.
+//!
+//! Change polling, documents, the audit log and the API console have no
+//! GraphQL counterpart; they go to the REST client given with
+//! [`GraphQlClient::with_rest`], or are reported as unsupported without one.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::api::{check_status, ApiClient, ConnectionOptions, Download, RateLimit, RawResponse, DEFAULT_TIMEOUT};
+use crate::body;
+use crate::error::{ApiError, ApiResult};
+use crate::trace;
+use crate::models::{
+    AuditEventDto, ChangesDto, ClientDto, CreateClientDto, CreateProjectDto, CreateUserDto, DocumentDto,
+    PaginatedResult, ProjectDto, UpdateClientDto, UpdateProjectDto, UpdateUserDto, UserDto,
+};
+use crate::source::{unsupported, DataSource, Refresh, SourceFuture};
+
+/// Selection set of a project
+const PROJECT_FIELDS: &str = "id clientId name startDate plannedEndDate actualEndDate managerId";
+/// Selection set of a client
+const CLIENT_FIELDS: &str = "id name address projectsTotal projectsCompleted";
+/// Selection set of a user
+const USER_FIELDS: &str = "id name login role";
+
+/// Response envelope
+#[derive(Debug, Deserialize)]
+struct Envelope {
+    #[serde(default)]
+    data: Option<Map<String, Value>>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+/// GraphQL client for the SWEeM backend
+#[derive(Debug, Clone)]
+pub struct GraphQlClient {
+    client: Client,
+    url: String,
+    /// Serves what GraphQL does not
+    rest: Option<ApiClient>,
+}
+
+impl GraphQlClient {
+    /// Create a client for the GraphQL endpoint at `url`
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        Self::with_options(url, &ConnectionOptions::default())
+    }
+
+    /// Create a client with custom connection settings
+    pub fn with_options(url: impl Into<String>, options: &ConnectionOptions) -> Result<Self> {
+        let client = options.builder(DEFAULT_TIMEOUT).build().context("Failed to create HTTP client")?;
+        Ok(Self { client, url: url.into(), rest: None })
+    }
+
+    /// Send change polling, documents, audit and raw requests to `rest`
+    pub fn with_rest(mut self, rest: ApiClient) -> Self {
+        self.rest = Some(rest);
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Run a query or mutation and return its `data`
+    async fn execute(&self, operation: &str, document: &str, variables: Value) -> ApiResult<Map<String, Value>> {
+        let endpoint = format!("GraphQL {}", operation);
+        let request = self.client.post(&self.url).json(&json!({ "query": document, "variables": variables }));
+        let response = trace::send(&self.client, &endpoint, request)
+            .await
+            .map_err(|e| ApiError::from_reqwest(&endpoint, e))?;
+        let response = check_status(&endpoint, response).await?;
+        let envelope: Envelope = body::decode(&endpoint, response).await?;
+        if !envelope.errors.is_empty() {
+            let message = envelope.errors.into_iter().map(|e| e.message).collect::<Vec<_>>().join("; ");
+            return Err(ApiError::Status { endpoint, status: 400, message });
+        }
+        envelope.data.ok_or_else(|| ApiError::Decode { endpoint, message: "response has no data".to_string() })
+    }
+
+    /// Run an operation and decode the field of `data` it is named after
+    async fn field<T: DeserializeOwned>(&self, operation: &str, document: &str, variables: Value) -> ApiResult<T> {
+        let mut data = self.execute(operation, document, variables).await?;
+        take(&mut data, operation)
+    }
+
+    #[instrument(level = "debug", skip(self), err)]
+    async fn fetch_everything(&self) -> ApiResult<Map<String, Value>> {
+        let document = format!(
+            "query Refresh {{ projects {{ {} }} clients {{ {} }} users {{ {} }} }}",
+            PROJECT_FIELDS, CLIENT_FIELDS, USER_FIELDS
+        );
+        self.execute("refresh", &document, json!({})).await
+    }
+
+    /// `query { <name> { <fields> } }`
+    async fn fetch_list<T: DeserializeOwned>(&self, name: &str, fields: &str) -> ApiResult<Vec<T>> {
+        let document = format!("query {{ {} {{ {} }} }}", name, fields);
+        self.field(name, &document, json!({})).await
+    }
+
+    /// `query { <name>(id: $id) { <fields> } }`
+    async fn fetch_one<T: DeserializeOwned>(&self, name: &str, fields: &str, id: Uuid) -> ApiResult<T> {
+        let document = format!("query($id: ID!) {{ {}(id: $id) {{ {} }} }}", name, fields);
+        self.field(name, &document, json!({ "id": id })).await
+    }
+
+    /// `mutation { <name>(input: $input) { id } }`, returning the new id
+    async fn create(&self, name: &str, input_type: &str, input: Value) -> ApiResult<Uuid> {
+        #[derive(Deserialize)]
+        struct Created {
+            id: Uuid,
+        }
+        let document = format!("mutation($input: {}!) {{ {}(input: $input) {{ id }} }}", input_type, name);
+        let created: Created = self.field(name, &document, json!({ "input": input })).await?;
+        Ok(created.id)
+    }
+
+    /// `mutation { <name>(id: $id, input: $input) { <fields> } }`
+    async fn update<T: DeserializeOwned>(
+        &self,
+        name: &str,
+        input_type: &str,
+        fields: &str,
+        id: Uuid,
+        input: Value,
+    ) -> ApiResult<T> {
+        let document =
+            format!("mutation($id: ID!, $input: {}!) {{ {}(id: $id, input: $input) {{ {} }} }}", input_type, name, fields);
+        self.field(name, &document, json!({ "id": id, "input": input })).await
+    }
+
+    /// `mutation { <name>(id: $id) }`, returning the deleted id
+    async fn delete(&self, name: &str, id: Uuid) -> ApiResult<Uuid> {
+        let document = format!("mutation($id: ID!) {{ {}(id: $id) }}", name);
+        self.field(name, &document, json!({ "id": id })).await
+    }
+
+    async fn health(&self) -> ApiResult<bool> {
+        Ok(self.execute("health", "query { __typename }", json!({})).await.is_ok())
+    }
+}
+
+/// Decode one field of `data`; `null` means the entity does not exist
+fn take<T: DeserializeOwned>(data: &mut Map<String, Value>, name: &str) -> ApiResult<T> {
+    let endpoint = format!("GraphQL {}", name);
+    match data.remove(name) {
+        None | Some(Value::Null) => Err(ApiError::Status { endpoint, status: 404, message: format!("{} not found", name) }),
+        Some(value) => serde_json::from_value(value).map_err(|e| ApiError::Decode { endpoint, message: e.to_string() }),
+    }
+}
+
+/// Serialize a request body for an `input` variable
+fn input(body: &impl serde::Serialize) -> Value {
+    serde_json::to_value(body).unwrap_or(Value::Null)
+}
+
+impl DataSource for GraphQlClient {
+    fn health_check(&self) -> SourceFuture<'_, bool> {
+        Box::pin(self.health())
+    }
+
+    fn fetch_all_projects(&self) -> SourceFuture<'_, Vec<ProjectDto>> {
+        Box::pin(self.fetch_list("projects", PROJECT_FIELDS))
+    }
+
+    fn fetch_all_clients(&self) -> SourceFuture<'_, Vec<ClientDto>> {
+        Box::pin(self.fetch_list("clients", CLIENT_FIELDS))
+    }
+
+    fn fetch_all_users(&self) -> SourceFuture<'_, Vec<UserDto>> {
+        Box::pin(self.fetch_list("users", USER_FIELDS))
+    }
+
+    /// One query for all three lists
+    fn refresh(&self) -> Pin<Box<dyn Future<Output = Refresh> + Send + '_>> {
+        Box::pin(async move {
+            match self.fetch_everything().await {
+                Ok(mut data) => Refresh {
+                    projects: take(&mut data, "projects"),
+                    clients: take(&mut data, "clients"),
+                    users: take(&mut data, "users"),
+                },
+                Err(e) => Refresh { projects: Err(e.clone()), clients: Err(e.clone()), users: Err(e) },
+            }
+        })
+    }
+
+    fn fetch_project(&self, id: Uuid) -> SourceFuture<'_, ProjectDto> {
+        Box::pin(self.fetch_one("project", PROJECT_FIELDS, id))
+    }
+
+    fn fetch_client(&self, id: Uuid) -> SourceFuture<'_, ClientDto> {
+        Box::pin(self.fetch_one("client", CLIENT_FIELDS, id))
+    }
+
+    fn fetch_user(&self, id: Uuid) -> SourceFuture<'_, UserDto> {
+        Box::pin(self.fetch_one("user", USER_FIELDS, id))
+    }
+
+    fn create_project<'a>(&'a self, project: &'a CreateProjectDto) -> SourceFuture<'a, Uuid> {
+        Box::pin(self.create("createProject", "CreateProjectInput", input(project)))
+    }
+
+    fn update_project<'a>(&'a self, id: Uuid, project: &'a UpdateProjectDto) -> SourceFuture<'a, ProjectDto> {
+        Box::pin(self.update("updateProject", "UpdateProjectInput", PROJECT_FIELDS, id, input(project)))
+    }
+
+    fn delete_project(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        Box::pin(self.delete("deleteProject", id))
+    }
+
+    fn create_client<'a>(&'a self, client: &'a CreateClientDto) -> SourceFuture<'a, Uuid> {
+        Box::pin(self.create("createClient", "CreateClientInput", input(client)))
+    }
+
+    fn update_client<'a>(&'a self, id: Uuid, client: &'a UpdateClientDto) -> SourceFuture<'a, ClientDto> {
+        Box::pin(self.update("updateClient", "UpdateClientInput", CLIENT_FIELDS, id, input(client)))
+    }
+
+    fn delete_client(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        Box::pin(self.delete("deleteClient", id))
+    }
+
+    fn create_user<'a>(&'a self, user: &'a CreateUserDto) -> SourceFuture<'a, Uuid> {
+        Box::pin(self.create("createUser", "CreateUserInput", input(user)))
+    }
+
+    fn update_user<'a>(&'a self, id: Uuid, user: &'a UpdateUserDto) -> SourceFuture<'a, UserDto> {
+        Box::pin(self.update("updateUser", "UpdateUserInput", USER_FIELDS, id, input(user)))
+    }
+
+    fn delete_user(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        Box::pin(self.delete("deleteUser", id))
+    }
+
+    fn fetch_changes(&self) -> SourceFuture<'_, ChangesDto> {
+        match &self.rest {
+            Some(rest) => DataSource::fetch_changes(rest),
+            None => unsupported("GET /changes"),
+        }
+    }
+
+    fn fetch_project_documents(&self, project_id: Uuid) -> SourceFuture<'_, Vec<DocumentDto>> {
+        match &self.rest {
+            Some(rest) => DataSource::fetch_project_documents(rest, project_id),
+            None => unsupported("GET /projects/{id}/documents"),
+        }
+    }
+
+    fn download_document(&self, project_id: Uuid, document_id: Uuid) -> SourceFuture<'_, Download> {
+        match &self.rest {
+            Some(rest) => DataSource::download_document(rest, project_id, document_id),
+            None => unsupported("GET /projects/{id}/documents/{id}/content"),
+        }
+    }
+
+    fn fetch_audit<'a>(
+        &'a self,
+        page: i32,
+        page_size: i32,
+        search: Option<&'a str>,
+    ) -> SourceFuture<'a, PaginatedResult<AuditEventDto>> {
+        match &self.rest {
+            Some(rest) => DataSource::fetch_audit(rest, page, page_size, search),
+            None => unsupported("GET /audit"),
+        }
+    }
+
+    fn get_raw<'a>(&'a self, path: &'a str, query: &'a [(String, String)]) -> SourceFuture<'a, RawResponse> {
+        match &self.rest {
+            Some(rest) => DataSource::get_raw(rest, path, query),
+            None => unsupported(&format!("GET {}", path)),
+        }
+    }
+
+    fn rate_limit(&self) -> Option<RateLimit> {
+        self.rest.as_ref().and_then(ApiClient::rate_limit)
+    }
+}
@@ -0,0 +1,19 @@
+//! SWEeM core library.
+//!
+//! Everything of the SWEeM tooling that doesn't need a terminal: the API
+//! clients and the data sources built on them, the domain models and the
+//! statistics computed from them. The TUI and its headless commands build
+//! on this crate.
+
+pub mod aggregate;
+pub mod api;
+pub mod body;
+pub mod error;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+pub mod graphql;
+pub mod models;
+pub mod probe;
+pub mod source;
+pub mod stats;
+mod trace;
@@ -2,12 +2,12 @@
 //!
 //! These structs match the OpenAPI schema and use serde for JSON deserialization.
 //! DateOnly from C# is mapped to NaiveDate in Rust.
-//! Includes both read DTOs and write DTOs for CRUD operations.
+//! Includes both read DTOs and write DTOs for CRUD operations. Read DTOs keep
+//! fields they do not know in `extra`, so schema drift stays visible.
 
-#![allow(dead_code)]
-
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use uuid::Uuid;
 
 /// User role enumeration (Manager = 0, Admin = 1)
@@ -71,6 +71,9 @@ pub struct ClientDto {
     pub address: Option<String>,
     pub projects_total: i32,
     pub projects_completed: i32,
+    /// Fields the API sent that this schema does not know
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 impl ClientDto {
@@ -139,7 +142,7 @@ impl UpdateClientDto {
 // ============================================
 
 /// Project data transfer object (read)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectDto {
     pub id: Uuid,
@@ -149,6 +152,9 @@ pub struct ProjectDto {
     pub planned_end_date: NaiveDate,
     pub actual_end_date: Option<NaiveDate>,
     pub manager_id: Uuid,
+    /// Fields the API sent that this schema does not know
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 impl ProjectDto {
@@ -168,11 +174,28 @@ impl ProjectDto {
 
     /// Check if project is overdue (past planned end date but not completed)
     pub fn is_overdue(&self) -> bool {
-        if self.is_completed() {
-            return false;
-        }
-        let today = chrono::Local::now().date_naive();
-        today > self.planned_end_date
+        self.is_overdue_as_of(chrono::Local::now().date_naive())
+    }
+
+    /// Check if project had been completed on the given reference date
+    pub fn is_completed_as_of(&self, date: NaiveDate) -> bool {
+        self.actual_end_date.is_some_and(|end| end <= date)
+    }
+
+    /// Check if project was overdue on the given reference date
+    pub fn is_overdue_as_of(&self, date: NaiveDate) -> bool {
+        !self.is_completed_as_of(date) && date > self.planned_end_date
+    }
+
+    /// Actual end date if already reached on the given reference date
+    pub fn actual_end_as_of(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.actual_end_date.filter(|end| *end <= date)
+    }
+
+    /// Days from the reference date to the planned end (negative when overdue);
+    /// None once completed
+    pub fn days_left_as_of(&self, date: NaiveDate) -> Option<i64> {
+        (!self.is_completed_as_of(date)).then(|| (self.planned_end_date - date).num_days())
     }
 }
 
@@ -279,6 +302,9 @@ pub struct UserDto {
     pub name: Option<String>,
     pub login: Option<String>,
     pub role: Role,
+    /// Fields the API sent that this schema does not know
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 impl UserDto {
@@ -385,6 +411,80 @@ impl<T> PaginatedResult<T> {
     pub fn items(&self) -> &[T] {
         self.items.as_deref().unwrap_or(&[])
     }
+
+    /// Page `page` (from 1) of a complete list, as a paginating server would answer
+    pub fn slice(all: Vec<T>, page: i32, page_size: i32) -> Self {
+        let page = page.max(1);
+        let page_size = page_size.max(1);
+        let total_count = all.len() as i32;
+        let total_pages = (total_count + page_size - 1) / page_size;
+        let items = all.into_iter().skip(((page - 1) * page_size) as usize).take(page_size as usize).collect();
+        Self {
+            items: Some(items),
+            page,
+            page_size,
+            total_count,
+            total_pages,
+            has_previous: page > 1,
+            has_next: page < total_pages,
+        }
+    }
+}
+
+// ============================================
+// Change detection
+// ============================================
+
+/// Current data version from `GET /changes`
+///
+/// The version changes whenever any client, project or user is modified, so
+/// pollers only need to reload when it differs from the last one they saw.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangesDto {
+    pub version: i64,
+    pub last_modified: DateTime<Utc>,
+}
+
+/// A file attached to a project, from `GET /projects/{id}/documents`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentDto {
+    pub id: Uuid,
+    pub file_name: String,
+    /// Size in bytes
+    #[serde(default)]
+    pub size: Option<u64>,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub uploaded_at: Option<DateTime<Utc>>,
+}
+
+// ============================================
+// Audit log
+// ============================================
+
+/// One entry of the backend's activity feed, from `GET /audit`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEventDto {
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    /// Login of whoever made the change; `None` for system jobs
+    #[serde(default)]
+    pub actor: Option<String>,
+    /// "Created", "Updated", "Deleted", ...
+    pub action: String,
+    /// "Project", "Client" or "User"
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    /// Name at the time of the change (deleted entities keep theirs here)
+    #[serde(default)]
+    pub entity_name: Option<String>,
+    /// Changed fields in words
+    #[serde(default)]
+    pub summary: Option<String>,
 }
 
 // ============================================
@@ -402,9 +502,20 @@ pub struct ProblemDetails {
     pub instance: Option<String>,
 }
 
+impl std::fmt::Display for ProblemDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.title, &self.detail) {
+            (Some(title), Some(detail)) => write!(f, "{}: {}", title, detail),
+            (Some(message), None) | (None, Some(message)) => write!(f, "{}", message),
+            (None, None) => write!(f, "Unknown error"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures;
 
     #[test]
     fn test_role_serialization() {
@@ -414,18 +525,37 @@ mod tests {
         assert_eq!(i32::from(Role::Admin), 1);
     }
 
+    #[test]
+    fn test_unknown_fields_are_kept() {
+        let json = r#"{"id":"00000000-0000-0000-0000-000000000001","name":"Root","login":"root","role":1,"email":"root@example.com"}"#;
+        let user: UserDto = serde_json::from_str(json).unwrap();
+        assert_eq!(user.role, Role::Admin);
+        assert_eq!(user.extra.keys().collect::<Vec<_>>(), ["email"]);
+        assert_eq!(serde_json::to_string(&user).unwrap(), json, "serialized back in the received order");
+    }
+
     #[test]
     fn test_project_duration() {
+        let project = fixtures::project(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2024, 1, 31).unwrap());
+        assert_eq!(project.duration_days(), 30);
+    }
+
+    #[test]
+    fn test_project_status_as_of() {
         let project = ProjectDto {
-            id: Uuid::new_v4(),
-            client_id: Uuid::new_v4(),
-            name: Some("Test".to_string()),
-            start_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
-            planned_end_date: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
-            actual_end_date: None,
-            manager_id: Uuid::new_v4(),
+            actual_end_date: NaiveDate::from_ymd_opt(2024, 2, 10),
+            ..fixtures::project(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
         };
-        assert_eq!(project.duration_days(), 30);
+        let jan = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let feb = NaiveDate::from_ymd_opt(2024, 2, 5).unwrap();
+        let mar = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        assert!(!project.is_completed_as_of(jan) && !project.is_overdue_as_of(jan));
+        assert!(!project.is_completed_as_of(feb) && project.is_overdue_as_of(feb));
+        assert!(project.is_completed_as_of(mar) && !project.is_overdue_as_of(mar));
+        assert_eq!(project.days_left_as_of(jan), Some(16));
+        assert_eq!(project.days_left_as_of(feb), Some(-5));
+        assert_eq!(project.days_left_as_of(mar), None);
     }
 
     #[test]
@@ -451,4 +581,13 @@ mod tests {
         dto.password = Some("pass".to_string());
         assert!(dto.validate().is_ok());
     }
+
+    #[test]
+    fn test_slice_pages() {
+        let page = PaginatedResult::slice((1..=25).collect::<Vec<i32>>(), 3, 10);
+        assert_eq!(page.items(), [21, 22, 23, 24, 25]);
+        assert_eq!((page.total_count, page.total_pages, page.has_previous, page.has_next), (25, 3, true, false));
+        let page = PaginatedResult::slice(Vec::<i32>::new(), 1, 10);
+        assert!(page.items().is_empty() && !page.has_next);
+    }
 }
@@ -0,0 +1,286 @@
+//! Step-by-step connection diagnosis.
+//!
+//! "It doesn't work" mostly means a proxy, a VPN or TLS is in the way, which a
+//! failed request does not tell apart. [`diagnose`] goes through the layers of
+//! a request one at a time: resolving the host name, opening a TCP connection
+//! to it, the TLS handshake (for `https://`) and finally a plain GET of the
+//! first project with the API client's [`ConnectionOptions`]. The first
+//! failing stage ends the run; the stages after it are reported as skipped.
+//!
+//! With a proxy in the environment every stage takes the route the requests
+//! take: DNS and TCP reach the proxy, an `https://` API is tunnelled with
+//! `CONNECT` before the handshake, and the GET goes through the same proxy.
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use base64::Engine;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_native_tls::{native_tls, TlsConnector};
+
+use crate::api::{ConnectionOptions, Http2};
+
+/// Limit of each stage
+pub const STAGE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest proxy answer to `CONNECT` read before giving up
+const MAX_CONNECT_RESPONSE: usize = 8 * 1024;
+
+/// Layer of a request checked on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stage {
+    Dns,
+    Tcp,
+    Tls,
+    Http,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 4] = [Stage::Dns, Stage::Tcp, Stage::Tls, Stage::Http];
+}
+
+/// How a stage went
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    /// Passed, with what was found (addresses, peer, status)
+    Passed(String),
+    Failed(String),
+    /// Not run: an earlier stage failed, or it does not apply (TLS over
+    /// `http://`, or behind a proxy that is not spoken to over plain HTTP)
+    Skipped,
+}
+
+/// Result of one stage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageReport {
+    pub stage: Stage,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+}
+
+/// All stages for one base URL
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnosis {
+    pub url: String,
+    /// Proxy all stages go through, from the environment
+    pub proxy: Option<String>,
+    pub stages: Vec<StageReport>,
+}
+
+impl Diagnosis {
+    /// The stage that failed, if any
+    pub fn failed(&self) -> Option<Stage> {
+        self.stages.iter().find(|r| matches!(r.outcome, Outcome::Failed(_))).map(|r| r.stage)
+    }
+}
+
+/// Proxy the environment sets for a URL (`HTTPS_PROXY`, `HTTP_PROXY`,
+/// `ALL_PROXY`, also lower case), unless `NO_PROXY` lists its host
+pub fn proxy_for(url: &Url, env: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let lookup = |name: &str| {
+        [name.to_ascii_uppercase(), name.to_string()]
+            .into_iter()
+            .find_map(|name| env(&name).filter(|value| !value.is_empty()))
+    };
+    let host = url.host_str().unwrap_or_default().trim_matches(['[', ']']).to_ascii_lowercase();
+    let bypassed = lookup("no_proxy").is_some_and(|list| {
+        list.split(',').map(|entry| entry.trim().trim_start_matches('.').to_ascii_lowercase()).any(|entry| {
+            entry == "*" || (!entry.is_empty() && (host == entry || host.ends_with(&format!(".{}", entry))))
+        })
+    });
+    if bypassed {
+        return None;
+    }
+    let specific = if url.scheme() == "https" { "https_proxy" } else { "http_proxy" };
+    lookup(specific).or_else(|| lookup("all_proxy"))
+}
+
+/// A proxy setting as a URL; like reqwest, a bare `host:port` means HTTP
+fn proxy_url(proxy: &str) -> Result<Url, String> {
+    Url::parse(proxy)
+        .ok()
+        .filter(|url| url.has_host())
+        .or_else(|| Url::parse(&format!("http://{}", proxy)).ok())
+        .filter(|url| url.has_host())
+        .ok_or_else(|| format!("invalid proxy URL: {}", proxy))
+}
+
+/// Run a stage within [`STAGE_TIMEOUT`]
+async fn timed<T>(
+    stage: Stage,
+    reports: &mut Vec<StageReport>,
+    run: impl std::future::Future<Output = Result<(T, String), String>>,
+) -> Option<T> {
+    let started = Instant::now();
+    let result = match tokio::time::timeout(STAGE_TIMEOUT, run).await {
+        Ok(result) => result,
+        Err(_) => Err(format!("no answer within {}s", STAGE_TIMEOUT.as_secs())),
+    };
+    let (value, outcome) = match result {
+        Ok((value, detail)) => (Some(value), Outcome::Passed(detail)),
+        Err(error) => (None, Outcome::Failed(error)),
+    };
+    reports.push(StageReport { stage, outcome, elapsed: started.elapsed() });
+    value
+}
+
+/// Check DNS, TCP, TLS and HTTP for a base URL, stopping at the first failure;
+/// `env` gives the proxy variables (see [`proxy_for`])
+pub async fn diagnose(base_url: &str, options: &ConnectionOptions, env: impl Fn(&str) -> Option<String>) -> Diagnosis {
+    let mut stages = Vec::new();
+    let mut proxy = None;
+    match Url::parse(base_url) {
+        Ok(url) => {
+            proxy = proxy_for(&url, env);
+            match proxy.as_deref().map(proxy_url).transpose() {
+                Ok(via) => {
+                    run_stages(&url, via.as_ref(), options, &mut stages).await;
+                }
+                Err(error) => {
+                    stages.push(StageReport { stage: Stage::Dns, outcome: Outcome::Failed(error), elapsed: Duration::ZERO });
+                }
+            }
+        }
+        Err(e) => {
+            let outcome = Outcome::Failed(format!("invalid URL: {}", e));
+            stages.push(StageReport { stage: Stage::Dns, outcome, elapsed: Duration::ZERO });
+        }
+    }
+    for stage in Stage::ALL.into_iter().skip(stages.len()) {
+        stages.push(StageReport { stage, outcome: Outcome::Skipped, elapsed: Duration::ZERO });
+    }
+    Diagnosis { url: base_url.to_string(), proxy, stages }
+}
+
+fn host_of(url: &Url) -> String {
+    url.host_str().unwrap_or_default().trim_matches(['[', ']']).to_string()
+}
+
+/// The stages in order, through `proxy` if set; `None` once one failed
+async fn run_stages(url: &Url, proxy: Option<&Url>, options: &ConnectionOptions, stages: &mut Vec<StageReport>) -> Option<()> {
+    let host = host_of(url);
+    let port = url.port_or_known_default().unwrap_or(80);
+    let https = url.scheme() == "https";
+    // Only a plain HTTP proxy is spoken to here; SOCKS and TLS proxies are left to the GET
+    let http_proxy = proxy.is_some_and(|proxy| proxy.scheme() == "http");
+    let (dial_host, dial_port) = match proxy {
+        Some(proxy) => (host_of(proxy), proxy.port_or_known_default().unwrap_or(1080)),
+        None => (host.clone(), port),
+    };
+    let via = if proxy.is_some() { " (proxy)" } else { "" };
+
+    let addresses: Vec<SocketAddr> = timed(Stage::Dns, stages, async {
+        let addresses: Vec<SocketAddr> =
+            tokio::net::lookup_host((dial_host.as_str(), dial_port)).await.map_err(|e| e.to_string())?.collect();
+        if addresses.is_empty() {
+            return Err("no addresses".to_string());
+        }
+        let shown: Vec<String> = addresses.iter().map(|a| a.ip().to_string()).collect();
+        Ok((addresses, format!("{}{}", shown.join(", "), via)))
+    })
+    .await?;
+
+    let stream = timed(Stage::Tcp, stages, async {
+        let mut last_error = String::new();
+        for address in &addresses {
+            match TcpStream::connect(address).await {
+                Ok(mut stream) => {
+                    let mut detail = format!("connected to {}{}", address, via);
+                    if https && http_proxy {
+                        let target = format!("{}:{}", url.host_str().unwrap_or_default(), port);
+                        open_tunnel(&mut stream, &target, proxy.and_then(proxy_authorization)).await?;
+                        detail.push_str(&format!(", tunnel to {}", target));
+                    }
+                    return Ok((stream, detail));
+                }
+                Err(e) => last_error = format!("{}: {}", address, e),
+            }
+        }
+        Err(last_error)
+    })
+    .await?;
+
+    if https && (proxy.is_none() || http_proxy) {
+        timed(Stage::Tls, stages, async {
+            let protocols: &[&str] = if options.http2 == Http2::Off { &["http/1.1"] } else { &["h2", "http/1.1"] };
+            let connector = native_tls::TlsConnector::builder().request_alpns(protocols).build().map_err(|e| error_chain(&e))?;
+            let tls = TlsConnector::from(connector).connect(&host, stream).await.map_err(|e| error_chain(&e))?;
+            let protocol = tls.get_ref().negotiated_alpn().ok().flatten().unwrap_or_else(|| b"http/1.1".to_vec());
+            Ok(((), format!("certificate for {} accepted, {}", host, String::from_utf8_lossy(&protocol))))
+        })
+        .await?;
+    } else {
+        stages.push(StageReport { stage: Stage::Tls, outcome: Outcome::Skipped, elapsed: Duration::ZERO });
+    }
+
+    timed(Stage::Http, stages, async {
+        let builder = options.builder(STAGE_TIMEOUT);
+        let builder = match proxy {
+            Some(proxy) => builder.proxy(reqwest::Proxy::all(proxy.as_str()).map_err(|e| error_chain(&e))?),
+            None => builder.no_proxy(),
+        };
+        let client = builder.build().map_err(|e| error_chain(&e))?;
+        let endpoint = format!("{}/projects?page=1&pageSize=1", url.as_str().trim_end_matches('/'));
+        let response = client.get(&endpoint).send().await.map_err(|e| error_chain(&e))?;
+        let status = response.status();
+        let detail = format!("GET /projects: {} ({:?})", status, response.version());
+        if !status.is_success() {
+            return Err(detail);
+        }
+        Ok(((), detail))
+    })
+    .await
+}
+
+/// `Proxy-Authorization` value for the credentials in a proxy URL
+fn proxy_authorization(proxy: &Url) -> Option<String> {
+    if proxy.username().is_empty() {
+        return None;
+    }
+    let decode = |part: &str| percent_encoding::percent_decode_str(part).decode_utf8_lossy().into_owned();
+    let credentials = format!("{}:{}", decode(proxy.username()), decode(proxy.password().unwrap_or_default()));
+    Some(format!("Basic {}", base64::engine::general_purpose::STANDARD.encode(credentials)))
+}
+
+/// Ask an HTTP proxy for a tunnel to `target` (`host:port`)
+async fn open_tunnel(stream: &mut TcpStream, target: &str, authorization: Option<String>) -> Result<(), String> {
+    let mut request = format!("CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n");
+    if let Some(authorization) = authorization {
+        request.push_str(&format!("Proxy-Authorization: {}\r\n", authorization));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await.map_err(|e| e.to_string())?;
+
+    // Byte by byte, so nothing after the header is taken from the tunnel
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_CONNECT_RESPONSE {
+            return Err("proxy answer to CONNECT too long".to_string());
+        }
+        match stream.read_u8().await {
+            Ok(byte) => head.push(byte),
+            Err(e) => return Err(format!("proxy closed the connection: {}", e)),
+        }
+    }
+    let head = String::from_utf8_lossy(&head);
+    let status_line = head.lines().next().unwrap_or_default();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        return Err(format!("proxy refused CONNECT {}: {}", target, status_line));
+    }
+    Ok(())
+}
+
+/// An error with its causes, which is where reqwest keeps the useful part
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
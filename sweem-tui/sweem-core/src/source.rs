@@ -0,0 +1,382 @@
+//! Where the data comes from.
+//!
+//! Tools load and change projects, clients and users through [`DataSource`],
+//! so the backend is a configuration choice: [`ApiClient`] speaks REST,
+//! [`GraphQlClient`](crate::graphql::GraphQlClient) GraphQL, and the TUI adds
+//! a demo generator and its local history. Methods return boxed futures to
+//! keep the trait usable as `dyn DataSource`, since the source is picked at
+//! runtime.
+//!
+//! Change polling, documents, the audit log and raw GETs are optional: the
+//! defaults answer 404, which callers already treat as "this server does not
+//! have it". Sources combine, e.g. [`Fallback`] reads from the network and
+//! falls back to a cache while the network is unreachable, and
+//! [`Aggregate`](crate::aggregate::Aggregate) merges several backends.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::{ApiClient, Download, RateLimit, RawResponse};
+use crate::error::{ApiError, ApiErrorKind, ApiResult};
+use crate::models::{
+    AuditEventDto, ChangesDto, ClientDto, CreateClientDto, CreateProjectDto, CreateUserDto, DocumentDto,
+    PaginatedResult, ProjectDto, UpdateClientDto, UpdateProjectDto, UpdateUserDto, UserDto,
+};
+
+/// Future returned by [`DataSource`] methods
+pub type SourceFuture<'a, T> = Pin<Box<dyn Future<Output = ApiResult<T>> + Send + 'a>>;
+
+/// Everything a refresh shows, each list loaded (or failed) on its own
+#[derive(Debug)]
+pub struct Refresh {
+    pub projects: ApiResult<Vec<ProjectDto>>,
+    pub clients: ApiResult<Vec<ClientDto>>,
+    pub users: ApiResult<Vec<UserDto>>,
+}
+
+/// Whether one backend of a combined source answered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackendStatus {
+    pub name: String,
+    pub connected: bool,
+}
+
+/// A backend holding projects, clients and users
+pub trait DataSource: Send + Sync {
+    /// Whether the backend answers at all
+    fn health_check(&self) -> SourceFuture<'_, bool>;
+
+    fn fetch_all_projects(&self) -> SourceFuture<'_, Vec<ProjectDto>>;
+    fn fetch_all_clients(&self) -> SourceFuture<'_, Vec<ClientDto>>;
+    fn fetch_all_users(&self) -> SourceFuture<'_, Vec<UserDto>>;
+
+    /// Projects, clients and users; the default loads the three lists concurrently
+    fn refresh(&self) -> Pin<Box<dyn Future<Output = Refresh> + Send + '_>> {
+        Box::pin(async move {
+            let (projects, clients, users) =
+                tokio::join!(self.fetch_all_projects(), self.fetch_all_clients(), self.fetch_all_users());
+            Refresh { projects, clients, users }
+        })
+    }
+
+    /// One page of the clients; the default cuts it from the whole list
+    fn fetch_clients_page(&self, page: i32, page_size: i32) -> SourceFuture<'_, PaginatedResult<ClientDto>> {
+        Box::pin(async move { Ok(PaginatedResult::slice(self.fetch_all_clients().await?, page, page_size)) })
+    }
+
+    /// One page of the users; the default cuts it from the whole list
+    fn fetch_users_page(&self, page: i32, page_size: i32) -> SourceFuture<'_, PaginatedResult<UserDto>> {
+        Box::pin(async move { Ok(PaginatedResult::slice(self.fetch_all_users().await?, page, page_size)) })
+    }
+
+    fn fetch_project(&self, id: Uuid) -> SourceFuture<'_, ProjectDto>;
+    fn fetch_client(&self, id: Uuid) -> SourceFuture<'_, ClientDto>;
+    fn fetch_user(&self, id: Uuid) -> SourceFuture<'_, UserDto>;
+
+    fn create_project<'a>(&'a self, project: &'a CreateProjectDto) -> SourceFuture<'a, Uuid>;
+    fn update_project<'a>(&'a self, id: Uuid, project: &'a UpdateProjectDto) -> SourceFuture<'a, ProjectDto>;
+    fn delete_project(&self, id: Uuid) -> SourceFuture<'_, Uuid>;
+
+    fn create_client<'a>(&'a self, client: &'a CreateClientDto) -> SourceFuture<'a, Uuid>;
+    fn update_client<'a>(&'a self, id: Uuid, client: &'a UpdateClientDto) -> SourceFuture<'a, ClientDto>;
+    fn delete_client(&self, id: Uuid) -> SourceFuture<'_, Uuid>;
+
+    fn create_user<'a>(&'a self, user: &'a CreateUserDto) -> SourceFuture<'a, Uuid>;
+    fn update_user<'a>(&'a self, id: Uuid, user: &'a UpdateUserDto) -> SourceFuture<'a, UserDto>;
+    fn delete_user(&self, id: Uuid) -> SourceFuture<'_, Uuid>;
+
+    /// Current data version, for cheap change polling
+    fn fetch_changes(&self) -> SourceFuture<'_, ChangesDto> {
+        unsupported("GET /changes")
+    }
+
+    fn fetch_project_documents(&self, _project_id: Uuid) -> SourceFuture<'_, Vec<DocumentDto>> {
+        unsupported("GET /projects/{id}/documents")
+    }
+
+    fn download_document(&self, _project_id: Uuid, _document_id: Uuid) -> SourceFuture<'_, Download> {
+        unsupported("GET /projects/{id}/documents/{id}/content")
+    }
+
+    fn fetch_audit<'a>(
+        &'a self,
+        _page: i32,
+        _page_size: i32,
+        _search: Option<&'a str>,
+    ) -> SourceFuture<'a, PaginatedResult<AuditEventDto>> {
+        unsupported("GET /audit")
+    }
+
+    /// GET any path, answered as-is (the TUI's API console)
+    fn get_raw<'a>(&'a self, path: &'a str, _query: &'a [(String, String)]) -> SourceFuture<'a, RawResponse> {
+        unsupported(&format!("GET {}", path))
+    }
+
+    /// Request budget reported by the backend, if it has one
+    fn rate_limit(&self) -> Option<RateLimit> {
+        None
+    }
+
+    /// Backends of a source combining several, as of their last request
+    /// (empty for a single backend)
+    fn backends(&self) -> Vec<BackendStatus> {
+        Vec::new()
+    }
+}
+
+/// Answer of an optional method the source does not have
+pub fn unsupported<'a, T: Send + 'a>(endpoint: &str) -> SourceFuture<'a, T> {
+    let error = ApiError::Status {
+        endpoint: endpoint.to_string(),
+        status: 404,
+        message: "Not supported by this data source".to_string(),
+    };
+    Box::pin(std::future::ready(Err(error)))
+}
+
+impl DataSource for ApiClient {
+    fn health_check(&self) -> SourceFuture<'_, bool> {
+        Box::pin(ApiClient::health_check(self))
+    }
+
+    fn fetch_all_projects(&self) -> SourceFuture<'_, Vec<ProjectDto>> {
+        Box::pin(ApiClient::fetch_all_projects(self))
+    }
+
+    fn fetch_all_clients(&self) -> SourceFuture<'_, Vec<ClientDto>> {
+        Box::pin(ApiClient::fetch_all_clients(self))
+    }
+
+    fn fetch_all_users(&self) -> SourceFuture<'_, Vec<UserDto>> {
+        Box::pin(ApiClient::fetch_all_users(self))
+    }
+
+    fn fetch_clients_page(&self, page: i32, page_size: i32) -> SourceFuture<'_, PaginatedResult<ClientDto>> {
+        Box::pin(ApiClient::fetch_clients(self, page, page_size))
+    }
+
+    fn fetch_users_page(&self, page: i32, page_size: i32) -> SourceFuture<'_, PaginatedResult<UserDto>> {
+        Box::pin(ApiClient::fetch_users(self, page, page_size))
+    }
+
+    fn fetch_project(&self, id: Uuid) -> SourceFuture<'_, ProjectDto> {
+        Box::pin(ApiClient::fetch_project(self, id))
+    }
+
+    fn fetch_client(&self, id: Uuid) -> SourceFuture<'_, ClientDto> {
+        Box::pin(ApiClient::fetch_client(self, id))
+    }
+
+    fn fetch_user(&self, id: Uuid) -> SourceFuture<'_, UserDto> {
+        Box::pin(ApiClient::fetch_user(self, id))
+    }
+
+    fn create_project<'a>(&'a self, project: &'a CreateProjectDto) -> SourceFuture<'a, Uuid> {
+        Box::pin(ApiClient::create_project(self, project))
+    }
+
+    fn update_project<'a>(&'a self, id: Uuid, project: &'a UpdateProjectDto) -> SourceFuture<'a, ProjectDto> {
+        Box::pin(ApiClient::update_project(self, id, project))
+    }
+
+    fn delete_project(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        Box::pin(ApiClient::delete_project(self, id))
+    }
+
+    fn create_client<'a>(&'a self, client: &'a CreateClientDto) -> SourceFuture<'a, Uuid> {
+        Box::pin(ApiClient::create_client(self, client))
+    }
+
+    fn update_client<'a>(&'a self, id: Uuid, client: &'a UpdateClientDto) -> SourceFuture<'a, ClientDto> {
+        Box::pin(ApiClient::update_client(self, id, client))
+    }
+
+    fn delete_client(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        Box::pin(ApiClient::delete_client(self, id))
+    }
+
+    fn create_user<'a>(&'a self, user: &'a CreateUserDto) -> SourceFuture<'a, Uuid> {
+        Box::pin(ApiClient::create_user(self, user))
+    }
+
+    fn update_user<'a>(&'a self, id: Uuid, user: &'a UpdateUserDto) -> SourceFuture<'a, UserDto> {
+        Box::pin(ApiClient::update_user(self, id, user))
+    }
+
+    fn delete_user(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        Box::pin(ApiClient::delete_user(self, id))
+    }
+
+    fn fetch_changes(&self) -> SourceFuture<'_, ChangesDto> {
+        Box::pin(ApiClient::fetch_changes(self))
+    }
+
+    fn fetch_project_documents(&self, project_id: Uuid) -> SourceFuture<'_, Vec<DocumentDto>> {
+        Box::pin(ApiClient::fetch_project_documents(self, project_id))
+    }
+
+    fn download_document(&self, project_id: Uuid, document_id: Uuid) -> SourceFuture<'_, Download> {
+        Box::pin(ApiClient::download_document(self, project_id, document_id))
+    }
+
+    fn fetch_audit<'a>(
+        &'a self,
+        page: i32,
+        page_size: i32,
+        search: Option<&'a str>,
+    ) -> SourceFuture<'a, PaginatedResult<AuditEventDto>> {
+        Box::pin(ApiClient::fetch_audit(self, page, page_size, search))
+    }
+
+    fn get_raw<'a>(&'a self, path: &'a str, query: &'a [(String, String)]) -> SourceFuture<'a, RawResponse> {
+        Box::pin(ApiClient::get_raw(self, path, query))
+    }
+
+    fn rate_limit(&self) -> Option<RateLimit> {
+        ApiClient::rate_limit(self)
+    }
+}
+
+/// Network first: reads come from `primary`, or from `cache` while `primary`
+/// cannot be reached; changes and the optional methods only go to `primary`
+#[derive(Clone)]
+pub struct Fallback {
+    primary: Arc<dyn DataSource>,
+    cache: Arc<dyn DataSource>,
+}
+
+impl Fallback {
+    pub fn new(primary: Arc<dyn DataSource>, cache: Arc<dyn DataSource>) -> Self {
+        Self { primary, cache }
+    }
+
+    /// `read` from the primary source, or from the cache when the network is down
+    fn read<'a, T: Send + 'a>(
+        &'a self,
+        read: impl Fn(&'a dyn DataSource) -> SourceFuture<'a, T> + Send + 'a,
+    ) -> SourceFuture<'a, T> {
+        Box::pin(async move {
+            match read(self.primary.as_ref()).await {
+                Err(e) if matches!(e.kind(), ApiErrorKind::Connection | ApiErrorKind::Timeout) => {
+                    tracing::warn!(error = %e, "Network unreachable; reading from the cache");
+                    read(self.cache.as_ref()).await.map_err(|_| e)
+                }
+                result => result,
+            }
+        })
+    }
+}
+
+impl DataSource for Fallback {
+    /// Reads can be answered when either source can
+    fn health_check(&self) -> SourceFuture<'_, bool> {
+        Box::pin(async move {
+            Ok(self.primary.health_check().await.unwrap_or(false) || self.cache.health_check().await.unwrap_or(false))
+        })
+    }
+
+    fn fetch_all_projects(&self) -> SourceFuture<'_, Vec<ProjectDto>> {
+        self.read(|source| source.fetch_all_projects())
+    }
+
+    fn fetch_all_clients(&self) -> SourceFuture<'_, Vec<ClientDto>> {
+        self.read(|source| source.fetch_all_clients())
+    }
+
+    fn fetch_all_users(&self) -> SourceFuture<'_, Vec<UserDto>> {
+        self.read(|source| source.fetch_all_users())
+    }
+
+    fn fetch_clients_page(&self, page: i32, page_size: i32) -> SourceFuture<'_, PaginatedResult<ClientDto>> {
+        self.read(move |source| source.fetch_clients_page(page, page_size))
+    }
+
+    fn fetch_users_page(&self, page: i32, page_size: i32) -> SourceFuture<'_, PaginatedResult<UserDto>> {
+        self.read(move |source| source.fetch_users_page(page, page_size))
+    }
+
+    fn fetch_project(&self, id: Uuid) -> SourceFuture<'_, ProjectDto> {
+        self.read(move |source| source.fetch_project(id))
+    }
+
+    fn fetch_client(&self, id: Uuid) -> SourceFuture<'_, ClientDto> {
+        self.read(move |source| source.fetch_client(id))
+    }
+
+    fn fetch_user(&self, id: Uuid) -> SourceFuture<'_, UserDto> {
+        self.read(move |source| source.fetch_user(id))
+    }
+
+    fn create_project<'a>(&'a self, project: &'a CreateProjectDto) -> SourceFuture<'a, Uuid> {
+        self.primary.create_project(project)
+    }
+
+    fn update_project<'a>(&'a self, id: Uuid, project: &'a UpdateProjectDto) -> SourceFuture<'a, ProjectDto> {
+        self.primary.update_project(id, project)
+    }
+
+    fn delete_project(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        self.primary.delete_project(id)
+    }
+
+    fn create_client<'a>(&'a self, client: &'a CreateClientDto) -> SourceFuture<'a, Uuid> {
+        self.primary.create_client(client)
+    }
+
+    fn update_client<'a>(&'a self, id: Uuid, client: &'a UpdateClientDto) -> SourceFuture<'a, ClientDto> {
+        self.primary.update_client(id, client)
+    }
+
+    fn delete_client(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        self.primary.delete_client(id)
+    }
+
+    fn create_user<'a>(&'a self, user: &'a CreateUserDto) -> SourceFuture<'a, Uuid> {
+        self.primary.create_user(user)
+    }
+
+    fn update_user<'a>(&'a self, id: Uuid, user: &'a UpdateUserDto) -> SourceFuture<'a, UserDto> {
+        self.primary.update_user(id, user)
+    }
+
+    fn delete_user(&self, id: Uuid) -> SourceFuture<'_, Uuid> {
+        self.primary.delete_user(id)
+    }
+
+    fn fetch_changes(&self) -> SourceFuture<'_, ChangesDto> {
+        self.primary.fetch_changes()
+    }
+
+    fn fetch_project_documents(&self, project_id: Uuid) -> SourceFuture<'_, Vec<DocumentDto>> {
+        self.primary.fetch_project_documents(project_id)
+    }
+
+    fn download_document(&self, project_id: Uuid, document_id: Uuid) -> SourceFuture<'_, Download> {
+        self.primary.download_document(project_id, document_id)
+    }
+
+    fn fetch_audit<'a>(
+        &'a self,
+        page: i32,
+        page_size: i32,
+        search: Option<&'a str>,
+    ) -> SourceFuture<'a, PaginatedResult<AuditEventDto>> {
+        self.primary.fetch_audit(page, page_size, search)
+    }
+
+    fn get_raw<'a>(&'a self, path: &'a str, query: &'a [(String, String)]) -> SourceFuture<'a, RawResponse> {
+        self.primary.get_raw(path, query)
+    }
+
+    fn rate_limit(&self) -> Option<RateLimit> {
+        self.primary.rate_limit()
+    }
+
+    fn backends(&self) -> Vec<BackendStatus> {
+        self.primary.backends()
+    }
+}
@@ -0,0 +1,316 @@
+//! Project statistics shared by the views and reports.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use uuid::Uuid;
+
+use crate::models::ProjectDto;
+
+/// Completed and total project counts for a client, evaluated as of a date
+pub fn client_project_counts(projects: &[ProjectDto], client_id: Uuid, as_of: NaiveDate) -> (i32, i32) {
+    let client_projects: Vec<_> = projects.iter().filter(|p| p.client_id == client_id).collect();
+    let total = client_projects.len() as i32;
+    let completed = client_projects.iter().filter(|p| p.is_completed_as_of(as_of)).count() as i32;
+    (completed, total)
+}
+
+/// Number of projects overdue as of a date
+pub fn overdue_count(projects: &[ProjectDto], as_of: NaiveDate) -> usize {
+    projects.iter().filter(|p| p.is_overdue_as_of(as_of)).count()
+}
+
+/// Open projects ordered by planned end date, nearest (or most overdue) first
+pub fn upcoming_deadlines(projects: &[ProjectDto], as_of: NaiveDate) -> Vec<&ProjectDto> {
+    let mut open: Vec<_> = projects.iter().filter(|p| !p.is_completed_as_of(as_of)).collect();
+    open.sort_by_key(|p| p.planned_end_date);
+    open
+}
+
+/// Number of projects running in each of `weeks` weeks starting at `from`
+///
+/// Open projects count until their planned end, or until `from` when they
+/// are already overdue, since overdue work is still on someone's plate.
+pub fn weekly_load(projects: &[ProjectDto], from: NaiveDate, weeks: usize) -> Vec<u64> {
+    (0..weeks as i64)
+        .map(|week| projects.iter().filter(|p| runs_in_week(p, from, week)).count() as u64)
+        .collect()
+}
+
+/// [`weekly_load`] per manager, for the capacity report; managers without a
+/// project in the period are left out
+pub fn manager_weekly_load(projects: &[ProjectDto], from: NaiveDate, weeks: usize) -> BTreeMap<Uuid, Vec<u64>> {
+    let mut load: BTreeMap<Uuid, Vec<u64>> = BTreeMap::new();
+    for project in projects {
+        for week in 0..weeks as i64 {
+            if runs_in_week(project, from, week) {
+                load.entry(project.manager_id).or_insert_with(|| vec![0; weeks])[week as usize] += 1;
+            }
+        }
+    }
+    load
+}
+
+/// Whether a project is on someone's plate in the `week`th week from `from`
+fn runs_in_week(project: &ProjectDto, from: NaiveDate, week: i64) -> bool {
+    let week_start = from + Duration::weeks(week);
+    let week_end = week_start + Duration::days(6);
+    let end = project.actual_end_date.unwrap_or(project.planned_end_date.max(from));
+    project.start_date <= week_end && end >= week_start
+}
+
+/// First day of the month containing `date`
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// First day of the quarter containing `date`
+pub fn quarter_start(date: NaiveDate) -> NaiveDate {
+    let month = (date.month0() / 3) * 3 + 1;
+    NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap_or(date)
+}
+
+/// `date` moved by a signed number of months
+pub fn add_months(date: NaiveDate, months: i32) -> NaiveDate {
+    let shifted = if months >= 0 {
+        date.checked_add_months(Months::new(months as u32))
+    } else {
+        date.checked_sub_months(Months::new(months.unsigned_abs()))
+    };
+    shifted.unwrap_or(date)
+}
+
+/// Projects started and completed in one calendar month
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonthlyDelivery {
+    /// First day of the month
+    pub month: NaiveDate,
+    pub started: u64,
+    pub completed: u64,
+}
+
+/// Started and completed counts for `months` months from `first_month`
+///
+/// Completions are only counted once they happened as of `as_of`.
+pub fn monthly_delivery(projects: &[ProjectDto], first_month: NaiveDate, months: u32, as_of: NaiveDate) -> Vec<MonthlyDelivery> {
+    let first_month = month_start(first_month);
+    (0..months as i32)
+        .map(|offset| {
+            let month = add_months(first_month, offset);
+            let in_month = |date: NaiveDate| month_start(date) == month;
+            MonthlyDelivery {
+                month,
+                started: projects.iter().filter(|p| in_month(p.start_date)).count() as u64,
+                completed: projects.iter().filter(|p| p.actual_end_as_of(as_of).is_some_and(in_month)).count() as u64,
+            }
+        })
+        .collect()
+}
+
+/// How late the projects completed in one quarter were
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuarterlyOverrun {
+    /// First day of the quarter
+    pub quarter: NaiveDate,
+    pub completed: usize,
+    /// Mean days past the planned end (early completions count as 0), if any completed
+    pub average_days: Option<f64>,
+}
+
+/// Days past the planned end of the projects completed in `[from, to)` (early ones count as 0)
+fn overruns(projects: &[ProjectDto], from: NaiveDate, to: NaiveDate, as_of: NaiveDate) -> Vec<i64> {
+    projects
+        .iter()
+        .filter_map(|p| {
+            let end = p.actual_end_as_of(as_of).filter(|end| (from..to).contains(end))?;
+            Some((end - p.planned_end_date).num_days().max(0))
+        })
+        .collect()
+}
+
+/// Mean overrun in days of the projects completed in `[from, to)`, if any
+pub fn average_overrun(projects: &[ProjectDto], from: NaiveDate, to: NaiveDate, as_of: NaiveDate) -> Option<f64> {
+    let overruns = overruns(projects, from, to, as_of);
+    (!overruns.is_empty()).then(|| overruns.iter().sum::<i64>() as f64 / overruns.len() as f64)
+}
+
+/// Overrun per quarter for the quarters overlapping `[from, to)`
+pub fn quarterly_overrun(projects: &[ProjectDto], from: NaiveDate, to: NaiveDate, as_of: NaiveDate) -> Vec<QuarterlyOverrun> {
+    let mut quarters = Vec::new();
+    let mut quarter = quarter_start(from);
+    while quarter < to {
+        let next = add_months(quarter, 3);
+        quarters.push(QuarterlyOverrun {
+            quarter,
+            completed: overruns(projects, quarter, next, as_of).len(),
+            average_days: average_overrun(projects, quarter, next, as_of),
+        });
+        quarter = next;
+    }
+    quarters
+}
+
+/// Projects completed in a period, and how many of them by their planned end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OnTimeDelivery {
+    pub completed: usize,
+    pub on_time: usize,
+}
+
+impl OnTimeDelivery {
+    /// Share delivered on time, in percent (`None` without completions)
+    pub fn percent(&self) -> Option<f64> {
+        (self.completed > 0).then(|| self.on_time as f64 * 100.0 / self.completed as f64)
+    }
+}
+
+/// On-time delivery of projects completed in `[from, to)`
+pub fn on_time_delivery(projects: &[ProjectDto], from: NaiveDate, to: NaiveDate, as_of: NaiveDate) -> OnTimeDelivery {
+    projects
+        .iter()
+        .filter_map(|p| p.actual_end_as_of(as_of).filter(|end| (from..to).contains(end)).map(|end| (p, end)))
+        .fold(OnTimeDelivery::default(), |mut acc, (p, end)| {
+            acc.completed += 1;
+            if end <= p.planned_end_date {
+                acc.on_time += 1;
+            }
+            acc
+        })
+}
+
+/// Planned length of a project in days, counting both ends
+pub fn planned_days(project: &ProjectDto) -> i64 {
+    project.duration_days().max(0) + 1
+}
+
+/// One day of a burndown/burnup chart, in project-days
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BurnPoint {
+    pub date: NaiveDate,
+    /// Work that should still be open if every project ended as planned
+    pub planned_remaining: i64,
+    /// Work of projects not completed yet (`None` after `as_of`)
+    pub remaining: Option<i64>,
+    /// Work of completed projects (`None` after `as_of`)
+    pub completed: Option<i64>,
+}
+
+/// Daily burndown of a set of projects, from the first start to the last planned
+/// or actual end (or `as_of`, while projects are still open)
+///
+/// A project's planned days burn down all at once when it ends, as planned
+/// for the commitment line and as actually completed for the other two.
+pub fn burndown(projects: &[ProjectDto], as_of: NaiveDate) -> Vec<BurnPoint> {
+    let Some(first) = projects.iter().map(|p| p.start_date).min() else {
+        return Vec::new();
+    };
+    let mut last = projects
+        .iter()
+        .map(|p| p.actual_end_date.unwrap_or(p.planned_end_date).max(p.planned_end_date))
+        .max()
+        .unwrap_or(first);
+    if projects.iter().any(|p| !p.is_completed_as_of(as_of)) {
+        last = last.max(as_of);
+    }
+    let total: i64 = projects.iter().map(planned_days).sum();
+    first
+        .iter_days()
+        .take_while(|date| *date <= last)
+        .map(|date| {
+            let planned_done: i64 = projects.iter().filter(|p| p.planned_end_date < date).map(planned_days).sum();
+            let completed = (date <= as_of).then(|| {
+                projects.iter().filter(|p| p.actual_end_date.is_some_and(|end| end < date)).map(planned_days).sum::<i64>()
+            });
+            BurnPoint {
+                date,
+                planned_remaining: total - planned_done,
+                remaining: completed.map(|done| total - done),
+                completed,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn test_client_counts_and_overdue() {
+        let client_id = Uuid::new_v4();
+        let date = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let project = |client_id, end, actual| ProjectDto { client_id, actual_end_date: actual, ..fixtures::project(date(1), date(end)) };
+        let projects = vec![
+            project(client_id, 10, Some(date(9))),
+            project(client_id, 10, None),
+            project(Uuid::new_v4(), 20, None),
+        ];
+
+        assert_eq!(client_project_counts(&projects, client_id, date(15)), (1, 2));
+        assert_eq!(overdue_count(&projects, date(15)), 1);
+        assert_eq!(overdue_count(&projects, date(25)), 2);
+    }
+
+    #[test]
+    fn test_deadlines_and_weekly_load() {
+        let date = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let project = |start, end, actual: Option<u32>| ProjectDto { actual_end_date: actual.map(date), ..fixtures::project(date(start), date(end)) };
+        // Overdue since the 5th, ends in week 2, starts in week 3, done before `from`
+        let projects = vec![project(1, 5, None), project(1, 14, None), project(15, 31, None), project(1, 3, Some(4))];
+
+        let deadlines: Vec<_> = upcoming_deadlines(&projects, date(8)).iter().map(|p| p.planned_end_date).collect();
+        assert_eq!(deadlines, [date(5), date(14), date(31)]);
+        assert_eq!(weekly_load(&projects, date(8), 4), [2, 1, 1, 1]);
+
+        let mut projects = projects;
+        projects[2].manager_id = Uuid::from_u128(7);
+        let by_manager = manager_weekly_load(&projects, date(8), 4);
+        assert_eq!(by_manager[&Uuid::nil()], [2, 0, 0, 0]);
+        assert_eq!(by_manager[&Uuid::from_u128(7)], [0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_delivery_trends() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        let project = |start, planned, actual| ProjectDto { actual_end_date: actual, ..fixtures::project(start, planned) };
+        let projects = vec![
+            // Done 4 days late in February
+            project(date(1, 5), date(2, 1), Some(date(2, 5))),
+            // Done early in March
+            project(date(1, 20), date(3, 20), Some(date(3, 10))),
+            // Done 10 days late in April, after `as_of` for the first check
+            project(date(2, 1), date(4, 1), Some(date(4, 11))),
+            project(date(3, 1), date(6, 1), None),
+        ];
+
+        let months = monthly_delivery(&projects, date(1, 15), 4, date(3, 31));
+        let counts: Vec<_> = months.iter().map(|m| (m.month.month(), m.started, m.completed)).collect();
+        assert_eq!(counts, [(1, 2, 0), (2, 1, 1), (3, 1, 1), (4, 0, 0)]);
+
+        let quarters = quarterly_overrun(&projects, date(2, 1), date(5, 1), date(6, 30));
+        assert_eq!(quarters.len(), 2);
+        assert_eq!((quarters[0].quarter, quarters[0].completed, quarters[0].average_days), (date(1, 1), 2, Some(2.0)));
+        assert_eq!((quarters[1].quarter, quarters[1].average_days), (date(4, 1), Some(10.0)));
+
+        let on_time = on_time_delivery(&projects, date(1, 1), date(7, 1), date(6, 30));
+        assert_eq!((on_time.completed, on_time.on_time), (3, 1));
+        assert_eq!(OnTimeDelivery::default().percent(), None);
+        assert_eq!(add_months(date(3, 31), -1), date(2, 29));
+    }
+
+    #[test]
+    fn test_burndown() {
+        let date = |d| NaiveDate::from_ymd_opt(2024, 5, d).unwrap();
+        let project = |start, planned, actual: Option<u32>| ProjectDto { actual_end_date: actual.map(date), ..fixtures::project(date(start), date(planned)) };
+        // 5 and 6 project-days; the first finishes two days late
+        let projects = vec![project(1, 5, Some(7)), project(3, 8, None)];
+        let points = burndown(&projects, date(8));
+
+        assert_eq!(points.len(), 8);
+        assert_eq!(points[0], BurnPoint { date: date(1), planned_remaining: 11, remaining: Some(11), completed: Some(0) });
+        assert_eq!(points[5].planned_remaining, 6, "first project was planned to end on the 5th");
+        assert_eq!((points[5].remaining, points[7].remaining, points[7].completed), (Some(11), Some(6), Some(5)));
+        assert!(burndown(&[], date(8)).is_empty());
+    }
+}
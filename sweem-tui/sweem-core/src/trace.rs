@@ -0,0 +1,65 @@
+//! Client spans of HTTP requests.
+//!
+//! Every request of the REST and GraphQL clients runs in a `debug` span
+//! named after its endpoint (`GET /projects`) with the OpenTelemetry HTTP
+//! client attributes: method, URL and the response status. Applications that
+//! export spans (the TUI's `otel` feature) see one client span per request
+//! below the `ApiClient` method that sent it. With the `otel` feature of this
+//! crate, the span's trace context also goes out as a W3C `traceparent`
+//! header, so the backend's own traces join the same trace.
+
+use reqwest::{Client, RequestBuilder, Response};
+use tracing::field::Empty;
+use tracing::Instrument;
+
+/// Send a request inside its client span
+pub(crate) async fn send(client: &Client, endpoint: &str, request: RequestBuilder) -> reqwest::Result<Response> {
+    #[allow(unused_mut)]
+    let mut request = request.build()?;
+    let span = tracing::debug_span!(
+        "http_request",
+        otel.name = endpoint,
+        otel.kind = "client",
+        otel.status_code = Empty,
+        http.request.method = %request.method(),
+        url.full = %request.url(),
+        http.response.status_code = Empty,
+    );
+    #[cfg(feature = "otel")]
+    propagate(&span, request.headers_mut());
+
+    let result = client.execute(request).instrument(span.clone()).await;
+    match &result {
+        Ok(response) => {
+            span.record("http.response.status_code", response.status().as_u16());
+            if response.status().is_server_error() {
+                span.record("otel.status_code", "ERROR");
+            }
+        }
+        Err(_) => {
+            span.record("otel.status_code", "ERROR");
+        }
+    }
+    result
+}
+
+/// Add the `traceparent` (and `tracestate`) headers of the span's context
+#[cfg(feature = "otel")]
+fn propagate(span: &tracing::Span, headers: &mut reqwest::header::HeaderMap) {
+    use opentelemetry::propagation::Injector;
+    use reqwest::header::{HeaderName, HeaderValue};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    struct Headers<'a>(&'a mut reqwest::header::HeaderMap);
+
+    impl Injector for Headers<'_> {
+        fn set(&mut self, key: &str, value: String) {
+            if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(key.as_bytes()), HeaderValue::from_str(&value)) {
+                self.0.insert(name, value);
+            }
+        }
+    }
+
+    let context = span.context();
+    opentelemetry::global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut Headers(headers)));
+}
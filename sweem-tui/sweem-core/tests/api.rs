@@ -0,0 +1,573 @@
+//! ApiClient against a mock server: the backend contract as `cargo test` sees it.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use serde_json::{json, Value};
+use sweem_core::aggregate::{Aggregate, SOURCE_FIELD};
+use sweem_core::api::{ApiClient, ConnectionOptions, Http2, RateLimit, DEFAULT_TIMEOUT};
+use sweem_core::error::{ApiError, ApiErrorKind};
+use sweem_core::graphql::GraphQlClient;
+use sweem_core::probe::{self, Outcome, Stage};
+use sweem_core::source::{DataSource, Fallback};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use uuid::Uuid;
+use wiremock::matchers::{body_string_contains, headers, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client_json(n: u128) -> Value {
+    json!({
+        "id": Uuid::from_u128(n),
+        "name": format!("Client {}", n),
+        "address": null,
+        "projectsTotal": 0,
+        "projectsCompleted": 0
+    })
+}
+
+fn page(items: Vec<Value>, page: i32, total_pages: i32) -> Value {
+    json!({
+        "items": items,
+        "page": page,
+        "pageSize": 100,
+        "totalCount": 3,
+        "totalPages": total_pages,
+        "hasPrevious": page > 1,
+        "hasNext": page < total_pages
+    })
+}
+
+#[tokio::test]
+async fn test_fetch_all_follows_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/clients"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![client_json(1), client_json(2)], 1, 2)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/clients"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![client_json(3)], 2, 2)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(server.uri()).unwrap();
+    let clients = client.fetch_all_clients().await.unwrap();
+    let names: Vec<_> = clients.iter().map(|c| c.display_name()).collect();
+    assert_eq!(names, ["Client 1", "Client 2", "Client 3"]);
+}
+
+#[tokio::test]
+async fn test_pages_load_one_at_a_time() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/clients"))
+        .and(query_param("page", "2"))
+        .and(query_param("pageSize", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![client_json(3)], 2, 2)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Only the asked-for page goes over the wire
+    let source: Arc<dyn DataSource> = Arc::new(ApiClient::new(server.uri()).unwrap());
+    let clients = source.fetch_clients_page(2, 2).await.unwrap();
+    assert_eq!(clients.items().len(), 1);
+    assert_eq!((clients.total_count, clients.has_next), (3, false));
+}
+
+#[tokio::test]
+async fn test_problem_details_and_status_errors() {
+    let server = MockServer::start().await;
+    let id = Uuid::from_u128(7);
+    Mock::given(method("DELETE"))
+        .and(path(format!("/projects/{}", id)))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "type": "https://tools.ietf.org/html/rfc9110#section-15.5.5",
+            "title": "Not Found",
+            "status": 404,
+            "detail": "Project was not found"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("upstream exploded"))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(server.uri()).unwrap();
+
+    let error = client.delete_project(id).await.unwrap_err();
+    assert_eq!(error.kind(), ApiErrorKind::Rejected);
+    assert_eq!(error.endpoint(), "DELETE /projects/{id}");
+    assert_eq!(error.message(), "Not Found: Project was not found");
+    assert!(!error.is_retryable());
+
+    let error = client.fetch_users(1, 10).await.unwrap_err();
+    assert_eq!(error.kind(), ApiErrorKind::Server);
+    assert_eq!(error.to_string(), "API error 500 on GET /users: upstream exploded");
+    assert!(error.is_retryable());
+}
+
+#[tokio::test]
+async fn test_malformed_json_and_timeout() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{\"items\": [oops"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/clients"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::with_timeout(server.uri(), Duration::from_millis(200)).unwrap();
+
+    let error = client.fetch_projects(1, 10).await.unwrap_err();
+    assert!(matches!(error, ApiError::Decode { .. }), "{}", error);
+
+    let error = client.fetch_clients(1, 10).await.unwrap_err();
+    assert_eq!(error.kind(), ApiErrorKind::Timeout);
+    assert!(error.is_retryable());
+
+    // The health check reports failures instead of erroring
+    assert!(!client.health_check().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_compressed_pages_are_streamed() {
+    let server = MockServer::start().await;
+    // Random ids keep the page large enough to be parsed while it streams in
+    let clients: Vec<Value> = (1..=6000)
+        .map(|n| {
+            let mut client = client_json(n);
+            client["id"] = json!(Uuid::new_v4());
+            client
+        })
+        .collect();
+    let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+    gzip.write_all(page(clients, 1, 2).to_string().as_bytes()).unwrap();
+    let gzip = gzip.finish().unwrap();
+    assert!(gzip.len() as u64 > sweem_core::body::STREAM_THRESHOLD);
+    Mock::given(method("GET"))
+        .and(path("/clients"))
+        .and(query_param("page", "1"))
+        .and(headers("accept-encoding", vec!["gzip", "deflate"]))
+        .respond_with(ResponseTemplate::new(200).insert_header("content-encoding", "gzip").set_body_bytes(gzip))
+        .mount(&server)
+        .await;
+    let mut deflate = ZlibEncoder::new(Vec::new(), Compression::default());
+    deflate.write_all(page(vec![client_json(6001)], 2, 2).to_string().as_bytes()).unwrap();
+    Mock::given(method("GET"))
+        .and(path("/clients"))
+        .and(query_param("page", "2"))
+        .respond_with(
+            ResponseTemplate::new(200).insert_header("content-encoding", "deflate").set_body_bytes(deflate.finish().unwrap()),
+        )
+        .mount(&server)
+        .await;
+    let mut problem = GzEncoder::new(Vec::new(), Compression::default());
+    problem.write_all(br#"{"title": "Forbidden", "detail": "Admins only"}"#).unwrap();
+    Mock::given(method("GET"))
+        .and(path("/users"))
+        .respond_with(ResponseTemplate::new(403).insert_header("content-encoding", "gzip").set_body_bytes(problem.finish().unwrap()))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(server.uri()).unwrap();
+    let clients = client.fetch_all_clients().await.unwrap();
+    assert_eq!(clients.len(), 6001);
+    assert_eq!(clients[5999].display_name(), "Client 6000");
+    let error = client.fetch_users(1, 10).await.unwrap_err();
+    assert_eq!(error.message(), "Forbidden: Admins only", "error bodies are decompressed too");
+}
+
+#[tokio::test]
+async fn test_http2_and_http1_connection_options() {
+    let server = MockServer::start().await;
+    for n in 1..=3 {
+        Mock::given(method("GET"))
+            .and(path("/clients"))
+            .and(query_param("page", n.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![client_json(n as u128)], n, 3)))
+            .mount(&server)
+            .await;
+    }
+
+    let options = ConnectionOptions { http2: Http2::Always, ..Default::default() };
+    let client = ApiClient::with_options(server.uri(), DEFAULT_TIMEOUT, &options).unwrap();
+    // With prior knowledge nothing falls back to HTTP/1.1, so this only works over h2c
+    assert_eq!(client.fetch_all_clients().await.unwrap().len(), 3);
+
+    let options = ConnectionOptions { http2: Http2::Off, keep_alive: None, ..Default::default() };
+    let client = ApiClient::with_options(server.uri(), DEFAULT_TIMEOUT, &options).unwrap();
+    assert_eq!(client.fetch_all_clients().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_get_raw_keeps_status_and_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .and(query_param("pageSize", "5"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{\"items\": []}"))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(server.uri()).unwrap();
+    let response = client.get_raw("/projects", &[("pageSize".to_string(), "5".to_string())]).await.unwrap();
+    assert_eq!((response.status, response.body.as_str()), (200, "{\"items\": []}"));
+
+    // Error statuses are answers too
+    let response = client.get_raw("/nope", &[]).await.unwrap();
+    assert_eq!(response.status, 404);
+}
+
+#[tokio::test]
+async fn test_rate_limit_headers_are_kept() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/changes"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "version": 3, "lastModified": "2025-03-14T12:00:00Z" }))
+                .insert_header("X-RateLimit-Limit", "100")
+                .insert_header("X-RateLimit-Remaining", "7")
+                .insert_header("X-RateLimit-Reset", "30"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .respond_with(ResponseTemplate::new(429).insert_header("X-RateLimit-Remaining", "0"))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(server.uri()).unwrap();
+    assert_eq!(client.rate_limit(), None);
+    client.fetch_changes().await.unwrap();
+    let limit = RateLimit { remaining: 7, limit: Some(100), reset: Some(Duration::from_secs(30)) };
+    assert_eq!(client.clone().rate_limit(), Some(limit), "clones share the budget");
+
+    // Rejected requests update it too
+    assert_eq!(client.fetch_projects(1, 1).await.unwrap_err().status(), Some(429));
+    assert_eq!(client.rate_limit().map(|l| l.remaining), Some(0));
+}
+
+#[tokio::test]
+async fn test_documents_are_listed_and_streamed() {
+    let server = MockServer::start().await;
+    let project = Uuid::from_u128(1);
+    let document = Uuid::from_u128(2);
+    Mock::given(method("GET"))
+        .and(path(format!("/projects/{}/documents", project)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "id": document, "fileName": "spec.pdf", "size": 70000, "contentType": "application/pdf" }
+        ])))
+        .mount(&server)
+        .await;
+    let body = vec![7u8; 70000];
+    Mock::given(method("GET"))
+        .and(path(format!("/projects/{}/documents/{}/content", project, document)))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(server.uri()).unwrap();
+    let documents = client.fetch_project_documents(project).await.unwrap();
+    assert_eq!(documents[0].file_name, "spec.pdf");
+    assert_eq!(documents[0].uploaded_at, None);
+
+    let mut download = client.download_document(project, document).await.unwrap();
+    // Files are saved as sent, so they must not be compressed on the way
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.last().unwrap().headers.get("accept-encoding").unwrap(), "identity");
+    assert_eq!(download.total, Some(70000));
+    let mut received = Vec::new();
+    while let Some(chunk) = download.chunk().await.unwrap() {
+        received.extend(chunk);
+    }
+    assert_eq!(received, body);
+
+    let missing = client.fetch_project_documents(Uuid::from_u128(9)).await.unwrap_err();
+    assert_eq!(missing.status(), Some(404), "servers without documents");
+}
+
+#[tokio::test]
+async fn test_audit_feed_is_paged_and_filtered() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/audit"))
+        .and(query_param("page", "2"))
+        .and(query_param("search", "alice"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{
+                "id": Uuid::from_u128(1),
+                "timestamp": "2025-03-01T12:00:00Z",
+                "actor": "alice",
+                "action": "Updated",
+                "entityType": "Project",
+                "entityId": Uuid::from_u128(2),
+                "summary": "deadline 2025-04-01 → 2025-05-01"
+            }],
+            "page": 2, "pageSize": 50, "totalCount": 51, "totalPages": 2, "hasPrevious": true, "hasNext": false
+        })))
+        .mount(&server)
+        .await;
+
+    let client = ApiClient::new(server.uri()).unwrap();
+    let page = client.fetch_audit(2, 50, Some("alice")).await.unwrap();
+    assert_eq!((page.page, page.total_pages), (2, 2));
+    let event = &page.items()[0];
+    assert_eq!((event.actor.as_deref(), event.entity_id), (Some("alice"), Uuid::from_u128(2)));
+    assert_eq!(event.entity_name, None);
+
+    let unfiltered = client.fetch_audit(1, 50, None).await.unwrap_err();
+    assert_eq!(unfiltered.status(), Some(404), "servers without an audit log");
+}
+
+#[tokio::test]
+async fn test_graphql_refresh_is_one_request() {
+    let server = MockServer::start().await;
+    let project = Uuid::from_u128(10);
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("query Refresh"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {
+            "projects": [{
+                "id": project, "clientId": Uuid::from_u128(1), "name": "Website", "startDate": "2025-01-01",
+                "plannedEndDate": "2025-03-01", "actualEndDate": null, "managerId": Uuid::from_u128(2)
+            }],
+            "clients": [client_json(1)],
+            "users": [{ "id": Uuid::from_u128(2), "name": "Alice", "login": "alice", "role": 0 }]
+        }})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("deleteProject"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": null,
+            "errors": [{ "message": "Project has documents" }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("project(id: $id)"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": { "project": null } })))
+        .mount(&server)
+        .await;
+
+    let source: Box<dyn DataSource> = Box::new(GraphQlClient::new(format!("{}/graphql", server.uri())).unwrap());
+    let refresh = source.refresh().await;
+    assert_eq!(refresh.projects.unwrap()[0].id, project);
+    assert_eq!(refresh.clients.unwrap()[0].display_name(), "Client 1");
+    assert_eq!(refresh.users.unwrap()[0].login.as_deref(), Some("alice"));
+
+    let rejected = source.delete_project(project).await.unwrap_err();
+    assert_eq!((rejected.kind(), rejected.message()), (ApiErrorKind::Rejected, "Project has documents".to_string()));
+    let missing = source.fetch_project(Uuid::from_u128(99)).await.unwrap_err();
+    assert_eq!(missing.status(), Some(404));
+}
+
+#[tokio::test]
+async fn test_fallback_reads_the_cache_while_offline() {
+    let cache = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/clients"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![client_json(1)], 1, 1)))
+        .mount(&cache)
+        .await;
+    // Nothing listens on a port that was just released
+    let offline = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let primary = ApiClient::new(format!("http://{}", offline)).unwrap();
+    let source = Fallback::new(Arc::new(primary), Arc::new(ApiClient::new(cache.uri()).unwrap()));
+
+    let clients = source.fetch_all_clients().await.unwrap();
+    assert_eq!(clients[0].display_name(), "Client 1");
+    let write = source.delete_client(Uuid::from_u128(1)).await.unwrap_err();
+    assert_eq!(write.kind(), ApiErrorKind::Connection, "changes never go to the cache");
+
+    let graphql = GraphQlClient::new(format!("{}/graphql", cache.uri())).unwrap();
+    let audit = graphql.fetch_audit(1, 50, None).await.unwrap_err();
+    assert_eq!(audit.status(), Some(404), "no REST client to ask");
+}
+
+#[tokio::test]
+async fn test_aggregate_merges_backends_and_routes_changes() {
+    let (eu, us) = (MockServer::start().await, MockServer::start().await);
+    for (server, n) in [(&eu, 1), (&us, 2)] {
+        Mock::given(method("GET"))
+            .and(path("/clients"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![client_json(n)], 1, 1)))
+            .mount(server)
+            .await;
+    }
+    Mock::given(method("DELETE"))
+        .and(path(format!("/clients/{}", Uuid::from_u128(2))))
+        .respond_with(ResponseTemplate::new(200).set_body_json(Uuid::from_u128(2)))
+        .expect(1)
+        .mount(&us)
+        .await;
+    let offline = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let backend = |url: String| -> Arc<dyn DataSource> { Arc::new(ApiClient::new(url).unwrap()) };
+    let source = Aggregate::new(vec![
+        ("eu".to_string(), backend(eu.uri())),
+        ("us".to_string(), backend(us.uri())),
+        ("ap".to_string(), backend(format!("http://{}", offline))),
+    ]);
+
+    let clients = source.fetch_all_clients().await.unwrap();
+    let tagged: Vec<_> = clients.iter().map(|c| (c.display_name(), c.extra[SOURCE_FIELD].as_str().unwrap())).collect();
+    assert_eq!(tagged, [("Client 1", "eu"), ("Client 2", "us")], "the unreachable backend is left out");
+    let connected: Vec<_> = source.backends().into_iter().map(|b| (b.name, b.connected)).collect();
+    assert_eq!(connected, [("eu".to_string(), true), ("us".to_string(), true), ("ap".to_string(), false)]);
+
+    // Client 2 was loaded from "us", so that is where it is deleted
+    assert_eq!(source.delete_client(Uuid::from_u128(2)).await.unwrap(), Uuid::from_u128(2));
+}
+
+/// Answer `GET /changes` with `version` from now on
+async fn changes_at(server: &MockServer, version: i64) {
+    server.reset().await;
+    Mock::given(method("GET"))
+        .and(path("/changes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "version": version, "lastModified": "2025-03-14T12:00:00Z" })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_aggregate_changes_compare_each_backend() {
+    let (eu, us) = (MockServer::start().await, MockServer::start().await);
+    changes_at(&eu, 3).await;
+    changes_at(&us, 5).await;
+    let offline = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let backend = |url: String| -> Arc<dyn DataSource> { Arc::new(ApiClient::new(url).unwrap()) };
+    let source = Aggregate::new(vec![
+        ("eu".to_string(), backend(eu.uri())),
+        ("ap".to_string(), backend(format!("http://{}", offline))),
+        ("us".to_string(), backend(us.uri())),
+    ]);
+
+    let first = source.fetch_changes().await.expect("the unreachable backend is skipped").version;
+    assert_eq!(source.fetch_changes().await.unwrap().version, first, "nothing changed");
+
+    // Both move, but their sum stays the same
+    changes_at(&eu, 4).await;
+    changes_at(&us, 4).await;
+    let moved = source.fetch_changes().await.unwrap().version;
+    assert_ne!(moved, first);
+    assert_eq!(source.fetch_changes().await.unwrap().version, moved);
+
+    // A 404 everywhere still reads as "not supported"
+    let empty = MockServer::start().await;
+    let source = Aggregate::new(vec![("eu".to_string(), backend(empty.uri()))]);
+    assert_eq!(source.fetch_changes().await.unwrap_err().status(), Some(404));
+}
+
+#[tokio::test]
+async fn test_diagnosis_reports_the_failing_stage() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![], 1, 1)))
+        .mount(&server)
+        .await;
+    let options = ConnectionOptions::default();
+    let no_env = |_: &str| None;
+    let diagnosis = probe::diagnose(&server.uri(), &options, no_env).await;
+    let outcomes: Vec<_> = diagnosis.stages.iter().map(|r| (r.stage, matches!(r.outcome, Outcome::Passed(_)))).collect();
+    assert_eq!(outcomes, [(Stage::Dns, true), (Stage::Tcp, true), (Stage::Tls, false), (Stage::Http, true)]);
+    assert_eq!(diagnosis.stages[2].outcome, Outcome::Skipped, "no TLS over http://");
+    assert_eq!(diagnosis.failed(), None);
+
+    // Nothing listens on a port that was just given back
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let diagnosis = probe::diagnose(&format!("http://127.0.0.1:{}", port), &options, no_env).await;
+    assert_eq!(diagnosis.failed(), Some(Stage::Tcp));
+    assert_eq!(diagnosis.stages[3].outcome, Outcome::Skipped);
+
+    assert_eq!(probe::diagnose("not a url", &options, no_env).await.failed(), Some(Stage::Dns));
+    let url = "https://sweem.example.com".parse().unwrap();
+    let env = |name: &str| (name == "https_proxy").then(|| "http://proxy:3128".to_string());
+    assert_eq!(probe::proxy_for(&url, env).as_deref(), Some("http://proxy:3128"));
+    assert_eq!(probe::proxy_for(&"http://sweem.example.com".parse().unwrap(), env), None);
+    let env = |name: &str| match name {
+        "https_proxy" => Some("http://proxy:3128".to_string()),
+        "NO_PROXY" => Some("localhost, .example.com".to_string()),
+        _ => None,
+    };
+    assert_eq!(probe::proxy_for(&url, env), None, "NO_PROXY covers subdomains");
+}
+
+/// HTTP proxy on localhost: plain requests are passed on to `upstream`, and
+/// `CONNECT` is refused. Counts the connections it gets.
+async fn refusing_proxy(upstream: std::net::SocketAddr) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = connections.clone();
+    tokio::spawn(async move {
+        while let Ok((mut client, _)) = listener.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut head = Vec::new();
+                while !head.ends_with(b"\r\n\r\n") {
+                    match client.read_u8().await {
+                        Ok(byte) => head.push(byte),
+                        Err(_) => return,
+                    }
+                }
+                if head.starts_with(b"CONNECT ") {
+                    client.write_all(b"HTTP/1.1 403 Forbidden\r\n\r\n").await.ok();
+                    return;
+                }
+                let mut server = TcpStream::connect(upstream).await.unwrap();
+                server.write_all(&head).await.unwrap();
+                tokio::io::copy_bidirectional(&mut client, &mut server).await.ok();
+            });
+        }
+    });
+    (address, connections)
+}
+
+#[tokio::test]
+async fn test_diagnosis_goes_through_the_proxy() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![], 1, 1)))
+        .mount(&server)
+        .await;
+    let (proxy, connections) = refusing_proxy(*server.address()).await;
+    let options = ConnectionOptions::default();
+
+    // The API host does not resolve, only the proxy does
+    let env = |name: &str| (name == "http_proxy").then(|| proxy.clone());
+    let diagnosis = probe::diagnose("http://sweem.invalid", &options, env).await;
+    assert_eq!(diagnosis.proxy.as_deref(), Some(proxy.as_str()));
+    assert_eq!(diagnosis.failed(), None, "{:?}", diagnosis.stages);
+    assert!(matches!(&diagnosis.stages[1].outcome, Outcome::Passed(detail) if detail.ends_with("(proxy)")));
+    assert_eq!(connections.load(Ordering::SeqCst), 2, "TCP and HTTP both reach the proxy");
+
+    let env = |name: &str| (name == "HTTPS_PROXY").then(|| proxy.clone());
+    let diagnosis = probe::diagnose("https://sweem.invalid", &options, env).await;
+    assert_eq!(diagnosis.failed(), Some(Stage::Tcp));
+    let Outcome::Failed(error) = &diagnosis.stages[1].outcome else { unreachable!() };
+    assert_eq!(error, "proxy refused CONNECT sweem.invalid:443: HTTP/1.1 403 Forbidden");
+}